@@ -0,0 +1,369 @@
+use sqlx::types::Json;
+use time::{Duration, OffsetDateTime, Time};
+
+use crate::{prelude::*, utils::config::BillingConfig};
+
+/// Runs a background task that records the usage of every workspace at the end
+/// of every hour. The usage is recorded for the hour that just ended, and is
+/// used to calculate the billing statements of the workspaces. Any hours that
+/// were missed while the API was not running are recorded when it starts. If
+/// multiple instances of the API are running, each hour is only recorded once.
+/// This function runs for the lifetime of the application.
+#[instrument(skip(state))]
+pub async fn run(state: &AppState) {
+	loop {
+		_ = record_missed_usage(state).await.inspect_err(|err| {
+			error!("Error recording usage: {err}");
+		});
+
+		let now = OffsetDateTime::now_utc();
+		let next_hour = start_of_hour(now) + Duration::hours(1);
+
+		tokio::time::sleep((next_hour - now).unsigned_abs()).await;
+	}
+}
+
+/// The start of the hour that the given time is in
+fn start_of_hour(time: OffsetDateTime) -> OffsetDateTime {
+	time.replace_time(Time::MIDNIGHT) + Duration::hours(time.hour().into())
+}
+
+/// Records the usage of every hour that has ended since the last hour that was
+/// recorded. If no hour has been recorded yet, only the hour that just ended is
+/// recorded.
+#[instrument(skip(state))]
+async fn record_missed_usage(state: &AppState) -> Result<(), sqlx::Error> {
+	let current_hour = start_of_hour(OffsetDateTime::now_utc());
+
+	let last_recorded_hour = query!(
+		r#"
+		SELECT
+			MAX(hour) AS "hour"
+		FROM
+			workspace_usage_recorded_hour;
+		"#
+	)
+	.fetch_one(&state.database)
+	.await?
+	.hour;
+
+	let mut hour = last_recorded_hour.map_or(current_hour - Duration::hours(1), |hour| {
+		hour + Duration::hours(1)
+	});
+	if hour < current_hour - Duration::hours(1) {
+		warn!("Usage was not recorded since `{hour}`. Recording the missed hours");
+	}
+
+	while hour < current_hour {
+		record_usage(state, hour).await?;
+		hour += Duration::hours(1);
+	}
+
+	Ok(())
+}
+
+/// Records the usage of all the resources of every workspace for the hour
+/// starting at `hour`. Deployments are recorded with the most replicas that
+/// their runners reported as running within the hour, and the bandwidth of
+/// static sites is summed up over the hour. Volumes and container registry
+/// repositories are recorded if they existed at the end of the hour, but with
+/// their size as it is when this is called, since the history of their size is
+/// not kept. That is only different if the hour is being recorded late.
+///
+/// Once the last hour of a month is recorded, the billing plan of every
+/// workspace that has any usage in the month is saved, so that the statement of
+/// the month does not change after the month has ended.
+#[instrument(skip(state))]
+async fn record_usage(state: &AppState, hour: OffsetDateTime) -> Result<(), sqlx::Error> {
+	let hour_end = hour + Duration::hours(1);
+	let mut transaction = state.database.begin().await?;
+
+	let newly_recorded = query!(
+		r#"
+		INSERT INTO
+			workspace_usage_recorded_hour(
+				hour
+			)
+		VALUES
+			($1)
+		ON CONFLICT DO NOTHING;
+		"#,
+		hour
+	)
+	.execute(&mut *transaction)
+	.await?
+	.rows_affected();
+	if newly_recorded == 0 {
+		debug!("Usage for the hour `{hour}` is already recorded");
+		return Ok(());
+	}
+
+	let deployments = query!(
+		r#"
+		INSERT INTO
+			workspace_usage(
+				workspace_id,
+				resource_id,
+				usage_type,
+				hour,
+				machine_type,
+				quantity
+			)
+		SELECT
+			deployment.workspace_id,
+			deployment.id,
+			'deployment'::USAGE_TYPE,
+			$1,
+			deployment.machine_type,
+			MAX(deployment_replica_report.replicas)
+		FROM
+			deployment_replica_report
+		INNER JOIN
+			deployment
+		ON
+			deployment.id = deployment_replica_report.deployment_id
+		WHERE
+			deployment_replica_report.reported >= $1 AND
+			deployment_replica_report.reported < $2
+		GROUP BY
+			deployment.workspace_id,
+			deployment.id,
+			deployment.machine_type
+		HAVING
+			MAX(deployment_replica_report.replicas) > 0
+		ON CONFLICT DO NOTHING;
+		"#,
+		hour,
+		hour_end
+	)
+	.execute(&mut *transaction)
+	.await?
+	.rows_affected();
+
+	let volumes = query!(
+		r#"
+		INSERT INTO
+			workspace_usage(
+				workspace_id,
+				resource_id,
+				usage_type,
+				hour,
+				machine_type,
+				quantity
+			)
+		SELECT
+			resource.owner_id,
+			deployment_volume.id,
+			'volume'::USAGE_TYPE,
+			$1,
+			NULL,
+			deployment_volume.volume_size
+		FROM
+			deployment_volume
+		INNER JOIN
+			resource
+		ON
+			resource.id = deployment_volume.id
+		WHERE
+			resource.created < $2 AND
+			(
+				deployment_volume.deleted IS NULL OR
+				deployment_volume.deleted >= $2
+			)
+		ON CONFLICT DO NOTHING;
+		"#,
+		hour,
+		hour_end
+	)
+	.execute(&mut *transaction)
+	.await?
+	.rows_affected();
+
+	let repositories = query!(
+		r#"
+		INSERT INTO
+			workspace_usage(
+				workspace_id,
+				resource_id,
+				usage_type,
+				hour,
+				machine_type,
+				quantity
+			)
+		SELECT
+			container_registry_repository.workspace_id,
+			container_registry_repository.id,
+			'container_registry_storage'::USAGE_TYPE,
+			$1,
+			NULL,
+			SUM(container_registry_repository_blob.size)
+		FROM
+			container_registry_repository
+		INNER JOIN
+			(
+				SELECT DISTINCT
+					container_registry_repository_manifest.repository_id,
+					container_registry_manifest_blob.blob_digest
+				FROM
+					container_registry_repository_manifest
+				INNER JOIN
+					container_registry_manifest_blob
+				ON
+					container_registry_manifest_blob.manifest_digest = container_registry_repository_manifest.manifest_digest
+			) AS repository_blob
+		ON
+			repository_blob.repository_id = container_registry_repository.id
+		INNER JOIN
+			container_registry_repository_blob
+		ON
+			container_registry_repository_blob.blob_digest = repository_blob.blob_digest
+		WHERE
+			container_registry_repository.deleted IS NULL OR
+			container_registry_repository.deleted >= $2
+		GROUP BY
+			container_registry_repository.workspace_id,
+			container_registry_repository.id
+		ON CONFLICT DO NOTHING;
+		"#,
+		hour,
+		hour_end
+	)
+	.execute(&mut *transaction)
+	.await?
+	.rows_affected();
+
+	let static_sites = query!(
+		r#"
+		INSERT INTO
+			workspace_usage(
+				workspace_id,
+				resource_id,
+				usage_type,
+				hour,
+				machine_type,
+				quantity
+			)
+		SELECT
+			static_site.workspace_id,
+			static_site.id,
+			'static_site_bandwidth'::USAGE_TYPE,
+			$1,
+			NULL,
+			SUM(static_site_bandwidth.bytes)
+		FROM
+			static_site_bandwidth
+		INNER JOIN
+			static_site
+		ON
+			static_site.id = static_site_bandwidth.static_site_id
+		WHERE
+			static_site_bandwidth.recorded >= $1 AND
+			static_site_bandwidth.recorded < $2
+		GROUP BY
+			static_site.workspace_id,
+			static_site.id
+		ON CONFLICT DO NOTHING;
+		"#,
+		hour,
+		hour_end
+	)
+	.execute(&mut *transaction)
+	.await?
+	.rows_affected();
+
+	// The hour after the last hour of a month is the start of the next month
+	if hour_end.day() == 1 && hour_end.hour() == 0 {
+		let period_start =
+			hour.replace_time(Time::MIDNIGHT) - Duration::days(i64::from(hour.day()) - 1);
+		let workspaces = close_billing_period(
+			&mut transaction,
+			&state.config.billing,
+			period_start,
+			hour_end,
+		)
+		.await?;
+		info!("Saved the billing plans of {workspaces} workspaces for `{period_start}`");
+	}
+
+	transaction.commit().await?;
+
+	info!(
+		"Recorded usage of {deployments} deployments, {volumes} volumes, {repositories} \
+		repositories and {static_sites} static sites for the hour `{hour}`"
+	);
+
+	Ok(())
+}
+
+/// Saves the billing plan of every workspace that has any usage in the month
+/// from `period_start` to `period_end`. The statement of the month is priced
+/// with the saved plan from then on, so that changing the plan of a workspace
+/// (or the prices of a plan) does not change the statements of past months.
+/// Returns the number of workspaces that the plan was saved for.
+async fn close_billing_period(
+	connection: &mut DatabaseConnection,
+	billing: &BillingConfig,
+	period_start: OffsetDateTime,
+	period_end: OffsetDateTime,
+) -> Result<u64, sqlx::Error> {
+	let workspaces = query!(
+		r#"
+		SELECT
+			workspace.id,
+			workspace.billing_plan_id
+		FROM
+			workspace
+		WHERE
+			EXISTS(
+				SELECT
+					1
+				FROM
+					workspace_usage
+				WHERE
+					workspace_usage.workspace_id = workspace.id AND
+					workspace_usage.hour >= $1 AND
+					workspace_usage.hour < $2
+			);
+		"#,
+		period_start,
+		period_end
+	)
+	.fetch_all(&mut *connection)
+	.await?;
+
+	let mut closed = 0;
+	for workspace in workspaces {
+		let Some((plan_id, plan)) = billing.get_plan(workspace.billing_plan_id) else {
+			error!(
+				"Billing plan of workspace `{}` not found. Statement for `{period_start}` will \
+				be priced with the plan it is on when the statement is requested",
+				workspace.id
+			);
+			continue;
+		};
+
+		closed += query!(
+			r#"
+			INSERT INTO
+				workspace_billing_period(
+					workspace_id,
+					period_start,
+					plan_id,
+					plan
+				)
+			VALUES
+				($1, $2, $3, $4)
+			ON CONFLICT DO NOTHING;
+			"#,
+			workspace.id,
+			period_start,
+			plan_id,
+			Json(&plan) as _,
+		)
+		.execute(&mut *connection)
+		.await?
+		.rows_affected();
+	}
+
+	Ok(closed)
+}
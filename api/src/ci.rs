@@ -0,0 +1,305 @@
+use axum::http::HeaderMap;
+use hmac::{Hmac, Mac};
+use models::ci::GitProvider;
+use reqwest::{
+	header::{ACCEPT, AUTHORIZATION, USER_AGENT},
+	StatusCode,
+	Url,
+};
+use sha2::Sha256;
+
+use crate::prelude::*;
+
+/// A git repository connected to a workspace, along with the credentials to
+/// access the API of its provider. This is deliberately not [`Debug`], so that
+/// the access token never ends up in the logs.
+#[derive(Clone)]
+pub struct ProviderRepository {
+	/// The provider that the repository is hosted on
+	pub provider: GitProvider,
+	/// The URL of the API of the provider
+	pub api_url: String,
+	/// The full name of the repository on the provider, such as `patr/api`
+	pub full_name: String,
+	/// The token used to access the API of the provider, if any
+	pub access_token: Option<String>,
+}
+
+/// The status of a commit, as reported to the provider of a repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitStatus {
+	/// The pipeline is queued on a runner
+	Pending,
+	/// The pipeline succeeded
+	Success,
+	/// A step of the pipeline failed
+	Failure,
+	/// The pipeline could not be run, such as when the CI file is invalid
+	Error,
+}
+
+/// Checks the signature of a webhook sent by the provider of a repository.
+/// GitHub and Gitea sign the body of the webhook with the secret using
+/// HMAC-SHA256, while GitLab sends the secret itself as a token.
+pub fn verify_webhook_signature(
+	provider: GitProvider,
+	secret: &str,
+	headers: &HeaderMap,
+	body: &[u8],
+) -> bool {
+	let header = |name: &str| {
+		headers
+			.get(name)
+			.and_then(|value| value.to_str().ok())
+			.unwrap_or_default()
+	};
+	let mac = |message: &[u8]| {
+		let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+			.expect("HMAC can take a key of any size");
+		mac.update(message);
+		mac
+	};
+	let verify_body = |signature: &str| {
+		hex::decode(signature).is_ok_and(|signature| mac(body).verify_slice(&signature).is_ok())
+	};
+
+	match provider {
+		GitProvider::Github => header("X-Hub-Signature-256")
+			.strip_prefix("sha256=")
+			.is_some_and(verify_body),
+		GitProvider::Gitea => verify_body(header("X-Gitea-Signature")),
+		// The token is compared by its MAC, so that the comparison takes the
+		// same time however much of the token is right
+		GitProvider::Gitlab => mac(header("X-Gitlab-Token").as_bytes())
+			.verify_slice(&mac(secret.as_bytes()).finalize().into_bytes())
+			.is_ok(),
+	}
+}
+
+impl ProviderRepository {
+	/// Adds the access token of the repository to the URL the repository is
+	/// cloned from, so that private repositories can be cloned. The URL is
+	/// returned as is if there is no access token.
+	pub fn authenticated_clone_url(&self, clone_url: &str) -> String {
+		let Some(access_token) = &self.access_token else {
+			return clone_url.to_string();
+		};
+		let Ok(mut url) = Url::parse(clone_url) else {
+			return clone_url.to_string();
+		};
+
+		// Every provider has its own username that goes with an access token.
+		// Gitea takes the access token as the username instead.
+		let credentials = match self.provider {
+			GitProvider::Github => url
+				.set_username("x-access-token")
+				.and_then(|()| url.set_password(Some(access_token))),
+			GitProvider::Gitlab => url
+				.set_username("oauth2")
+				.and_then(|()| url.set_password(Some(access_token))),
+			GitProvider::Gitea => url.set_username(access_token),
+		};
+
+		match credentials {
+			Ok(()) => url.into(),
+			Err(()) => clone_url.to_string(),
+		}
+	}
+
+	/// Reads the CI file of the repository at the given commit. Returns
+	/// [`None`] if the repository has no CI file at that commit.
+	pub async fn fetch_ci_file(
+		&self,
+		path: &str,
+		commit_sha: &str,
+	) -> Result<Option<String>, anyhow::Error> {
+		let mut url = match self.provider {
+			GitProvider::Github => {
+				self.repository_url(["contents"].into_iter().chain(path.split('/')))
+			}
+			GitProvider::Gitea => self.repository_url(["raw"].into_iter().chain(path.split('/'))),
+			// GitLab takes the whole path of the file as a single segment
+			GitProvider::Gitlab => self.repository_url(["repository", "files", path, "raw"]),
+		}?;
+		url.query_pairs_mut().append_pair("ref", commit_sha);
+
+		let response = self
+			.request(reqwest::Method::GET, url)?
+			.header(ACCEPT, "application/vnd.github.raw")
+			.send()
+			.await?;
+
+		if response.status() == StatusCode::NOT_FOUND {
+			return Ok(None);
+		}
+
+		Ok(Some(response.error_for_status()?.text().await?))
+	}
+
+	/// Reports the status of a pipeline run on a commit back to the provider of
+	/// the repository. Nothing is reported if the repository has no access
+	/// token, since every provider needs one to report a status.
+	pub async fn report_commit_status(
+		&self,
+		commit_sha: &str,
+		status: CommitStatus,
+		description: &str,
+	) -> Result<(), anyhow::Error> {
+		if self.access_token.is_none() {
+			return Ok(());
+		}
+
+		let url = self.repository_url(["statuses", commit_sha])?;
+		let body = match self.provider {
+			GitProvider::Github | GitProvider::Gitea => serde_json::json!({
+				"state": match status {
+					CommitStatus::Pending => "pending",
+					CommitStatus::Success => "success",
+					CommitStatus::Failure => "failure",
+					CommitStatus::Error => "error",
+				},
+				"context": constants::CI_COMMIT_STATUS_CONTEXT,
+				"description": description,
+			}),
+			GitProvider::Gitlab => serde_json::json!({
+				"state": match status {
+					CommitStatus::Pending => "pending",
+					CommitStatus::Success => "success",
+					CommitStatus::Failure | CommitStatus::Error => "failed",
+				},
+				"name": constants::CI_COMMIT_STATUS_CONTEXT,
+				"description": description,
+			}),
+		};
+
+		self.request(reqwest::Method::POST, url)?
+			.json(&body)
+			.send()
+			.await?
+			.error_for_status()?;
+
+		Ok(())
+	}
+
+	/// Reports the status of a pipeline run on a commit in the background, so
+	/// that a slow or failing provider does not hold up the request. Failures
+	/// are only logged, since the run itself is not affected by them.
+	pub fn spawn_commit_status_report(
+		self,
+		commit_sha: String,
+		status: CommitStatus,
+		description: impl Into<String>,
+	) {
+		let description = description.into();
+		tokio::spawn(async move {
+			_ = self
+				.report_commit_status(&commit_sha, status, &description)
+				.await
+				.inspect_err(|err| {
+					warn!(
+						"Error reporting the status of commit `{commit_sha}` of `{}`: {err}",
+						self.full_name
+					);
+				});
+		});
+	}
+
+	/// The URL of an endpoint of the API of the provider that is about the
+	/// repository, with the given segments after the path of the repository
+	fn repository_url<'a>(
+		&'a self,
+		segments: impl IntoIterator<Item = &'a str>,
+	) -> Result<Url, anyhow::Error> {
+		let mut url = Url::parse(&self.api_url)?;
+		{
+			let mut path = url
+				.path_segments_mut()
+				.map_err(|()| anyhow::anyhow!("The API URL `{}` cannot be a base", self.api_url))?;
+			path.pop_if_empty();
+			match self.provider {
+				GitProvider::Github | GitProvider::Gitea => {
+					path.push("repos").extend(self.full_name.split('/'));
+				}
+				// GitLab takes the full name of the project as a single segment
+				GitProvider::Gitlab => {
+					path.extend(["projects", self.full_name.as_str()]);
+				}
+			}
+			path.extend(segments);
+		}
+
+		Ok(url)
+	}
+
+	/// Starts a request to the API of the provider, authenticated with the
+	/// access token of the repository if it has one
+	fn request(
+		&self,
+		method: reqwest::Method,
+		url: Url,
+	) -> Result<reqwest::RequestBuilder, anyhow::Error> {
+		let request = reqwest::Client::builder()
+			.timeout(constants::CI_PROVIDER_REQUEST_TIMEOUT.unsigned_abs())
+			.build()?
+			.request(method, url)
+			// GitHub rejects requests without a user agent
+			.header(USER_AGENT, "Patr");
+
+		Ok(match (&self.access_token, self.provider) {
+			(None, _) => request,
+			(Some(access_token), GitProvider::Github) => {
+				request.header(AUTHORIZATION, format!("Bearer {access_token}"))
+			}
+			(Some(access_token), GitProvider::Gitea) => {
+				request.header(AUTHORIZATION, format!("token {access_token}"))
+			}
+			(Some(access_token), GitProvider::Gitlab) => {
+				request.header("PRIVATE-TOKEN", access_token)
+			}
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use axum::http::HeaderMap;
+	use models::ci::GitProvider;
+
+	use super::verify_webhook_signature;
+
+	// Test case 2 of RFC 4231
+	const SECRET: &str = "Jefe";
+	const BODY: &[u8] = b"what do ya want for nothing?";
+	const SIGNATURE: &str = "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843";
+
+	fn verify(provider: GitProvider, header: &'static str, value: &str, body: &[u8]) -> bool {
+		let mut headers = HeaderMap::new();
+		headers.insert(header, value.parse().unwrap());
+		verify_webhook_signature(provider, SECRET, &headers, body)
+	}
+
+	#[test]
+	fn assert_github_signatures_are_verified() {
+		let signature = format!("sha256={}", SIGNATURE);
+		assert!(verify(GitProvider::Github, "X-Hub-Signature-256", &signature, BODY));
+		assert!(!verify(GitProvider::Github, "X-Hub-Signature-256", &signature, b"other"));
+		assert!(!verify(GitProvider::Github, "X-Hub-Signature-256", SIGNATURE, BODY));
+		assert!(!verify(GitProvider::Github, "X-Gitea-Signature", SIGNATURE, BODY));
+	}
+
+	#[test]
+	fn assert_gitea_signatures_are_verified() {
+		assert!(verify(GitProvider::Gitea, "X-Gitea-Signature", SIGNATURE, BODY));
+		assert!(!verify(GitProvider::Gitea, "X-Gitea-Signature", SIGNATURE, b"other"));
+		assert!(!verify(GitProvider::Gitea, "X-Gitea-Signature", "not hex", BODY));
+		assert!(!verify(GitProvider::Gitea, "X-Gitea-Signature", &SIGNATURE[2..], BODY));
+	}
+
+	#[test]
+	fn assert_gitlab_tokens_are_verified() {
+		assert!(verify(GitProvider::Gitlab, "X-Gitlab-Token", SECRET, b""));
+		assert!(!verify(GitProvider::Gitlab, "X-Gitlab-Token", "Jef", b""));
+		assert!(!verify(GitProvider::Gitlab, "X-Gitlab-Token", "Jefe ", b""));
+		assert!(!verify(GitProvider::Gitlab, "X-Hub-Signature-256", SECRET, b""));
+	}
+}
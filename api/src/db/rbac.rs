@@ -28,6 +28,8 @@ pub async fn initialize_rbac_tables(
 			id UUID NOT NULL,
 			resource_type_id UUID,
 			owner_id UUID NOT NULL,
+			/* The project that the resource is grouped under, if any */
+			project_id UUID,
 			created TIMESTAMPTZ NOT NULL,
 			deleted TIMESTAMPTZ
 		);
@@ -68,7 +70,28 @@ pub async fn initialize_rbac_tables(
 		CREATE TABLE workspace_user(
 			user_id UUID NOT NULL,
 			workspace_id UUID NOT NULL,
-			role_id UUID NOT NULL
+			role_id UUID NOT NULL,
+			/* The role is only given to the user within this time range. A NULL
+			value means that the range is unbounded on that side */
+			valid_from TIMESTAMPTZ,
+			valid_until TIMESTAMPTZ
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	// Users can request a role in a workspace for a limited amount of time
+	query!(
+		r#"
+		CREATE TABLE workspace_access_request(
+			id UUID NOT NULL,
+			workspace_id UUID NOT NULL,
+			user_id UUID NOT NULL,
+			role_id UUID NOT NULL,
+			duration_hours INTEGER NOT NULL,
+			reason VARCHAR(500) NOT NULL,
+			created TIMESTAMPTZ NOT NULL
 		);
 		"#
 	)
@@ -158,6 +181,18 @@ pub async fn initialize_rbac_indices(
 	.execute(&mut *connection)
 	.await?;
 
+	query!(
+		r#"
+		CREATE INDEX
+			resource_idx_project_id
+		ON
+			resource
+		(project_id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
 	// Roles belong to an workspace
 	query!(
 		r#"
@@ -214,6 +249,17 @@ pub async fn initialize_rbac_indices(
 	.execute(&mut *connection)
 	.await?;
 
+	query!(
+		r#"
+		ALTER TABLE workspace_access_request
+			ADD CONSTRAINT workspace_access_request_pk PRIMARY KEY(id),
+			ADD CONSTRAINT workspace_access_request_uq_workspace_id_user_id_role_id
+				UNIQUE(workspace_id, user_id, role_id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
 	query!(
 		r#"
 		ALTER TABLE role_resource_permissions_type
@@ -268,7 +314,10 @@ pub async fn initialize_rbac_constraints(
 				FOREIGN KEY(resource_type_id) REFERENCES resource_type(id),
 			ADD CONSTRAINT resource_fk_owner_id
 				FOREIGN KEY(owner_id) REFERENCES workspace(id)
-					DEFERRABLE INITIALLY IMMEDIATE;
+					DEFERRABLE INITIALLY IMMEDIATE,
+			ADD CONSTRAINT resource_fk_project_id_owner_id
+				FOREIGN KEY(project_id, owner_id) REFERENCES project(id, workspace_id),
+			ADD CONSTRAINT resource_chk_project_id_is_not_self CHECK(project_id != id);
 		"#
 	)
 	.execute(&mut *connection)
@@ -294,7 +343,27 @@ pub async fn initialize_rbac_constraints(
 			ADD CONSTRAINT workspace_user_fk_workspace_id
 				FOREIGN KEY(workspace_id) REFERENCES workspace(id),
 			ADD CONSTRAINT workspace_user_fk_role_id
-				FOREIGN KEY(role_id) REFERENCES role(id);
+				FOREIGN KEY(role_id) REFERENCES role(id),
+			ADD CONSTRAINT workspace_user_chk_valid_until_after_valid_from
+				CHECK(valid_until > valid_from);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		ALTER TABLE workspace_access_request
+			ADD CONSTRAINT workspace_access_request_fk_workspace_id
+				FOREIGN KEY(workspace_id) REFERENCES workspace(id),
+			ADD CONSTRAINT workspace_access_request_fk_user_id
+				FOREIGN KEY(user_id) REFERENCES "user"(id),
+			ADD CONSTRAINT workspace_access_request_fk_role_id
+				FOREIGN KEY(role_id) REFERENCES role(id),
+			ADD CONSTRAINT workspace_access_request_chk_duration_hours_is_positive
+				CHECK(duration_hours > 0),
+			ADD CONSTRAINT workspace_access_request_chk_reason_is_trimmed
+				CHECK(reason = TRIM(reason));
 		"#
 	)
 	.execute(&mut *connection)
@@ -429,6 +498,7 @@ pub async fn initialize_rbac_constraints(
 			id UUID,
 			resource_type_id UUID,
 			owner_id UUID,
+			project_id UUID,
 			created TIMESTAMPTZ,
 			deleted TIMESTAMPTZ
 		) AS $$
@@ -448,6 +518,8 @@ pub async fn initialize_rbac_constraints(
 				RAISE EXCEPTION 'Permission `%` not found', permission_name;
 			END IF;
 
+			/* A resource matches an include or exclude permission either by its
+			own ID, or by the ID of the project that it is a part of */
 			RETURN QUERY SELECT
 				resource.*
 			FROM
@@ -473,7 +545,7 @@ pub async fn initialize_rbac_constraints(
 						workspace.super_admin_id = user_login.user_id
 					WHERE
 						user_login.login_id = RESOURCES_WITH_PERMISSION_FOR_LOGIN_ID.login_id
-				) OR resource.id IN (
+				) OR ARRAY[resource.id, resource.project_id] && ARRAY(
 					SELECT
 						COALESCE(
 							user_api_token_resource_permissions_include.resource_id,
@@ -489,7 +561,9 @@ pub async fn initialize_rbac_constraints(
 					LEFT JOIN
 						workspace_user
 					ON
-						workspace_user.user_id = user_login.user_id
+						workspace_user.user_id = user_login.user_id AND
+						(workspace_user.valid_from IS NULL OR workspace_user.valid_from <= NOW()) AND
+						(workspace_user.valid_until IS NULL OR workspace_user.valid_until > NOW())
 					LEFT JOIN
 						role_resource_permissions_include
 					ON
@@ -501,7 +575,7 @@ pub async fn initialize_rbac_constraints(
 							user_api_token_resource_permissions_include.workspace_id,
 							workspace_user.workspace_id
 						)
-				) OR resource.id NOT IN (
+				) OR NOT (ARRAY[resource.id, resource.project_id] && ARRAY(
 					SELECT
 						COALESCE(
 							user_api_token_resource_permissions_exclude.resource_id,
@@ -517,7 +591,9 @@ pub async fn initialize_rbac_constraints(
 					LEFT JOIN
 						workspace_user
 					ON
-						workspace_user.user_id = user_login.user_id
+						workspace_user.user_id = user_login.user_id AND
+						(workspace_user.valid_from IS NULL OR workspace_user.valid_from <= NOW()) AND
+						(workspace_user.valid_until IS NULL OR workspace_user.valid_until > NOW())
 					LEFT JOIN
 						role_resource_permissions_exclude
 					ON
@@ -529,7 +605,7 @@ pub async fn initialize_rbac_constraints(
 							user_api_token_resource_permissions_exclude.workspace_id,
 							workspace_user.workspace_id
 						)
-				);
+				));
 		END;
 		$$ LANGUAGE plpgsql;
 		"#
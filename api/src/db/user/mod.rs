@@ -9,6 +9,8 @@ mod user_email;
 /// The user login tables. This is used to store the login information of the
 /// user and their API tokens.
 mod user_login;
+/// The identities of users on external OpenID Connect providers
+mod user_oidc_identity;
 /// The phone numbers of the user
 mod user_phone;
 
@@ -22,6 +24,7 @@ pub async fn initialize_user_tables(
 	user_email::initialize_user_email_tables(&mut *connection).await?;
 	user_phone::initialize_user_phone_tables(&mut *connection).await?;
 	user_login::initialize_user_login_tables(&mut *connection).await?;
+	user_oidc_identity::initialize_user_oidc_identity_tables(&mut *connection).await?;
 	sign_up::initialize_user_sign_up_tables(&mut *connection).await?;
 
 	Ok(())
@@ -37,6 +40,7 @@ pub async fn initialize_user_indices(
 	user_email::initialize_user_email_indices(&mut *connection).await?;
 	user_phone::initialize_user_phone_indices(&mut *connection).await?;
 	user_login::initialize_user_login_indices(&mut *connection).await?;
+	user_oidc_identity::initialize_user_oidc_identity_indices(&mut *connection).await?;
 	sign_up::initialize_user_sign_up_indices(&mut *connection).await?;
 
 	Ok(())
@@ -52,6 +56,7 @@ pub async fn initialize_user_constraints(
 	user_email::initialize_user_email_constraints(&mut *connection).await?;
 	user_phone::initialize_user_phone_constraints(&mut *connection).await?;
 	user_login::initialize_user_login_constraints(&mut *connection).await?;
+	user_oidc_identity::initialize_user_oidc_identity_constraints(&mut *connection).await?;
 	sign_up::initialize_user_sign_up_constraints(&mut *connection).await?;
 
 	Ok(())
@@ -20,7 +20,10 @@ pub async fn initialize_user_sign_up_tables(
 			recovery_phone_number VARCHAR(15),
 
 			otp_hash TEXT NOT NULL,
-			otp_expiry TIMESTAMPTZ NOT NULL
+			otp_expiry TIMESTAMPTZ NOT NULL,
+
+			/* The signed token of a workspace invite to accept once signed up */
+			invite_token TEXT
 		);
 		"#
 	)
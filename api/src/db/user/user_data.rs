@@ -23,7 +23,9 @@ pub async fn initialize_user_data_tables(
 			password_reset_token TEXT,
 			password_reset_token_expiry TIMESTAMPTZ NULL,
 			password_reset_attempts INT NULL,
-			mfa_secret TEXT
+			mfa_secret TEXT,
+			/* Service accounts are owned by a workspace, and cannot log in */
+			is_service_account BOOLEAN NOT NULL DEFAULT FALSE
 		);
 		"#
 	)
@@ -44,6 +46,7 @@ pub async fn initialize_user_data_indices(
 		ALTER TABLE "user"
 			ADD CONSTRAINT user_pk PRIMARY KEY(id),
 			ADD CONSTRAINT user_uq_username UNIQUE(username),
+			ADD CONSTRAINT user_uq_id_is_service_account UNIQUE(id, is_service_account),
 			ADD CONSTRAINT user_uq_recovery_email UNIQUE(recovery_email),
 			ADD CONSTRAINT user_uq_recovery_phone_country_code_recovery_phone_number
 				UNIQUE(recovery_phone_country_code, recovery_phone_number);
@@ -90,6 +93,7 @@ pub async fn initialize_user_data_constraints(
 				recovery_phone_country_code = UPPER(recovery_phone_country_code)
 			),
 			ADD CONSTRAINT user_chk_email_or_phone_present CHECK(
+				is_service_account OR
 				(
 					recovery_email IS NOT NULL
 				) OR
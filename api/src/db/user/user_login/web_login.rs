@@ -25,6 +25,10 @@ pub async fn initialize_web_login_tables(
 			created_city TEXT NOT NULL,
 			created_timezone TEXT NOT NULL,
 
+			last_activity TIMESTAMPTZ NOT NULL,
+			/* The ID of the external OpenID Connect provider this login was created with, if any */
+			sso_provider_id TEXT,
+
 			login_type USER_LOGIN_TYPE NOT NULL GENERATED ALWAYS AS ('web_login') STORED
 		);
 		"#
@@ -0,0 +1,64 @@
+use crate::prelude::*;
+
+/// Initializes the user OIDC identity tables
+#[instrument(skip(connection))]
+pub async fn initialize_user_oidc_identity_tables(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up user OIDC identity tables");
+	query!(
+		r#"
+		CREATE TABLE user_oidc_identity(
+			issuer TEXT NOT NULL,
+			subject TEXT NOT NULL,
+			user_id UUID NOT NULL,
+			provider_id TEXT NOT NULL,
+			created TIMESTAMPTZ NOT NULL
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Initializes the user OIDC identity indices
+#[instrument(skip(connection))]
+pub async fn initialize_user_oidc_identity_indices(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up user OIDC identity indices");
+	query!(
+		r#"
+		ALTER TABLE user_oidc_identity
+			ADD CONSTRAINT user_oidc_identity_pk PRIMARY KEY(issuer, subject),
+			ADD CONSTRAINT user_oidc_identity_uq_user_id_issuer UNIQUE(
+				user_id, issuer
+			);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Initializes the user OIDC identity constraints
+#[instrument(skip(connection))]
+pub async fn initialize_user_oidc_identity_constraints(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up user OIDC identity constraints");
+	query!(
+		r#"
+		ALTER TABLE user_oidc_identity
+			ADD CONSTRAINT user_oidc_identity_fk_user_id
+				FOREIGN KEY(user_id) REFERENCES "user"(id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
@@ -12,7 +12,8 @@ pub async fn initialize_workspace_tables(
 		CREATE TYPE AUDIT_LOG_TYPE AS ENUM (
 			'create',
 			'update',
-			'delete'
+			'delete',
+			'account_locked'
 		);
 		"#
 	)
@@ -23,12 +24,23 @@ pub async fn initialize_workspace_tables(
 		r#"
 		CREATE TABLE audit_log(
 			id UUID NOT NULL,
-			/* workspace_id is kept in case the resource is moved to another workspace */
-			workspace_id UUID NOT NULL,
-			resource_id UUID NOT NULL,
+			/* workspace_id is kept in case the resource is moved to another workspace.
+			This is NULL for account-level events, such as lockouts */
+			workspace_id UUID,
+			resource_id UUID,
+			/* The user that made the request, or the user that the audit log is
+			about, for account-level events */
+			user_id UUID,
 			timestamp TIMESTAMPTZ NOT NULL,
 			action AUDIT_LOG_TYPE NOT NULL,
-			login_id UUID NOT NULL
+			login_id UUID,
+			ip_address INET NOT NULL,
+			/* The ID of the request that resulted in this audit log */
+			request_id UUID NOT NULL,
+			metadata JSON NOT NULL,
+			/* Whether the action was done by Patr itself, and not by a user */
+			patr_action BOOLEAN NOT NULL,
+			success BOOLEAN NOT NULL
 		);
 		"#
 	)
@@ -39,21 +51,76 @@ pub async fn initialize_workspace_tables(
 }
 
 /// Initializes all audit log-related indices
-#[instrument(skip(_connection))]
+#[instrument(skip(connection))]
 pub async fn initialize_workspace_indices(
-	_connection: &mut DatabaseConnection,
+	connection: &mut DatabaseConnection,
 ) -> Result<(), sqlx::Error> {
 	info!("Setting up audit logs indices");
 
+	query!(
+		r#"
+		ALTER TABLE audit_log
+		ADD CONSTRAINT audit_log_pk
+		PRIMARY KEY(id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE INDEX
+			audit_log_idx_user_id_timestamp
+		ON
+			audit_log
+		(user_id, timestamp);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE INDEX
+			audit_log_idx_workspace_id_timestamp
+		ON
+			audit_log
+		(workspace_id, timestamp);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
 	Ok(())
 }
 
 /// Initializes all audit log-related constraints
-#[instrument(skip(_connection))]
+#[instrument(skip(connection))]
 pub async fn initialize_workspace_constraints(
-	_connection: &mut DatabaseConnection,
+	connection: &mut DatabaseConnection,
 ) -> Result<(), sqlx::Error> {
 	info!("Setting up audit logs constraints");
 
+	query!(
+		r#"
+		ALTER TABLE audit_log
+			ADD CONSTRAINT audit_log_fk_user_id
+				FOREIGN KEY(user_id) REFERENCES "user"(id),
+			ADD CONSTRAINT audit_log_chk_workspace_id_resource_id_login_id CHECK(
+				(
+					action = 'account_locked' AND
+					user_id IS NOT NULL
+				) OR (
+					action != 'account_locked' AND
+					workspace_id IS NOT NULL AND
+					resource_id IS NOT NULL AND
+					login_id IS NOT NULL
+				)
+			);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
 	Ok(())
 }
@@ -0,0 +1,254 @@
+use crate::prelude::*;
+
+/// Initializes all billing-related tables
+#[instrument(skip(connection))]
+pub async fn initialize_billing_tables(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up billing tables");
+
+	query!(
+		r#"
+		CREATE TYPE USAGE_TYPE AS ENUM(
+			'deployment',
+			'volume',
+			'container_registry_storage',
+			'static_site_bandwidth'
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TABLE workspace_usage(
+			workspace_id UUID NOT NULL,
+			resource_id UUID NOT NULL,
+			usage_type USAGE_TYPE NOT NULL,
+			/* The start of the hour that the usage was recorded for */
+			hour TIMESTAMPTZ NOT NULL,
+			/* Only set for deployments, since they are priced by machine type */
+			machine_type UUID,
+			/* The number of replicas for deployments, GB for volumes, bytes
+			for container registry storage and bytes transferred for static
+			site bandwidth */
+			quantity BIGINT NOT NULL
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TABLE workspace_usage_recorded_hour(
+			/* The start of an hour that the usage of all workspaces has been
+			recorded for */
+			hour TIMESTAMPTZ NOT NULL
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TABLE workspace_billing_period(
+			workspace_id UUID NOT NULL,
+			/* The start of the month that the plan was used for */
+			period_start TIMESTAMPTZ NOT NULL,
+			plan_id TEXT NOT NULL,
+			/* The plan as it was configured when the month ended, so that
+			changes to the plans do not change the statements of past months */
+			plan JSONB NOT NULL
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TABLE deployment_replica_report(
+			deployment_id UUID NOT NULL,
+			/* The number of replicas that the runner had running */
+			replicas INT NOT NULL,
+			reported TIMESTAMPTZ NOT NULL
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TABLE static_site_bandwidth(
+			static_site_id UUID NOT NULL,
+			bytes BIGINT NOT NULL,
+			recorded TIMESTAMPTZ NOT NULL
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Initializes all billing-related indices
+#[instrument(skip(connection))]
+pub async fn initialize_billing_indices(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up billing indices");
+
+	query!(
+		r#"
+		ALTER TABLE workspace_usage
+		ADD CONSTRAINT workspace_usage_pk
+		PRIMARY KEY(resource_id, usage_type, hour);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE INDEX
+			workspace_usage_idx_workspace_id_hour
+		ON
+			workspace_usage
+		(workspace_id, hour);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		ALTER TABLE workspace_usage_recorded_hour
+		ADD CONSTRAINT workspace_usage_recorded_hour_pk
+		PRIMARY KEY(hour);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		ALTER TABLE workspace_billing_period
+		ADD CONSTRAINT workspace_billing_period_pk
+		PRIMARY KEY(workspace_id, period_start);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE INDEX
+			deployment_replica_report_idx_reported
+		ON
+			deployment_replica_report
+		(reported);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE INDEX
+			static_site_bandwidth_idx_recorded
+		ON
+			static_site_bandwidth
+		(recorded);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Initializes all billing-related constraints
+#[instrument(skip(connection))]
+pub async fn initialize_billing_constraints(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up billing constraints");
+
+	query!(
+		r#"
+		ALTER TABLE workspace_usage
+			ADD CONSTRAINT workspace_usage_fk_workspace_id
+				FOREIGN KEY(workspace_id) REFERENCES workspace(id),
+			ADD CONSTRAINT workspace_usage_fk_machine_type
+				FOREIGN KEY(machine_type) REFERENCES deployment_machine_type(id),
+			ADD CONSTRAINT workspace_usage_chk_quantity_non_negative
+				CHECK(quantity >= 0),
+			ADD CONSTRAINT workspace_usage_chk_hour_is_hour
+				CHECK(hour = DATE_TRUNC('hour', hour)),
+			ADD CONSTRAINT workspace_usage_chk_machine_type CHECK(
+				(
+					usage_type = 'deployment' AND
+					machine_type IS NOT NULL
+				) OR (
+					usage_type != 'deployment' AND
+					machine_type IS NULL
+				)
+			);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		ALTER TABLE static_site_bandwidth
+			ADD CONSTRAINT static_site_bandwidth_fk_static_site_id
+				FOREIGN KEY(static_site_id) REFERENCES static_site(id),
+			ADD CONSTRAINT static_site_bandwidth_chk_bytes_non_negative
+				CHECK(bytes >= 0);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		ALTER TABLE workspace_usage_recorded_hour
+			ADD CONSTRAINT workspace_usage_recorded_hour_chk_hour_is_hour
+				CHECK(hour = DATE_TRUNC('hour', hour));
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		ALTER TABLE workspace_billing_period
+			ADD CONSTRAINT workspace_billing_period_fk_workspace_id
+				FOREIGN KEY(workspace_id) REFERENCES workspace(id),
+			ADD CONSTRAINT workspace_billing_period_chk_period_start_is_month
+				CHECK(period_start = DATE_TRUNC('month', period_start, 'UTC'));
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		ALTER TABLE deployment_replica_report
+			ADD CONSTRAINT deployment_replica_report_fk_deployment_id
+				FOREIGN KEY(deployment_id) REFERENCES deployment(id),
+			ADD CONSTRAINT deployment_replica_report_chk_replicas_non_negative
+				CHECK(replicas >= 0);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
@@ -0,0 +1,219 @@
+use crate::prelude::*;
+
+/// Initializes the CI tables
+#[instrument(skip(connection))]
+pub async fn initialize_ci_tables(connection: &mut DatabaseConnection) -> Result<(), sqlx::Error> {
+	info!("Setting up CI tables");
+	query!(
+		r#"
+		CREATE TYPE GIT_PROVIDER AS ENUM(
+			'github',
+			'gitlab',
+			'gitea'
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TYPE CI_EVENT AS ENUM(
+			'commit',
+			'tag',
+			'pull'
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TYPE CI_RUN_STATE AS ENUM(
+			'queued',
+			'succeeded',
+			'failed'
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TABLE ci_repository(
+			id UUID NOT NULL,
+			workspace_id UUID NOT NULL,
+			provider GIT_PROVIDER NOT NULL,
+			api_url TEXT NOT NULL,
+			full_name TEXT NOT NULL,
+			runner_id UUID NOT NULL,
+			ci_file_path TEXT NOT NULL,
+			/* The secret that the webhooks of the repository are signed with */
+			webhook_secret TEXT NOT NULL,
+			/* The token used to access the API of the provider, if any */
+			access_token TEXT,
+			enabled BOOLEAN NOT NULL DEFAULT TRUE,
+			created TIMESTAMPTZ NOT NULL,
+			deleted TIMESTAMPTZ
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TABLE ci_run(
+			id UUID NOT NULL,
+			repository_id UUID NOT NULL,
+			runner_id UUID NOT NULL,
+			commit_sha TEXT NOT NULL,
+			branch TEXT,
+			tag TEXT,
+			event CI_EVENT NOT NULL,
+			state CI_RUN_STATE NOT NULL DEFAULT 'queued',
+			error TEXT,
+			/* The report of every step of the run, once it is complete */
+			steps JSONB NOT NULL DEFAULT '[]',
+			created TIMESTAMPTZ NOT NULL,
+			finished TIMESTAMPTZ
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TABLE ci_run_log(
+			run_id UUID NOT NULL,
+			/* The position of the line among every line of the run */
+			line_number BIGINT NOT NULL,
+			step TEXT NOT NULL,
+			line TEXT NOT NULL,
+			logged TIMESTAMPTZ NOT NULL
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Initializes the CI indices
+#[instrument(skip(connection))]
+pub async fn initialize_ci_indices(connection: &mut DatabaseConnection) -> Result<(), sqlx::Error> {
+	info!("Setting up CI indices");
+	query!(
+		r#"
+		ALTER TABLE ci_repository
+		ADD CONSTRAINT ci_repository_pk
+		PRIMARY KEY(id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE UNIQUE INDEX
+			ci_repository_uq_workspace_id_api_url_full_name
+		ON
+			ci_repository(workspace_id, api_url, full_name)
+		WHERE
+			deleted IS NULL;
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		ALTER TABLE ci_run
+		ADD CONSTRAINT ci_run_pk
+		PRIMARY KEY(id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE INDEX
+			ci_run_idx_repository_id_created
+		ON
+			ci_run
+		(repository_id, created);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		ALTER TABLE ci_run_log
+		ADD CONSTRAINT ci_run_log_pk
+		PRIMARY KEY(run_id, line_number);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Initializes the CI constraints
+#[instrument(skip(connection))]
+pub async fn initialize_ci_constraints(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up CI constraints");
+	query!(
+		r#"
+		ALTER TABLE ci_repository
+			ADD CONSTRAINT ci_repository_fk_id_workspace_id_deleted
+				FOREIGN KEY(id, workspace_id, deleted)
+					REFERENCES resource(id, owner_id, deleted)
+					DEFERRABLE INITIALLY IMMEDIATE,
+			ADD CONSTRAINT ci_repository_fk_runner_id
+				FOREIGN KEY(runner_id) REFERENCES runner(id),
+			ADD CONSTRAINT ci_repository_chk_full_name_is_trimmed
+				CHECK(full_name = TRIM(full_name)),
+			ADD CONSTRAINT ci_repository_chk_api_url_is_http
+				CHECK(api_url LIKE 'http://%' OR api_url LIKE 'https://%');
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		ALTER TABLE ci_run
+			ADD CONSTRAINT ci_run_fk_repository_id
+				FOREIGN KEY(repository_id) REFERENCES ci_repository(id),
+			ADD CONSTRAINT ci_run_fk_runner_id
+				FOREIGN KEY(runner_id) REFERENCES runner(id),
+			ADD CONSTRAINT ci_run_chk_finished_once_not_queued
+				CHECK((state = 'queued') = (finished IS NULL));
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		ALTER TABLE ci_run_log
+			ADD CONSTRAINT ci_run_log_fk_run_id
+				FOREIGN KEY(run_id) REFERENCES ci_run(id),
+			ADD CONSTRAINT ci_run_log_chk_line_number_unsigned
+				CHECK(line_number >= 0);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
@@ -0,0 +1,109 @@
+use crate::prelude::*;
+
+/// Initializes the workspace deletion tables
+#[instrument(skip(connection))]
+pub async fn initialize_deletion_tables(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up workspace deletion tables");
+	query!(
+		r#"
+		CREATE TYPE WORKSPACE_DELETION_STEP AS ENUM(
+			'deployments',
+			'static_sites',
+			'managed_databases',
+			'volumes',
+			'container_registry',
+			'managed_urls',
+			'domains',
+			'secrets',
+			'runners',
+			'workspace'
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TABLE workspace_deletion(
+			workspace_id UUID NOT NULL,
+			requested_by UUID NOT NULL,
+			requested TIMESTAMPTZ NOT NULL,
+			/* The end of the grace period, after which the teardown starts */
+			scheduled TIMESTAMPTZ NOT NULL,
+			/* The step that is yet to be run. NULL once the teardown is completed */
+			current_step WORKSPACE_DELETION_STEP DEFAULT 'deployments',
+			failed_attempts INTEGER NOT NULL DEFAULT 0,
+			last_error TEXT,
+			last_attempt TIMESTAMPTZ,
+			completed TIMESTAMPTZ
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Initializes the workspace deletion indices
+#[instrument(skip(connection))]
+pub async fn initialize_deletion_indices(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up workspace deletion indices");
+	query!(
+		r#"
+		ALTER TABLE workspace_deletion
+		ADD CONSTRAINT workspace_deletion_pk
+		PRIMARY KEY(workspace_id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE INDEX
+			workspace_deletion_idx_scheduled
+		ON
+			workspace_deletion
+		(scheduled)
+		WHERE
+			completed IS NULL;
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Initializes the workspace deletion constraints
+#[instrument(skip(connection))]
+pub async fn initialize_deletion_constraints(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up workspace deletion constraints");
+	query!(
+		r#"
+		ALTER TABLE workspace_deletion
+			ADD CONSTRAINT workspace_deletion_fk_workspace_id
+				FOREIGN KEY(workspace_id) REFERENCES workspace(id),
+			ADD CONSTRAINT workspace_deletion_fk_requested_by
+				FOREIGN KEY(requested_by) REFERENCES "user"(id),
+			ADD CONSTRAINT workspace_deletion_chk_scheduled_after_requested
+				CHECK(scheduled >= requested),
+			ADD CONSTRAINT workspace_deletion_chk_failed_attempts_non_negative
+				CHECK(failed_attempts >= 0),
+			ADD CONSTRAINT workspace_deletion_chk_completed_has_no_step
+				CHECK((current_step IS NULL) = (completed IS NOT NULL));
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
@@ -279,7 +279,8 @@ pub async fn initialize_deployment_constraints(
 				FOREIGN KEY(machine_type) REFERENCES deployment_machine_type(id),
 			ADD CONSTRAINT deployment_fk_repository_id_workspace_id
 				FOREIGN KEY(repository_id, workspace_id)
-					REFERENCES container_registry_repository(id, workspace_id),
+					REFERENCES container_registry_repository(id, workspace_id)
+					DEFERRABLE INITIALLY IMMEDIATE,
 			ADD CONSTRAINT deployment_chk_repository_id_is_valid CHECK(
 				(
 					registry = 'registry.patr.cloud' AND
@@ -0,0 +1,136 @@
+use crate::prelude::*;
+
+/// Initializes the workspace invite tables
+#[instrument(skip(connection))]
+pub async fn initialize_invite_tables(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up workspace invite tables");
+	query!(
+		r#"
+		CREATE TABLE workspace_invite(
+			id UUID NOT NULL,
+			workspace_id UUID NOT NULL,
+			/* The invited user is either an email or an existing user */
+			email TEXT,
+			user_id UUID,
+			invited_by UUID NOT NULL,
+			/* The ID of the currently valid invite token. Changed on resend */
+			token_id UUID NOT NULL,
+			created TIMESTAMPTZ NOT NULL,
+			expiry TIMESTAMPTZ NOT NULL
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TABLE workspace_invite_role(
+			invite_id UUID NOT NULL,
+			role_id UUID NOT NULL
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Initializes the workspace invite indices
+#[instrument(skip(connection))]
+pub async fn initialize_invite_indices(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up workspace invite indices");
+	query!(
+		r#"
+		ALTER TABLE workspace_invite
+		ADD CONSTRAINT workspace_invite_pk
+		PRIMARY KEY(id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE UNIQUE INDEX
+			workspace_invite_uq_workspace_id_email
+		ON
+			workspace_invite(workspace_id, email)
+		WHERE
+			email IS NOT NULL;
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE UNIQUE INDEX
+			workspace_invite_uq_workspace_id_user_id
+		ON
+			workspace_invite(workspace_id, user_id)
+		WHERE
+			user_id IS NOT NULL;
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		ALTER TABLE workspace_invite_role
+		ADD CONSTRAINT workspace_invite_role_pk
+		PRIMARY KEY(invite_id, role_id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Initializes the workspace invite constraints
+#[instrument(skip(connection))]
+pub async fn initialize_invite_constraints(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up workspace invite constraints");
+	query!(
+		r#"
+		ALTER TABLE workspace_invite
+			ADD CONSTRAINT workspace_invite_fk_workspace_id
+				FOREIGN KEY(workspace_id) REFERENCES workspace(id),
+			ADD CONSTRAINT workspace_invite_fk_user_id
+				FOREIGN KEY(user_id) REFERENCES "user"(id),
+			ADD CONSTRAINT workspace_invite_fk_invited_by
+				FOREIGN KEY(invited_by) REFERENCES "user"(id),
+			ADD CONSTRAINT workspace_invite_chk_email_or_user_id CHECK(
+				num_nonnulls(email, user_id) = 1
+			),
+			ADD CONSTRAINT workspace_invite_chk_email_is_lower_case CHECK(
+				email = LOWER(email)
+			);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		ALTER TABLE workspace_invite_role
+			ADD CONSTRAINT workspace_invite_role_fk_invite_id
+				FOREIGN KEY(invite_id) REFERENCES workspace_invite(id),
+			ADD CONSTRAINT workspace_invite_role_fk_role_id
+				FOREIGN KEY(role_id) REFERENCES role(id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
@@ -140,7 +140,8 @@ pub async fn initialize_managed_url_constraints(
 					REFERENCES deployment_exposed_port(deployment_id, port)
 						DEFERRABLE INITIALLY IMMEDIATE,
 			ADD CONSTRAINT managed_url_fk_deployment_id_workspace_id
-				FOREIGN KEY(deployment_id, workspace_id) REFERENCES deployment(id, workspace_id),
+				FOREIGN KEY(deployment_id, workspace_id) REFERENCES deployment(id, workspace_id)
+					DEFERRABLE INITIALLY IMMEDIATE,
 			ADD CONSTRAINT managed_url_fk_static_site_id_workspace_id
 				FOREIGN KEY(static_site_id, workspace_id) REFERENCES static_site(id, workspace_id);
 		"#
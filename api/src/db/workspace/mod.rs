@@ -2,10 +2,24 @@ use crate::prelude::*;
 
 /// All tables related to the audit logs go here
 mod audit_log;
+/// The usage of resources in a workspace, recorded for billing
+mod billing;
 /// The data stored in the container registry
 mod container_registry;
+/// The workspaces that are scheduled for deletion, along with the progress of
+/// destroying their resources
+mod deletion;
 /// The list of domains that are added to a workspace
 mod domain;
+/// The pending invites for users to join a workspace
+mod invite;
+/// The pending transfers of the ownership of a workspace to another user
+mod ownership_transfer;
+/// The list of projects that are used to group resources in a workspace
+mod project;
+/// The webhooks that the events of a workspace are sent to, along with the log
+/// of deliveries of the events
+mod webhook;
 
 /// The list of deployments that are present in a workspace
 mod deployment;
@@ -18,10 +32,16 @@ mod static_site;
 /// The list of deployment volumes that are created in a workspace
 mod volume;
 
+/// The git repositories connected to a workspace, along with the runs of their
+/// CI pipelines
+mod ci;
 /// The list of runners that are a part of a workspace
 mod runner;
 /// The list of secrets that are added to a workspace
 mod secret;
+/// The service accounts of a workspace, which are non-human identities that
+/// authenticate as a user that cannot log in
+mod service_account;
 
 /// Initializes all workspace-related tables
 #[instrument(skip(connection))]
@@ -35,6 +55,16 @@ pub async fn initialize_workspace_tables(
 			id UUID NOT NULL,
 			name CITEXT NOT NULL,
 			super_admin_id UUID NOT NULL,
+			/* If set, members must sign in with this external OpenID Connect provider */
+			sso_provider_id TEXT,
+			deployment_limit INTEGER NOT NULL,
+			static_site_limit INTEGER NOT NULL,
+			cpu_count_limit INTEGER NOT NULL,
+			memory_count_limit INTEGER NOT NULL, /* Multiples of 0.25 GB */
+			volume_storage_limit INTEGER NOT NULL, /* In GB */
+			container_registry_storage_limit BIGINT NOT NULL, /* In bytes */
+			/* The billing plan in the config. NULL uses the default plan */
+			billing_plan_id TEXT,
 			deleted TIMESTAMPTZ
 		);
 		"#
@@ -55,8 +85,14 @@ pub async fn initialize_workspace_tables(
 	.await?;
 
 	audit_log::initialize_workspace_tables(connection).await?;
+	billing::initialize_billing_tables(connection).await?;
 	container_registry::initialize_container_registry_tables(connection).await?;
+	deletion::initialize_deletion_tables(connection).await?;
 	domain::initialize_domain_tables(connection).await?;
+	invite::initialize_invite_tables(connection).await?;
+	ownership_transfer::initialize_ownership_transfer_tables(connection).await?;
+	project::initialize_project_tables(connection).await?;
+	webhook::initialize_webhook_tables(connection).await?;
 
 	deployment::initialize_deployment_tables(connection).await?;
 	managed_database::initialize_managed_database_tables(connection).await?;
@@ -64,8 +100,10 @@ pub async fn initialize_workspace_tables(
 	static_site::initialize_static_site_tables(connection).await?;
 	volume::initialize_volume_tables(connection).await?;
 
+	ci::initialize_ci_tables(connection).await?;
 	runner::initialize_runner_tables(connection).await?;
 	secret::initialize_secret_tables(connection).await?;
+	service_account::initialize_service_account_tables(connection).await?;
 
 	Ok(())
 }
@@ -113,8 +151,14 @@ pub async fn initialize_workspace_indices(
 	.await?;
 
 	audit_log::initialize_workspace_indices(connection).await?;
+	billing::initialize_billing_indices(connection).await?;
 	container_registry::initialize_container_registry_indices(connection).await?;
+	deletion::initialize_deletion_indices(connection).await?;
 	domain::initialize_domain_indices(connection).await?;
+	invite::initialize_invite_indices(connection).await?;
+	ownership_transfer::initialize_ownership_transfer_indices(connection).await?;
+	project::initialize_project_indices(connection).await?;
+	webhook::initialize_webhook_indices(connection).await?;
 
 	deployment::initialize_deployment_indices(connection).await?;
 	managed_database::initialize_managed_database_indices(connection).await?;
@@ -122,8 +166,10 @@ pub async fn initialize_workspace_indices(
 	static_site::initialize_static_site_indices(connection).await?;
 	volume::initialize_volume_indices(connection).await?;
 
+	ci::initialize_ci_indices(connection).await?;
 	runner::initialize_runner_indices(connection).await?;
 	secret::initialize_secret_indices(connection).await?;
+	service_account::initialize_service_account_indices(connection).await?;
 
 	Ok(())
 }
@@ -140,15 +186,29 @@ pub async fn initialize_workspace_constraints(
 			ADD CONSTRAINT workspace_fk_id FOREIGN KEY(id) REFERENCES resource(id)
 				DEFERRABLE INITIALLY IMMEDIATE,
 			ADD CONSTRAINT workspace_fk_super_admin_id
-				FOREIGN KEY(super_admin_id) REFERENCES "user"(id);
+				FOREIGN KEY(super_admin_id) REFERENCES "user"(id),
+			ADD CONSTRAINT workspace_chk_limits_positive CHECK(
+				deployment_limit >= 0 AND
+				static_site_limit >= 0 AND
+				cpu_count_limit >= 0 AND
+				memory_count_limit >= 0 AND
+				volume_storage_limit >= 0 AND
+				container_registry_storage_limit >= 0
+			);
 		"#
 	)
 	.execute(&mut *connection)
 	.await?;
 
 	audit_log::initialize_workspace_constraints(connection).await?;
+	billing::initialize_billing_constraints(connection).await?;
 	container_registry::initialize_container_registry_constraints(connection).await?;
+	deletion::initialize_deletion_constraints(connection).await?;
 	domain::initialize_domain_constraints(connection).await?;
+	invite::initialize_invite_constraints(connection).await?;
+	ownership_transfer::initialize_ownership_transfer_constraints(connection).await?;
+	project::initialize_project_constraints(connection).await?;
+	webhook::initialize_webhook_constraints(connection).await?;
 
 	deployment::initialize_deployment_constraints(connection).await?;
 	managed_database::initialize_managed_database_constraints(connection).await?;
@@ -156,8 +216,10 @@ pub async fn initialize_workspace_constraints(
 	static_site::initialize_static_site_constraints(connection).await?;
 	volume::initialize_volume_constraints(connection).await?;
 
+	ci::initialize_ci_constraints(connection).await?;
 	runner::initialize_runner_constraints(connection).await?;
 	secret::initialize_secret_constraints(connection).await?;
+	service_account::initialize_service_account_constraints(connection).await?;
 
 	Ok(())
 }
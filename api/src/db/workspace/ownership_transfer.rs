@@ -0,0 +1,82 @@
+use crate::prelude::*;
+
+/// Initializes the workspace ownership transfer tables
+#[instrument(skip(connection))]
+pub async fn initialize_ownership_transfer_tables(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up workspace ownership transfer tables");
+	query!(
+		r#"
+		CREATE TABLE workspace_ownership_transfer(
+			workspace_id UUID NOT NULL,
+			new_owner_id UUID NOT NULL,
+			requested_by UUID NOT NULL,
+			created TIMESTAMPTZ NOT NULL,
+			expiry TIMESTAMPTZ NOT NULL
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Initializes the workspace ownership transfer indices
+#[instrument(skip(connection))]
+pub async fn initialize_ownership_transfer_indices(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up workspace ownership transfer indices");
+	query!(
+		r#"
+		ALTER TABLE workspace_ownership_transfer
+		ADD CONSTRAINT workspace_ownership_transfer_pk
+		PRIMARY KEY(workspace_id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE INDEX
+			workspace_ownership_transfer_idx_new_owner_id
+		ON
+			workspace_ownership_transfer
+		(new_owner_id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Initializes the workspace ownership transfer constraints
+#[instrument(skip(connection))]
+pub async fn initialize_ownership_transfer_constraints(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up workspace ownership transfer constraints");
+	query!(
+		r#"
+		ALTER TABLE workspace_ownership_transfer
+			ADD CONSTRAINT workspace_ownership_transfer_fk_workspace_id
+				FOREIGN KEY(workspace_id) REFERENCES workspace(id),
+			ADD CONSTRAINT workspace_ownership_transfer_fk_new_owner_id
+				FOREIGN KEY(new_owner_id) REFERENCES "user"(id),
+			ADD CONSTRAINT workspace_ownership_transfer_fk_requested_by
+				FOREIGN KEY(requested_by) REFERENCES "user"(id),
+			ADD CONSTRAINT workspace_ownership_transfer_chk_new_owner_is_not_requester
+				CHECK(new_owner_id != requested_by),
+			ADD CONSTRAINT workspace_ownership_transfer_chk_expiry_after_created
+				CHECK(expiry > created);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
@@ -0,0 +1,78 @@
+use crate::prelude::*;
+
+/// Initializes the project tables
+#[instrument(skip(connection))]
+pub async fn initialize_project_tables(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up project tables");
+	query!(
+		r#"
+		CREATE TABLE project(
+			id UUID NOT NULL,
+			name CITEXT NOT NULL,
+			description VARCHAR(500) NOT NULL,
+			workspace_id UUID NOT NULL,
+			deleted TIMESTAMPTZ
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Initializes the project indices
+#[instrument(skip(connection))]
+pub async fn initialize_project_indices(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up project indices");
+	query!(
+		r#"
+		ALTER TABLE project
+			ADD CONSTRAINT project_pk PRIMARY KEY(id),
+			ADD CONSTRAINT project_uq_id_workspace_id UNIQUE(id, workspace_id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE UNIQUE INDEX
+			project_uq_workspace_id_name
+		ON
+			project(workspace_id, name)
+		WHERE
+			deleted IS NULL;
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Initializes the project constraints
+#[instrument(skip(connection))]
+pub async fn initialize_project_constraints(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up project constraints");
+	query!(
+		r#"
+		ALTER TABLE project
+			ADD CONSTRAINT project_chk_name_is_trimmed CHECK(name = TRIM(name)),
+			ADD CONSTRAINT project_fk_id_workspace_id_deleted
+				FOREIGN KEY(id, workspace_id, deleted)
+					REFERENCES resource(id, owner_id, deleted)
+					DEFERRABLE INITIALLY IMMEDIATE;
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
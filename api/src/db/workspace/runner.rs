@@ -20,6 +20,24 @@ pub async fn initialize_runner_tables(
 	.execute(&mut *connection)
 	.await?;
 
+	query!(
+		r#"
+		CREATE TABLE runner_join_token(
+			id UUID NOT NULL,
+			runner_id UUID NOT NULL,
+			/* The service account that the runner authenticates as once it joins */
+			service_account_id UUID NOT NULL,
+			token_hash TEXT NOT NULL,
+			created TIMESTAMPTZ NOT NULL,
+			expiry TIMESTAMPTZ NOT NULL,
+			/* A join token can only be used once */
+			used TIMESTAMPTZ
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
 	Ok(())
 }
 
@@ -53,6 +71,16 @@ pub async fn initialize_runner_indices(
 	.execute(&mut *connection)
 	.await?;
 
+	query!(
+		r#"
+		ALTER TABLE runner_join_token
+		ADD CONSTRAINT runner_join_token_pk
+		PRIMARY KEY(id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
 	Ok(())
 }
 
@@ -76,5 +104,19 @@ pub async fn initialize_runner_constraints(
 	.execute(&mut *connection)
 	.await?;
 
+	query!(
+		r#"
+		ALTER TABLE runner_join_token
+			ADD CONSTRAINT runner_join_token_fk_runner_id
+				FOREIGN KEY(runner_id) REFERENCES runner(id),
+			ADD CONSTRAINT runner_join_token_fk_service_account_id
+				FOREIGN KEY(service_account_id) REFERENCES service_account(id),
+			ADD CONSTRAINT runner_join_token_chk_expiry_after_created
+				CHECK(expiry > created);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
 	Ok(())
 }
@@ -0,0 +1,82 @@
+use crate::prelude::*;
+
+/// Initializes the service account tables
+#[instrument(skip(connection))]
+pub async fn initialize_service_account_tables(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up service account tables");
+	query!(
+		r#"
+		CREATE TABLE service_account(
+			/* The ID of the user that the service account authenticates as */
+			id UUID NOT NULL,
+			workspace_id UUID NOT NULL,
+			name CITEXT NOT NULL,
+			created TIMESTAMPTZ NOT NULL,
+			deleted TIMESTAMPTZ,
+			is_service_account BOOLEAN NOT NULL GENERATED ALWAYS AS (TRUE) STORED
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Initializes the service account indices
+#[instrument(skip(connection))]
+pub async fn initialize_service_account_indices(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up service account indices");
+	query!(
+		r#"
+		ALTER TABLE service_account
+		ADD CONSTRAINT service_account_pk
+		PRIMARY KEY(id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE UNIQUE INDEX
+			service_account_uq_workspace_id_name
+		ON
+			service_account(workspace_id, name)
+		WHERE
+			deleted IS NULL;
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Initializes the service account constraints
+#[instrument(skip(connection))]
+pub async fn initialize_service_account_constraints(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up service account constraints");
+	query!(
+		r#"
+		ALTER TABLE service_account
+			ADD CONSTRAINT service_account_fk_id_is_service_account
+				FOREIGN KEY(id, is_service_account)
+					REFERENCES "user"(id, is_service_account),
+			ADD CONSTRAINT service_account_fk_workspace_id
+				FOREIGN KEY(workspace_id) REFERENCES workspace(id),
+			ADD CONSTRAINT service_account_chk_name_is_trimmed
+				CHECK(name = TRIM(name));
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
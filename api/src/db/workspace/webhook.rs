@@ -0,0 +1,403 @@
+use crate::prelude::*;
+
+/// Initializes the webhook tables
+#[instrument(skip(connection))]
+pub async fn initialize_webhook_tables(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up webhook tables");
+	query!(
+		r#"
+		CREATE TYPE WEBHOOK_EVENT_TYPE AS ENUM(
+			'deployment_created',
+			'deployment_updated',
+			'deployment_status_changed',
+			'image_pushed',
+			'static_site_uploaded',
+			'runner_disconnected',
+			'member_added'
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TYPE WEBHOOK_DELIVERY_STATUS AS ENUM(
+			'pending',
+			'succeeded',
+			'failed'
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TABLE webhook(
+			id UUID NOT NULL,
+			workspace_id UUID NOT NULL,
+			name TEXT NOT NULL,
+			url TEXT NOT NULL,
+			/* The secret used to sign the payloads sent to the webhook */
+			secret TEXT NOT NULL,
+			event_types WEBHOOK_EVENT_TYPE[] NOT NULL,
+			enabled BOOLEAN NOT NULL DEFAULT TRUE,
+			created TIMESTAMPTZ NOT NULL,
+			deleted TIMESTAMPTZ
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TABLE webhook_event(
+			/* The ID generated when the event is published, so that an event
+			that is received more than once is only recorded once */
+			id UUID NOT NULL,
+			workspace_id UUID NOT NULL,
+			event_type WEBHOOK_EVENT_TYPE NOT NULL,
+			payload JSONB NOT NULL,
+			created TIMESTAMPTZ NOT NULL
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TABLE webhook_delivery(
+			id UUID NOT NULL,
+			webhook_id UUID NOT NULL,
+			event_id UUID NOT NULL,
+			status WEBHOOK_DELIVERY_STATUS NOT NULL DEFAULT 'pending',
+			attempts INTEGER NOT NULL DEFAULT 0,
+			/* NULL once the delivery is no longer pending */
+			next_attempt TIMESTAMPTZ,
+			last_attempt TIMESTAMPTZ,
+			response_status_code INTEGER,
+			last_error TEXT,
+			created TIMESTAMPTZ NOT NULL
+		);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Initializes the webhook indices
+#[instrument(skip(connection))]
+pub async fn initialize_webhook_indices(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up webhook indices");
+	query!(
+		r#"
+		ALTER TABLE webhook
+		ADD CONSTRAINT webhook_pk
+		PRIMARY KEY(id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE INDEX
+			webhook_idx_workspace_id
+		ON
+			webhook
+		(workspace_id)
+		WHERE
+			deleted IS NULL;
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		ALTER TABLE webhook_event
+		ADD CONSTRAINT webhook_event_pk
+		PRIMARY KEY(id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		ALTER TABLE webhook_delivery
+		ADD CONSTRAINT webhook_delivery_pk
+		PRIMARY KEY(id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE UNIQUE INDEX
+			webhook_delivery_uq_webhook_id_event_id
+		ON
+			webhook_delivery
+		(webhook_id, event_id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE INDEX
+			webhook_delivery_idx_next_attempt
+		ON
+			webhook_delivery
+		(next_attempt)
+		WHERE
+			status = 'pending';
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Initializes the webhook constraints, along with the triggers that publish
+/// the events of a workspace on the change feed of the database
+#[instrument(skip(connection))]
+pub async fn initialize_webhook_constraints(
+	connection: &mut DatabaseConnection,
+) -> Result<(), sqlx::Error> {
+	info!("Setting up webhook constraints");
+	query!(
+		r#"
+		ALTER TABLE webhook
+			ADD CONSTRAINT webhook_fk_workspace_id
+				FOREIGN KEY(workspace_id) REFERENCES workspace(id),
+			ADD CONSTRAINT webhook_chk_name_is_trimmed
+				CHECK(name = TRIM(name)),
+			ADD CONSTRAINT webhook_chk_url_is_http
+				CHECK(url LIKE 'http://%' OR url LIKE 'https://%');
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		ALTER TABLE webhook_event
+			ADD CONSTRAINT webhook_event_fk_workspace_id
+				FOREIGN KEY(workspace_id) REFERENCES workspace(id);
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		ALTER TABLE webhook_delivery
+			ADD CONSTRAINT webhook_delivery_fk_webhook_id
+				FOREIGN KEY(webhook_id) REFERENCES webhook(id),
+			ADD CONSTRAINT webhook_delivery_fk_event_id
+				FOREIGN KEY(event_id) REFERENCES webhook_event(id),
+			ADD CONSTRAINT webhook_delivery_chk_attempts_non_negative
+				CHECK(attempts >= 0),
+			ADD CONSTRAINT webhook_delivery_chk_pending_has_next_attempt
+				CHECK((status = 'pending') = (next_attempt IS NOT NULL));
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE FUNCTION NOTIFY_WORKSPACE_EVENT(
+			event_workspace_id UUID,
+			event JSONB
+		) RETURNS VOID AS $$
+		BEGIN
+			PERFORM pg_notify(
+				'data',
+				jsonb_build_object(
+					'id', gen_random_uuid(),
+					'workspaceId', event_workspace_id,
+					'event', event
+				)::TEXT
+			);
+		END;
+		$$ LANGUAGE plpgsql;
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE FUNCTION NOTIFY_DEPLOYMENT_EVENT() RETURNS TRIGGER AS $$
+		BEGIN
+			IF NEW.deleted IS NOT NULL THEN
+				RETURN NULL;
+			END IF;
+
+			IF TG_OP = 'INSERT' THEN
+				PERFORM NOTIFY_WORKSPACE_EVENT(
+					NEW.workspace_id,
+					jsonb_build_object(
+						'type', 'deploymentCreated',
+						'deploymentId', NEW.id
+					)
+				);
+			ELSIF OLD.status IS DISTINCT FROM NEW.status THEN
+				PERFORM NOTIFY_WORKSPACE_EVENT(
+					NEW.workspace_id,
+					jsonb_build_object(
+						'type', 'deploymentStatusChanged',
+						'deploymentId', NEW.id,
+						'status', NEW.status
+					)
+				);
+			ELSIF OLD IS DISTINCT FROM NEW THEN
+				PERFORM NOTIFY_WORKSPACE_EVENT(
+					NEW.workspace_id,
+					jsonb_build_object(
+						'type', 'deploymentUpdated',
+						'deploymentId', NEW.id
+					)
+				);
+			END IF;
+
+			RETURN NULL;
+		END;
+		$$ LANGUAGE plpgsql;
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TRIGGER deployment_trg_notify_workspace_event
+		AFTER INSERT OR UPDATE ON deployment
+		FOR EACH ROW EXECUTE FUNCTION NOTIFY_DEPLOYMENT_EVENT();
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE FUNCTION NOTIFY_IMAGE_PUSHED_EVENT() RETURNS TRIGGER AS $$
+		BEGIN
+			IF TG_OP = 'UPDATE' AND OLD.manifest_digest = NEW.manifest_digest THEN
+				RETURN NULL;
+			END IF;
+
+			PERFORM NOTIFY_WORKSPACE_EVENT(
+				container_registry_repository.workspace_id,
+				jsonb_build_object(
+					'type', 'imagePushed',
+					'repositoryId', NEW.repository_id,
+					'tag', NEW.tag,
+					'digest', NEW.manifest_digest
+				)
+			)
+			FROM
+				container_registry_repository
+			WHERE
+				container_registry_repository.id = NEW.repository_id;
+
+			RETURN NULL;
+		END;
+		$$ LANGUAGE plpgsql;
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TRIGGER container_registry_repository_tag_trg_notify_workspace_event
+		AFTER INSERT OR UPDATE ON container_registry_repository_tag
+		FOR EACH ROW EXECUTE FUNCTION NOTIFY_IMAGE_PUSHED_EVENT();
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE FUNCTION NOTIFY_STATIC_SITE_UPLOADED_EVENT() RETURNS TRIGGER AS $$
+		BEGIN
+			PERFORM NOTIFY_WORKSPACE_EVENT(
+				static_site.workspace_id,
+				jsonb_build_object(
+					'type', 'staticSiteUploaded',
+					'staticSiteId', NEW.static_site_id,
+					'uploadId', NEW.upload_id
+				)
+			)
+			FROM
+				static_site
+			WHERE
+				static_site.id = NEW.static_site_id;
+
+			RETURN NULL;
+		END;
+		$$ LANGUAGE plpgsql;
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TRIGGER static_site_upload_history_trg_notify_workspace_event
+		AFTER INSERT ON static_site_upload_history
+		FOR EACH ROW EXECUTE FUNCTION NOTIFY_STATIC_SITE_UPLOADED_EVENT();
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE FUNCTION NOTIFY_MEMBER_ADDED_EVENT() RETURNS TRIGGER AS $$
+		BEGIN
+			PERFORM NOTIFY_WORKSPACE_EVENT(
+				NEW.workspace_id,
+				jsonb_build_object(
+					'type', 'memberAdded',
+					'userId', NEW.user_id,
+					'roleId', NEW.role_id
+				)
+			);
+
+			RETURN NULL;
+		END;
+		$$ LANGUAGE plpgsql;
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		CREATE TRIGGER workspace_user_trg_notify_workspace_event
+		AFTER INSERT ON workspace_user
+		FOR EACH ROW EXECUTE FUNCTION NOTIFY_MEMBER_ADDED_EVENT();
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
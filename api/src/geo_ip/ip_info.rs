@@ -0,0 +1,60 @@
+use std::net::IpAddr;
+
+use models::utils::GeoLocation;
+
+use super::IpLocation;
+use crate::prelude::*;
+
+/// Lookup the location of an IP address using the ipinfo.io API. Bogon IP
+/// addresses (private, reserved, etc) do not have a location and will return
+/// `None`.
+pub async fn lookup(token: &str, ip_address: IpAddr) -> Option<IpLocation> {
+	let ip_info = ipinfo::IpInfo::new(ipinfo::IpInfoConfig {
+		token: Some(token.to_string()),
+		..Default::default()
+	})
+	.inspect_err(|err| {
+		warn!("Error creating IpInfo: {err}");
+	})
+	.ok()?
+	.lookup(ip_address.to_string().as_str())
+	.await
+	.inspect_err(|err| {
+		warn!("Error looking up IP address `{ip_address}`: {err}");
+	})
+	.ok()?;
+
+	if ip_info.bogon.unwrap_or(false) {
+		debug!("IP address `{ip_address}` is a bogon IP address");
+		return None;
+	}
+
+	let Some((latitude, longitude)) = ip_info.loc.split_once(',').and_then(|(lat, lng)| {
+		Some((
+			lat.parse::<f64>()
+				.inspect_err(|err| {
+					warn!("Error parsing latitude: `{lat}` - {err}");
+				})
+				.ok()?,
+			lng.parse::<f64>()
+				.inspect_err(|err| {
+					warn!("Error parsing longitude: `{lng}` - {err}");
+				})
+				.ok()?,
+		))
+	}) else {
+		warn!("Unknown latitude and longitude: `{}`", ip_info.loc);
+		return None;
+	};
+
+	Some(IpLocation {
+		location: GeoLocation {
+			latitude,
+			longitude,
+		},
+		country: ip_info.country,
+		region: ip_info.region,
+		city: ip_info.city,
+		timezone: ip_info.timezone.unwrap_or_default(),
+	})
+}
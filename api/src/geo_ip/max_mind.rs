@@ -0,0 +1,129 @@
+use std::{
+	net::IpAddr,
+	path::Path,
+	sync::{Arc, RwLock},
+	time::{Duration, SystemTime},
+};
+
+use maxminddb::{geoip2, MaxMindDBError, Reader};
+use models::utils::GeoLocation;
+
+use super::IpLocation;
+use crate::prelude::*;
+
+/// The currently loaded MaxMind database. This is `None` until the database is
+/// loaded for the first time. The database is swapped out whenever the file is
+/// modified, and any lookups in progress continue to use the older database
+/// until they're done.
+static DATABASE: RwLock<Option<Arc<Reader<Vec<u8>>>>> = RwLock::new(None);
+
+/// Lookup the location of an IP address in the currently loaded MaxMind
+/// database. Returns `None` if the database is not loaded yet, or if the IP
+/// address is not found in the database.
+pub fn lookup(ip_address: IpAddr) -> Option<IpLocation> {
+	let Some(database) = DATABASE
+		.read()
+		.expect("GeoIP database lock poisoned")
+		.clone()
+	else {
+		warn!("GeoIP database is not loaded yet");
+		return None;
+	};
+
+	let city = match database.lookup::<geoip2::City>(ip_address) {
+		Ok(city) => city,
+		Err(MaxMindDBError::AddressNotFoundError(_)) => {
+			debug!("IP address `{ip_address}` not found in the GeoIP database");
+			return None;
+		}
+		Err(err) => {
+			warn!("Error looking up IP address `{ip_address}`: {err}");
+			return None;
+		}
+	};
+
+	let location = city.location.as_ref();
+
+	Some(IpLocation {
+		location: GeoLocation {
+			latitude: location
+				.and_then(|location| location.latitude)
+				.unwrap_or_default(),
+			longitude: location
+				.and_then(|location| location.longitude)
+				.unwrap_or_default(),
+		},
+		country: city
+			.country
+			.and_then(|country| country.iso_code)
+			.unwrap_or_default()
+			.to_string(),
+		region: city
+			.subdivisions
+			.and_then(|subdivisions| subdivisions.into_iter().next())
+			.and_then(|subdivision| subdivision.names)
+			.and_then(|names| names.get("en").copied())
+			.unwrap_or_default()
+			.to_string(),
+		city: city
+			.city
+			.and_then(|city| city.names)
+			.and_then(|names| names.get("en").copied())
+			.unwrap_or_default()
+			.to_string(),
+		timezone: location
+			.and_then(|location| location.time_zone)
+			.unwrap_or_default()
+			.to_string(),
+	})
+}
+
+/// Loads the MaxMind database from the given path, and reloads it every time
+/// the file is modified. The file is checked for modifications every
+/// `reload_interval` seconds. This function runs for the lifetime of the
+/// application.
+pub async fn run(database_path: &Path, reload_interval: u64) {
+	let mut last_modified = None::<SystemTime>;
+
+	loop {
+		let modified = tokio::fs::metadata(database_path)
+			.await
+			.and_then(|metadata| metadata.modified())
+			.inspect_err(|err| {
+				error!(
+					"Error reading metadata of GeoIP database `{}`: {err}",
+					database_path.display()
+				);
+			})
+			.ok();
+
+		if modified.is_some() && modified != last_modified {
+			match load_database(database_path).await {
+				Ok(database) => {
+					info!(
+						"Loaded GeoIP database `{}` built on epoch {}",
+						database_path.display(),
+						database.metadata.build_epoch
+					);
+					*DATABASE.write().expect("GeoIP database lock poisoned") =
+						Some(Arc::new(database));
+					last_modified = modified;
+				}
+				Err(err) => {
+					error!(
+						"Error loading GeoIP database `{}`: {err}",
+						database_path.display()
+					);
+				}
+			}
+		}
+
+		tokio::time::sleep(Duration::from_secs(reload_interval)).await;
+	}
+}
+
+/// Reads and parses the MaxMind database from the given path
+async fn load_database(database_path: &Path) -> Result<Reader<Vec<u8>>, anyhow::Error> {
+	let data = tokio::fs::read(database_path).await?;
+	Ok(Reader::from_source(data)?)
+}
@@ -0,0 +1,72 @@
+use std::net::IpAddr;
+
+use models::utils::GeoLocation;
+
+use crate::{prelude::*, utils::config::GeoIpConfig};
+
+/// The provider that uses the ipinfo.io API to lookup IP addresses
+mod ip_info;
+/// The provider that uses a local MaxMind-format (mmdb) database file to lookup
+/// IP addresses. The database is reloaded whenever the file is modified.
+mod max_mind;
+
+/// The details of the location of an IP address, as returned by the configured
+/// geo-location provider.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IpLocation {
+	/// The geo-location (latitude and longitude) of the IP address
+	pub location: GeoLocation,
+	/// The ISO code of the country of the IP address
+	pub country: String,
+	/// The region (state, province, etc) of the IP address
+	pub region: String,
+	/// The city of the IP address
+	pub city: String,
+	/// The timezone of the IP address
+	pub timezone: String,
+}
+
+impl Default for IpLocation {
+	fn default() -> Self {
+		Self {
+			location: GeoLocation {
+				latitude: 0.0,
+				longitude: 0.0,
+			},
+			country: String::new(),
+			region: String::new(),
+			city: String::new(),
+			timezone: String::new(),
+		}
+	}
+}
+
+/// Lookup the location of an IP address using the configured provider. If the
+/// location of the IP address cannot be found (such as for private IP
+/// addresses), or if the provider fails, `None` is returned. This is
+/// intentionally infallible, so that things like logins do not depend on the
+/// availability of the provider.
+#[instrument(skip(config))]
+pub async fn lookup(config: &GeoIpConfig, ip_address: IpAddr) -> Option<IpLocation> {
+	match config {
+		GeoIpConfig::MaxMind { .. } => max_mind::lookup(ip_address),
+		GeoIpConfig::IpInfo { token } => ip_info::lookup(token, ip_address).await,
+	}
+}
+
+/// Loads the geo-location provider, if required, and keeps it up to date. For
+/// the MaxMind provider, this loads the database file and reloads it whenever
+/// the file is modified. This function runs for the lifetime of the
+/// application.
+#[instrument(skip(state))]
+pub async fn run(state: &AppState) {
+	match &state.config.geo_ip {
+		GeoIpConfig::MaxMind {
+			database_path,
+			reload_interval,
+		} => max_mind::run(database_path, *reload_interval).await,
+		GeoIpConfig::IpInfo { .. } => {
+			info!("Using ipinfo.io for IP address lookups");
+		}
+	}
+}
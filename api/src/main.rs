@@ -5,13 +5,26 @@
 /// This module contains the main application logic. Most of the app requests,
 /// states, and mounting of endpoints are done here
 pub mod app;
+/// This module records the usage of the resources of every workspace every
+/// hour, which is used to calculate the billing statements of workspaces.
+pub mod billing;
+/// This module talks to the git providers of the repositories connected to
+/// workspaces. It checks the signatures of their webhooks, reads the CI files
+/// of the repositories and reports the statuses of the pipeline runs back.
+pub mod ci;
 /// This module contains the database connection logic, as well as all the
 /// ORM entities.
 pub mod db;
+/// This module contains the providers used to lookup the geo-location of IP
+/// addresses, such as a local MaxMind database or the ipinfo.io API.
+pub mod geo_ip;
 /// This module contains the models used by the API. These are the structs that
 /// are used for encoding and decoding things that are not a part of the API
 /// (eg, JWT).
 pub mod models;
+/// This module generates the OpenAPI document of the API from the endpoints
+/// that are mounted, so that the API can be explored and clients generated.
+pub mod openapi;
 /// This module contains the Redis connection and all utilities to set and get
 /// data in Redis.
 pub mod redis;
@@ -25,6 +38,12 @@ pub mod routes;
 /// like the config parser, the [`tower::Layer`]s that are used to parse the
 /// requests.
 pub mod utils;
+/// This module records the events of workspaces from the change feed of the
+/// database, and delivers them to the webhooks that are subscribed to them.
+pub mod webhook;
+/// This module destroys the resources of the workspaces that are deleted along
+/// with all their resources, once the grace period of the deletion has ended.
+pub mod workspace_deletion;
 
 /// A prelude that re-exports commonly used items.
 pub mod prelude {
@@ -169,5 +188,12 @@ async fn main() {
 		.await
 		.expect("error initializing database");
 
-	futures::future::join(app::serve(&state), redis_publisher::run(&state)).await;
+	tokio::join!(
+		app::serve(&state),
+		redis_publisher::run(&state),
+		geo_ip::run(&state),
+		billing::run(&state),
+		workspace_deletion::run(&state),
+		webhook::run(&state),
+	);
 }
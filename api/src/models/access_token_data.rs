@@ -91,7 +91,7 @@ impl AccessTokenData {
 }
 
 /// A module to help serialize and deserialize `OffsetDateTime` as seconds
-mod datetime_as_seconds {
+pub(super) mod datetime_as_seconds {
 	use serde::{de::Error, Deserialize, Deserializer, Serializer};
 	use time::OffsetDateTime;
 
@@ -2,3 +2,5 @@
 pub mod access_token_data;
 /// Contains all the structs that will be stored in Redis
 pub mod redis;
+/// Contains the struct that will be encoded in the JWT of a workspace invite.
+pub mod workspace_invite_token_data;
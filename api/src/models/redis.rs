@@ -15,3 +15,17 @@ pub struct UserPermissionCache {
 	/// The timestamp when the user's permissions were inserted into Redis
 	pub creation_time: OffsetDateTime,
 }
+
+/// The struct that is stored in Redis when a user starts signing in with an
+/// external OpenID Connect provider. This is used to validate the callback
+/// from the provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcAuthorizationState {
+	/// The ID of the provider that the sign in was started with
+	pub provider_id: String,
+	/// The nonce that must be present in the ID token
+	pub nonce: String,
+	/// The PKCE code verifier that the code challenge was derived from
+	pub code_verifier: String,
+}
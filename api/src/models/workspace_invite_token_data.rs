@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use super::access_token_data::datetime_as_seconds;
+use crate::prelude::*;
+
+/// A struct representing the data that is stored inside the token of an invite
+/// to a workspace, which will be encoded as a JWT. The token is used in the
+/// invite link sent to the invited user.
+///
+/// The token only identifies the invite. Whether the invite is still pending,
+/// and who it was sent to, is always checked against the database. Resending an
+/// invite changes the `jti` of the invite, so that any previously sent links
+/// stop working.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceInviteTokenData {
+	/// The issuer of the JWT. This is always [`constants::JWT_ISSUER`].
+	pub iss: String,
+	/// The ID of the invite
+	pub sub: Uuid,
+	/// The audience of the JWT. This is always
+	/// [`constants::WORKSPACE_INVITE_JWT_AUDIENCE`].
+	pub aud: String,
+	/// The timestamp (in seconds) after which the invite can no longer be
+	/// accepted
+	#[serde(with = "datetime_as_seconds")]
+	pub exp: OffsetDateTime,
+	/// The ID of the token. This must match the token ID of the invite in the
+	/// database.
+	pub jti: Uuid,
+}
@@ -0,0 +1,566 @@
+use std::sync::{OnceLock, RwLock};
+
+use axum::http::{header::AUTHORIZATION, Method};
+use axum_extra::routing::TypedPath;
+use headers::Header;
+use models::{
+	utils::{
+		EndpointAuthentication,
+		EndpointDocumentation,
+		FieldDocumentation,
+		HeaderDocumentation,
+		RequestBodyDocumentation,
+		ResponseDocumentation,
+		SchemaGenerator,
+		SchemaSettings,
+		TotalCountHeader,
+	},
+	ErrorType,
+};
+use preprocess::Preprocessable;
+use serde_json::{json, Map, Value};
+
+use crate::prelude::*;
+
+/// The name of the security scheme that the authenticated endpoints use in the
+/// OpenAPI document
+const SECURITY_SCHEME: &str = "bearerAuth";
+
+/// The name of the schema of the error responses in the OpenAPI document
+const ERROR_SCHEMA: &str = "Error";
+
+/// An endpoint that is documented in the OpenAPI document. The documentation is
+/// only generated when the document is, since it needs a [`SchemaGenerator`]
+/// that is shared across all the endpoints.
+struct RegisteredEndpoint {
+	/// The HTTP method of the endpoint
+	method: Method,
+	/// The path of the endpoint, with the parameters in the axum format
+	path: &'static str,
+	/// Generates the documentation of the endpoint
+	documentation: fn(&mut SchemaGenerator) -> EndpointDocumentation,
+}
+
+/// All the endpoints that are documented in the OpenAPI document, in the order
+/// they were mounted
+static REGISTERED_ENDPOINTS: RwLock<Vec<RegisteredEndpoint>> = RwLock::new(Vec::new());
+
+/// The OpenAPI document, generated the first time it is requested. All the
+/// endpoints are mounted before the server starts, so the document never
+/// changes after that.
+static DOCUMENT: OnceLock<Value> = OnceLock::new();
+
+/// Registers an endpoint to be documented in the OpenAPI document. This is
+/// called by the [`RouterExt`][1] functions when mounting an endpoint.
+/// Endpoints that are only accessible by the Web UI are not documented.
+///
+/// [1]: crate::utils::RouterExt
+pub fn register_endpoint<E>()
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+	if !E::API_ALLOWED {
+		return;
+	}
+
+	REGISTERED_ENDPOINTS
+		.write()
+		.expect("OpenAPI endpoint registry poisoned")
+		.push(RegisteredEndpoint {
+			method: E::METHOD,
+			path: <E::RequestPath as TypedPath>::PATH,
+			documentation: E::documentation,
+		});
+}
+
+/// The OpenAPI 3.1 document of all the endpoints that can be accessed through
+/// the API
+pub fn document() -> &'static Value {
+	DOCUMENT.get_or_init(generate_document)
+}
+
+/// Generates the OpenAPI document from the registered endpoints
+fn generate_document() -> Value {
+	let mut generator = SchemaSettings::draft2019_09()
+		.with(|settings| {
+			settings.definitions_path = "#/components/schemas/".to_string();
+			settings.meta_schema = None;
+		})
+		.into_generator();
+
+	let mut paths = Map::new();
+	for endpoint in REGISTERED_ENDPOINTS
+		.read()
+		.expect("OpenAPI endpoint registry poisoned")
+		.iter()
+	{
+		let documentation = (endpoint.documentation)(&mut generator);
+		let operation = operation(endpoint.path, documentation);
+
+		if let Value::Object(path) = paths
+			.entry(openapi_path(endpoint.path))
+			.or_insert_with(|| Value::Object(Map::new()))
+		{
+			path.insert(endpoint.method.as_str().to_lowercase(), operation);
+		}
+	}
+
+	let error_type = to_json(&generator.subschema_for::<ErrorType>());
+	let mut schemas = generator
+		.definitions()
+		.iter()
+		.map(|(name, schema)| (name.clone(), to_json(schema)))
+		.collect::<Map<_, _>>();
+	schemas.insert(
+		ERROR_SCHEMA.to_string(),
+		json!({
+			"type": "object",
+			"required": ["success", "error", "message"],
+			"properties": {
+				"success": {
+					"const": false,
+				},
+				"error": error_type,
+				"message": {
+					"description": "A user-friendly message describing the error",
+					"type": "string",
+				},
+			},
+		}),
+	);
+
+	json!({
+		"openapi": "3.1.0",
+		"info": {
+			"title": "Patr API",
+			"version": env!("CARGO_PKG_VERSION"),
+		},
+		"servers": [{
+			"url": constants::API_URL,
+		}],
+		"paths": paths,
+		"components": {
+			"securitySchemes": {
+				SECURITY_SCHEME: {
+					"type": "http",
+					"scheme": "bearer",
+				},
+			},
+			"schemas": schemas,
+		},
+	})
+}
+
+/// Generates the OpenAPI operation of an endpoint from its documentation
+fn operation(path: &str, documentation: EndpointDocumentation) -> Value {
+	let EndpointDocumentation {
+		name,
+		description,
+		authentication,
+		path: path_parameters,
+		query,
+		paginated,
+		request_headers,
+		request_body,
+		response_headers,
+		response,
+	} = documentation;
+
+	let mut parameters = path_parameters
+		.into_iter()
+		.map(|field| parameter("path", field))
+		.chain(query.into_iter().map(|field| parameter("query", field)))
+		.collect::<Vec<_>>();
+	if paginated {
+		parameters.extend([
+			json!({
+				"name": "count",
+				"in": "query",
+				"description": "The number of items that should be returned per page",
+				"schema": {
+					"type": "integer",
+					"minimum": 0,
+					"default": Paginated::<()>::DEFAULT_PAGE_SIZE,
+				},
+			}),
+			json!({
+				"name": "page",
+				"in": "query",
+				"description": "The page number that should be returned, starting from 0",
+				"schema": {
+					"type": "integer",
+					"minimum": 0,
+					"default": 0,
+				},
+			}),
+		]);
+	}
+	// The authorization header is documented by the security scheme instead
+	parameters.extend(
+		request_headers
+			.into_iter()
+			.filter(|header| header.name != AUTHORIZATION.as_str())
+			.map(|HeaderDocumentation { name, description }| {
+				json!({
+					"name": name,
+					"in": "header",
+					"description": description,
+					"required": true,
+					"schema": {
+						"type": "string",
+					},
+				})
+			}),
+	);
+
+	let (success_status, success_response) = match response {
+		ResponseDocumentation::Json(fields) => (
+			"2XX",
+			json!({
+				"description": "The request was successful",
+				"headers": response_headers
+					.into_iter()
+					.map(|HeaderDocumentation { name, description }| {
+						let schema = if name == TotalCountHeader::name().as_str() {
+							json!({ "type": "integer", "minimum": 0 })
+						} else {
+							json!({ "type": "string" })
+						};
+						(
+							name.to_string(),
+							json!({
+								"description": description,
+								"required": true,
+								"schema": schema,
+							}),
+						)
+					})
+					.collect::<Map<_, _>>(),
+				"content": {
+					"application/json": {
+						"schema": {
+							"allOf": [
+								{
+									"type": "object",
+									"required": ["success"],
+									"properties": {
+										"success": {
+											"const": true,
+										},
+									},
+								},
+								object_schema(fields),
+							],
+						},
+					},
+				},
+			}),
+		),
+		ResponseDocumentation::WebSocket => (
+			"101",
+			json!({
+				"description": "The request was upgraded to a websocket, over which JSON messages are sent",
+			}),
+		),
+	};
+
+	let mut operation = json!({
+		"operationId": operation_id(name),
+		"summary": description,
+		"tags": [tag(path)],
+		"parameters": parameters,
+		"responses": {
+			success_status: success_response,
+			"default": {
+				"description": "The request failed",
+				"content": {
+					"application/json": {
+						"schema": {
+							"$ref": format!("#/components/schemas/{ERROR_SCHEMA}"),
+						},
+					},
+				},
+			},
+		},
+		"security": match authentication {
+			EndpointAuthentication::None => json!([]),
+			_ => json!([{ SECURITY_SCHEME: [] }]),
+		},
+	});
+
+	let Value::Object(fields) = &mut operation else {
+		unreachable!("an operation is always an object");
+	};
+	if let Some(access) = access_description(authentication) {
+		fields.insert(
+			"description".to_string(),
+			Value::String(format!("{description}\n\n{access}")),
+		);
+	}
+	if let EndpointAuthentication::ResourcePermission(permission) = authentication {
+		fields.insert(
+			"x-patr-permission".to_string(),
+			Value::String(permission.to_string()),
+		);
+	}
+	if let Some(request_body) = request_body {
+		fields.insert("requestBody".to_string(), request_body_object(request_body));
+	}
+
+	operation
+}
+
+/// Generates the OpenAPI request body of an endpoint. The maximum size of
+/// streamed bodies is documented in the description, since OpenAPI has no
+/// keyword for it.
+fn request_body_object(request_body: RequestBodyDocumentation) -> Value {
+	match request_body {
+		RequestBodyDocumentation::Json(fields) => json!({
+			"required": true,
+			"content": {
+				"application/json": {
+					"schema": object_schema(fields),
+				},
+			},
+		}),
+		RequestBodyDocumentation::Raw { max_size } => json!({
+			"description": format!("The raw bytes of the upload, of at most {max_size} bytes"),
+			"required": true,
+			"content": {
+				"application/octet-stream": {
+					"schema": {
+						"type": "string",
+						"format": "binary",
+					},
+				},
+			},
+		}),
+		RequestBodyDocumentation::Multipart { fields, max_size } => json!({
+			"description": format!(
+				"A form with the fields, followed by any number of files. The whole form can be at most {max_size} bytes"
+			),
+			"required": true,
+			"content": {
+				"multipart/form-data": {
+					"schema": {
+						"allOf": [
+							object_schema(fields),
+							{
+								"type": "object",
+								"additionalProperties": {
+									"type": "string",
+									"format": "binary",
+								},
+							},
+						],
+					},
+				},
+			},
+		}),
+	}
+}
+
+/// Generates the OpenAPI parameter of a field of the path or query of an
+/// endpoint. Path parameters are always required.
+fn parameter(location: &str, field: FieldDocumentation) -> Value {
+	let FieldDocumentation {
+		name,
+		description,
+		required,
+		flatten: _,
+		schema,
+	} = field;
+
+	json!({
+		"name": name,
+		"in": location,
+		"description": description,
+		"required": required || location == "path",
+		"schema": to_json(&schema),
+	})
+}
+
+/// Generates the schema of a JSON object with the given fields. Flattened
+/// fields are merged into the object using `allOf`.
+fn object_schema(fields: Vec<FieldDocumentation>) -> Value {
+	let mut properties = Map::new();
+	let mut required = Vec::new();
+	let mut flattened = Vec::new();
+
+	for field in fields {
+		let mut schema = to_json(&field.schema);
+		if field.flatten {
+			flattened.push(schema);
+			continue;
+		}
+
+		if let Value::Object(schema) = &mut schema {
+			if !field.description.is_empty() {
+				schema.insert("description".to_string(), Value::String(field.description));
+			}
+		}
+		if field.required {
+			required.push(field.name.clone());
+		}
+		properties.insert(field.name, schema);
+	}
+
+	let mut object = Map::new();
+	object.insert("type".to_string(), json!("object"));
+	object.insert("properties".to_string(), Value::Object(properties));
+	if !required.is_empty() {
+		object.insert("required".to_string(), json!(required));
+	}
+	let object = Value::Object(object);
+
+	if flattened.is_empty() {
+		object
+	} else {
+		json!({
+			"allOf": [object].into_iter().chain(flattened).collect::<Vec<_>>(),
+		})
+	}
+}
+
+/// Describes who can access an endpoint, for the endpoints that need more than
+/// just a valid token
+fn access_description(authentication: EndpointAuthentication) -> Option<String> {
+	match authentication {
+		EndpointAuthentication::None | EndpointAuthentication::PlainToken => None,
+		EndpointAuthentication::WorkspaceSuperAdmin => {
+			Some("Only the super admin of the workspace can access this endpoint.".to_string())
+		}
+		EndpointAuthentication::WorkspaceMembership => {
+			Some("Only the members of the workspace can access this endpoint.".to_string())
+		}
+		EndpointAuthentication::ResourcePermission(permission) => Some(format!(
+			"Requires the `{permission}` permission on the resource."
+		)),
+	}
+}
+
+/// Converts a path in the axum format, such as `/workspace/:workspace_id`, to
+/// the OpenAPI format, such as `/workspace/{workspace_id}`
+fn openapi_path(path: &str) -> String {
+	path.split('/')
+		.map(|segment| match segment.strip_prefix(':') {
+			Some(parameter) => format!("{{{parameter}}}"),
+			None => segment.to_string(),
+		})
+		.collect::<Vec<_>>()
+		.join("/")
+}
+
+/// The ID of the operation of an endpoint, which is the name of the endpoint
+/// in camelCase
+fn operation_id(name: &str) -> String {
+	let mut chars = name.chars();
+	chars
+		.next()
+		.map(|first| first.to_lowercase().chain(chars).collect())
+		.unwrap_or_default()
+}
+
+/// The tag that an endpoint is grouped under, which is the resource the
+/// endpoint is about. For workspace endpoints, this is the segment after the
+/// workspace ID, such as `deployment` or `ci`.
+fn tag(path: &str) -> &str {
+	let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+	let first = segments.next().unwrap_or_default();
+	if first != "workspace" {
+		return first;
+	}
+
+	segments
+		.nth(1)
+		.filter(|segment| !segment.starts_with(':'))
+		.unwrap_or(first)
+}
+
+/// Converts a JSON schema to a JSON value, to be put in the document
+fn to_json<T>(schema: &T) -> Value
+where
+	T: serde::Serialize,
+{
+	serde_json::to_value(schema).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+	use models::api::{auth::IsEmailValidRequest, workspace::ci::GetCiRunLogsRequest};
+
+	use super::{generate_document, openapi_path, operation_id, register_endpoint, tag};
+
+	#[test]
+	fn assert_paths_are_converted_to_openapi() {
+		assert_eq!(
+			openapi_path("/workspace/:workspace_id/deployment/:deployment_id"),
+			"/workspace/{workspace_id}/deployment/{deployment_id}"
+		);
+		assert_eq!(openapi_path("/auth/sign-in"), "/auth/sign-in");
+		assert_eq!(openapi_path("/"), "/");
+	}
+
+	#[test]
+	fn assert_operation_ids_are_camel_case() {
+		assert_eq!(operation_id("GetCiRunLogs"), "getCiRunLogs");
+		assert_eq!(operation_id("A"), "a");
+		assert_eq!(operation_id(""), "");
+	}
+
+	#[test]
+	fn assert_endpoints_are_tagged_by_resource() {
+		assert_eq!(tag("/auth/sign-in"), "auth");
+		assert_eq!(tag("/user/api-token/:token_id"), "user");
+		assert_eq!(tag("/workspace/:workspace_id/deployment/:deployment_id"), "deployment");
+		assert_eq!(tag("/workspace/:workspace_id/ci/repository"), "ci");
+		assert_eq!(tag("/workspace/:workspace_id"), "workspace");
+		assert_eq!(tag("/workspace"), "workspace");
+	}
+
+	#[test]
+	fn assert_document_is_generated_from_registered_endpoints() {
+		register_endpoint::<GetCiRunLogsRequest>();
+		register_endpoint::<IsEmailValidRequest>();
+
+		let document = generate_document();
+		assert_eq!(document["openapi"], "3.1.0");
+
+		let paths = document["paths"].as_object().unwrap();
+		assert!(!paths.contains_key("/auth/email-valid"));
+
+		let path = "/workspace/{workspace_id}/ci/repository/{repository_id}/run/{run_id}/logs";
+		let operation = &paths[path]["get"];
+		assert_eq!(operation["operationId"], "getCiRunLogs");
+		assert_eq!(operation["tags"], serde_json::json!(["ci"]));
+		assert_eq!(operation["security"], serde_json::json!([{ "bearerAuth": [] }]));
+		assert_eq!(operation["x-patr-permission"], "ciRepository::view");
+
+		let parameters = operation["parameters"]
+			.as_array()
+			.unwrap()
+			.iter()
+			.map(|parameter| parameter["name"].as_str().unwrap())
+			.collect::<Vec<_>>();
+		assert_eq!(
+			parameters,
+			[
+				"workspace_id",
+				"repository_id",
+				"run_id",
+				"count",
+				"page",
+				"user-agent",
+			]
+		);
+		assert_eq!(
+			operation["responses"]["default"]["content"]["application/json"]["schema"]["$ref"],
+			"#/components/schemas/Error"
+		);
+
+		let schemas = document["components"]["schemas"].as_object().unwrap();
+		assert!(schemas.contains_key("Error"));
+		assert!(schemas.contains_key("CiStepLog"));
+	}
+}
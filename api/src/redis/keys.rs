@@ -1,3 +1,5 @@
+use std::net::IpAddr;
+
 use crate::prelude::*;
 
 /// The key used to store the permissions for a login ID
@@ -49,3 +51,35 @@ pub fn runner_connection_lock(runner_id: &Uuid) -> String {
 pub fn runner_connection_lock_prefix() -> String {
 	String::from("runnerConnectionLock:")
 }
+
+/// The key used to store the number of requests made from an IP address to a
+/// rate limited bucket of endpoints, for a given window. The window is the
+/// unix timestamp divided by the window duration.
+pub fn rate_limit_requests_for_ip(bucket: &str, ip_address: &IpAddr, window: i64) -> String {
+	format!("rateLimitRequests:{}:{}:{}", bucket, ip_address, window)
+}
+
+/// The key used to store the number of failed authentication attempts on an
+/// account, for a given window. The window is the unix timestamp divided by the
+/// window duration.
+pub fn failed_attempts_for_account(account: &str, window: i64) -> String {
+	format!("failedAttempts:{}:{}", account, window)
+}
+
+/// The key used to store the unix timestamp until which an account is locked
+/// due to too many failed authentication attempts.
+pub fn account_locked_until(account: &str) -> String {
+	format!("accountLockedUntil:{}", account)
+}
+
+/// The key used to store the number of times an account has been locked
+/// recently. This is used to progressively increase the lockout duration.
+pub fn account_lockout_count(account: &str) -> String {
+	format!("accountLockoutCount:{}", account)
+}
+
+/// The key used to store the state of a sign in with an external OpenID Connect
+/// provider, between the authorization request and the callback.
+pub fn oidc_authorization_state(state: &str) -> String {
+	format!("oidcAuthorizationState:{}", state)
+}
@@ -1,4 +1,4 @@
-use std::{num::ParseFloatError, ops::Add};
+use std::ops::Add;
 
 use argon2::{
 	password_hash::SaltString,
@@ -10,11 +10,15 @@ use argon2::{
 };
 use axum::http::StatusCode;
 use jsonwebtoken::EncodingKey;
-use models::api::auth::*;
+use models::{api::auth::*, utils::GeoLocation};
 use sqlx::types::ipnetwork::IpNetwork;
 use time::OffsetDateTime;
 
-use crate::{models::access_token_data::AccessTokenData, prelude::*};
+use crate::{
+	geo_ip::{self, IpLocation},
+	models::access_token_data::AccessTokenData,
+	prelude::*,
+};
 
 pub async fn complete_sign_up(
 	AppRequest {
@@ -212,6 +216,28 @@ pub async fn complete_sign_up(
 
 	trace!("Constraints set to immediate");
 
+	if let Some(invite_token) = row.invite_token {
+		trace!("User signed up with an invite. Accepting the invite");
+
+		// The sign up should not fail just because the invite is no longer
+		// valid
+		match super::super::workspace::accept_workspace_invite_for_user(
+			&mut **database,
+			&config,
+			&user_id,
+			&invite_token,
+		)
+		.await
+		{
+			Ok(workspace_id) => {
+				info!("User `{user_id}` joined workspace `{workspace_id}` using an invite");
+			}
+			Err(err) => {
+				warn!("Unable to accept invite for user `{user_id}`: {err:?}");
+			}
+		}
+	}
+
 	let refresh_token = Uuid::new_v4();
 	let hashed_refresh_token = argon2::Argon2::new_with_secret(
 		config.password_pepper.as_ref(),
@@ -234,55 +260,24 @@ pub async fn complete_sign_up(
 	.to_string();
 	let refresh_token_expiry = now.add(constants::INACTIVE_REFRESH_TOKEN_VALIDITY);
 
-	let ip_info = ipinfo::IpInfo::new(ipinfo::IpInfoConfig {
-		token: { Some(config.ipinfo.token) },
-		..Default::default()
-	})
-	.inspect_err(|err| {
-		info!("Error creating IpInfo: {err}");
-	})
-	.map_err(ErrorType::server_error)?
-	.lookup(client_ip.to_string().as_str())
-	.await
-	.inspect_err(|err| {
-		info!("Error looking up IP address: {err}");
-	})
-	.map_err(ErrorType::server_error)?;
-
-	if !cfg!(debug_assertions) && ip_info.bogon.unwrap_or(false) {
-		return Err(ErrorType::server_error(format!(
-			"cannot use bogon IP address: `{}`",
-			client_ip
-		)));
-	}
+	let IpLocation {
+		location: GeoLocation {
+			latitude: lat,
+			longitude: lng,
+		},
+		country,
+		region,
+		city,
+		timezone,
+	} = geo_ip::lookup(&config.geo_ip, client_ip)
+		.await
+		.unwrap_or_else(|| {
+			info!("Unable to find the location of IP address: `{}`", client_ip);
+			IpLocation::default()
+		});
 
 	let client_ip = IpNetwork::from(client_ip);
 
-	let (lat, lng) = if cfg!(debug_assertions) {
-		(0f64, 0f64)
-	} else {
-		ip_info
-			.loc
-			.split_once(',')
-			.map(|(lat, lng)| {
-				Ok::<_, ParseFloatError>((
-					lat.parse::<f64>().inspect_err(|err| {
-						info!("Error parsing latitude: `{lat}` - {err}");
-					})?,
-					lng.parse::<f64>().inspect_err(|err| {
-						info!("Error parsing longitude: `{lng}` - {err}");
-					})?,
-				))
-			})
-			.ok_or_else(|| {
-				ErrorType::server_error(format!("unknown latitude and longitude: {}", ip_info.loc))
-			})??
-	};
-	let country = ip_info.country;
-	let region = ip_info.region;
-	let city = ip_info.city;
-	let timezone = ip_info.timezone.unwrap_or_else(Default::default);
-
 	let user_agent = user_agent.to_string();
 
 	let login_id = query!(
@@ -331,7 +326,9 @@ pub async fn complete_sign_up(
 				created_country,
 				created_region,
 				created_city,
-				created_timezone
+				created_timezone,
+
+				last_activity
 			)
 		VALUES
 			(
@@ -349,7 +346,9 @@ pub async fn complete_sign_up(
 				$10,
 				$11,
 				$12,
-				$13
+				$13,
+
+				$5
 			);
 		"#,
 		login_id as _,
@@ -22,6 +22,7 @@ pub async fn create_account(
 						first_name,
 						last_name,
 						recovery_method,
+						invite_token,
 					},
 			},
 		database,
@@ -178,7 +179,9 @@ pub async fn create_account(
 				recovery_phone_number,
 
 				otp_hash,
-				otp_expiry
+				otp_expiry,
+
+				invite_token
 			)
 		VALUES
 			(
@@ -192,7 +195,9 @@ pub async fn create_account(
 				$7,
 				
 				$8,
-				$9
+				$9,
+
+				$10
 			)
 		ON CONFLICT
 			(username)
@@ -204,7 +209,8 @@ pub async fn create_account(
 			recovery_phone_country_code = EXCLUDED.recovery_phone_country_code,
 			recovery_phone_number = EXCLUDED.recovery_phone_number,
 			otp_hash = EXCLUDED.otp_hash,
-			otp_expiry = EXCLUDED.otp_expiry
+			otp_expiry = EXCLUDED.otp_expiry,
+			invite_token = EXCLUDED.invite_token
 		WHERE
 			EXCLUDED.otp_expiry > NOW();
 		"#,
@@ -217,6 +223,7 @@ pub async fn create_account(
 		recovery_phone_number,
 		hashed_otp,
 		otp_expiry,
+		invite_token,
 	)
 	.execute(&mut **database)
 	.await?;
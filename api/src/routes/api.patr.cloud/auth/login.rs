@@ -1,4 +1,4 @@
-use std::{num::ParseFloatError, ops::Add};
+use std::ops::Add;
 
 use argon2::{
 	password_hash::SaltString,
@@ -10,12 +10,16 @@ use argon2::{
 };
 use axum::http::StatusCode;
 use jsonwebtoken::EncodingKey;
-use models::api::auth::*;
+use models::{api::auth::*, utils::GeoLocation};
 use sqlx::types::ipnetwork::IpNetwork;
 use time::OffsetDateTime;
 use totp_rs::{Algorithm as TotpAlgorithm, Secret, TOTP};
 
-use crate::{models::access_token_data::AccessTokenData, prelude::*};
+use crate::{
+	geo_ip::{self, IpLocation},
+	models::access_token_data::AccessTokenData,
+	prelude::*,
+};
 
 /// The handler to login the user. This will return the access token and the
 /// refresh token.
@@ -62,13 +66,16 @@ pub async fn login(
 		ON
 			phone_number_country_code.country_code = user_phone_number.country_code
 		WHERE
-			"user".username = $1 OR
-			user_email.email = $1 OR
-			CONCAT(
-				'+',
-				phone_number_country_code.phone_code,
-				user_phone_number.number
-			) = $1;
+			"user".is_service_account = FALSE AND
+			(
+				"user".username = $1 OR
+				user_email.email = $1 OR
+				CONCAT(
+					'+',
+					phone_number_country_code.phone_code,
+					user_phone_number.number
+				) = $1
+			);
 		"#,
 		&user_id,
 	)
@@ -170,53 +177,24 @@ pub async fn login(
 	.to_string();
 	let refresh_token_expiry = now.add(constants::INACTIVE_REFRESH_TOKEN_VALIDITY);
 
-	let ip_info = ipinfo::IpInfo::new(ipinfo::IpInfoConfig {
-		token: { Some(config.ipinfo.token) },
-		..Default::default()
-	})
-	.inspect_err(|err| {
-		info!("Error creating IpInfo: {err}");
-	})?
-	.lookup(client_ip.to_string().as_str())
-	.await
-	.inspect_err(|err| {
-		info!("Error looking up IP address: {err}");
-	})?;
-
-	if !cfg!(debug_assertions) && ip_info.bogon.unwrap_or(false) {
-		return Err(ErrorType::server_error(format!(
-			"cannot use bogon IP address: `{}`",
-			client_ip
-		)));
-	}
+	let IpLocation {
+		location: GeoLocation {
+			latitude: lat,
+			longitude: lng,
+		},
+		country,
+		region,
+		city,
+		timezone,
+	} = geo_ip::lookup(&config.geo_ip, client_ip)
+		.await
+		.unwrap_or_else(|| {
+			info!("Unable to find the location of IP address: `{}`", client_ip);
+			IpLocation::default()
+		});
 
 	let client_ip = IpNetwork::from(client_ip);
 
-	let (lat, lng) = if cfg!(debug_assertions) {
-		(0f64, 0f64)
-	} else {
-		ip_info
-			.loc
-			.split_once(',')
-			.map(|(lat, lng)| {
-				Ok::<_, ParseFloatError>((
-					lat.parse::<f64>().inspect_err(|err| {
-						info!("Error parsing latitude: `{lat}` - {err}");
-					})?,
-					lng.parse::<f64>().inspect_err(|err| {
-						info!("Error parsing longitude: `{lng}` - {err}");
-					})?,
-				))
-			})
-			.ok_or_else(|| {
-				ErrorType::server_error(format!("unknown latitude and longitude: {}", ip_info.loc))
-			})??
-	};
-	let country = ip_info.country;
-	let region = ip_info.region;
-	let city = ip_info.city;
-	let timezone = ip_info.timezone.unwrap_or_else(Default::default);
-
 	let user_agent = user_agent.to_string();
 
 	let login_id = query!(
@@ -263,7 +241,9 @@ pub async fn login(
 				created_country,
 				created_region,
 				created_city,
-				created_timezone
+				created_timezone,
+
+				last_activity
 			)
 		VALUES
 			(
@@ -281,7 +261,9 @@ pub async fn login(
 				$10,
 				$11,
 				$12,
-				$13
+				$13,
+
+				$5
 			);
 		"#,
 		login_id as _,
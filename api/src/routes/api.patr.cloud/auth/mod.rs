@@ -12,6 +12,7 @@ mod login;
 mod logout;
 #[expect(unused_variables)]
 mod oauth;
+mod oidc;
 mod renew_access_token;
 mod resend_otp;
 mod reset_password;
@@ -35,6 +36,7 @@ use self::{
 pub async fn setup_routes(state: &AppState) -> Router {
 	Router::new()
 		.merge(oauth::setup_routes(state).await)
+		.merge(oidc::setup_routes(state).await)
 		.mount_endpoint(login, state)
 		.mount_auth_endpoint(logout, state)
 		.mount_endpoint(create_account, state)
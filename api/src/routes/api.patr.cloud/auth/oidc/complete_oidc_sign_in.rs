@@ -0,0 +1,496 @@
+use std::ops::Add;
+
+use argon2::{password_hash::SaltString, Algorithm, PasswordHasher, Version};
+use axum::http::StatusCode;
+use jsonwebtoken::EncodingKey;
+use models::{api::auth::oidc::*, utils::GeoLocation};
+use rand::Rng;
+use rustis::commands::StringCommands;
+use sqlx::types::ipnetwork::IpNetwork;
+use time::OffsetDateTime;
+
+use super::OidcIdTokenClaims;
+use crate::{
+	geo_ip::{self, IpLocation},
+	models::{access_token_data::AccessTokenData, redis::OidcAuthorizationState},
+	prelude::*,
+	utils::config::AppConfig,
+};
+
+/// The handler to complete signing in with an external OpenID Connect provider.
+/// The authorization code is exchanged for an ID token, which is validated and
+/// used to find the user to sign in. The user is found in the following order:
+/// - A user that has already been linked to the identity on the provider
+/// - A user with the same email as the verified email of the identity, which is
+///   then linked to the identity
+/// - A new user, if the provider allows sign ups
+///
+/// Once the user is found, a web login is created and the access token and
+/// refresh token are returned, the same as a regular login. Multi-factor
+/// authentication is left to the provider.
+pub async fn complete_oidc_sign_in(
+	AppRequest {
+		request:
+			ProcessedApiRequest {
+				path: CompleteOidcSignInPath { provider_id },
+				query: (),
+				headers: CompleteOidcSignInRequestHeaders { user_agent },
+				body: CompleteOidcSignInRequestProcessed { code, state },
+			},
+		database,
+		redis,
+		client_ip,
+		config,
+	}: AppRequest<'_, CompleteOidcSignInRequest>,
+) -> Result<AppResponse<CompleteOidcSignInRequest>, ErrorType> {
+	info!("Completing sign in with OIDC provider `{provider_id}`");
+
+	let provider = config
+		.oidc
+		.get(&provider_id)
+		.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	// The state is read and deleted in one command, so that it can only be
+	// used once even when the callback is called twice at the same time
+	let authorization_state = redis
+		.getdel::<_, Option<String>>(redis::keys::oidc_authorization_state(&state))
+		.await?
+		.ok_or(ErrorType::InvalidOidcState)?;
+
+	let OidcAuthorizationState {
+		provider_id: state_provider_id,
+		nonce,
+		code_verifier,
+	} = serde_json::from_str(&authorization_state)?;
+
+	if state_provider_id != provider_id {
+		warn!(
+			"OIDC state was created for provider `{}`, but used for `{}`",
+			state_provider_id, provider_id
+		);
+		return Err(ErrorType::InvalidOidcState);
+	}
+
+	trace!("OIDC state is valid");
+
+	let claims =
+		super::exchange_authorization_code(provider, &code, &code_verifier, &nonce).await?;
+
+	trace!("ID token is valid for subject `{}`", claims.sub);
+
+	let now = OffsetDateTime::now_utc();
+
+	let linked_user_id: Option<Uuid> = query!(
+		r#"
+		SELECT
+			user_id
+		FROM
+			user_oidc_identity
+		WHERE
+			issuer = $1 AND
+			subject = $2;
+		"#,
+		&provider.issuer_url,
+		&claims.sub,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.map(|row| row.user_id.into());
+
+	let user_id = if let Some(user_id) = linked_user_id {
+		trace!("Found user `{user_id}` linked to the identity");
+		user_id
+	} else {
+		let email = claims
+			.email
+			.as_deref()
+			.filter(|_| claims.email_verified == Some(true))
+			.map(str::to_lowercase)
+			.ok_or(ErrorType::OidcEmailNotVerified)?;
+
+		let existing_user_id: Option<Uuid> = query!(
+			r#"
+			SELECT
+				user_id
+			FROM
+				user_email
+			WHERE
+				email = $1;
+			"#,
+			&email,
+		)
+		.fetch_optional(&mut **database)
+		.await?
+		.map(|row| row.user_id.into());
+
+		let user_id = if let Some(user_id) = existing_user_id {
+			trace!("Found user `{user_id}` with the same verified email");
+			user_id
+		} else if provider.allow_sign_up {
+			create_user(&mut **database, &config, &claims, &email, now).await?
+		} else {
+			debug!("No user found for the identity, and sign ups are not allowed");
+			return Err(ErrorType::UserNotFound);
+		};
+
+		query!(
+			r#"
+			INSERT INTO
+				user_oidc_identity(
+					issuer,
+					subject,
+					user_id,
+					provider_id,
+					created
+				)
+			VALUES
+				($1, $2, $3, $4, $5);
+			"#,
+			&provider.issuer_url,
+			&claims.sub,
+			user_id as _,
+			&provider_id,
+			now,
+		)
+		.execute(&mut **database)
+		.await?;
+
+		trace!("Linked identity to user `{user_id}`");
+
+		user_id
+	};
+
+	let refresh_token = Uuid::new_v4();
+	let hashed_refresh_token = argon2::Argon2::new_with_secret(
+		config.password_pepper.as_ref(),
+		Algorithm::Argon2id,
+		Version::V0x13,
+		constants::HASHING_PARAMS,
+	)
+	.inspect_err(|err| {
+		error!("Error creating Argon2: `{}`", err);
+	})
+	.map_err(ErrorType::server_error)?
+	.hash_password(
+		refresh_token.as_bytes(),
+		SaltString::generate(&mut rand::thread_rng()).as_salt(),
+	)
+	.inspect_err(|err| {
+		error!("Error hashing refresh token: `{}`", err);
+	})
+	.map_err(ErrorType::server_error)?
+	.to_string();
+	let refresh_token_expiry = now.add(constants::INACTIVE_REFRESH_TOKEN_VALIDITY);
+
+	let IpLocation {
+		location: GeoLocation {
+			latitude: lat,
+			longitude: lng,
+		},
+		country,
+		region,
+		city,
+		timezone,
+	} = geo_ip::lookup(&config.geo_ip, client_ip)
+		.await
+		.unwrap_or_else(|| {
+			info!("Unable to find the location of IP address: `{}`", client_ip);
+			IpLocation::default()
+		});
+
+	let client_ip = IpNetwork::from(client_ip);
+
+	let user_agent = user_agent.to_string();
+
+	let login_id = query!(
+		r#"
+		INSERT INTO
+			user_login(
+				login_id,
+				user_id,
+				login_type,
+				created
+			)
+		VALUES
+			(
+				GENERATE_LOGIN_ID(),
+				$1,
+				'web_login',
+				$2
+			)
+		RETURNING login_id;
+		"#,
+		user_id as _,
+		now,
+	)
+	.fetch_one(&mut **database)
+	.await?
+	.login_id
+	.into();
+
+	query!(
+		r#"
+		INSERT INTO
+			web_login(
+				login_id,
+				original_login_id,
+				user_id,
+
+				refresh_token,
+				token_expiry,
+
+				created,
+				created_ip,
+				created_location,
+				created_user_agent,
+				created_country,
+				created_region,
+				created_city,
+				created_timezone,
+
+				last_activity,
+				sso_provider_id
+			)
+		VALUES
+			(
+				$1,
+				NULL,
+				$2,
+
+				$3,
+				$4,
+
+				$5,
+				$6,
+				ST_SetSRID(POINT($7, $8)::GEOMETRY, 4326),
+				$9,
+				$10,
+				$11,
+				$12,
+				$13,
+
+				$5,
+				$14
+			);
+		"#,
+		login_id as _,
+		user_id as _,
+		hashed_refresh_token,
+		refresh_token_expiry,
+		now,
+		client_ip,
+		lat,
+		lng,
+		user_agent,
+		country,
+		region,
+		city,
+		timezone,
+		&provider_id,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	let access_token = AccessTokenData {
+		iss: constants::JWT_ISSUER.to_string(),
+		sub: login_id,
+		aud: OneOrMore::One(constants::PATR_JWT_AUDIENCE.to_string()),
+		exp: now.add(constants::ACCESS_TOKEN_VALIDITY),
+		nbf: now,
+		iat: now,
+		jti: Uuid::now_v1(),
+	};
+
+	let access_token = jsonwebtoken::encode(
+		&Default::default(),
+		&access_token,
+		&EncodingKey::from_secret(config.jwt_secret.as_ref()),
+	)
+	.inspect_err(|err| {
+		error!("Error encoding JWT: `{}`", err);
+	})?;
+
+	let refresh_token = format!("{login_id}.{refresh_token}");
+
+	AppResponse::builder()
+		.body(CompleteOidcSignInResponse {
+			access_token,
+			refresh_token,
+		})
+		.headers(())
+		.status_code(StatusCode::ACCEPTED)
+		.build()
+		.into_result()
+}
+
+/// Creates a new user for an identity on an external OpenID Connect provider.
+/// The username is derived from the preferred username (or the email) of the
+/// identity, and the user is given an unusable password. The user can set a
+/// password later using the forgot password flow, if they want to.
+async fn create_user(
+	connection: &mut DatabaseConnection,
+	config: &AppConfig,
+	claims: &OidcIdTokenClaims,
+	email: &str,
+	now: OffsetDateTime,
+) -> Result<Uuid, ErrorType> {
+	let base_username = claims
+		.preferred_username
+		.as_deref()
+		.unwrap_or_else(|| email.split('@').next().unwrap_or_default())
+		.to_lowercase()
+		.chars()
+		.map(|c| {
+			if c.is_ascii_alphanumeric() || c == '_' {
+				c
+			} else {
+				'_'
+			}
+		})
+		.take(90)
+		.collect::<String>();
+	let base_username = format!("{base_username:_<4}");
+
+	let mut username = base_username.clone();
+	loop {
+		let taken = query!(
+			r#"
+			SELECT
+				EXISTS(
+					SELECT 1 FROM "user" WHERE username = $1
+				) OR EXISTS(
+					SELECT 1 FROM user_to_sign_up WHERE username = $1 AND otp_expiry > NOW()
+				) AS "taken!";
+			"#,
+			&username,
+		)
+		.fetch_one(&mut *connection)
+		.await?
+		.taken;
+
+		if !taken {
+			break;
+		}
+
+		username = format!(
+			"{base_username}_{:04}",
+			rand::thread_rng().gen_range(0..10000)
+		);
+	}
+
+	trace!("Creating user `{username}` for OIDC identity");
+
+	let first_name = claims
+		.given_name
+		.clone()
+		.or_else(|| claims.name.clone())
+		.unwrap_or_else(|| username.clone());
+	let last_name = claims.family_name.clone().unwrap_or_default();
+
+	let password = argon2::Argon2::new_with_secret(
+		config.password_pepper.as_ref(),
+		Algorithm::Argon2id,
+		Version::V0x13,
+		constants::HASHING_PARAMS,
+	)
+	.inspect_err(|err| {
+		error!("Error creating Argon2: `{}`", err);
+	})
+	.map_err(ErrorType::server_error)?
+	.hash_password(
+		Uuid::new_v4().as_bytes(),
+		SaltString::generate(&mut rand::thread_rng()).as_salt(),
+	)
+	.inspect_err(|err| {
+		error!("Error hashing password: `{}`", err);
+	})
+	.map_err(ErrorType::server_error)?
+	.to_string();
+
+	let user_id = Uuid::new_v4();
+
+	query!(
+		r#"
+		SET CONSTRAINTS ALL DEFERRED;
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		INSERT INTO
+			"user"(
+				id,
+				username,
+				password,
+				first_name,
+				last_name,
+				created,
+				recovery_email,
+				recovery_phone_country_code,
+				recovery_phone_number,
+				workspace_limit,
+				password_reset_token,
+				password_reset_token_expiry,
+				password_reset_attempts,
+				mfa_secret
+			)
+		VALUES
+			(
+				$1,
+				$2,
+				$3,
+				$4,
+				$5,
+				$6,
+				$7,
+				NULL,
+				NULL,
+				$8,
+				NULL,
+				NULL,
+				NULL,
+				NULL
+			);
+		"#,
+		user_id as _,
+		&username,
+		password,
+		first_name.chars().take(100).collect::<String>(),
+		last_name.chars().take(100).collect::<String>(),
+		now,
+		email,
+		constants::DEFAULT_WORKSPACE_LIMIT,
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		INSERT INTO
+			user_email(
+				user_id,
+				email
+			)
+		VALUES
+			($1, $2);
+		"#,
+		user_id as _,
+		email,
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		SET CONSTRAINTS ALL IMMEDIATE;
+		"#
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	trace!("User `{user_id}` created");
+
+	Ok(user_id)
+}
@@ -0,0 +1,40 @@
+use axum::http::StatusCode;
+use models::api::auth::oidc::*;
+
+use crate::prelude::*;
+
+/// The handler to list all the external OpenID Connect providers that are
+/// configured, so that the frontend can show a sign in button for each of them.
+pub async fn list_oidc_providers(
+	AppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ListOidcProvidersPath,
+				query: (),
+				headers: ListOidcProvidersRequestHeaders { user_agent: _ },
+				body: ListOidcProvidersRequestProcessed,
+			},
+		database: _,
+		redis: _,
+		client_ip: _,
+		config,
+	}: AppRequest<'_, ListOidcProvidersRequest>,
+) -> Result<AppResponse<ListOidcProvidersRequest>, ErrorType> {
+	info!("Listing all OIDC providers");
+
+	let providers = config
+		.oidc
+		.into_iter()
+		.map(|(id, provider)| OidcProvider {
+			id,
+			name: provider.name,
+		})
+		.collect();
+
+	AppResponse::builder()
+		.body(ListOidcProvidersResponse { providers })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
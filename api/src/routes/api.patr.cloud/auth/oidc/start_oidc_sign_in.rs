@@ -0,0 +1,92 @@
+use axum::http::StatusCode;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use models::api::auth::oidc::*;
+use rand::Rng;
+use rustis::commands::StringCommands;
+use sha2::{Digest, Sha256};
+
+use crate::{models::redis::OidcAuthorizationState, prelude::*};
+
+/// The handler to start signing in with an external OpenID Connect provider.
+/// This generates the state, nonce and PKCE code verifier for the sign in,
+/// stores them in Redis, and returns the URL of the provider that the user
+/// should be redirected to.
+pub async fn start_oidc_sign_in(
+	AppRequest {
+		request:
+			ProcessedApiRequest {
+				path: StartOidcSignInPath { provider_id },
+				query: (),
+				headers: StartOidcSignInRequestHeaders { user_agent: _ },
+				body: StartOidcSignInRequestProcessed,
+			},
+		database: _,
+		redis,
+		client_ip: _,
+		config,
+	}: AppRequest<'_, StartOidcSignInRequest>,
+) -> Result<AppResponse<StartOidcSignInRequest>, ErrorType> {
+	info!("Starting sign in with OIDC provider `{provider_id}`");
+
+	let provider = config
+		.oidc
+		.get(&provider_id)
+		.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	let discovery = super::get_discovery_document(provider).await?;
+
+	trace!("Fetched discovery document for OIDC provider `{provider_id}`");
+
+	let state = URL_SAFE_NO_PAD.encode(rand::thread_rng().gen::<[u8; 32]>());
+	let nonce = URL_SAFE_NO_PAD.encode(rand::thread_rng().gen::<[u8; 32]>());
+	let code_verifier = URL_SAFE_NO_PAD.encode(rand::thread_rng().gen::<[u8; 32]>());
+	let code_challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()));
+
+	redis
+		.setex(
+			redis::keys::oidc_authorization_state(&state),
+			constants::OIDC_AUTHORIZATION_STATE_VALIDITY.whole_seconds() as u64,
+			serde_json::to_string(&OidcAuthorizationState {
+				provider_id: provider_id.clone(),
+				nonce: nonce.clone(),
+				code_verifier,
+			})?,
+		)
+		.await
+		.inspect_err(|err| {
+			error!("Error setting the OIDC authorization state: `{}`", err);
+		})?;
+
+	let scope = std::iter::once("openid")
+		.chain(
+			provider
+				.scopes
+				.iter()
+				.map(String::as_str)
+				.filter(|scope| *scope != "openid"),
+		)
+		.collect::<Vec<_>>()
+		.join(" ");
+
+	let authorization_url = reqwest::Url::parse_with_params(
+		&discovery.authorization_endpoint,
+		&[
+			("response_type", "code"),
+			("client_id", provider.client_id.as_str()),
+			("redirect_uri", provider.redirect_url.as_str()),
+			("scope", scope.as_str()),
+			("state", state.as_str()),
+			("nonce", nonce.as_str()),
+			("code_challenge", code_challenge.as_str()),
+			("code_challenge_method", "S256"),
+		],
+	)?
+	.to_string();
+
+	AppResponse::builder()
+		.body(StartOidcSignInResponse { authorization_url })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
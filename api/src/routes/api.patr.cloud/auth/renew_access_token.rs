@@ -92,6 +92,21 @@ pub async fn renew_access_token(
 		return Err(ErrorType::MalformedRefreshToken);
 	}
 
+	query!(
+		r#"
+		UPDATE
+			web_login
+		SET
+			last_activity = $2
+		WHERE
+			login_id = $1;
+		"#,
+		login_id as _,
+		now,
+	)
+	.execute(&mut **database)
+	.await?;
+
 	let access_token = AccessTokenData {
 		iss: constants::JWT_ISSUER.to_string(),
 		sub: login_id,
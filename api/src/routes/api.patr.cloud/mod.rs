@@ -1,4 +1,5 @@
 mod auth;
+mod openapi;
 mod user;
 mod workspace;
 
@@ -14,4 +15,5 @@ pub async fn setup_routes(state: &AppState) -> Router {
 		.merge(auth::setup_routes(state).await)
 		.merge(user::setup_routes(state).await)
 		.merge(workspace::setup_routes(state).await)
+		.merge(openapi::setup_routes(state).await)
 }
@@ -0,0 +1,45 @@
+use axum::{response::Html, routing::get, Json, Router};
+use serde_json::Value;
+
+use crate::prelude::*;
+
+/// The page that renders the OpenAPI document as an API reference, using
+/// Scalar. The version of the script is pinned, so that the page does not
+/// change when a new version of Scalar is published
+const API_REFERENCE_PAGE: &str = r#"<!doctype html>
+<html>
+	<head>
+		<title>Patr API Reference</title>
+		<meta charset="utf-8" />
+		<meta name="viewport" content="width=device-width, initial-scale=1" />
+	</head>
+	<body>
+		<script id="api-reference" data-url="/openapi.json"></script>
+		<script
+			src="https://cdn.jsdelivr.net/npm/@scalar/api-reference@1.25.0/dist/browser/standalone.js"
+			crossorigin="anonymous"
+		></script>
+	</body>
+</html>
+"#;
+
+/// Sets up the routes for the OpenAPI document of the API and the API
+/// reference generated from it
+#[instrument(skip(state))]
+pub async fn setup_routes(state: &AppState) -> Router {
+	Router::new()
+		.route("/openapi.json", get(get_openapi_document))
+		.route("/docs", get(get_api_reference))
+		.with_state(state.clone())
+}
+
+/// The handler to get the OpenAPI document of all the endpoints that can be
+/// accessed through the API
+async fn get_openapi_document() -> Json<&'static Value> {
+	Json(crate::openapi::document())
+}
+
+/// The handler to get the API reference page
+async fn get_api_reference() -> Html<&'static str> {
+	Html(API_REFERENCE_PAGE)
+}
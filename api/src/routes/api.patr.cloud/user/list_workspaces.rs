@@ -30,13 +30,16 @@ pub async fn list_workspaces(
 		SELECT DISTINCT
 			workspace.id,
 			workspace.name::TEXT AS "name!",
-			workspace.super_admin_id
+			workspace.super_admin_id,
+			workspace.sso_provider_id
 		FROM
 			workspace
 		LEFT JOIN
 			workspace_user
 		ON
-			workspace.id = workspace_user.workspace_id
+			workspace.id = workspace_user.workspace_id AND
+			(workspace_user.valid_from IS NULL OR workspace_user.valid_from <= NOW()) AND
+			(workspace_user.valid_until IS NULL OR workspace_user.valid_until > NOW())
 		WHERE
 			(
 				workspace.super_admin_id = $1 OR
@@ -55,6 +58,7 @@ pub async fn list_workspaces(
 			Workspace {
 				name: row.name,
 				super_admin_id: row.super_admin_id.into(),
+				sso_provider_id: row.sso_provider_id,
 			},
 		)
 	})
@@ -11,7 +11,6 @@ mod mfa;
 #[allow(unreachable_code, unused_variables)]
 mod recovery_options;
 mod update_user_info;
-#[allow(unreachable_code, unused_variables)]
 mod web_logins;
 
 pub use self::{
@@ -0,0 +1,87 @@
+use axum::http::StatusCode;
+use models::api::user::*;
+use rustis::commands::StringCommands;
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// The handler to delete all the web logins of the current user, except the
+/// one making the request. This is used to "sign out everywhere else". The
+/// cached permissions of all the logins of the user are revoked, and will be
+/// refetched for the current login on the next request.
+pub async fn delete_other_web_logins(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: DeleteOtherWebLoginsPath,
+				query: (),
+				headers:
+					DeleteOtherWebLoginsRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: DeleteOtherWebLoginsRequestProcessed,
+			},
+		database,
+		redis,
+		client_ip: _,
+		user_data,
+		config: _,
+	}: AuthenticatedAppRequest<'_, DeleteOtherWebLoginsRequest>,
+) -> Result<AppResponse<DeleteOtherWebLoginsRequest>, ErrorType> {
+	info!(
+		"Deleting all web logins of user `{}` except `{}`",
+		user_data.id, user_data.login_id
+	);
+
+	query!(
+		r#"
+		DELETE FROM
+			web_login
+		WHERE
+			user_id = $1 AND
+			login_id != $2;
+		"#,
+		user_data.id as _,
+		user_data.login_id as _,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	trace!("Deleted web logins");
+
+	query!(
+		r#"
+		DELETE FROM
+			user_login
+		WHERE
+			user_id = $1 AND
+			login_id != $2 AND
+			login_type = 'web_login';
+		"#,
+		user_data.id as _,
+		user_data.login_id as _,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	trace!("Deleted user logins");
+
+	redis
+		.setex(
+			redis::keys::user_id_revocation_timestamp(&user_data.id),
+			constants::CACHED_PERMISSIONS_VALIDITY.whole_seconds() as u64 + 100,
+			OffsetDateTime::now_utc().unix_timestamp(),
+		)
+		.await
+		.inspect_err(|err| {
+			error!("Error setting the revocation timestamp: `{}`", err);
+		})?;
+
+	AppResponse::builder()
+		.body(DeleteOtherWebLoginsResponse)
+		.headers(())
+		.status_code(StatusCode::RESET_CONTENT)
+		.build()
+		.into_result()
+}
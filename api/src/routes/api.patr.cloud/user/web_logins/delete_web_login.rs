@@ -1,21 +1,91 @@
+use axum::http::StatusCode;
 use models::api::user::*;
+use rustis::commands::{GenericCommands, StringCommands};
+use time::OffsetDateTime;
 
 use crate::prelude::*;
 
+/// The handler to delete a web login of the current user. This will log the
+/// login out immediately, and revoke any cached permissions for the login.
 pub async fn delete_web_login(
 	AuthenticatedAppRequest {
-		request: ProcessedApiRequest {
-			path,
-			query,
-			headers,
-			body,
-		},
+		request:
+			ProcessedApiRequest {
+				path: DeleteWebLoginPath { login_id },
+				query: (),
+				headers:
+					DeleteWebLoginRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: DeleteWebLoginRequestProcessed,
+			},
 		database,
 		redis,
-		client_ip,
+		client_ip: _,
 		user_data,
-		config,
+		config: _,
 	}: AuthenticatedAppRequest<'_, DeleteWebLoginRequest>,
 ) -> Result<AppResponse<DeleteWebLoginRequest>, ErrorType> {
-	todo!()
+	info!("Deleting web login: {}", login_id);
+
+	query!(
+		r#"
+		DELETE FROM
+			web_login
+		WHERE
+			login_id = $1 AND
+			user_id = $2
+		RETURNING
+			login_id;
+		"#,
+		login_id as _,
+		user_data.id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	trace!("Deleted web login");
+
+	query!(
+		r#"
+		DELETE FROM
+			user_login
+		WHERE
+			login_id = $1;
+		"#,
+		login_id as _,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	trace!("Deleted user login");
+
+	_ = redis
+		.del(redis::keys::permission_for_login_id(&login_id))
+		.await
+		.inspect_err(|err| {
+			error!(
+				"Error deleting the cached permission for login `{}`: `{}`",
+				login_id, err
+			);
+		});
+	redis
+		.setex(
+			redis::keys::login_id_revocation_timestamp(&login_id),
+			constants::CACHED_PERMISSIONS_VALIDITY.whole_seconds() as u64 + 100,
+			OffsetDateTime::now_utc().unix_timestamp(),
+		)
+		.await
+		.inspect_err(|err| {
+			error!("Error setting the revocation timestamp: `{}`", err);
+		})?;
+
+	AppResponse::builder()
+		.body(DeleteWebLoginResponse)
+		.headers(())
+		.status_code(StatusCode::RESET_CONTENT)
+		.build()
+		.into_result()
 }
@@ -1,21 +1,87 @@
-use models::api::user::*;
+use axum::http::StatusCode;
+use models::{api::user::*, utils::GeoLocation};
 
 use crate::prelude::*;
 
+/// The handler to get the details of a web login of the current user.
 pub async fn get_web_login_info(
 	AuthenticatedAppRequest {
-		request: ProcessedApiRequest {
-			path,
-			query,
-			headers,
-			body,
-		},
+		request:
+			ProcessedApiRequest {
+				path: GetWebLoginInfoPath { login_id },
+				query: (),
+				headers:
+					GetWebLoginInfoRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: GetWebLoginInfoRequestProcessed,
+			},
 		database,
-		redis,
-		client_ip,
+		redis: _,
+		client_ip: _,
 		user_data,
-		config,
+		config: _,
 	}: AuthenticatedAppRequest<'_, GetWebLoginInfoRequest>,
 ) -> Result<AppResponse<GetWebLoginInfoRequest>, ErrorType> {
-	todo!()
+	trace!("Getting web login info for loginId: {}", login_id);
+
+	let login = query!(
+		r#"
+		SELECT
+			login_id,
+			token_expiry,
+			created,
+			created_ip,
+			ST_X(created_location) AS "latitude!",
+			ST_Y(created_location) AS "longitude!",
+			created_user_agent,
+			created_country,
+			created_region,
+			created_city,
+			created_timezone,
+			last_activity
+		FROM
+			web_login
+		WHERE
+			login_id = $1 AND
+			user_id = $2 AND
+			token_expiry > NOW();
+		"#,
+		login_id as _,
+		user_data.id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.map(|row| {
+		let (created_browser, created_os) = super::parse_user_agent(&row.created_user_agent);
+		WithId::new(
+			row.login_id,
+			UserWebLogin {
+				token_expiry: row.token_expiry,
+				created: row.created,
+				created_ip: row.created_ip.ip(),
+				created_location: GeoLocation {
+					latitude: row.latitude,
+					longitude: row.longitude,
+				},
+				created_user_agent: row.created_user_agent,
+				created_browser,
+				created_os,
+				created_country: row.created_country,
+				created_region: row.created_region,
+				created_city: row.created_city,
+				created_timezone: row.created_timezone,
+				last_activity: row.last_activity,
+			},
+		)
+	})
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	AppResponse::builder()
+		.body(GetWebLoginInfoResponse { login })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
 }
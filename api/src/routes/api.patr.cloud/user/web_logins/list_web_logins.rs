@@ -1,21 +1,106 @@
-use models::api::user::*;
+use axum::http::StatusCode;
+use models::{
+	api::user::*,
+	utils::{GeoLocation, TotalCountHeader},
+};
 
 use crate::prelude::*;
 
+/// The handler to list all the web logins of the current user. This only lists
+/// the logins that have not expired yet, with the most recently active logins
+/// first.
 pub async fn list_web_logins(
 	AuthenticatedAppRequest {
-		request: ProcessedApiRequest {
-			path,
-			query,
-			headers,
-			body,
-		},
+		request:
+			ProcessedApiRequest {
+				path: ListWebLoginsPath,
+				query: Paginated {
+					data: (),
+					count,
+					page,
+				},
+				headers:
+					ListWebLoginsRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ListWebLoginsRequestProcessed,
+			},
 		database,
-		redis,
-		client_ip,
+		redis: _,
+		client_ip: _,
 		user_data,
-		config,
+		config: _,
 	}: AuthenticatedAppRequest<'_, ListWebLoginsRequest>,
 ) -> Result<AppResponse<ListWebLoginsRequest>, ErrorType> {
-	todo!()
+	trace!("Listing web logins for user: {}", user_data.id);
+
+	let mut total_count = 0;
+	let logins = query!(
+		r#"
+		SELECT
+			login_id,
+			token_expiry,
+			created,
+			created_ip,
+			ST_X(created_location) AS "latitude!",
+			ST_Y(created_location) AS "longitude!",
+			created_user_agent,
+			created_country,
+			created_region,
+			created_city,
+			created_timezone,
+			last_activity,
+			COUNT(*) OVER() AS "total_count!"
+		FROM
+			web_login
+		WHERE
+			user_id = $1 AND
+			token_expiry > NOW()
+		ORDER BY
+			last_activity DESC
+		LIMIT $2
+		OFFSET $3;
+		"#,
+		user_data.id as _,
+		count as i32,
+		(count * page) as i32,
+	)
+	.fetch_all(&mut **database)
+	.await?
+	.into_iter()
+	.map(|row| {
+		total_count = row.total_count;
+		let (created_browser, created_os) = super::parse_user_agent(&row.created_user_agent);
+		WithId::new(
+			row.login_id,
+			UserWebLogin {
+				token_expiry: row.token_expiry,
+				created: row.created,
+				created_ip: row.created_ip.ip(),
+				created_location: GeoLocation {
+					latitude: row.latitude,
+					longitude: row.longitude,
+				},
+				created_user_agent: row.created_user_agent,
+				created_browser,
+				created_os,
+				created_country: row.created_country,
+				created_region: row.created_region,
+				created_city: row.created_city,
+				created_timezone: row.created_timezone,
+				last_activity: row.last_activity,
+			},
+		)
+	})
+	.collect();
+
+	AppResponse::builder()
+		.body(ListWebLoginsResponse { logins })
+		.headers(ListWebLoginsResponseHeaders {
+			total_count: TotalCountHeader(total_count as _),
+		})
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
 }
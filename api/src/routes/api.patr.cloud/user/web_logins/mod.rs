@@ -1,17 +1,46 @@
+use axum::Router;
+use woothee::parser::Parser;
+
+use crate::prelude::*;
+
+mod delete_other_web_logins;
 mod delete_web_login;
 mod get_web_login_info;
 mod list_web_logins;
 
-use axum::Router;
-
-pub use self::{delete_web_login::*, get_web_login_info::*, list_web_logins::*};
-use crate::prelude::*;
+pub use self::{
+	delete_other_web_logins::*,
+	delete_web_login::*,
+	get_web_login_info::*,
+	list_web_logins::*,
+};
 
 /// Sets up the web logins routes
 #[instrument(skip(state))]
 pub async fn setup_routes(state: &AppState) -> Router {
 	Router::new()
+		.mount_auth_endpoint(delete_other_web_logins, state)
 		.mount_auth_endpoint(delete_web_login, state)
 		.mount_auth_endpoint(get_web_login_info, state)
 		.mount_auth_endpoint(list_web_logins, state)
 }
+
+/// Parses the user-agent of a web login and returns the name and version of
+/// the browser and the operating system, in that order. If the user-agent
+/// cannot be parsed, `Unknown` is returned for the respective values.
+fn parse_user_agent(user_agent: &str) -> (String, String) {
+	let Some(result) = Parser::new().parse(user_agent) else {
+		return (String::from("Unknown"), String::from("Unknown"));
+	};
+
+	let format = |name: &str, version: &str| match (name, version) {
+		("UNKNOWN", _) => String::from("Unknown"),
+		(name, "UNKNOWN" | "") => name.to_string(),
+		(name, version) => format!("{name} {version}"),
+	};
+
+	(
+		format(result.name, result.version),
+		format(result.os, &result.os_version),
+	)
+}
@@ -0,0 +1,51 @@
+use axum::http::StatusCode;
+use models::api::workspace::billing::*;
+
+use crate::prelude::*;
+
+/// The handler to get the billing statement of a workspace for a month. The
+/// usage recorded within the month is priced with the billing plan that the
+/// workspace was on when the month ended, or its current plan for the current
+/// month.
+pub async fn get_billing_statement(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: GetBillingStatementPath {
+					workspace_id,
+					year,
+					month,
+				},
+				query: (),
+				headers:
+					GetBillingStatementRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: GetBillingStatementRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, GetBillingStatementRequest>,
+) -> Result<AppResponse<GetBillingStatementRequest>, ErrorType> {
+	info!("Getting billing statement of workspace `{workspace_id}` for {year}-{month:02}");
+
+	let statement = super::get_billing_statement_for_month(
+		&mut **database,
+		&config.billing,
+		&workspace_id,
+		year,
+		month,
+	)
+	.await?;
+
+	AppResponse::builder()
+		.body(GetBillingStatementResponse { statement })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
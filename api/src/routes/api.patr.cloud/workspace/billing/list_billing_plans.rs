@@ -0,0 +1,42 @@
+use axum::http::StatusCode;
+use models::api::workspace::billing::*;
+
+use crate::prelude::*;
+
+/// The handler to list all the billing plans that a workspace can be on, along
+/// with the current billing plan of the workspace.
+pub async fn list_billing_plans(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ListBillingPlansPath { workspace_id },
+				query: (),
+				headers:
+					ListBillingPlansRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ListBillingPlansRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ListBillingPlansRequest>,
+) -> Result<AppResponse<ListBillingPlansRequest>, ErrorType> {
+	info!("Listing billing plans for workspace `{workspace_id}`");
+
+	let (current_plan_id, _) =
+		super::get_workspace_billing_plan(&mut **database, &config.billing, &workspace_id).await?;
+
+	AppResponse::builder()
+		.body(ListBillingPlansResponse {
+			current_plan_id,
+			plans: config.billing.plans,
+		})
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
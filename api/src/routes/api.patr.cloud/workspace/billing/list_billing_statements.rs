@@ -0,0 +1,74 @@
+use axum::http::StatusCode;
+use models::api::workspace::billing::*;
+
+use crate::prelude::*;
+
+/// The handler to list the billing statements of a workspace, for all the
+/// months that the workspace has any usage recorded in, latest first.
+pub async fn list_billing_statements(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ListBillingStatementsPath { workspace_id },
+				query: (),
+				headers:
+					ListBillingStatementsRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ListBillingStatementsRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ListBillingStatementsRequest>,
+) -> Result<AppResponse<ListBillingStatementsRequest>, ErrorType> {
+	info!("Listing billing statements of workspace `{workspace_id}`");
+
+	let months = query!(
+		r#"
+		SELECT
+			DATE_TRUNC('month', hour, 'UTC') AS "month!"
+		FROM
+			workspace_usage
+		WHERE
+			workspace_id = $1
+		GROUP BY
+			DATE_TRUNC('month', hour, 'UTC')
+		ORDER BY
+			DATE_TRUNC('month', hour, 'UTC') DESC;
+		"#,
+		workspace_id as _
+	)
+	.fetch_all(&mut **database)
+	.await?;
+
+	let mut statements = Vec::with_capacity(months.len());
+	for row in months {
+		let year = row.month.year() as u16;
+		let month = row.month.month().into();
+		let statement = super::get_billing_statement_for_month(
+			&mut **database,
+			&config.billing,
+			&workspace_id,
+			year,
+			month,
+		)
+		.await?;
+
+		statements.push(BillingStatementSummary {
+			year,
+			month,
+			total: statement.total,
+		});
+	}
+
+	AppResponse::builder()
+		.body(ListBillingStatementsResponse { statements })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
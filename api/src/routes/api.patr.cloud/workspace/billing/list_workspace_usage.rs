@@ -0,0 +1,98 @@
+use axum::http::StatusCode;
+use models::{api::workspace::billing::*, utils::TotalCountHeader};
+
+use crate::prelude::*;
+
+/// The handler to list the hourly usage records of a workspace, latest first.
+/// The usage records can be filtered by the resource, the type of usage and
+/// the hour that they were recorded for.
+pub async fn list_workspace_usage(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ListWorkspaceUsagePath { workspace_id },
+				query:
+					Paginated {
+						data:
+							ListWorkspaceUsageQuery {
+								resource_id,
+								usage_type,
+								from,
+								to,
+							},
+						count,
+						page,
+					},
+				headers:
+					ListWorkspaceUsageRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ListWorkspaceUsageRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ListWorkspaceUsageRequest>,
+) -> Result<AppResponse<ListWorkspaceUsageRequest>, ErrorType> {
+	info!("Listing usage of workspace `{workspace_id}`");
+
+	let mut total_count = 0;
+	let usage = query!(
+		r#"
+		SELECT
+			resource_id,
+			usage_type AS "usage_type: WorkspaceUsageType",
+			hour,
+			machine_type,
+			quantity,
+			COUNT(*) OVER() AS "total_count!"
+		FROM
+			workspace_usage
+		WHERE
+			workspace_id = $1 AND
+			($4::UUID IS NULL OR resource_id = $4) AND
+			($5::USAGE_TYPE IS NULL OR usage_type = $5) AND
+			($6::TIMESTAMPTZ IS NULL OR hour >= $6) AND
+			($7::TIMESTAMPTZ IS NULL OR hour < $7)
+		ORDER BY
+			hour DESC,
+			usage_type,
+			resource_id
+		LIMIT $2
+		OFFSET $3;
+		"#,
+		workspace_id as _,
+		count as i64,
+		(count * page) as i64,
+		resource_id as _,
+		usage_type as _,
+		from,
+		to,
+	)
+	.fetch_all(&mut **database)
+	.await?
+	.into_iter()
+	.map(|row| {
+		total_count = row.total_count;
+		WorkspaceUsage {
+			resource_id: row.resource_id.into(),
+			usage_type: row.usage_type,
+			hour: row.hour,
+			machine_type: row.machine_type.map(Into::into),
+			quantity: row.quantity.max(0) as u64,
+		}
+	})
+	.collect();
+
+	AppResponse::builder()
+		.body(ListWorkspaceUsageResponse { usage })
+		.headers(ListWorkspaceUsageResponseHeaders {
+			total_count: TotalCountHeader(total_count as _),
+		})
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,210 @@
+use axum::Router;
+use models::api::workspace::billing::*;
+use sqlx::types::Json;
+use time::{Date, Month, Time};
+
+use crate::{prelude::*, utils::config::BillingConfig};
+
+/// The handler to get the billing statement of a workspace for a month
+mod get_billing_statement;
+/// The handler to list the billing plans, along with the current plan of the
+/// workspace
+mod list_billing_plans;
+/// The handler to list the billing statements of all the months a workspace
+/// has any usage in
+mod list_billing_statements;
+/// The handler to list the hourly usage records of a workspace
+mod list_workspace_usage;
+/// The handler to change the billing plan of a workspace
+mod update_billing_plan;
+
+use self::{
+	get_billing_statement::*,
+	list_billing_plans::*,
+	list_billing_statements::*,
+	list_workspace_usage::*,
+	update_billing_plan::*,
+};
+
+#[instrument(skip(state))]
+pub async fn setup_routes(state: &AppState) -> Router {
+	Router::new()
+		.mount_auth_endpoint(get_billing_statement, state)
+		.mount_auth_endpoint(list_billing_plans, state)
+		.mount_auth_endpoint(list_billing_statements, state)
+		.mount_auth_endpoint(list_workspace_usage, state)
+		.mount_auth_endpoint(update_billing_plan, state)
+}
+
+/// The number of bytes in a GB, used to price the usage that is recorded in
+/// bytes
+const BYTES_PER_GB: f64 = (1024 * 1024 * 1024) as f64;
+
+/// Get the ID of the billing plan of a workspace, along with the plan itself.
+/// Workspaces that have not chosen a plan (or are on a plan that is no longer
+/// configured) are on the default plan.
+async fn get_workspace_billing_plan(
+	connection: &mut DatabaseConnection,
+	billing: &BillingConfig,
+	workspace_id: &Uuid,
+) -> Result<(String, BillingPlan), ErrorType> {
+	let plan_id = query!(
+		r#"
+		SELECT
+			billing_plan_id
+		FROM
+			workspace
+		WHERE
+			id = $1 AND
+			deleted IS NULL;
+		"#,
+		workspace_id as _
+	)
+	.fetch_optional(&mut *connection)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?
+	.billing_plan_id;
+
+	billing.get_plan(plan_id).ok_or_else(|| {
+		ErrorType::server_error(format!(
+			"default billing plan `{}` not found",
+			billing.default_plan
+		))
+	})
+}
+
+/// Calculate the billing statement of a workspace for the given month, by
+/// pricing all the usage recorded within the month. Months that have ended are
+/// priced with the plan that was saved for the workspace when the month ended,
+/// and the current month is priced with the current plan of the workspace.
+/// Each line item is rounded to the nearest cent.
+async fn get_billing_statement_for_month(
+	connection: &mut DatabaseConnection,
+	billing: &BillingConfig,
+	workspace_id: &Uuid,
+	year: u16,
+	month: u8,
+) -> Result<BillingStatement, ErrorType> {
+	let start_month = Month::try_from(month).map_err(|_| ErrorType::WrongParameters)?;
+	let period_start = Date::from_calendar_date(year.into(), start_month, 1)
+		.map_err(|_| ErrorType::WrongParameters)?
+		.with_time(Time::MIDNIGHT)
+		.assume_utc();
+	let (end_year, end_month) = if start_month == Month::December {
+		(i32::from(year) + 1, Month::January)
+	} else {
+		(year.into(), start_month.next())
+	};
+	let period_end = Date::from_calendar_date(end_year, end_month, 1)
+		.map_err(|_| ErrorType::WrongParameters)?
+		.with_time(Time::MIDNIGHT)
+		.assume_utc();
+
+	let saved_plan = query!(
+		r#"
+		SELECT
+			plan_id,
+			plan AS "plan: Json<BillingPlan>"
+		FROM
+			workspace_billing_period
+		WHERE
+			workspace_id = $1 AND
+			period_start = $2;
+		"#,
+		workspace_id as _,
+		period_start,
+	)
+	.fetch_optional(&mut *connection)
+	.await?;
+	let (plan_id, plan) = match saved_plan {
+		Some(saved_plan) => (saved_plan.plan_id, saved_plan.plan.0),
+		None => get_workspace_billing_plan(connection, billing, workspace_id).await?,
+	};
+
+	let hours_per_month = constants::BILLING_HOURS_PER_MONTH as f64;
+	let line_items = query!(
+		r#"
+		SELECT
+			workspace_usage.usage_type AS "usage_type: WorkspaceUsageType",
+			workspace_usage.machine_type,
+			deployment_machine_type.cpu_count AS "cpu_count?",
+			deployment_machine_type.memory_count AS "memory_count?",
+			SUM(workspace_usage.quantity)::BIGINT AS "quantity!"
+		FROM
+			workspace_usage
+		LEFT JOIN
+			deployment_machine_type
+		ON
+			deployment_machine_type.id = workspace_usage.machine_type
+		WHERE
+			workspace_usage.workspace_id = $1 AND
+			workspace_usage.hour >= $2 AND
+			workspace_usage.hour < $3
+		GROUP BY
+			workspace_usage.usage_type,
+			workspace_usage.machine_type,
+			deployment_machine_type.cpu_count,
+			deployment_machine_type.memory_count
+		ORDER BY
+			workspace_usage.usage_type,
+			deployment_machine_type.cpu_count,
+			deployment_machine_type.memory_count;
+		"#,
+		workspace_id as _,
+		period_start,
+		period_end,
+	)
+	.fetch_all(&mut *connection)
+	.await?
+	.into_iter()
+	.map(|row| {
+		let quantity = row.quantity.max(0) as u64;
+		let (description, amount) = match row.usage_type {
+			WorkspaceUsageType::Deployment => {
+				let cpu_count = row.cpu_count.unwrap_or_default();
+				let memory_gb = row.memory_count.unwrap_or_default() as f64 / 4.0;
+				let monthly_price = f64::from(cpu_count) * plan.cpu_monthly_price as f64 +
+					memory_gb * plan.memory_gb_monthly_price as f64;
+				(
+					format!("Deployment replica-hours ({cpu_count} vCPU, {memory_gb} GB memory)"),
+					quantity as f64 * monthly_price / hours_per_month,
+				)
+			}
+			WorkspaceUsageType::Volume => (
+				"Volume storage (GB-hours)".to_string(),
+				quantity as f64 * plan.volume_gb_monthly_price as f64 / hours_per_month,
+			),
+			WorkspaceUsageType::ContainerRegistryStorage => (
+				"Container registry storage (byte-hours)".to_string(),
+				quantity as f64 / BYTES_PER_GB * plan.container_registry_gb_monthly_price as f64 /
+					hours_per_month,
+			),
+			WorkspaceUsageType::StaticSiteBandwidth => (
+				"Static site bandwidth (bytes)".to_string(),
+				quantity as f64 / BYTES_PER_GB * plan.static_site_bandwidth_gb_price as f64,
+			),
+		};
+
+		BillingStatementLineItem {
+			usage_type: row.usage_type,
+			machine_type: row.machine_type.map(Into::into),
+			description,
+			quantity,
+			amount: amount.round() as u64,
+		}
+	})
+	.collect::<Vec<_>>();
+
+	let total = line_items.iter().map(|line_item| line_item.amount).sum();
+
+	Ok(BillingStatement {
+		year,
+		month,
+		period_start,
+		period_end,
+		plan_id,
+		plan,
+		line_items,
+		total,
+	})
+}
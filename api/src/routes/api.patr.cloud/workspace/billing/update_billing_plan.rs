@@ -0,0 +1,58 @@
+use axum::http::StatusCode;
+use models::api::workspace::billing::*;
+
+use crate::prelude::*;
+
+/// The handler to change the billing plan of a workspace. The plan must be one
+/// of the configured billing plans. Statements of months that have ended are
+/// priced with the plan that the workspace was on when the month ended, so this
+/// only changes the price of the statement of the current month.
+pub async fn update_billing_plan(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: UpdateBillingPlanPath { workspace_id },
+				query: (),
+				headers:
+					UpdateBillingPlanRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: UpdateBillingPlanRequestProcessed { plan_id },
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, UpdateBillingPlanRequest>,
+) -> Result<AppResponse<UpdateBillingPlanRequest>, ErrorType> {
+	info!("Changing billing plan of workspace `{workspace_id}` to `{plan_id}`");
+
+	if !config.billing.plans.contains_key(&plan_id) {
+		debug!("Billing plan `{plan_id}` is not configured");
+		return Err(ErrorType::WrongParameters);
+	}
+
+	query!(
+		r#"
+		UPDATE
+			workspace
+		SET
+			billing_plan_id = $1
+		WHERE
+			id = $2;
+		"#,
+		plan_id,
+		workspace_id as _,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	AppResponse::builder()
+		.body(UpdateBillingPlanResponse)
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
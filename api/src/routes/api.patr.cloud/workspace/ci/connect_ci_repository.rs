@@ -0,0 +1,128 @@
+use axum::http::StatusCode;
+use models::{api::workspace::ci::*, prelude::*};
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// The handler to connect a git repository to a workspace. A random secret is
+/// generated for the webhook of the repository, which the provider signs the
+/// webhooks with. The secret is only returned here, and cannot be retrieved
+/// later.
+pub async fn connect_ci_repository(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ConnectCiRepositoryPath { workspace_id },
+				query: (),
+				headers:
+					ConnectCiRepositoryRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body:
+					ConnectCiRepositoryRequestProcessed {
+						provider,
+						api_url,
+						full_name,
+						runner_id,
+						ci_file_path,
+						access_token,
+					},
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ConnectCiRepositoryRequest>,
+) -> Result<AppResponse<ConnectCiRepositoryRequest>, ErrorType> {
+	info!("Connecting repository `{full_name}` to workspace `{workspace_id}`");
+
+	// Self-hosted providers have no default API URL, so one must be given
+	let api_url = api_url
+		.or_else(|| provider.default_api_url().map(str::to_string))
+		.ok_or(ErrorType::WrongParameters)?;
+	if !super::is_valid_api_url(&api_url) {
+		return Err(ErrorType::WrongParameters);
+	}
+
+	super::ensure_runner_in_workspace(&mut **database, &workspace_id, &runner_id).await?;
+
+	let id = query!(
+		r#"
+		INSERT INTO
+			resource(
+				id,
+				resource_type_id,
+				owner_id,
+				created
+			)
+		VALUES
+			(
+				GENERATE_RESOURCE_ID(),
+				(SELECT id FROM resource_type WHERE name = 'ci_repository'),
+				$1,
+				NOW()
+			)
+		RETURNING id;
+		"#,
+		workspace_id as _,
+	)
+	.fetch_one(&mut **database)
+	.await?
+	.id;
+
+	let webhook_secret = rand::random::<[u8; 32]>()
+		.iter()
+		.map(|byte| format!("{byte:02x}"))
+		.collect::<String>();
+
+	query!(
+		r#"
+		INSERT INTO
+			ci_repository(
+				id,
+				workspace_id,
+				provider,
+				api_url,
+				full_name,
+				runner_id,
+				ci_file_path,
+				webhook_secret,
+				access_token,
+				enabled,
+				created,
+				deleted
+			)
+		VALUES
+			($1, $2, $3, $4, $5, $6, $7, $8, $9, TRUE, $10, NULL);
+		"#,
+		id as _,
+		workspace_id as _,
+		provider as _,
+		api_url,
+		full_name,
+		runner_id as _,
+		ci_file_path.unwrap_or_else(|| constants::DEFAULT_CI_FILE_PATH.to_string()),
+		webhook_secret,
+		access_token,
+		OffsetDateTime::now_utc(),
+	)
+	.execute(&mut **database)
+	.await
+	.map_err(|err| match err {
+		sqlx::Error::Database(err) if err.is_unique_violation() => ErrorType::ResourceAlreadyExists,
+		err => err.into(),
+	})?;
+
+	AppResponse::builder()
+		.body(ConnectCiRepositoryResponse {
+			id: WithId::from(id),
+			webhook_url: super::webhook_url(&workspace_id, &id.into()),
+			webhook_secret,
+		})
+		.headers(())
+		.status_code(StatusCode::CREATED)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,82 @@
+use axum::http::StatusCode;
+use models::{api::workspace::ci::*, prelude::*};
+
+use crate::prelude::*;
+
+/// The handler to disconnect a git repository from a workspace. The webhooks
+/// that the provider still sends for the repository are rejected after this,
+/// and the runs of the repository are kept for its history.
+pub async fn disconnect_ci_repository(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: DisconnectCiRepositoryPath {
+					workspace_id,
+					repository_id,
+				},
+				query: (),
+				headers:
+					DisconnectCiRepositoryRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: DisconnectCiRepositoryRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, DisconnectCiRepositoryRequest>,
+) -> Result<AppResponse<DisconnectCiRepositoryRequest>, ErrorType> {
+	info!("Disconnecting CI repository `{repository_id}` from workspace `{workspace_id}`");
+
+	query!(
+		r#"
+		SET CONSTRAINTS ALL DEFERRED;
+		"#
+	)
+	.execute(&mut **database)
+	.await?;
+
+	query!(
+		r#"
+		UPDATE
+			ci_repository
+		SET
+			deleted = NOW()
+		WHERE
+			id = $1 AND
+			workspace_id = $2 AND
+			deleted IS NULL
+		RETURNING
+			id;
+		"#,
+		repository_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	query!(
+		r#"
+		UPDATE
+			resource
+		SET
+			deleted = NOW()
+		WHERE
+			id = $1;
+		"#,
+		repository_id as _,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	AppResponse::builder()
+		.body(DisconnectCiRepositoryResponse)
+		.headers(())
+		.status_code(StatusCode::RESET_CONTENT)
+		.build()
+		.into_result()
+}
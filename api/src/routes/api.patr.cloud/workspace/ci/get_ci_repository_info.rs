@@ -0,0 +1,81 @@
+use axum::http::StatusCode;
+use models::{api::workspace::ci::*, ci::GitProvider, prelude::*};
+
+use crate::prelude::*;
+
+/// The handler to get the details of a git repository connected to a
+/// workspace. The webhook secret and access token of the repository are never
+/// returned.
+pub async fn get_ci_repository_info(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: GetCiRepositoryInfoPath {
+					workspace_id,
+					repository_id,
+				},
+				query: (),
+				headers:
+					GetCiRepositoryInfoRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: GetCiRepositoryInfoRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, GetCiRepositoryInfoRequest>,
+) -> Result<AppResponse<GetCiRepositoryInfoRequest>, ErrorType> {
+	info!("Getting CI repository `{repository_id}` of workspace `{workspace_id}`");
+
+	let repository = query!(
+		r#"
+		SELECT
+			id,
+			provider AS "provider: GitProvider",
+			api_url,
+			full_name,
+			runner_id,
+			ci_file_path,
+			access_token IS NOT NULL AS "has_access_token!",
+			enabled,
+			created
+		FROM
+			ci_repository
+		WHERE
+			id = $1 AND
+			workspace_id = $2 AND
+			deleted IS NULL;
+		"#,
+		repository_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.map(|row| {
+		WithId::new(
+			row.id,
+			CiRepository {
+				provider: row.provider,
+				api_url: row.api_url,
+				full_name: row.full_name,
+				runner_id: row.runner_id.into(),
+				ci_file_path: row.ci_file_path,
+				has_access_token: row.has_access_token,
+				enabled: row.enabled,
+				created: row.created,
+			},
+		)
+	})
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	AppResponse::builder()
+		.body(GetCiRepositoryInfoResponse { repository })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,104 @@
+use axum::http::StatusCode;
+use models::{api::workspace::ci::*, ci::CiStepLog, prelude::*};
+
+use crate::prelude::*;
+
+/// The handler to get the logs of the steps of a pipeline run, in the order
+/// they were written. The logs of a run that is still queued are the lines that
+/// the runner has sent so far.
+pub async fn get_ci_run_logs(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path:
+					GetCiRunLogsPath {
+						workspace_id,
+						repository_id,
+						run_id,
+					},
+				query: Paginated {
+					data: (),
+					count,
+					page,
+				},
+				headers:
+					GetCiRunLogsRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: GetCiRunLogsRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, GetCiRunLogsRequest>,
+) -> Result<AppResponse<GetCiRunLogsRequest>, ErrorType> {
+	info!("Getting the logs of CI run `{run_id}` of repository `{repository_id}`");
+
+	query!(
+		r#"
+		SELECT
+			ci_run.id
+		FROM
+			ci_run
+		INNER JOIN
+			ci_repository
+		ON
+			ci_run.repository_id = ci_repository.id
+		WHERE
+			ci_run.id = $1 AND
+			ci_run.repository_id = $2 AND
+			ci_repository.workspace_id = $3 AND
+			ci_repository.deleted IS NULL;
+		"#,
+		run_id as _,
+		repository_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	let mut total_count = 0;
+	let logs = query!(
+		r#"
+		SELECT
+			step,
+			line,
+			COUNT(*) OVER() AS "total_count!"
+		FROM
+			ci_run_log
+		WHERE
+			run_id = $1
+		ORDER BY
+			line_number
+		LIMIT $2
+		OFFSET $3;
+		"#,
+		run_id as _,
+		count as i32,
+		(count * page) as i32,
+	)
+	.fetch_all(&mut **database)
+	.await?
+	.into_iter()
+	.map(|row| {
+		total_count = row.total_count;
+		CiStepLog {
+			step: row.step,
+			line: row.line,
+		}
+	})
+	.collect();
+
+	AppResponse::builder()
+		.body(GetCiRunLogsResponse { logs })
+		.headers(GetCiRunLogsResponseHeaders {
+			total_count: TotalCountHeader(total_count as _),
+		})
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
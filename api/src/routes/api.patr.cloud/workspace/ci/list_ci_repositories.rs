@@ -0,0 +1,98 @@
+use axum::http::StatusCode;
+use models::{api::workspace::ci::*, ci::GitProvider, prelude::*};
+
+use crate::prelude::*;
+
+/// The handler to list the git repositories connected to a workspace that the
+/// user has permission to view. The webhook secrets and access tokens of the
+/// repositories are never returned.
+pub async fn list_ci_repositories(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ListCiRepositoriesPath { workspace_id },
+				query: Paginated {
+					data: (),
+					count,
+					page,
+				},
+				headers:
+					ListCiRepositoriesRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ListCiRepositoriesRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data,
+	}: AuthenticatedAppRequest<'_, ListCiRepositoriesRequest>,
+) -> Result<AppResponse<ListCiRepositoriesRequest>, ErrorType> {
+	info!("Listing CI repositories of workspace `{workspace_id}`");
+
+	let mut total_count = 0;
+	let repositories = query!(
+		r#"
+		SELECT
+			ci_repository.id,
+			ci_repository.provider AS "provider: GitProvider",
+			ci_repository.api_url,
+			ci_repository.full_name,
+			ci_repository.runner_id,
+			ci_repository.ci_file_path,
+			ci_repository.access_token IS NOT NULL AS "has_access_token!",
+			ci_repository.enabled,
+			ci_repository.created,
+			COUNT(*) OVER() AS "total_count!"
+		FROM
+			ci_repository
+		INNER JOIN
+			RESOURCES_WITH_PERMISSION_FOR_LOGIN_ID($2, $3) AS resource
+		ON
+			ci_repository.id = resource.id
+		WHERE
+			ci_repository.workspace_id = $1 AND
+			ci_repository.deleted IS NULL
+		ORDER BY
+			ci_repository.created DESC
+		LIMIT $4
+		OFFSET $5;
+		"#,
+		workspace_id as _,
+		user_data.login_id as _,
+		Permission::CiRepository(CiRepositoryPermission::View) as _,
+		count as i32,
+		(count * page) as i32,
+	)
+	.fetch_all(&mut **database)
+	.await?
+	.into_iter()
+	.map(|row| {
+		total_count = row.total_count;
+		WithId::new(
+			row.id,
+			CiRepository {
+				provider: row.provider,
+				api_url: row.api_url,
+				full_name: row.full_name,
+				runner_id: row.runner_id.into(),
+				ci_file_path: row.ci_file_path,
+				has_access_token: row.has_access_token,
+				enabled: row.enabled,
+				created: row.created,
+			},
+		)
+	})
+	.collect();
+
+	AppResponse::builder()
+		.body(ListCiRepositoriesResponse { repositories })
+		.headers(ListCiRepositoriesResponseHeaders {
+			total_count: TotalCountHeader(total_count as _),
+		})
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,113 @@
+use axum::http::StatusCode;
+use models::{
+	api::workspace::ci::*,
+	ci::{CiStepReport, Event},
+	prelude::*,
+};
+use sqlx::types::Json;
+
+use crate::prelude::*;
+
+/// The handler to list the pipeline runs of a git repository connected to a
+/// workspace, latest first. The runs can be filtered by their state.
+pub async fn list_ci_runs_for_repository(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ListCiRunsForRepositoryPath {
+					workspace_id,
+					repository_id,
+				},
+				query:
+					Paginated {
+						data: ListCiRunsForRepositoryQuery { state },
+						count,
+						page,
+					},
+				headers:
+					ListCiRunsForRepositoryRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ListCiRunsForRepositoryRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ListCiRunsForRepositoryRequest>,
+) -> Result<AppResponse<ListCiRunsForRepositoryRequest>, ErrorType> {
+	info!("Listing CI runs of repository `{repository_id}` of workspace `{workspace_id}`");
+
+	let mut total_count = 0;
+	let runs = query!(
+		r#"
+		SELECT
+			ci_run.id,
+			ci_run.runner_id,
+			ci_run.commit_sha,
+			ci_run.branch,
+			ci_run.tag,
+			ci_run.event AS "event: Event",
+			ci_run.state AS "state: CiRunState",
+			ci_run.error,
+			ci_run.steps AS "steps: Json<Vec<CiStepReport>>",
+			ci_run.created,
+			ci_run.finished,
+			COUNT(*) OVER() AS "total_count!"
+		FROM
+			ci_run
+		INNER JOIN
+			ci_repository
+		ON
+			ci_run.repository_id = ci_repository.id
+		WHERE
+			ci_run.repository_id = $1 AND
+			ci_repository.workspace_id = $2 AND
+			ci_repository.deleted IS NULL AND
+			($3::CI_RUN_STATE IS NULL OR ci_run.state = $3)
+		ORDER BY
+			ci_run.created DESC
+		LIMIT $4
+		OFFSET $5;
+		"#,
+		repository_id as _,
+		workspace_id as _,
+		state as _,
+		count as i32,
+		(count * page) as i32,
+	)
+	.fetch_all(&mut **database)
+	.await?
+	.into_iter()
+	.map(|row| {
+		total_count = row.total_count;
+		WithId::new(
+			row.id,
+			CiRun {
+				repository_id,
+				runner_id: row.runner_id.into(),
+				commit_sha: row.commit_sha,
+				branch: row.branch,
+				tag: row.tag,
+				event: row.event,
+				state: row.state,
+				error: row.error,
+				steps: row.steps.0,
+				created: row.created,
+				finished: row.finished,
+			},
+		)
+	})
+	.collect();
+
+	AppResponse::builder()
+		.body(ListCiRunsForRepositoryResponse { runs })
+		.headers(ListCiRunsForRepositoryResponseHeaders {
+			total_count: TotalCountHeader(total_count as _),
+		})
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
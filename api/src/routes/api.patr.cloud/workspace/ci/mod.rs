@@ -0,0 +1,93 @@
+use axum::{routing::post, Router};
+
+use crate::prelude::*;
+
+mod connect_ci_repository;
+mod disconnect_ci_repository;
+mod get_ci_repository_info;
+mod get_ci_run_logs;
+mod list_ci_repositories;
+mod list_ci_runs_for_repository;
+mod receive_git_webhook;
+mod update_ci_repository;
+mod validate_ci_file;
+
+use self::{
+	connect_ci_repository::*,
+	disconnect_ci_repository::*,
+	get_ci_repository_info::*,
+	get_ci_run_logs::*,
+	list_ci_repositories::*,
+	list_ci_runs_for_repository::*,
+	receive_git_webhook::*,
+	update_ci_repository::*,
+	validate_ci_file::*,
+};
+
+/// The path that the providers of connected git repositories send their
+/// webhooks to
+const WEBHOOK_PATH: &str = "/workspace/:workspace_id/ci/repository/:repository_id/webhook";
+
+#[instrument(skip(state))]
+pub async fn setup_routes(state: &AppState) -> Router {
+	Router::new()
+		.mount_auth_endpoint(validate_ci_file, state)
+		.mount_auth_endpoint(connect_ci_repository, state)
+		.mount_auth_endpoint(disconnect_ci_repository, state)
+		.mount_auth_endpoint(get_ci_repository_info, state)
+		.mount_auth_endpoint(get_ci_run_logs, state)
+		.mount_auth_endpoint(list_ci_repositories, state)
+		.mount_auth_endpoint(list_ci_runs_for_repository, state)
+		.mount_auth_endpoint(update_ci_repository, state)
+		.merge(
+			Router::new()
+				.route(WEBHOOK_PATH, post(receive_git_webhook))
+				.with_state(state.clone()),
+		)
+}
+
+/// The URL that the provider of a connected git repository needs to send the
+/// webhooks of the repository to
+fn webhook_url(workspace_id: &Uuid, repository_id: &Uuid) -> String {
+	format!(
+		"{}/workspace/{}/ci/repository/{}/webhook",
+		constants::API_URL,
+		workspace_id,
+		repository_id
+	)
+}
+
+/// Checks if a URL can be used as the API URL of a git provider. Only absolute
+/// `http` and `https` URLs with a host are allowed.
+fn is_valid_api_url(url: &str) -> bool {
+	reqwest::Url::parse(url)
+		.is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.host_str().is_some())
+}
+
+/// Makes sure that a runner exists in the given workspace, so that the
+/// pipelines of a repository are never run on the runner of another workspace
+async fn ensure_runner_in_workspace(
+	connection: &mut DatabaseConnection,
+	workspace_id: &Uuid,
+	runner_id: &Uuid,
+) -> Result<(), ErrorType> {
+	query!(
+		r#"
+		SELECT
+			id
+		FROM
+			runner
+		WHERE
+			id = $1 AND
+			workspace_id = $2 AND
+			deleted IS NULL;
+		"#,
+		runner_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut *connection)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	Ok(())
+}
@@ -0,0 +1,258 @@
+use axum::{
+	body::Bytes,
+	extract::{Path, State},
+	http::{HeaderMap, StatusCode},
+};
+use models::{
+	api::workspace::runner::StreamRunnerDataForWorkspaceServerMsg,
+	ci::{
+		validate_ci_file,
+		CiDiagnosticSeverity,
+		CiFlow,
+		CiPipelineRun,
+		CiSecrets,
+		CiTrigger,
+		GitProvider,
+	},
+	ApiErrorResponse,
+};
+use rustis::commands::PubSubCommands;
+
+use crate::{
+	ci::{CommitStatus, ProviderRepository},
+	prelude::*,
+};
+
+/// The handler for the webhooks that the provider of a connected git
+/// repository sends. This is not an [`ApiEndpoint`], since the signature of a
+/// webhook is checked against the body exactly as it was sent.
+///
+/// The CI file of the repository is read at the commit of the event, and the
+/// pipeline in it is queued on the runner of the repository. If the file is
+/// invalid, the run is recorded as failed without being sent to the runner.
+pub async fn receive_git_webhook(
+	Path((workspace_id, repository_id)): Path<(Uuid, Uuid)>,
+	State(state): State<AppState>,
+	headers: HeaderMap,
+	body: Bytes,
+) -> Result<StatusCode, ApiErrorResponse> {
+	info!("Receiving a webhook for CI repository `{repository_id}`");
+
+	let mut database = state.database.begin().await?;
+
+	let repository = query!(
+		r#"
+		SELECT
+			provider AS "provider: GitProvider",
+			api_url,
+			full_name,
+			runner_id,
+			ci_file_path,
+			webhook_secret,
+			access_token,
+			enabled
+		FROM
+			ci_repository
+		WHERE
+			id = $1 AND
+			workspace_id = $2 AND
+			deleted IS NULL;
+		"#,
+		repository_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut *database)
+	.await?
+	.ok_or_else(|| ApiErrorResponse::error(ErrorType::ResourceDoesNotExist))?;
+
+	if !crate::ci::verify_webhook_signature(
+		repository.provider,
+		&repository.webhook_secret,
+		&headers,
+		&body,
+	) {
+		return Err(ApiErrorResponse::error(ErrorType::Unauthorized));
+	}
+
+	if !repository.enabled {
+		return Ok(StatusCode::NO_CONTENT);
+	}
+
+	let event = headers
+		.get(repository.provider.event_header())
+		.and_then(|value| value.to_str().ok())
+		.ok_or_else(|| ApiErrorResponse::error(ErrorType::WrongParameters))?;
+	let Some(trigger) = repository
+		.provider
+		.parse_webhook_event(event, &body)
+		.map_err(|err| ApiErrorResponse::error_with_message(ErrorType::WrongParameters, err))?
+	else {
+		return Ok(StatusCode::NO_CONTENT);
+	};
+
+	let provider_repository = ProviderRepository {
+		provider: repository.provider,
+		api_url: repository.api_url,
+		full_name: repository.full_name,
+		access_token: repository.access_token,
+	};
+
+	// Commits without a CI file have nothing to run
+	let Some(content) = provider_repository
+		.fetch_ci_file(&repository.ci_file_path, &trigger.commit_sha)
+		.await
+		.map_err(ApiErrorResponse::internal_error)?
+	else {
+		return Ok(StatusCode::NO_CONTENT);
+	};
+
+	let run_id = Uuid::new_v4();
+	let validation = validate_ci_file(&content);
+	let has_errors = validation.has_errors();
+
+	let pipeline = match validation.flow.filter(|_| !has_errors) {
+		Some(CiFlow::Pipeline(pipeline)) => {
+			let secrets = pipeline.secret_names();
+			if secrets.is_empty() {
+				Ok(pipeline)
+			} else {
+				Err((
+					format!(
+						"The pipeline uses the secrets {}, but secrets cannot be passed to \
+						pipelines yet",
+						secrets
+							.into_iter()
+							.map(|secret| format!("`{}`", secret))
+							.collect::<Vec<_>>()
+							.join(", ")
+					),
+					"The pipeline uses secrets, which are not supported yet",
+				))
+			}
+		}
+		None => Err((
+			validation
+				.diagnostics
+				.iter()
+				.filter(|diagnostic| diagnostic.severity == CiDiagnosticSeverity::Error)
+				.map(ToString::to_string)
+				.collect::<Vec<_>>()
+				.join("\n"),
+			"The CI file is invalid",
+		)),
+	};
+
+	// Pipelines that cannot be run are recorded as failed without being sent
+	// to the runner
+	let pipeline = match pipeline {
+		Ok(pipeline) => pipeline,
+		Err((error, description)) => {
+			query!(
+				r#"
+				INSERT INTO
+					ci_run(
+						id,
+						repository_id,
+						runner_id,
+						commit_sha,
+						branch,
+						tag,
+						event,
+						state,
+						error,
+						created,
+						finished
+					)
+				VALUES
+					($1, $2, $3, $4, $5, $6, $7, 'failed', $8, NOW(), NOW());
+				"#,
+				run_id as _,
+				repository_id as _,
+				repository.runner_id,
+				trigger.commit_sha,
+				trigger.branch,
+				trigger.tag,
+				trigger.event as _,
+				error,
+			)
+			.execute(&mut *database)
+			.await?;
+
+			database.commit().await?;
+
+			provider_repository.spawn_commit_status_report(
+				trigger.commit_sha,
+				CommitStatus::Error,
+				description,
+			);
+
+			return Ok(StatusCode::ACCEPTED);
+		}
+	};
+
+	query!(
+		r#"
+		INSERT INTO
+			ci_run(
+				id,
+				repository_id,
+				runner_id,
+				commit_sha,
+				branch,
+				tag,
+				event,
+				state,
+				created
+			)
+		VALUES
+			($1, $2, $3, $4, $5, $6, $7, 'queued', NOW());
+		"#,
+		run_id as _,
+		repository_id as _,
+		repository.runner_id,
+		trigger.commit_sha,
+		trigger.branch,
+		trigger.tag,
+		trigger.event as _,
+	)
+	.execute(&mut *database)
+	.await?;
+
+	let commit_sha = trigger.commit_sha.clone();
+	let run = CiPipelineRun {
+		trigger: CiTrigger {
+			clone_url: provider_repository.authenticated_clone_url(&trigger.clone_url),
+			..trigger
+		},
+		pipeline,
+		// Passing secrets to pipelines is not supported, since the API does not
+		// store the values of secrets. Pipelines that use secrets are rejected
+		// above, so there are never any values to send.
+		secrets: CiSecrets::default(),
+	};
+
+	// If this fails, the run is rolled back, so the provider can redeliver the
+	// webhook
+	state
+		.redis
+		.publish(
+			format!("{}/runner/{}/stream", workspace_id, repository.runner_id),
+			serde_json::to_string(
+				&StreamRunnerDataForWorkspaceServerMsg::CiPipelineRunRequested {
+					run: WithId::new(run_id, run),
+				},
+			)
+			.unwrap(),
+		)
+		.await?;
+
+	database.commit().await?;
+
+	provider_repository.spawn_commit_status_report(
+		commit_sha,
+		CommitStatus::Pending,
+		"The pipeline is queued",
+	);
+
+	Ok(StatusCode::ACCEPTED)
+}
@@ -0,0 +1,94 @@
+use axum::http::StatusCode;
+use models::{api::workspace::ci::*, prelude::*};
+
+use crate::prelude::*;
+
+/// The handler to update a git repository connected to a workspace. The runs
+/// that are already queued are not affected, even if the runner is changed.
+pub async fn update_ci_repository(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: UpdateCiRepositoryPath {
+					workspace_id,
+					repository_id,
+				},
+				query: (),
+				headers:
+					UpdateCiRepositoryRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body:
+					UpdateCiRepositoryRequestProcessed {
+						runner_id,
+						ci_file_path,
+						access_token,
+						remove_access_token,
+						enabled,
+					},
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, UpdateCiRepositoryRequest>,
+) -> Result<AppResponse<UpdateCiRepositoryRequest>, ErrorType> {
+	info!("Updating CI repository `{repository_id}` of workspace `{workspace_id}`");
+
+	if runner_id.is_none() &&
+		ci_file_path.is_none() &&
+		access_token.is_none() &&
+		!remove_access_token &&
+		enabled.is_none()
+	{
+		return Err(ErrorType::WrongParameters);
+	}
+
+	if remove_access_token && access_token.is_some() {
+		return Err(ErrorType::WrongParameters);
+	}
+
+	if let Some(runner_id) = &runner_id {
+		super::ensure_runner_in_workspace(&mut **database, &workspace_id, runner_id).await?;
+	}
+
+	query!(
+		r#"
+		UPDATE
+			ci_repository
+		SET
+			runner_id = COALESCE($3, runner_id),
+			ci_file_path = COALESCE($4, ci_file_path),
+			access_token = CASE
+				WHEN $6 THEN NULL
+				ELSE COALESCE($5, access_token)
+			END,
+			enabled = COALESCE($7, enabled)
+		WHERE
+			id = $1 AND
+			workspace_id = $2 AND
+			deleted IS NULL
+		RETURNING
+			id;
+		"#,
+		repository_id as _,
+		workspace_id as _,
+		runner_id as _,
+		ci_file_path,
+		access_token,
+		remove_access_token,
+		enabled,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	AppResponse::builder()
+		.body(UpdateCiRepositoryResponse)
+		.headers(())
+		.status_code(StatusCode::ACCEPTED)
+		.build()
+		.into_result()
+}
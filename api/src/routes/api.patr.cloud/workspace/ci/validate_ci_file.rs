@@ -0,0 +1,42 @@
+use axum::http::StatusCode;
+use models::{api::workspace::ci::*, ci::validate_ci_file as validate};
+
+use crate::prelude::*;
+
+/// The handler to validate a CI file. The file is parsed and checked for
+/// problems such as steps that refer to steps that do not exist, and every
+/// problem found is returned along with where it is in the file.
+pub async fn validate_ci_file(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ValidateCiFilePath { workspace_id },
+				query: (),
+				headers:
+					ValidateCiFileRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ValidateCiFileRequestProcessed { content },
+			},
+		database: _,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ValidateCiFileRequest>,
+) -> Result<AppResponse<ValidateCiFileRequest>, ErrorType> {
+	info!("Validating a CI file for workspace `{workspace_id}`");
+
+	let validation = validate(&content);
+
+	AppResponse::builder()
+		.body(ValidateCiFileResponse {
+			valid: !validation.has_errors(),
+			diagnostics: validation.diagnostics,
+		})
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
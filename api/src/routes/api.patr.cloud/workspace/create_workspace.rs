@@ -96,14 +96,26 @@ pub async fn create_workspace(
 				id,
 				name,
 				super_admin_id,
+				deployment_limit,
+				static_site_limit,
+				cpu_count_limit,
+				memory_count_limit,
+				volume_storage_limit,
+				container_registry_storage_limit,
 				deleted
 			)
 		VALUES
-			($1, $2, $3, NULL);
+			($1, $2, $3, $4, $5, $6, $7, $8, $9, NULL);
 		"#,
 		workspace_id as _,
 		&name,
 		user_id as _,
+		constants::DEFAULT_DEPLOYMENT_LIMIT,
+		constants::DEFAULT_STATIC_SITE_LIMIT,
+		constants::DEFAULT_CPU_COUNT_LIMIT,
+		constants::DEFAULT_MEMORY_COUNT_LIMIT,
+		constants::DEFAULT_VOLUME_STORAGE_LIMIT,
+		constants::DEFAULT_CONTAINER_REGISTRY_STORAGE_LIMIT,
 	)
 	.execute(&mut **database)
 	.await?;
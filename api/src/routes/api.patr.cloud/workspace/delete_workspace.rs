@@ -8,13 +8,15 @@ use crate::prelude::*;
 /// The handler to delete a workspace. This will delete all associated data
 /// with the workspace, including the database, container registry, and any
 /// other resources. This is a destructive operation and cannot be undone.
-/// The workspace must be empty before it can be deleted.
+/// The workspace must be empty before it can be deleted, unless `cascade` is
+/// set, in which case the workspace is scheduled for deletion along with all
+/// its resources, and can be restored until the grace period ends.
 pub async fn delete_workspace(
 	AuthenticatedAppRequest {
 		request:
 			ProcessedApiRequest {
 				path: DeleteWorkspacePath { workspace_id },
-				query: (),
+				query: DeleteWorkspaceQuery { cascade },
 				headers:
 					DeleteWorkspaceRequestHeaders {
 						authorization: _,
@@ -52,6 +54,80 @@ pub async fn delete_workspace(
 		return Err(ErrorType::ResourceDoesNotExist);
 	}
 
+	if cascade {
+		info!("Scheduling workspace `{workspace_id}` for deletion along with its resources");
+
+		let now = OffsetDateTime::now_utc();
+
+		query!(
+			r#"
+			INSERT INTO
+				workspace_deletion(
+					workspace_id,
+					requested_by,
+					requested,
+					scheduled
+				)
+			VALUES
+				($1, $2, $3, $4);
+			"#,
+			&workspace_id as _,
+			user_data.id as _,
+			now,
+			now + constants::WORKSPACE_DELETION_GRACE_PERIOD,
+		)
+		.execute(&mut **database)
+		.await
+		.map_err(|err| match err {
+			sqlx::Error::Database(err) if err.is_unique_violation() => {
+				ErrorType::WorkspaceDeletionInProgress
+			}
+			err => ErrorType::server_error(err),
+		})?;
+
+		// A pending transfer would let another user take over a workspace that
+		// is about to be destroyed
+		query!(
+			r#"
+			DELETE FROM
+				workspace_ownership_transfer
+			WHERE
+				workspace_id = $1;
+			"#,
+			&workspace_id as _,
+		)
+		.execute(&mut **database)
+		.await?;
+	} else {
+		delete_empty_workspace(&mut **database, &workspace_id).await?;
+	}
+
+	// Revoke all tokens that have access to the workspace
+	redis
+		.setex(
+			redis::keys::workspace_id_revocation_timestamp(&workspace.id.into()),
+			constants::CACHED_PERMISSIONS_VALIDITY.whole_seconds() as u64 + 300,
+			OffsetDateTime::now_utc().unix_timestamp(),
+		)
+		.await
+		.inspect_err(|err| {
+			error!("Error setting the revocation timestamp: `{}`", err);
+		})?;
+
+	AppResponse::builder()
+		.body(DeleteWorkspaceResponse)
+		.headers(())
+		.status_code(StatusCode::RESET_CONTENT)
+		.build()
+		.into_result()
+}
+
+/// Deletes a workspace that has no resources in it, failing with
+/// [`ErrorType::WorkspaceNotEmpty`] otherwise.
+async fn delete_empty_workspace(
+	connection: &mut DatabaseConnection,
+	workspace_id: &Uuid,
+) -> Result<(), ErrorType> {
 	// Make sure there are no resources in the workspace
 	let resources = query!(
 		r#"
@@ -63,9 +139,9 @@ pub async fn delete_workspace(
 			owner_id = $1 AND
 			deleted IS NULL;
 		"#,
-		&workspace_id as _,
+		workspace_id as _,
 	)
-	.fetch_optional(&mut **database)
+	.fetch_optional(&mut *connection)
 	.await?
 	.and_then(|row| row.count)
 	.unwrap_or(0);
@@ -83,27 +159,10 @@ pub async fn delete_workspace(
 		WHERE
 			id = $1;
 		"#,
-		&workspace_id as _,
+		workspace_id as _,
 	)
-	.execute(&mut **database)
+	.execute(&mut *connection)
 	.await?;
 
-	// Revoke all tokens that have access to the workspace
-	redis
-		.setex(
-			redis::keys::workspace_id_revocation_timestamp(&workspace.id.into()),
-			constants::CACHED_PERMISSIONS_VALIDITY.whole_seconds() as u64 + 300,
-			OffsetDateTime::now_utc().unix_timestamp(),
-		)
-		.await
-		.inspect_err(|err| {
-			error!("Error setting the revocation timestamp: `{}`", err);
-		})?;
-
-	AppResponse::builder()
-		.body(DeleteWorkspaceResponse)
-		.headers(())
-		.status_code(StatusCode::RESET_CONTENT)
-		.build()
-		.into_result()
+	Ok(())
 }
@@ -0,0 +1,39 @@
+use axum::http::StatusCode;
+use models::api::workspace::deletion::*;
+
+use crate::prelude::*;
+
+/// The handler to list everything that will be destroyed if a workspace is
+/// deleted along with all its resources. This is a dry run of the deletion,
+/// and does not change anything in the workspace.
+pub async fn get_workspace_deletion_plan(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: GetWorkspaceDeletionPlanPath { workspace_id },
+				query: (),
+				headers:
+					GetWorkspaceDeletionPlanRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: GetWorkspaceDeletionPlanRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, GetWorkspaceDeletionPlanRequest>,
+) -> Result<AppResponse<GetWorkspaceDeletionPlanRequest>, ErrorType> {
+	info!("Getting the deletion plan of workspace `{workspace_id}`");
+
+	let resources = super::get_resources_to_delete(&mut **database, &workspace_id).await?;
+
+	AppResponse::builder()
+		.body(GetWorkspaceDeletionPlanResponse { resources })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
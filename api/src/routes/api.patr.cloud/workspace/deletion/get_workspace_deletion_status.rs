@@ -0,0 +1,80 @@
+use axum::http::StatusCode;
+use models::api::workspace::deletion::*;
+
+use crate::prelude::*;
+
+/// The handler to get the progress of the deletion of a workspace, along with
+/// the resources that are yet to be destroyed. The workspace cannot be accessed
+/// through the workspace authenticators once it is scheduled for deletion, so
+/// this checks that the user is the super admin of the workspace by itself.
+pub async fn get_workspace_deletion_status(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: GetWorkspaceDeletionStatusPath { workspace_id },
+				query: (),
+				headers:
+					GetWorkspaceDeletionStatusRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: GetWorkspaceDeletionStatusRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data,
+	}: AuthenticatedAppRequest<'_, GetWorkspaceDeletionStatusRequest>,
+) -> Result<AppResponse<GetWorkspaceDeletionStatusRequest>, ErrorType> {
+	info!("Getting the deletion status of workspace `{workspace_id}`");
+
+	let status = query!(
+		r#"
+		SELECT
+			workspace_deletion.requested_by,
+			workspace_deletion.requested,
+			workspace_deletion.scheduled,
+			workspace_deletion.current_step AS "current_step: WorkspaceDeletionStep",
+			workspace_deletion.failed_attempts,
+			workspace_deletion.last_error,
+			workspace_deletion.completed
+		FROM
+			workspace_deletion
+		INNER JOIN
+			workspace
+		ON
+			workspace.id = workspace_deletion.workspace_id
+		WHERE
+			workspace_deletion.workspace_id = $1 AND
+			workspace.super_admin_id = $2;
+		"#,
+		workspace_id as _,
+		user_data.id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.map(|row| WorkspaceDeletionStatus {
+		requested_by: row.requested_by.into(),
+		requested: row.requested,
+		scheduled: row.scheduled,
+		current_step: row.current_step,
+		failed_attempts: row.failed_attempts as u32,
+		last_error: row.last_error,
+		completed: row.completed,
+	})
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	let remaining_resources =
+		super::get_resources_to_delete(&mut **database, &workspace_id).await?;
+
+	AppResponse::builder()
+		.body(GetWorkspaceDeletionStatusResponse {
+			status,
+			remaining_resources,
+		})
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,177 @@
+use axum::Router;
+use models::api::workspace::deletion::*;
+
+use crate::prelude::*;
+
+mod get_workspace_deletion_plan;
+mod get_workspace_deletion_status;
+mod restore_workspace;
+
+use self::{
+	get_workspace_deletion_plan::*,
+	get_workspace_deletion_status::*,
+	restore_workspace::*,
+};
+
+#[instrument(skip(state))]
+pub async fn setup_routes(state: &AppState) -> Router {
+	Router::new()
+		.mount_auth_endpoint(get_workspace_deletion_plan, state)
+		.mount_auth_endpoint(get_workspace_deletion_status, state)
+		.mount_auth_endpoint(restore_workspace, state)
+}
+
+/// Lists the resources of a workspace that are yet to be destroyed by its
+/// deletion, in the order of the steps they are destroyed in. This is used both
+/// to show what a deletion will destroy before it is requested, and to show
+/// what is left while it is running.
+async fn get_resources_to_delete(
+	connection: &mut DatabaseConnection,
+	workspace_id: &Uuid,
+) -> Result<Vec<WorkspaceDeletionPlanItem>, sqlx::Error> {
+	let resources = query!(
+		r#"
+		SELECT
+			'deployments'::WORKSPACE_DELETION_STEP AS "step!: WorkspaceDeletionStep",
+			id AS "resource_id!",
+			name::TEXT AS "name!"
+		FROM
+			deployment
+		WHERE
+			workspace_id = $1 AND
+			deleted IS NULL
+		UNION ALL
+		SELECT
+			'static_sites'::WORKSPACE_DELETION_STEP,
+			id,
+			name::TEXT
+		FROM
+			static_site
+		WHERE
+			workspace_id = $1 AND
+			deleted IS NULL
+		UNION ALL
+		SELECT
+			'managed_databases'::WORKSPACE_DELETION_STEP,
+			id,
+			name::TEXT
+		FROM
+			managed_database
+		WHERE
+			workspace_id = $1 AND
+			deleted IS NULL
+		UNION ALL
+		SELECT
+			'volumes'::WORKSPACE_DELETION_STEP,
+			deployment_volume.id,
+			deployment_volume.name
+		FROM
+			deployment_volume
+		INNER JOIN
+			resource
+		ON
+			resource.id = deployment_volume.id
+		WHERE
+			resource.owner_id = $1 AND
+			deployment_volume.deleted IS NULL
+		UNION ALL
+		SELECT
+			'container_registry'::WORKSPACE_DELETION_STEP,
+			id,
+			name::TEXT
+		FROM
+			container_registry_repository
+		WHERE
+			workspace_id = $1 AND
+			deleted IS NULL
+		UNION ALL
+		SELECT
+			'managed_urls'::WORKSPACE_DELETION_STEP,
+			managed_url.id,
+			CONCAT(
+				CASE
+					WHEN managed_url.sub_domain = '@' THEN ''
+					ELSE CONCAT(managed_url.sub_domain, '.')
+				END,
+				workspace_domain.name,
+				'.',
+				workspace_domain.tld,
+				managed_url.path
+			)
+		FROM
+			managed_url
+		INNER JOIN
+			workspace_domain
+		ON
+			workspace_domain.id = managed_url.domain_id
+		WHERE
+			managed_url.workspace_id = $1 AND
+			managed_url.deleted IS NULL
+		UNION ALL
+		SELECT
+			'domains'::WORKSPACE_DELETION_STEP,
+			id,
+			CONCAT(name, '.', tld)
+		FROM
+			workspace_domain
+		WHERE
+			workspace_id = $1 AND
+			deleted IS NULL
+		UNION ALL
+		SELECT
+			'secrets'::WORKSPACE_DELETION_STEP,
+			id,
+			name::TEXT
+		FROM
+			secret
+		WHERE
+			workspace_id = $1 AND
+			deleted IS NULL
+		UNION ALL
+		SELECT
+			'runners'::WORKSPACE_DELETION_STEP,
+			id,
+			name
+		FROM
+			runner
+		WHERE
+			workspace_id = $1 AND
+			deleted IS NULL
+		UNION ALL
+		SELECT
+			'workspace'::WORKSPACE_DELETION_STEP,
+			id,
+			name::TEXT
+		FROM
+			project
+		WHERE
+			workspace_id = $1 AND
+			deleted IS NULL
+		UNION ALL
+		SELECT
+			'workspace'::WORKSPACE_DELETION_STEP,
+			id,
+			name::TEXT
+		FROM
+			workspace
+		WHERE
+			id = $1 AND
+			deleted IS NULL
+		ORDER BY
+			1,
+			3;
+		"#,
+		workspace_id as _,
+	)
+	.fetch_all(&mut *connection)
+	.await?
+	.into_iter()
+	.map(|row| WorkspaceDeletionPlanItem {
+		step: row.step,
+		resource_id: row.resource_id.into(),
+		name: row.name,
+	})
+	.collect();
+
+	Ok(resources)
+}
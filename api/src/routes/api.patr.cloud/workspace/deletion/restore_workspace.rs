@@ -0,0 +1,119 @@
+use axum::http::StatusCode;
+use models::api::workspace::deletion::*;
+use rustis::commands::StringCommands;
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// The handler to restore a workspace that is scheduled for deletion. This can
+/// only be done before the grace period of the deletion ends. Once the
+/// resources of the workspace start getting destroyed, the deletion can no
+/// longer be stopped.
+pub async fn restore_workspace(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: RestoreWorkspacePath { workspace_id },
+				query: (),
+				headers:
+					RestoreWorkspaceRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: RestoreWorkspaceRequestProcessed,
+			},
+		database,
+		redis,
+		client_ip: _,
+		config: _,
+		user_data,
+	}: AuthenticatedAppRequest<'_, RestoreWorkspaceRequest>,
+) -> Result<AppResponse<RestoreWorkspaceRequest>, ErrorType> {
+	info!("Restoring workspace `{workspace_id}`");
+
+	// Locks the deletion, so that it cannot start while it is being removed
+	let scheduled = query!(
+		r#"
+		SELECT
+			workspace_deletion.scheduled
+		FROM
+			workspace_deletion
+		INNER JOIN
+			workspace
+		ON
+			workspace.id = workspace_deletion.workspace_id
+		WHERE
+			workspace_deletion.workspace_id = $1 AND
+			workspace.super_admin_id = $2
+		FOR UPDATE OF
+			workspace_deletion;
+		"#,
+		workspace_id as _,
+		user_data.id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?
+	.scheduled;
+
+	if scheduled <= OffsetDateTime::now_utc() {
+		return Err(ErrorType::WorkspaceDeletionInProgress);
+	}
+
+	query!(
+		r#"
+		DELETE FROM
+			workspace_deletion
+		WHERE
+			workspace_id = $1;
+		"#,
+		workspace_id as _,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	// The workspace is not a part of the cached permissions of its members
+	// while it is scheduled for deletion, so the cache of every member is
+	// revoked instead of the cache of the workspace
+	let users = query!(
+		r#"
+		SELECT
+			user_id
+		FROM
+			workspace_user
+		WHERE
+			workspace_id = $1
+		UNION
+		SELECT
+			super_admin_id
+		FROM
+			workspace
+		WHERE
+			id = $1;
+		"#,
+		workspace_id as _,
+	)
+	.fetch_all(&mut **database)
+	.await?;
+
+	for user in users {
+		let Some(user_id) = user.user_id else {
+			continue;
+		};
+
+		redis
+			.setex(
+				redis::keys::user_id_revocation_timestamp(&user_id.into()),
+				constants::CACHED_PERMISSIONS_VALIDITY.whole_seconds() as u64,
+				OffsetDateTime::now_utc().unix_timestamp(),
+			)
+			.await?;
+	}
+
+	AppResponse::builder()
+		.body(RestoreWorkspaceResponse)
+		.headers(())
+		.status_code(StatusCode::RESET_CONTENT)
+		.build()
+		.into_result()
+}
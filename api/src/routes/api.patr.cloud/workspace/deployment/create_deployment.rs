@@ -1,5 +1,9 @@
 use axum::http::StatusCode;
-use models::api::workspace::{deployment::*, runner::StreamRunnerDataForWorkspaceServerMsg};
+use models::api::workspace::{
+	deployment::*,
+	runner::StreamRunnerDataForWorkspaceServerMsg,
+	WorkspaceQuotaType,
+};
 use rustis::commands::PubSubCommands;
 use time::OffsetDateTime;
 
@@ -317,6 +321,17 @@ pub async fn create_deployment(
 		err => ErrorType::server_error(err),
 	})?;
 
+	super::super::ensure_within_workspace_quota(
+		&mut **database,
+		&workspace_id,
+		&[
+			WorkspaceQuotaType::Deployments,
+			WorkspaceQuotaType::CpuCount,
+			WorkspaceQuotaType::MemoryCount,
+		],
+	)
+	.await?;
+
 	if let DeploymentRegistry::PatrRegistry { repository_id, .. } = &registry {
 		let digest = query!(
 			r#"
@@ -362,7 +377,7 @@ pub async fn create_deployment(
 		}
 	}
 
-	// TODO Temporary workaround until audit logs and triggers are implemented
+	// TODO Temporary workaround until triggers on the audit logs are implemented
 	redis
 		.publish(
 			format!("{}/runner/{}/stream", workspace_id, runner),
@@ -147,7 +147,7 @@ pub async fn delete_deployment(
 		err => ErrorType::server_error(err),
 	})?;
 
-	// TODO Temporary workaround until audit logs and triggers are implemented
+	// TODO Temporary workaround until triggers on the audit logs are implemented
 	redis
 		.publish(
 			format!("{}/runner/{}/stream", workspace_id, runner),
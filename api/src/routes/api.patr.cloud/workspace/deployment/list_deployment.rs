@@ -10,11 +10,12 @@ pub async fn list_deployment(
 		request:
 			ProcessedApiRequest {
 				path: ListDeploymentPath { workspace_id },
-				query: Paginated {
-					data: (),
-					count,
-					page,
-				},
+				query:
+					Paginated {
+						data: ListDeploymentQuery { project_id },
+						count,
+						page,
+					},
 				headers:
 					ListDeploymentRequestHeaders {
 						authorization: _,
@@ -54,7 +55,8 @@ pub async fn list_deployment(
 			deployment.id = resource.id
 		WHERE
 			workspace_id = $1 AND
-			deployment.deleted IS NULL
+			deployment.deleted IS NULL AND
+			($6::UUID IS NULL OR resource.project_id = $6)
 		ORDER BY
 			resource.created DESC
 		LIMIT $4
@@ -65,6 +67,7 @@ pub async fn list_deployment(
 		Permission::Deployment(DeploymentPermission::View) as _,
 		count as i32,
 		(count * page) as i32,
+		project_id as _,
 	)
 	.fetch_all(&mut **database)
 	.await?
@@ -1,5 +1,5 @@
 use axum::http::StatusCode;
-use models::api::workspace::deployment::*;
+use models::api::workspace::{deployment::*, WorkspaceQuotaType};
 
 use crate::prelude::*;
 
@@ -13,7 +13,7 @@ pub async fn update_deployment(
 		request:
 			ProcessedApiRequest {
 				path: UpdateDeploymentPath {
-					workspace_id: _,
+					workspace_id,
 					deployment_id,
 				},
 				query: (),
@@ -237,6 +237,15 @@ pub async fn update_deployment(
 	.execute(&mut **database)
 	.await?;
 
+	if machine_type.is_some() || max_horizontal_scale.is_some() {
+		super::super::ensure_within_workspace_quota(
+			&mut **database,
+			&workspace_id,
+			&[WorkspaceQuotaType::CpuCount, WorkspaceQuotaType::MemoryCount],
+		)
+		.await?;
+	}
+
 	if let Some(environment_variables) = environment_variables {
 		query!(
 			r#"
@@ -1,5 +1,5 @@
 use axum::{http::StatusCode, Router};
-use models::api::workspace::domain::*;
+use models::{api::workspace::domain::*, utils::TotalCountHeader};
 
 use crate::prelude::*;
 
@@ -215,27 +215,83 @@ async fn get_domain_info_in_workspace(
 
 async fn get_domains_for_workspace(
 	AuthenticatedAppRequest {
-		request: ProcessedApiRequest {
-			path,
-			query: _,
-			headers,
-			body,
-		},
+		request:
+			ProcessedApiRequest {
+				path: GetDomainsForWorkspacePath { workspace_id },
+				query: Paginated {
+					data: (),
+					count,
+					page,
+				},
+				headers:
+					GetDomainsForWorkspaceRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: GetDomainsForWorkspaceRequestProcessed,
+			},
 		database,
 		redis: _,
 		client_ip: _,
-		config,
+		config: _,
 		user_data,
 	}: AuthenticatedAppRequest<'_, GetDomainsForWorkspaceRequest>,
 ) -> Result<AppResponse<GetDomainsForWorkspaceRequest>, ErrorType> {
-	info!("Starting: Get domains for workspace");
-
-	// LOGIC
+	info!("Listing all domains in workspace: {}", workspace_id);
+
+	let mut total_count = 0;
+	let domains = query!(
+		r#"
+		SELECT
+			workspace_domain.id,
+			workspace_domain.name,
+			workspace_domain.tld,
+			workspace_domain.is_verified,
+			workspace_domain.nameserver_type AS "nameserver_type: DomainNameserverType",
+			COUNT(*) OVER() AS "total_count!"
+		FROM
+			workspace_domain
+		INNER JOIN
+			RESOURCES_WITH_PERMISSION_FOR_LOGIN_ID($2, $3) AS resource
+		ON
+			workspace_domain.id = resource.id
+		WHERE
+			workspace_domain.workspace_id = $1 AND
+			workspace_domain.deleted IS NULL
+		ORDER BY
+			resource.created DESC
+		LIMIT $4
+		OFFSET $5;
+		"#,
+		workspace_id as _,
+		user_data.login_id as _,
+		Permission::Domain(DomainPermission::View) as _,
+		count as i32,
+		(count * page) as i32,
+	)
+	.fetch_all(&mut **database)
+	.await?
+	.into_iter()
+	.map(|row| {
+		total_count = row.total_count;
+		WithId::new(
+			row.id,
+			WorkspaceDomain {
+				domain: Domain {
+					name: format!("{}.{}", row.name, row.tld),
+					last_unverified: None,
+				},
+				is_verified: row.is_verified,
+				nameserver_type: row.nameserver_type,
+			},
+		)
+	})
+	.collect();
 
 	AppResponse::builder()
-		.body(GetDomainsForWorkspaceResponse { domains: todo!() })
+		.body(GetDomainsForWorkspaceResponse { domains })
 		.headers(GetDomainsForWorkspaceResponseHeaders {
-			total_count: todo!(),
+			total_count: TotalCountHeader(total_count as _),
 		})
 		.status_code(StatusCode::OK)
 		.build()
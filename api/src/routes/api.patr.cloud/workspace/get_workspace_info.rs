@@ -50,6 +50,7 @@ pub async fn get_workspace_info(
 				Workspace {
 					name: workspace.name,
 					super_admin_id: workspace.super_admin_id.into(),
+					sso_provider_id: workspace.sso_provider_id,
 				},
 			),
 		})
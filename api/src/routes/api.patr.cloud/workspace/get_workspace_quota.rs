@@ -0,0 +1,228 @@
+use std::collections::BTreeMap;
+
+use axum::http::StatusCode;
+use models::api::workspace::*;
+
+use crate::prelude::*;
+
+/// The handler to get the quotas of a workspace, along with the current usage
+/// of each quota.
+pub async fn get_workspace_quota(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: GetWorkspaceQuotaPath { workspace_id },
+				query: (),
+				headers:
+					GetWorkspaceQuotaRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: GetWorkspaceQuotaRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, GetWorkspaceQuotaRequest>,
+) -> Result<AppResponse<GetWorkspaceQuotaRequest>, ErrorType> {
+	info!("Getting the quotas of workspace `{workspace_id}`");
+
+	let quotas = get_workspace_quota_usage(&mut **database, &workspace_id).await?;
+
+	AppResponse::builder()
+		.body(GetWorkspaceQuotaResponse { quotas })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
+
+/// Gets the current usage and the limit of each quota of a workspace
+pub async fn get_workspace_quota_usage(
+	connection: &mut DatabaseConnection,
+	workspace_id: &Uuid,
+) -> Result<BTreeMap<WorkspaceQuotaType, WorkspaceQuotaUsage>, ErrorType> {
+	let usage = query!(
+		r#"
+		SELECT
+			workspace.deployment_limit,
+			workspace.static_site_limit,
+			workspace.cpu_count_limit,
+			workspace.memory_count_limit,
+			workspace.volume_storage_limit,
+			workspace.container_registry_storage_limit,
+			(
+				SELECT
+					COUNT(*)
+				FROM
+					deployment
+				WHERE
+					workspace_id = $1 AND
+					deleted IS NULL
+			) AS "deployments!",
+			(
+				SELECT
+					COUNT(*)
+				FROM
+					static_site
+				WHERE
+					workspace_id = $1 AND
+					deleted IS NULL
+			) AS "static_sites!",
+			(
+				SELECT
+					COALESCE(
+						SUM(
+							deployment_machine_type.cpu_count *
+							deployment.max_horizontal_scale
+						),
+						0
+					)
+				FROM
+					deployment
+				INNER JOIN
+					deployment_machine_type
+				ON
+					deployment_machine_type.id = deployment.machine_type
+				WHERE
+					deployment.workspace_id = $1 AND
+					deployment.deleted IS NULL
+			)::BIGINT AS "cpu_count!",
+			(
+				SELECT
+					COALESCE(
+						SUM(
+							deployment_machine_type.memory_count *
+							deployment.max_horizontal_scale
+						),
+						0
+					)
+				FROM
+					deployment
+				INNER JOIN
+					deployment_machine_type
+				ON
+					deployment_machine_type.id = deployment.machine_type
+				WHERE
+					deployment.workspace_id = $1 AND
+					deployment.deleted IS NULL
+			)::BIGINT AS "memory_count!",
+			(
+				SELECT
+					COALESCE(SUM(deployment_volume.volume_size), 0)
+				FROM
+					deployment_volume
+				INNER JOIN
+					resource
+				ON
+					resource.id = deployment_volume.id
+				WHERE
+					resource.owner_id = $1 AND
+					deployment_volume.deleted IS NULL
+			)::BIGINT AS "volume_storage!",
+			(
+				SELECT
+					COALESCE(SUM(container_registry_repository_blob.size), 0)
+				FROM
+					container_registry_repository_blob
+				WHERE
+					container_registry_repository_blob.blob_digest IN (
+						SELECT
+							container_registry_manifest_blob.blob_digest
+						FROM
+							container_registry_repository_manifest
+						INNER JOIN
+							container_registry_repository
+						ON
+							container_registry_repository.id = container_registry_repository_manifest.repository_id
+						INNER JOIN
+							container_registry_manifest_blob
+						ON
+							container_registry_manifest_blob.manifest_digest = container_registry_repository_manifest.manifest_digest
+						WHERE
+							container_registry_repository.workspace_id = $1 AND
+							container_registry_repository.deleted IS NULL
+					)
+			)::BIGINT AS "container_registry_storage!"
+		FROM
+			workspace
+		WHERE
+			id = $1 AND
+			deleted IS NULL;
+		"#,
+		workspace_id as _
+	)
+	.fetch_optional(&mut *connection)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	Ok([
+		(
+			WorkspaceQuotaType::Deployments,
+			usage.deployments,
+			usage.deployment_limit.into(),
+		),
+		(
+			WorkspaceQuotaType::StaticSites,
+			usage.static_sites,
+			usage.static_site_limit.into(),
+		),
+		(
+			WorkspaceQuotaType::CpuCount,
+			usage.cpu_count,
+			usage.cpu_count_limit.into(),
+		),
+		(
+			WorkspaceQuotaType::MemoryCount,
+			usage.memory_count,
+			usage.memory_count_limit.into(),
+		),
+		(
+			WorkspaceQuotaType::VolumeStorage,
+			usage.volume_storage,
+			usage.volume_storage_limit.into(),
+		),
+		(
+			WorkspaceQuotaType::ContainerRegistryStorage,
+			usage.container_registry_storage,
+			usage.container_registry_storage_limit,
+		),
+	]
+	.into_iter()
+	.map(|(quota, used, limit): (_, i64, i64)| {
+		(
+			quota,
+			WorkspaceQuotaUsage {
+				used: used.max(0) as u64,
+				limit: limit.max(0) as u64,
+			},
+		)
+	})
+	.collect())
+}
+
+/// Ensures that a workspace is within the given quotas. Since the usage is
+/// calculated from the database, this must be called after the resources have
+/// been created or updated within the request's transaction, so that the
+/// transaction is rolled back if any of the quotas are exceeded.
+pub async fn ensure_within_workspace_quota(
+	connection: &mut DatabaseConnection,
+	workspace_id: &Uuid,
+	quotas: &[WorkspaceQuotaType],
+) -> Result<(), ErrorType> {
+	let usage = get_workspace_quota_usage(connection, workspace_id).await?;
+
+	for quota in quotas {
+		let Some(WorkspaceQuotaUsage { used, limit }) = usage.get(quota) else {
+			continue;
+		};
+		if used > limit {
+			info!("Workspace `{workspace_id}` is over its {quota:?} quota: {used}/{limit}");
+			return Err(ErrorType::QuotaExceeded { quota: *quota });
+		}
+	}
+
+	Ok(())
+}
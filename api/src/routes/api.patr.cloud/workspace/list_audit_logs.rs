@@ -0,0 +1,121 @@
+use axum::http::StatusCode;
+use models::{api::workspace::*, utils::TotalCountHeader};
+
+use crate::prelude::*;
+
+/// The handler to list the audit logs of a workspace, latest first. The audit
+/// logs can be filtered by the resource, the user that made the request, the
+/// action performed and the time at which it was recorded.
+pub async fn list_audit_logs(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ListAuditLogsPath { workspace_id },
+				query:
+					Paginated {
+						data:
+							ListAuditLogsQuery {
+								resource_id,
+								user_id,
+								action,
+								from,
+								to,
+							},
+						count,
+						page,
+					},
+				headers:
+					ListAuditLogsRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ListAuditLogsRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ListAuditLogsRequest>,
+) -> Result<AppResponse<ListAuditLogsRequest>, ErrorType> {
+	info!("Listing audit logs of workspace `{workspace_id}`");
+
+	let mut total_count = 0;
+	let audit_logs = query!(
+		r#"
+		SELECT
+			audit_log.id,
+			audit_log.timestamp,
+			audit_log.ip_address,
+			audit_log.workspace_id AS "workspace_id!",
+			audit_log.user_id,
+			audit_log.login_id,
+			audit_log.resource_id AS "resource_id!",
+			audit_log.action::TEXT AS "action!",
+			audit_log.request_id,
+			audit_log.metadata,
+			audit_log.patr_action,
+			audit_log.success,
+			service_account.id IS NOT NULL AS "service_account!",
+			COUNT(*) OVER() AS "total_count!"
+		FROM
+			audit_log
+		LEFT JOIN
+			service_account
+		ON
+			service_account.id = audit_log.user_id
+		WHERE
+			audit_log.workspace_id = $1 AND
+			($4::UUID IS NULL OR audit_log.resource_id = $4) AND
+			($5::UUID IS NULL OR audit_log.user_id = $5) AND
+			($6::TEXT IS NULL OR audit_log.action::TEXT = $6) AND
+			($7::TIMESTAMPTZ IS NULL OR audit_log.timestamp >= $7) AND
+			($8::TIMESTAMPTZ IS NULL OR audit_log.timestamp < $8)
+		ORDER BY
+			audit_log.timestamp DESC
+		LIMIT $2
+		OFFSET $3;
+		"#,
+		workspace_id as _,
+		count as i64,
+		(count * page) as i64,
+		resource_id as _,
+		user_id as _,
+		action,
+		from,
+		to,
+	)
+	.fetch_all(&mut **database)
+	.await?
+	.into_iter()
+	.map(|row| {
+		total_count = row.total_count;
+		WithId::new(
+			row.id,
+			WorkspaceAuditLog {
+				date: row.timestamp,
+				ip_address: row.ip_address.ip().to_string(),
+				workspace_id: row.workspace_id.into(),
+				user_id: row.user_id.map(Into::into),
+				login_id: row.login_id.map(Into::into),
+				service_account: row.service_account,
+				resource_id: row.resource_id.into(),
+				action: row.action,
+				request_id: row.request_id.into(),
+				metadata: row.metadata,
+				patr_action: row.patr_action,
+				request_success: row.success,
+			},
+		)
+	})
+	.collect();
+
+	AppResponse::builder()
+		.body(ListAuditLogsResponse { audit_logs })
+		.headers(ListAuditLogsResponseHeaders {
+			total_count: TotalCountHeader(total_count as _),
+		})
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -17,6 +17,7 @@ pub async fn list_managed_url(
 								order: _, // TODO implement these
 								order_by: _,
 								filter: _,
+								project_id,
 							},
 						count,
 						page,
@@ -63,7 +64,8 @@ pub async fn list_managed_url(
 			managed_url.id = resource.id
 		WHERE
 			workspace_id = $1 AND
-			managed_url.deleted IS NULL
+			managed_url.deleted IS NULL AND
+			($6::UUID IS NULL OR resource.project_id = $6)
 		ORDER BY
 			resource.created DESC
 		LIMIT $4
@@ -74,6 +76,7 @@ pub async fn list_managed_url(
 		Permission::ManagedURL(ManagedURLPermission::View) as _,
 		count as i32,
 		(count * page) as i32,
+		project_id as _,
 	)
 	.fetch_all(&mut **database)
 	.await?
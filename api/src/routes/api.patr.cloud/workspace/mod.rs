@@ -2,63 +2,102 @@ use axum::Router;
 
 use crate::prelude::*;
 
+mod billing;
+mod ci;
 // mod container_registry;
 #[allow(unreachable_code, unused_variables)]
 mod database;
+mod deletion;
 mod deployment;
 #[allow(unreachable_code, unused_variables)]
 mod domain;
 mod managed_url;
+mod ownership_transfer;
+mod project;
 mod rbac;
 mod runner;
 #[allow(unreachable_code, unused_variables)]
 mod secret;
+mod service_account;
 #[allow(unreachable_code, unused_variables)]
 mod static_site;
 mod volume;
+mod webhook;
 
 /// The handler to create a new workspace. The workspace name must be unique.
 mod create_workspace;
 /// The handler to delete a workspace. This will delete all associated data
 /// with the workspace, including the database, container registry, and any
 /// other resources. This is a destructive operation and cannot be undone.
-/// The workspace must be empty before it can be deleted.
+/// The workspace must be empty before it can be deleted, unless it is deleted
+/// along with all its resources, which is done by the
+/// [`workspace_deletion`][crate::workspace_deletion] background task.
 mod delete_workspace;
 /// The handler to get the information of a workspace. This includes the
 /// workspace's name, the user who created it, and the date it was created.
 mod get_workspace_info;
+/// The handler to get the quotas of a workspace, along with the current usage
+/// of each quota. The quotas are enforced when resources are created or
+/// updated, using [`ensure_within_workspace_quota`].
+mod get_workspace_quota;
 /// The handler to check if a workspace name is available. This is used when
 /// creating a new workspace to ensure that the name is unique.
 mod is_name_available;
+/// The handler to list the audit logs of a workspace. Every state-changing
+/// request made on a workspace is recorded in the audit log by the
+/// [`AuditLoggerLayer`][crate::utils::layers::AuditLoggerLayer].
+mod list_audit_logs;
+/// The handler to move a deployment, secret, volume or container registry
+/// repository to another workspace, along with the resources it depends on.
+mod move_resource;
 /// The handler to update the information of a workspace. At the moment, only
 /// the name can be updated. However, this will be expanded in the future. At
 /// least one parameter must be provided for the update.
 mod update_workspace_info;
+/// The handler to update the single sign-on settings of a workspace. This sets
+/// the external OpenID Connect provider that members must sign in with.
+mod update_workspace_sso;
 
+pub(super) use self::rbac::accept_workspace_invite_for_user;
 use self::{
 	create_workspace::*,
 	delete_workspace::*,
 	get_workspace_info::*,
+	get_workspace_quota::*,
 	is_name_available::*,
+	list_audit_logs::*,
+	move_resource::*,
 	update_workspace_info::*,
+	update_workspace_sso::*,
 };
 
 #[instrument(skip(state))]
 pub async fn setup_routes(state: &AppState) -> Router {
 	Router::new()
+		.merge(billing::setup_routes(state).await)
+		.merge(ci::setup_routes(state).await)
 		// .merge(container_registry::setup_routes(state).await)
 		.merge(domain::setup_routes(state).await)
 		.merge(database::setup_routes(state).await)
+		.merge(deletion::setup_routes(state).await)
 		.merge(deployment::setup_routes(state).await)
 		.merge(managed_url::setup_routes(state).await)
+		.merge(ownership_transfer::setup_routes(state).await)
+		.merge(project::setup_routes(state).await)
 		.merge(rbac::setup_routes(state).await)
 		.merge(runner::setup_routes(state).await)
 		.merge(secret::setup_routes(state).await)
+		.merge(service_account::setup_routes(state).await)
 		.merge(static_site::setup_routes(state).await)
 		.merge(volume::setup_routes(state).await)
+		.merge(webhook::setup_routes(state).await)
 		.mount_auth_endpoint(create_workspace, state)
 		.mount_auth_endpoint(delete_workspace, state)
 		.mount_auth_endpoint(get_workspace_info, state)
+		.mount_auth_endpoint(get_workspace_quota, state)
 		.mount_auth_endpoint(is_name_available, state)
+		.mount_auth_endpoint(list_audit_logs, state)
+		.mount_auth_endpoint(move_resource, state)
 		.mount_auth_endpoint(update_workspace_info, state)
+		.mount_auth_endpoint(update_workspace_sso, state)
 }
@@ -0,0 +1,665 @@
+use axum::http::StatusCode;
+use models::api::workspace::{runner::StreamRunnerDataForWorkspaceServerMsg, *};
+use rustis::commands::{PubSubCommands, StringCommands};
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// The handler to move a deployment, secret, volume or container registry
+/// repository to another workspace that the user is the super admin of.
+///
+/// A deployment can only be moved once it is stopped, and is moved along with
+/// the secrets used in its environment variables, its container registry
+/// repository and the managed URLs that point to it. Its environment
+/// variables, exposed ports and config mounts are moved implicitly, since they
+/// belong to the deployment. If any of the secrets or the repository are used
+/// by another deployment, the move fails with [`ErrorType::ResourceInUse`].
+/// Secrets, volumes and repositories that are in use by a deployment cannot be
+/// moved on their own.
+///
+/// Since the deployment has to move to a runner of the new workspace, and the
+/// data of volumes is kept on the runner, deployments with volumes cannot be
+/// moved. Managed URLs keep their domain when they are moved, so a deployment
+/// can only be moved if all the managed URLs that point to it are on verified
+/// domains of the new workspace. Otherwise, the move fails with
+/// [`ErrorType::ResourceInUse`].
+///
+/// The moved resources are removed from any project of the old workspace, and
+/// any permissions on them given by the roles and API tokens of the old
+/// workspace are removed.
+pub async fn move_resource(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: MoveResourceToWorkspacePath {
+					workspace_id,
+					resource_id,
+				},
+				query: (),
+				headers:
+					MoveResourceToWorkspaceRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body:
+					MoveResourceToWorkspaceRequestProcessed {
+						new_workspace_id,
+						runner_id,
+					},
+			},
+		database,
+		redis,
+		client_ip: _,
+		config: _,
+		user_data,
+	}: AuthenticatedAppRequest<'_, MoveResourceToWorkspaceRequest>,
+) -> Result<AppResponse<MoveResourceToWorkspaceRequest>, ErrorType> {
+	info!(
+		"Moving resource `{resource_id}` from workspace `{workspace_id}` to workspace \
+		`{new_workspace_id}`"
+	);
+
+	if new_workspace_id == workspace_id {
+		debug!("Resource is already in the workspace");
+		return Err(ErrorType::WrongParameters);
+	}
+
+	query!(
+		r#"
+		SELECT
+			id
+		FROM
+			workspace
+		WHERE
+			id = $1 AND
+			super_admin_id = $2 AND
+			deleted IS NULL;
+		"#,
+		new_workspace_id as _,
+		user_data.id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::Unauthorized)?;
+
+	let resource = query!(
+		r#"
+		SELECT
+			EXISTS(
+				SELECT
+					1
+				FROM
+					deployment
+				WHERE
+					id = $1 AND
+					workspace_id = $2 AND
+					deleted IS NULL
+			) AS "is_deployment!",
+			EXISTS(
+				SELECT
+					1
+				FROM
+					secret
+				WHERE
+					id = $1 AND
+					workspace_id = $2 AND
+					deleted IS NULL
+			) AS "is_secret!",
+			EXISTS(
+				SELECT
+					1
+				FROM
+					deployment_volume
+				INNER JOIN
+					resource
+				ON
+					resource.id = deployment_volume.id
+				WHERE
+					deployment_volume.id = $1 AND
+					resource.owner_id = $2 AND
+					deployment_volume.deleted IS NULL
+			) AS "is_volume!",
+			EXISTS(
+				SELECT
+					1
+				FROM
+					container_registry_repository
+				WHERE
+					id = $1 AND
+					workspace_id = $2 AND
+					deleted IS NULL
+			) AS "is_repository!";
+		"#,
+		resource_id as _,
+		workspace_id as _,
+	)
+	.fetch_one(&mut **database)
+	.await?;
+
+	let mut deployment_runner = None;
+	let (moved_resources, quotas) = if resource.is_deployment {
+		let Some(runner_id) = runner_id else {
+			debug!("A runner is required to move a deployment");
+			return Err(ErrorType::WrongParameters);
+		};
+
+		query!(
+			r#"
+			SELECT
+				id
+			FROM
+				runner
+			WHERE
+				id = $1 AND
+				workspace_id = $2 AND
+				deleted IS NULL;
+			"#,
+			runner_id as _,
+			new_workspace_id as _,
+		)
+		.fetch_optional(&mut **database)
+		.await?
+		.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+		let deployment = query!(
+			r#"
+			SELECT
+				runner,
+				repository_id,
+				(
+					status = 'running' OR
+					status = 'deploying'
+				) AS "is_running!"
+			FROM
+				deployment
+			WHERE
+				id = $1;
+			"#,
+			resource_id as _,
+		)
+		.fetch_one(&mut **database)
+		.await?;
+
+		if deployment.is_running {
+			debug!("The deployment must be stopped before it can be moved");
+			return Err(ErrorType::ResourceInUse);
+		}
+
+		// The data of the volumes is on the old runner, and would not be there
+		// on the new one
+		let has_volumes = query!(
+			r#"
+			SELECT
+				EXISTS(
+					SELECT
+						1
+					FROM
+						deployment_volume_mount
+					WHERE
+						deployment_id = $1
+				) AS "has_volumes!";
+			"#,
+			resource_id as _,
+		)
+		.fetch_one(&mut **database)
+		.await?
+		.has_volumes;
+
+		if has_volumes {
+			debug!("A deployment with volumes cannot be moved to another runner");
+			return Err(ErrorType::ResourceInUse);
+		}
+
+		let secrets = query!(
+			r#"
+			SELECT DISTINCT
+				secret_id AS "secret_id!"
+			FROM
+				deployment_environment_variable
+			WHERE
+				deployment_id = $1 AND
+				secret_id IS NOT NULL;
+			"#,
+			resource_id as _,
+		)
+		.fetch_all(&mut **database)
+		.await?
+		.into_iter()
+		.map(|row| row.secret_id)
+		.collect::<Vec<_>>();
+
+		let is_shared = query!(
+			r#"
+			SELECT
+				EXISTS(
+					SELECT
+						1
+					FROM
+						deployment_environment_variable
+					INNER JOIN
+						deployment
+					ON
+						deployment.id = deployment_environment_variable.deployment_id
+					WHERE
+						deployment_environment_variable.secret_id = ANY($2) AND
+						deployment.id != $1 AND
+						deployment.deleted IS NULL
+				) OR
+				EXISTS(
+					SELECT
+						1
+					FROM
+						deployment
+					WHERE
+						repository_id = $3 AND
+						id != $1 AND
+						deleted IS NULL
+				) AS "is_shared!";
+			"#,
+			resource_id as _,
+			&secrets,
+			deployment.repository_id,
+		)
+		.fetch_one(&mut **database)
+		.await?
+		.is_shared;
+
+		if is_shared {
+			debug!("A dependency of the deployment is used by another deployment");
+			return Err(ErrorType::ResourceInUse);
+		}
+
+		let managed_urls = query!(
+			r#"
+			SELECT
+				managed_url.id,
+				(
+					workspace_domain.workspace_id = $2 AND
+					workspace_domain.is_verified = TRUE AND
+					workspace_domain.deleted IS NULL
+				) AS "is_on_new_workspace_domain!"
+			FROM
+				managed_url
+			INNER JOIN
+				workspace_domain
+			ON
+				workspace_domain.id = managed_url.domain_id
+			WHERE
+				managed_url.deployment_id = $1 AND
+				managed_url.deleted IS NULL;
+			"#,
+			resource_id as _,
+			new_workspace_id as _,
+		)
+		.fetch_all(&mut **database)
+		.await?;
+
+		// The managed URLs would still be on the domains of the old workspace
+		if managed_urls
+			.iter()
+			.any(|managed_url| !managed_url.is_on_new_workspace_domain)
+		{
+			debug!("A managed URL of the deployment is not on a domain of the new workspace");
+			return Err(ErrorType::ResourceInUse);
+		}
+
+		let managed_urls = managed_urls
+			.into_iter()
+			.map(|managed_url| managed_url.id)
+			.collect::<Vec<_>>();
+
+		query!(
+			r#"
+			SET CONSTRAINTS ALL DEFERRED;
+			"#
+		)
+		.execute(&mut **database)
+		.await?;
+
+		query!(
+			r#"
+			UPDATE
+				deployment
+			SET
+				workspace_id = $2,
+				runner = $3
+			WHERE
+				id = $1;
+			"#,
+			resource_id as _,
+			new_workspace_id as _,
+			runner_id as _,
+		)
+		.execute(&mut **database)
+		.await
+		.map_err(map_move_error)?;
+
+		query!(
+			r#"
+			UPDATE
+				secret
+			SET
+				workspace_id = $2
+			WHERE
+				id = ANY($1);
+			"#,
+			&secrets,
+			new_workspace_id as _,
+		)
+		.execute(&mut **database)
+		.await
+		.map_err(map_move_error)?;
+
+		query!(
+			r#"
+			UPDATE
+				container_registry_repository
+			SET
+				workspace_id = $2
+			WHERE
+				id = $1;
+			"#,
+			deployment.repository_id,
+			new_workspace_id as _,
+		)
+		.execute(&mut **database)
+		.await
+		.map_err(map_move_error)?;
+
+		query!(
+			r#"
+			UPDATE
+				managed_url
+			SET
+				workspace_id = $2
+			WHERE
+				id = ANY($1);
+			"#,
+			&managed_urls,
+			new_workspace_id as _,
+		)
+		.execute(&mut **database)
+		.await
+		.map_err(map_move_error)?;
+
+		deployment_runner = Some(deployment.runner);
+
+		let moved_resources = [resource_id]
+			.into_iter()
+			.chain(secrets.into_iter().map(Into::into))
+			.chain(deployment.repository_id.map(Into::into))
+			.chain(managed_urls.into_iter().map(Into::into))
+			.collect::<Vec<_>>();
+
+		(
+			moved_resources,
+			vec![
+				WorkspaceQuotaType::Deployments,
+				WorkspaceQuotaType::CpuCount,
+				WorkspaceQuotaType::MemoryCount,
+				WorkspaceQuotaType::ContainerRegistryStorage,
+			],
+		)
+	} else if resource.is_secret {
+		let in_use = query!(
+			r#"
+			SELECT
+				EXISTS(
+					SELECT
+						1
+					FROM
+						deployment_environment_variable
+					INNER JOIN
+						deployment
+					ON
+						deployment.id = deployment_environment_variable.deployment_id
+					WHERE
+						deployment_environment_variable.secret_id = $1 AND
+						deployment.deleted IS NULL
+				) AS "in_use!";
+			"#,
+			resource_id as _,
+		)
+		.fetch_one(&mut **database)
+		.await?
+		.in_use;
+
+		if in_use {
+			return Err(ErrorType::ResourceInUse);
+		}
+
+		query!(
+			r#"
+			SET CONSTRAINTS ALL DEFERRED;
+			"#
+		)
+		.execute(&mut **database)
+		.await?;
+
+		query!(
+			r#"
+			UPDATE
+				secret
+			SET
+				workspace_id = $2
+			WHERE
+				id = $1;
+			"#,
+			resource_id as _,
+			new_workspace_id as _,
+		)
+		.execute(&mut **database)
+		.await
+		.map_err(map_move_error)?;
+
+		(vec![resource_id], vec![])
+	} else if resource.is_volume {
+		let in_use = query!(
+			r#"
+			SELECT
+				EXISTS(
+					SELECT
+						1
+					FROM
+						deployment_volume_mount
+					WHERE
+						volume_id = $1
+				) AS "in_use!";
+			"#,
+			resource_id as _,
+		)
+		.fetch_one(&mut **database)
+		.await?
+		.in_use;
+
+		if in_use {
+			return Err(ErrorType::ResourceInUse);
+		}
+
+		(vec![resource_id], vec![WorkspaceQuotaType::VolumeStorage])
+	} else if resource.is_repository {
+		let in_use = query!(
+			r#"
+			SELECT
+				EXISTS(
+					SELECT
+						1
+					FROM
+						deployment
+					WHERE
+						repository_id = $1 AND
+						deleted IS NULL
+				) AS "in_use!";
+			"#,
+			resource_id as _,
+		)
+		.fetch_one(&mut **database)
+		.await?
+		.in_use;
+
+		if in_use {
+			return Err(ErrorType::ResourceInUse);
+		}
+
+		query!(
+			r#"
+			SET CONSTRAINTS ALL DEFERRED;
+			"#
+		)
+		.execute(&mut **database)
+		.await?;
+
+		query!(
+			r#"
+			UPDATE
+				container_registry_repository
+			SET
+				workspace_id = $2
+			WHERE
+				id = $1;
+			"#,
+			resource_id as _,
+			new_workspace_id as _,
+		)
+		.execute(&mut **database)
+		.await
+		.map_err(map_move_error)?;
+
+		(
+			vec![resource_id],
+			vec![WorkspaceQuotaType::ContainerRegistryStorage],
+		)
+	} else {
+		debug!("Resource is not a deployment, secret, volume or repository of the workspace");
+		return Err(ErrorType::ResourceDoesNotExist);
+	};
+
+	let resource_ids = moved_resources
+		.iter()
+		.map(|id| (*id).into())
+		.collect::<Vec<_>>();
+
+	// The permissions given by the old workspace no longer apply to the moved
+	// resources. The API token permissions reference the owner of the resource,
+	// so they have to be removed before the resource is re-homed
+	query!(
+		r#"
+		DELETE FROM
+			role_resource_permissions_include
+		WHERE
+			resource_id = ANY($1);
+		"#,
+		&resource_ids,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	query!(
+		r#"
+		DELETE FROM
+			role_resource_permissions_exclude
+		WHERE
+			resource_id = ANY($1);
+		"#,
+		&resource_ids,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	query!(
+		r#"
+		DELETE FROM
+			user_api_token_resource_permissions_include
+		WHERE
+			resource_id = ANY($1);
+		"#,
+		&resource_ids,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	query!(
+		r#"
+		DELETE FROM
+			user_api_token_resource_permissions_exclude
+		WHERE
+			resource_id = ANY($1);
+		"#,
+		&resource_ids,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	query!(
+		r#"
+		UPDATE
+			resource
+		SET
+			owner_id = $2,
+			project_id = NULL
+		WHERE
+			id = ANY($1);
+		"#,
+		&resource_ids,
+		new_workspace_id as _,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	query!(
+		r#"
+		SET CONSTRAINTS ALL IMMEDIATE;
+		"#
+	)
+	.execute(&mut **database)
+	.await
+	.map_err(map_move_error)?;
+
+	super::ensure_within_workspace_quota(&mut **database, &new_workspace_id, &quotas).await?;
+
+	if let Some(runner) = deployment_runner {
+		// TODO Temporary workaround until triggers on the audit logs are
+		// implemented
+		redis
+			.publish(
+				format!("{}/runner/{}/stream", workspace_id, runner),
+				serde_json::to_string(&StreamRunnerDataForWorkspaceServerMsg::DeploymentDeleted {
+					id: resource_id,
+				})
+				.unwrap(),
+			)
+			.await?;
+	}
+
+	// The permissions of the moved resources have changed in both workspaces
+	for workspace_id in [workspace_id, new_workspace_id] {
+		redis
+			.setex(
+				redis::keys::workspace_id_revocation_timestamp(&workspace_id),
+				constants::CACHED_PERMISSIONS_VALIDITY.whole_seconds() as u64,
+				OffsetDateTime::now_utc().unix_timestamp(),
+			)
+			.await
+			.inspect_err(|err| {
+				error!("Error setting the revocation timestamp: `{}`", err);
+			})?;
+	}
+
+	AppResponse::builder()
+		.body(MoveResourceToWorkspaceResponse { moved_resources })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
+
+/// Maps the errors from re-homing a resource. A resource with the same name in
+/// the new workspace causes a unique violation, and a resource that still
+/// references the old workspace causes a foreign key violation.
+fn map_move_error(err: sqlx::Error) -> ErrorType {
+	match err {
+		sqlx::Error::Database(err) if err.is_unique_violation() => ErrorType::ResourceAlreadyExists,
+		sqlx::Error::Database(err) if err.is_foreign_key_violation() => ErrorType::ResourceInUse,
+		err => ErrorType::server_error(err),
+	}
+}
@@ -0,0 +1,101 @@
+use axum::http::StatusCode;
+use models::api::workspace::ownership_transfer::*;
+use rustis::commands::StringCommands;
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// The handler to accept the ownership of a workspace. The transfer must have
+/// been sent to the currently logged in user, and the user that started it
+/// must still be the super admin of the workspace. The previous super admin
+/// only keeps access to the workspace through the roles they have in it, if
+/// any.
+pub async fn accept_ownership_transfer(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: AcceptWorkspaceOwnershipTransferPath { workspace_id },
+				query: (),
+				headers:
+					AcceptWorkspaceOwnershipTransferRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: AcceptWorkspaceOwnershipTransferRequestProcessed,
+			},
+		database,
+		redis,
+		client_ip: _,
+		config: _,
+		user_data,
+	}: AuthenticatedAppRequest<'_, AcceptWorkspaceOwnershipTransferRequest>,
+) -> Result<AppResponse<AcceptWorkspaceOwnershipTransferRequest>, ErrorType> {
+	info!(
+		"User `{}` is accepting the ownership of workspace `{workspace_id}`",
+		user_data.id
+	);
+
+	let previous_owner_id = query!(
+		r#"
+		DELETE FROM
+			workspace_ownership_transfer
+		WHERE
+			workspace_id = $1 AND
+			new_owner_id = $2 AND
+			expiry > NOW()
+		RETURNING
+			requested_by;
+		"#,
+		workspace_id as _,
+		user_data.id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::InvalidOwnershipTransfer)?
+	.requested_by;
+
+	let updated = query!(
+		r#"
+		UPDATE
+			workspace
+		SET
+			super_admin_id = $1
+		WHERE
+			id = $2 AND
+			super_admin_id = $3 AND
+			deleted IS NULL;
+		"#,
+		user_data.id as _,
+		workspace_id as _,
+		previous_owner_id,
+	)
+	.execute(&mut **database)
+	.await?
+	.rows_affected();
+
+	if updated == 0 {
+		debug!("The user that started the transfer is no longer the super admin");
+		return Err(ErrorType::InvalidOwnershipTransfer);
+	}
+
+	let now = OffsetDateTime::now_utc();
+	for user_id in [Uuid::from(previous_owner_id), user_data.id] {
+		redis
+			.setex(
+				redis::keys::user_id_revocation_timestamp(&user_id),
+				constants::CACHED_PERMISSIONS_VALIDITY.whole_seconds() as u64,
+				now.unix_timestamp(),
+			)
+			.await
+			.inspect_err(|err| {
+				error!("Error setting the revocation timestamp: `{}`", err);
+			})?;
+	}
+
+	AppResponse::builder()
+		.body(AcceptWorkspaceOwnershipTransferResponse)
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,66 @@
+use axum::http::StatusCode;
+use models::api::workspace::ownership_transfer::*;
+
+use crate::prelude::*;
+
+/// The handler to cancel a pending transfer of the ownership of a workspace.
+/// The super admin of the workspace can cancel the transfer, and the new owner
+/// can decline it. Once cancelled, the transfer is deleted.
+pub async fn cancel_ownership_transfer(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: CancelWorkspaceOwnershipTransferPath { workspace_id },
+				query: (),
+				headers:
+					CancelWorkspaceOwnershipTransferRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: CancelWorkspaceOwnershipTransferRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data,
+	}: AuthenticatedAppRequest<'_, CancelWorkspaceOwnershipTransferRequest>,
+) -> Result<AppResponse<CancelWorkspaceOwnershipTransferRequest>, ErrorType> {
+	info!("Cancelling the ownership transfer of workspace `{workspace_id}`");
+
+	let deleted = query!(
+		r#"
+		DELETE FROM
+			workspace_ownership_transfer
+		WHERE
+			workspace_id = $1 AND
+			(
+				new_owner_id = $2 OR
+				workspace_id IN (
+					SELECT
+						id
+					FROM
+						workspace
+					WHERE
+						super_admin_id = $2
+				)
+			);
+		"#,
+		workspace_id as _,
+		user_data.id as _,
+	)
+	.execute(&mut **database)
+	.await?
+	.rows_affected();
+
+	if deleted == 0 {
+		return Err(ErrorType::InvalidOwnershipTransfer);
+	}
+
+	AppResponse::builder()
+		.body(CancelWorkspaceOwnershipTransferResponse)
+		.headers(())
+		.status_code(StatusCode::RESET_CONTENT)
+		.build()
+		.into_result()
+}
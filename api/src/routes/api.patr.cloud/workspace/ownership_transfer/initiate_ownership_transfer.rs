@@ -0,0 +1,88 @@
+use axum::http::StatusCode;
+use models::api::workspace::ownership_transfer::*;
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// The handler to start transferring the ownership of a workspace to another
+/// user. The transfer only takes effect once the new owner accepts it. Any
+/// pending transfer of the workspace is replaced by the new one.
+pub async fn initiate_ownership_transfer(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: InitiateWorkspaceOwnershipTransferPath { workspace_id },
+				query: (),
+				headers:
+					InitiateWorkspaceOwnershipTransferRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: InitiateWorkspaceOwnershipTransferRequestProcessed { new_owner_id },
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data,
+	}: AuthenticatedAppRequest<'_, InitiateWorkspaceOwnershipTransferRequest>,
+) -> Result<AppResponse<InitiateWorkspaceOwnershipTransferRequest>, ErrorType> {
+	info!("Transferring ownership of workspace `{workspace_id}` to user `{new_owner_id}`");
+
+	if new_owner_id == user_data.id {
+		debug!("User is already the owner of the workspace");
+		return Err(ErrorType::WrongParameters);
+	}
+
+	query!(
+		r#"
+		SELECT
+			id
+		FROM
+			"user"
+		WHERE
+			id = $1;
+		"#,
+		new_owner_id as _
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::UserNotFound)?;
+
+	let now = OffsetDateTime::now_utc();
+	let expiry = now + constants::WORKSPACE_OWNERSHIP_TRANSFER_VALIDITY;
+
+	query!(
+		r#"
+		INSERT INTO
+			workspace_ownership_transfer(
+				workspace_id,
+				new_owner_id,
+				requested_by,
+				created,
+				expiry
+			)
+		VALUES
+			($1, $2, $3, $4, $5)
+		ON CONFLICT(workspace_id) DO UPDATE SET
+			new_owner_id = EXCLUDED.new_owner_id,
+			requested_by = EXCLUDED.requested_by,
+			created = EXCLUDED.created,
+			expiry = EXCLUDED.expiry;
+		"#,
+		workspace_id as _,
+		new_owner_id as _,
+		user_data.id as _,
+		now,
+		expiry,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	AppResponse::builder()
+		.body(InitiateWorkspaceOwnershipTransferResponse { expiry })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,78 @@
+use axum::http::StatusCode;
+use models::api::workspace::ownership_transfer::*;
+
+use crate::prelude::*;
+
+/// The handler to list all the pending transfers of the ownership of a
+/// workspace to the currently logged in user. Expired transfers, and transfers
+/// of workspaces that have been deleted, are not listed.
+pub async fn list_ownership_transfers(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ListOwnershipTransfersPath,
+				query: (),
+				headers:
+					ListOwnershipTransfersRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ListOwnershipTransfersRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data,
+	}: AuthenticatedAppRequest<'_, ListOwnershipTransfersRequest>,
+) -> Result<AppResponse<ListOwnershipTransfersRequest>, ErrorType> {
+	info!("Listing ownership transfers for user `{}`", user_data.id);
+
+	let transfers = query!(
+		r#"
+		SELECT
+			workspace_ownership_transfer.workspace_id,
+			workspace.name::TEXT AS "workspace_name!",
+			workspace_ownership_transfer.new_owner_id,
+			workspace_ownership_transfer.requested_by,
+			workspace_ownership_transfer.created,
+			workspace_ownership_transfer.expiry
+		FROM
+			workspace_ownership_transfer
+		INNER JOIN
+			workspace
+		ON
+			workspace.id = workspace_ownership_transfer.workspace_id
+		WHERE
+			workspace_ownership_transfer.new_owner_id = $1 AND
+			workspace_ownership_transfer.expiry > NOW() AND
+			workspace.deleted IS NULL
+		ORDER BY
+			workspace_ownership_transfer.created DESC;
+		"#,
+		user_data.id as _
+	)
+	.fetch_all(&mut **database)
+	.await?
+	.into_iter()
+	.map(|row| {
+		WithId::new(
+			row.workspace_id,
+			WorkspaceOwnershipTransfer {
+				workspace_name: row.workspace_name,
+				new_owner_id: row.new_owner_id.into(),
+				requested_by: row.requested_by.into(),
+				created: row.created,
+				expiry: row.expiry,
+			},
+		)
+	})
+	.collect();
+
+	AppResponse::builder()
+		.body(ListOwnershipTransfersResponse { transfers })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
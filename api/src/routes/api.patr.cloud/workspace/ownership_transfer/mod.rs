@@ -0,0 +1,24 @@
+use axum::Router;
+
+use crate::prelude::*;
+
+mod accept_ownership_transfer;
+mod cancel_ownership_transfer;
+mod initiate_ownership_transfer;
+mod list_ownership_transfers;
+
+use self::{
+	accept_ownership_transfer::*,
+	cancel_ownership_transfer::*,
+	initiate_ownership_transfer::*,
+	list_ownership_transfers::*,
+};
+
+#[instrument(skip(state))]
+pub async fn setup_routes(state: &AppState) -> Router {
+	Router::new()
+		.mount_auth_endpoint(accept_ownership_transfer, state)
+		.mount_auth_endpoint(cancel_ownership_transfer, state)
+		.mount_auth_endpoint(initiate_ownership_transfer, state)
+		.mount_auth_endpoint(list_ownership_transfers, state)
+}
@@ -0,0 +1,102 @@
+use axum::http::StatusCode;
+use models::api::workspace::project::*;
+
+use crate::prelude::*;
+
+/// The handler to add a resource to a project. Only deployments, static sites,
+/// secrets, volumes and managed URLs can be added to a project. If the resource
+/// is already in another project, it is moved to this project.
+pub async fn add_resource_to_project(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path:
+					AddResourceToProjectPath {
+						workspace_id,
+						project_id,
+						resource_id,
+					},
+				query: (),
+				headers:
+					AddResourceToProjectRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: AddResourceToProjectRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, AddResourceToProjectRequest>,
+) -> Result<AppResponse<AddResourceToProjectRequest>, ErrorType> {
+	info!("Adding resource `{resource_id}` to project `{project_id}`");
+
+	query!(
+		r#"
+		SELECT
+			id
+		FROM
+			project
+		WHERE
+			id = $1 AND
+			workspace_id = $2 AND
+			deleted IS NULL;
+		"#,
+		project_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	let resource = query!(
+		r#"
+		SELECT
+			EXISTS(SELECT 1 FROM deployment WHERE id = $1) OR
+			EXISTS(SELECT 1 FROM static_site WHERE id = $1) OR
+			EXISTS(SELECT 1 FROM secret WHERE id = $1) OR
+			EXISTS(SELECT 1 FROM deployment_volume WHERE id = $1) OR
+			EXISTS(SELECT 1 FROM managed_url WHERE id = $1) AS "can_be_grouped!"
+		FROM
+			resource
+		WHERE
+			id = $1 AND
+			owner_id = $2 AND
+			deleted IS NULL;
+		"#,
+		resource_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	if !resource.can_be_grouped {
+		debug!("Resource `{resource_id}` cannot be added to a project");
+		return Err(ErrorType::WrongParameters);
+	}
+
+	query!(
+		r#"
+		UPDATE
+			resource
+		SET
+			project_id = $1
+		WHERE
+			id = $2;
+		"#,
+		project_id as _,
+		resource_id as _,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	AppResponse::builder()
+		.body(AddResourceToProjectResponse)
+		.headers(())
+		.status_code(StatusCode::ACCEPTED)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,100 @@
+use axum::http::StatusCode;
+use models::api::workspace::project::*;
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// The handler to create a new project in a workspace. The name of the project
+/// must be unique within the workspace.
+pub async fn create_project(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: CreateProjectPath { workspace_id },
+				query: (),
+				headers:
+					CreateProjectRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: CreateProjectRequestProcessed { name, description },
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, CreateProjectRequest>,
+) -> Result<AppResponse<CreateProjectRequest>, ErrorType> {
+	info!("Creating project `{name}` in workspace `{workspace_id}`");
+
+	let now = OffsetDateTime::now_utc();
+	let project_id = query!(
+		r#"
+		INSERT INTO
+			resource(
+				id,
+				resource_type_id,
+				owner_id,
+				created,
+				deleted
+			)
+		VALUES
+			(
+				GENERATE_RESOURCE_ID(),
+				(SELECT id FROM resource_type WHERE name = 'project'),
+				$1,
+				$2,
+				NULL
+			)
+		RETURNING id;
+		"#,
+		workspace_id as _,
+		now
+	)
+	.fetch_one(&mut **database)
+	.await?
+	.id;
+
+	trace!("Resource created. Inserting project");
+
+	query!(
+		r#"
+		INSERT INTO
+			project(
+				id,
+				name,
+				description,
+				workspace_id,
+				deleted
+			)
+		VALUES
+			(
+				$1,
+				$2,
+				$3,
+				$4,
+				NULL
+			);
+		"#,
+		project_id as _,
+		&name,
+		&description,
+		workspace_id as _,
+	)
+	.execute(&mut **database)
+	.await
+	.map_err(|err| match err {
+		sqlx::Error::Database(dbe) if dbe.is_unique_violation() => ErrorType::ResourceAlreadyExists,
+		other => other.into(),
+	})?;
+
+	AppResponse::builder()
+		.body(CreateProjectResponse {
+			id: WithId::from(project_id),
+		})
+		.headers(())
+		.status_code(StatusCode::CREATED)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,126 @@
+use axum::http::StatusCode;
+use models::api::workspace::project::*;
+
+use crate::prelude::*;
+
+/// The handler to delete a project. The resources in the project are not
+/// deleted, and are removed from the project instead. Any permissions granted
+/// on the project no longer apply to those resources.
+pub async fn delete_project(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: DeleteProjectPath {
+					workspace_id,
+					project_id,
+				},
+				query: (),
+				headers:
+					DeleteProjectRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: DeleteProjectRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, DeleteProjectRequest>,
+) -> Result<AppResponse<DeleteProjectRequest>, ErrorType> {
+	info!("Deleting project `{project_id}` in workspace `{workspace_id}`");
+
+	query!(
+		r#"
+		SELECT
+			id
+		FROM
+			project
+		WHERE
+			id = $1 AND
+			workspace_id = $2 AND
+			deleted IS NULL;
+		"#,
+		project_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	query!(
+		r#"
+		UPDATE
+			resource
+		SET
+			project_id = NULL
+		WHERE
+			project_id = $1;
+		"#,
+		project_id as _,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	trace!("Resources removed from the project");
+
+	query!(
+		r#"
+		SET CONSTRAINTS ALL DEFERRED;
+		"#
+	)
+	.execute(&mut **database)
+	.await?;
+
+	query!(
+		r#"
+		UPDATE
+			project
+		SET
+			deleted = NOW()
+		WHERE
+			id = $1;
+		"#,
+		project_id as _,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	// Mark the resource as deleted in the database
+	query!(
+		r#"
+		UPDATE
+			resource
+		SET
+			deleted = (
+				SELECT
+					deleted
+				FROM
+					project
+				WHERE
+					id = $1
+			)
+		WHERE
+			id = $1;
+		"#,
+		project_id as _,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	query!(
+		r#"
+		SET CONSTRAINTS ALL IMMEDIATE;
+		"#
+	)
+	.execute(&mut **database)
+	.await?;
+
+	AppResponse::builder()
+		.body(DeleteProjectResponse)
+		.headers(())
+		.status_code(StatusCode::RESET_CONTENT)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,66 @@
+use axum::http::StatusCode;
+use models::api::workspace::project::*;
+
+use crate::prelude::*;
+
+/// The handler to get the details of a project in a workspace
+pub async fn get_project_info(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: GetProjectInfoPath {
+					workspace_id,
+					project_id,
+				},
+				query: (),
+				headers:
+					GetProjectInfoRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: GetProjectInfoRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, GetProjectInfoRequest>,
+) -> Result<AppResponse<GetProjectInfoRequest>, ErrorType> {
+	trace!("Getting info of project `{project_id}`");
+
+	let row = query!(
+		r#"
+		SELECT
+			id,
+			name,
+			description
+		FROM
+			project
+		WHERE
+			id = $1 AND
+			workspace_id = $2 AND
+			deleted IS NULL;
+		"#,
+		project_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	AppResponse::builder()
+		.body(GetProjectInfoResponse {
+			project: WithId::new(
+				row.id,
+				Project {
+					name: row.name,
+					description: row.description,
+				},
+			),
+		})
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,85 @@
+use axum::http::StatusCode;
+use models::{api::workspace::project::*, utils::TotalCountHeader};
+
+use crate::prelude::*;
+
+/// The handler to list all the projects in a workspace. This will only return
+/// the projects that the user has permission to view.
+pub async fn list_projects(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ListProjectsPath { workspace_id },
+				query: Paginated {
+					data: (),
+					count,
+					page,
+				},
+				headers:
+					ListProjectsRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ListProjectsRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data,
+	}: AuthenticatedAppRequest<'_, ListProjectsRequest>,
+) -> Result<AppResponse<ListProjectsRequest>, ErrorType> {
+	info!("Listing all projects in workspace `{workspace_id}`");
+
+	let mut total_count = 0;
+	let projects = query!(
+		r#"
+		SELECT
+			project.id,
+			project.name,
+			project.description,
+			COUNT(*) OVER() AS "total_count!"
+		FROM
+			project
+		INNER JOIN
+			RESOURCES_WITH_PERMISSION_FOR_LOGIN_ID($2, $3) AS resource
+		ON
+			project.id = resource.id
+		WHERE
+			project.workspace_id = $1 AND
+			project.deleted IS NULL
+		ORDER BY
+			resource.created DESC
+		LIMIT $4
+		OFFSET $5;
+		"#,
+		workspace_id as _,
+		user_data.login_id as _,
+		Permission::Project(ProjectPermission::View) as _,
+		count as i32,
+		(count * page) as i32,
+	)
+	.fetch_all(&mut **database)
+	.await?
+	.into_iter()
+	.map(|row| {
+		total_count = row.total_count;
+		WithId::new(
+			row.id,
+			Project {
+				name: row.name,
+				description: row.description,
+			},
+		)
+	})
+	.collect();
+
+	AppResponse::builder()
+		.body(ListProjectsResponse { projects })
+		.headers(ListProjectsResponseHeaders {
+			total_count: TotalCountHeader(total_count as _),
+		})
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,33 @@
+use axum::Router;
+
+use crate::prelude::*;
+
+mod add_resource_to_project;
+mod create_project;
+mod delete_project;
+mod get_project_info;
+mod list_projects;
+mod remove_resource_from_project;
+mod update_project;
+
+use self::{
+	add_resource_to_project::*,
+	create_project::*,
+	delete_project::*,
+	get_project_info::*,
+	list_projects::*,
+	remove_resource_from_project::*,
+	update_project::*,
+};
+
+#[instrument(skip(state))]
+pub async fn setup_routes(state: &AppState) -> Router {
+	Router::new()
+		.mount_auth_endpoint(add_resource_to_project, state)
+		.mount_auth_endpoint(create_project, state)
+		.mount_auth_endpoint(delete_project, state)
+		.mount_auth_endpoint(get_project_info, state)
+		.mount_auth_endpoint(list_projects, state)
+		.mount_auth_endpoint(remove_resource_from_project, state)
+		.mount_auth_endpoint(update_project, state)
+}
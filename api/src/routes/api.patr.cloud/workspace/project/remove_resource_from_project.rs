@@ -0,0 +1,61 @@
+use axum::http::StatusCode;
+use models::api::workspace::project::*;
+
+use crate::prelude::*;
+
+/// The handler to remove a resource from a project. The resource itself is not
+/// deleted, and any permissions granted on the project no longer apply to it.
+pub async fn remove_resource_from_project(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path:
+					RemoveResourceFromProjectPath {
+						workspace_id,
+						project_id,
+						resource_id,
+					},
+				query: (),
+				headers:
+					RemoveResourceFromProjectRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: RemoveResourceFromProjectRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, RemoveResourceFromProjectRequest>,
+) -> Result<AppResponse<RemoveResourceFromProjectRequest>, ErrorType> {
+	info!("Removing resource `{resource_id}` from project `{project_id}`");
+
+	query!(
+		r#"
+		UPDATE
+			resource
+		SET
+			project_id = NULL
+		WHERE
+			id = $1 AND
+			owner_id = $2 AND
+			project_id = $3
+		RETURNING id;
+		"#,
+		resource_id as _,
+		workspace_id as _,
+		project_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	AppResponse::builder()
+		.body(RemoveResourceFromProjectResponse)
+		.headers(())
+		.status_code(StatusCode::RESET_CONTENT)
+		.build()
+		.into_result()
+}
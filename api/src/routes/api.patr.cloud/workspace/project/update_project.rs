@@ -0,0 +1,69 @@
+use axum::http::StatusCode;
+use models::api::workspace::project::*;
+
+use crate::prelude::*;
+
+/// The handler to update the name and / or description of a project. At least
+/// one of the parameters must be provided for the update.
+pub async fn update_project(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: UpdateProjectPath {
+					workspace_id,
+					project_id,
+				},
+				query: (),
+				headers:
+					UpdateProjectRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: UpdateProjectRequestProcessed { name, description },
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, UpdateProjectRequest>,
+) -> Result<AppResponse<UpdateProjectRequest>, ErrorType> {
+	info!("Updating project `{project_id}`");
+
+	if name.is_none() && description.is_none() {
+		return Err(ErrorType::WrongParameters);
+	}
+
+	query!(
+		r#"
+		UPDATE
+			project
+		SET
+			name = COALESCE($1, name),
+			description = COALESCE($2, description)
+		WHERE
+			id = $3 AND
+			workspace_id = $4 AND
+			deleted IS NULL
+		RETURNING id;
+		"#,
+		name.as_deref(),
+		description.as_deref(),
+		project_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await
+	.map_err(|err| match err {
+		sqlx::Error::Database(dbe) if dbe.is_unique_violation() => ErrorType::ResourceAlreadyExists,
+		other => other.into(),
+	})?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	AppResponse::builder()
+		.body(UpdateProjectResponse)
+		.headers(())
+		.status_code(StatusCode::ACCEPTED)
+		.build()
+		.into_result()
+}
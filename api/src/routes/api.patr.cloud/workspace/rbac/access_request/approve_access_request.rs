@@ -0,0 +1,112 @@
+use axum::http::StatusCode;
+use models::api::workspace::rbac::access_request::*;
+use rustis::commands::StringCommands;
+use time::{Duration, OffsetDateTime};
+
+use crate::prelude::*;
+
+/// The handler to approve a pending request for elevated access to a workspace.
+/// The user is given the requested role from now until the requested duration
+/// runs out. If the user already has the role for a limited time, the role is
+/// extended instead. If the user already has the role without any expiry, the
+/// role is left as-is.
+pub async fn approve_access_request(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ApproveAccessRequestPath {
+					workspace_id,
+					request_id,
+				},
+				query: (),
+				headers:
+					ApproveAccessRequestRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ApproveAccessRequestRequestProcessed,
+			},
+		database,
+		redis,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ApproveAccessRequestRequest>,
+) -> Result<AppResponse<ApproveAccessRequestRequest>, ErrorType> {
+	info!("Approving access request `{request_id}` of workspace `{workspace_id}`");
+
+	let access_request = query!(
+		r#"
+		DELETE FROM
+			workspace_access_request
+		WHERE
+			id = $1 AND
+			workspace_id = $2
+		RETURNING user_id, role_id, duration_hours;
+		"#,
+		request_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	let now = OffsetDateTime::now_utc();
+	let valid_until = now + Duration::hours(access_request.duration_hours.into());
+
+	let valid_until = query!(
+		r#"
+		INSERT INTO
+			workspace_user(
+				workspace_id,
+				user_id,
+				role_id,
+				valid_from,
+				valid_until
+			)
+		VALUES
+			($1, $2, $3, $4, $5)
+		ON CONFLICT(user_id, workspace_id, role_id) DO UPDATE SET
+			valid_from = CASE
+				WHEN workspace_user.valid_from IS NULL THEN NULL
+				ELSE LEAST(workspace_user.valid_from, EXCLUDED.valid_from)
+			END,
+			valid_until = CASE
+				WHEN workspace_user.valid_until IS NULL THEN NULL
+				ELSE GREATEST(workspace_user.valid_until, EXCLUDED.valid_until)
+			END
+		RETURNING valid_until;
+		"#,
+		workspace_id as _,
+		access_request.user_id,
+		access_request.role_id,
+		now,
+		valid_until,
+	)
+	.fetch_one(&mut **database)
+	.await?
+	.valid_until;
+
+	info!(
+		"User `{}` given role `{}` until `{:?}`. Setting revocation timestamp",
+		access_request.user_id, access_request.role_id, valid_until
+	);
+
+	redis
+		.setex(
+			redis::keys::user_id_revocation_timestamp(&access_request.user_id.into()),
+			constants::CACHED_PERMISSIONS_VALIDITY.whole_seconds() as u64,
+			now.unix_timestamp(),
+		)
+		.await
+		.inspect_err(|err| {
+			error!("Error setting the revocation timestamp: `{}`", err);
+		})?;
+
+	AppResponse::builder()
+		.body(ApproveAccessRequestResponse { valid_until })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,124 @@
+use axum::http::StatusCode;
+use models::api::workspace::rbac::access_request::*;
+
+use crate::prelude::*;
+
+/// The handler to request a role in a workspace for a limited number of hours.
+/// Only members of the workspace can request elevated access. The request stays
+/// pending until it is approved or rejected by a user that can modify the roles
+/// of the workspace.
+pub async fn create_access_request(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: CreateAccessRequestPath { workspace_id },
+				query: (),
+				headers:
+					CreateAccessRequestRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body:
+					CreateAccessRequestRequestProcessed {
+						role_id,
+						duration_hours,
+						reason,
+					},
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data,
+	}: AuthenticatedAppRequest<'_, CreateAccessRequestRequest>,
+) -> Result<AppResponse<CreateAccessRequestRequest>, ErrorType> {
+	info!(
+		"User `{}` is requesting role `{role_id}` in workspace `{workspace_id}`",
+		user_data.id
+	);
+
+	query!(
+		r#"
+		SELECT
+			id
+		FROM
+			role
+		WHERE
+			id = $1 AND
+			owner_id = $2;
+		"#,
+		role_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::RoleDoesNotExist)?;
+
+	// Members whose time-bound roles have expired are still allowed to request
+	// access again
+	let is_member = query!(
+		r#"
+		SELECT
+			user_id
+		FROM
+			workspace_user
+		WHERE
+			workspace_id = $1 AND
+			user_id = $2
+		LIMIT 1;
+		"#,
+		workspace_id as _,
+		user_data.id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.is_some();
+
+	if !is_member {
+		debug!("Only members of the workspace can request elevated access");
+		return Err(ErrorType::Unauthorized);
+	}
+
+	let request_id = Uuid::new_v4();
+
+	query!(
+		r#"
+		INSERT INTO
+			workspace_access_request(
+				id,
+				workspace_id,
+				user_id,
+				role_id,
+				duration_hours,
+				reason,
+				created
+			)
+		VALUES
+			($1, $2, $3, $4, $5, $6, NOW());
+		"#,
+		request_id as _,
+		workspace_id as _,
+		user_data.id as _,
+		role_id as _,
+		duration_hours as i32,
+		reason,
+	)
+	.execute(&mut **database)
+	.await
+	.map_err(|e| match e {
+		sqlx::Error::Database(dbe) if dbe.is_unique_violation() => {
+			debug!("The user has already requested the role");
+			ErrorType::ResourceAlreadyExists
+		}
+		other => other.into(),
+	})?;
+
+	AppResponse::builder()
+		.body(CreateAccessRequestResponse {
+			id: WithId::from(request_id),
+		})
+		.headers(())
+		.status_code(StatusCode::CREATED)
+		.build()
+		.into_result()
+}
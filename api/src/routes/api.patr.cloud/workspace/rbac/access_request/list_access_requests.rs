@@ -0,0 +1,84 @@
+use axum::http::StatusCode;
+use models::{api::workspace::rbac::access_request::*, utils::TotalCountHeader};
+
+use crate::prelude::*;
+
+/// The handler to list all the pending requests for elevated access to a
+/// workspace, oldest first.
+pub async fn list_access_requests(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ListAccessRequestsPath { workspace_id },
+				query: Paginated {
+					data: (),
+					count,
+					page,
+				},
+				headers:
+					ListAccessRequestsRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ListAccessRequestsRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ListAccessRequestsRequest>,
+) -> Result<AppResponse<ListAccessRequestsRequest>, ErrorType> {
+	info!("Listing all access requests of workspace `{workspace_id}`");
+
+	let mut total_count = 0;
+	let access_requests = query!(
+		r#"
+		SELECT
+			id,
+			user_id,
+			role_id,
+			duration_hours,
+			reason,
+			created,
+			COUNT(*) OVER() AS "total_count!"
+		FROM
+			workspace_access_request
+		WHERE
+			workspace_id = $1
+		ORDER BY
+			created
+		LIMIT $2
+		OFFSET $3;
+		"#,
+		workspace_id as _,
+		count as i64,
+		(count * page) as i64,
+	)
+	.fetch_all(&mut **database)
+	.await?
+	.into_iter()
+	.map(|row| {
+		total_count = row.total_count;
+		WithId::new(
+			row.id,
+			WorkspaceAccessRequest {
+				user_id: row.user_id.into(),
+				role_id: row.role_id.into(),
+				duration_hours: row.duration_hours as u32,
+				reason: row.reason,
+				created: row.created,
+			},
+		)
+	})
+	.collect();
+
+	AppResponse::builder()
+		.body(ListAccessRequestsResponse { access_requests })
+		.headers(ListAccessRequestsResponseHeaders {
+			total_count: TotalCountHeader(total_count as _),
+		})
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,24 @@
+use axum::Router;
+
+use crate::prelude::*;
+
+mod approve_access_request;
+mod create_access_request;
+mod list_access_requests;
+mod reject_access_request;
+
+use self::{
+	approve_access_request::*,
+	create_access_request::*,
+	list_access_requests::*,
+	reject_access_request::*,
+};
+
+#[instrument(skip(state))]
+pub async fn setup_routes(state: &AppState) -> Router {
+	Router::new()
+		.mount_auth_endpoint(approve_access_request, state)
+		.mount_auth_endpoint(create_access_request, state)
+		.mount_auth_endpoint(list_access_requests, state)
+		.mount_auth_endpoint(reject_access_request, state)
+}
@@ -0,0 +1,55 @@
+use axum::http::StatusCode;
+use models::api::workspace::rbac::access_request::*;
+
+use crate::prelude::*;
+
+/// The handler to reject a pending request for elevated access to a workspace.
+/// The request is deleted without giving the user the requested role.
+pub async fn reject_access_request(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: RejectAccessRequestPath {
+					workspace_id,
+					request_id,
+				},
+				query: (),
+				headers:
+					RejectAccessRequestRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: RejectAccessRequestRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, RejectAccessRequestRequest>,
+) -> Result<AppResponse<RejectAccessRequestRequest>, ErrorType> {
+	info!("Rejecting access request `{request_id}` of workspace `{workspace_id}`");
+
+	query!(
+		r#"
+		DELETE FROM
+			workspace_access_request
+		WHERE
+			id = $1 AND
+			workspace_id = $2
+		RETURNING id;
+		"#,
+		request_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	AppResponse::builder()
+		.body(RejectAccessRequestResponse)
+		.headers(())
+		.status_code(StatusCode::RESET_CONTENT)
+		.build()
+		.into_result()
+}
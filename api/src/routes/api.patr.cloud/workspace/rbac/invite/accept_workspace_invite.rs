@@ -0,0 +1,61 @@
+use axum::http::StatusCode;
+use models::api::workspace::rbac::invite::*;
+use rustis::commands::StringCommands;
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// The handler to accept an invite to a workspace. The currently logged in
+/// user is added to the workspace with the roles of the invite, and the
+/// revocation timestamp of the user is set in Redis so that the new
+/// permissions are picked up.
+pub async fn accept_workspace_invite(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: AcceptWorkspaceInvitePath,
+				query: (),
+				headers:
+					AcceptWorkspaceInviteRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: AcceptWorkspaceInviteRequestProcessed { invite_token },
+			},
+		database,
+		redis,
+		client_ip: _,
+		config,
+		user_data,
+	}: AuthenticatedAppRequest<'_, AcceptWorkspaceInviteRequest>,
+) -> Result<AppResponse<AcceptWorkspaceInviteRequest>, ErrorType> {
+	info!("User `{}` is accepting a workspace invite", user_data.id);
+
+	let workspace_id = super::accept_workspace_invite_for_user(
+		&mut **database,
+		&config,
+		&user_data.id,
+		&invite_token,
+	)
+	.await?;
+
+	info!("Invite accepted. Setting revocation timestamp");
+
+	redis
+		.setex(
+			redis::keys::user_id_revocation_timestamp(&user_data.id),
+			constants::CACHED_PERMISSIONS_VALIDITY.whole_seconds() as u64,
+			OffsetDateTime::now_utc().unix_timestamp(),
+		)
+		.await
+		.inspect_err(|err| {
+			error!("Error setting the revocation timestamp: `{}`", err);
+		})?;
+
+	AppResponse::builder()
+		.body(AcceptWorkspaceInviteResponse { workspace_id })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,234 @@
+use std::collections::BTreeSet;
+
+use axum::http::StatusCode;
+use models::api::workspace::rbac::invite::*;
+
+use crate::prelude::*;
+
+/// The handler to invite a user to a workspace. The user can be invited either
+/// by their email or by their username. Once the user accepts the invite, they
+/// are added to the workspace with the roles mentioned in the invite.
+pub async fn create_workspace_invite(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: CreateWorkspaceInvitePath { workspace_id },
+				query: (),
+				headers:
+					CreateWorkspaceInviteRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: CreateWorkspaceInviteRequestProcessed { invitee, roles },
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config,
+		user_data,
+	}: AuthenticatedAppRequest<'_, CreateWorkspaceInviteRequest>,
+) -> Result<AppResponse<CreateWorkspaceInviteRequest>, ErrorType> {
+	info!("Creating an invite to workspace `{workspace_id}`");
+
+	let roles = roles.into_iter().collect::<BTreeSet<_>>();
+
+	if roles.is_empty() {
+		debug!("No roles provided for the invite");
+		return Err(ErrorType::WrongParameters);
+	}
+
+	let valid_roles = query!(
+		r#"
+		SELECT
+			COUNT(*) AS "count!"
+		FROM
+			role
+		WHERE
+			owner_id = $1 AND
+			id = ANY($2::UUID[]);
+		"#,
+		workspace_id as _,
+		&roles.iter().map(|role| (*role).into()).collect::<Vec<_>>(),
+	)
+	.fetch_one(&mut **database)
+	.await?
+	.count;
+
+	if valid_roles != roles.len() as i64 {
+		debug!("Some of the roles provided do not exist in the workspace");
+		return Err(ErrorType::RoleDoesNotExist);
+	}
+
+	let (email, user_id) = match invitee {
+		WorkspaceInvitee::Email { email } => {
+			let email = email.to_lowercase();
+
+			let is_member = query!(
+				r#"
+				SELECT
+					workspace_user.user_id
+				FROM
+					workspace_user
+				INNER JOIN
+					user_email
+				ON
+					user_email.user_id = workspace_user.user_id
+				WHERE
+					workspace_user.workspace_id = $1 AND
+					LOWER(user_email.email) = $2
+				LIMIT 1;
+				"#,
+				workspace_id as _,
+				&email,
+			)
+			.fetch_optional(&mut **database)
+			.await?
+			.is_some();
+
+			if is_member {
+				debug!("A user with the email is already a member of the workspace");
+				return Err(ErrorType::ResourceAlreadyExists);
+			}
+
+			(Some(email), None)
+		}
+		WorkspaceInvitee::Username { username } => {
+			let user_id: Uuid = query!(
+				r#"
+				SELECT
+					id
+				FROM
+					"user"
+				WHERE
+					username = $1;
+				"#,
+				&username,
+			)
+			.fetch_optional(&mut **database)
+			.await?
+			.ok_or(ErrorType::UserNotFound)?
+			.id
+			.into();
+
+			let is_member = query!(
+				r#"
+				SELECT
+					id
+				FROM
+					workspace
+				WHERE
+					id = $1 AND
+					(
+						super_admin_id = $2 OR
+						EXISTS(
+							SELECT
+								1
+							FROM
+								workspace_user
+							WHERE
+								workspace_id = $1 AND
+								user_id = $2
+						)
+					);
+				"#,
+				workspace_id as _,
+				user_id as _,
+			)
+			.fetch_optional(&mut **database)
+			.await?
+			.is_some();
+
+			if is_member {
+				debug!("User `{user_id}` is already a member of the workspace");
+				return Err(ErrorType::ResourceAlreadyExists);
+			}
+
+			(None, Some(user_id))
+		}
+	};
+
+	let invite_id = Uuid::new_v4();
+	let token_id = Uuid::new_v4();
+	let expiry = super::new_invite_expiry();
+
+	query!(
+		r#"
+		INSERT INTO
+			workspace_invite(
+				id,
+				workspace_id,
+				email,
+				user_id,
+				invited_by,
+				token_id,
+				created,
+				expiry
+			)
+		VALUES
+			(
+				$1,
+				$2,
+				$3,
+				$4,
+				$5,
+				$6,
+				NOW(),
+				$7
+			);
+		"#,
+		invite_id as _,
+		workspace_id as _,
+		email,
+		user_id as _,
+		user_data.id as _,
+		token_id as _,
+		expiry,
+	)
+	.execute(&mut **database)
+	.await
+	.map_err(|e| match e {
+		sqlx::Error::Database(dbe) if dbe.is_unique_violation() => {
+			debug!("The user has already been invited to the workspace");
+			ErrorType::ResourceAlreadyExists
+		}
+		other => other.into(),
+	})?;
+
+	trace!("Invite created. Inserting roles");
+
+	query!(
+		r#"
+		INSERT INTO
+			workspace_invite_role(
+				invite_id,
+				role_id
+			)
+		VALUES
+			(
+				$1,
+				UNNEST($2::UUID[])
+			);
+		"#,
+		invite_id as _,
+		&roles
+			.into_iter()
+			.map(|role| role.into())
+			.collect::<Vec<_>>(),
+	)
+	.execute(&mut **database)
+	.await?;
+
+	let invite_token = super::generate_invite_token(&config, invite_id, token_id, expiry)?;
+
+	// TODO send the invite link to the invited user via email
+
+	AppResponse::builder()
+		.body(CreateWorkspaceInviteResponse {
+			id: WithId::from(invite_id),
+			invite_token,
+		})
+		.headers(())
+		.status_code(StatusCode::CREATED)
+		.build()
+		.into_result()
+}
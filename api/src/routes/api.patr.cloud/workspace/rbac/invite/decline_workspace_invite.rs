@@ -0,0 +1,42 @@
+use axum::http::StatusCode;
+use models::api::workspace::rbac::invite::*;
+
+use crate::prelude::*;
+
+/// The handler to decline an invite to a workspace. The invite must have been
+/// sent to the currently logged in user. Once declined, the invite is deleted.
+pub async fn decline_workspace_invite(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: DeclineWorkspaceInvitePath,
+				query: (),
+				headers:
+					DeclineWorkspaceInviteRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: DeclineWorkspaceInviteRequestProcessed { invite_token },
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config,
+		user_data,
+	}: AuthenticatedAppRequest<'_, DeclineWorkspaceInviteRequest>,
+) -> Result<AppResponse<DeclineWorkspaceInviteRequest>, ErrorType> {
+	info!("User `{}` is declining a workspace invite", user_data.id);
+
+	let (invite_id, _) =
+		super::get_pending_invite_for_user(&mut **database, &config, &user_data.id, &invite_token)
+			.await?;
+
+	super::delete_invite(&mut **database, &invite_id).await?;
+
+	AppResponse::builder()
+		.body(DeclineWorkspaceInviteResponse)
+		.headers(())
+		.status_code(StatusCode::RESET_CONTENT)
+		.build()
+		.into_result()
+}
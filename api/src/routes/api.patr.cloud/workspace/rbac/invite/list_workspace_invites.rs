@@ -0,0 +1,105 @@
+use axum::http::StatusCode;
+use models::{api::workspace::rbac::invite::*, utils::TotalCountHeader};
+
+use crate::prelude::*;
+
+/// The handler to list all the pending invites of a workspace, along with the
+/// roles that each invite would grant.
+pub async fn list_workspace_invites(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ListWorkspaceInvitesPath { workspace_id },
+				query: Paginated {
+					data: (),
+					count,
+					page,
+				},
+				headers:
+					ListWorkspaceInvitesRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ListWorkspaceInvitesRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ListWorkspaceInvitesRequest>,
+) -> Result<AppResponse<ListWorkspaceInvitesRequest>, ErrorType> {
+	info!("Listing all invites of workspace `{workspace_id}`");
+
+	let mut total_count = 0;
+	let invites = query!(
+		r#"
+		SELECT
+			workspace_invite.id,
+			workspace_invite.email,
+			"user".username AS "username?",
+			workspace_invite.invited_by,
+			workspace_invite.created,
+			workspace_invite.expiry,
+			ARRAY(
+				SELECT
+					role_id
+				FROM
+					workspace_invite_role
+				WHERE
+					invite_id = workspace_invite.id
+			) AS "roles!",
+			COUNT(*) OVER() AS "total_count!"
+		FROM
+			workspace_invite
+		LEFT JOIN
+			"user"
+		ON
+			"user".id = workspace_invite.user_id
+		WHERE
+			workspace_invite.workspace_id = $1 AND
+			workspace_invite.expiry > NOW()
+		ORDER BY
+			workspace_invite.created DESC
+		LIMIT $2
+		OFFSET $3;
+		"#,
+		workspace_id as _,
+		count as i64,
+		(count * page) as i64,
+	)
+	.fetch_all(&mut **database)
+	.await?
+	.into_iter()
+	.filter_map(|row| {
+		total_count = row.total_count;
+		let invitee = match (row.email, row.username) {
+			(Some(email), None) => WorkspaceInvitee::Email { email },
+			(None, Some(username)) => WorkspaceInvitee::Username { username },
+			_ => {
+				error!("Invite `{}` has an invalid invitee", row.id);
+				return None;
+			}
+		};
+		Some(WithId::new(
+			row.id,
+			WorkspaceInvite {
+				invitee,
+				roles: row.roles.into_iter().map(Into::into).collect(),
+				invited_by: row.invited_by.into(),
+				created: row.created,
+				expiry: row.expiry,
+			},
+		))
+	})
+	.collect();
+
+	AppResponse::builder()
+		.body(ListWorkspaceInvitesResponse { invites })
+		.headers(ListWorkspaceInvitesResponseHeaders {
+			total_count: TotalCountHeader(total_count as _),
+		})
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,229 @@
+use std::ops::Add;
+
+use axum::Router;
+use jsonwebtoken::{DecodingKey, EncodingKey, Validation};
+use time::OffsetDateTime;
+
+use crate::{
+	models::workspace_invite_token_data::WorkspaceInviteTokenData,
+	prelude::*,
+	utils::config::AppConfig,
+};
+
+mod accept_workspace_invite;
+mod create_workspace_invite;
+mod decline_workspace_invite;
+mod list_workspace_invites;
+mod resend_workspace_invite;
+mod revoke_workspace_invite;
+
+use self::{
+	accept_workspace_invite::*,
+	create_workspace_invite::*,
+	decline_workspace_invite::*,
+	list_workspace_invites::*,
+	resend_workspace_invite::*,
+	revoke_workspace_invite::*,
+};
+
+#[instrument(skip(state))]
+pub async fn setup_routes(state: &AppState) -> Router {
+	Router::new()
+		.mount_auth_endpoint(accept_workspace_invite, state)
+		.mount_auth_endpoint(create_workspace_invite, state)
+		.mount_auth_endpoint(decline_workspace_invite, state)
+		.mount_auth_endpoint(list_workspace_invites, state)
+		.mount_auth_endpoint(resend_workspace_invite, state)
+		.mount_auth_endpoint(revoke_workspace_invite, state)
+}
+
+/// Generates the signed token for an invite, which is sent to the invited user
+/// as a part of the invite link.
+fn generate_invite_token(
+	config: &AppConfig,
+	invite_id: Uuid,
+	token_id: Uuid,
+	expiry: OffsetDateTime,
+) -> Result<String, ErrorType> {
+	let token = jsonwebtoken::encode(
+		&Default::default(),
+		&WorkspaceInviteTokenData {
+			iss: constants::JWT_ISSUER.to_string(),
+			sub: invite_id,
+			aud: constants::WORKSPACE_INVITE_JWT_AUDIENCE.to_string(),
+			exp: expiry,
+			jti: token_id,
+		},
+		&EncodingKey::from_secret(config.jwt_secret.as_ref()),
+	)
+	.inspect_err(|err| {
+		error!("Error encoding invite token: {err}");
+	})?;
+
+	Ok(token)
+}
+
+/// Parses and validates the signed token of an invite. This only validates the
+/// token itself. Whether the invite is still pending, and who it was sent to,
+/// needs to be checked against the database.
+fn parse_invite_token(
+	config: &AppConfig,
+	invite_token: &str,
+) -> Result<WorkspaceInviteTokenData, ErrorType> {
+	jsonwebtoken::decode::<WorkspaceInviteTokenData>(
+		invite_token,
+		&DecodingKey::from_secret(config.jwt_secret.as_ref()),
+		&{
+			let mut validation = Validation::default();
+
+			validation.set_issuer(&[constants::JWT_ISSUER]);
+			validation.set_audience(&[constants::WORKSPACE_INVITE_JWT_AUDIENCE]);
+
+			validation
+		},
+	)
+	.map(|token| token.claims)
+	.map_err(|err| {
+		info!("Invalid invite token provided: {err}");
+		ErrorType::InvalidWorkspaceInvite
+	})
+}
+
+/// Finds the pending invite that the given token refers to, ensuring that the
+/// invite was sent to the given user, either by their username or by one of
+/// their verified emails. Returns the ID of the invite and the ID of the
+/// workspace that the user is invited to.
+async fn get_pending_invite_for_user(
+	connection: &mut DatabaseConnection,
+	config: &AppConfig,
+	user_id: &Uuid,
+	invite_token: &str,
+) -> Result<(Uuid, Uuid), ErrorType> {
+	let token = parse_invite_token(config, invite_token)?;
+
+	let invite = query!(
+		r#"
+		SELECT
+			workspace_invite.id,
+			workspace_invite.workspace_id
+		FROM
+			workspace_invite
+		INNER JOIN
+			workspace
+		ON
+			workspace.id = workspace_invite.workspace_id
+		WHERE
+			workspace_invite.id = $1 AND
+			workspace_invite.token_id = $2 AND
+			workspace_invite.expiry > NOW() AND
+			workspace.deleted IS NULL AND
+			(
+				workspace_invite.user_id = $3 OR
+				workspace_invite.email IN (
+					SELECT
+						LOWER(email)
+					FROM
+						user_email
+					WHERE
+						user_id = $3
+				)
+			);
+		"#,
+		token.sub as _,
+		token.jti as _,
+		user_id as _,
+	)
+	.fetch_optional(&mut *connection)
+	.await?
+	.ok_or_else(|| {
+		info!("No pending invite found for the given token and user");
+		ErrorType::InvalidWorkspaceInvite
+	})?;
+
+	Ok((invite.id.into(), invite.workspace_id.into()))
+}
+
+/// Deletes an invite, along with the roles that it would have granted
+async fn delete_invite(
+	connection: &mut DatabaseConnection,
+	invite_id: &Uuid,
+) -> Result<(), ErrorType> {
+	query!(
+		r#"
+		DELETE FROM
+			workspace_invite_role
+		WHERE
+			invite_id = $1;
+		"#,
+		invite_id as _,
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	query!(
+		r#"
+		DELETE FROM
+			workspace_invite
+		WHERE
+			id = $1;
+		"#,
+		invite_id as _,
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	Ok(())
+}
+
+/// Accepts an invite to a workspace on behalf of the given user. The user is
+/// added to the workspace with the roles of the invite, and the invite is
+/// deleted. Returns the ID of the workspace that the user is now a member of.
+///
+/// The caller is responsible for revoking the cached permissions of the user.
+pub async fn accept_workspace_invite_for_user(
+	connection: &mut DatabaseConnection,
+	config: &AppConfig,
+	user_id: &Uuid,
+	invite_token: &str,
+) -> Result<Uuid, ErrorType> {
+	let (invite_id, workspace_id) =
+		get_pending_invite_for_user(&mut *connection, config, user_id, invite_token).await?;
+
+	info!("User `{user_id}` is accepting invite `{invite_id}` to workspace `{workspace_id}`");
+
+	query!(
+		r#"
+		INSERT INTO
+			workspace_user(
+				user_id,
+				workspace_id,
+				role_id
+			)
+		SELECT
+			$1,
+			$2,
+			role_id
+		FROM
+			workspace_invite_role
+		WHERE
+			invite_id = $3
+		ON CONFLICT DO NOTHING;
+		"#,
+		user_id as _,
+		workspace_id as _,
+		invite_id as _,
+	)
+	.execute(&mut *connection)
+	.await?;
+
+	trace!("User added to the workspace. Deleting invite");
+
+	delete_invite(&mut *connection, &invite_id).await?;
+
+	Ok(workspace_id)
+}
+
+/// Computes the expiry of an invite that is created or resent right now
+fn new_invite_expiry() -> OffsetDateTime {
+	OffsetDateTime::now_utc().add(constants::WORKSPACE_INVITE_VALIDITY)
+}
@@ -0,0 +1,68 @@
+use axum::http::StatusCode;
+use models::api::workspace::rbac::invite::*;
+
+use crate::prelude::*;
+
+/// The handler to resend an invite to a workspace. This generates a new token
+/// for the invite and extends its expiry, so that any previously sent invite
+/// links can no longer be used.
+pub async fn resend_workspace_invite(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ResendWorkspaceInvitePath {
+					workspace_id,
+					invite_id,
+				},
+				query: (),
+				headers:
+					ResendWorkspaceInviteRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ResendWorkspaceInviteRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ResendWorkspaceInviteRequest>,
+) -> Result<AppResponse<ResendWorkspaceInviteRequest>, ErrorType> {
+	info!("Resending invite `{invite_id}` of workspace `{workspace_id}`");
+
+	let token_id = Uuid::new_v4();
+	let expiry = super::new_invite_expiry();
+
+	query!(
+		r#"
+		UPDATE
+			workspace_invite
+		SET
+			token_id = $3,
+			expiry = $4
+		WHERE
+			id = $1 AND
+			workspace_id = $2
+		RETURNING id;
+		"#,
+		invite_id as _,
+		workspace_id as _,
+		token_id as _,
+		expiry,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	let invite_token = super::generate_invite_token(&config, invite_id, token_id, expiry)?;
+
+	// TODO send the invite link to the invited user via email
+
+	AppResponse::builder()
+		.body(ResendWorkspaceInviteResponse { invite_token })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
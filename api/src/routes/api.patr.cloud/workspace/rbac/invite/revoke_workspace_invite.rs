@@ -0,0 +1,58 @@
+use axum::http::StatusCode;
+use models::api::workspace::rbac::invite::*;
+
+use crate::prelude::*;
+
+/// The handler to revoke a pending invite to a workspace. Once revoked, the
+/// invite can no longer be accepted.
+pub async fn revoke_workspace_invite(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: RevokeWorkspaceInvitePath {
+					workspace_id,
+					invite_id,
+				},
+				query: (),
+				headers:
+					RevokeWorkspaceInviteRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: RevokeWorkspaceInviteRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, RevokeWorkspaceInviteRequest>,
+) -> Result<AppResponse<RevokeWorkspaceInviteRequest>, ErrorType> {
+	info!("Revoking invite `{invite_id}` of workspace `{workspace_id}`");
+
+	query!(
+		r#"
+		SELECT
+			id
+		FROM
+			workspace_invite
+		WHERE
+			id = $1 AND
+			workspace_id = $2;
+		"#,
+		invite_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	super::delete_invite(&mut **database, &invite_id).await?;
+
+	AppResponse::builder()
+		.body(RevokeWorkspaceInviteResponse)
+		.headers(())
+		.status_code(StatusCode::RESET_CONTENT)
+		.build()
+		.into_result()
+}
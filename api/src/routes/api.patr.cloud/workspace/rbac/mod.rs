@@ -2,13 +2,19 @@ use axum::Router;
 
 use crate::prelude::*;
 
+mod access_request;
+mod invite;
 mod permission;
 mod role;
 mod user;
 
+pub(crate) use self::invite::accept_workspace_invite_for_user;
+
 #[instrument(skip(state))]
 pub async fn setup_routes(state: &AppState) -> Router {
 	Router::new()
+		.merge(access_request::setup_routes(state).await)
+		.merge(invite::setup_routes(state).await)
 		.merge(permission::setup_routes(state).await)
 		.merge(role::setup_routes(state).await)
 		.merge(user::setup_routes(state).await)
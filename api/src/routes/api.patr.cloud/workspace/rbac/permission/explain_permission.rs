@@ -0,0 +1,85 @@
+use axum::http::StatusCode;
+use models::api::workspace::rbac::*;
+
+use super::{explain_permission_for_user, get_ids_matching_resource};
+use crate::prelude::*;
+
+/// The handler to explain whether a user (or an API token) has a permission on
+/// a resource in the workspace. This returns the decision, along with the
+/// roles and rules that led to it, which is useful to debug why a request was
+/// not authorized.
+pub async fn explain_permission(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ExplainPermissionPath { workspace_id },
+				query:
+					ExplainPermissionQuery {
+						user_id,
+						token_id,
+						permission_id,
+						resource_id,
+					},
+				headers:
+					ExplainPermissionRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ExplainPermissionRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ExplainPermissionRequest>,
+) -> Result<AppResponse<ExplainPermissionRequest>, ErrorType> {
+	info!("Explaining permission `{permission_id}` on resource `{resource_id}`");
+
+	let user_id = match (user_id, token_id) {
+		(Some(user_id), None) => user_id,
+		(None, Some(token_id)) => query!(
+			r#"
+			SELECT
+				user_id
+			FROM
+				user_api_token
+			WHERE
+				token_id = $1;
+			"#,
+			token_id as _
+		)
+		.fetch_optional(&mut **database)
+		.await?
+		.ok_or(ErrorType::ResourceDoesNotExist)?
+		.user_id
+		.into(),
+		_ => {
+			info!("Exactly one of the user ID or the token ID must be provided");
+			return Err(ErrorType::WrongParameters);
+		}
+	};
+
+	let resource_ids =
+		get_ids_matching_resource(&mut **database, &workspace_id, &permission_id, &resource_id)
+			.await?;
+
+	let (allowed, decision) = explain_permission_for_user(
+		&mut **database,
+		&workspace_id,
+		&user_id,
+		token_id.as_ref(),
+		&permission_id,
+		&resource_ids,
+	)
+	.await?;
+
+	trace!("Permission allowed: {allowed}");
+
+	AppResponse::builder()
+		.body(ExplainPermissionResponse { allowed, decision })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
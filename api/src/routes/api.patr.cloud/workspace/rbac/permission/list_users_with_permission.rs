@@ -0,0 +1,90 @@
+use std::collections::BTreeSet;
+
+use axum::http::StatusCode;
+use models::api::workspace::rbac::*;
+
+use super::{explain_permission_for_user, get_ids_matching_resource};
+use crate::prelude::*;
+
+/// The handler to list all the users in the workspace that have a permission on
+/// a resource. Each user is returned along with the reason they have the
+/// permission. Users that do not have the permission are not returned.
+pub async fn list_users_with_permission(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ListUsersWithPermissionPath {
+					workspace_id,
+					permission_id,
+				},
+				query: ListUsersWithPermissionQuery { resource_id },
+				headers:
+					ListUsersWithPermissionRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ListUsersWithPermissionRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ListUsersWithPermissionRequest>,
+) -> Result<AppResponse<ListUsersWithPermissionRequest>, ErrorType> {
+	info!("Listing users with permission `{permission_id}` on resource `{resource_id}`");
+
+	let resource_ids =
+		get_ids_matching_resource(&mut **database, &workspace_id, &permission_id, &resource_id)
+			.await?;
+
+	let members = query!(
+		r#"
+		SELECT
+			super_admin_id AS "user_id!"
+		FROM
+			workspace
+		WHERE
+			id = $1
+		UNION
+		SELECT
+			user_id
+		FROM
+			workspace_user
+		WHERE
+			workspace_id = $1 AND
+			(valid_from IS NULL OR valid_from <= NOW()) AND
+			(valid_until IS NULL OR valid_until > NOW());
+		"#,
+		workspace_id as _
+	)
+	.fetch_all(&mut **database)
+	.await?
+	.into_iter()
+	.map(|row| row.user_id.into())
+	.collect::<BTreeSet<Uuid>>();
+
+	let mut users = Vec::new();
+	for user_id in members {
+		let (allowed, decision) = explain_permission_for_user(
+			&mut **database,
+			&workspace_id,
+			&user_id,
+			None,
+			&permission_id,
+			&resource_ids,
+		)
+		.await?;
+
+		if allowed {
+			users.push(WithId::new(user_id, decision));
+		}
+	}
+
+	AppResponse::builder()
+		.body(ListUsersWithPermissionResponse { users })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -1,18 +1,311 @@
 use axum::Router;
+use models::{
+	api::workspace::rbac::{PermissionDecision, PermissionRule, PermissionRuleSource},
+	rbac::ResourcePermissionTypeDiscriminant,
+};
 
 use crate::prelude::*;
 
+mod explain_permission;
 mod get_current_permissions;
 mod list_all_permissions;
 mod list_all_resource_types;
+mod list_users_with_permission;
 
-pub use self::{get_current_permissions::*, list_all_permissions::*, list_all_resource_types::*};
+pub use self::{
+	explain_permission::*,
+	get_current_permissions::*,
+	list_all_permissions::*,
+	list_all_resource_types::*,
+	list_users_with_permission::*,
+};
 
 #[instrument(skip(state))]
 pub async fn setup_routes(state: &AppState) -> Router {
 	Router::new()
+		.mount_auth_endpoint(explain_permission, state)
 		.mount_auth_endpoint(get_current_permissions, state)
 		.mount_auth_endpoint(list_all_permissions, state)
 		.mount_auth_endpoint(list_all_resource_types, state)
+		.mount_auth_endpoint(list_users_with_permission, state)
 		.with_state(state.clone())
 }
+
+/// Ensures that the permission exists and that the resource belongs to the
+/// workspace. Returns the IDs that a permission rule can list to match the
+/// resource, which is the resource itself and the project that it is a part
+/// of, if any.
+async fn get_ids_matching_resource(
+	connection: &mut DatabaseConnection,
+	workspace_id: &Uuid,
+	permission_id: &Uuid,
+	resource_id: &Uuid,
+) -> Result<Vec<Uuid>, ErrorType> {
+	query!(
+		r#"
+		SELECT
+			id
+		FROM
+			permission
+		WHERE
+			id = $1;
+		"#,
+		permission_id as _
+	)
+	.fetch_optional(&mut *connection)
+	.await?
+	.ok_or(ErrorType::WrongParameters)?;
+
+	let resource = query!(
+		r#"
+		SELECT
+			id,
+			project_id
+		FROM
+			resource
+		WHERE
+			id = $1 AND
+			owner_id = $2 AND
+			deleted IS NULL;
+		"#,
+		resource_id as _,
+		workspace_id as _
+	)
+	.fetch_optional(&mut *connection)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	Ok([Some(resource.id), resource.project_id]
+		.into_iter()
+		.flatten()
+		.map(Into::into)
+		.collect())
+}
+
+/// Explains whether a user has a permission on a resource in a workspace. If an
+/// API token is given, the permission is explained for the token instead, which
+/// is limited to the permissions of the token itself. Returns whether the
+/// permission is granted, along with the reason for the decision.
+///
+/// The `resource_ids` are the IDs that a rule can list to match the resource,
+/// as returned by [`get_ids_matching_resource`].
+async fn explain_permission_for_user(
+	connection: &mut DatabaseConnection,
+	workspace_id: &Uuid,
+	user_id: &Uuid,
+	token_id: Option<&Uuid>,
+	permission_id: &Uuid,
+	resource_ids: &[Uuid],
+) -> Result<(bool, PermissionDecision), ErrorType> {
+	let is_super_admin = query!(
+		r#"
+		SELECT
+			super_admin_id
+		FROM
+			workspace
+		WHERE
+			id = $1;
+		"#,
+		workspace_id as _
+	)
+	.fetch_optional(&mut *connection)
+	.await?
+	.is_some_and(|workspace| Uuid::from(workspace.super_admin_id) == *user_id);
+
+	if is_super_admin && token_id.is_none() {
+		return Ok((true, PermissionDecision::SuperAdmin));
+	}
+
+	let is_member = is_super_admin ||
+		query!(
+			r#"
+			SELECT
+				user_id
+			FROM
+				workspace_user
+			WHERE
+				workspace_id = $1 AND
+				user_id = $2 AND
+				(valid_from IS NULL OR valid_from <= NOW()) AND
+				(valid_until IS NULL OR valid_until > NOW())
+			LIMIT 1;
+			"#,
+			workspace_id as _,
+			user_id as _
+		)
+		.fetch_optional(&mut *connection)
+		.await?
+		.is_some();
+
+	if !is_member {
+		return Ok((false, PermissionDecision::NotAMember));
+	}
+
+	let resource_ids = resource_ids
+		.iter()
+		.map(|resource_id| (*resource_id).into())
+		.collect::<Vec<_>>();
+
+	let rules = if let Some(token_id) = token_id {
+		let is_token_super_admin = query!(
+			r#"
+			SELECT
+				token_id
+			FROM
+				user_api_token_workspace_super_admin
+			WHERE
+				token_id = $1 AND
+				workspace_id = $2;
+			"#,
+			token_id as _,
+			workspace_id as _
+		)
+		.fetch_optional(&mut *connection)
+		.await?
+		.is_some();
+
+		if is_token_super_admin {
+			return Ok((true, PermissionDecision::ApiTokenSuperAdmin));
+		}
+
+		query!(
+			r#"
+			SELECT
+				resource_permission_type AS "permission_type: ResourcePermissionTypeDiscriminant",
+				(
+					SELECT
+						resource_id
+					FROM
+						user_api_token_resource_permissions_include
+					WHERE
+						token_id = $1 AND
+						workspace_id = $2 AND
+						permission_id = $3 AND
+						resource_id = ANY($4)
+					LIMIT 1
+				) AS "included_resource_id",
+				(
+					SELECT
+						resource_id
+					FROM
+						user_api_token_resource_permissions_exclude
+					WHERE
+						token_id = $1 AND
+						workspace_id = $2 AND
+						permission_id = $3 AND
+						resource_id = ANY($4)
+					LIMIT 1
+				) AS "excluded_resource_id"
+			FROM
+				user_api_token_resource_permissions_type
+			WHERE
+				token_id = $1 AND
+				workspace_id = $2 AND
+				permission_id = $3;
+			"#,
+			token_id as _,
+			workspace_id as _,
+			permission_id as _,
+			&resource_ids,
+		)
+		.fetch_all(&mut *connection)
+		.await?
+		.into_iter()
+		.map(|row| PermissionRule {
+			source: PermissionRuleSource::ApiToken {
+				token_id: *token_id,
+			},
+			permission_type: row.permission_type,
+			matched_resource_id: match row.permission_type {
+				ResourcePermissionTypeDiscriminant::Include => row.included_resource_id,
+				ResourcePermissionTypeDiscriminant::Exclude => row.excluded_resource_id,
+			}
+			.map(Into::into),
+		})
+		.collect::<Vec<_>>()
+	} else {
+		query!(
+			r#"
+			SELECT
+				role.id,
+				role.name,
+				role_resource_permissions_type.permission_type AS "permission_type: ResourcePermissionTypeDiscriminant",
+				(
+					SELECT
+						resource_id
+					FROM
+						role_resource_permissions_include
+					WHERE
+						role_id = role.id AND
+						permission_id = $3 AND
+						resource_id = ANY($4)
+					LIMIT 1
+				) AS "included_resource_id",
+				(
+					SELECT
+						resource_id
+					FROM
+						role_resource_permissions_exclude
+					WHERE
+						role_id = role.id AND
+						permission_id = $3 AND
+						resource_id = ANY($4)
+					LIMIT 1
+				) AS "excluded_resource_id"
+			FROM
+				workspace_user
+			INNER JOIN
+				role
+			ON
+				role.id = workspace_user.role_id
+			INNER JOIN
+				role_resource_permissions_type
+			ON
+				role_resource_permissions_type.role_id = role.id
+			WHERE
+				workspace_user.workspace_id = $1 AND
+				workspace_user.user_id = $2 AND
+				(workspace_user.valid_from IS NULL OR workspace_user.valid_from <= NOW()) AND
+				(workspace_user.valid_until IS NULL OR workspace_user.valid_until > NOW()) AND
+				role_resource_permissions_type.permission_id = $3
+			ORDER BY
+				role.name;
+			"#,
+			workspace_id as _,
+			user_id as _,
+			permission_id as _,
+			&resource_ids,
+		)
+		.fetch_all(&mut *connection)
+		.await?
+		.into_iter()
+		.map(|row| PermissionRule {
+			source: PermissionRuleSource::Role {
+				role_id: row.id.into(),
+				name: row.name,
+			},
+			permission_type: row.permission_type,
+			matched_resource_id: match row.permission_type {
+				ResourcePermissionTypeDiscriminant::Include => row.included_resource_id,
+				ResourcePermissionTypeDiscriminant::Exclude => row.excluded_resource_id,
+			}
+			.map(Into::into),
+		})
+		.collect::<Vec<_>>()
+	};
+
+	// Includes take precedence over excludes, the same way they do when the
+	// permissions of a login are calculated
+	let included = rules.iter().any(|rule| {
+		rule.permission_type == ResourcePermissionTypeDiscriminant::Include &&
+			rule.matched_resource_id.is_some()
+	});
+	let mut excludes = rules
+		.iter()
+		.filter(|rule| rule.permission_type == ResourcePermissionTypeDiscriminant::Exclude)
+		.peekable();
+	let allowed = included ||
+		(excludes.peek().is_some() && excludes.all(|rule| rule.matched_resource_id.is_none()));
+
+	Ok((allowed, PermissionDecision::Rules { rules }))
+}
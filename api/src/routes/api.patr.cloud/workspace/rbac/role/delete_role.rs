@@ -58,6 +58,20 @@ pub async fn delete_role(
 		return Err(ErrorType::RoleInUse);
 	}
 
+	query!(
+		r#"
+		DELETE FROM
+			workspace_access_request
+		WHERE
+			role_id = $1;
+		"#,
+		role_id as _
+	)
+	.execute(&mut **database)
+	.await?;
+
+	trace!("Deleted all the pending access requests for the role");
+
 	query!(
 		r#"
         DELETE FROM
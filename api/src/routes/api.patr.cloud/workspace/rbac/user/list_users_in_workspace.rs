@@ -42,7 +42,8 @@ pub async fn list_users_in_workspace(
         FROM
             workspace_user
         WHERE
-            workspace_id = $1
+            workspace_id = $1 AND
+            (valid_until IS NULL OR valid_until > NOW())
         ORDER BY
             user_id, role_id
         LIMIT $2
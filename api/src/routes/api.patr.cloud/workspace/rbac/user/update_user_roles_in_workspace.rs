@@ -7,7 +7,8 @@ use crate::prelude::*;
 
 /// The handler to update a user's roles in a workspace. This requires the user
 /// who is sending the request to have the permission to update roles in the
-/// workspace.
+/// workspace. The roles can optionally be given for a limited amount of time,
+/// after which the authenticator stops considering them.
 pub async fn update_user_roles_in_workspace(
 	AuthenticatedAppRequest {
 		request:
@@ -22,7 +23,12 @@ pub async fn update_user_roles_in_workspace(
 						authorization: _,
 						user_agent: _,
 					},
-				body: UpdateUserRolesInWorkspaceRequestProcessed { roles },
+				body:
+					UpdateUserRolesInWorkspaceRequestProcessed {
+						roles,
+						valid_from,
+						valid_until,
+					},
 			},
 		database,
 		redis,
@@ -33,6 +39,39 @@ pub async fn update_user_roles_in_workspace(
 ) -> Result<AppResponse<UpdateUserRolesInWorkspaceRequest>, ErrorType> {
 	info!("Updating user `{user_id}`'s roles in workspace `{workspace_id}`");
 
+	if let Some(valid_until) = valid_until {
+		if valid_until <= valid_from.unwrap_or_else(OffsetDateTime::now_utc) {
+			debug!("The roles would expire before they are valid");
+			return Err(ErrorType::WrongParameters);
+		}
+	}
+
+	// Service accounts can only be given roles in the workspace that owns them
+	let foreign_service_account = query!(
+		r#"
+		SELECT
+			id
+		FROM
+			service_account
+		WHERE
+			id = $1 AND
+			(
+				workspace_id != $2 OR
+				deleted IS NOT NULL
+			);
+		"#,
+		user_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.is_some();
+
+	if foreign_service_account {
+		debug!("The user is not a service account of the workspace");
+		return Err(ErrorType::ResourceDoesNotExist);
+	}
+
 	query!(
 		r#"
 		DELETE FROM
@@ -53,10 +92,12 @@ pub async fn update_user_roles_in_workspace(
 			workspace_user(
 				workspace_id,
 				user_id,
-				role_id
+				role_id,
+				valid_from,
+				valid_until
 			)
 		VALUES
-			($1, $2, UNNEST($3::UUID[]));
+			($1, $2, UNNEST($3::UUID[]), $4, $5);
 		"#,
 		workspace_id as _,
 		user_id as _,
@@ -64,6 +105,8 @@ pub async fn update_user_roles_in_workspace(
 			.into_iter()
 			.map(|role| role.into())
 			.collect::<Vec<_>>(),
+		valid_from,
+		valid_until,
 	)
 	.execute(&mut **database)
 	.await?;
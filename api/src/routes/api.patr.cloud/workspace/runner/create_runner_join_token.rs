@@ -0,0 +1,120 @@
+use argon2::{password_hash::SaltString, Algorithm, PasswordHasher, Version};
+use axum::http::StatusCode;
+use models::api::workspace::runner::*;
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// The handler to create a one-time join token for a runner. The runner
+/// exchanges the join token for an API token of the given service account when
+/// it starts, using the [`join_runner`][super::join_runner] handler.
+pub async fn create_runner_join_token(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: CreateRunnerJoinTokenPath {
+					workspace_id,
+					runner_id,
+				},
+				query: (),
+				headers:
+					CreateRunnerJoinTokenRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: CreateRunnerJoinTokenRequestProcessed { service_account_id },
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, CreateRunnerJoinTokenRequest>,
+) -> Result<AppResponse<CreateRunnerJoinTokenRequest>, ErrorType> {
+	info!("Creating join token for runner `{runner_id}`");
+
+	query!(
+		r#"
+		SELECT
+			id
+		FROM
+			runner
+		WHERE
+			id = $1 AND
+			workspace_id = $2 AND
+			deleted IS NULL;
+		"#,
+		runner_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	super::super::service_account::ensure_service_account_in_workspace(
+		&mut **database,
+		&workspace_id,
+		&service_account_id,
+	)
+	.await?;
+
+	let now = OffsetDateTime::now_utc();
+	let expiry = now + constants::RUNNER_JOIN_TOKEN_VALIDITY;
+
+	let join_token_id = Uuid::new_v4();
+	let secret = Uuid::new_v4();
+	let hashed_secret = argon2::Argon2::new_with_secret(
+		config.password_pepper.as_ref(),
+		Algorithm::Argon2id,
+		Version::V0x13,
+		constants::HASHING_PARAMS,
+	)
+	.inspect_err(|err| {
+		error!("Error creating Argon2: `{}`", err);
+	})
+	.map_err(ErrorType::server_error)?
+	.hash_password(
+		secret.as_bytes(),
+		SaltString::generate(&mut rand::thread_rng()).as_salt(),
+	)
+	.inspect_err(|err| {
+		error!("Error hashing join token: `{}`", err);
+	})
+	.map_err(ErrorType::server_error)?
+	.to_string();
+
+	query!(
+		r#"
+		INSERT INTO
+			runner_join_token(
+				id,
+				runner_id,
+				service_account_id,
+				token_hash,
+				created,
+				expiry,
+				used
+			)
+		VALUES
+			($1, $2, $3, $4, $5, $6, NULL);
+		"#,
+		join_token_id as _,
+		runner_id as _,
+		service_account_id as _,
+		&hashed_secret,
+		now,
+		expiry,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	AppResponse::builder()
+		.body(CreateRunnerJoinTokenResponse {
+			join_token: format!("patrjoin.{}.{}", secret, join_token_id),
+			expiry,
+		})
+		.headers(())
+		.status_code(StatusCode::CREATED)
+		.build()
+		.into_result()
+}
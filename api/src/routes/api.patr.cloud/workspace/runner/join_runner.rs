@@ -0,0 +1,138 @@
+use argon2::{Algorithm, PasswordHash, PasswordVerifier, Version};
+use axum::http::StatusCode;
+use models::api::workspace::runner::*;
+
+use crate::prelude::*;
+
+/// The handler for a runner to exchange its join token for an API token of the
+/// service account that the join token was created for. The join token is
+/// marked as used, so that it cannot be exchanged again.
+pub async fn join_runner(
+	AppRequest {
+		request:
+			ProcessedApiRequest {
+				path: JoinRunnerPath,
+				query: (),
+				headers: JoinRunnerRequestHeaders { user_agent: _ },
+				body: JoinRunnerRequestProcessed { join_token },
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config,
+	}: AppRequest<'_, JoinRunnerRequest>,
+) -> Result<AppResponse<JoinRunnerRequest>, ErrorType> {
+	info!("Exchanging join token of a runner");
+
+	let (secret, join_token_id) = join_token
+		.strip_prefix("patrjoin.")
+		.and_then(|token| token.split_once('.'))
+		.ok_or_else(|| {
+			debug!("Invalid join token provided");
+			ErrorType::AuthorizationTokenInvalid
+		})?;
+
+	let secret = Uuid::parse_str(secret).map_err(|err| {
+		debug!("Cannot parse the secret of the join token as UUID: {}", err);
+		ErrorType::AuthorizationTokenInvalid
+	})?;
+	let join_token_id = Uuid::parse_str(join_token_id).map_err(|err| {
+		debug!("Cannot parse the ID of the join token as UUID: {}", err);
+		ErrorType::AuthorizationTokenInvalid
+	})?;
+
+	let token = query!(
+		r#"
+		SELECT
+			runner_join_token.token_hash,
+			runner.id AS "runner_id",
+			runner.workspace_id,
+			service_account.id AS "service_account_id"
+		FROM
+			runner_join_token
+		INNER JOIN
+			runner
+		ON
+			runner.id = runner_join_token.runner_id
+		INNER JOIN
+			service_account
+		ON
+			service_account.id = runner_join_token.service_account_id
+		WHERE
+			runner_join_token.id = $1 AND
+			runner_join_token.used IS NULL AND
+			runner_join_token.expiry > NOW() AND
+			runner.deleted IS NULL AND
+			service_account.deleted IS NULL AND
+			service_account.workspace_id = runner.workspace_id
+		FOR UPDATE OF runner_join_token;
+		"#,
+		join_token_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or_else(|| {
+		debug!("Join token `{join_token_id}` does not exist or cannot be used");
+		ErrorType::AuthorizationTokenInvalid
+	})?;
+
+	let success = argon2::Argon2::new_with_secret(
+		config.password_pepper.as_ref(),
+		Algorithm::Argon2id,
+		Version::V0x13,
+		constants::HASHING_PARAMS,
+	)
+	.inspect_err(|err| {
+		error!("Error creating Argon2: `{}`", err);
+	})
+	.map_err(ErrorType::server_error)?
+	.verify_password(
+		secret.as_bytes(),
+		&PasswordHash::new(&token.token_hash).map_err(ErrorType::server_error)?,
+	)
+	.is_ok();
+
+	if !success {
+		debug!("Join token `{join_token_id}` does not match");
+		return Err(ErrorType::AuthorizationTokenInvalid);
+	}
+
+	query!(
+		r#"
+		UPDATE
+			runner_join_token
+		SET
+			used = NOW()
+		WHERE
+			id = $1;
+		"#,
+		join_token_id as _,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	let runner_id = Uuid::from(token.runner_id);
+	let (_, api_token) = super::super::service_account::create_api_token_for_service_account(
+		&mut **database,
+		&config,
+		&token.service_account_id.into(),
+		&format!("runner-{}-{}", runner_id, join_token_id),
+		None,
+		None,
+		None,
+	)
+	.await?;
+
+	info!("Runner `{runner_id}` joined");
+
+	AppResponse::builder()
+		.body(JoinRunnerResponse {
+			workspace_id: token.workspace_id.into(),
+			runner_id,
+			api_token,
+		})
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -3,16 +3,26 @@ use axum::Router;
 use crate::prelude::*;
 
 mod add_runner_to_workspace;
+mod create_runner_join_token;
 mod get_runner_info;
+mod join_runner;
 mod list_runners_for_workspace;
 mod remove_runner_from_workspace;
+mod report_ci_pipeline_run;
+mod report_ci_step_logs;
+mod report_running_replicas;
 mod stream_runner_data_for_workspace;
 
 use self::{
 	add_runner_to_workspace::*,
+	create_runner_join_token::*,
 	get_runner_info::*,
+	join_runner::*,
 	list_runners_for_workspace::*,
 	remove_runner_from_workspace::*,
+	report_ci_pipeline_run::*,
+	report_ci_step_logs::*,
+	report_running_replicas::*,
 	stream_runner_data_for_workspace::*,
 };
 
@@ -24,4 +34,9 @@ pub async fn setup_routes(state: &AppState) -> Router {
 		.mount_auth_endpoint(remove_runner_from_workspace, state)
 		.mount_auth_endpoint(list_runners_for_workspace, state)
 		.mount_auth_endpoint(get_runner_info, state)
+		.mount_auth_endpoint(create_runner_join_token, state)
+		.mount_auth_endpoint(report_ci_pipeline_run, state)
+		.mount_auth_endpoint(report_ci_step_logs, state)
+		.mount_auth_endpoint(report_running_replicas, state)
+		.mount_endpoint(join_runner, state)
 }
@@ -0,0 +1,117 @@
+use axum::http::StatusCode;
+use models::{
+	api::workspace::{ci::CiRunState, runner::*},
+	ci::{CiPipelineRunReport, CiRunStatus, GitProvider},
+	prelude::*,
+};
+use sqlx::types::Json;
+
+use crate::{
+	ci::{CommitStatus, ProviderRepository},
+	prelude::*,
+};
+
+/// The handler for a runner to report how a CI pipeline run went. The run is
+/// marked as complete with the report, and its status is reported back to the
+/// commit it was run on. A run can only be reported once, by the runner it was
+/// queued on.
+pub async fn report_ci_pipeline_run(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path:
+					ReportCiPipelineRunPath {
+						workspace_id,
+						runner_id,
+						run_id,
+					},
+				query: (),
+				headers:
+					ReportCiPipelineRunRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body:
+					ReportCiPipelineRunRequestProcessed {
+						report:
+							CiPipelineRunReport {
+								status,
+								error,
+								steps,
+							},
+					},
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ReportCiPipelineRunRequest>,
+) -> Result<AppResponse<ReportCiPipelineRunRequest>, ErrorType> {
+	info!("Runner `{runner_id}` reporting CI pipeline run `{run_id}`");
+
+	// Runs of repositories that were disconnected after they were queued are
+	// still completed, so the repository is not checked for being deleted
+	let run = query!(
+		r#"
+		UPDATE
+			ci_run
+		SET
+			state = $4,
+			error = $5,
+			steps = $6,
+			finished = NOW()
+		FROM
+			ci_repository
+		WHERE
+			ci_run.id = $1 AND
+			ci_run.runner_id = $2 AND
+			ci_run.state = 'queued' AND
+			ci_repository.id = ci_run.repository_id AND
+			ci_repository.workspace_id = $3
+		RETURNING
+			ci_run.commit_sha,
+			ci_repository.provider AS "provider: GitProvider",
+			ci_repository.api_url,
+			ci_repository.full_name,
+			ci_repository.access_token;
+		"#,
+		run_id as _,
+		runner_id as _,
+		workspace_id as _,
+		match status {
+			CiRunStatus::Succeeded => CiRunState::Succeeded,
+			CiRunStatus::Failed => CiRunState::Failed,
+		} as _,
+		error,
+		Json(&steps) as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	ProviderRepository {
+		provider: run.provider,
+		api_url: run.api_url,
+		full_name: run.full_name,
+		access_token: run.access_token,
+	}
+	.spawn_commit_status_report(
+		run.commit_sha,
+		match status {
+			CiRunStatus::Succeeded => CommitStatus::Success,
+			CiRunStatus::Failed => CommitStatus::Failure,
+		},
+		match status {
+			CiRunStatus::Succeeded => "The pipeline succeeded",
+			CiRunStatus::Failed => "The pipeline failed",
+		},
+	);
+
+	AppResponse::builder()
+		.body(ReportCiPipelineRunResponse)
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,107 @@
+use axum::http::StatusCode;
+use models::{api::workspace::runner::*, ci::CiStepLog};
+
+use crate::prelude::*;
+
+/// The handler for a runner to send the logs of the steps of a CI pipeline run
+/// while the run is in progress. Logs can only be sent by the runner the run is
+/// queued on, until the run is reported as complete. Lines that were already
+/// received are ignored, so that a batch can be sent again.
+pub async fn report_ci_step_logs(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path:
+					ReportCiStepLogsPath {
+						workspace_id,
+						runner_id,
+						run_id,
+					},
+				query: (),
+				headers:
+					ReportCiStepLogsRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ReportCiStepLogsRequestProcessed { first_line, logs },
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ReportCiStepLogsRequest>,
+) -> Result<AppResponse<ReportCiStepLogsRequest>, ErrorType> {
+	trace!(
+		"Runner `{runner_id}` sending {} lines of logs of CI run `{run_id}`",
+		logs.len()
+	);
+
+	let first_line = i64::try_from(first_line).map_err(|_| ErrorType::WrongParameters)?;
+
+	query!(
+		r#"
+		SELECT
+			ci_run.id
+		FROM
+			ci_run
+		INNER JOIN
+			ci_repository
+		ON
+			ci_run.repository_id = ci_repository.id
+		WHERE
+			ci_run.id = $1 AND
+			ci_run.runner_id = $2 AND
+			ci_run.state = 'queued' AND
+			ci_repository.workspace_id = $3;
+		"#,
+		run_id as _,
+		runner_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	let (steps, lines): (Vec<_>, Vec<_>) = logs
+		.into_iter()
+		.map(|CiStepLog { step, line }| (step, line))
+		.unzip();
+
+	query!(
+		r#"
+		INSERT INTO
+			ci_run_log(
+				run_id,
+				line_number,
+				step,
+				line,
+				logged
+			)
+		SELECT
+			$1,
+			$2 + log.ordinality - 1,
+			log.step,
+			log.line,
+			NOW()
+		FROM
+			UNNEST($3::TEXT[], $4::TEXT[]) WITH ORDINALITY AS log(step, line, ordinality)
+		ON CONFLICT
+			(run_id, line_number)
+		DO NOTHING;
+		"#,
+		run_id as _,
+		first_line,
+		&steps,
+		&lines,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	AppResponse::builder()
+		.body(ReportCiStepLogsResponse)
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
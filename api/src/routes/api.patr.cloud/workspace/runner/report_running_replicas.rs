@@ -0,0 +1,82 @@
+use axum::http::StatusCode;
+use models::api::workspace::runner::*;
+
+use crate::prelude::*;
+
+/// The handler for a runner to report how many replicas of its deployments are
+/// running. Every report is recorded, and each deployment is billed for an hour
+/// by the most replicas that were reported for it within that hour. Reports for
+/// deployments that are not on the runner are ignored.
+pub async fn report_running_replicas(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path:
+					ReportRunningReplicasPath {
+						workspace_id,
+						runner_id,
+					},
+				query: (),
+				headers:
+					ReportRunningReplicasRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ReportRunningReplicasRequestProcessed { replicas },
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ReportRunningReplicasRequest>,
+) -> Result<AppResponse<ReportRunningReplicasRequest>, ErrorType> {
+	info!("Runner `{runner_id}` reporting running replicas");
+
+	let reported = query!(
+		r#"
+		INSERT INTO
+			deployment_replica_report(
+				deployment_id,
+				replicas,
+				reported
+			)
+		SELECT
+			deployment.id,
+			report.replicas,
+			NOW()
+		FROM
+			UNNEST($1::UUID[], $2::INTEGER[]) AS report(deployment_id, replicas)
+		INNER JOIN
+			deployment
+		ON
+			deployment.id = report.deployment_id
+		WHERE
+			deployment.runner = $3 AND
+			deployment.workspace_id = $4 AND
+			deployment.deleted IS NULL;
+		"#,
+		&replicas
+			.keys()
+			.map(|deployment_id| (*deployment_id).into())
+			.collect::<Vec<_>>(),
+		&replicas
+			.values()
+			.map(|replicas| i32::from(*replicas))
+			.collect::<Vec<_>>(),
+		runner_id as _,
+		workspace_id as _,
+	)
+	.execute(&mut **database)
+	.await?
+	.rows_affected();
+
+	trace!("Recorded replicas of {reported} deployments");
+
+	AppResponse::builder()
+		.body(ReportRunningReplicasResponse)
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -4,10 +4,13 @@ use axum::{http::StatusCode, response::IntoResponse};
 use axum_typed_websockets::Message;
 use futures::{future::Either, prelude::stream::*};
 use models::{
-	api::workspace::runner::*,
+	api::workspace::{
+		runner::*,
+		webhook::{WebhookEvent, WorkspaceEvent},
+	},
 	utils::{GenericResponse, WebSocketUpgrade},
 };
-use rustis::commands::{SetCondition, SetExpiration, StringCommands};
+use rustis::commands::{PubSubCommands, SetCondition, SetExpiration, StringCommands};
 
 use crate::prelude::*;
 
@@ -141,6 +144,23 @@ pub async fn stream_runner_data_for_workspace(
 						.unsubscribe(&redis_channel)
 						.await
 						.inspect_err(|err| error!("Error streaming runner data: {:?}", err));
+
+					// The connection of a runner is not stored in the database, so the
+					// event is published on the change feed directly
+					_ = redis
+						.publish(
+							constants::DATABASE_CHANNEL,
+							serde_json::to_string(&WorkspaceEvent {
+								id: Uuid::new_v4(),
+								workspace_id,
+								event: WebhookEvent::RunnerDisconnected { runner_id },
+							})
+							.unwrap(),
+						)
+						.await
+						.inspect_err(|err| {
+							error!("Error publishing runner disconnection: {:?}", err)
+						});
 				})
 				.into_response(),
 		))
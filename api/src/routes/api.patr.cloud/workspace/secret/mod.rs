@@ -1,5 +1,5 @@
 use axum::{http::StatusCode, Router};
-use models::{api::workspace::secret::*, ErrorType};
+use models::{api::workspace::secret::*, utils::TotalCountHeader, ErrorType};
 
 use crate::prelude::*;
 
@@ -74,27 +74,79 @@ async fn delete_secret(
 
 async fn list_secrets_for_workspace(
 	AuthenticatedAppRequest {
-		request: ProcessedApiRequest {
-			path,
-			query: _,
-			headers,
-			body,
-		},
+		request:
+			ProcessedApiRequest {
+				path: ListSecretsForWorkspacePath { workspace_id },
+				query:
+					Paginated {
+						data: ListSecretsForWorkspaceQuery { project_id },
+						count,
+						page,
+					},
+				headers:
+					ListSecretsForWorkspaceRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ListSecretsForWorkspaceRequestProcessed,
+			},
 		database,
 		redis: _,
 		client_ip: _,
-		config,
+		config: _,
 		user_data,
 	}: AuthenticatedAppRequest<'_, ListSecretsForWorkspaceRequest>,
 ) -> Result<AppResponse<ListSecretsForWorkspaceRequest>, ErrorType> {
-	info!("Starting: List secrets for a workspace");
+	info!("Listing all secrets in workspace: {}", workspace_id);
 
-	// LOGIC
+	let mut total_count = 0;
+	let secrets = query!(
+		r#"
+		SELECT
+			secret.id,
+			name,
+			COUNT(*) OVER() AS "total_count!"
+		FROM
+			secret
+		INNER JOIN
+			RESOURCES_WITH_PERMISSION_FOR_LOGIN_ID($2, $3) AS resource
+		ON
+			secret.id = resource.id
+		WHERE
+			workspace_id = $1 AND
+			secret.deleted IS NULL AND
+			($6::UUID IS NULL OR resource.project_id = $6)
+		ORDER BY
+			resource.created DESC
+		LIMIT $4
+		OFFSET $5;
+		"#,
+		workspace_id as _,
+		user_data.login_id as _,
+		Permission::Secret(SecretPermission::View) as _,
+		count as i32,
+		(count * page) as i32,
+		project_id as _,
+	)
+	.fetch_all(&mut **database)
+	.await?
+	.into_iter()
+	.map(|row| {
+		total_count = row.total_count;
+		WithId::new(
+			row.id,
+			Secret {
+				name: row.name,
+				deployment_id: None,
+			},
+		)
+	})
+	.collect();
 
 	AppResponse::builder()
-		.body(ListSecretsForWorkspaceResponse { secrets: todo!() })
+		.body(ListSecretsForWorkspaceResponse { secrets })
 		.headers(ListSecretsForWorkspaceResponseHeaders {
-			total_count: todo!(),
+			total_count: TotalCountHeader(total_count as _),
 		})
 		.status_code(StatusCode::OK)
 		.build()
@@ -0,0 +1,119 @@
+use axum::http::StatusCode;
+use models::api::workspace::service_account::*;
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// The handler to create a service account in a workspace. A service account
+/// is backed by a user that cannot log in, so that it can be given roles in the
+/// workspace and be recorded in the audit logs the same way as a user.
+pub async fn create_service_account(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: CreateServiceAccountPath { workspace_id },
+				query: (),
+				headers:
+					CreateServiceAccountRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: CreateServiceAccountRequestProcessed { name },
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, CreateServiceAccountRequest>,
+) -> Result<AppResponse<CreateServiceAccountRequest>, ErrorType> {
+	info!("Creating service account `{name}` in workspace `{workspace_id}`");
+
+	let now = OffsetDateTime::now_utc();
+	let service_account_id = Uuid::new_v4();
+
+	query!(
+		r#"
+		INSERT INTO
+			"user"(
+				id,
+				username,
+				password,
+				first_name,
+				last_name,
+				created,
+				recovery_email,
+				recovery_phone_country_code,
+				recovery_phone_number,
+				workspace_limit,
+				password_reset_token,
+				password_reset_token_expiry,
+				password_reset_attempts,
+				mfa_secret,
+				is_service_account
+			)
+		VALUES
+			(
+				$1,
+				$2,
+				'',
+				$3,
+				'',
+				$4,
+				NULL,
+				NULL,
+				NULL,
+				0,
+				NULL,
+				NULL,
+				NULL,
+				NULL,
+				TRUE
+			);
+		"#,
+		service_account_id as _,
+		format!("sa-{}", service_account_id),
+		&name,
+		now,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	trace!("Service account user inserted");
+
+	query!(
+		r#"
+		INSERT INTO
+			service_account(
+				id,
+				workspace_id,
+				name,
+				created,
+				deleted
+			)
+		VALUES
+			($1, $2, $3, $4, NULL);
+		"#,
+		service_account_id as _,
+		workspace_id as _,
+		&name,
+		now,
+	)
+	.execute(&mut **database)
+	.await
+	.map_err(|err| match err {
+		sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+			ErrorType::ResourceAlreadyExists
+		}
+		err => ErrorType::from(err),
+	})?;
+
+	AppResponse::builder()
+		.body(CreateServiceAccountResponse {
+			id: WithId::from(service_account_id),
+		})
+		.headers(())
+		.status_code(StatusCode::CREATED)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,68 @@
+use axum::http::StatusCode;
+use models::api::workspace::service_account::*;
+
+use crate::prelude::*;
+
+/// The handler to create an API token for a service account. The token has the
+/// permissions of the roles of the service account, and can be restricted to
+/// a set of IP addresses and a validity period.
+pub async fn create_service_account_api_token(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path:
+					CreateServiceAccountApiTokenPath {
+						workspace_id,
+						service_account_id,
+					},
+				query: (),
+				headers:
+					CreateServiceAccountApiTokenRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body:
+					CreateServiceAccountApiTokenRequestProcessed {
+						name,
+						token_nbf,
+						token_exp,
+						allowed_ips,
+					},
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, CreateServiceAccountApiTokenRequest>,
+) -> Result<AppResponse<CreateServiceAccountApiTokenRequest>, ErrorType> {
+	info!("Creating API token for service account `{service_account_id}`");
+
+	if let (Some(token_nbf), Some(token_exp)) = (token_nbf, token_exp) {
+		if token_exp <= token_nbf {
+			debug!("The token would expire before it is valid");
+			return Err(ErrorType::WrongParameters);
+		}
+	}
+
+	super::ensure_service_account_in_workspace(&mut **database, &workspace_id, &service_account_id)
+		.await?;
+
+	let (id, token) = super::create_api_token_for_service_account(
+		&mut **database,
+		&config,
+		&service_account_id,
+		&name,
+		token_nbf,
+		token_exp,
+		allowed_ips.as_deref(),
+	)
+	.await?;
+
+	AppResponse::builder()
+		.body(CreateServiceAccountApiTokenResponse { id, token })
+		.headers(())
+		.status_code(StatusCode::CREATED)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,125 @@
+use axum::http::StatusCode;
+use models::api::workspace::service_account::*;
+use rustis::commands::{GenericCommands, StringCommands};
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// The handler to delete a service account. All the API tokens of the service
+/// account are revoked, its roles are taken away and the join tokens of runners
+/// that have not been used yet can no longer be used.
+pub async fn delete_service_account(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: DeleteServiceAccountPath {
+					workspace_id,
+					service_account_id,
+				},
+				query: (),
+				headers:
+					DeleteServiceAccountRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: DeleteServiceAccountRequestProcessed,
+			},
+		database,
+		redis,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, DeleteServiceAccountRequest>,
+) -> Result<AppResponse<DeleteServiceAccountRequest>, ErrorType> {
+	info!("Deleting service account `{service_account_id}` of workspace `{workspace_id}`");
+
+	query!(
+		r#"
+		UPDATE
+			service_account
+		SET
+			deleted = NOW()
+		WHERE
+			id = $1 AND
+			workspace_id = $2 AND
+			deleted IS NULL
+		RETURNING
+			id;
+		"#,
+		service_account_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	let revoked_tokens = query!(
+		r#"
+		UPDATE
+			user_api_token
+		SET
+			revoked = NOW()
+		WHERE
+			user_id = $1 AND
+			revoked IS NULL
+		RETURNING
+			token_id;
+		"#,
+		service_account_id as _,
+	)
+	.fetch_all(&mut **database)
+	.await?;
+
+	trace!("Revoked {} API tokens", revoked_tokens.len());
+
+	query!(
+		r#"
+		DELETE FROM
+			workspace_user
+		WHERE
+			workspace_id = $1 AND
+			user_id = $2;
+		"#,
+		workspace_id as _,
+		service_account_id as _,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	query!(
+		r#"
+		DELETE FROM
+			runner_join_token
+		WHERE
+			service_account_id = $1 AND
+			used IS NULL;
+		"#,
+		service_account_id as _,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	for token in revoked_tokens {
+		redis
+			.del(redis::keys::permission_for_login_id(&token.token_id.into()))
+			.await?;
+	}
+
+	redis
+		.setex(
+			redis::keys::user_id_revocation_timestamp(&service_account_id),
+			constants::CACHED_PERMISSIONS_VALIDITY.whole_seconds() as u64,
+			OffsetDateTime::now_utc().unix_timestamp(),
+		)
+		.await
+		.inspect_err(|err| {
+			error!("Error setting the revocation timestamp: `{}`", err);
+		})?;
+
+	AppResponse::builder()
+		.body(DeleteServiceAccountResponse)
+		.headers(())
+		.status_code(StatusCode::RESET_CONTENT)
+		.build()
+		.into_result()
+}
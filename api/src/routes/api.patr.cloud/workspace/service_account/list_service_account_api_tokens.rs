@@ -0,0 +1,79 @@
+use axum::http::StatusCode;
+use models::api::workspace::service_account::*;
+
+use crate::prelude::*;
+
+/// The handler to list all the API tokens of a service account that have not
+/// been revoked.
+pub async fn list_service_account_api_tokens(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path:
+					ListServiceAccountApiTokensPath {
+						workspace_id,
+						service_account_id,
+					},
+				query: (),
+				headers:
+					ListServiceAccountApiTokensRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ListServiceAccountApiTokensRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ListServiceAccountApiTokensRequest>,
+) -> Result<AppResponse<ListServiceAccountApiTokensRequest>, ErrorType> {
+	info!("Listing API tokens of service account `{service_account_id}`");
+
+	super::ensure_service_account_in_workspace(&mut **database, &workspace_id, &service_account_id)
+		.await?;
+
+	let tokens = query!(
+		r#"
+		SELECT
+			token_id,
+			name,
+			token_nbf,
+			token_exp,
+			allowed_ips,
+			created
+		FROM
+			user_api_token
+		WHERE
+			user_id = $1 AND
+			revoked IS NULL
+		ORDER BY
+			created;
+		"#,
+		service_account_id as _,
+	)
+	.fetch_all(&mut **database)
+	.await?
+	.into_iter()
+	.map(|row| {
+		WithId::new(
+			row.token_id,
+			ServiceAccountApiToken {
+				name: row.name,
+				token_nbf: row.token_nbf,
+				token_exp: row.token_exp,
+				allowed_ips: row.allowed_ips,
+				created: row.created,
+			},
+		)
+	})
+	.collect();
+
+	AppResponse::builder()
+		.body(ListServiceAccountApiTokensResponse { tokens })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
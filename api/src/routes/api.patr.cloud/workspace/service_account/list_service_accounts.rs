@@ -0,0 +1,65 @@
+use axum::http::StatusCode;
+use models::api::workspace::service_account::*;
+
+use crate::prelude::*;
+
+/// The handler to list all the service accounts of a workspace.
+pub async fn list_service_accounts(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ListServiceAccountsPath { workspace_id },
+				query: (),
+				headers:
+					ListServiceAccountsRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ListServiceAccountsRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ListServiceAccountsRequest>,
+) -> Result<AppResponse<ListServiceAccountsRequest>, ErrorType> {
+	info!("Listing service accounts of workspace `{workspace_id}`");
+
+	let service_accounts = query!(
+		r#"
+		SELECT
+			id,
+			name,
+			created
+		FROM
+			service_account
+		WHERE
+			workspace_id = $1 AND
+			deleted IS NULL
+		ORDER BY
+			created;
+		"#,
+		workspace_id as _,
+	)
+	.fetch_all(&mut **database)
+	.await?
+	.into_iter()
+	.map(|row| {
+		WithId::new(
+			row.id,
+			ServiceAccount {
+				name: row.name,
+				created: row.created,
+			},
+		)
+	})
+	.collect();
+
+	AppResponse::builder()
+		.body(ListServiceAccountsResponse { service_accounts })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,177 @@
+use argon2::{password_hash::SaltString, Algorithm, PasswordHasher, Version};
+use axum::Router;
+use sqlx::types::ipnetwork::IpNetwork;
+use time::OffsetDateTime;
+
+use crate::{prelude::*, utils::config::AppConfig};
+
+mod create_service_account;
+mod create_service_account_api_token;
+mod delete_service_account;
+mod list_service_account_api_tokens;
+mod list_service_accounts;
+mod revoke_service_account_api_token;
+
+use self::{
+	create_service_account::*,
+	create_service_account_api_token::*,
+	delete_service_account::*,
+	list_service_account_api_tokens::*,
+	list_service_accounts::*,
+	revoke_service_account_api_token::*,
+};
+
+#[instrument(skip(state))]
+pub async fn setup_routes(state: &AppState) -> Router {
+	Router::new()
+		.mount_auth_endpoint(create_service_account, state)
+		.mount_auth_endpoint(create_service_account_api_token, state)
+		.mount_auth_endpoint(delete_service_account, state)
+		.mount_auth_endpoint(list_service_account_api_tokens, state)
+		.mount_auth_endpoint(list_service_accounts, state)
+		.mount_auth_endpoint(revoke_service_account_api_token, state)
+}
+
+/// Creates an API token for a service account, returning the ID of the token
+/// along with the token itself. The token does not have any permissions of its
+/// own, so it always has the permissions of the roles of the service account.
+/// This is also used when a runner exchanges its join token.
+#[instrument(skip(connection, config))]
+pub(super) async fn create_api_token_for_service_account(
+	connection: &mut DatabaseConnection,
+	config: &AppConfig,
+	service_account_id: &Uuid,
+	name: &str,
+	token_nbf: Option<OffsetDateTime>,
+	token_exp: Option<OffsetDateTime>,
+	allowed_ips: Option<&[IpNetwork]>,
+) -> Result<(Uuid, String), ErrorType> {
+	let now = OffsetDateTime::now_utc();
+
+	let refresh_token = Uuid::new_v4();
+	let hashed_refresh_token = argon2::Argon2::new_with_secret(
+		config.password_pepper.as_ref(),
+		Algorithm::Argon2id,
+		Version::V0x13,
+		constants::HASHING_PARAMS,
+	)
+	.inspect_err(|err| {
+		error!("Error creating Argon2: `{}`", err);
+	})
+	.map_err(ErrorType::server_error)?
+	.hash_password(
+		refresh_token.as_bytes(),
+		SaltString::generate(&mut rand::thread_rng()).as_salt(),
+	)
+	.inspect_err(|err| {
+		error!("Error hashing refresh token: `{}`", err);
+	})
+	.map_err(ErrorType::server_error)?
+	.to_string();
+
+	let token_id = query!(
+		r#"
+		INSERT INTO
+			user_login(
+				login_id,
+				user_id,
+				login_type,
+				created
+			)
+		VALUES
+			(
+				GENERATE_LOGIN_ID(),
+				$1,
+				'api_token',
+				$2
+			)
+		RETURNING login_id;
+		"#,
+		service_account_id as _,
+		now,
+	)
+	.fetch_one(&mut *connection)
+	.await?
+	.login_id
+	.into();
+
+	trace!("Service account login inserted");
+
+	query!(
+		r#"
+		INSERT INTO
+			user_api_token(
+				token_id,
+				name,
+				user_id,
+				token_hash,
+				token_nbf,
+				token_exp,
+				allowed_ips,
+				created,
+				revoked,
+				login_type
+			)
+		VALUES
+			(
+				$1,
+				$2,
+				$3,
+				$4,
+				$5,
+				$6,
+				$7,
+				$8,
+				NULL,
+				DEFAULT
+			);
+		"#,
+		token_id as _,
+		name,
+		service_account_id as _,
+		&hashed_refresh_token,
+		token_nbf,
+		token_exp,
+		allowed_ips,
+		now,
+	)
+	.execute(&mut *connection)
+	.await
+	.map_err(|err| match err {
+		sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+			ErrorType::ApiTokenAlreadyExists
+		}
+		err => ErrorType::from(err),
+	})?;
+
+	trace!("Service account API token inserted");
+
+	Ok((token_id, format!("patrv1.{}.{}", refresh_token, token_id)))
+}
+
+/// Checks if a service account exists in a workspace and is not deleted,
+/// returning [`ErrorType::ResourceDoesNotExist`] if it is not.
+pub(super) async fn ensure_service_account_in_workspace(
+	connection: &mut DatabaseConnection,
+	workspace_id: &Uuid,
+	service_account_id: &Uuid,
+) -> Result<(), ErrorType> {
+	query!(
+		r#"
+		SELECT
+			id
+		FROM
+			service_account
+		WHERE
+			id = $1 AND
+			workspace_id = $2 AND
+			deleted IS NULL;
+		"#,
+		service_account_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut *connection)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)
+	.map(|_| ())
+}
@@ -0,0 +1,69 @@
+use axum::http::StatusCode;
+use models::api::workspace::service_account::*;
+use rustis::commands::GenericCommands;
+
+use crate::prelude::*;
+
+/// The handler to revoke an API token of a service account. The token can no
+/// longer be used once it is revoked.
+pub async fn revoke_service_account_api_token(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path:
+					RevokeServiceAccountApiTokenPath {
+						workspace_id,
+						service_account_id,
+						token_id,
+					},
+				query: (),
+				headers:
+					RevokeServiceAccountApiTokenRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: RevokeServiceAccountApiTokenRequestProcessed,
+			},
+		database,
+		redis,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, RevokeServiceAccountApiTokenRequest>,
+) -> Result<AppResponse<RevokeServiceAccountApiTokenRequest>, ErrorType> {
+	info!("Revoking API token `{token_id}` of service account `{service_account_id}`");
+
+	super::ensure_service_account_in_workspace(&mut **database, &workspace_id, &service_account_id)
+		.await?;
+
+	query!(
+		r#"
+		UPDATE
+			user_api_token
+		SET
+			revoked = NOW()
+		WHERE
+			token_id = $1 AND
+			user_id = $2 AND
+			revoked IS NULL
+		RETURNING
+			token_id;
+		"#,
+		token_id as _,
+		service_account_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	redis
+		.del(redis::keys::permission_for_login_id(&token_id))
+		.await?;
+
+	AppResponse::builder()
+		.body(RevokeServiceAccountApiTokenResponse)
+		.headers(())
+		.status_code(StatusCode::RESET_CONTENT)
+		.build()
+		.into_result()
+}
@@ -1,8 +1,34 @@
-use axum::{http::StatusCode, Router};
-use models::api::workspace::static_site::*;
+use std::io;
+
+use axum::{
+	extract::{Path, State},
+	http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+	routing::post,
+	Json,
+	Router,
+};
+use futures::TryStreamExt;
+use models::{
+	api::workspace::{
+		deployment::DeploymentStatus,
+		static_site::*,
+		WorkspaceQuotaType,
+		WorkspaceQuotaUsage,
+	},
+	utils::{Multipart, TotalCountHeader},
+	ApiErrorResponse,
+};
+use s3::Bucket;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tokio_util::io::StreamReader;
 
 use crate::prelude::*;
 
+/// The path that the Cloudflare ingress worker reports the bandwidth served by
+/// static sites to
+const BANDWIDTH_PATH: &str = "/static-site/:static_site_id/bandwidth";
+
 #[instrument(skip(state))]
 pub async fn setup_routes(state: &AppState) -> Router {
 	Router::new()
@@ -16,6 +42,73 @@ pub async fn setup_routes(state: &AppState) -> Router {
 		.mount_auth_endpoint(stop_static_site, state)
 		.mount_auth_endpoint(update_static_site, state)
 		.mount_auth_endpoint(upload_static_site, state)
+		.merge(
+			Router::new()
+				.route(BANDWIDTH_PATH, post(record_static_site_bandwidth))
+				.with_state(state.clone()),
+		)
+}
+
+/// The bandwidth served by a static site, as reported by the Cloudflare ingress
+/// worker
+#[derive(Debug, Deserialize)]
+struct StaticSiteBandwidth {
+	/// The number of bytes of the response that was served
+	bytes: u64,
+}
+
+/// The handler for the Cloudflare ingress worker to report the bytes served by
+/// a static site. This is not an [`ApiEndpoint`], since it is only called by
+/// the worker, which is authorized with the ingress token in the config rather
+/// than a user's token. The bytes are summed up every hour to bill the
+/// workspace of the static site.
+async fn record_static_site_bandwidth(
+	Path(static_site_id): Path<Uuid>,
+	State(state): State<AppState>,
+	headers: HeaderMap,
+	Json(StaticSiteBandwidth { bytes }): Json<StaticSiteBandwidth>,
+) -> Result<StatusCode, ApiErrorResponse> {
+	// The digests of the tokens are compared, so that the time taken to
+	// compare them does not depend on how much of the token is correct
+	let is_authorized = headers
+		.get(AUTHORIZATION)
+		.and_then(|value| value.to_str().ok())
+		.and_then(|value| value.strip_prefix("Bearer "))
+		.is_some_and(|token| {
+			Sha256::digest(token) == Sha256::digest(&state.config.cloudflare.ingress_token)
+		});
+	if !is_authorized {
+		return Err(ApiErrorResponse::error(ErrorType::Unauthorized));
+	}
+
+	let recorded = query!(
+		r#"
+		INSERT INTO
+			static_site_bandwidth(
+				static_site_id,
+				bytes,
+				recorded
+			)
+		SELECT
+			id,
+			$2,
+			NOW()
+		FROM
+			static_site
+		WHERE
+			id = $1;
+		"#,
+		static_site_id as _,
+		i64::try_from(bytes).unwrap_or(i64::MAX),
+	)
+	.execute(&state.database)
+	.await?
+	.rows_affected();
+	if recorded == 0 {
+		return Err(ApiErrorResponse::error(ErrorType::ResourceDoesNotExist));
+	}
+
+	Ok(StatusCode::NO_CONTENT)
 }
 
 async fn create_static_site(
@@ -35,6 +128,23 @@ async fn create_static_site(
 ) -> Result<AppResponse<CreateStaticSiteRequest>, ErrorType> {
 	info!("Starting: Create static site");
 
+	// The static site is not created yet, so the workspace needs room for one
+	// more static site, rather than just being within its quota
+	let usage = super::get_workspace_quota_usage(&mut **database, &path.workspace_id)
+		.await?
+		.get(&WorkspaceQuotaType::StaticSites)
+		.copied();
+	if let Some(WorkspaceQuotaUsage { used, limit }) = usage.filter(|usage| usage.used >= usage.limit)
+	{
+		info!(
+			"Workspace `{}` is at its static site quota: {used}/{limit}",
+			path.workspace_id
+		);
+		return Err(ErrorType::QuotaExceeded {
+			quota: WorkspaceQuotaType::StaticSites,
+		});
+	}
+
 	// LOGIC
 
 	AppResponse::builder()
@@ -104,29 +214,82 @@ async fn get_static_site_info(
 
 async fn list_static_site(
 	AuthenticatedAppRequest {
-		request: ProcessedApiRequest {
-			path,
-			query: _,
-			headers,
-			body,
-		},
+		request:
+			ProcessedApiRequest {
+				path: ListStaticSitePath { workspace_id },
+				query:
+					Paginated {
+						data: ListStaticSiteQuery { project_id },
+						count,
+						page,
+					},
+				headers:
+					ListStaticSiteRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ListStaticSiteRequestProcessed,
+			},
 		database,
 		redis: _,
 		client_ip: _,
-		config,
+		config: _,
 		user_data,
 	}: AuthenticatedAppRequest<'_, ListStaticSiteRequest>,
 ) -> Result<AppResponse<ListStaticSiteRequest>, ErrorType> {
-	info!("Starting: List static site");
-
-	// LOGIC
+	info!("Listing all static sites in workspace: {}", workspace_id);
+
+	let mut total_count = 0;
+	let static_sites = query!(
+		r#"
+		SELECT
+			static_site.id,
+			name,
+			status AS "status: DeploymentStatus",
+			current_live_upload,
+			COUNT(*) OVER() AS "total_count!"
+		FROM
+			static_site
+		INNER JOIN
+			RESOURCES_WITH_PERMISSION_FOR_LOGIN_ID($2, $3) AS resource
+		ON
+			static_site.id = resource.id
+		WHERE
+			workspace_id = $1 AND
+			static_site.deleted IS NULL AND
+			($6::UUID IS NULL OR resource.project_id = $6)
+		ORDER BY
+			resource.created DESC
+		LIMIT $4
+		OFFSET $5;
+		"#,
+		workspace_id as _,
+		user_data.login_id as _,
+		Permission::StaticSite(StaticSitePermission::View) as _,
+		count as i32,
+		(count * page) as i32,
+		project_id as _,
+	)
+	.fetch_all(&mut **database)
+	.await?
+	.into_iter()
+	.map(|row| {
+		total_count = row.total_count;
+		WithId::new(
+			row.id,
+			StaticSite {
+				name: row.name,
+				status: row.status,
+				current_live_upload: row.current_live_upload.map(Into::into),
+			},
+		)
+	})
+	.collect();
 
 	AppResponse::builder()
-		.body(ListStaticSiteResponse {
-			static_sites: todo!(),
-		})
+		.body(ListStaticSiteResponse { static_sites })
 		.headers(ListStaticSiteResponseHeaders {
-			total_count: todo!(),
+			total_count: TotalCountHeader(total_count as _),
 		})
 		.status_code(StatusCode::OK)
 		.build()
@@ -273,10 +436,16 @@ async fn update_static_site(
 async fn upload_static_site(
 	AuthenticatedAppRequest {
 		request: ProcessedApiRequest {
-			path,
+			path: UploadStaticSitePath {
+				workspace_id,
+				static_site_id,
+			},
 			query: _,
-			headers,
-			body,
+			headers: _,
+			body: Multipart {
+				fields: UploadStaticSiteRequestFieldsProcessed { message },
+				mut files,
+			},
 		},
 		database,
 		redis: _,
@@ -287,12 +456,156 @@ async fn upload_static_site(
 ) -> Result<AppResponse<UploadStaticSiteRequest>, ErrorType> {
 	info!("Starting: Upload static site");
 
-	// LOGIC
+	query!(
+		r#"
+		SELECT
+			id
+		FROM
+			static_site
+		WHERE
+			id = $1 AND
+			workspace_id = $2 AND
+			deleted IS NULL;
+		"#,
+		static_site_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	let Some(file) = files.next_file().await? else {
+		debug!("No file was uploaded");
+		return Err(ErrorType::WrongParameters);
+	};
+
+	let upload_id = query!(
+		r#"
+		SELECT
+			GENERATE_RESOURCE_ID() AS "id!";
+		"#
+	)
+	.fetch_one(&mut **database)
+	.await?
+	.id;
+	let object_name = get_s3_object_name_for_upload(&static_site_id, &Uuid::from(upload_id));
+
+	let bucket = Bucket::new(
+		config.s3.bucket.as_str(),
+		s3::Region::Custom {
+			region: config.s3.region,
+			endpoint: config.s3.endpoint,
+		},
+		{
+			s3::creds::Credentials::new(
+				Some(&config.s3.key),
+				Some(&config.s3.secret),
+				None,
+				None,
+				None,
+			)?
+		},
+	)?;
+
+	// The file is streamed to S3 as it is received, before anything is written
+	// to the database, so that no rows are held while the upload is in
+	// progress. If reading the file fails, the error is kept so that the client
+	// gets that error instead of a generic S3 error
+	let mut read_error = None;
+	let uploaded = {
+		let mut reader = StreamReader::new(file.into_stream().map_err(|err| {
+			let message = err.to_string();
+			read_error.get_or_insert(err);
+			io::Error::other(message)
+		}));
+		bucket.put_object_stream(&mut reader, &object_name).await
+	};
+	if let Some(error) = read_error {
+		return Err(error);
+	}
+	let uploaded = uploaded?;
+	if !(200..300).contains(&uploaded.status_code()) {
+		return Err(ErrorType::server_error(format!(
+			"S3 responded with status code {} to the upload",
+			uploaded.status_code()
+		)));
+	}
+
+	let recorded = async {
+		if files.next_file().await?.is_some() {
+			debug!("More than one file was uploaded");
+			return Err(ErrorType::WrongParameters);
+		}
+
+		query!(
+			r#"
+			INSERT INTO
+				resource(
+					id,
+					resource_type_id,
+					owner_id,
+					created
+				)
+			VALUES
+				(
+					$1,
+					(SELECT id FROM resource_type WHERE name = 'static_site_upload'),
+					$2,
+					NOW()
+				);
+			"#,
+			upload_id as _,
+			workspace_id as _,
+		)
+		.execute(&mut **database)
+		.await?;
+
+		query!(
+			r#"
+			INSERT INTO
+				static_site_upload_history(
+					upload_id,
+					static_site_id,
+					message,
+					uploaded_by,
+					created,
+					processed
+				)
+			VALUES
+				($1, $2, $3, $4, NOW(), NULL);
+			"#,
+			upload_id as _,
+			static_site_id as _,
+			message,
+			user_data.id as _,
+		)
+		.execute(&mut **database)
+		.await?;
+
+		Ok::<_, ErrorType>(())
+	}
+	.await;
+
+	// The upload is only kept if it was recorded, so that there are no objects
+	// in S3 that no upload refers to
+	if let Err(error) = recorded {
+		if let Err(err) = bucket.delete_object(&object_name).await {
+			warn!("Failed to delete the upload `{object_name}` from S3: {err}");
+		}
+		return Err(error);
+	}
 
 	AppResponse::builder()
-		.body(UploadStaticSiteResponse { upload_id: todo!() })
+		.body(UploadStaticSiteResponse {
+			upload_id: WithId::from(upload_id),
+		})
 		.headers(())
 		.status_code(StatusCode::OK)
 		.build()
 		.into_result()
 }
+
+/// The name of the object in S3 that an upload of a static site is stored as
+fn get_s3_object_name_for_upload(static_site_id: &Uuid, upload_id: &Uuid) -> String {
+	format!("static-sites/{static_site_id}/{upload_id}")
+}
@@ -0,0 +1,92 @@
+use axum::http::StatusCode;
+use models::api::workspace::*;
+use rustis::commands::StringCommands;
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// The handler to update the single sign-on settings of a workspace. The
+/// provider must be one of the configured OpenID Connect providers. Since this
+/// changes which logins can access the workspace, the cached permissions of all
+/// the members of the workspace are revoked.
+pub async fn update_workspace_sso(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: UpdateWorkspaceSsoPath { workspace_id },
+				query: (),
+				headers:
+					UpdateWorkspaceSsoRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: UpdateWorkspaceSsoRequestProcessed { sso_provider_id },
+			},
+		database,
+		redis,
+		client_ip: _,
+		config,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, UpdateWorkspaceSsoRequest>,
+) -> Result<AppResponse<UpdateWorkspaceSsoRequest>, ErrorType> {
+	info!("Updating SSO settings for workspace `{workspace_id}`");
+
+	if let Some(ref sso_provider_id) = sso_provider_id {
+		if !config.oidc.contains_key(sso_provider_id) {
+			debug!("OIDC provider `{sso_provider_id}` is not configured");
+			return Err(ErrorType::WrongParameters);
+		}
+	}
+
+	query!(
+		r#"
+		UPDATE
+			workspace
+		SET
+			sso_provider_id = $1
+		WHERE
+			id = $2;
+		"#,
+		sso_provider_id.as_deref(),
+		&workspace_id as _,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	// Logins that are excluded from the workspace don't have it in their cached
+	// permissions, so the revocation has to be done for each member
+	let members = query!(
+		r#"
+		SELECT DISTINCT
+			user_id
+		FROM
+			workspace_user
+		WHERE
+			workspace_id = $1;
+		"#,
+		&workspace_id as _,
+	)
+	.fetch_all(&mut **database)
+	.await?;
+
+	let now = OffsetDateTime::now_utc();
+	for member in members {
+		redis
+			.setex(
+				redis::keys::user_id_revocation_timestamp(&member.user_id.into()),
+				constants::CACHED_PERMISSIONS_VALIDITY.whole_seconds() as u64 + 100,
+				now.unix_timestamp(),
+			)
+			.await
+			.inspect_err(|err| {
+				error!("Error setting the revocation timestamp: `{}`", err);
+			})?;
+	}
+
+	AppResponse::builder()
+		.body(UpdateWorkspaceSsoResponse)
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
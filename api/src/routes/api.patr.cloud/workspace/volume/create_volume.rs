@@ -1,5 +1,5 @@
 use axum::http::StatusCode;
-use models::api::workspace::volume::*;
+use models::api::workspace::{volume::*, WorkspaceQuotaType};
 use time::OffsetDateTime;
 
 use crate::prelude::*;
@@ -78,6 +78,13 @@ pub async fn create_volume(
 	.execute(&mut **database)
 	.await?;
 
+	super::super::ensure_within_workspace_quota(
+		&mut **database,
+		&workspace_id,
+		&[WorkspaceQuotaType::VolumeStorage],
+	)
+	.await?;
+
 	AppResponse::builder()
 		.body(CreateVolumeResponse {
 			id: WithId::from(volume_id),
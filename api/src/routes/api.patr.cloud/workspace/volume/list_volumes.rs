@@ -8,11 +8,12 @@ pub async fn list_volumes(
 		request:
 			ProcessedApiRequest {
 				path: ListVolumesInWorkspacePath { workspace_id },
-				query: Paginated {
-					data: (),
-					count,
-					page,
-				},
+				query:
+					Paginated {
+						data: ListVolumesInWorkspaceQuery { project_id },
+						count,
+						page,
+					},
 				headers:
 					ListVolumesInWorkspaceRequestHeaders {
 						authorization: _,
@@ -50,7 +51,8 @@ pub async fn list_volumes(
 		ON
 			deployment_volume.id = resource.id
 		WHERE
-			resource.owner_id = $1
+			resource.owner_id = $1 AND
+			($4::UUID IS NULL OR resource.project_id = $4)
 		ORDER BY
 			resource.created DESC
 		LIMIT $2
@@ -58,7 +60,8 @@ pub async fn list_volumes(
 		"#,
 		workspace_id as _,
 		count as i32,
-		(page * count) as i32
+		(page * count) as i32,
+		project_id as _,
 	)
 	.fetch_all(&mut **database)
 	.await?
@@ -1,5 +1,5 @@
 use axum::http::StatusCode;
-use models::api::workspace::volume::*;
+use models::api::workspace::{volume::*, WorkspaceQuotaType};
 
 use crate::prelude::*;
 
@@ -71,6 +71,15 @@ pub async fn update_volume(
 	.execute(&mut **database)
 	.await?;
 
+	if size.is_some() {
+		super::super::ensure_within_workspace_quota(
+			&mut **database,
+			&workspace_id,
+			&[WorkspaceQuotaType::VolumeStorage],
+		)
+		.await?;
+	}
+
 	AppResponse::builder()
 		.body(UpdateVolumeResponse)
 		.headers(())
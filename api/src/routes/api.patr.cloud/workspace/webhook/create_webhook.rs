@@ -0,0 +1,83 @@
+use axum::http::StatusCode;
+use models::api::workspace::webhook::*;
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// The handler to create a webhook in a workspace. A random secret is generated
+/// for the webhook, which is used to sign the events sent to it. The secret is
+/// only returned here, and cannot be retrieved later.
+pub async fn create_webhook(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: CreateWebhookPath { workspace_id },
+				query: (),
+				headers:
+					CreateWebhookRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: CreateWebhookRequestProcessed {
+					name,
+					url,
+					event_types,
+				},
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, CreateWebhookRequest>,
+) -> Result<AppResponse<CreateWebhookRequest>, ErrorType> {
+	info!("Creating webhook in workspace `{workspace_id}`");
+
+	if !super::is_valid_webhook_url(&url).await {
+		return Err(ErrorType::WrongParameters);
+	}
+
+	let id = Uuid::new_v4();
+	let secret = rand::random::<[u8; 32]>()
+		.iter()
+		.map(|byte| format!("{byte:02x}"))
+		.collect::<String>();
+
+	query!(
+		r#"
+		INSERT INTO
+			webhook(
+				id,
+				workspace_id,
+				name,
+				url,
+				secret,
+				event_types,
+				enabled,
+				created,
+				deleted
+			)
+		VALUES
+			($1, $2, $3, $4, $5, $6, TRUE, $7, NULL);
+		"#,
+		id as _,
+		workspace_id as _,
+		name,
+		url,
+		secret,
+		event_types.into_iter().collect::<Vec<_>>() as _,
+		OffsetDateTime::now_utc(),
+	)
+	.execute(&mut **database)
+	.await?;
+
+	AppResponse::builder()
+		.body(CreateWebhookResponse {
+			id: WithId::from(id),
+			secret,
+		})
+		.headers(())
+		.status_code(StatusCode::CREATED)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,76 @@
+use axum::http::StatusCode;
+use models::api::workspace::webhook::*;
+
+use crate::prelude::*;
+
+/// The handler to delete a webhook. The pending deliveries of the webhook are
+/// marked as failed, since they can no longer be delivered.
+pub async fn delete_webhook(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: DeleteWebhookPath {
+					workspace_id,
+					webhook_id,
+				},
+				query: (),
+				headers:
+					DeleteWebhookRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: DeleteWebhookRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, DeleteWebhookRequest>,
+) -> Result<AppResponse<DeleteWebhookRequest>, ErrorType> {
+	info!("Deleting webhook `{webhook_id}` of workspace `{workspace_id}`");
+
+	query!(
+		r#"
+		UPDATE
+			webhook
+		SET
+			deleted = NOW()
+		WHERE
+			id = $1 AND
+			workspace_id = $2 AND
+			deleted IS NULL
+		RETURNING
+			id;
+		"#,
+		webhook_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	query!(
+		r#"
+		UPDATE
+			webhook_delivery
+		SET
+			status = 'failed',
+			next_attempt = NULL,
+			last_error = 'The webhook was deleted'
+		WHERE
+			webhook_id = $1 AND
+			status = 'pending';
+		"#,
+		webhook_id as _,
+	)
+	.execute(&mut **database)
+	.await?;
+
+	AppResponse::builder()
+		.body(DeleteWebhookResponse)
+		.headers(())
+		.status_code(StatusCode::RESET_CONTENT)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,120 @@
+use axum::http::StatusCode;
+use models::{api::workspace::webhook::*, prelude::*};
+
+use crate::prelude::*;
+
+/// The handler to list the deliveries of events to a webhook, latest first.
+/// Deliveries of a deleted webhook can still be listed.
+pub async fn list_webhook_deliveries(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ListWebhookDeliveriesPath {
+					workspace_id,
+					webhook_id,
+				},
+				query:
+					Paginated {
+						data: ListWebhookDeliveriesQuery { status },
+						count,
+						page,
+					},
+				headers:
+					ListWebhookDeliveriesRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ListWebhookDeliveriesRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ListWebhookDeliveriesRequest>,
+) -> Result<AppResponse<ListWebhookDeliveriesRequest>, ErrorType> {
+	info!("Listing deliveries of webhook `{webhook_id}` of workspace `{workspace_id}`");
+
+	query!(
+		r#"
+		SELECT
+			id
+		FROM
+			webhook
+		WHERE
+			id = $1 AND
+			workspace_id = $2;
+		"#,
+		webhook_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	let mut total_count = 0;
+
+	let deliveries = query!(
+		r#"
+		SELECT
+			webhook_delivery.id,
+			webhook_delivery.event_id,
+			webhook_event.event_type AS "event_type: WebhookEventType",
+			webhook_delivery.status AS "status: WebhookDeliveryStatus",
+			webhook_delivery.attempts,
+			webhook_delivery.last_attempt,
+			webhook_delivery.next_attempt,
+			webhook_delivery.response_status_code,
+			webhook_delivery.last_error,
+			webhook_delivery.created,
+			COUNT(*) OVER() AS "total_count!"
+		FROM
+			webhook_delivery
+		INNER JOIN
+			webhook_event
+		ON
+			webhook_event.id = webhook_delivery.event_id
+		WHERE
+			webhook_delivery.webhook_id = $1 AND
+			($2::WEBHOOK_DELIVERY_STATUS IS NULL OR webhook_delivery.status = $2)
+		ORDER BY
+			webhook_delivery.created DESC
+		LIMIT $3
+		OFFSET $4;
+		"#,
+		webhook_id as _,
+		status as _,
+		count as i32,
+		(count * page) as i32,
+	)
+	.fetch_all(&mut **database)
+	.await?
+	.into_iter()
+	.map(|row| {
+		total_count = row.total_count;
+		WithId::new(
+			row.id,
+			WebhookDelivery {
+				event_id: row.event_id.into(),
+				event_type: row.event_type,
+				status: row.status,
+				attempts: row.attempts as u32,
+				last_attempt: row.last_attempt,
+				next_attempt: row.next_attempt,
+				response_status_code: row.response_status_code.map(|code| code as u16),
+				last_error: row.last_error,
+				created: row.created,
+			},
+		)
+	})
+	.collect();
+
+	AppResponse::builder()
+		.body(ListWebhookDeliveriesResponse { deliveries })
+		.headers(ListWebhookDeliveriesResponseHeaders {
+			total_count: TotalCountHeader(total_count as _),
+		})
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
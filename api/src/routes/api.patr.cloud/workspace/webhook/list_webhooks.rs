@@ -0,0 +1,72 @@
+use axum::http::StatusCode;
+use models::api::workspace::webhook::*;
+
+use crate::prelude::*;
+
+/// The handler to list all the webhooks of a workspace. The secrets of the
+/// webhooks are not returned.
+pub async fn list_webhooks(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: ListWebhooksPath { workspace_id },
+				query: (),
+				headers:
+					ListWebhooksRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: ListWebhooksRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, ListWebhooksRequest>,
+) -> Result<AppResponse<ListWebhooksRequest>, ErrorType> {
+	info!("Listing webhooks of workspace `{workspace_id}`");
+
+	let webhooks = query!(
+		r#"
+		SELECT
+			id,
+			name,
+			url,
+			event_types AS "event_types: Vec<WebhookEventType>",
+			enabled,
+			created
+		FROM
+			webhook
+		WHERE
+			workspace_id = $1 AND
+			deleted IS NULL
+		ORDER BY
+			created;
+		"#,
+		workspace_id as _,
+	)
+	.fetch_all(&mut **database)
+	.await?
+	.into_iter()
+	.map(|row| {
+		WithId::new(
+			row.id,
+			Webhook {
+				name: row.name,
+				url: row.url,
+				event_types: row.event_types.into_iter().collect(),
+				enabled: row.enabled,
+				created: row.created,
+			},
+		)
+	})
+	.collect();
+
+	AppResponse::builder()
+		.body(ListWebhooksResponse { webhooks })
+		.headers(())
+		.status_code(StatusCode::OK)
+		.build()
+		.into_result()
+}
@@ -0,0 +1,39 @@
+use axum::Router;
+
+use crate::prelude::*;
+
+mod create_webhook;
+mod delete_webhook;
+mod list_webhook_deliveries;
+mod list_webhooks;
+mod redeliver_webhook_delivery;
+mod update_webhook;
+
+use self::{
+	create_webhook::*,
+	delete_webhook::*,
+	list_webhook_deliveries::*,
+	list_webhooks::*,
+	redeliver_webhook_delivery::*,
+	update_webhook::*,
+};
+
+#[instrument(skip(state))]
+pub async fn setup_routes(state: &AppState) -> Router {
+	Router::new()
+		.mount_auth_endpoint(create_webhook, state)
+		.mount_auth_endpoint(delete_webhook, state)
+		.mount_auth_endpoint(list_webhook_deliveries, state)
+		.mount_auth_endpoint(list_webhooks, state)
+		.mount_auth_endpoint(redeliver_webhook_delivery, state)
+		.mount_auth_endpoint(update_webhook, state)
+}
+
+/// Checks if a URL can be used for a webhook. Only absolute `http` and `https`
+/// URLs with a host that resolves to public addresses are allowed. See
+/// [`crate::webhook::is_public_url`] for more details.
+async fn is_valid_webhook_url(url: &str) -> bool {
+	reqwest::Url::parse(url)
+		.is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.host_str().is_some()) &&
+		crate::webhook::is_public_url(url).await
+}
@@ -0,0 +1,71 @@
+use axum::http::StatusCode;
+use models::api::workspace::webhook::*;
+
+use crate::prelude::*;
+
+/// The handler to deliver an event to a webhook again. The delivery is reset to
+/// pending with no attempts, so that it is attempted right away and retried as
+/// usual if it fails. The webhook must not be deleted.
+pub async fn redeliver_webhook_delivery(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path:
+					RedeliverWebhookDeliveryPath {
+						workspace_id,
+						webhook_id,
+						delivery_id,
+					},
+				query: (),
+				headers:
+					RedeliverWebhookDeliveryRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body: RedeliverWebhookDeliveryRequestProcessed,
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, RedeliverWebhookDeliveryRequest>,
+) -> Result<AppResponse<RedeliverWebhookDeliveryRequest>, ErrorType> {
+	info!("Redelivering delivery `{delivery_id}` of webhook `{webhook_id}`");
+
+	query!(
+		r#"
+		UPDATE
+			webhook_delivery
+		SET
+			status = 'pending',
+			attempts = 0,
+			next_attempt = NOW(),
+			response_status_code = NULL,
+			last_error = NULL
+		FROM
+			webhook
+		WHERE
+			webhook_delivery.id = $1 AND
+			webhook_delivery.webhook_id = $2 AND
+			webhook.id = webhook_delivery.webhook_id AND
+			webhook.workspace_id = $3 AND
+			webhook.deleted IS NULL
+		RETURNING
+			webhook_delivery.id;
+		"#,
+		delivery_id as _,
+		webhook_id as _,
+		workspace_id as _,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	AppResponse::builder()
+		.body(RedeliverWebhookDeliveryResponse)
+		.headers(())
+		.status_code(StatusCode::ACCEPTED)
+		.build()
+		.into_result()
+}
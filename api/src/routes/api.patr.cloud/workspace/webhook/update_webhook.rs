@@ -0,0 +1,82 @@
+use axum::http::StatusCode;
+use models::api::workspace::webhook::*;
+
+use crate::prelude::*;
+
+/// The handler to update a webhook. Disabling a webhook does not discard its
+/// pending deliveries. They are attempted once the webhook is enabled again.
+pub async fn update_webhook(
+	AuthenticatedAppRequest {
+		request:
+			ProcessedApiRequest {
+				path: UpdateWebhookPath {
+					workspace_id,
+					webhook_id,
+				},
+				query: (),
+				headers:
+					UpdateWebhookRequestHeaders {
+						authorization: _,
+						user_agent: _,
+					},
+				body:
+					UpdateWebhookRequestProcessed {
+						name,
+						url,
+						event_types,
+						enabled,
+					},
+			},
+		database,
+		redis: _,
+		client_ip: _,
+		config: _,
+		user_data: _,
+	}: AuthenticatedAppRequest<'_, UpdateWebhookRequest>,
+) -> Result<AppResponse<UpdateWebhookRequest>, ErrorType> {
+	info!("Updating webhook `{webhook_id}` of workspace `{workspace_id}`");
+
+	if name.is_none() && url.is_none() && event_types.is_none() && enabled.is_none() {
+		return Err(ErrorType::WrongParameters);
+	}
+
+	if let Some(url) = &url {
+		if !super::is_valid_webhook_url(url).await {
+			return Err(ErrorType::WrongParameters);
+		}
+	}
+
+	query!(
+		r#"
+		UPDATE
+			webhook
+		SET
+			name = COALESCE($3, name),
+			url = COALESCE($4, url),
+			event_types = COALESCE($5, event_types),
+			enabled = COALESCE($6, enabled)
+		WHERE
+			id = $1 AND
+			workspace_id = $2 AND
+			deleted IS NULL
+		RETURNING
+			id;
+		"#,
+		webhook_id as _,
+		workspace_id as _,
+		name,
+		url,
+		event_types.map(|event_types| event_types.into_iter().collect::<Vec<_>>()) as _,
+		enabled,
+	)
+	.fetch_optional(&mut **database)
+	.await?
+	.ok_or(ErrorType::ResourceDoesNotExist)?;
+
+	AppResponse::builder()
+		.body(UpdateWebhookResponse)
+		.headers(())
+		.status_code(StatusCode::ACCEPTED)
+		.build()
+		.into_result()
+}
@@ -1,10 +1,13 @@
 use std::{
+	collections::BTreeMap,
 	env,
 	fmt::{Display, Formatter},
 	net::SocketAddr,
+	path::PathBuf,
 };
 
 use config::{Config, Environment, File};
+use models::api::workspace::billing::BillingPlan;
 use serde::{Deserialize, Serialize};
 
 /// Parses the configuration of the application and returns the parsed config.
@@ -72,8 +75,19 @@ pub struct AppConfig {
 	pub cloudflare: CloudflareConfig,
 	/// The opentelemetry endpoint to send traces to
 	pub opentelemetry: OpenTelemetryConfig,
-	/// The configuration for IpInfo to get IpAddress details
-	pub ipinfo: IpInfoConfig,
+	/// The configuration for the provider used to get the geo-location of IP
+	/// addresses
+	#[serde(alias = "geoip")]
+	pub geo_ip: GeoIpConfig,
+	/// The external OpenID Connect providers that users can sign in with,
+	/// keyed by the ID of the provider. The ID is used in the URLs of the
+	/// sign in flow and to identify the provider a workspace requires SSO
+	/// through.
+	#[serde(default)]
+	pub oidc: BTreeMap<String, OidcProviderConfig>,
+	/// The billing plans that the usage of workspaces is priced with
+	#[serde(default)]
+	pub billing: BillingConfig,
 }
 
 /// The environment the application is running in
@@ -171,6 +185,10 @@ pub struct CloudflareConfig {
 	/// The API key to use to connect to Cloudflare
 	#[serde(alias = "apikey")]
 	pub api_key: String,
+	/// The token that the Cloudflare ingress worker uses to report the
+	/// bandwidth served by static sites
+	#[serde(alias = "ingresstoken")]
+	pub ingress_token: String,
 }
 
 /// The configuration for the SMTP server to use to send emails to users
@@ -231,10 +249,100 @@ pub struct MetricsConfig {
 	pub password: String,
 }
 
-/// The configuration for IpInfo to get information about an IP Address
+/// The configuration for the provider used to get the geo-location of an IP
+/// Address. This is used to get the location of a user's login, etc.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "provider")]
+pub enum GeoIpConfig {
+	/// Use a local MaxMind-format (mmdb) database file to lookup IP addresses.
+	/// The file is reloaded automatically whenever it is modified, so that it
+	/// can be updated without restarting the API.
+	#[serde(rename_all = "camelCase")]
+	MaxMind {
+		/// The path to the mmdb database file
+		#[serde(alias = "databasepath")]
+		database_path: PathBuf,
+		/// The interval (in seconds) at which the database file is checked for
+		/// modifications
+		#[serde(alias = "reloadinterval", default = "default_geo_ip_reload_interval")]
+		reload_interval: u64,
+	},
+	/// Use the ipinfo.io API to lookup IP addresses
+	#[serde(rename_all = "camelCase")]
+	IpInfo {
+		/// The token for connecting to ipinfo.io
+		token: String,
+	},
+}
+
+/// The default interval (in seconds) at which the mmdb database file is checked
+/// for modifications
+fn default_geo_ip_reload_interval() -> u64 {
+	60
+}
+
+/// The configuration for an external OpenID Connect provider that users can
+/// sign in with. The endpoints of the provider are found using OIDC discovery
+/// on the issuer URL, so any compliant provider (including a local mock issuer
+/// for testing) can be used.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcProviderConfig {
+	/// The name of the provider, as shown to the user on the sign in page
+	pub name: String,
+	/// The issuer URL of the provider. The discovery document is fetched from
+	/// `{issuerUrl}/.well-known/openid-configuration`, and the `iss` claim of
+	/// the ID token must match this exactly.
+	#[serde(alias = "issuerurl")]
+	pub issuer_url: String,
+	/// The client ID that Patr is registered with on the provider
+	#[serde(alias = "clientid")]
+	pub client_id: String,
+	/// The client secret that Patr is registered with on the provider
+	#[serde(alias = "clientsecret")]
+	pub client_secret: String,
+	/// The URL that the provider redirects the user back to after they have
+	/// authorized the sign in. This must point to the frontend page that calls
+	/// the callback endpoint.
+	#[serde(alias = "redirecturl")]
+	pub redirect_url: String,
+	/// The scopes to request from the provider. `openid` is always requested.
+	#[serde(default = "default_oidc_scopes")]
+	pub scopes: Vec<String>,
+	/// Whether a new user should be created when someone signs in with this
+	/// provider and there is no existing user with the same verified email
+	#[serde(alias = "allowsignup", default)]
+	pub allow_sign_up: bool,
+}
+
+/// The default scopes requested from an OpenID Connect provider
+fn default_oidc_scopes() -> Vec<String> {
+	vec!["email".to_string(), "profile".to_string()]
+}
+
+/// The configuration for billing the usage of workspaces
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct IpInfoConfig {
-	/// The token for connecting to ipinfo.io
-	pub token: String,
+pub struct BillingConfig {
+	/// The ID of the plan used for workspaces that have not chosen a plan
+	#[serde(alias = "defaultplan")]
+	pub default_plan: String,
+	/// The plans that a workspace can be on, keyed by the ID of the plan
+	#[serde(default)]
+	pub plans: BTreeMap<String, BillingPlan>,
+}
+
+impl BillingConfig {
+	/// Get the plan with the given ID, along with the ID of the plan. Workspaces
+	/// that have not chosen a plan (or are on a plan that is no longer
+	/// configured) are on the default plan. Returns [`None`] only if the
+	/// default plan is not configured either.
+	pub fn get_plan(&self, plan_id: Option<String>) -> Option<(String, BillingPlan)> {
+		let plan_id = plan_id
+			.filter(|plan_id| self.plans.contains_key(plan_id))
+			.unwrap_or_else(|| self.default_plan.clone());
+		let plan = self.plans.get(&plan_id)?.clone();
+
+		Some((plan_id, plan))
+	}
 }
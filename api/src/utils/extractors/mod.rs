@@ -1,4 +1,6 @@
 /// The extractor to get the client's IP address from the request.
 mod client_ip;
+/// The extractor to get the ID of the request.
+mod request_id;
 
-pub use self::client_ip::*;
+pub use self::{client_ip::*, request_id::*};
@@ -0,0 +1,33 @@
+use std::{convert::Infallible, str::FromStr};
+
+use axum::{extract::FromRequestParts, http::request::Parts};
+use models::utils::Uuid;
+
+/// The header that the ID of a request is read from, and returned in
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Extractor for the ID of a request, which is taken from the `X-Request-Id`
+/// header (usually set by the load balancer), so that the request can be
+/// traced across services. If the header is missing or is not a valid UUID, a
+/// new ID is generated for the request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RequestId(
+	/// The ID of the request.
+	pub Uuid,
+);
+
+#[axum::async_trait]
+impl FromRequestParts<()> for RequestId {
+	type Rejection = Infallible;
+
+	async fn from_request_parts(parts: &mut Parts, _: &()) -> Result<Self, Self::Rejection> {
+		let request_id = parts
+			.headers
+			.get(REQUEST_ID_HEADER)
+			.and_then(|header_value| header_value.to_str().ok())
+			.and_then(|value| Uuid::from_str(value.trim()).ok())
+			.unwrap_or_else(Uuid::new_v4);
+
+		Ok(Self(request_id))
+	}
+}
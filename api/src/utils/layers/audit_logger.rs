@@ -0,0 +1,703 @@
+use std::{
+	future::Future,
+	marker::PhantomData,
+	net::IpAddr,
+	task::{Context, Poll},
+};
+
+use axum::http::Method;
+use axum_extra::routing::TypedPath;
+use models::utils::IntoAxumResponse;
+use preprocess::Preprocessable;
+use serde_json::{Map, Value};
+use sqlx::types::ipnetwork::IpNetwork;
+use tower::{Layer, Service};
+
+use super::RequestDetails;
+use crate::prelude::*;
+
+/// The value that the sensitive fields of a request or a resource are replaced
+/// with in the audit log
+const REDACTED: &str = "[REDACTED]";
+
+/// The [`tower::Layer`] used to record every state-changing request made on a
+/// workspace in the audit log. Requests that only read data (`GET`, `HEAD` and
+/// `OPTIONS`) are passed through as-is, as are requests that are not made on a
+/// workspace (such as updating the user's own details).
+///
+/// The action of a request is derived from the name of its endpoint (see
+/// [`get_audit_action`]), so that requests such as starting a deployment are
+/// not recorded as creating one. The resource that a request acts on is taken
+/// to be the most specific ID in the path of the endpoint (for example, the
+/// `deployment_id` in `/workspace/:workspace_id/deployment/:deployment_id`).
+/// Endpoints that create a resource take the ID of the created resource from
+/// the `id` of their response instead. If neither has an ID, the workspace
+/// itself is recorded as the resource.
+///
+/// Along with the parameters and the body of the request, the changes made to
+/// the resource by a successful request are recorded, by comparing a snapshot
+/// of the resource from before the request with one from after it. The values
+/// of sensitive fields (such as passwords, secrets and tokens) are redacted.
+///
+/// Successful requests are recorded within the request's database transaction,
+/// so that the audit log is only stored if the change itself is committed.
+/// Failed requests are rolled back, and hence are recorded directly to the
+/// database pool instead.
+pub struct AuditLoggerLayer<E>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+	/// The state of the application, used to access the database pool outside
+	/// of the request's transaction.
+	state: AppState,
+	/// The endpoint type that this layer will handle.
+	endpoint: PhantomData<E>,
+}
+
+impl<E> AuditLoggerLayer<E>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+	/// Create a new instance of the [`AuditLoggerLayer`] with the given state.
+	pub fn with_state(state: AppState) -> Self {
+		Self {
+			state,
+			endpoint: PhantomData,
+		}
+	}
+}
+
+impl<E, S> Layer<S> for AuditLoggerLayer<E>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+	type Service = AuditLoggerService<E, S>;
+
+	fn layer(&self, inner: S) -> Self::Service {
+		AuditLoggerService {
+			inner,
+			state: self.state.clone(),
+			endpoint: PhantomData,
+		}
+	}
+}
+
+impl<E> Clone for AuditLoggerLayer<E>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+	fn clone(&self) -> Self {
+		Self {
+			state: self.state.clone(),
+			endpoint: PhantomData,
+		}
+	}
+}
+
+/// The underlying service that runs when the [`AuditLoggerLayer`] is used.
+pub struct AuditLoggerService<E, S>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+	/// The inner service that will be called with the request.
+	inner: S,
+	/// The state of the application, used to access the database pool outside
+	/// of the request's transaction.
+	state: AppState,
+	/// The endpoint type that this service will handle.
+	endpoint: PhantomData<E>,
+}
+
+impl<E, S> Clone for AuditLoggerService<E, S>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+	S: Clone,
+{
+	fn clone(&self) -> Self {
+		Self {
+			inner: self.inner.clone(),
+			state: self.state.clone(),
+			endpoint: PhantomData,
+		}
+	}
+}
+
+impl<'a, E, S> Service<AuthenticatedAppRequest<'a, E>> for AuditLoggerService<E, S>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+	for<'b> S: Service<AuthenticatedAppRequest<'b, E>, Response = AppResponse<E>, Error = ErrorType>
+		+ Clone,
+{
+	type Error = ErrorType;
+	type Response = AppResponse<E>;
+
+	type Future = impl Future<Output = Result<Self::Response, Self::Error>>;
+
+	fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		self.inner.poll_ready(cx)
+	}
+
+	#[instrument(skip(self, req), name = "AuditLoggerService")]
+	fn call(&mut self, req: AuthenticatedAppRequest<'a, E>) -> Self::Future {
+		let mut inner = self.inner.clone();
+		let state = self.state.clone();
+		async move {
+			if matches!(E::METHOD, Method::GET | Method::HEAD | Method::OPTIONS) {
+				return inner.call(req).await;
+			}
+			let action = get_audit_action(E::NAME);
+
+			let params = serde_json::to_value(&req.request.path).unwrap_or_default();
+			let Some((workspace_id, resource_id)) = get_audit_log_target::<E>(&params) else {
+				trace!("Request is not made on a workspace. Skipping audit log");
+				return inner.call(req).await;
+			};
+
+			let AuthenticatedAppRequest {
+				request,
+				database,
+				redis,
+				client_ip,
+				config,
+				user_data,
+			} = req;
+
+			let RequestDetails { request_id, body } =
+				RequestDetails::current().unwrap_or_else(|| {
+					warn!("Request details not found. Generating a new request ID");
+					RequestDetails {
+						request_id: Uuid::new_v4(),
+						body: None,
+					}
+				});
+
+			let mut audit_log = AuditLogEntry {
+				request_id,
+				workspace_id,
+				resource_id,
+				user_id: user_data.id,
+				login_id: user_data.login_id,
+				ip_address: client_ip,
+				action,
+				metadata: serde_json::json!({
+					"endpoint": E::NAME,
+					"method": E::METHOD.as_str(),
+					"path": <E::RequestPath as TypedPath>::PATH,
+					"params": params,
+					"query": redact_sensitive_fields(
+						serde_json::to_value(&request.query).unwrap_or_default()
+					),
+					"body": body.map(redact_sensitive_fields),
+				}),
+			};
+
+			// A resource that is being created has no snapshot from before the
+			// request, and its ID is only known from the response
+			let before = if action == AuditAction::Create {
+				None
+			} else {
+				get_resource_snapshot(&mut **database, resource_id).await?
+			};
+
+			let result = inner
+				.call(AuthenticatedAppRequest {
+					request,
+					database: &mut *database,
+					redis: &mut *redis,
+					client_ip,
+					config,
+					user_data,
+				})
+				.await;
+
+			match result {
+				Ok(response) => {
+					if action == AuditAction::Create {
+						if let Some(created_id) = response
+							.body
+							.to_json()
+							.as_ref()
+							.and_then(get_created_resource_id)
+						{
+							audit_log.resource_id = created_id;
+						}
+					}
+
+					let after =
+						get_resource_snapshot(&mut **database, audit_log.resource_id).await?;
+					if let Value::Object(metadata) = &mut audit_log.metadata {
+						metadata.insert("changes".to_string(), get_changes(before, after));
+					}
+
+					let status_code = response.status_code.as_u16();
+					audit_log
+						.record(&mut **database, status_code, None)
+						.await
+						.inspect_err(|err| {
+							error!("Error recording audit log: `{}`", err);
+						})?;
+					Ok(response)
+				}
+				Err(error) => {
+					let status_code = error.default_status_code().as_u16();
+					let message: String = error.message().into();
+					_ = audit_log
+						.record(&state.database, status_code, Some(message))
+						.await
+						.inspect_err(|err| {
+							error!("Error recording audit log of failed request: `{}`", err);
+						});
+					Err(error)
+				}
+			}
+		}
+	}
+}
+
+/// The details of a request that are recorded in the audit log, once the
+/// result of the request is known.
+struct AuditLogEntry {
+	/// The ID of the request, used to group the changes made by a request.
+	request_id: Uuid,
+	/// The workspace that the request was made on.
+	workspace_id: Uuid,
+	/// The resource that the request acts on.
+	resource_id: Uuid,
+	/// The user that made the request.
+	user_id: Uuid,
+	/// The login (web login or API token) that was used to make the request.
+	login_id: Uuid,
+	/// The IP address of the client that made the request.
+	ip_address: IpAddr,
+	/// The action performed by the request.
+	action: AuditAction,
+	/// The parameters and the body of the request, along with the changes made
+	/// to the resource, describing the change that was made.
+	metadata: Value,
+}
+
+impl AuditLogEntry {
+	/// Record the audit log, along with the status code of the response and
+	/// the error message in case the request failed.
+	async fn record<'c, C>(
+		self,
+		executor: C,
+		status_code: u16,
+		error: Option<String>,
+	) -> Result<(), sqlx::Error>
+	where
+		C: sqlx::Executor<'c, Database = sqlx::Postgres>,
+	{
+		let success = error.is_none();
+		let mut metadata = self.metadata;
+		if let Value::Object(map) = &mut metadata {
+			map.insert("statusCode".to_string(), status_code.into());
+			if let Some(error) = error {
+				map.insert("error".to_string(), error.into());
+			}
+		}
+
+		query!(
+			r#"
+			INSERT INTO
+				audit_log(
+					id,
+					workspace_id,
+					resource_id,
+					user_id,
+					timestamp,
+					action,
+					login_id,
+					ip_address,
+					request_id,
+					metadata,
+					patr_action,
+					success
+				)
+			VALUES
+				(
+					$1,
+					$2,
+					$3,
+					$4,
+					NOW(),
+					$5::TEXT::AUDIT_LOG_TYPE,
+					$6,
+					$7,
+					$8,
+					$9,
+					FALSE,
+					$10
+				);
+			"#,
+			Uuid::new_v4() as _,
+			self.workspace_id as _,
+			self.resource_id as _,
+			self.user_id as _,
+			self.action.as_str(),
+			self.login_id as _,
+			IpNetwork::from(self.ip_address),
+			self.request_id as _,
+			metadata,
+			success,
+		)
+		.execute(executor)
+		.await?;
+
+		Ok(())
+	}
+}
+
+/// The action performed by a request, as recorded in the `AUDIT_LOG_TYPE` of
+/// the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AuditAction {
+	/// The request creates a resource
+	Create,
+	/// The request changes a resource, or does something with it (such as
+	/// starting or stopping it)
+	Update,
+	/// The request deletes a resource
+	Delete,
+}
+
+impl AuditAction {
+	/// The value of the action in the `AUDIT_LOG_TYPE` enum
+	fn as_str(self) -> &'static str {
+		match self {
+			Self::Create => "create",
+			Self::Update => "update",
+			Self::Delete => "delete",
+		}
+	}
+}
+
+/// Get the action that a request performs from the name of its endpoint, using
+/// the verb the name starts with. Endpoints that neither create nor delete a
+/// resource, such as `StartDeployment` or `RedeliverWebhookDelivery`, update
+/// it.
+fn get_audit_action(endpoint: &str) -> AuditAction {
+	let starts_with_any =
+		|verbs: &[&str]| verbs.iter().any(|verb| endpoint.starts_with(verb));
+
+	if starts_with_any(&["Create", "Add", "Connect"]) {
+		AuditAction::Create
+	} else if starts_with_any(&[
+		"Delete",
+		"Remove",
+		"Disconnect",
+		"Revoke",
+		"Reject",
+		"Cancel",
+	]) {
+		AuditAction::Delete
+	} else {
+		AuditAction::Update
+	}
+}
+
+/// Get the ID of the resource created by a request from its response, which
+/// has the ID in its `id` field
+fn get_created_resource_id(response: &Value) -> Option<Uuid> {
+	response
+		.get("id")
+		.cloned()
+		.and_then(|id| serde_json::from_value(id).ok())
+}
+
+/// Get the workspace and the resource that a request acts on, from the
+/// serialized path parameters of the request. The resource is the last ID in
+/// the path of the endpoint, or the workspace itself if there is no other ID.
+/// Returns [`None`] if the request is not made on a workspace.
+fn get_audit_log_target<E>(params: &Value) -> Option<(Uuid, Uuid)>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+	let get_id = |name: &str| {
+		params
+			.get(name)
+			.cloned()
+			.and_then(|value| serde_json::from_value::<Uuid>(value).ok())
+	};
+
+	let workspace_id = get_id("workspace_id")?;
+	let resource_id = <E::RequestPath as TypedPath>::PATH
+		.split('/')
+		.filter_map(|segment| segment.strip_prefix(':'))
+		.filter(|param| param.ends_with("_id"))
+		.filter_map(get_id)
+		.last()
+		.unwrap_or(workspace_id);
+
+	Some((workspace_id, resource_id))
+}
+
+/// Get a snapshot of the resource that a request acts on, as a JSON object of
+/// the columns of its row, so that the changes made by the request can be
+/// recorded. Returns [`None`] if the resource does not exist (such as before it
+/// is created).
+async fn get_resource_snapshot(
+	connection: &mut DatabaseConnection,
+	resource_id: Uuid,
+) -> Result<Option<Value>, sqlx::Error> {
+	let snapshot = query!(
+		r#"
+		SELECT
+			snapshot AS "snapshot!"
+		FROM
+			(
+				SELECT TO_JSON(workspace) AS snapshot FROM workspace WHERE id = $1
+				UNION ALL
+				SELECT TO_JSON(project) FROM project WHERE id = $1
+				UNION ALL
+				SELECT TO_JSON(runner) FROM runner WHERE id = $1
+				UNION ALL
+				SELECT TO_JSON(deployment) FROM deployment WHERE id = $1
+				UNION ALL
+				SELECT TO_JSON(deployment_volume) FROM deployment_volume WHERE id = $1
+				UNION ALL
+				SELECT TO_JSON(managed_database) FROM managed_database WHERE id = $1
+				UNION ALL
+				SELECT TO_JSON(static_site) FROM static_site WHERE id = $1
+				UNION ALL
+				SELECT TO_JSON(container_registry_repository)
+				FROM container_registry_repository WHERE id = $1
+				UNION ALL
+				SELECT TO_JSON(secret) FROM secret WHERE id = $1
+				UNION ALL
+				SELECT TO_JSON(workspace_domain) FROM workspace_domain WHERE id = $1
+				UNION ALL
+				SELECT TO_JSON(patr_domain_dns_record) FROM patr_domain_dns_record WHERE id = $1
+				UNION ALL
+				SELECT TO_JSON(managed_url) FROM managed_url WHERE id = $1
+				UNION ALL
+				SELECT TO_JSON(ci_repository) FROM ci_repository WHERE id = $1
+				UNION ALL
+				SELECT TO_JSON(webhook) FROM webhook WHERE id = $1
+				UNION ALL
+				SELECT TO_JSON(role) FROM role WHERE id = $1
+			) AS snapshots
+		LIMIT 1;
+		"#,
+		resource_id as _,
+	)
+	.fetch_optional(connection)
+	.await?
+	.map(|row| row.snapshot);
+
+	Ok(snapshot)
+}
+
+/// Get the changes between two snapshots of a resource, as an object of the
+/// fields that changed along with their values before and after the change. A
+/// snapshot that does not exist (such as before the resource is created) is
+/// treated as having no fields. The values of sensitive fields are redacted,
+/// but the fact that they changed is still recorded.
+fn get_changes(before: Option<Value>, after: Option<Value>) -> Value {
+	let into_fields = |snapshot: Option<Value>| match snapshot {
+		Some(Value::Object(fields)) => fields,
+		_ => Map::new(),
+	};
+	let before = into_fields(before);
+	let after = into_fields(after);
+
+	let mut changes = Map::new();
+	for field in before.keys().chain(after.keys()) {
+		let old = before.get(field).cloned().unwrap_or_default();
+		let new = after.get(field).cloned().unwrap_or_default();
+		if old == new || changes.contains_key(field) {
+			continue;
+		}
+
+		let (old, new) = if is_sensitive_field(field) {
+			(redact_value(old), redact_value(new))
+		} else {
+			(redact_sensitive_fields(old), redact_sensitive_fields(new))
+		};
+		changes.insert(
+			field.clone(),
+			serde_json::json!({
+				"before": old,
+				"after": new,
+			}),
+		);
+	}
+
+	Value::Object(changes)
+}
+
+/// Checks if a field of a request or a resource holds a sensitive value, such
+/// as a password, a secret or a token, that should never be recorded
+fn is_sensitive_field(name: &str) -> bool {
+	let name = name.to_lowercase();
+	name == "value" ||
+		["password", "secret", "token", "key", "hash"]
+			.iter()
+			.any(|sensitive| name.contains(sensitive))
+}
+
+/// Replaces a sensitive value with [`REDACTED`]. Missing values are kept as
+/// they are, so that it is still known whether the value was set.
+fn redact_value(value: Value) -> Value {
+	if value.is_null() {
+		value
+	} else {
+		Value::String(REDACTED.to_string())
+	}
+}
+
+/// Redacts the values of all the sensitive fields in a JSON value, including
+/// the fields of any nested objects
+fn redact_sensitive_fields(value: Value) -> Value {
+	match value {
+		Value::Object(fields) => Value::Object(
+			fields
+				.into_iter()
+				.map(|(name, value)| {
+					let value = if is_sensitive_field(&name) {
+						redact_value(value)
+					} else {
+						redact_sensitive_fields(value)
+					};
+					(name, value)
+				})
+				.collect(),
+		),
+		Value::Array(values) => {
+			Value::Array(values.into_iter().map(redact_sensitive_fields).collect())
+		}
+		value => value,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::json;
+
+	use super::{
+		get_audit_action,
+		get_changes,
+		get_created_resource_id,
+		redact_sensitive_fields,
+		AuditAction,
+		REDACTED,
+	};
+
+	#[test]
+	fn assert_sensitive_fields_are_redacted() {
+		let body = json!({
+			"name": "db-password",
+			"value": "hunter2",
+			"newPassword": "hunter2",
+			"config": {
+				"apiToken": "abc",
+				"webhookSecret": null,
+				"port": 8080,
+			},
+			"keys": [{ "privateKey": "-----BEGIN" }],
+		});
+
+		assert_eq!(
+			redact_sensitive_fields(body),
+			json!({
+				"name": "db-password",
+				"value": REDACTED,
+				"newPassword": REDACTED,
+				"config": {
+					"apiToken": REDACTED,
+					"webhookSecret": null,
+					"port": 8080,
+				},
+				"keys": REDACTED,
+			})
+		);
+	}
+
+	#[test]
+	fn assert_changes_only_have_changed_fields() {
+		let before = json!({ "id": "1", "name": "old", "minHorizontalScale": 1 });
+		let after = json!({ "id": "1", "name": "new", "minHorizontalScale": 1 });
+
+		assert_eq!(
+			get_changes(Some(before), Some(after)),
+			json!({ "name": { "before": "old", "after": "new" } })
+		);
+	}
+
+	#[test]
+	fn assert_changes_of_created_and_deleted_resources() {
+		let resource = json!({ "id": "1", "name": "site" });
+
+		assert_eq!(
+			get_changes(None, Some(resource.clone())),
+			json!({
+				"id": { "before": null, "after": "1" },
+				"name": { "before": null, "after": "site" },
+			})
+		);
+		assert_eq!(
+			get_changes(Some(resource), None),
+			json!({
+				"id": { "before": "1", "after": null },
+				"name": { "before": "site", "after": null },
+			})
+		);
+	}
+
+	#[test]
+	fn assert_changes_of_sensitive_fields_are_redacted() {
+		let before = json!({ "value": "old", "passwordHash": null });
+		let after = json!({ "value": "new", "passwordHash": "$argon2id$" });
+
+		assert_eq!(
+			get_changes(Some(before), Some(after)),
+			json!({
+				"value": { "before": REDACTED, "after": REDACTED },
+				"passwordHash": { "before": null, "after": REDACTED },
+			})
+		);
+	}
+
+	#[test]
+	fn assert_actions_are_derived_from_endpoint_names() {
+		for endpoint in ["CreateDeployment", "AddDNSRecord", "ConnectCiRepository"] {
+			assert_eq!(get_audit_action(endpoint), AuditAction::Create);
+		}
+		for endpoint in [
+			"DeleteDeployment",
+			"RemoveUserFromWorkspace",
+			"DisconnectCiRepository",
+			"RevokeServiceAccountApiToken",
+		] {
+			assert_eq!(get_audit_action(endpoint), AuditAction::Delete);
+		}
+		for endpoint in [
+			"UpdateDeployment",
+			"StartDeployment",
+			"StopStaticSite",
+			"RevertStaticSite",
+			"RedeliverWebhookDelivery",
+		] {
+			assert_eq!(get_audit_action(endpoint), AuditAction::Update);
+		}
+	}
+
+	#[test]
+	fn assert_created_resource_id_is_taken_from_the_response() {
+		let id = "6ec04c3ab3a14b3b9c1e0d5c6a2c0a3e";
+		assert_eq!(
+			get_created_resource_id(&json!({ "id": id })).map(|id| id.to_string()),
+			Some(id.to_string())
+		);
+		assert_eq!(get_created_resource_id(&json!({ "name": "site" })), None);
+		assert_eq!(get_created_resource_id(&json!({ "id": "not-an-id" })), None);
+	}
+}
@@ -395,6 +395,24 @@ where
 						return Err(ErrorType::AuthorizationTokenInvalid);
 					}
 
+					// The last activity is only updated once a minute, so that
+					// every request does not have to write to the database
+					query!(
+						r#"
+						UPDATE
+							web_login
+						SET
+							last_activity = NOW()
+						WHERE
+							login_id = $1 AND
+							last_activity < NOW() - INTERVAL '1 minute';
+						"#,
+						sub as _
+					)
+					.execute(&mut **req.database)
+					.await?;
+					trace!("Web login last activity updated");
+
 					if !aud
 						.clone()
 						.into_iter()
@@ -636,7 +654,9 @@ async fn get_permissions_for_login_id(
 		LEFT JOIN
 			workspace_user
 		ON
-			workspace_user.user_id = user_login.user_id
+			workspace_user.user_id = user_login.user_id AND
+			(workspace_user.valid_from IS NULL OR workspace_user.valid_from <= NOW()) AND
+			(workspace_user.valid_until IS NULL OR workspace_user.valid_until > NOW())
 		LEFT JOIN
 			role_resource_permissions_exclude
 		ON
@@ -703,7 +723,9 @@ async fn get_permissions_for_login_id(
 		LEFT JOIN
 			workspace_user
 		ON
-			workspace_user.user_id = user_login.user_id
+			workspace_user.user_id = user_login.user_id AND
+			(workspace_user.valid_from IS NULL OR workspace_user.valid_from <= NOW()) AND
+			(workspace_user.valid_until IS NULL OR workspace_user.valid_until > NOW())
 		LEFT JOIN
 			role_resource_permissions_include
 		ON
@@ -743,10 +765,164 @@ async fn get_permissions_for_login_id(
 		}
 	});
 
+	// An API token can only access a workspace as long as the user that created
+	// it is a member of the workspace. This makes sure that the token loses
+	// access as soon as a time-bound role of the user expires.
+	query!(
+		r#"
+		SELECT
+			workspace.id
+		FROM
+			user_login
+		INNER JOIN
+			workspace
+		ON
+			workspace.id = ANY($2::UUID[])
+		WHERE
+			user_login.login_id = $1 AND
+			user_login.login_type = 'api_token' AND
+			workspace.super_admin_id != user_login.user_id AND
+			NOT EXISTS(
+				SELECT
+					1
+				FROM
+					workspace_user
+				WHERE
+					workspace_user.user_id = user_login.user_id AND
+					workspace_user.workspace_id = workspace.id AND
+					(workspace_user.valid_from IS NULL OR workspace_user.valid_from <= NOW()) AND
+					(workspace_user.valid_until IS NULL OR workspace_user.valid_until > NOW())
+			);
+		"#,
+		login_id as _,
+		&workspace_permissions
+			.keys()
+			.map(|workspace_id| (*workspace_id).into())
+			.collect::<Vec<_>>(),
+	)
+	.fetch_all(&mut *db_connection)
+	.await?
+	.into_iter()
+	.for_each(|row| {
+		trace!("Excluding workspace `{}` as the token's user is not a member", row.id);
+		workspace_permissions.remove(&row.id.into());
+	});
+
+	// Workspaces that require SSO can only be accessed by members with a web
+	// login from the required provider
+	query!(
+		r#"
+		SELECT
+			workspace.id,
+			workspace.sso_provider_id AS "workspace_sso_provider_id!",
+			workspace.super_admin_id = user_login.user_id AS "is_super_admin!",
+			user_login.login_type = 'api_token' AS "is_api_token!",
+			web_login.sso_provider_id AS "login_sso_provider_id?"
+		FROM
+			user_login
+		LEFT JOIN
+			web_login
+		ON
+			web_login.login_id = user_login.login_id
+		INNER JOIN
+			workspace
+		ON
+			workspace.id = ANY($2::UUID[])
+		WHERE
+			user_login.login_id = $1 AND
+			workspace.sso_provider_id IS NOT NULL;
+		"#,
+		login_id as _,
+		&workspace_permissions
+			.keys()
+			.map(|workspace_id| (*workspace_id).into())
+			.collect::<Vec<_>>(),
+	)
+	.fetch_all(&mut *db_connection)
+	.await?
+	.into_iter()
+	.filter(|row| {
+		is_excluded_by_sso(
+			&row.workspace_sso_provider_id,
+			row.is_super_admin,
+			row.is_api_token,
+			row.login_sso_provider_id.as_deref(),
+		)
+	})
+	.for_each(|row| {
+		trace!("Excluding workspace `{}` as it requires SSO", row.id);
+		workspace_permissions.remove(&row.id.into());
+	});
+
+	// Workspaces that are scheduled for deletion cannot be accessed by anyone,
+	// including their super admin, until they are restored
+	query!(
+		r#"
+		SELECT
+			workspace_id
+		FROM
+			workspace_deletion
+		WHERE
+			workspace_id = ANY($1::UUID[]);
+		"#,
+		&workspace_permissions
+			.keys()
+			.map(|workspace_id| (*workspace_id).into())
+			.collect::<Vec<_>>(),
+	)
+	.fetch_all(&mut *db_connection)
+	.await?
+	.into_iter()
+	.for_each(|row| {
+		trace!(
+			"Excluding workspace `{}` as it is scheduled for deletion",
+			row.workspace_id
+		);
+		workspace_permissions.remove(&row.workspace_id.into());
+	});
+
+	// Time-bound roles that start or expire before the cache would expire must
+	// invalidate the cache at that point, so that the permissions are
+	// recalculated
+	let next_role_change = query!(
+		r#"
+		SELECT
+			MIN(
+				LEAST(
+					CASE
+						WHEN workspace_user.valid_from > NOW() THEN workspace_user.valid_from
+					END,
+					CASE
+						WHEN workspace_user.valid_until > NOW() THEN workspace_user.valid_until
+					END
+				)
+			) AS "next_change"
+		FROM
+			workspace_user
+		INNER JOIN
+			user_login
+		ON
+			workspace_user.user_id = user_login.user_id
+		WHERE
+			user_login.login_id = $1;
+		"#,
+		login_id as _
+	)
+	.fetch_one(&mut *db_connection)
+	.await?
+	.next_change;
+	let cache_validity = next_role_change
+		.map(|next_change| {
+			(next_change - OffsetDateTime::now_utc())
+				.whole_seconds()
+				.clamp(1, constants::CACHED_PERMISSIONS_VALIDITY.whole_seconds())
+		})
+		.unwrap_or(constants::CACHED_PERMISSIONS_VALIDITY.whole_seconds());
+
 	redis_connection
 		.setex(
 			redis::keys::permission_for_login_id(login_id),
-			constants::CACHED_PERMISSIONS_VALIDITY.whole_seconds() as u64,
+			cache_validity as u64,
 			serde_json::to_string(&UserPermissionCache {
 				permission: workspace_permissions.clone(),
 				creation_time: OffsetDateTime::now_utc(),
@@ -762,3 +938,49 @@ async fn get_permissions_for_login_id(
 
 	Ok(workspace_permissions)
 }
+
+/// Checks if a login is excluded from a workspace that requires signing in
+/// with the SSO provider `workspace_sso_provider_id`. Only web logins signed in
+/// with the same provider can access the workspace. API tokens are not created
+/// through the provider, so they are excluded as well. The super admin of the
+/// workspace is never excluded, so that they cannot be locked out of their own
+/// workspace.
+fn is_excluded_by_sso(
+	workspace_sso_provider_id: &str,
+	is_super_admin: bool,
+	is_api_token: bool,
+	login_sso_provider_id: Option<&str>,
+) -> bool {
+	if is_super_admin {
+		return false;
+	}
+
+	is_api_token || login_sso_provider_id != Some(workspace_sso_provider_id)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::is_excluded_by_sso;
+
+	#[test]
+	fn assert_sso_web_login_is_allowed() {
+		assert!(!is_excluded_by_sso("okta", false, false, Some("okta")));
+	}
+
+	#[test]
+	fn assert_web_login_without_sso_is_excluded() {
+		assert!(is_excluded_by_sso("okta", false, false, None));
+		assert!(is_excluded_by_sso("okta", false, false, Some("google")));
+	}
+
+	#[test]
+	fn assert_api_token_is_excluded() {
+		assert!(is_excluded_by_sso("okta", false, true, None));
+	}
+
+	#[test]
+	fn assert_super_admin_is_never_excluded() {
+		assert!(!is_excluded_by_sso("okta", true, false, None));
+		assert!(!is_excluded_by_sso("okta", true, true, None));
+	}
+}
@@ -1,3 +1,6 @@
+/// Records every state-changing request made on a workspace in the audit log,
+/// along with who made the request, from where, and what was changed
+mod audit_logger;
 /// Handles functions that processes authenticated requests
 mod auth_endpoint_handler;
 /// Handles the authentication of the requests in case the route is protected
@@ -15,6 +18,11 @@ mod login_id_manager;
 /// Handles the preprocessing of the request, such as the validation of the
 /// request body and returning the error if the request body is invalid
 mod preprocess_handler;
+/// Handles the rate limiting of the requests, as specified by the
+/// [`RateLimit`][models::utils::RateLimit] of the endpoint. This is used to protect endpoints from
+/// brute-force attacks by limiting the requests per IP address and locking
+/// accounts with too many failed attempts
+mod rate_limiter;
 /// Handles the parsing of the request in the required format and passes a
 /// [`ApiRequest`][ApiRequest] to the next layer
 mod request_parser;
@@ -25,12 +33,14 @@ mod request_parser;
 mod user_agent_validation_layer;
 
 pub use self::{
+	audit_logger::*,
 	auth_endpoint_handler::*,
 	authenticator::*,
 	data_store_connection_handler::*,
 	endpoint_handler::*,
 	login_id_manager::*,
 	preprocess_handler::*,
+	rate_limiter::*,
 	request_parser::*,
 	user_agent_validation_layer::*,
 };
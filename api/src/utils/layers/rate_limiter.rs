@@ -0,0 +1,528 @@
+use std::{
+	future::Future,
+	marker::PhantomData,
+	net::IpAddr,
+	task::{Context, Poll},
+};
+
+use models::utils::RateLimit;
+use preprocess::Preprocessable;
+use rustis::{
+	client::Client as RedisClient,
+	commands::{ExpireOption, GenericCommands, StringCommands},
+};
+use sqlx::types::ipnetwork::IpNetwork;
+use time::OffsetDateTime;
+use tower::{Layer, Service};
+
+use super::RequestDetails;
+use crate::prelude::*;
+
+/// The [`tower::Layer`] used to rate limit requests to an endpoint, as
+/// specified by the [`RateLimit`] of the endpoint. If the endpoint does not
+/// have any rate limits, the request is passed through as-is.
+///
+/// This layer enforces a sliding-window limit on the number of requests made
+/// from a single IP address, as well as a sliding-window limit on the number of
+/// failed authentication attempts made on a single account. Once an account
+/// crosses the limit, it is locked for a duration that doubles with every
+/// subsequent lockout, and the lockout is recorded in the audit log.
+///
+/// Since the request's database transaction is rolled back when the request
+/// fails, the audit log is written directly to the database pool instead.
+pub struct RateLimiterLayer<E>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+	/// The state of the application, used to access the database pool and the
+	/// redis connection outside of the request's transaction.
+	state: AppState,
+	/// The endpoint type that this layer will handle.
+	endpoint: PhantomData<E>,
+}
+
+impl<E> RateLimiterLayer<E>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+	/// Create a new instance of the [`RateLimiterLayer`] with the given state.
+	pub fn with_state(state: AppState) -> Self {
+		Self {
+			state,
+			endpoint: PhantomData,
+		}
+	}
+}
+
+impl<E, S> Layer<S> for RateLimiterLayer<E>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+	type Service = RateLimiterService<E, S>;
+
+	fn layer(&self, inner: S) -> Self::Service {
+		RateLimiterService {
+			inner,
+			state: self.state.clone(),
+			endpoint: PhantomData,
+		}
+	}
+}
+
+impl<E> Clone for RateLimiterLayer<E>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+	fn clone(&self) -> Self {
+		Self {
+			state: self.state.clone(),
+			endpoint: PhantomData,
+		}
+	}
+}
+
+/// The underlying service that runs when the [`RateLimiterLayer`] is used.
+pub struct RateLimiterService<E, S>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+	/// The inner service that will be called if the request is within the
+	/// rate limits.
+	inner: S,
+	/// The state of the application, used to access the database pool and the
+	/// redis connection outside of the request's transaction.
+	state: AppState,
+	/// The endpoint type that this service will handle.
+	endpoint: PhantomData<E>,
+}
+
+impl<E, S> Clone for RateLimiterService<E, S>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+	S: Clone,
+{
+	fn clone(&self) -> Self {
+		Self {
+			inner: self.inner.clone(),
+			state: self.state.clone(),
+			endpoint: PhantomData,
+		}
+	}
+}
+
+impl<'a, E, S> Service<UnprocessedAppRequest<'a, E>> for RateLimiterService<E, S>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+	for<'b> S: Service<UnprocessedAppRequest<'b, E>, Response = AppResponse<E>, Error = ErrorType>
+		+ Clone,
+{
+	type Error = ErrorType;
+	type Response = AppResponse<E>;
+
+	type Future = impl Future<Output = Result<Self::Response, Self::Error>>;
+
+	fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		self.inner.poll_ready(cx)
+	}
+
+	#[instrument(skip(self, req), name = "RateLimiterService")]
+	fn call(&mut self, req: UnprocessedAppRequest<'a, E>) -> Self::Future {
+		let mut inner = self.inner.clone();
+		let state = self.state.clone();
+		async move {
+			let Some(rate_limit) = E::get_rate_limit() else {
+				return inner.call(req).await;
+			};
+
+			let client_ip = req.client_ip;
+			let account = if let Some(extract_account) = rate_limit.extract_account {
+				Some(resolve_account(&state, &extract_account(&req.request)).await?)
+			} else {
+				None
+			};
+
+			let mut redis = state.redis.clone();
+			check_rate_limit(
+				&mut redis,
+				&rate_limit,
+				&client_ip,
+				account.as_ref().map(|(account, _)| account.as_str()),
+			)
+			.await?;
+
+			let result = inner.call(req).await;
+
+			let Some((account, user_id)) = account else {
+				return result;
+			};
+
+			record_attempt(&state, &rate_limit, &client_ip, &account, user_id, result).await
+		}
+	}
+}
+
+impl<'a, E, S> Service<AuthenticatedAppRequest<'a, E>> for RateLimiterService<E, S>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+	for<'b> S: Service<AuthenticatedAppRequest<'b, E>, Response = AppResponse<E>, Error = ErrorType>
+		+ Clone,
+{
+	type Error = ErrorType;
+	type Response = AppResponse<E>;
+
+	type Future = impl Future<Output = Result<Self::Response, Self::Error>>;
+
+	fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		self.inner.poll_ready(cx)
+	}
+
+	#[instrument(skip(self, req), name = "RateLimiterService")]
+	fn call(&mut self, req: AuthenticatedAppRequest<'a, E>) -> Self::Future {
+		let mut inner = self.inner.clone();
+		let state = self.state.clone();
+		async move {
+			let Some(rate_limit) = E::get_rate_limit() else {
+				return inner.call(req).await;
+			};
+
+			// For authenticated requests, the failed attempts are always tracked
+			// against the authenticated user
+			let client_ip = req.client_ip;
+			let user_id = req.user_data.id;
+			let account = user_id.to_string();
+
+			let mut redis = state.redis.clone();
+			check_rate_limit(&mut redis, &rate_limit, &client_ip, Some(&account)).await?;
+
+			let result = inner.call(req).await;
+
+			record_attempt(
+				&state,
+				&rate_limit,
+				&client_ip,
+				&account,
+				Some(user_id),
+				result,
+			)
+			.await
+		}
+	}
+}
+
+/// Normalizes the account identifier, so that the same account cannot bypass
+/// the limits by changing the case or adding whitespace to the identifier.
+fn normalize_account(account: &str) -> String {
+	account.trim().to_lowercase()
+}
+
+/// Resolves the account identifier (a username, email or phone number) to the
+/// ID of the user it belongs to, the same way the login endpoint looks up the
+/// user, so that the same account cannot bypass the limits by switching
+/// between its identifiers. The failed attempts are then tracked against the
+/// user ID, the same as for authenticated requests. Identifiers that do not
+/// belong to any user are tracked as-is.
+async fn resolve_account(
+	state: &AppState,
+	account: &str,
+) -> Result<(String, Option<Uuid>), ErrorType> {
+	let account = normalize_account(account);
+
+	let user_id: Option<Uuid> = query!(
+		r#"
+		SELECT
+			"user".id
+		FROM
+			"user"
+		LEFT JOIN
+			user_email
+		ON
+			user_email.user_id = "user".id
+		LEFT JOIN
+			user_phone_number
+		ON
+			user_phone_number.user_id = "user".id
+		LEFT JOIN
+			phone_number_country_code
+		ON
+			phone_number_country_code.country_code = user_phone_number.country_code
+		WHERE
+			"user".username = $1 OR
+			user_email.email = $1 OR
+			CONCAT(
+				'+',
+				phone_number_country_code.phone_code,
+				user_phone_number.number
+			) = $1;
+		"#,
+		&account,
+	)
+	.fetch_optional(&state.database)
+	.await?
+	.map(|row| row.id.into());
+
+	Ok(match user_id {
+		Some(user_id) => (user_id.to_string(), Some(user_id)),
+		None => (account, None),
+	})
+}
+
+/// Checks if the error returned by an endpoint is a failed authentication
+/// attempt that should be counted towards the account's lockout.
+fn is_failed_attempt(error: &ErrorType) -> bool {
+	matches!(
+		error,
+		ErrorType::UserNotFound |
+			ErrorType::InvalidPassword |
+			ErrorType::MfaOtpInvalid |
+			ErrorType::InvalidPasswordResetToken
+	)
+}
+
+/// Returns the current window, the previous window and the fraction of the
+/// current window that has elapsed, for a given unix timestamp. These are used
+/// to calculate the sliding-window count using the fixed-window counters.
+fn get_windows(now: i64) -> (i64, i64, f64) {
+	let window_length = constants::RATE_LIMIT_WINDOW.whole_seconds();
+	let current_window = now / window_length;
+	let elapsed = (now % window_length) as f64 / window_length as f64;
+
+	(current_window, current_window - 1, elapsed)
+}
+
+/// Increments the counter for the current window of the given key, and returns
+/// the weighted count over the sliding window, which is the count of the
+/// current window plus the count of the previous window weighted by the
+/// fraction of the sliding window that still overlaps the previous window.
+async fn increment_sliding_window(
+	redis: &mut RedisClient,
+	current_key: String,
+	previous_key: String,
+	elapsed: f64,
+) -> Result<f64, ErrorType> {
+	let current = redis.incr(current_key.as_str()).await?;
+	redis
+		.expire(
+			current_key.as_str(),
+			(constants::RATE_LIMIT_WINDOW.whole_seconds() * 2) as u64,
+			ExpireOption::None,
+		)
+		.await?;
+	let previous = redis
+		.get::<_, Option<i64>>(previous_key)
+		.await?
+		.unwrap_or(0);
+
+	Ok(current as f64 + (previous as f64 * (1f64 - elapsed)))
+}
+
+/// Checks if the account is currently locked, and if the IP address has
+/// crossed the maximum number of requests allowed within the window. This also
+/// counts the current request towards the IP address' limit.
+async fn check_rate_limit<E>(
+	redis: &mut RedisClient,
+	rate_limit: &RateLimit<E>,
+	client_ip: &IpAddr,
+	account: Option<&str>,
+) -> Result<(), ErrorType>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+	let now = OffsetDateTime::now_utc().unix_timestamp();
+
+	if let Some(account) = account {
+		let locked_until = redis
+			.get::<_, Option<i64>>(redis::keys::account_locked_until(account))
+			.await?
+			.filter(|locked_until| *locked_until > now);
+
+		if let Some(locked_until) = locked_until {
+			info!("Account `{}` is locked until `{}`", account, locked_until);
+			return Err(ErrorType::AccountLocked {
+				retry_after: (locked_until - now) as u64,
+			});
+		}
+	}
+
+	let (current_window, previous_window, elapsed) = get_windows(now);
+	let requests = increment_sliding_window(
+		redis,
+		redis::keys::rate_limit_requests_for_ip(rate_limit.bucket, client_ip, current_window),
+		redis::keys::rate_limit_requests_for_ip(rate_limit.bucket, client_ip, previous_window),
+		elapsed,
+	)
+	.await?;
+
+	if requests > rate_limit.max_requests_per_ip as f64 {
+		info!(
+			"IP address `{}` has crossed the rate limit for `{}`",
+			client_ip, rate_limit.bucket
+		);
+		let window_length = constants::RATE_LIMIT_WINDOW.whole_seconds();
+		return Err(ErrorType::TooManyRequests {
+			retry_after: (window_length - (now % window_length)) as u64,
+		});
+	}
+
+	Ok(())
+}
+
+/// Records the result of an attempt on an account. A successful attempt clears
+/// the failed attempts on the account. A failed attempt is counted towards the
+/// account's limit, and locks the account if the limit is crossed. Any errors
+/// while recording the attempt are logged and do not affect the response.
+async fn record_attempt<E>(
+	state: &AppState,
+	rate_limit: &RateLimit<E>,
+	client_ip: &IpAddr,
+	account: &str,
+	user_id: Option<Uuid>,
+	result: Result<AppResponse<E>, ErrorType>,
+) -> Result<AppResponse<E>, ErrorType>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+	let mut redis = state.redis.clone();
+	let now = OffsetDateTime::now_utc().unix_timestamp();
+	let (current_window, previous_window, elapsed) = get_windows(now);
+
+	let error = match result {
+		Ok(response) => {
+			_ = redis
+				.del([
+					redis::keys::failed_attempts_for_account(account, current_window),
+					redis::keys::failed_attempts_for_account(account, previous_window),
+				])
+				.await
+				.inspect_err(|err| {
+					error!("Error clearing failed attempts for `{}`: `{}`", account, err);
+				});
+			return Ok(response);
+		}
+		Err(error) if is_failed_attempt(&error) => error,
+		Err(error) => return Err(error),
+	};
+
+	let Ok(failures) = increment_sliding_window(
+		&mut redis,
+		redis::keys::failed_attempts_for_account(account, current_window),
+		redis::keys::failed_attempts_for_account(account, previous_window),
+		elapsed,
+	)
+	.await
+	else {
+		error!("Error recording failed attempt for `{}`", account);
+		return Err(error);
+	};
+
+	if failures < rate_limit.max_failures_per_account as f64 {
+		return Err(error);
+	}
+
+	match lock_account(state, &mut redis, client_ip, account, user_id, now).await {
+		Ok(retry_after) => Err(ErrorType::AccountLocked { retry_after }),
+		Err(err) => {
+			error!("Error locking account `{}`: `{:?}`", account, err);
+			Err(error)
+		}
+	}
+}
+
+/// Locks the account for a duration that doubles with every recent lockout,
+/// and records the lockout in the audit log. Returns the number of seconds for
+/// which the account is locked.
+async fn lock_account(
+	state: &AppState,
+	redis: &mut RedisClient,
+	client_ip: &IpAddr,
+	account: &str,
+	user_id: Option<Uuid>,
+	now: i64,
+) -> Result<u64, ErrorType> {
+	let lockout_count = redis
+		.incr(redis::keys::account_lockout_count(account))
+		.await?;
+	redis
+		.expire(
+			redis::keys::account_lockout_count(account),
+			constants::ACCOUNT_LOCKOUT_COUNT_VALIDITY.whole_seconds() as u64,
+			ExpireOption::None,
+		)
+		.await?;
+
+	let lockout_duration = constants::INITIAL_ACCOUNT_LOCKOUT_DURATION
+		.checked_mul(2i32.saturating_pow(lockout_count.saturating_sub(1).clamp(0, 30) as u32))
+		.unwrap_or(constants::MAX_ACCOUNT_LOCKOUT_DURATION)
+		.min(constants::MAX_ACCOUNT_LOCKOUT_DURATION)
+		.whole_seconds();
+
+	redis
+		.setex(
+			redis::keys::account_locked_until(account),
+			lockout_duration as u64,
+			now + lockout_duration,
+		)
+		.await?;
+
+	// Start counting the failed attempts afresh after the lockout
+	let (current_window, previous_window, _) = get_windows(now);
+	redis
+		.del([
+			redis::keys::failed_attempts_for_account(account, current_window),
+			redis::keys::failed_attempts_for_account(account, previous_window),
+		])
+		.await?;
+
+	warn!(
+		"Account `{}` locked for {} seconds after too many failed attempts",
+		account, lockout_duration
+	);
+
+	// Lockouts on accounts that do not exist cannot be attributed to a user,
+	// and hence are not recorded in the audit log
+	if let Some(user_id) = user_id {
+		let request_id = RequestDetails::current()
+			.map(|details| details.request_id)
+			.unwrap_or_else(Uuid::new_v4);
+
+		query!(
+			r#"
+			INSERT INTO
+				audit_log(
+					id,
+					user_id,
+					timestamp,
+					action,
+					ip_address,
+					request_id,
+					metadata,
+					patr_action,
+					success
+				)
+			VALUES
+				($1, $2, NOW(), 'account_locked', $3, $4, $5, TRUE, TRUE);
+			"#,
+			Uuid::new_v4() as _,
+			user_id as _,
+			IpNetwork::from(*client_ip),
+			request_id as _,
+			serde_json::json!({
+				"lockoutCount": lockout_count,
+				"lockoutDuration": lockout_duration,
+			}),
+		)
+		.execute(&state.database)
+		.await?;
+	}
+
+	Ok(lockout_duration as u64)
+}
@@ -9,19 +9,46 @@ use std::{
 use axum::{
 	body::Body,
 	extract::Path,
-	http::Request,
+	http::{HeaderValue, Request},
 	response::{IntoResponse, Response},
 	RequestExt,
 };
 use models::{
 	prelude::*,
-	utils::{FromAxumRequest, GenericResponse, Headers, IntoAxumResponse},
+	utils::{FromAxumRequest, GenericResponse, Headers, IntoAxumResponse, Uuid},
 	ApiErrorResponse,
 };
 use preprocess::Preprocessable;
+use serde_json::Value;
 use tower::{Layer, Service};
 
-use crate::utils::extractors::ClientIP;
+use crate::utils::extractors::{ClientIP, RequestId, REQUEST_ID_HEADER};
+
+tokio::task_local! {
+	/// The details of the request that is currently being handled. This is set
+	/// by the [`RequestParserService`] for the rest of the layers, since these
+	/// details are not a part of the parsed request that is passed to them.
+	static CURRENT_REQUEST: RequestDetails;
+}
+
+/// The details of a request that are not a part of the parsed request, but are
+/// needed by some of the layers (such as the audit logger)
+#[derive(Debug, Clone)]
+pub struct RequestDetails {
+	/// The ID of the request, as given by the [`RequestId`] extractor
+	pub request_id: Uuid,
+	/// The body of the request as JSON, if it can be recorded. See
+	/// [`FromAxumRequest::to_json`] for more details.
+	pub body: Option<Value>,
+}
+
+impl RequestDetails {
+	/// The details of the request that is currently being handled, or [`None`]
+	/// if this is not called while handling a request
+	pub fn current() -> Option<Self> {
+		CURRENT_REQUEST.try_with(Clone::clone).ok()
+	}
+}
 
 /// A [`tower::Layer`] that can be used to parse the request and call the inner
 /// service with the parsed request. Ideally, this will automatically be done by
@@ -141,21 +168,34 @@ where
 			};
 
 			let Ok(ClientIP(client_ip)) = req.extract_parts().await;
+			let Ok(RequestId(request_id)) = req.extract_parts().await;
 
-			let Ok(body) =
+			let body =
 				<<E as ApiEndpoint>::RequestBody as FromAxumRequest>::from_axum_request(req)
 					.await
-					.inspect_err(|err| debug!("Error parsing body: {}", err.to_string()))
-			else {
-				return Ok(ApiErrorResponse::error_with_message(
-					ErrorType::WrongParameters,
-					"Invalid body",
-				)
-				.into_response());
+					.inspect_err(|err| debug!("Error parsing body: {}", err.to_string()));
+			// A body that is too large is reported as is, so that the client knows
+			// that the request can't be retried as it is
+			let body = match body {
+				Ok(body) => body,
+				Err(ErrorType::PayloadTooLarge) => {
+					return Ok(ApiErrorResponse::error(ErrorType::PayloadTooLarge).into_response());
+				}
+				Err(_) => {
+					return Ok(ApiErrorResponse::error_with_message(
+						ErrorType::WrongParameters,
+						"Invalid body",
+					)
+					.into_response());
+				}
 			};
 
 			debug!("Request parsed successfully");
 
+			let request_details = RequestDetails {
+				request_id,
+				body: body.to_json(),
+			};
 			let request = ApiRequest {
 				path,
 				query,
@@ -165,8 +205,8 @@ where
 
 			info!("Calling inner service");
 
-			let response = inner
-				.call((request, client_ip))
+			let mut response = CURRENT_REQUEST
+				.scope(request_details, inner.call((request, client_ip)))
 				.await
 				.inspect(|_| info!("Inner service called successfully"))
 				.map(|response| {
@@ -189,6 +229,9 @@ where
 					}
 					ApiErrorResponse::error(error).into_response()
 				});
+			if let Ok(request_id) = HeaderValue::from_str(&request_id.to_string()) {
+				response.headers_mut().insert(REQUEST_ID_HEADER, request_id);
+			}
 
 			Ok(response)
 		}
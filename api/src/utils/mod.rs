@@ -40,6 +40,11 @@ pub mod constants {
 	/// The `aud` field in Patr's JWT
 	pub const PATR_JWT_AUDIENCE: &str = "https://app.patr.cloud";
 
+	/// The `aud` field in the JWT of a workspace invite. This is different
+	/// from [`PATR_JWT_AUDIENCE`] so that an invite token can never be used as
+	/// an access token, and vice versa.
+	pub const WORKSPACE_INVITE_JWT_AUDIENCE: &str = "https://app.patr.cloud/invite";
+
 	/// The parameters that will be used to hash, using argon2 as the hashing
 	/// algorithm. This is used for all sorts of hashing, from API tokens, user
 	/// passwords, sign up tokens, etc.
@@ -97,7 +102,116 @@ pub mod constants {
 	/// ticket with the team.
 	pub const DEFAULT_WORKSPACE_LIMIT: i32 = 10;
 
+	/// The default maximum number of deployments a workspace can have
+	pub const DEFAULT_DEPLOYMENT_LIMIT: i32 = 10;
+
+	/// The default maximum number of static sites a workspace can have
+	pub const DEFAULT_STATIC_SITE_LIMIT: i32 = 30;
+
+	/// The default maximum number of CPUs that all the deployments of a
+	/// workspace can use together, across all their replicas
+	pub const DEFAULT_CPU_COUNT_LIMIT: i32 = 16;
+
+	/// The default maximum amount of memory, in multiples of 0.25 GB, that all
+	/// the deployments of a workspace can use together, across all their
+	/// replicas. This amounts to 32 GB.
+	pub const DEFAULT_MEMORY_COUNT_LIMIT: i32 = 128;
+
+	/// The default maximum size of all the volumes of a workspace, in GB
+	pub const DEFAULT_VOLUME_STORAGE_LIMIT: i32 = 100;
+
+	/// The default maximum size of all the images in the container registry of
+	/// a workspace, in bytes. This amounts to 10 GB.
+	pub const DEFAULT_CONTAINER_REGISTRY_STORAGE_LIMIT: i64 = 10 * 1024 * 1024 * 1024;
+
 	/// The maximum number of times a user can attempt to reset a password
 	/// before getting banned altogether
 	pub const MAX_PASSWORD_RESET_ATTEMPTS: u16 = 5;
+
+	/// The duration of the sliding window used for rate limiting. The number
+	/// of requests (per IP) and failed attempts (per account) are counted over
+	/// this window.
+	pub const RATE_LIMIT_WINDOW: time::Duration = time::Duration::minutes(15);
+
+	/// The duration for which an account is locked the first time it crosses
+	/// the maximum number of failed attempts. Every subsequent lockout doubles
+	/// this duration, up to [`MAX_ACCOUNT_LOCKOUT_DURATION`].
+	pub const INITIAL_ACCOUNT_LOCKOUT_DURATION: time::Duration = time::Duration::minutes(5);
+
+	/// The maximum duration for which an account can be locked, regardless of
+	/// the number of times it has been locked before.
+	pub const MAX_ACCOUNT_LOCKOUT_DURATION: time::Duration = time::Duration::hours(24);
+
+	/// How long the number of lockouts of an account is remembered for. If the
+	/// account is not locked again within this duration, the next lockout will
+	/// start again from [`INITIAL_ACCOUNT_LOCKOUT_DURATION`].
+	pub const ACCOUNT_LOCKOUT_COUNT_VALIDITY: time::Duration = time::Duration::days(1);
+
+	/// How long a user has to complete a sign in with an external OpenID
+	/// Connect provider, after which the state of the sign in is discarded.
+	pub const OIDC_AUTHORIZATION_STATE_VALIDITY: time::Duration = time::Duration::minutes(10);
+
+	/// How long an invite to a workspace is valid for, after which it has to be
+	/// resent to be accepted.
+	pub const WORKSPACE_INVITE_VALIDITY: time::Duration = time::Duration::days(7);
+
+	/// How long a transfer of the ownership of a workspace can be accepted by
+	/// the new owner, after which it has to be started again.
+	pub const WORKSPACE_OWNERSHIP_TRANSFER_VALIDITY: time::Duration = time::Duration::days(7);
+
+	/// How long a join token of a runner can be exchanged for an API token,
+	/// after which a new join token has to be created.
+	pub const RUNNER_JOIN_TOKEN_VALIDITY: time::Duration = time::Duration::hours(1);
+
+	/// How long a workspace that is deleted along with all its resources can
+	/// still be restored, after which its resources start getting destroyed.
+	pub const WORKSPACE_DELETION_GRACE_PERIOD: time::Duration = time::Duration::days(7);
+
+	/// How often the workspaces whose grace period of deletion has ended are
+	/// checked for, so that their resources can be destroyed.
+	pub const WORKSPACE_DELETION_POLL_INTERVAL: time::Duration = time::Duration::minutes(1);
+
+	/// How long to wait before retrying a step of the deletion of a workspace
+	/// that has failed.
+	pub const WORKSPACE_DELETION_RETRY_INTERVAL: time::Duration = time::Duration::minutes(15);
+
+	/// How often the pending deliveries of webhooks are checked for, so that
+	/// they can be attempted.
+	pub const WEBHOOK_DELIVERY_POLL_INTERVAL: time::Duration = time::Duration::seconds(5);
+
+	/// How long to wait for a webhook to respond before the attempt to deliver
+	/// an event to it is considered failed.
+	pub const WEBHOOK_DELIVERY_TIMEOUT: time::Duration = time::Duration::seconds(10);
+
+	/// How long to wait before retrying the first failed attempt to deliver an
+	/// event to a webhook. The wait is doubled after every failed attempt.
+	pub const WEBHOOK_DELIVERY_INITIAL_RETRY_INTERVAL: time::Duration =
+		time::Duration::seconds(30);
+
+	/// The number of times the delivery of an event to a webhook is attempted
+	/// before it is marked as failed.
+	pub const WEBHOOK_DELIVERY_MAX_ATTEMPTS: i32 = 8;
+
+	/// The URL that the API is served at, used to build the URLs that other
+	/// services call back to, such as the webhooks of connected git
+	/// repositories.
+	pub const API_URL: &str = "https://api.patr.cloud";
+
+	/// The path of the CI file in a connected git repository, if another one
+	/// is not given when the repository is connected.
+	pub const DEFAULT_CI_FILE_PATH: &str = ".patr.yml";
+
+	/// How long to wait for the API of a git provider to respond, when reading
+	/// the CI file of a repository or reporting the status of a commit.
+	pub const CI_PROVIDER_REQUEST_TIMEOUT: time::Duration = time::Duration::seconds(10);
+
+	/// The name that the statuses of the pipeline runs are reported to the git
+	/// providers under.
+	pub const CI_COMMIT_STATUS_CONTEXT: &str = "patr";
+
+	/// The number of hours in a month, used to prorate the monthly prices of a
+	/// billing plan over the number of hours a resource is used for. This is
+	/// the average number of hours in a month, so that every month is priced
+	/// the same.
+	pub const BILLING_HOURS_PER_MONTH: u64 = 730;
 }
@@ -16,9 +16,11 @@ use tower::{
 };
 
 use super::layers::{
+	AuditLoggerLayer,
 	AuthenticationLayer,
 	ClientType,
 	PreprocessLayer,
+	RateLimiterLayer,
 	RequestParserLayer,
 	UserAgentValidationLayer,
 };
@@ -86,11 +88,10 @@ where
 					ErrorType,
 				>::new(
 					ServiceBuilder::new()
-						// .layer(todo!("Add rate limiter checker middleware here")),
 						.layer(DataStoreConnectionLayer::<E>::with_state(state.clone()))
+						.layer(RateLimiterLayer::with_state(state.clone()))
 						.layer(PreprocessLayer::new())
 						.layer(UserAgentValidationLayer::new())
-						// .layer(todo!("Add rate limiter value updater middleware here"))
 						.layer(EndpointLayer::new(handler.clone())),
 				)),
 			)
@@ -102,6 +103,8 @@ where
 				);
 			});
 
+		crate::openapi::register_endpoint::<E>();
+
 		// Setup the layers for the backend
 		if <E as ApiEndpoint>::API_ALLOWED || cfg!(debug_assertions) {
 			self.route(
@@ -113,10 +116,9 @@ where
 					)
 					.layer(
 						ServiceBuilder::new()
-							// .layer(todo!("Add rate limiter checker middleware here")),
 							.layer(RequestParserLayer::new())
 							.layer(DataStoreConnectionLayer::with_state(state.clone()))
-							// .layer(todo!("Add rate limiter value updater middleware here"))
+							.layer(RateLimiterLayer::with_state(state.clone()))
 							.layer(PreprocessLayer::new())
 							.layer(UserAgentValidationLayer::new())
 							.layer(EndpointLayer::new(handler)),
@@ -150,14 +152,13 @@ where
 					ErrorType,
 				>::new(
 					ServiceBuilder::new()
-						// .layer(todo!("Add rate limiter checker middleware here")),
 						.layer(DataStoreConnectionLayer::with_state(state.clone()))
 						.layer(PreprocessLayer::new())
 						.layer(UserAgentValidationLayer::new())
 						.layer(AuthenticationLayer::new(ClientType::WebDashboard))
 						// .layer(todo!("Add permission checker middleware here"))
-						// .layer(todo!("Add rate limiter value updater middleware here"))
-						// .layer(todo!("Add audit logger middleware here"))
+						.layer(RateLimiterLayer::with_state(state.clone()))
+						.layer(AuditLoggerLayer::with_state(state.clone()))
 						.layer(AuthEndpointLayer::new(handler.clone())),
 				)),
 			)
@@ -169,6 +170,8 @@ where
 				);
 			});
 
+		crate::openapi::register_endpoint::<E>();
+
 		// Setup the layers for the backend
 		if <E as ApiEndpoint>::API_ALLOWED || cfg!(debug_assertions) {
 			self.route(
@@ -180,15 +183,14 @@ where
 					)
 					.layer(
 						ServiceBuilder::new()
-							// .layer(todo!("Add rate limiter checker middleware here")),
 							.layer(RequestParserLayer::new())
 							.layer(DataStoreConnectionLayer::with_state(state.clone()))
 							.layer(PreprocessLayer::new())
 							.layer(UserAgentValidationLayer::new())
 							.layer(AuthenticationLayer::new(ClientType::ApiToken))
 							// .layer(todo!("Add permission checker middleware here"))
-							// .layer(todo!("Add rate limiter value updater middleware here"))
-							// .layer(todo!("Add audit logger middleware here"))
+							.layer(RateLimiterLayer::with_state(state.clone()))
+							.layer(AuditLoggerLayer::with_state(state.clone()))
 							.layer(AuthEndpointLayer::new(handler)),
 					),
 			)
@@ -0,0 +1,482 @@
+use std::{
+	net::{IpAddr, Ipv4Addr, Ipv6Addr},
+	sync::Arc,
+};
+
+use futures::StreamExt;
+use hmac::{Hmac, Mac};
+use models::api::workspace::webhook::{
+	WebhookDeliveryStatus,
+	WebhookEvent,
+	WebhookPayload,
+	WorkspaceEvent,
+};
+use reqwest::{
+	dns::{Addrs, Name, Resolve, Resolving},
+	header::CONTENT_TYPE,
+	redirect::Policy,
+	Url,
+};
+use rustis::commands::PubSubCommands;
+use sha2::Sha256;
+use time::OffsetDateTime;
+use tokio::net::lookup_host;
+
+use crate::prelude::*;
+
+/// Runs the background tasks of webhooks. The events of workspaces are read
+/// from the change feed that [`crate::redis_publisher`] relays to Redis, and a
+/// delivery is recorded for every webhook that is subscribed to the event. The
+/// pending deliveries are then attempted, and retried with an exponential
+/// backoff until they succeed or run out of attempts. This function runs for
+/// the lifetime of the application.
+#[instrument(skip(state))]
+pub async fn run(state: &AppState) {
+	futures::future::join(record_events(state), deliver_events(state)).await;
+}
+
+/// Listens to the change feed for the events of workspaces and records them,
+/// reconnecting to Redis if the subscription ends.
+async fn record_events(state: &AppState) {
+	loop {
+		let mut pub_sub = state.redis.create_pub_sub();
+
+		if let Err(err) = pub_sub.subscribe(constants::DATABASE_CHANNEL).await {
+			error!("Error subscribing to the change feed: {err:?}");
+		} else {
+			while let Some(message) = pub_sub.next().await {
+				let Ok(message) = message.inspect_err(|err| {
+					error!("Error reading from the change feed: {err:?}");
+				}) else {
+					break;
+				};
+
+				// The change feed carries other data as well, which is ignored
+				let Ok(event) = serde_json::from_slice::<WorkspaceEvent>(&message.payload) else {
+					continue;
+				};
+
+				_ = record_event(state, &event).await.inspect_err(|err| {
+					error!("Error recording event `{}`: {err}", event.id);
+				});
+			}
+		}
+
+		tokio::time::sleep(constants::WEBHOOK_DELIVERY_POLL_INTERVAL.unsigned_abs()).await;
+	}
+}
+
+/// Records an event, along with a pending delivery for every enabled webhook
+/// of the workspace that is subscribed to the event. Every instance of the API
+/// receives the same event, so an event that is already recorded is ignored.
+#[instrument(skip(state))]
+async fn record_event(state: &AppState, event: &WorkspaceEvent) -> Result<(), sqlx::Error> {
+	let mut transaction = state.database.begin().await?;
+
+	let inserted = query!(
+		r#"
+		INSERT INTO
+			webhook_event(
+				id,
+				workspace_id,
+				event_type,
+				payload,
+				created
+			)
+		VALUES
+			($1, $2, $3, $4, NOW())
+		ON CONFLICT(id) DO NOTHING;
+		"#,
+		event.id as _,
+		event.workspace_id as _,
+		event.event.event_type() as _,
+		serde_json::to_value(&event.event).expect("event should be serializable"),
+	)
+	.execute(&mut *transaction)
+	.await?
+	.rows_affected() >
+		0;
+
+	if !inserted {
+		return Ok(());
+	}
+
+	query!(
+		r#"
+		INSERT INTO
+			webhook_delivery(
+				id,
+				webhook_id,
+				event_id,
+				status,
+				attempts,
+				next_attempt,
+				created
+			)
+		SELECT
+			gen_random_uuid(),
+			id,
+			$2,
+			'pending',
+			0,
+			NOW(),
+			NOW()
+		FROM
+			webhook
+		WHERE
+			workspace_id = $1 AND
+			deleted IS NULL AND
+			enabled = TRUE AND
+			$3 = ANY(event_types);
+		"#,
+		event.workspace_id as _,
+		event.id as _,
+		event.event.event_type() as _,
+	)
+	.execute(&mut *transaction)
+	.await?;
+
+	transaction.commit().await?;
+
+	Ok(())
+}
+
+/// Periodically attempts the deliveries that are due.
+async fn deliver_events(state: &AppState) {
+	// Redirects are not followed, since they could point to an address that
+	// webhooks can't be sent to
+	let client = reqwest::Client::builder()
+		.timeout(constants::WEBHOOK_DELIVERY_TIMEOUT.unsigned_abs())
+		.dns_resolver(Arc::new(PublicAddressResolver))
+		.redirect(Policy::none())
+		.build()
+		.expect("unable to create HTTP client for webhooks");
+
+	loop {
+		_ = deliver_due_events(state, &client).await.inspect_err(|err| {
+			error!("Error delivering webhook events: {err}");
+		});
+
+		tokio::time::sleep(constants::WEBHOOK_DELIVERY_POLL_INTERVAL.unsigned_abs()).await;
+	}
+}
+
+/// Attempts every delivery that is due, one at a time. Every delivery is
+/// locked while it is being attempted, so that other instances of the API
+/// attempt the other deliveries instead.
+async fn deliver_due_events(state: &AppState, client: &reqwest::Client) -> Result<(), sqlx::Error> {
+	loop {
+		let mut transaction = state.database.begin().await?;
+
+		let Some(delivery) = query!(
+			r#"
+			SELECT
+				webhook_delivery.id,
+				webhook_delivery.attempts,
+				webhook.url,
+				webhook.secret,
+				webhook_event.id AS "event_id",
+				webhook_event.workspace_id,
+				webhook_event.payload,
+				webhook_event.created
+			FROM
+				webhook_delivery
+			INNER JOIN
+				webhook
+			ON
+				webhook.id = webhook_delivery.webhook_id
+			INNER JOIN
+				webhook_event
+			ON
+				webhook_event.id = webhook_delivery.event_id
+			WHERE
+				webhook_delivery.status = 'pending' AND
+				webhook_delivery.next_attempt <= NOW() AND
+				webhook.deleted IS NULL AND
+				webhook.enabled = TRUE
+			ORDER BY
+				webhook_delivery.next_attempt
+			LIMIT 1
+			FOR UPDATE OF webhook_delivery SKIP LOCKED;
+			"#
+		)
+		.fetch_optional(&mut *transaction)
+		.await?
+		else {
+			return Ok(());
+		};
+
+		let delivery_id = Uuid::from(delivery.id);
+		let event_type = delivery.payload["type"]
+			.as_str()
+			.unwrap_or_default()
+			.to_string();
+
+		// The host is checked again on every delivery, since it can resolve to
+		// a different address than when the webhook was created
+		let is_url_public = is_public_url(&delivery.url).await;
+
+		let result = match serde_json::from_value::<WebhookEvent>(delivery.payload) {
+			Ok(_) if !is_url_public => Err((
+				None,
+				"The URL of the webhook does not resolve to a public address".to_string(),
+			)),
+			Ok(event) => {
+				let payload = WebhookPayload {
+					event_id: delivery.event_id.into(),
+					workspace_id: delivery.workspace_id.into(),
+					created: delivery.created,
+					event,
+				};
+				let body = serde_json::to_vec(&payload).expect("payload should be serializable");
+
+				client
+					.post(&delivery.url)
+					.header(CONTENT_TYPE, "application/json")
+					.header("X-Patr-Event", event_type)
+					.header("X-Patr-Delivery", delivery_id.to_string())
+					.header(
+						"X-Patr-Signature-256",
+						format!("sha256={}", sign_payload(delivery.secret.as_bytes(), &body)),
+					)
+					.body(body)
+					.send()
+					.await
+					.map_err(|err| (err.status().map(|status| status.as_u16()), err.to_string()))
+					.and_then(|response| {
+						let status = response.status();
+						if status.is_success() {
+							Ok(status.as_u16())
+						} else {
+							Err((
+								Some(status.as_u16()),
+								format!("The webhook responded with status code {status}"),
+							))
+						}
+					})
+			}
+			Err(err) => Err((None, format!("The event could not be parsed: {err}"))),
+		};
+
+		let attempts = delivery.attempts + 1;
+		let (status, next_attempt, response_status_code, last_error) = match result {
+			Ok(status_code) => (
+				WebhookDeliveryStatus::Succeeded,
+				None,
+				Some(status_code),
+				None,
+			),
+			Err((status_code, error)) if attempts >= constants::WEBHOOK_DELIVERY_MAX_ATTEMPTS => (
+				WebhookDeliveryStatus::Failed,
+				None,
+				status_code,
+				Some(error),
+			),
+			Err((status_code, error)) => (
+				WebhookDeliveryStatus::Pending,
+				Some(
+					OffsetDateTime::now_utc() +
+						constants::WEBHOOK_DELIVERY_INITIAL_RETRY_INTERVAL *
+							2i32.pow(attempts as u32 - 1),
+				),
+				status_code,
+				Some(error),
+			),
+		};
+
+		if let Some(error) = &last_error {
+			debug!("Attempt {attempts} of delivery `{delivery_id}` failed: {error}");
+		}
+
+		query!(
+			r#"
+			UPDATE
+				webhook_delivery
+			SET
+				status = $2,
+				attempts = $3,
+				next_attempt = $4,
+				last_attempt = NOW(),
+				response_status_code = $5,
+				last_error = $6
+			WHERE
+				id = $1;
+			"#,
+			delivery_id as _,
+			status as _,
+			attempts,
+			next_attempt,
+			response_status_code.map(i32::from),
+			last_error,
+		)
+		.execute(&mut *transaction)
+		.await?;
+
+		transaction.commit().await?;
+	}
+}
+
+/// Signs the body of a request to a webhook with the secret of the webhook,
+/// using HMAC-SHA256. The signature is returned as a lowercase hex string.
+pub fn sign_payload(secret: &[u8], body: &[u8]) -> String {
+	let mut mac =
+		Hmac::<Sha256>::new_from_slice(secret).expect("HMAC can take a key of any size");
+	mac.update(body);
+
+	mac.finalize()
+		.into_bytes()
+		.iter()
+		.map(|byte| format!("{byte:02x}"))
+		.collect()
+}
+
+/// Checks if a URL only points to addresses that webhooks can be sent to. The
+/// host of the URL is resolved, and every address it resolves to must be a
+/// public address. See [`is_public_address`] for more details.
+pub async fn is_public_url(url: &str) -> bool {
+	let Ok(url) = Url::parse(url) else {
+		return false;
+	};
+
+	let addresses = if let Some(domain) = url.domain() {
+		match lookup_host((domain, 0)).await {
+			Ok(addresses) => addresses.map(|address| address.ip()).collect(),
+			Err(err) => {
+				debug!("Failed to resolve `{domain}`: {err}");
+				return false;
+			}
+		}
+	} else {
+		// IPv6 hosts are enclosed in brackets in URLs
+		url.host_str()
+			.and_then(|host| host.trim_start_matches('[').trim_end_matches(']').parse().ok())
+			.into_iter()
+			.collect::<Vec<_>>()
+	};
+
+	!addresses.is_empty() && addresses.into_iter().all(is_public_address)
+}
+
+/// Checks if an address is one that webhooks can be sent to. Webhooks can't be
+/// sent to loopback, private, link-local, unique local or unspecified
+/// addresses, so that they can't be used to reach the services that are only
+/// accessible from within the network of the API.
+fn is_public_address(address: IpAddr) -> bool {
+	match address {
+		IpAddr::V4(address) => is_public_ipv4_address(address),
+		IpAddr::V6(address) => {
+			if let Some(address) = address.to_ipv4_mapped() {
+				return is_public_ipv4_address(address);
+			}
+			let is_unique_local = (address.segments()[0] & 0xfe00) == 0xfc00;
+			let is_link_local = (address.segments()[0] & 0xffc0) == 0xfe80;
+			address != Ipv6Addr::LOCALHOST &&
+				address != Ipv6Addr::UNSPECIFIED &&
+				!is_unique_local &&
+				!is_link_local
+		}
+	}
+}
+
+/// Checks if an IPv4 address is one that webhooks can be sent to. See
+/// [`is_public_address`] for more details.
+fn is_public_ipv4_address(address: Ipv4Addr) -> bool {
+	!address.is_loopback() &&
+		!address.is_private() &&
+		!address.is_link_local() &&
+		!address.is_unspecified()
+}
+
+/// Resolves the hosts of the URLs that webhooks are sent to, leaving out any
+/// addresses that webhooks can't be sent to. Since the addresses are checked
+/// when the request is made, a host can't be changed to resolve to an internal
+/// address after it is checked.
+struct PublicAddressResolver;
+
+impl Resolve for PublicAddressResolver {
+	fn resolve(&self, name: Name) -> Resolving {
+		Box::pin(async move {
+			let addresses = lookup_host((name.as_str(), 0))
+				.await?
+				.filter(|address| is_public_address(address.ip()))
+				.collect::<Vec<_>>();
+			if addresses.is_empty() {
+				return Err(format!("`{}` does not resolve to a public address", name.as_str()).into());
+			}
+
+			Ok(Box::new(addresses.into_iter()) as Addrs)
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::net::IpAddr;
+
+	use super::{is_public_address, is_public_url, sign_payload};
+
+	fn is_public(address: &str) -> bool {
+		is_public_address(address.parse::<IpAddr>().unwrap())
+	}
+
+	#[test]
+	fn assert_public_addresses_are_allowed() {
+		assert!(is_public("93.184.216.34"));
+		assert!(is_public("2606:2800:220:1:248:1893:25c8:1946"));
+	}
+
+	#[test]
+	fn assert_loopback_addresses_are_rejected() {
+		assert!(!is_public("127.0.0.1"));
+		assert!(!is_public("127.255.255.254"));
+		assert!(!is_public("::1"));
+		assert!(!is_public("::ffff:127.0.0.1"));
+	}
+
+	#[test]
+	fn assert_private_addresses_are_rejected() {
+		assert!(!is_public("10.0.0.1"));
+		assert!(!is_public("172.16.0.1"));
+		assert!(!is_public("172.31.255.255"));
+		assert!(!is_public("192.168.1.1"));
+		assert!(!is_public("::ffff:10.0.0.1"));
+	}
+
+	#[test]
+	fn assert_link_local_addresses_are_rejected() {
+		assert!(!is_public("169.254.169.254"));
+		assert!(!is_public("fe80::1"));
+		assert!(!is_public("febf::1"));
+	}
+
+	#[test]
+	fn assert_unique_local_addresses_are_rejected() {
+		assert!(!is_public("fc00::1"));
+		assert!(!is_public("fd12:3456:789a::1"));
+	}
+
+	#[test]
+	fn assert_unspecified_addresses_are_rejected() {
+		assert!(!is_public("0.0.0.0"));
+		assert!(!is_public("::"));
+	}
+
+	#[tokio::test]
+	async fn assert_urls_are_checked_by_their_addresses() {
+		assert!(is_public_url("https://93.184.216.34/hook").await);
+		assert!(!is_public_url("http://127.0.0.1:8080/hook").await);
+		assert!(!is_public_url("http://[::1]/hook").await);
+		assert!(!is_public_url("http://[fd00::1]/hook").await);
+		assert!(!is_public_url("http://localhost/hook").await);
+		assert!(!is_public_url("not a url").await);
+	}
+
+	#[test]
+	fn assert_payload_is_signed_with_hmac_sha256() {
+		// Test case 2 of RFC 4231
+		assert_eq!(
+			sign_payload(b"Jefe", b"what do ya want for nothing?"),
+			"5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+		);
+	}
+}
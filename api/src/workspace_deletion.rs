@@ -0,0 +1,632 @@
+use models::api::workspace::{
+	deletion::WorkspaceDeletionStep,
+	runner::StreamRunnerDataForWorkspaceServerMsg,
+};
+use rustis::{client::Client as RedisClient, commands::PubSubCommands};
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// Runs a background task that destroys the resources of the workspaces whose
+/// grace period of deletion has ended. The resources are destroyed one step at
+/// a time, and the progress is saved after every step. A step that fails is
+/// retried after [`constants::WORKSPACE_DELETION_RETRY_INTERVAL`], continuing
+/// from where the deletion left off. This function runs for the lifetime of
+/// the application.
+#[instrument(skip(state))]
+pub async fn run(state: &AppState) {
+	loop {
+		_ = run_due_deletions(state).await.inspect_err(|err| {
+			error!("Error running the deletion of workspaces: {err}");
+		});
+
+		tokio::time::sleep(constants::WORKSPACE_DELETION_POLL_INTERVAL.unsigned_abs()).await;
+	}
+}
+
+/// Runs the deletion of every workspace whose grace period has ended, skipping
+/// the ones that failed too recently to be retried yet.
+async fn run_due_deletions(state: &AppState) -> Result<(), sqlx::Error> {
+	let workspaces = query!(
+		r#"
+		SELECT
+			workspace_id
+		FROM
+			workspace_deletion
+		WHERE
+			completed IS NULL AND
+			scheduled <= NOW() AND
+			(
+				last_error IS NULL OR
+				last_attempt <= $1
+			);
+		"#,
+		OffsetDateTime::now_utc() - constants::WORKSPACE_DELETION_RETRY_INTERVAL
+	)
+	.fetch_all(&state.database)
+	.await?;
+
+	for workspace in workspaces {
+		let workspace_id = Uuid::from(workspace.workspace_id);
+
+		let Err(err) = run_deletion(state, &workspace_id).await else {
+			continue;
+		};
+
+		error!("Error deleting workspace `{workspace_id}`: {err:#}");
+
+		query!(
+			r#"
+			UPDATE
+				workspace_deletion
+			SET
+				failed_attempts = failed_attempts + 1,
+				last_error = $2,
+				last_attempt = NOW()
+			WHERE
+				workspace_id = $1;
+			"#,
+			workspace_id as _,
+			format!("{err:#}"),
+		)
+		.execute(&state.database)
+		.await?;
+	}
+
+	Ok(())
+}
+
+/// Runs the remaining steps of the deletion of a workspace. Every step is run
+/// in its own transaction, along with saving the progress of the deletion, so
+/// that a step is either completed entirely or not at all. If another instance
+/// of the API is already running the deletion, this does nothing.
+#[instrument(skip(state))]
+async fn run_deletion(state: &AppState, workspace_id: &Uuid) -> Result<(), anyhow::Error> {
+	loop {
+		let mut transaction = state.database.begin().await?;
+
+		// The row is locked until the step is completed, so that the workspace
+		// cannot be restored while its resources are being destroyed
+		let Some(current_step) = query!(
+			r#"
+			SELECT
+				current_step AS "current_step: WorkspaceDeletionStep"
+			FROM
+				workspace_deletion
+			WHERE
+				workspace_id = $1 AND
+				completed IS NULL AND
+				scheduled <= NOW()
+			FOR UPDATE SKIP LOCKED;
+			"#,
+			workspace_id as _,
+		)
+		.fetch_optional(&mut *transaction)
+		.await?
+		.and_then(|row| row.current_step) else {
+			return Ok(());
+		};
+
+		info!("Running step `{current_step:?}` of the deletion of workspace `{workspace_id}`");
+
+		query!(
+			r#"
+			SET CONSTRAINTS ALL DEFERRED;
+			"#
+		)
+		.execute(&mut *transaction)
+		.await?;
+
+		let deleted_resources =
+			run_deletion_step(&mut transaction, &state.redis, workspace_id, current_step)
+				.await
+				.with_context(|| format!("Error running step `{current_step:?}`"))?;
+
+		query!(
+			r#"
+			UPDATE
+				resource
+			SET
+				deleted = NOW()
+			WHERE
+				id = ANY($1) AND
+				deleted IS NULL;
+			"#,
+			&deleted_resources as _,
+		)
+		.execute(&mut *transaction)
+		.await?;
+
+		query!(
+			r#"
+			SET CONSTRAINTS ALL IMMEDIATE;
+			"#
+		)
+		.execute(&mut *transaction)
+		.await?;
+
+		let next_step = current_step.next();
+
+		query!(
+			r#"
+			UPDATE
+				workspace_deletion
+			SET
+				current_step = $2,
+				failed_attempts = 0,
+				last_error = NULL,
+				last_attempt = NOW(),
+				completed = CASE
+					WHEN $2::WORKSPACE_DELETION_STEP IS NULL THEN NOW()
+				END
+			WHERE
+				workspace_id = $1;
+			"#,
+			workspace_id as _,
+			next_step as _,
+		)
+		.execute(&mut *transaction)
+		.await?;
+
+		transaction.commit().await?;
+
+		info!(
+			"Step `{current_step:?}` of workspace `{workspace_id}` deleted {} resources",
+			deleted_resources.len()
+		);
+
+		if next_step.is_none() {
+			info!("Workspace `{workspace_id}` has been deleted");
+			return Ok(());
+		}
+	}
+}
+
+/// Destroys the resources of a workspace that are a part of the given step of
+/// the deletion, and returns the IDs of the resources that were destroyed, so
+/// that they can be marked as deleted. Every step only destroys the resources
+/// that are not already deleted, so running a step again is harmless.
+async fn run_deletion_step(
+	connection: &mut DatabaseConnection,
+	redis: &RedisClient,
+	workspace_id: &Uuid,
+	step: WorkspaceDeletionStep,
+) -> Result<Vec<sqlx::types::Uuid>, anyhow::Error> {
+	match step {
+		WorkspaceDeletionStep::Deployments => {
+			let deployments = query!(
+				r#"
+				UPDATE
+					deployment
+				SET
+					status = 'deleted',
+					deleted = NOW()
+				WHERE
+					workspace_id = $1 AND
+					deleted IS NULL
+				RETURNING
+					id,
+					runner;
+				"#,
+				workspace_id as _,
+			)
+			.fetch_all(&mut *connection)
+			.await?;
+
+			// If this fails, the step is rolled back and retried, so the
+			// runners are notified again
+			for deployment in &deployments {
+				redis
+					.publish(
+						format!("{}/runner/{}/stream", workspace_id, deployment.runner),
+						serde_json::to_string(
+							&StreamRunnerDataForWorkspaceServerMsg::DeploymentDeleted {
+								id: deployment.id.into(),
+							},
+						)
+						.unwrap(),
+					)
+					.await?;
+			}
+
+			Ok(deployments
+				.into_iter()
+				.map(|deployment| deployment.id)
+				.collect())
+		}
+		WorkspaceDeletionStep::StaticSites => Ok(query!(
+			r#"
+			UPDATE
+				static_site
+			SET
+				status = 'deleted',
+				deleted = NOW()
+			WHERE
+				workspace_id = $1 AND
+				deleted IS NULL
+			RETURNING
+				id;
+			"#,
+			workspace_id as _,
+		)
+		.fetch_all(&mut *connection)
+		.await?
+		.into_iter()
+		.map(|static_site| static_site.id)
+		.collect()),
+		WorkspaceDeletionStep::ManagedDatabases => Ok(query!(
+			r#"
+			UPDATE
+				managed_database
+			SET
+				status = 'deleted',
+				deleted = NOW()
+			WHERE
+				workspace_id = $1 AND
+				deleted IS NULL
+			RETURNING
+				id;
+			"#,
+			workspace_id as _,
+		)
+		.fetch_all(&mut *connection)
+		.await?
+		.into_iter()
+		.map(|database| database.id)
+		.collect()),
+		WorkspaceDeletionStep::Volumes => Ok(query!(
+			r#"
+			UPDATE
+				deployment_volume
+			SET
+				deleted = NOW()
+			FROM
+				resource
+			WHERE
+				resource.id = deployment_volume.id AND
+				resource.owner_id = $1 AND
+				deployment_volume.deleted IS NULL
+			RETURNING
+				deployment_volume.id;
+			"#,
+			workspace_id as _,
+		)
+		.fetch_all(&mut *connection)
+		.await?
+		.into_iter()
+		.map(|volume| volume.id)
+		.collect()),
+		WorkspaceDeletionStep::ContainerRegistry => {
+			let repositories = query!(
+				r#"
+				UPDATE
+					container_registry_repository
+				SET
+					deleted = NOW()
+				WHERE
+					workspace_id = $1 AND
+					deleted IS NULL
+				RETURNING
+					id;
+				"#,
+				workspace_id as _,
+			)
+			.fetch_all(&mut *connection)
+			.await?
+			.into_iter()
+			.map(|repository| repository.id)
+			.collect::<Vec<_>>();
+
+			// Removing the tags and manifests of the repositories releases the
+			// blobs of their images
+			query!(
+				r#"
+				DELETE FROM
+					container_registry_repository_tag
+				WHERE
+					repository_id = ANY($1);
+				"#,
+				&repositories as _,
+			)
+			.execute(&mut *connection)
+			.await?;
+
+			query!(
+				r#"
+				DELETE FROM
+					container_registry_repository_manifest
+				WHERE
+					repository_id = ANY($1);
+				"#,
+				&repositories as _,
+			)
+			.execute(&mut *connection)
+			.await?;
+
+			Ok(repositories)
+		}
+		WorkspaceDeletionStep::ManagedUrls => Ok(query!(
+			r#"
+			UPDATE
+				managed_url
+			SET
+				deleted = NOW()
+			WHERE
+				workspace_id = $1 AND
+				deleted IS NULL
+			RETURNING
+				id;
+			"#,
+			workspace_id as _,
+		)
+		.fetch_all(&mut *connection)
+		.await?
+		.into_iter()
+		.map(|managed_url| managed_url.id)
+		.collect()),
+		WorkspaceDeletionStep::Domains => {
+			let domains = query!(
+				r#"
+				UPDATE
+					workspace_domain
+				SET
+					deleted = NOW()
+				WHERE
+					workspace_id = $1 AND
+					deleted IS NULL
+				RETURNING
+					id;
+				"#,
+				workspace_id as _,
+			)
+			.fetch_all(&mut *connection)
+			.await?
+			.into_iter()
+			.map(|domain| domain.id)
+			.collect::<Vec<_>>();
+
+			let dns_records = query!(
+				r#"
+				DELETE FROM
+					patr_domain_dns_record
+				WHERE
+					domain_id = ANY($1)
+				RETURNING
+					id;
+				"#,
+				&domains as _,
+			)
+			.fetch_all(&mut *connection)
+			.await?
+			.into_iter()
+			.map(|record| record.id);
+
+			Ok(domains.iter().copied().chain(dns_records).collect())
+		}
+		WorkspaceDeletionStep::Secrets => Ok(query!(
+			r#"
+			UPDATE
+				secret
+			SET
+				deleted = NOW()
+			WHERE
+				workspace_id = $1 AND
+				deleted IS NULL
+			RETURNING
+				id;
+			"#,
+			workspace_id as _,
+		)
+		.fetch_all(&mut *connection)
+		.await?
+		.into_iter()
+		.map(|secret| secret.id)
+		.collect()),
+		WorkspaceDeletionStep::Runners => Ok(query!(
+			r#"
+			UPDATE
+				runner
+			SET
+				deleted = NOW()
+			WHERE
+				workspace_id = $1 AND
+				deleted IS NULL
+			RETURNING
+				id;
+			"#,
+			workspace_id as _,
+		)
+		.fetch_all(&mut *connection)
+		.await?
+		.into_iter()
+		.map(|runner| runner.id)
+		.collect()),
+		WorkspaceDeletionStep::Workspace => {
+			query!(
+				r#"
+				DELETE FROM
+					workspace_invite_role
+				USING
+					workspace_invite
+				WHERE
+					workspace_invite_role.invite_id = workspace_invite.id AND
+					workspace_invite.workspace_id = $1;
+				"#,
+				workspace_id as _,
+			)
+			.execute(&mut *connection)
+			.await?;
+
+			query!(
+				r#"
+				DELETE FROM
+					workspace_invite
+				WHERE
+					workspace_id = $1;
+				"#,
+				workspace_id as _,
+			)
+			.execute(&mut *connection)
+			.await?;
+
+			query!(
+				r#"
+				UPDATE
+					webhook_delivery
+				SET
+					status = 'failed',
+					next_attempt = NULL,
+					last_error = 'The workspace was deleted'
+				FROM
+					webhook
+				WHERE
+					webhook.id = webhook_delivery.webhook_id AND
+					webhook.workspace_id = $1 AND
+					webhook_delivery.status = 'pending';
+				"#,
+				workspace_id as _,
+			)
+			.execute(&mut *connection)
+			.await?;
+
+			query!(
+				r#"
+				UPDATE
+					webhook
+				SET
+					deleted = NOW()
+				WHERE
+					workspace_id = $1 AND
+					deleted IS NULL;
+				"#,
+				workspace_id as _,
+			)
+			.execute(&mut *connection)
+			.await?;
+
+			query!(
+				r#"
+				DELETE FROM
+					runner_join_token
+				USING
+					service_account
+				WHERE
+					service_account.id = runner_join_token.service_account_id AND
+					service_account.workspace_id = $1 AND
+					runner_join_token.used IS NULL;
+				"#,
+				workspace_id as _,
+			)
+			.execute(&mut *connection)
+			.await?;
+
+			query!(
+				r#"
+				UPDATE
+					user_api_token
+				SET
+					revoked = NOW()
+				FROM
+					service_account
+				WHERE
+					service_account.id = user_api_token.user_id AND
+					service_account.workspace_id = $1 AND
+					user_api_token.revoked IS NULL;
+				"#,
+				workspace_id as _,
+			)
+			.execute(&mut *connection)
+			.await?;
+
+			query!(
+				r#"
+				UPDATE
+					service_account
+				SET
+					deleted = NOW()
+				WHERE
+					workspace_id = $1 AND
+					deleted IS NULL;
+				"#,
+				workspace_id as _,
+			)
+			.execute(&mut *connection)
+			.await?;
+
+			// The resources of the repositories are deleted along with the
+			// rest of the resources below
+			query!(
+				r#"
+				UPDATE
+					ci_repository
+				SET
+					deleted = NOW()
+				WHERE
+					workspace_id = $1 AND
+					deleted IS NULL;
+				"#,
+				workspace_id as _,
+			)
+			.execute(&mut *connection)
+			.await?;
+
+			let mut resources = query!(
+				r#"
+				UPDATE
+					project
+				SET
+					deleted = NOW()
+				WHERE
+					workspace_id = $1 AND
+					deleted IS NULL
+				RETURNING
+					id;
+				"#,
+				workspace_id as _,
+			)
+			.fetch_all(&mut *connection)
+			.await?
+			.into_iter()
+			.map(|project| project.id)
+			.collect::<Vec<_>>();
+
+			// Any resources that are not a part of the other steps
+			resources.extend(
+				query!(
+					r#"
+					SELECT
+						id
+					FROM
+						resource
+					WHERE
+						owner_id = $1 AND
+						deleted IS NULL;
+					"#,
+					workspace_id as _,
+				)
+				.fetch_all(&mut *connection)
+				.await?
+				.into_iter()
+				.map(|resource| resource.id),
+			);
+
+			query!(
+				r#"
+				UPDATE
+					workspace
+				SET
+					deleted = NOW()
+				WHERE
+					id = $1;
+				"#,
+				workspace_id as _,
+			)
+			.execute(&mut *connection)
+			.await?;
+
+			resources.push((*workspace_id).into());
+
+			Ok(resources)
+		}
+	}
+}
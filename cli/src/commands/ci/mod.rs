@@ -0,0 +1,36 @@
+use clap::Subcommand;
+use models::ApiErrorResponse;
+
+use self::{schema::SchemaArgs, validate::ValidateArgs};
+use crate::prelude::*;
+
+/// The command to print the JSON schema of CI files
+mod schema;
+/// The command to check a CI file for problems
+mod validate;
+
+/// A list of all the commands for working with CI files.
+#[derive(Debug, Clone, Subcommand)]
+#[command(rename_all = "kebab-case")]
+pub enum CiCommands {
+	/// Check a CI file for problems, such as steps that refer to steps that do
+	/// not exist
+	#[command(alias = "lint")]
+	Validate(ValidateArgs),
+	/// Print the JSON schema of CI files, which editors can use to autocomplete
+	/// them
+	Schema(SchemaArgs),
+}
+
+impl CommandExecutor for CiCommands {
+	async fn execute(
+		self,
+		global_args: GlobalArgs,
+		state: AppState,
+	) -> Result<CommandOutput, ApiErrorResponse> {
+		match self {
+			Self::Validate(args) => validate::execute(global_args, args, state).await,
+			Self::Schema(args) => schema::execute(global_args, args, state).await,
+		}
+	}
+}
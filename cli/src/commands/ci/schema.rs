@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use models::{ci::ci_file_json_schema, ApiErrorResponse};
+
+use crate::prelude::*;
+
+/// The arguments that can be passed to the schema command.
+#[derive(Debug, Clone, Args)]
+pub struct SchemaArgs {
+	/// The file to write the schema to, instead of printing it
+	pub file: Option<PathBuf>,
+}
+
+pub(super) async fn execute(
+	_: GlobalArgs,
+	args: SchemaArgs,
+	_: AppState,
+) -> Result<CommandOutput, ApiErrorResponse> {
+	let schema = ci_file_json_schema();
+	let contents = serde_json::to_string_pretty(&schema)?;
+
+	let text = match &args.file {
+		Some(file) => {
+			tokio::fs::write(file, contents).await?;
+			format!("Wrote the schema of CI files to `{}`", file.display())
+		}
+		None => contents,
+	};
+
+	CommandOutput {
+		text,
+		json: schema.to_json_value(),
+	}
+	.into_result()
+}
@@ -0,0 +1,93 @@
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use models::{
+	ci::{validate_ci_file, CiDiagnostic, CiDiagnosticSeverity},
+	ApiErrorResponse,
+};
+
+use crate::prelude::*;
+
+/// The arguments that can be passed to the validate command.
+#[derive(Debug, Clone, Args)]
+pub struct ValidateArgs {
+	/// The CI file to check
+	pub file: PathBuf,
+}
+
+pub(super) async fn execute(
+	_: GlobalArgs,
+	args: ValidateArgs,
+	_: AppState,
+) -> Result<CommandOutput, ApiErrorResponse> {
+	let source = tokio::fs::read_to_string(&args.file).await.map_err(|err| {
+		ApiErrorResponse::error_with_message(
+			ErrorType::WrongParameters,
+			format!("Cannot read the file `{}`: {}", args.file.display(), err),
+		)
+	})?;
+	let validation = validate_ci_file(&source);
+
+	let report = validation
+		.diagnostics
+		.iter()
+		.map(|diagnostic| render_diagnostic(&args.file, &source, diagnostic))
+		.collect::<Vec<_>>()
+		.join("\n\n");
+
+	if validation.has_errors() {
+		return Err(ApiErrorResponse::error_with_message(
+			ErrorType::WrongParameters,
+			report,
+		));
+	}
+
+	CommandOutput {
+		text: if validation.diagnostics.is_empty() {
+			format!("The CI file `{}` has no problems", args.file.display())
+		} else {
+			report
+		},
+		json: validation.diagnostics.to_json_value(),
+	}
+	.into_result()
+}
+
+/// Render a problem found in a CI file along with the line of the file that
+/// it is on, underlining where the problem is
+fn render_diagnostic(file: &Path, source: &str, diagnostic: &CiDiagnostic) -> String {
+	let severity = match diagnostic.severity {
+		CiDiagnosticSeverity::Error => "error",
+		CiDiagnosticSeverity::Warning => "warning",
+	};
+	let Some(span) = diagnostic.span else {
+		return format!(
+			"{}: {}\n  --> {}",
+			severity,
+			diagnostic.message,
+			file.display()
+		);
+	};
+
+	let location = if diagnostic.path.is_empty() {
+		format!("{}:{}:{}", file.display(), span.line, span.column)
+	} else {
+		format!(
+			"{}:{}:{} ({})",
+			file.display(),
+			span.line,
+			span.column,
+			diagnostic.path
+		)
+	};
+	let line = source.lines().nth(span.line - 1).unwrap_or_default();
+	let gutter = " ".repeat(span.line.to_string().len());
+
+	format!(
+		"{severity}: {message}\n{gutter}--> {location}\n{gutter} |\n{number} | {line}\n{gutter} | {padding}{underline}",
+		message = diagnostic.message,
+		number = span.line,
+		padding = " ".repeat(span.column.saturating_sub(1)),
+		underline = "^".repeat(span.length.max(1)),
+	)
+}
@@ -1,9 +1,11 @@
 use clap::{Args, Parser, Subcommand};
 use models::ApiErrorResponse;
 
-use self::{login::LoginArgs, workspaced::WorkspacedCommands};
+use self::{ci::CiCommands, login::LoginArgs, workspaced::WorkspacedCommands};
 use crate::prelude::*;
 
+/// All commands for working with CI files.
+mod ci;
 /// The command to get information about the current logged in user.
 mod info;
 /// The command to login to your Patr account.
@@ -48,6 +50,9 @@ pub enum GlobalCommands {
 	/// Get information about the current logged in user.
 	#[command(alias = "whoami")]
 	Info,
+	/// All the commands for working with CI files
+	#[command(subcommand)]
+	Ci(CiCommands),
 	/// All the commands that are meant for a workspace
 	#[command(flatten)]
 	Workspaced(WorkspacedCommands),
@@ -63,6 +68,7 @@ impl CommandExecutor for GlobalCommands {
 			Self::Login(args) => login::execute(args, global_args, state).await,
 			Self::Logout => logout::execute(global_args, state).await,
 			Self::Info => info::execute(global_args, state).await,
+			Self::Ci(commands) => commands.execute(global_args, state).await,
 			Self::Workspaced(commands) => commands.execute(global_args, state).await,
 		}
 	}
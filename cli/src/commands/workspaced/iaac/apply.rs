@@ -0,0 +1,83 @@
+use std::{
+	io::{IsTerminal, Write},
+	path::PathBuf,
+};
+
+use clap::Args;
+use models::ApiErrorResponse;
+
+use crate::{iaac::apply_plan, prelude::*};
+
+/// The arguments that can be passed to the apply command.
+#[derive(Debug, Clone, Args)]
+pub struct ApplyArgs {
+	/// The directory containing the IaaC files
+	#[arg(default_value = ".")]
+	pub dir: PathBuf,
+	/// The workspace to apply the changes to. Defaults to the current
+	/// workspace
+	#[arg(short = 'w', long = "workspace")]
+	pub workspace: Option<Uuid>,
+	/// Delete the resources of the workspace that are not declared in the
+	/// IaaC files
+	#[arg(long = "prune")]
+	pub prune: bool,
+	/// Apply the changes without asking for confirmation
+	#[arg(long = "auto-approve")]
+	pub auto_approve: bool,
+}
+
+pub(super) async fn execute(
+	global_args: GlobalArgs,
+	args: ApplyArgs,
+	state: AppState,
+) -> Result<CommandOutput, ApiErrorResponse> {
+	let (token, workspace_id) = super::resolve_context(&global_args, args.workspace, state)?;
+	let plan = super::create_plan(&args.dir, workspace_id, &token, args.prune).await?;
+
+	if plan.is_empty() {
+		return CommandOutput {
+			text: plan.to_string(),
+			json: Vec::<()>::new().to_json_value(),
+		}
+		.into_result();
+	}
+
+	if !args.auto_approve {
+		if !std::io::stdin().is_terminal() {
+			return Err(ApiErrorResponse::error_with_message(
+				ErrorType::WrongParameters,
+				"Applying changes needs confirmation. Use `--auto-approve` when not running in an interactive terminal.",
+			));
+		}
+
+		eprintln!("{}", plan);
+		eprintln!();
+		eprint!("Do you want to apply these changes? Only `yes` will be accepted: ");
+		std::io::stderr().flush()?;
+
+		let mut answer = String::new();
+		std::io::stdin().read_line(&mut answer)?;
+		if answer.trim() != "yes" {
+			return Err(ApiErrorResponse::error_with_message(
+				ErrorType::WrongParameters,
+				"Apply cancelled. No changes were made.",
+			));
+		}
+	}
+
+	let applied = apply_plan(&plan, &token).await?;
+
+	CommandOutput {
+		text: format!(
+			"Apply complete! {} resource(s) changed in the workspace `{}`.",
+			applied
+				.iter()
+				.filter(|change| change.action != crate::iaac::PlannedAction::NoOp)
+				.count(),
+			workspace_id
+		),
+		json: applied.to_json_value(),
+	}
+	.into_result()
+}
@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use models::ApiErrorResponse;
+
+use crate::{
+	iaac::{export_resources, LiveState},
+	prelude::*,
+};
+
+/// The arguments that can be passed to the export command.
+#[derive(Debug, Clone, Args)]
+pub struct ExportArgs {
+	/// The directory to write the IaaC files to
+	#[arg(default_value = ".")]
+	pub dir: PathBuf,
+	/// The workspace to export. Defaults to the current workspace
+	#[arg(short = 'w', long = "workspace")]
+	pub workspace: Option<Uuid>,
+	/// Overwrite the IaaC files in the directory if they already exist
+	#[arg(long = "force")]
+	pub force: bool,
+}
+
+pub(super) async fn execute(
+	global_args: GlobalArgs,
+	args: ExportArgs,
+	state: AppState,
+) -> Result<CommandOutput, ApiErrorResponse> {
+	let (token, workspace_id) = super::resolve_context(&global_args, args.workspace, state)?;
+	let live = LiveState::fetch(workspace_id, &token).await?;
+	let files = export_resources(&live)?;
+
+	if !args.force {
+		for file in files.keys() {
+			let path = args.dir.join(file);
+			if tokio::fs::try_exists(&path).await? {
+				return Err(ApiErrorResponse::error_with_message(
+					ErrorType::WrongParameters,
+					format!(
+						"The file `{}` already exists. Use `--force` to overwrite it.",
+						path.display()
+					),
+				));
+			}
+		}
+	}
+
+	tokio::fs::create_dir_all(&args.dir).await?;
+	let mut written = Vec::with_capacity(files.len());
+	for (file, resources) in files {
+		let path = args.dir.join(file);
+		tokio::fs::write(&path, serde_yaml::to_string(&resources)?).await?;
+		written.push(path);
+	}
+
+	CommandOutput {
+		text: if written.is_empty() {
+			format!(
+				"The workspace `{}` has no resources to export.",
+				workspace_id
+			)
+		} else {
+			format!(
+				"Exported the workspace `{}` to:\n{}",
+				workspace_id,
+				written
+					.iter()
+					.map(|path| format!("  {}", path.display()))
+					.collect::<Vec<_>>()
+					.join("\n")
+			)
+		},
+		json: written.to_json_value(),
+	}
+	.into_result()
+}
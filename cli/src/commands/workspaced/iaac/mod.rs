@@ -0,0 +1,104 @@
+use std::{path::Path, str::FromStr};
+
+use clap::Subcommand;
+use models::ApiErrorResponse;
+
+use self::{apply::ApplyArgs, export::ExportArgs, plan::PlanArgs};
+use crate::{
+	iaac::{load_resources, LiveState, Plan, ResourceGraph},
+	prelude::*,
+};
+
+/// The command to apply the changes of the IaaC files to a workspace
+mod apply;
+/// The command to export the resources of a workspace as IaaC files
+mod export;
+/// The command to show the changes that applying the IaaC files would make
+mod plan;
+
+/// A list of all the commands for managing a workspace using IaaC files.
+#[derive(Debug, Clone, Subcommand)]
+#[command(rename_all = "kebab-case")]
+pub enum IaacCommands {
+	/// Show the changes that applying the IaaC files of a directory would make
+	/// to a workspace
+	Plan(PlanArgs),
+	/// Apply the changes of the IaaC files of a directory to a workspace
+	Apply(ApplyArgs),
+	/// Export the resources of a workspace as IaaC files to a directory
+	Export(ExportArgs),
+}
+
+impl CommandExecutor for IaacCommands {
+	async fn execute(
+		self,
+		global_args: GlobalArgs,
+		state: AppState,
+	) -> Result<CommandOutput, ApiErrorResponse> {
+		match self {
+			Self::Plan(args) => plan::execute(global_args, args, state).await,
+			Self::Apply(args) => apply::execute(global_args, args, state).await,
+			Self::Export(args) => export::execute(global_args, args, state).await,
+		}
+	}
+}
+
+/// Get the token to authenticate with and the workspace to work with. The token
+/// given in the arguments takes precedence over the logged in user, and the
+/// workspace given in the arguments takes precedence over the current
+/// workspace.
+fn resolve_context(
+	global_args: &GlobalArgs,
+	workspace: Option<Uuid>,
+	state: AppState,
+) -> Result<(BearerToken, Uuid), ApiErrorResponse> {
+	let (token, current_workspace) = match (&global_args.token, state) {
+		(Some(token), state) => (
+			BearerToken::from_str(token)?,
+			match state {
+				AppState::LoggedIn {
+					current_workspace, ..
+				} => current_workspace,
+				AppState::LoggedOut => None,
+			},
+		),
+		(
+			None,
+			AppState::LoggedIn {
+				token,
+				refresh_token: _,
+				current_workspace,
+			},
+		) => (token, current_workspace),
+		(None, AppState::LoggedOut) => {
+			return Err(ApiErrorResponse::error_with_message(
+				ErrorType::Unauthorized,
+				"You are not logged in. Please log in or provide an API token with `--token`.",
+			));
+		}
+	};
+
+	let workspace_id = workspace.or(current_workspace).ok_or_else(|| {
+		ApiErrorResponse::error_with_message(
+			ErrorType::WrongParameters,
+			"No workspace selected. Please switch to a workspace or provide one with `--workspace`.",
+		)
+	})?;
+
+	Ok((token, workspace_id))
+}
+
+/// Load the IaaC files of a directory and plan the changes needed for the
+/// workspace to match them
+async fn create_plan(
+	dir: &Path,
+	workspace_id: Uuid,
+	token: &BearerToken,
+	prune: bool,
+) -> Result<Plan, ApiErrorResponse> {
+	let resources = load_resources(dir).await?;
+	let graph = ResourceGraph::build(resources)?;
+	let live = LiveState::fetch(workspace_id, token).await?;
+
+	Plan::create(workspace_id, &graph, &live, prune)
+}
@@ -0,0 +1,37 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use models::ApiErrorResponse;
+
+use crate::prelude::*;
+
+/// The arguments that can be passed to the plan command.
+#[derive(Debug, Clone, Args)]
+pub struct PlanArgs {
+	/// The directory containing the IaaC files
+	#[arg(default_value = ".")]
+	pub dir: PathBuf,
+	/// The workspace to plan the changes for. Defaults to the current
+	/// workspace
+	#[arg(short = 'w', long = "workspace")]
+	pub workspace: Option<Uuid>,
+	/// Delete the resources of the workspace that are not declared in the
+	/// IaaC files
+	#[arg(long = "prune")]
+	pub prune: bool,
+}
+
+pub(super) async fn execute(
+	global_args: GlobalArgs,
+	args: PlanArgs,
+	state: AppState,
+) -> Result<CommandOutput, ApiErrorResponse> {
+	let (token, workspace_id) = super::resolve_context(&global_args, args.workspace, state)?;
+	let plan = super::create_plan(&args.dir, workspace_id, &token, args.prune).await?;
+
+	CommandOutput {
+		text: plan.to_string(),
+		json: plan.to_json_value(),
+	}
+	.into_result()
+}
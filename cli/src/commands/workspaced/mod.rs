@@ -1,10 +1,12 @@
 use clap::Subcommand;
 use models::ApiErrorResponse;
 
-use self::workspace::WorkspaceCommands;
+use self::{iaac::IaacCommands, workspace::WorkspaceCommands};
 use super::{CommandExecutor, GlobalArgs};
 use crate::prelude::*;
 
+/// The commands to manage a workspace using IaaC files
+mod iaac;
 mod infrastructure;
 mod workspace;
 
@@ -14,6 +16,8 @@ mod workspace;
 pub enum WorkspacedCommands {
 	#[command(flatten)]
 	WorkspaceCommands(WorkspaceCommands),
+	#[command(flatten)]
+	IaacCommands(IaacCommands),
 	// #[command(flatten)]
 	// InfrastructureCommands(InfrastructureCommands),
 	// #[command(flatten)]
@@ -28,6 +32,7 @@ impl CommandExecutor for WorkspacedCommands {
 	) -> Result<CommandOutput, ApiErrorResponse> {
 		match self {
 			Self::WorkspaceCommands(commands) => commands.execute(global_args, state).await,
+			Self::IaacCommands(commands) => commands.execute(global_args, state).await,
 			/* Self::InfrastructureCommands(commands) => {
 			 * 	commands.execute(global_args, writer).await
 			 * }
@@ -0,0 +1,1050 @@
+use std::collections::BTreeMap;
+
+use models::{
+	api::workspace::{
+		container_registry::*,
+		database::*,
+		deployment::*,
+		domain::*,
+		managed_url::*,
+		secret::*,
+		static_site::*,
+	},
+	iaac::{IaacConversionError, IaacResourceType},
+	ApiErrorResponse,
+};
+use serde::Serialize;
+
+use super::{
+	deployment_recreate_request,
+	deployment_update_request,
+	output_from_id,
+	AppliedIds,
+	DesiredDatabase,
+	DesiredDeployment,
+	DesiredResource,
+	LiveResource,
+	Plan,
+	PlannedAction,
+	PlannedChange,
+	ResolvedValue,
+	ResourceKey,
+};
+use crate::prelude::*;
+
+/// A change that was made to a resource of a workspace when applying a plan
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppliedChange {
+	/// The type of the resource
+	pub resource_type: IaacResourceType,
+	/// The name of the resource
+	pub name: String,
+	/// What was done to the resource
+	pub action: PlannedAction,
+	/// The ID of the resource after the change, if it still exists
+	pub id: Option<Uuid>,
+}
+
+/// A single API call that was made while applying a plan, along with what is
+/// needed to undo it
+#[derive(Debug, Clone)]
+enum CompletedStep {
+	/// A resource was created
+	Created {
+		/// The type of the resource
+		resource_type: IaacResourceType,
+		/// The name of the resource
+		name: String,
+		/// The ID of the created resource
+		id: Uuid,
+	},
+	/// A deployment was updated
+	UpdatedDeployment {
+		/// The deployment as it was before the update
+		previous: GetDeploymentInfoResponse,
+	},
+	/// A managed URL was updated
+	UpdatedManagedUrl {
+		/// The name of the managed URL
+		name: String,
+		/// The managed URL as it was before the update
+		previous: WithId<ManagedUrl>,
+	},
+	/// The value of a secret was changed. The previous value of a secret
+	/// cannot be read back, so this cannot be undone.
+	UpdatedSecret {
+		/// The name of the secret
+		name: String,
+	},
+	/// A resource was deleted
+	Deleted {
+		/// The name of the resource
+		name: String,
+		/// The resource as it was before it was deleted
+		previous: LiveResource,
+	},
+}
+
+impl CompletedStep {
+	/// Get the type and the name of the resource that the step was made to
+	fn resource(&self) -> (IaacResourceType, &String) {
+		match self {
+			Self::Created {
+				resource_type,
+				name,
+				..
+			} => (*resource_type, name),
+			Self::UpdatedDeployment { previous } => {
+				(IaacResourceType::Deployment, &previous.deployment.data.name)
+			}
+			Self::UpdatedManagedUrl { name, .. } => (IaacResourceType::ManagedUrl, name),
+			Self::UpdatedSecret { name } => (IaacResourceType::Secret, name),
+			Self::Deleted { name, previous } => (previous.resource_type(), name),
+		}
+	}
+}
+
+/// Apply a plan to its workspace, making the changes in the order of the plan.
+/// If any change fails, the changes that were already made are undone in the
+/// reverse order, so that the workspace is left as it was before.
+pub async fn apply_plan(
+	plan: &Plan,
+	token: &BearerToken,
+) -> Result<Vec<AppliedChange>, ApiErrorResponse> {
+	let mut journal = Vec::new();
+	let mut applied = Vec::with_capacity(plan.changes.len());
+	// The IDs of the resources that the plan has been applied to so far, which
+	// the outputs that are only known after applying are derived from
+	let mut ids = BTreeMap::<ResourceKey, Uuid>::new();
+
+	for change in &plan.changes {
+		match apply_change(plan.workspace_id, change, &ids, token, &mut journal).await {
+			Ok(id) => {
+				if let Some(id) = id {
+					ids.insert((change.resource_type, change.name.clone()), id);
+				}
+				applied.push(AppliedChange {
+					resource_type: change.resource_type,
+					name: change.name.clone(),
+					action: change.action,
+					id,
+				});
+			}
+			Err(err) => {
+				error!(
+					"Failed to apply the change to the {} `{}`: {}",
+					change.resource_type, change.name, err.body.message
+				);
+				let rolled_back = journal.len();
+				let failures = rollback(plan.workspace_id, journal, token).await;
+
+				let message = if failures.is_empty() {
+					format!(
+						"Failed to apply the change to the {} `{}`: {}. {} completed change(s) were rolled back.",
+						change.resource_type, change.name, err.body.message, rolled_back
+					)
+				} else {
+					format!(
+						"Failed to apply the change to the {} `{}`: {}. Rolling back failed, the workspace may need to be fixed manually: {}",
+						change.resource_type,
+						change.name,
+						err.body.message,
+						failures.join("; ")
+					)
+				};
+
+				return Err(ApiErrorResponse::error_with_message(
+					err.body.error,
+					message,
+				));
+			}
+		}
+	}
+
+	Ok(applied)
+}
+
+/// Apply a single change of a plan, recording every API call that succeeds in
+/// the journal. Returns the ID of the resource after the change, if it still
+/// exists.
+async fn apply_change(
+	workspace_id: Uuid,
+	change: &PlannedChange,
+	ids: &BTreeMap<ResourceKey, Uuid>,
+	token: &BearerToken,
+	journal: &mut Vec<CompletedStep>,
+) -> Result<Option<Uuid>, ApiErrorResponse> {
+	match (change.action, &change.current, &change.desired) {
+		(PlannedAction::NoOp, Some(current), _) => Ok(Some(current.id())),
+		(PlannedAction::Create, None, Some(desired)) => {
+			create_resource(workspace_id, change, desired, ids, token, journal)
+				.await
+				.map(Some)
+		}
+		(
+			PlannedAction::Update,
+			Some(LiveResource::Deployment(current)),
+			Some(DesiredResource::Deployment(desired)),
+		) => {
+			let desired = fill_pending_outputs(desired, ids)?;
+			update_deployment(
+				workspace_id,
+				current.deployment.id,
+				token,
+				deployment_update_request(current, &desired),
+			)
+			.await?;
+			journal.push(CompletedStep::UpdatedDeployment {
+				previous: current.clone(),
+			});
+			Ok(Some(current.deployment.id))
+		}
+		(
+			PlannedAction::Update,
+			Some(LiveResource::ManagedUrl(current)),
+			Some(DesiredResource::ManagedUrl(desired)),
+		) => {
+			let request = desired
+				.to_create_request(&AppliedIds(ids))
+				.map_err(|err| unresolved(change, err))?;
+			update_managed_url(
+				workspace_id,
+				current.id,
+				token,
+				UpdateManagedURLRequest {
+					path: request.path,
+					url_type: request.url_type,
+				},
+			)
+			.await?;
+			journal.push(CompletedStep::UpdatedManagedUrl {
+				name: change.name.clone(),
+				previous: current.clone(),
+			});
+			Ok(Some(current.id))
+		}
+		(PlannedAction::Replace, Some(current), Some(desired)) => {
+			delete_resource(workspace_id, change.resource_type, current.id(), token).await?;
+			journal.push(CompletedStep::Deleted {
+				name: change.name.clone(),
+				previous: current.clone(),
+			});
+			create_resource(workspace_id, change, desired, ids, token, journal)
+				.await
+				.map(Some)
+		}
+		(PlannedAction::Delete, Some(current), None) => {
+			delete_resource(workspace_id, change.resource_type, current.id(), token).await?;
+			journal.push(CompletedStep::Deleted {
+				name: change.name.clone(),
+				previous: current.clone(),
+			});
+			Ok(None)
+		}
+		_ => Err(ApiErrorResponse::internal_error(format!(
+			"invalid planned change to the {} `{}`",
+			change.resource_type, change.name
+		))),
+	}
+}
+
+/// Create a declared resource, recording it in the journal. The password of a
+/// database is saved to its secret right after the database is created.
+/// Returns the ID of the created resource.
+async fn create_resource(
+	workspace_id: Uuid,
+	change: &PlannedChange,
+	desired: &DesiredResource,
+	ids: &BTreeMap<ResourceKey, Uuid>,
+	token: &BearerToken,
+	journal: &mut Vec<CompletedStep>,
+) -> Result<Uuid, ApiErrorResponse> {
+	let id = match desired {
+		DesiredResource::Deployment(desired) => {
+			create_deployment(workspace_id, token, fill_pending_outputs(desired, ids)?).await?
+		}
+		DesiredResource::Database(desired) => {
+			create_database(workspace_id, token, desired.request.clone()).await?
+		}
+		DesiredResource::Domain(request) => {
+			add_domain(workspace_id, token, request.clone()).await?
+		}
+		DesiredResource::DockerRepository(request) => {
+			create_repository(workspace_id, token, request.clone()).await?
+		}
+		DesiredResource::Secret(desired) => {
+			let value = match &desired.value {
+				ResolvedValue::Known { value, .. } => value.clone(),
+				ResolvedValue::KnownAfterApply(reference) => ids
+					.get(&(reference.resource_type, reference.name.clone()))
+					.and_then(|id| output_from_id(reference.output, *id))
+					.ok_or_else(|| {
+						ApiErrorResponse::error_with_message(
+							ErrorType::WrongParameters,
+							format!(
+								"The value of the secret `{}` is sourced from `{}`, which is not known after applying the {} `{}`",
+								desired.name, reference, reference.resource_type, reference.name
+							),
+						)
+					})?,
+			};
+			create_secret(
+				workspace_id,
+				token,
+				CreateSecretRequest {
+					name: desired.name.clone(),
+					value,
+				},
+			)
+			.await?
+		}
+		DesiredResource::ManagedUrl(desired) => {
+			let request = desired
+				.to_create_request(&AppliedIds(ids))
+				.map_err(|err| unresolved(change, err))?;
+			create_managed_url(workspace_id, token, request).await?
+		}
+		DesiredResource::StaticSite(request) => {
+			create_static_site(workspace_id, token, request.clone()).await?
+		}
+	};
+	journal.push(CompletedStep::Created {
+		resource_type: change.resource_type,
+		name: change.name.clone(),
+		id,
+	});
+
+	if let DesiredResource::Database(DesiredDatabase {
+		password_secret: Some((secret, secret_id)),
+		..
+	}) = desired
+	{
+		let password = get_database(workspace_id, id, token)
+			.await?
+			.data
+			.public_connection
+			.password;
+		match secret_id {
+			Some(secret_id) => {
+				update_secret(
+					workspace_id,
+					*secret_id,
+					token,
+					UpdateSecretRequest {
+						name: None,
+						value: Some(password),
+					},
+				)
+				.await?;
+				journal.push(CompletedStep::UpdatedSecret {
+					name: secret.clone(),
+				});
+			}
+			None => {
+				let secret_id = create_secret(
+					workspace_id,
+					token,
+					CreateSecretRequest {
+						name: secret.clone(),
+						value: password,
+					},
+				)
+				.await?;
+				journal.push(CompletedStep::Created {
+					resource_type: IaacResourceType::Secret,
+					name: secret.clone(),
+					id: secret_id,
+				});
+			}
+		}
+	}
+
+	Ok(id)
+}
+
+/// The error for a resource that refers to a resource which was expected to be
+/// created earlier in the plan, but was not
+fn unresolved(change: &PlannedChange, err: IaacConversionError) -> ApiErrorResponse {
+	ApiErrorResponse::error_with_message(
+		ErrorType::WrongParameters,
+		format!(
+			"The {} `{}` cannot be applied: {}",
+			change.resource_type, change.name, err
+		),
+	)
+}
+
+/// Build the request that creates a deployment, with the values of the
+/// environment variables that are outputs of resources or secrets that were
+/// applied earlier in the plan filled in
+fn fill_pending_outputs(
+	desired: &DesiredDeployment,
+	ids: &BTreeMap<ResourceKey, Uuid>,
+) -> Result<CreateDeploymentRequest, ApiErrorResponse> {
+	let mut request = desired.request.clone();
+	for (key, reference) in &desired.pending_outputs {
+		let value = ids
+			.get(&(reference.resource_type, reference.name.clone()))
+			.and_then(|id| output_from_id(reference.output, *id))
+			.ok_or_else(|| {
+				ApiErrorResponse::error_with_message(
+					ErrorType::WrongParameters,
+					format!(
+						"The environment variable `{}` of the deployment `{}` is sourced from `{}`, which is not known after applying the {} `{}`",
+						key,
+						request.name,
+						reference,
+						reference.resource_type,
+						reference.name
+					),
+				)
+			})?;
+		request
+			.running_details
+			.environment_variables
+			.insert(key.clone(), EnvironmentVariableValue::String(value));
+	}
+	for (key, secret) in &desired.pending_secrets {
+		let from_secret = ids
+			.get(&(IaacResourceType::Secret, secret.clone()))
+			.copied()
+			.ok_or_else(|| {
+				ApiErrorResponse::error_with_message(
+					ErrorType::WrongParameters,
+					format!(
+						"The environment variable `{}` of the deployment `{}` is the secret `{}`, which was not created",
+						key, request.name, secret
+					),
+				)
+			})?;
+		request
+			.running_details
+			.environment_variables
+			.insert(key.clone(), EnvironmentVariableValue::Secret { from_secret });
+	}
+	Ok(request)
+}
+
+/// Undo the completed steps of a plan in the reverse order they were made in.
+/// Returns a description of every step that could not be undone.
+async fn rollback(
+	workspace_id: Uuid,
+	journal: Vec<CompletedStep>,
+	token: &BearerToken,
+) -> Vec<String> {
+	let mut failures = Vec::new();
+
+	for step in journal.into_iter().rev() {
+		let (resource_type, name) = step.resource();
+		let (action, result) = match &step {
+			CompletedStep::Created { id, .. } => {
+				info!(
+					"Rolling back the creation of the {} `{}`",
+					resource_type, name
+				);
+				(
+					"delete",
+					delete_resource(workspace_id, resource_type, *id, token)
+						.await
+						.map_err(|err| err.body.message),
+				)
+			}
+			CompletedStep::UpdatedDeployment { previous } => {
+				info!("Rolling back the update of the deployment `{}`", name);
+				(
+					"restore",
+					update_deployment(
+						workspace_id,
+						previous.deployment.id,
+						token,
+						deployment_restore_request(previous),
+					)
+					.await
+					.map_err(|err| err.body.message),
+				)
+			}
+			CompletedStep::UpdatedManagedUrl { previous, .. } => {
+				info!("Rolling back the update of the managed URL `{}`", name);
+				(
+					"restore",
+					update_managed_url(
+						workspace_id,
+						previous.id,
+						token,
+						UpdateManagedURLRequest {
+							path: previous.data.path.clone(),
+							url_type: previous.data.url_type.clone(),
+						},
+					)
+					.await
+					.map_err(|err| err.body.message),
+				)
+			}
+			CompletedStep::UpdatedSecret { .. } => (
+				"restore",
+				Err("the previous value of a secret cannot be read back".to_string()),
+			),
+			CompletedStep::Deleted { previous, .. } => {
+				info!("Rolling back the deletion of the {} `{}`", resource_type, name);
+				(
+					"recreate",
+					recreate_resource(workspace_id, previous, token)
+						.await
+						.map(|id| {
+							warn!(
+								"The {} `{}` was recreated with the new ID `{}`",
+								resource_type, name, id
+							);
+						}),
+				)
+			}
+		};
+
+		if let Err(message) = result {
+			failures.push(format!(
+				"could not {} the {} `{}`: {}",
+				action, resource_type, name, message
+			));
+		}
+	}
+
+	failures
+}
+
+/// Create a resource that was deleted by a plan again, as it was before it was
+/// deleted. Only the resource itself is recreated: the data of a database, the
+/// images of a repository and the files of a static site are gone with it, and
+/// the value of a secret cannot be read back at all. Returns the new ID of the
+/// resource.
+async fn recreate_resource(
+	workspace_id: Uuid,
+	previous: &LiveResource,
+	token: &BearerToken,
+) -> Result<Uuid, String> {
+	let result = match previous {
+		LiveResource::Deployment(previous) => {
+			create_deployment(workspace_id, token, deployment_recreate_request(previous)).await
+		}
+		LiveResource::Database(previous) => {
+			create_database(
+				workspace_id,
+				token,
+				CreateDatabaseRequest {
+					name: previous.data.name.clone(),
+					engine: previous.data.engine.clone(),
+					database_plan_id: previous.data.database_plan_id,
+					region: previous.data.region,
+					version: previous.data.version.clone(),
+					num_node: previous.data.num_nodes,
+				},
+			)
+			.await
+		}
+		LiveResource::Domain(previous) => {
+			add_domain(
+				workspace_id,
+				token,
+				AddDomainToWorkspaceRequest {
+					domain: previous.data.domain.name.clone(),
+					nameserver_type: previous.data.nameserver_type.clone(),
+				},
+			)
+			.await
+		}
+		LiveResource::DockerRepository(previous) => {
+			create_repository(
+				workspace_id,
+				token,
+				CreateContainerRepositoryRequest {
+					name: previous.data.name.clone(),
+				},
+			)
+			.await
+		}
+		LiveResource::Secret(_) => {
+			return Err("the value of a secret cannot be read back".to_string());
+		}
+		LiveResource::ManagedUrl(previous) => {
+			create_managed_url(
+				workspace_id,
+				token,
+				CreateManagedURLRequest {
+					sub_domain: previous.data.sub_domain.clone(),
+					domain_id: previous.data.domain_id,
+					path: previous.data.path.clone(),
+					url_type: previous.data.url_type.clone(),
+				},
+			)
+			.await
+		}
+		LiveResource::StaticSite(previous) => {
+			create_static_site(
+				workspace_id,
+				token,
+				CreateStaticSiteRequest {
+					name: previous.data.name.clone(),
+					message: "Recreated after a failed IaaC apply".to_string(),
+					file: None,
+					static_site_details: StaticSiteDetails {},
+				},
+			)
+			.await
+		}
+	};
+
+	result.map_err(|err| err.body.message)
+}
+
+/// Delete a resource of a workspace
+async fn delete_resource(
+	workspace_id: Uuid,
+	resource_type: IaacResourceType,
+	id: Uuid,
+	token: &BearerToken,
+) -> Result<(), ApiErrorResponse> {
+	match resource_type {
+		IaacResourceType::Deployment => delete_deployment(workspace_id, id, token).await,
+		IaacResourceType::Database => delete_database(workspace_id, id, token).await,
+		IaacResourceType::Domain => delete_domain(workspace_id, id, token).await,
+		IaacResourceType::DockerRepository => delete_repository(workspace_id, id, token).await,
+		IaacResourceType::Secret => delete_secret(workspace_id, id, token).await,
+		IaacResourceType::ManagedUrl => delete_managed_url(workspace_id, id, token).await,
+		IaacResourceType::StaticSite => delete_static_site(workspace_id, id, token).await,
+	}
+}
+
+/// Build the request that sets every field of a deployment back to how it was
+fn deployment_restore_request(previous: &GetDeploymentInfoResponse) -> UpdateDeploymentRequest {
+	let deployment = &previous.deployment.data;
+	let details = &previous.running_details;
+
+	UpdateDeploymentRequest {
+		name: None,
+		runner: Some(deployment.runner),
+		machine_type: Some(deployment.machine_type),
+		deploy_on_push: Some(details.deploy_on_push),
+		min_horizontal_scale: Some(details.min_horizontal_scale),
+		max_horizontal_scale: Some(details.max_horizontal_scale),
+		ports: Some(details.ports.clone()),
+		environment_variables: Some(details.environment_variables.clone()),
+		startup_probe: details.startup_probe.clone(),
+		liveness_probe: details.liveness_probe.clone(),
+		config_mounts: Some(details.config_mounts.clone()),
+		volumes: Some(details.volumes.clone()),
+	}
+}
+
+/// Create a deployment in a workspace, returning its ID
+async fn create_deployment(
+	workspace_id: Uuid,
+	token: &BearerToken,
+	request: CreateDeploymentRequest,
+) -> Result<Uuid, ApiErrorResponse> {
+	make_request(
+		ApiRequest::<CreateDeploymentRequest>::builder()
+			.path(CreateDeploymentPath { workspace_id })
+			.headers(CreateDeploymentRequestHeaders {
+				authorization: token.clone(),
+				user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+			})
+			.query(())
+			.body(request)
+			.build(),
+	)
+	.await
+	.map(|response| response.body.id.id)
+}
+
+/// Update a deployment of a workspace
+async fn update_deployment(
+	workspace_id: Uuid,
+	deployment_id: Uuid,
+	token: &BearerToken,
+	request: UpdateDeploymentRequest,
+) -> Result<(), ApiErrorResponse> {
+	make_request(
+		ApiRequest::<UpdateDeploymentRequest>::builder()
+			.path(UpdateDeploymentPath {
+				workspace_id,
+				deployment_id,
+			})
+			.headers(UpdateDeploymentRequestHeaders {
+				authorization: token.clone(),
+				user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+			})
+			.query(())
+			.body(request)
+			.build(),
+	)
+	.await
+	.map(|_| ())
+}
+
+/// Delete a deployment of a workspace
+async fn delete_deployment(
+	workspace_id: Uuid,
+	deployment_id: Uuid,
+	token: &BearerToken,
+) -> Result<(), ApiErrorResponse> {
+	make_request(
+		ApiRequest::<DeleteDeploymentRequest>::builder()
+			.path(DeleteDeploymentPath {
+				workspace_id,
+				deployment_id,
+			})
+			.headers(DeleteDeploymentRequestHeaders {
+				authorization: token.clone(),
+				user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+			})
+			.query(())
+			.body(DeleteDeploymentRequest)
+			.build(),
+	)
+	.await
+	.map(|_| ())
+}
+
+/// Get a database of a workspace
+async fn get_database(
+	workspace_id: Uuid,
+	database_id: Uuid,
+	token: &BearerToken,
+) -> Result<WithId<Database>, ApiErrorResponse> {
+	make_request(
+		ApiRequest::<GetDatabaseRequest>::builder()
+			.path(GetDatabasePath {
+				workspace_id,
+				database_id,
+			})
+			.headers(GetDatabaseRequestHeaders {
+				authorization: token.clone(),
+				user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+			})
+			.query(())
+			.body(GetDatabaseRequest)
+			.build(),
+	)
+	.await
+	.map(|response| response.body.database)
+}
+
+/// Create a database in a workspace, returning its ID
+async fn create_database(
+	workspace_id: Uuid,
+	token: &BearerToken,
+	request: CreateDatabaseRequest,
+) -> Result<Uuid, ApiErrorResponse> {
+	make_request(
+		ApiRequest::<CreateDatabaseRequest>::builder()
+			.path(CreateDatabasePath { workspace_id })
+			.headers(CreateDatabaseRequestHeaders {
+				authorization: token.clone(),
+				user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+			})
+			.query(())
+			.body(request)
+			.build(),
+	)
+	.await
+	.map(|response| response.body.id.id)
+}
+
+/// Delete a database of a workspace
+async fn delete_database(
+	workspace_id: Uuid,
+	database_id: Uuid,
+	token: &BearerToken,
+) -> Result<(), ApiErrorResponse> {
+	make_request(
+		ApiRequest::<DeleteDatabaseRequest>::builder()
+			.path(DeleteDatabasePath {
+				workspace_id,
+				database_id,
+			})
+			.headers(DeleteDatabaseRequestHeaders {
+				authorization: token.clone(),
+				user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+			})
+			.query(())
+			.body(DeleteDatabaseRequest)
+			.build(),
+	)
+	.await
+	.map(|_| ())
+}
+
+/// Add a domain to a workspace, returning its ID
+async fn add_domain(
+	workspace_id: Uuid,
+	token: &BearerToken,
+	request: AddDomainToWorkspaceRequest,
+) -> Result<Uuid, ApiErrorResponse> {
+	make_request(
+		ApiRequest::<AddDomainToWorkspaceRequest>::builder()
+			.path(AddDomainToWorkspacePath { workspace_id })
+			.headers(AddDomainToWorkspaceRequestHeaders {
+				authorization: token.clone(),
+			})
+			.query(())
+			.body(request)
+			.build(),
+	)
+	.await
+	.map(|response| response.body.id.id)
+}
+
+/// Delete a domain of a workspace
+async fn delete_domain(
+	workspace_id: Uuid,
+	domain_id: Uuid,
+	token: &BearerToken,
+) -> Result<(), ApiErrorResponse> {
+	make_request(
+		ApiRequest::<DeleteDomainInWorkspaceRequest>::builder()
+			.path(DeleteDomainInWorkspacePath {
+				workspace_id,
+				domain_id,
+			})
+			.headers(DeleteDomainInWorkspaceRequestHeaders {
+				authorization: token.clone(),
+				user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+			})
+			.query(())
+			.body(DeleteDomainInWorkspaceRequest)
+			.build(),
+	)
+	.await
+	.map(|_| ())
+}
+
+/// Create a repository in the container registry of a workspace, returning its
+/// ID
+async fn create_repository(
+	workspace_id: Uuid,
+	token: &BearerToken,
+	request: CreateContainerRepositoryRequest,
+) -> Result<Uuid, ApiErrorResponse> {
+	make_request(
+		ApiRequest::<CreateContainerRepositoryRequest>::builder()
+			.path(CreateContainerRepositoryPath { workspace_id })
+			.headers(CreateContainerRepositoryRequestHeaders {
+				authorization: token.clone(),
+				user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+			})
+			.query(())
+			.body(request)
+			.build(),
+	)
+	.await
+	.map(|response| response.body.id.id)
+}
+
+/// Delete a repository of the container registry of a workspace
+async fn delete_repository(
+	workspace_id: Uuid,
+	repository_id: Uuid,
+	token: &BearerToken,
+) -> Result<(), ApiErrorResponse> {
+	make_request(
+		ApiRequest::<DeleteContainerRepositoryRequest>::builder()
+			.path(DeleteContainerRepositoryPath {
+				workspace_id,
+				repository_id,
+			})
+			.headers(DeleteContainerRepositoryRequestHeaders {
+				authorization: token.clone(),
+				user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+			})
+			.query(())
+			.body(DeleteContainerRepositoryRequest)
+			.build(),
+	)
+	.await
+	.map(|_| ())
+}
+
+/// Create a secret in a workspace, returning its ID
+async fn create_secret(
+	workspace_id: Uuid,
+	token: &BearerToken,
+	request: CreateSecretRequest,
+) -> Result<Uuid, ApiErrorResponse> {
+	make_request(
+		ApiRequest::<CreateSecretRequest>::builder()
+			.path(CreateSecretPath { workspace_id })
+			.headers(CreateSecretRequestHeaders {
+				authorization: token.clone(),
+				user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+			})
+			.query(())
+			.body(request)
+			.build(),
+	)
+	.await
+	.map(|response| response.body.id.id)
+}
+
+/// Update a secret of a workspace
+async fn update_secret(
+	workspace_id: Uuid,
+	secret_id: Uuid,
+	token: &BearerToken,
+	request: UpdateSecretRequest,
+) -> Result<(), ApiErrorResponse> {
+	make_request(
+		ApiRequest::<UpdateSecretRequest>::builder()
+			.path(UpdateSecretPath {
+				workspace_id,
+				secret_id,
+			})
+			.headers(UpdateSecretRequestHeaders {
+				authorization: token.clone(),
+				user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+			})
+			.query(())
+			.body(request)
+			.build(),
+	)
+	.await
+	.map(|_| ())
+}
+
+/// Delete a secret of a workspace
+async fn delete_secret(
+	workspace_id: Uuid,
+	secret_id: Uuid,
+	token: &BearerToken,
+) -> Result<(), ApiErrorResponse> {
+	make_request(
+		ApiRequest::<DeleteSecretRequest>::builder()
+			.path(DeleteSecretPath {
+				workspace_id,
+				secret_id,
+			})
+			.headers(DeleteSecretRequestHeaders {
+				authorization: token.clone(),
+				user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+			})
+			.query(())
+			.body(DeleteSecretRequest)
+			.build(),
+	)
+	.await
+	.map(|_| ())
+}
+
+/// Create a managed URL in a workspace, returning its ID
+async fn create_managed_url(
+	workspace_id: Uuid,
+	token: &BearerToken,
+	request: CreateManagedURLRequest,
+) -> Result<Uuid, ApiErrorResponse> {
+	make_request(
+		ApiRequest::<CreateManagedURLRequest>::builder()
+			.path(CreateManagedURLPath { workspace_id })
+			.headers(CreateManagedURLRequestHeaders {
+				authorization: token.clone(),
+				user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+			})
+			.query(())
+			.body(request)
+			.build(),
+	)
+	.await
+	.map(|response| response.body.id.id)
+}
+
+/// Update a managed URL of a workspace
+async fn update_managed_url(
+	workspace_id: Uuid,
+	managed_url_id: Uuid,
+	token: &BearerToken,
+	request: UpdateManagedURLRequest,
+) -> Result<(), ApiErrorResponse> {
+	make_request(
+		ApiRequest::<UpdateManagedURLRequest>::builder()
+			.path(UpdateManagedURLPath {
+				workspace_id,
+				managed_url_id,
+			})
+			.headers(UpdateManagedURLRequestHeaders {
+				authorization: token.clone(),
+				user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+			})
+			.query(())
+			.body(request)
+			.build(),
+	)
+	.await
+	.map(|_| ())
+}
+
+/// Delete a managed URL of a workspace
+async fn delete_managed_url(
+	workspace_id: Uuid,
+	managed_url_id: Uuid,
+	token: &BearerToken,
+) -> Result<(), ApiErrorResponse> {
+	make_request(
+		ApiRequest::<DeleteManagedURLRequest>::builder()
+			.path(DeleteManagedURLPath {
+				workspace_id,
+				managed_url_id,
+			})
+			.headers(DeleteManagedURLRequestHeaders {
+				authorization: token.clone(),
+				user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+			})
+			.query(())
+			.body(DeleteManagedURLRequest)
+			.build(),
+	)
+	.await
+	.map(|_| ())
+}
+
+/// Create a static site in a workspace, returning its ID
+async fn create_static_site(
+	workspace_id: Uuid,
+	token: &BearerToken,
+	request: CreateStaticSiteRequest,
+) -> Result<Uuid, ApiErrorResponse> {
+	make_request(
+		ApiRequest::<CreateStaticSiteRequest>::builder()
+			.path(CreateStaticSitePath { workspace_id })
+			.headers(CreateStaticSiteRequestHeaders {
+				authorization: token.clone(),
+				user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+			})
+			.query(())
+			.body(request)
+			.build(),
+	)
+	.await
+	.map(|response| response.body.id.id)
+}
+
+/// Delete a static site of a workspace
+async fn delete_static_site(
+	workspace_id: Uuid,
+	static_site_id: Uuid,
+	token: &BearerToken,
+) -> Result<(), ApiErrorResponse> {
+	make_request(
+		ApiRequest::<DeleteStaticSiteRequest>::builder()
+			.path(DeleteStaticSitePath {
+				workspace_id,
+				static_site_id,
+			})
+			.headers(DeleteStaticSiteRequestHeaders {
+				authorization: token.clone(),
+				user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+			})
+			.query(())
+			.body(DeleteStaticSiteRequest)
+			.build(),
+	)
+	.await
+	.map(|_| ())
+}
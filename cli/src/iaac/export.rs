@@ -0,0 +1,508 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use either::Either;
+use models::{
+	api::workspace::{
+		deployment::*,
+		managed_url::{ManagedUrl, ManagedUrlType},
+	},
+	iaac::{
+		Dependency,
+		IaacDeployment,
+		IaacDeploymentEnvVars,
+		IaacDeploymentImage,
+		IaacDeploymentMachineType,
+		IaacDeploymentPorts,
+		IaacDockerRepository,
+		IaacDomain,
+		IaacEnvironmentVariableValue,
+		IaacManagedUrl,
+		IaacManagedUrlTarget,
+		IaacResource,
+		IaacResourceType,
+		IaacSecret,
+		MaybeExternallySourced,
+		Resource,
+	},
+	ApiErrorResponse,
+};
+
+use super::LiveState;
+use crate::prelude::*;
+
+/// Convert the live state of a workspace to the resources that declare it, by
+/// the name of the file that each list of resources is written to. Planning the
+/// exported resources against the same workspace results in no changes.
+pub fn export_resources(
+	live: &LiveState,
+) -> Result<BTreeMap<&'static str, Vec<Resource>>, ApiErrorResponse> {
+	let mut files = BTreeMap::new();
+
+	let repositories = live
+		.repositories
+		.values()
+		.map(|repository| Resource {
+			data: IaacResource::DockerRepository(IaacDockerRepository {
+				name: repository.data.name.clone(),
+			}),
+			depends_on: OneOrMore::Multiple(Vec::new()),
+		})
+		.collect::<Vec<_>>();
+	files.insert("docker_repositories.yaml", repositories);
+
+	// The values of the secrets cannot be read back, so they are read from
+	// environment variables when the secrets are created from the files
+	let secrets = live
+		.secrets
+		.values()
+		.map(|secret| Resource {
+			data: IaacResource::Secret(IaacSecret {
+				name: secret.data.name.clone(),
+				value: MaybeExternallySourced::FromEnvironment {
+					from_env: secret_env_name(&secret.data.name),
+				},
+			}),
+			depends_on: OneOrMore::Multiple(Vec::new()),
+		})
+		.collect::<Vec<_>>();
+	files.insert("secrets.yaml", secrets);
+
+	let domains = live
+		.domains
+		.values()
+		.map(|domain| Resource {
+			data: IaacResource::Domain(IaacDomain {
+				name: domain.data.domain.name.clone(),
+				nameserver_type: domain.data.nameserver_type.clone().into(),
+			}),
+			depends_on: OneOrMore::Multiple(Vec::new()),
+		})
+		.collect::<Vec<_>>();
+	files.insert("domains.yaml", domains);
+
+	let deployments = live
+		.deployments
+		.values()
+		.map(|deployment| export_deployment(deployment, live))
+		.collect::<Result<Vec<_>, _>>()?;
+	files.insert("deployments.yaml", deployments);
+
+	let mut managed_urls = live
+		.managed_urls
+		.iter()
+		.map(|managed_url| export_managed_url(&managed_url.data, live))
+		.collect::<Vec<_>>();
+	managed_urls.sort_by(|a, b| a.data.get_name().cmp(b.data.get_name()));
+	files.insert("managed_urls.yaml", managed_urls);
+
+	files.retain(|_, resources| !resources.is_empty());
+	Ok(files)
+}
+
+/// Convert a deployment of a workspace to the resource that declares it
+fn export_deployment(
+	current: &GetDeploymentInfoResponse,
+	live: &LiveState,
+) -> Result<Resource, ApiErrorResponse> {
+	let deployment = &current.deployment.data;
+	let details = &current.running_details;
+	let mut depends_on = BTreeSet::new();
+
+	let image = match &deployment.registry {
+		DeploymentRegistry::PatrRegistry {
+			registry,
+			repository_id,
+		} => IaacDeploymentImage::PatrRegistry {
+			registry: *registry,
+			repository: match name_of(live.repositories.values(), *repository_id, |repository| {
+				&repository.data.name
+			}) {
+				Some(name) => {
+					depends_on.insert((IaacResourceType::DockerRepository, name.clone()));
+					Either::Right(name.clone())
+				}
+				None => Either::Left(*repository_id),
+			},
+			tag: deployment.image_tag.clone(),
+		},
+		DeploymentRegistry::ExternalRegistry {
+			registry,
+			image_name,
+		} => IaacDeploymentImage::ExternalRegistry {
+			registry: registry.clone(),
+			repository: image_name.clone(),
+			tag: deployment.image_tag.clone(),
+		},
+	};
+
+	// Runners are referred to by name, unless another runner has the same name
+	let is_unique = |name: &String| {
+		live.runners
+			.iter()
+			.filter(|runner| &runner.data.name == name)
+			.count() ==
+			1
+	};
+	let region = match name_of(&live.runners, deployment.runner, |runner| &runner.data.name) {
+		Some(name) if is_unique(name) => name.clone(),
+		_ => deployment.runner.to_string(),
+	};
+
+	let machine_type = live
+		.machine_types
+		.iter()
+		.find(|machine_type| machine_type.id == deployment.machine_type)
+		.map(|machine_type| IaacDeploymentMachineType::from(&machine_type.data))
+		.ok_or_else(|| {
+			ApiErrorResponse::error_with_message(
+				ErrorType::InternalServerError,
+				format!(
+					"The machine type of the deployment `{}` is not available",
+					deployment.name
+				),
+			)
+		})?;
+
+	let mut environment_variables = BTreeMap::new();
+	for (key, value) in &details.environment_variables {
+		let value = match value {
+			EnvironmentVariableValue::String(value) => {
+				IaacEnvironmentVariableValue::Value(MaybeExternallySourced::Value(value.clone()))
+			}
+			EnvironmentVariableValue::Secret { from_secret } => {
+				IaacEnvironmentVariableValue::Secret {
+					from_secret: match name_of(live.secrets.values(), *from_secret, |secret| {
+						&secret.data.name
+					}) {
+						Some(name) => {
+							depends_on.insert((IaacResourceType::Secret, name.clone()));
+							Either::Right(name.clone())
+						}
+						None => Either::Left(*from_secret),
+					},
+				}
+			}
+		};
+		environment_variables.insert(key.clone(), value);
+	}
+
+	let config_mounts = details
+		.config_mounts
+		.iter()
+		.map(|(path, contents)| {
+			String::from_utf8(contents.to_vec())
+				.map(|contents| (path.clone(), contents))
+				.map_err(|_| {
+					ApiErrorResponse::error_with_message(
+						ErrorType::WrongParameters,
+						format!(
+							"The config mount `{}` of the deployment `{}` is not valid UTF-8, and cannot be exported",
+							path, deployment.name
+						),
+					)
+				})
+		})
+		.collect::<Result<_, _>>()?;
+
+	let scale = |scale: u16| {
+		u8::try_from(scale).map_err(|_| {
+			ApiErrorResponse::error_with_message(
+				ErrorType::WrongParameters,
+				format!(
+					"The scale of the deployment `{}` is too large to be exported",
+					deployment.name
+				),
+			)
+		})
+	};
+
+	Ok(Resource {
+		data: IaacResource::Deployment(IaacDeployment {
+			name: deployment.name.clone(),
+			image,
+			region,
+			machine_type,
+			deploy_on_push: details.deploy_on_push,
+			min_horizontal_scale: scale(details.min_horizontal_scale)?,
+			max_horizontal_scale: scale(details.max_horizontal_scale)?,
+			ports: IaacDeploymentPorts(details.ports.clone()),
+			environment_variables: IaacDeploymentEnvVars(environment_variables),
+			startup_probe: details.startup_probe.clone(),
+			liveness_probe: details.liveness_probe.clone(),
+			config_mounts,
+			volumes: (!details.volumes.is_empty()).then(|| details.volumes.clone()),
+		}),
+		depends_on: dependencies(depends_on),
+	})
+}
+
+/// Convert a managed URL of a workspace to the resource that declares it
+fn export_managed_url(managed_url: &ManagedUrl, live: &LiveState) -> Resource {
+	let mut depends_on = BTreeSet::new();
+
+	let domain_name = name_of(live.domains.values(), managed_url.domain_id, |domain| {
+		&domain.data.domain.name
+	});
+	let domain = match domain_name {
+		Some(name) => {
+			depends_on.insert((IaacResourceType::Domain, name.clone()));
+			Either::Right(name.clone())
+		}
+		None => Either::Left(managed_url.domain_id),
+	};
+
+	let target = match &managed_url.url_type {
+		ManagedUrlType::ProxyDeployment {
+			deployment_id,
+			port,
+		} => IaacManagedUrlTarget::Deployment {
+			deployment: match live
+				.deployments
+				.iter()
+				.find(|(_, deployment)| deployment.deployment.id == *deployment_id)
+			{
+				Some((name, _)) => {
+					depends_on.insert((IaacResourceType::Deployment, name.clone()));
+					Either::Right(name.clone())
+				}
+				None => Either::Left(*deployment_id),
+			},
+			port: *port,
+		},
+		url_type => url_type.clone().into(),
+	};
+
+	// Managed URLs have no name in Patr, so they are named after the URL
+	// that they handle
+	let host = match (managed_url.sub_domain.as_str(), domain_name) {
+		("@", Some(domain)) => domain.clone(),
+		(sub_domain, Some(domain)) => format!("{}.{}", sub_domain, domain),
+		(sub_domain, None) => format!("{}.{}", sub_domain, managed_url.domain_id),
+	};
+
+	Resource {
+		data: IaacResource::ManagedUrl(IaacManagedUrl {
+			name: format!("{}{}", host, managed_url.path),
+			sub_domain: managed_url.sub_domain.clone(),
+			domain,
+			path: managed_url.path.clone(),
+			target,
+		}),
+		depends_on: dependencies(depends_on),
+	}
+}
+
+/// Find the name of the resource with the given ID
+fn name_of<'a, T: 'a>(
+	resources: impl IntoIterator<Item = &'a WithId<T>>,
+	id: Uuid,
+	name: impl Fn(&'a WithId<T>) -> &'a String,
+) -> Option<&'a String> {
+	resources
+		.into_iter()
+		.find(|resource| resource.id == id)
+		.map(name)
+}
+
+/// The dependencies on resources that are declared by name
+fn dependencies(resources: BTreeSet<(IaacResourceType, String)>) -> OneOrMore<Dependency> {
+	OneOrMore::Multiple(
+		resources
+			.into_iter()
+			.map(|(resource, name)| Dependency {
+				resource,
+				identifier: Either::Right(name),
+			})
+			.collect(),
+	)
+}
+
+/// The name of the environment variable that the value of a secret is read
+/// from, such as `DATABASE_PASSWORD` for the secret `database-password`
+fn secret_env_name(name: &str) -> String {
+	name.chars()
+		.map(|c| {
+			if c.is_ascii_alphanumeric() {
+				c.to_ascii_uppercase()
+			} else {
+				'_'
+			}
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{collections::BTreeMap, path::PathBuf};
+
+	use models::{
+		api::{
+			workspace::{
+				deployment::*,
+				domain::{Domain, DomainNameserverType, WorkspaceDomain},
+				managed_url::{ManagedUrl, ManagedUrlType},
+				runner::Runner,
+				secret::Secret,
+			},
+			WithId,
+		},
+		utils::{Base64String, StringifiedU16, Uuid},
+	};
+
+	use super::export_resources;
+	use crate::iaac::{LiveState, Plan, PlannedAction, ResourceGraph, SourcedResource};
+
+	/// A workspace with a deployment that uses a secret, and two managed URLs
+	/// on a domain, one of which points to the deployment
+	fn workspace() -> LiveState {
+		let runner = WithId::new(
+			Uuid::new_v4(),
+			Runner {
+				name: "default".to_string(),
+				connected: true,
+				last_seen: None,
+			},
+		);
+		let machine_type = WithId::new(
+			Uuid::new_v4(),
+			DeploymentMachineType {
+				cpu_count: 1,
+				memory_count: 4,
+			},
+		);
+		let secret = WithId::new(
+			Uuid::new_v4(),
+			Secret {
+				name: "database-password".to_string(),
+				deployment_id: None,
+			},
+		);
+		let domain = WithId::new(
+			Uuid::new_v4(),
+			WorkspaceDomain {
+				domain: Domain {
+					name: "example.com".to_string(),
+					last_unverified: None,
+				},
+				is_verified: true,
+				nameserver_type: DomainNameserverType::External,
+			},
+		);
+
+		let deployment = GetDeploymentInfoResponse {
+			deployment: WithId::new(
+				Uuid::new_v4(),
+				Deployment {
+					name: "api".to_string(),
+					registry: DeploymentRegistry::ExternalRegistry {
+						registry: "registry.hub.docker.com".to_string(),
+						image_name: "library/nginx".to_string(),
+					},
+					image_tag: "latest".to_string(),
+					status: DeploymentStatus::Running,
+					runner: runner.id,
+					machine_type: machine_type.id,
+					current_live_digest: None,
+				},
+			),
+			running_details: DeploymentRunningDetails {
+				deploy_on_push: true,
+				min_horizontal_scale: 1,
+				max_horizontal_scale: 2,
+				ports: BTreeMap::from([(StringifiedU16::from(80), ExposedPortType::Http)]),
+				environment_variables: BTreeMap::from([
+					(
+						"DATABASE_PASSWORD".to_string(),
+						EnvironmentVariableValue::Secret {
+							from_secret: secret.id,
+						},
+					),
+					(
+						"LOG_LEVEL".to_string(),
+						EnvironmentVariableValue::String("info".to_string()),
+					),
+				]),
+				startup_probe: None,
+				liveness_probe: None,
+				config_mounts: BTreeMap::from([(
+					"/etc/app.conf".to_string(),
+					Base64String::from(b"key=value".as_slice()),
+				)]),
+				volumes: BTreeMap::new(),
+			},
+		};
+
+		let managed_urls = vec![
+			WithId::new(
+				Uuid::new_v4(),
+				ManagedUrl {
+					sub_domain: "api".to_string(),
+					domain_id: domain.id,
+					path: "/".to_string(),
+					url_type: ManagedUrlType::ProxyDeployment {
+						deployment_id: deployment.deployment.id,
+						port: 80,
+					},
+					is_configured: true,
+				},
+			),
+			WithId::new(
+				Uuid::new_v4(),
+				ManagedUrl {
+					sub_domain: "www".to_string(),
+					domain_id: domain.id,
+					path: "/".to_string(),
+					url_type: ManagedUrlType::Redirect {
+						url: "https://example.com".to_string(),
+						permanent_redirect: true,
+						http_only: false,
+					},
+					is_configured: true,
+				},
+			),
+		];
+
+		LiveState {
+			deployments: BTreeMap::from([("api".to_string(), deployment)]),
+			runners: vec![runner],
+			machine_types: vec![machine_type],
+			databases: BTreeMap::new(),
+			repositories: BTreeMap::new(),
+			domains: BTreeMap::from([("example.com".to_string(), domain)]),
+			managed_urls,
+			secrets: BTreeMap::from([("database-password".to_string(), secret)]),
+			static_sites: BTreeMap::new(),
+		}
+	}
+
+	#[test]
+	fn assert_exported_resources_match_the_workspace() {
+		let live = workspace();
+		let resources = export_resources(&live)
+			.unwrap()
+			.into_iter()
+			.flat_map(|(file, resources)| {
+				resources.into_iter().map(move |resource| SourcedResource {
+					file: PathBuf::from(file),
+					resource,
+				})
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(resources.len(), 5);
+
+		let graph = ResourceGraph::build(resources).unwrap();
+		let plan = Plan::create(Uuid::new_v4(), &graph, &live, true).unwrap();
+
+		assert_eq!(plan.changes.len(), 5);
+		for change in &plan.changes {
+			assert_eq!(
+				change.action,
+				PlannedAction::NoOp,
+				"the {} `{}` is planned to change: {:?}",
+				change.resource_type,
+				change.name,
+				change.changes
+			);
+		}
+	}
+}
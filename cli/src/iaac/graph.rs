@@ -0,0 +1,354 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use either::Either;
+use models::{
+	iaac::{
+		IaacDeploymentImage,
+		IaacEnvironmentVariableValue,
+		IaacManagedUrlTarget,
+		IaacResource,
+		IaacResourceType,
+	},
+	ApiErrorResponse,
+};
+
+use super::SourcedResource;
+use crate::prelude::*;
+
+/// The key that a resource is identified by among the declared resources,
+/// which is its type along with its name
+pub type ResourceKey = (IaacResourceType, String);
+
+/// The declared resources of a workspace, ordered such that every resource
+/// comes after all the resources it depends on
+#[derive(Debug, Clone)]
+pub struct ResourceGraph {
+	/// The declared resources, in the order they should be created in
+	pub resources: Vec<SourcedResource>,
+	/// The resources that are depended on by ID, which are expected to already
+	/// exist in the workspace
+	pub external_dependencies: BTreeSet<(IaacResourceType, Uuid)>,
+}
+
+impl ResourceGraph {
+	/// Build the dependency graph of the declared resources and sort them
+	/// topologically. Besides the explicit dependencies, a resource depends on
+	/// every declared resource that it refers to by name or whose outputs it
+	/// refers to. Fails if a resource
+	/// is declared more than once, if a dependency refers to a resource that is
+	/// not declared, or if the dependencies form a cycle.
+	pub fn build(resources: Vec<SourcedResource>) -> Result<Self, ApiErrorResponse> {
+		let mut nodes = BTreeMap::<ResourceKey, SourcedResource>::new();
+		for resource in resources {
+			let key = (
+				resource.resource.data.get_resource_type(),
+				resource.resource.data.get_name().to_string(),
+			);
+			if let Some(existing) = nodes.get(&key) {
+				return Err(ApiErrorResponse::error_with_message(
+					ErrorType::WrongParameters,
+					format!(
+						"The {} `{}` is declared in both `{}` and `{}`",
+						key.0,
+						key.1,
+						existing.file.display(),
+						resource.file.display()
+					),
+				));
+			}
+			nodes.insert(key, resource);
+		}
+
+		let mut external_dependencies = BTreeSet::new();
+		let mut dependencies = BTreeMap::<ResourceKey, BTreeSet<ResourceKey>>::new();
+		let mut dependents = BTreeMap::<ResourceKey, BTreeSet<ResourceKey>>::new();
+
+		for (key, resource) in &nodes {
+			let mut resource_dependencies = BTreeSet::new();
+			for dependency in resource.resource.depends_on.clone() {
+				match dependency.identifier {
+					Either::Left(id) => {
+						external_dependencies.insert((dependency.resource, id));
+					}
+					Either::Right(name) => {
+						let dependency_key = (dependency.resource, name);
+						if !nodes.contains_key(&dependency_key) {
+							return Err(ApiErrorResponse::error_with_message(
+								ErrorType::WrongParameters,
+								format!(
+									"The {} `{}` depends on the {} `{}`, which is not declared",
+									key.0, key.1, dependency_key.0, dependency_key.1
+								),
+							));
+						}
+						dependents
+							.entry(dependency_key.clone())
+							.or_default()
+							.insert(key.clone());
+						resource_dependencies.insert(dependency_key);
+					}
+				}
+			}
+			// Referring to a declared resource or to one of its outputs
+			// implicitly depends on it. Resources that are not declared are
+			// read from the live state of the workspace instead.
+			let implicit_dependencies = resource
+				.resource
+				.data
+				.referenced_outputs()
+				.into_iter()
+				.map(|reference| (reference.resource_type, reference.name.clone()))
+				.chain(referenced_resources(&resource.resource.data));
+			for dependency_key in implicit_dependencies {
+				if !nodes.contains_key(&dependency_key) {
+					continue;
+				}
+				dependents
+					.entry(dependency_key.clone())
+					.or_default()
+					.insert(key.clone());
+				resource_dependencies.insert(dependency_key);
+			}
+			dependencies.insert(key.clone(), resource_dependencies);
+		}
+
+		// Kahn's algorithm. The set of ready resources is ordered, so that the
+		// resulting order is the same every time for the same files.
+		let mut ready = dependencies
+			.iter()
+			.filter(|(_, dependencies)| dependencies.is_empty())
+			.map(|(key, _)| key.clone())
+			.collect::<BTreeSet<_>>();
+		let mut ordered = Vec::with_capacity(nodes.len());
+
+		while let Some(key) = ready.pop_first() {
+			dependencies.remove(&key);
+			for dependent in dependents.remove(&key).unwrap_or_default() {
+				let Some(remaining) = dependencies.get_mut(&dependent) else {
+					continue;
+				};
+				remaining.remove(&key);
+				if remaining.is_empty() {
+					ready.insert(dependent);
+				}
+			}
+			if let Some(resource) = nodes.remove(&key) {
+				ordered.push(resource);
+			}
+		}
+
+		if !dependencies.is_empty() {
+			return Err(ApiErrorResponse::error_with_message(
+				ErrorType::WrongParameters,
+				format!(
+					"The dependencies of the following resources form a cycle: {}",
+					dependencies
+						.keys()
+						.map(|(resource_type, name)| format!("{} `{}`", resource_type, name))
+						.collect::<Vec<_>>()
+						.join(", ")
+				),
+			));
+		}
+
+		Ok(Self {
+			resources: ordered,
+			external_dependencies,
+		})
+	}
+}
+
+/// Get the resources that a resource refers to by name, other than the
+/// resources whose outputs its values are sourced from
+fn referenced_resources(resource: &IaacResource) -> Vec<ResourceKey> {
+	match resource {
+		IaacResource::Deployment(deployment) => {
+			let repository = match &deployment.image {
+				IaacDeploymentImage::PatrRegistry {
+					repository: Either::Right(name),
+					..
+				} => Some((IaacResourceType::DockerRepository, name.clone())),
+				_ => None,
+			};
+			let secrets = deployment
+				.environment_variables
+				.0
+				.values()
+				.filter_map(|value| match value {
+					IaacEnvironmentVariableValue::Secret {
+						from_secret: Either::Right(name),
+					} => Some((IaacResourceType::Secret, name.clone())),
+					_ => None,
+				});
+			repository.into_iter().chain(secrets).collect()
+		}
+		IaacResource::ManagedUrl(managed_url) => {
+			let domain = match &managed_url.domain {
+				Either::Right(name) => Some((IaacResourceType::Domain, name.clone())),
+				Either::Left(_) => None,
+			};
+			let target = match &managed_url.target {
+				IaacManagedUrlTarget::Deployment {
+					deployment: Either::Right(name),
+					..
+				} => Some((IaacResourceType::Deployment, name.clone())),
+				IaacManagedUrlTarget::StaticSite {
+					static_site: Either::Right(name),
+				} => Some((IaacResourceType::StaticSite, name.clone())),
+				_ => None,
+			};
+			domain.into_iter().chain(target).collect()
+		}
+		IaacResource::Database(_) |
+		IaacResource::StaticSite(_) |
+		IaacResource::Domain(_) |
+		IaacResource::DockerRepository(_) |
+		IaacResource::Secret(_) => Vec::new(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::path::PathBuf;
+
+	use models::iaac::{IaacResourceType, Resource};
+
+	use super::{ResourceGraph, ResourceKey};
+	use crate::iaac::SourcedResource;
+
+	/// Parse a list of resources, as they would be declared in an IaaC file
+	fn resources(yaml: &str) -> Vec<SourcedResource> {
+		serde_yaml::from_str::<Vec<Resource>>(yaml)
+			.unwrap()
+			.into_iter()
+			.map(|resource| SourcedResource {
+				file: PathBuf::from("resources.yaml"),
+				resource,
+			})
+			.collect()
+	}
+
+	/// The order of the resources of a graph, by their type and name
+	fn order(graph: &ResourceGraph) -> Vec<ResourceKey> {
+		graph
+			.resources
+			.iter()
+			.map(|resource| {
+				(
+					resource.resource.data.get_resource_type(),
+					resource.resource.data.get_name().to_string(),
+				)
+			})
+			.collect()
+	}
+
+	const RESOURCES: &str = r#"
+- type: Secret
+  name: a-secret
+  value:
+    from_resource: docker_repository.y-repo.id
+- type: Secret
+  name: b-secret
+  value: hunter2
+  depends_on:
+    - resource: DockerRepository
+      identifier: z-repo
+- type: DockerRepository
+  name: z-repo
+- type: DockerRepository
+  name: y-repo
+- type: ManagedUrl
+  name: site
+  domain: example.com
+  target:
+    proxy:
+      url: https://example.org
+- type: Domain
+  name: example.com
+  nameserver_type: external
+"#;
+
+	#[test]
+	fn assert_resources_are_ordered_after_their_dependencies() {
+		let graph = ResourceGraph::build(resources(RESOURCES)).unwrap();
+		assert_eq!(
+			order(&graph),
+			vec![
+				(IaacResourceType::Domain, "example.com".to_string()),
+				(IaacResourceType::ManagedUrl, "site".to_string()),
+				(IaacResourceType::DockerRepository, "y-repo".to_string()),
+				(IaacResourceType::DockerRepository, "z-repo".to_string()),
+				(IaacResourceType::Secret, "a-secret".to_string()),
+				(IaacResourceType::Secret, "b-secret".to_string()),
+			]
+		);
+		assert!(graph.external_dependencies.is_empty());
+
+		// The order does not depend on the order the resources are declared in
+		let mut reversed = resources(RESOURCES);
+		reversed.reverse();
+		assert_eq!(order(&ResourceGraph::build(reversed).unwrap()), order(&graph));
+	}
+
+	#[test]
+	fn assert_dependency_cycles_are_rejected() {
+		let result = ResourceGraph::build(resources(
+			r#"
+- type: Secret
+  name: a-secret
+  value: hunter2
+  depends_on:
+    - resource: Secret
+      identifier: b-secret
+- type: Secret
+  name: b-secret
+  value:
+    from_resource: secret.a-secret.id
+- type: DockerRepository
+  name: c-repo
+  depends_on:
+    - resource: Secret
+      identifier: a-secret
+- type: DockerRepository
+  name: d-repo
+"#,
+		));
+
+		let message = result.unwrap_err().body.message;
+		assert_eq!(
+			message,
+			"The dependencies of the following resources form a cycle: docker_repository `c-repo`, secret `a-secret`, secret `b-secret`"
+		);
+	}
+
+	#[test]
+	fn assert_invalid_dependencies_are_rejected() {
+		let result = ResourceGraph::build(resources(
+			r#"
+- type: Secret
+  name: a-secret
+  value: hunter2
+  depends_on:
+    - resource: DockerRepository
+      identifier: missing-repo
+"#,
+		));
+		assert_eq!(
+			result.unwrap_err().body.message,
+			"The secret `a-secret` depends on the docker_repository `missing-repo`, which is not declared"
+		);
+
+		let result = ResourceGraph::build(resources(
+			r#"
+- type: DockerRepository
+  name: repo
+- type: DockerRepository
+  name: repo
+"#,
+		));
+		assert_eq!(
+			result.unwrap_err().body.message,
+			"The docker_repository `repo` is declared in both `resources.yaml` and `resources.yaml`"
+		);
+	}
+}
@@ -0,0 +1,104 @@
+use std::path::{Path, PathBuf};
+
+use models::{iaac::Resource, ApiErrorResponse};
+use serde::Deserialize;
+
+use crate::prelude::*;
+
+/// A resource declared in an IaaC file, along with the file it was declared in
+#[derive(Debug, Clone)]
+pub struct SourcedResource {
+	/// The file that the resource was declared in
+	pub file: PathBuf,
+	/// The resource itself
+	pub resource: Resource,
+}
+
+/// Load all the resources declared in the IaaC files of a directory. YAML
+/// (`.yaml` / `.yml`) and JSON (`.json`) files are read recursively, in
+/// alphabetical order of their paths. A YAML file can contain multiple
+/// documents, and every document (or JSON file) can either be a single
+/// resource or a list of resources.
+pub async fn load_resources(dir: &Path) -> Result<Vec<SourcedResource>, ApiErrorResponse> {
+	let mut files = Vec::new();
+	let mut pending = vec![dir.to_path_buf()];
+
+	while let Some(dir) = pending.pop() {
+		let mut entries = tokio::fs::read_dir(&dir).await.map_err(|err| {
+			ApiErrorResponse::error_with_message(
+				ErrorType::WrongParameters,
+				format!("Cannot read directory `{}`: {}", dir.display(), err),
+			)
+		})?;
+
+		while let Some(entry) = entries.next_entry().await? {
+			let path = entry.path();
+			if entry.file_type().await?.is_dir() {
+				pending.push(path);
+				continue;
+			}
+
+			let is_iaac_file = path
+				.extension()
+				.and_then(|extension| extension.to_str())
+				.is_some_and(|extension| matches!(extension, "yaml" | "yml" | "json"));
+			if is_iaac_file {
+				files.push(path);
+			}
+		}
+	}
+
+	files.sort();
+	trace!("Found {} IaaC files in `{}`", files.len(), dir.display());
+
+	let mut resources = Vec::new();
+	for file in files {
+		let contents = tokio::fs::read_to_string(&file).await?;
+		for resource in parse_file(&file, &contents)? {
+			resources.push(SourcedResource {
+				file: file.clone(),
+				resource,
+			});
+		}
+	}
+
+	Ok(resources)
+}
+
+/// Parse the contents of a single IaaC file into the resources declared in it
+fn parse_file(file: &Path, contents: &str) -> Result<Vec<Resource>, ApiErrorResponse> {
+	let invalid_file = |err: &dyn std::fmt::Display| {
+		ApiErrorResponse::error_with_message(
+			ErrorType::WrongParameters,
+			format!("Invalid IaaC file `{}`: {}", file.display(), err),
+		)
+	};
+
+	let documents = if file
+		.extension()
+		.is_some_and(|extension| extension == "json")
+	{
+		vec![serde_json::from_str::<serde_yaml::Value>(contents)
+			.map_err(|err| invalid_file(&err))?]
+	} else {
+		serde_yaml::Deserializer::from_str(contents)
+			.map(serde_yaml::Value::deserialize)
+			.filter(|document| !matches!(document, Ok(serde_yaml::Value::Null)))
+			.collect::<Result<Vec<_>, _>>()
+			.map_err(|err| invalid_file(&err))?
+	};
+
+	let mut resources = Vec::new();
+	for document in documents {
+		if document.is_sequence() {
+			resources.extend(
+				serde_yaml::from_value::<Vec<Resource>>(document)
+					.map_err(|err| invalid_file(&err))?,
+			);
+		} else {
+			resources.push(serde_yaml::from_value(document).map_err(|err| invalid_file(&err))?);
+		}
+	}
+
+	Ok(resources)
+}
@@ -0,0 +1,17 @@
+/// Applies a plan to a workspace, rolling back the changes that were already
+/// made if any of them fail
+mod apply;
+/// Converts the live state of a workspace to the IaaC resources that declare it
+mod export;
+/// Builds the dependency graph of the resources and orders them so that
+/// dependencies are created before the resources that depend on them
+mod graph;
+/// Loads the resources declared in a directory of IaaC files
+mod loader;
+/// Diffs the declared resources against the live state of a workspace
+mod plan;
+/// Resolves the values of the declared resources that are sourced from the
+/// environment or from the outputs of other resources
+mod resolve;
+
+pub use self::{apply::*, export::*, graph::*, loader::*, plan::*, resolve::*};
@@ -0,0 +1,1853 @@
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	fmt::{self, Display, Formatter},
+};
+
+use either::Either;
+use models::{
+	api::workspace::{
+		container_registry::{
+			ContainerRepository,
+			CreateContainerRepositoryRequest,
+			ListContainerRepositoriesPath,
+			ListContainerRepositoriesRequest,
+			ListContainerRepositoriesRequestHeaders,
+		},
+		database::{
+			CreateDatabaseRequest,
+			Database,
+			ListDatabasePath,
+			ListDatabaseRequest,
+			ListDatabaseRequestHeaders,
+		},
+		deployment::*,
+		domain::{
+			AddDomainToWorkspaceRequest,
+			GetDomainsForWorkspacePath,
+			GetDomainsForWorkspaceRequest,
+			GetDomainsForWorkspaceRequestHeaders,
+			WorkspaceDomain,
+		},
+		managed_url::{
+			ListManagedURLPath,
+			ListManagedURLQuery,
+			ListManagedURLRequest,
+			ListManagedURLRequestHeaders,
+			ManagedUrl,
+		},
+		runner::*,
+		secret::{
+			ListSecretsForWorkspacePath,
+			ListSecretsForWorkspaceQuery,
+			ListSecretsForWorkspaceRequest,
+			ListSecretsForWorkspaceRequestHeaders,
+			Secret,
+		},
+		static_site::{
+			CreateStaticSiteRequest,
+			ListStaticSitePath,
+			ListStaticSiteQuery,
+			ListStaticSiteRequest,
+			ListStaticSiteRequestHeaders,
+			StaticSite,
+		},
+	},
+	iaac::{
+		IaacConversionError,
+		IaacDatabase,
+		IaacDeployment,
+		IaacDeploymentImage,
+		IaacDockerRepository,
+		IaacDomain,
+		IaacEnvironmentVariableValue,
+		IaacManagedUrl,
+		IaacManagedUrlTarget,
+		IaacOutputReference,
+		IaacReference,
+		IaacResolver,
+		IaacResource,
+		IaacResourceType,
+		IaacSecret,
+		IaacStaticSite,
+	},
+	ApiErrorResponse,
+};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{AppliedIds, ResolvedValue, ResourceGraph, ValueResolver};
+use crate::prelude::*;
+
+/// The number of items fetched per page when listing the live resources of a
+/// workspace
+const LIST_PAGE_SIZE: usize = 100;
+
+/// What is shown in place of a value that is a secret
+const SENSITIVE_VALUE: &str = "(sensitive value)";
+
+/// What is shown in place of a value that is only known once the plan is
+/// applied
+const KNOWN_AFTER_APPLY: &str = "(known after apply)";
+
+/// The live state of a workspace, as fetched from the API
+#[derive(Debug, Clone)]
+pub struct LiveState {
+	/// The deployments of the workspace, by their name
+	pub deployments: BTreeMap<String, GetDeploymentInfoResponse>,
+	/// The runners of the workspace
+	pub runners: Vec<WithId<Runner>>,
+	/// The machine types that deployments can run on
+	pub machine_types: Vec<WithId<DeploymentMachineType>>,
+	/// The databases of the workspace, by their name
+	pub databases: BTreeMap<String, WithId<Database>>,
+	/// The repositories of the container registry of the workspace, by their
+	/// name
+	pub repositories: BTreeMap<String, WithId<ContainerRepository>>,
+	/// The domains of the workspace, by their name
+	pub domains: BTreeMap<String, WithId<WorkspaceDomain>>,
+	/// The managed URLs of the workspace
+	pub managed_urls: Vec<WithId<ManagedUrl>>,
+	/// The secrets of the workspace, by their name. The values of the secrets
+	/// cannot be read back.
+	pub secrets: BTreeMap<String, WithId<Secret>>,
+	/// The static sites of the workspace, by their name
+	pub static_sites: BTreeMap<String, WithId<StaticSite>>,
+}
+
+impl LiveState {
+	/// Fetch the live state of a workspace from the API
+	pub async fn fetch(workspace_id: Uuid, token: &BearerToken) -> Result<Self, ApiErrorResponse> {
+		let mut deployment_list = Vec::new();
+		for page in 0.. {
+			let response = make_request(
+				ApiRequest::<ListDeploymentRequest>::builder()
+					.path(ListDeploymentPath { workspace_id })
+					.headers(ListDeploymentRequestHeaders {
+						authorization: token.clone(),
+						user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+					})
+					.query(Paginated {
+						data: ListDeploymentQuery { project_id: None },
+						count: LIST_PAGE_SIZE,
+						page,
+					})
+					.body(ListDeploymentRequest)
+					.build(),
+			)
+			.await?;
+
+			let fetched = response.body.deployments.len();
+			deployment_list.extend(response.body.deployments);
+			if fetched == 0 || deployment_list.len() >= response.headers.total_count.0 {
+				break;
+			}
+		}
+
+		let mut deployments = BTreeMap::new();
+		for deployment in deployment_list {
+			let info = make_request(
+				ApiRequest::<GetDeploymentInfoRequest>::builder()
+					.path(GetDeploymentInfoPath {
+						workspace_id,
+						deployment_id: deployment.id,
+					})
+					.headers(GetDeploymentInfoRequestHeaders {
+						authorization: token.clone(),
+						user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+					})
+					.query(())
+					.body(GetDeploymentInfoRequest)
+					.build(),
+			)
+			.await?
+			.body;
+			deployments.insert(deployment.data.name, info);
+		}
+
+		let mut runners = Vec::new();
+		for page in 0.. {
+			let response = make_request(
+				ApiRequest::<ListRunnersForWorkspaceRequest>::builder()
+					.path(ListRunnersForWorkspacePath { workspace_id })
+					.headers(ListRunnersForWorkspaceRequestHeaders {
+						authorization: token.clone(),
+						user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+					})
+					.query(Paginated {
+						data: (),
+						count: LIST_PAGE_SIZE,
+						page,
+					})
+					.body(ListRunnersForWorkspaceRequest)
+					.build(),
+			)
+			.await?;
+
+			let fetched = response.body.runners.len();
+			runners.extend(response.body.runners);
+			if fetched == 0 || runners.len() >= response.headers.total_count.0 {
+				break;
+			}
+		}
+
+		let machine_types = make_request(
+			ApiRequest::<ListAllDeploymentMachineTypeRequest>::builder()
+				.path(ListAllDeploymentMachineTypePath { workspace_id })
+				.headers(ListAllDeploymentMachineTypeRequestHeaders {
+					user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+				})
+				.query(())
+				.body(ListAllDeploymentMachineTypeRequest)
+				.build(),
+		)
+		.await?
+		.body
+		.machine_types;
+
+		let mut databases = BTreeMap::new();
+		for page in 0.. {
+			let response = make_request(
+				ApiRequest::<ListDatabaseRequest>::builder()
+					.path(ListDatabasePath { workspace_id })
+					.headers(ListDatabaseRequestHeaders {
+						authorization: token.clone(),
+						user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+					})
+					.query(Paginated {
+						data: (),
+						count: LIST_PAGE_SIZE,
+						page,
+					})
+					.body(ListDatabaseRequest)
+					.build(),
+			)
+			.await?;
+
+			let fetched = response.body.database.len();
+			databases.extend(
+				response
+					.body
+					.database
+					.into_iter()
+					.map(|database| (database.data.name.clone(), database)),
+			);
+			if fetched == 0 || databases.len() >= response.headers.total_count.0 {
+				break;
+			}
+		}
+
+		let mut repositories = BTreeMap::new();
+		for page in 0.. {
+			let response = make_request(
+				ApiRequest::<ListContainerRepositoriesRequest>::builder()
+					.path(ListContainerRepositoriesPath { workspace_id })
+					.headers(ListContainerRepositoriesRequestHeaders {
+						authorization: token.clone(),
+						user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+					})
+					.query(Paginated {
+						data: (),
+						count: LIST_PAGE_SIZE,
+						page,
+					})
+					.body(ListContainerRepositoriesRequest)
+					.build(),
+			)
+			.await?;
+
+			let fetched = response.body.repositories.len();
+			repositories.extend(
+				response
+					.body
+					.repositories
+					.into_iter()
+					.map(|repository| (repository.data.name.clone(), repository)),
+			);
+			if fetched == 0 || repositories.len() >= response.headers.total_count.0 {
+				break;
+			}
+		}
+
+		let mut domains = BTreeMap::new();
+		for page in 0.. {
+			let response = make_request(
+				ApiRequest::<GetDomainsForWorkspaceRequest>::builder()
+					.path(GetDomainsForWorkspacePath { workspace_id })
+					.headers(GetDomainsForWorkspaceRequestHeaders {
+						authorization: token.clone(),
+						user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+					})
+					.query(Paginated {
+						data: (),
+						count: LIST_PAGE_SIZE,
+						page,
+					})
+					.body(GetDomainsForWorkspaceRequest)
+					.build(),
+			)
+			.await?;
+
+			let fetched = response.body.domains.len();
+			domains.extend(
+				response
+					.body
+					.domains
+					.into_iter()
+					.map(|domain| (domain.data.domain.name.clone(), domain)),
+			);
+			if fetched == 0 || domains.len() >= response.headers.total_count.0 {
+				break;
+			}
+		}
+
+		let mut managed_urls = Vec::new();
+		for page in 0.. {
+			let response = make_request(
+				ApiRequest::<ListManagedURLRequest>::builder()
+					.path(ListManagedURLPath { workspace_id })
+					.headers(ListManagedURLRequestHeaders {
+						authorization: token.clone(),
+						user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+					})
+					.query(Paginated {
+						data: ListManagedURLQuery {
+							order: None,
+							order_by: None,
+							filter: None,
+							project_id: None,
+						},
+						count: LIST_PAGE_SIZE,
+						page,
+					})
+					.body(ListManagedURLRequest)
+					.build(),
+			)
+			.await?;
+
+			let fetched = response.body.urls.len();
+			managed_urls.extend(response.body.urls);
+			if fetched == 0 || managed_urls.len() >= response.headers.total_count.0 {
+				break;
+			}
+		}
+
+		let mut secrets = BTreeMap::new();
+		for page in 0.. {
+			let response = make_request(
+				ApiRequest::<ListSecretsForWorkspaceRequest>::builder()
+					.path(ListSecretsForWorkspacePath { workspace_id })
+					.headers(ListSecretsForWorkspaceRequestHeaders {
+						authorization: token.clone(),
+						user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+					})
+					.query(Paginated {
+						data: ListSecretsForWorkspaceQuery { project_id: None },
+						count: LIST_PAGE_SIZE,
+						page,
+					})
+					.body(ListSecretsForWorkspaceRequest)
+					.build(),
+			)
+			.await?;
+
+			let fetched = response.body.secrets.len();
+			secrets.extend(
+				response
+					.body
+					.secrets
+					.into_iter()
+					.map(|secret| (secret.data.name.clone(), secret)),
+			);
+			if fetched == 0 || secrets.len() >= response.headers.total_count.0 {
+				break;
+			}
+		}
+
+		let mut static_sites = BTreeMap::new();
+		for page in 0.. {
+			let response = make_request(
+				ApiRequest::<ListStaticSiteRequest>::builder()
+					.path(ListStaticSitePath { workspace_id })
+					.headers(ListStaticSiteRequestHeaders {
+						authorization: token.clone(),
+						user_agent: UserAgent::from_static(constants::USER_AGENT_STRING),
+					})
+					.query(Paginated {
+						data: ListStaticSiteQuery { project_id: None },
+						count: LIST_PAGE_SIZE,
+						page,
+					})
+					.body(ListStaticSiteRequest)
+					.build(),
+			)
+			.await?;
+
+			let fetched = response.body.static_sites.len();
+			static_sites.extend(
+				response
+					.body
+					.static_sites
+					.into_iter()
+					.map(|static_site| (static_site.data.name.clone(), static_site)),
+			);
+			if fetched == 0 || static_sites.len() >= response.headers.total_count.0 {
+				break;
+			}
+		}
+
+		Ok(Self {
+			deployments,
+			runners,
+			machine_types,
+			databases,
+			repositories,
+			domains,
+			managed_urls,
+			secrets,
+			static_sites,
+		})
+	}
+}
+
+impl IaacResolver for LiveState {
+	fn resource_id(&self, resource_type: IaacResourceType, name: &str) -> Option<Uuid> {
+		match resource_type {
+			IaacResourceType::Deployment => self
+				.deployments
+				.get(name)
+				.map(|deployment| deployment.deployment.id),
+			IaacResourceType::Database => self.databases.get(name).map(|database| database.id),
+			IaacResourceType::Domain => self.domains.get(name).map(|domain| domain.id),
+			IaacResourceType::DockerRepository => {
+				self.repositories.get(name).map(|repository| repository.id)
+			}
+			IaacResourceType::Secret => self.secrets.get(name).map(|secret| secret.id),
+			IaacResourceType::StaticSite => self
+				.static_sites
+				.get(name)
+				.map(|static_site| static_site.id),
+			// Managed URLs have no name in Patr
+			IaacResourceType::ManagedUrl => None,
+		}
+	}
+
+	fn runner_id(&self, name: &str) -> Option<Uuid> {
+		self.runners
+			.iter()
+			.find(|runner| runner.data.name == name)
+			.map(|runner| runner.id)
+	}
+}
+
+/// The changes that need to be made to a workspace for it to match the
+/// declared resources, in the order they need to be made in
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Plan {
+	/// The workspace that the plan is for
+	pub workspace_id: Uuid,
+	/// The changes to be made, in order
+	pub changes: Vec<PlannedChange>,
+}
+
+/// A change to be made to a single resource of a workspace
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedChange {
+	/// The type of the resource
+	pub resource_type: IaacResourceType,
+	/// The name of the resource
+	pub name: String,
+	/// What is going to be done to the resource
+	pub action: PlannedAction,
+	/// The resource as it currently exists in the workspace, if it exists.
+	/// This is never serialized, since it can contain secrets.
+	#[serde(skip)]
+	pub current: Option<LiveResource>,
+	/// The resource as it is declared, if it is declared. This is never
+	/// serialized, since it can contain secrets.
+	#[serde(skip)]
+	pub desired: Option<DesiredResource>,
+	/// The fields of the resource that are changed
+	pub changes: Vec<FieldChange>,
+}
+
+/// What is going to be done to a resource
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PlannedAction {
+	/// The resource does not exist and will be created
+	Create,
+	/// The resource exists and will be updated in-place
+	Update,
+	/// The resource exists, but cannot be updated in-place. It will be
+	/// deleted and created again.
+	Replace,
+	/// The resource exists but is not declared, and will be deleted
+	Delete,
+	/// The resource already matches the declaration
+	NoOp,
+}
+
+/// A resource as it currently exists in a workspace
+#[derive(Debug, Clone)]
+pub enum LiveResource {
+	/// A deployment
+	Deployment(GetDeploymentInfoResponse),
+	/// A database
+	Database(WithId<Database>),
+	/// A domain
+	Domain(WithId<WorkspaceDomain>),
+	/// A repository in the container registry
+	DockerRepository(WithId<ContainerRepository>),
+	/// A secret
+	Secret(WithId<Secret>),
+	/// A managed URL
+	ManagedUrl(WithId<ManagedUrl>),
+	/// A static site
+	StaticSite(WithId<StaticSite>),
+}
+
+impl LiveResource {
+	/// Get the ID of the resource
+	pub fn id(&self) -> Uuid {
+		match self {
+			Self::Deployment(deployment) => deployment.deployment.id,
+			Self::Database(database) => database.id,
+			Self::Domain(domain) => domain.id,
+			Self::DockerRepository(repository) => repository.id,
+			Self::Secret(secret) => secret.id,
+			Self::ManagedUrl(managed_url) => managed_url.id,
+			Self::StaticSite(static_site) => static_site.id,
+		}
+	}
+	/// Get the type of the resource
+	pub fn resource_type(&self) -> IaacResourceType {
+		match self {
+			Self::Deployment(_) => IaacResourceType::Deployment,
+			Self::Database(_) => IaacResourceType::Database,
+			Self::Domain(_) => IaacResourceType::Domain,
+			Self::DockerRepository(_) => IaacResourceType::DockerRepository,
+			Self::Secret(_) => IaacResourceType::Secret,
+			Self::ManagedUrl(_) => IaacResourceType::ManagedUrl,
+			Self::StaticSite(_) => IaacResourceType::StaticSite,
+		}
+	}
+}
+
+/// A resource as it is declared, resolved to the format of the API
+#[derive(Debug, Clone)]
+pub enum DesiredResource {
+	/// A deployment
+	Deployment(DesiredDeployment),
+	/// A database
+	Database(DesiredDatabase),
+	/// A domain
+	Domain(AddDomainToWorkspaceRequest),
+	/// A repository in the container registry
+	DockerRepository(CreateContainerRepositoryRequest),
+	/// A secret
+	Secret(DesiredSecret),
+	/// A managed URL. The resources that it refers to by name are only looked
+	/// up when applying, since they may be created by the plan. The references
+	/// to the resources that already exist are replaced with their IDs.
+	ManagedUrl(IaacManagedUrl),
+	/// A static site
+	StaticSite(CreateStaticSiteRequest),
+}
+
+/// A declared deployment, resolved to the format of the API
+#[derive(Debug, Clone)]
+pub struct DesiredDeployment {
+	/// The request that creates the deployment. The environment variables
+	/// that are only known after applying are not part of it yet.
+	pub request: CreateDeploymentRequest,
+	/// The environment variables whose values are outputs of resources that
+	/// are created or replaced by the plan, by the name of the variable
+	pub pending_outputs: BTreeMap<String, IaacOutputReference>,
+	/// The environment variables whose values are secrets that are created by
+	/// the plan, along with the name of the secret, by the name of the variable
+	pub pending_secrets: BTreeMap<String, String>,
+	/// The environment variables whose values are secrets
+	pub sensitive: BTreeSet<String>,
+}
+
+/// A declared database, resolved to the format of the API
+#[derive(Debug, Clone)]
+pub struct DesiredDatabase {
+	/// The request that creates the database
+	pub request: CreateDatabaseRequest,
+	/// The name of the secret that the password of the database is saved to
+	/// once it is created, along with the ID of the secret if it already exists
+	pub password_secret: Option<(String, Option<Uuid>)>,
+}
+
+/// A declared secret, with its value resolved
+#[derive(Debug, Clone)]
+pub struct DesiredSecret {
+	/// The name of the secret
+	pub name: String,
+	/// The value of the secret
+	pub value: ResolvedValue,
+}
+
+/// A field of a resource that is changed
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+	/// The name of the field
+	pub field: String,
+	/// The current value of the field, or `null` if the resource is created
+	pub from: Value,
+	/// The new value of the field, or `null` if the resource is deleted
+	pub to: Value,
+}
+
+impl Plan {
+	/// Create a plan for the declared resources of a workspace against its
+	/// live state. If `prune` is set, the resources of the workspace that are
+	/// not declared are deleted.
+	pub fn create(
+		workspace_id: Uuid,
+		graph: &ResourceGraph,
+		live: &LiveState,
+		prune: bool,
+	) -> Result<Self, ApiErrorResponse> {
+		for (resource_type, id) in &graph.external_dependencies {
+			let exists = match resource_type {
+				IaacResourceType::Deployment => live
+					.deployments
+					.values()
+					.any(|deployment| deployment.deployment.id == *id),
+				IaacResourceType::Database => {
+					live.databases.values().any(|database| database.id == *id)
+				}
+				IaacResourceType::DockerRepository => live
+					.repositories
+					.values()
+					.any(|repository| repository.id == *id),
+				IaacResourceType::Domain => live.domains.values().any(|domain| domain.id == *id),
+				IaacResourceType::Secret => live.secrets.values().any(|secret| secret.id == *id),
+				IaacResourceType::ManagedUrl => live
+					.managed_urls
+					.iter()
+					.any(|managed_url| managed_url.id == *id),
+				IaacResourceType::StaticSite => live
+					.static_sites
+					.values()
+					.any(|static_site| static_site.id == *id),
+			};
+			if !exists {
+				return Err(ApiErrorResponse::error_with_message(
+					ErrorType::WrongParameters,
+					format!(
+						"The {} `{}` does not exist in the workspace",
+						resource_type, id
+					),
+				));
+			}
+		}
+
+		let mut changes = Vec::with_capacity(graph.resources.len());
+		// The IDs of the resources of the workspace that are declared, which
+		// are left out when pruning
+		let mut declared = BTreeSet::new();
+		let mut resolver = ValueResolver::new(live);
+
+		// The resources are planned in the order of the graph, so every
+		// resource that is referred to is planned before the resources that
+		// refer to it
+		for resource in &graph.resources {
+			let change = match &resource.resource.data {
+				IaacResource::Deployment(deployment) => {
+					let current = live.deployments.get(&deployment.name);
+					let desired = resolve_deployment(deployment, current, live, &resolver)?;
+					plan_deployment(current, desired)
+				}
+				IaacResource::Database(database) => plan_database(database, live)?,
+				IaacResource::Domain(domain) => plan_domain(domain, live),
+				IaacResource::DockerRepository(repository) => plan_repository(repository, live),
+				IaacResource::Secret(secret) => plan_secret(secret, live, &resolver)?,
+				IaacResource::ManagedUrl(managed_url) => {
+					plan_managed_url(managed_url, live, &resolver)?
+				}
+				IaacResource::StaticSite(static_site) => plan_static_site(static_site, live)?,
+			};
+			if matches!(
+				change.action,
+				PlannedAction::Create | PlannedAction::Replace
+			) {
+				resolver.mark_pending(change.resource_type, &change.name);
+			}
+			if let Some(current) = &change.current {
+				declared.insert(current.id());
+			}
+			changes.push(change);
+		}
+
+		if prune {
+			// The resources are deleted before the resources they may depend
+			// on, so managed URLs go first and domains go last
+			let undeclared = live
+				.managed_urls
+				.iter()
+				.map(|managed_url| {
+					(
+						IaacResourceType::ManagedUrl,
+						managed_url_name(&managed_url.data, live),
+						LiveResource::ManagedUrl(managed_url.clone()),
+					)
+				})
+				.chain(live.deployments.iter().rev().map(|(name, deployment)| {
+					(
+						IaacResourceType::Deployment,
+						name.clone(),
+						LiveResource::Deployment(deployment.clone()),
+					)
+				}))
+				.chain(live.static_sites.iter().map(|(name, static_site)| {
+					(
+						IaacResourceType::StaticSite,
+						name.clone(),
+						LiveResource::StaticSite(static_site.clone()),
+					)
+				}))
+				.chain(live.databases.iter().map(|(name, database)| {
+					(
+						IaacResourceType::Database,
+						name.clone(),
+						LiveResource::Database(database.clone()),
+					)
+				}))
+				.chain(live.secrets.iter().map(|(name, secret)| {
+					(
+						IaacResourceType::Secret,
+						name.clone(),
+						LiveResource::Secret(secret.clone()),
+					)
+				}))
+				.chain(live.repositories.iter().map(|(name, repository)| {
+					(
+						IaacResourceType::DockerRepository,
+						name.clone(),
+						LiveResource::DockerRepository(repository.clone()),
+					)
+				}))
+				.chain(live.domains.iter().map(|(name, domain)| {
+					(
+						IaacResourceType::Domain,
+						name.clone(),
+						LiveResource::Domain(domain.clone()),
+					)
+				}))
+				.filter(|(_, _, current)| !declared.contains(&current.id()))
+				.map(|(resource_type, name, current)| PlannedChange {
+					resource_type,
+					name,
+					action: PlannedAction::Delete,
+					current: Some(current),
+					desired: None,
+					changes: Vec::new(),
+				});
+			changes.extend(undeclared);
+		}
+
+		Ok(Self {
+			workspace_id,
+			changes,
+		})
+	}
+
+	/// Check if applying the plan would not change anything
+	pub fn is_empty(&self) -> bool {
+		self.changes
+			.iter()
+			.all(|change| change.action == PlannedAction::NoOp)
+	}
+
+	/// Count the number of changes with the given action
+	fn count(&self, action: PlannedAction) -> usize {
+		self.changes
+			.iter()
+			.filter(|change| change.action == action)
+			.count()
+	}
+}
+
+impl Display for Plan {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		if self.is_empty() {
+			return write!(
+				f,
+				"No changes. The workspace `{}` matches the configuration.",
+				self.workspace_id
+			);
+		}
+
+		writeln!(
+			f,
+			"The following actions will be performed on the workspace `{}`:",
+			self.workspace_id
+		)?;
+
+		for change in &self.changes {
+			let (marker, description) = match change.action {
+				PlannedAction::Create => ("+", "will be created"),
+				PlannedAction::Update => ("~", "will be updated in-place"),
+				PlannedAction::Replace => ("-/+", "must be replaced"),
+				PlannedAction::Delete => ("-", "will be deleted"),
+				PlannedAction::NoOp => continue,
+			};
+
+			writeln!(f)?;
+			writeln!(
+				f,
+				"  {} {} `{}` {}",
+				marker, change.resource_type, change.name, description
+			)?;
+			for field_change in &change.changes {
+				match change.action {
+					PlannedAction::Create => {
+						writeln!(f, "      + {} = {}", field_change.field, field_change.to)?
+					}
+					_ => writeln!(
+						f,
+						"      ~ {}: {} -> {}",
+						field_change.field, field_change.from, field_change.to
+					)?,
+				}
+			}
+		}
+
+		writeln!(f)?;
+		write!(
+			f,
+			"Plan: {} to create, {} to update, {} to replace, {} to delete, {} unchanged.",
+			self.count(PlannedAction::Create),
+			self.count(PlannedAction::Update),
+			self.count(PlannedAction::Replace),
+			self.count(PlannedAction::Delete),
+			self.count(PlannedAction::NoOp),
+		)
+	}
+}
+
+/// Plan the change to a database, by comparing the database that currently
+/// exists with the same name (if any) against the declared one. Databases
+/// cannot be changed in-place, so a database that differs from its declaration
+/// is replaced, which loses the data in it.
+fn plan_database(
+	database: &IaacDatabase,
+	live: &LiveState,
+) -> Result<PlannedChange, ApiErrorResponse> {
+	let request = database
+		.to_create_request(live)
+		.map_err(|err| conversion_error(IaacResourceType::Database, &database.name, err))?;
+	let current = live.databases.get(&database.name);
+	let data = current.map(|current| &current.data);
+
+	let mut changes = Vec::new();
+	compare(
+		&mut changes,
+		"engine",
+		data.map(|data| &data.engine),
+		&request.engine,
+	);
+	compare(
+		&mut changes,
+		"version",
+		data.map(|data| &data.version),
+		&request.version,
+	);
+	compare(
+		&mut changes,
+		"database_plan_id",
+		data.map(|data| &data.database_plan_id),
+		&request.database_plan_id,
+	);
+	compare(
+		&mut changes,
+		"region",
+		data.map(|data| &data.region),
+		&request.region,
+	);
+	compare(
+		&mut changes,
+		"num_nodes",
+		data.map(|data| &data.num_nodes),
+		&request.num_node,
+	);
+
+	let action = match current {
+		None => PlannedAction::Create,
+		Some(_) if changes.is_empty() => PlannedAction::NoOp,
+		Some(_) => PlannedAction::Replace,
+	};
+
+	Ok(PlannedChange {
+		resource_type: IaacResourceType::Database,
+		name: database.name.clone(),
+		action,
+		current: current.cloned().map(LiveResource::Database),
+		desired: Some(DesiredResource::Database(DesiredDatabase {
+			request,
+			password_secret: database.save_password_to.clone().map(|name| {
+				let id = live.secrets.get(&name).map(|secret| secret.id);
+				(name, id)
+			}),
+		})),
+		changes,
+	})
+}
+
+/// Plan the change to a domain. The nameservers of a domain cannot be changed
+/// in-place, so a domain whose nameservers differ from its declaration is
+/// replaced.
+fn plan_domain(domain: &IaacDomain, live: &LiveState) -> PlannedChange {
+	let request = AddDomainToWorkspaceRequest::from(domain.clone());
+	let current = live.domains.get(&domain.name);
+
+	let mut changes = Vec::new();
+	compare(
+		&mut changes,
+		"nameserver_type",
+		current.map(|current| &current.data.nameserver_type),
+		&request.nameserver_type,
+	);
+
+	let action = match current {
+		None => PlannedAction::Create,
+		Some(_) if changes.is_empty() => PlannedAction::NoOp,
+		Some(_) => PlannedAction::Replace,
+	};
+
+	PlannedChange {
+		resource_type: IaacResourceType::Domain,
+		name: domain.name.clone(),
+		action,
+		current: current.cloned().map(LiveResource::Domain),
+		desired: Some(DesiredResource::Domain(request)),
+		changes,
+	}
+}
+
+/// Plan the change to a repository of the container registry. A repository has
+/// nothing but its name, so it is either created or left as it is.
+fn plan_repository(repository: &IaacDockerRepository, live: &LiveState) -> PlannedChange {
+	let current = live.repositories.get(&repository.name);
+
+	PlannedChange {
+		resource_type: IaacResourceType::DockerRepository,
+		name: repository.name.clone(),
+		action: if current.is_some() {
+			PlannedAction::NoOp
+		} else {
+			PlannedAction::Create
+		},
+		current: current.cloned().map(LiveResource::DockerRepository),
+		desired: Some(DesiredResource::DockerRepository(
+			CreateContainerRepositoryRequest::from(repository.clone()),
+		)),
+		changes: Vec::new(),
+	}
+}
+
+/// Plan the change to a secret. The value of a secret cannot be read back, so
+/// there is no way to tell if an existing secret differs from its declaration.
+/// An existing secret is always left as it is, and its value is not resolved
+/// at all.
+fn plan_secret(
+	secret: &IaacSecret,
+	live: &LiveState,
+	resolver: &ValueResolver<'_>,
+) -> Result<PlannedChange, ApiErrorResponse> {
+	let (action, current, value) = match live.secrets.get(&secret.name) {
+		Some(current) => (PlannedAction::NoOp, Some(current), None),
+		None => (
+			PlannedAction::Create,
+			None,
+			Some(resolver.resolve(
+				IaacResourceType::Secret,
+				&secret.name,
+				"value",
+				&secret.value,
+			)?),
+		),
+	};
+
+	let changes = value
+		.iter()
+		.map(|value| FieldChange {
+			field: "value".to_string(),
+			from: Value::Null,
+			to: Value::String(
+				match value {
+					ResolvedValue::Known { .. } => SENSITIVE_VALUE,
+					ResolvedValue::KnownAfterApply(_) => KNOWN_AFTER_APPLY,
+				}
+				.to_string(),
+			),
+		})
+		.collect();
+
+	Ok(PlannedChange {
+		resource_type: IaacResourceType::Secret,
+		name: secret.name.clone(),
+		action,
+		current: current.cloned().map(LiveResource::Secret),
+		desired: value.map(|value| {
+			DesiredResource::Secret(DesiredSecret {
+				name: secret.name.clone(),
+				value,
+			})
+		}),
+		changes,
+	})
+}
+
+/// Plan the change to a managed URL. Managed URLs have no name in Patr, so the
+/// managed URL of the workspace with the same sub-domain, domain and path as
+/// the declared one is the one that is changed. Only the target of a managed
+/// URL can be changed in-place.
+fn plan_managed_url(
+	managed_url: &IaacManagedUrl,
+	live: &LiveState,
+	resolver: &ValueResolver<'_>,
+) -> Result<PlannedChange, ApiErrorResponse> {
+	let pin = |resource_type: IaacResourceType, reference: &IaacReference| match reference {
+		Either::Right(name) if !resolver.is_pending(resource_type, name) => live
+			.resource_id(resource_type, name)
+			.map(Either::Left)
+			.ok_or_else(|| {
+				ApiErrorResponse::error_with_message(
+					ErrorType::WrongParameters,
+					format!(
+						"The managed URL `{}` refers to the {} `{}`, which does not exist in the workspace",
+						managed_url.name, resource_type, name
+					),
+				)
+			}),
+		reference => Ok(reference.clone()),
+	};
+
+	// The references to the resources that already exist are resolved now, so
+	// that only the ones to resources created by the plan are left for later
+	let mut desired = managed_url.clone();
+	desired.domain = pin(IaacResourceType::Domain, &managed_url.domain)?;
+	match &mut desired.target {
+		IaacManagedUrlTarget::Deployment { deployment, .. } => {
+			*deployment = pin(IaacResourceType::Deployment, deployment)?;
+		}
+		IaacManagedUrlTarget::StaticSite { static_site } => {
+			*static_site = pin(IaacResourceType::StaticSite, static_site)?;
+		}
+		IaacManagedUrlTarget::Proxy { .. } | IaacManagedUrlTarget::Redirect { .. } => (),
+	}
+
+	// A managed URL on a domain that is created by the plan cannot exist yet
+	let current = match &desired.domain {
+		Either::Left(domain_id) => live.managed_urls.iter().find(|current| {
+			current.data.sub_domain == desired.sub_domain &&
+				current.data.domain_id == *domain_id &&
+				current.data.path == desired.path
+		}),
+		Either::Right(_) => None,
+	};
+
+	let mut changes = Vec::new();
+	compare(
+		&mut changes,
+		"sub_domain",
+		current.map(|current| &current.data.sub_domain),
+		&desired.sub_domain,
+	);
+	compare(
+		&mut changes,
+		"path",
+		current.map(|current| &current.data.path),
+		&desired.path,
+	);
+	// A target that is created or replaced by the plan only gets its ID once
+	// the plan is applied
+	let current_url_type = current.map(|current| &current.data.url_type);
+	match desired.target.to_url_type(&AppliedIds(&BTreeMap::new())) {
+		Ok(url_type) => compare(&mut changes, "url_type", current_url_type, &url_type),
+		Err(_) => changes.push(FieldChange {
+			field: "url_type".to_string(),
+			from: current_url_type
+				.map(|url_type| url_type.to_json_value())
+				.unwrap_or(Value::Null),
+			to: Value::String(KNOWN_AFTER_APPLY.to_string()),
+		}),
+	}
+
+	let action = match current {
+		None => PlannedAction::Create,
+		Some(_) if changes.is_empty() => PlannedAction::NoOp,
+		Some(_) => PlannedAction::Update,
+	};
+
+	Ok(PlannedChange {
+		resource_type: IaacResourceType::ManagedUrl,
+		name: managed_url.name.clone(),
+		action,
+		current: current.cloned().map(LiveResource::ManagedUrl),
+		desired: Some(DesiredResource::ManagedUrl(desired)),
+		changes,
+	})
+}
+
+/// Plan the change to a static site. A static site has nothing to change but
+/// its files, which cannot be uploaded from IaaC files yet, so it is either
+/// created without any files or left as it is.
+fn plan_static_site(
+	static_site: &IaacStaticSite,
+	live: &LiveState,
+) -> Result<PlannedChange, ApiErrorResponse> {
+	if static_site.source.is_some() {
+		return Err(ApiErrorResponse::error_with_message(
+			ErrorType::WrongParameters,
+			format!(
+				"Uploading the files of the static site `{}` is not supported yet. Remove its `source` and upload the files separately.",
+				static_site.name
+			),
+		));
+	}
+
+	let current = live.static_sites.get(&static_site.name);
+
+	Ok(PlannedChange {
+		resource_type: IaacResourceType::StaticSite,
+		name: static_site.name.clone(),
+		action: if current.is_some() {
+			PlannedAction::NoOp
+		} else {
+			PlannedAction::Create
+		},
+		current: current.cloned().map(LiveResource::StaticSite),
+		desired: Some(DesiredResource::StaticSite(CreateStaticSiteRequest::from(
+			static_site.clone(),
+		))),
+		changes: Vec::new(),
+	})
+}
+
+/// The name of a managed URL of the workspace that is not declared, which is
+/// the URL that it handles
+fn managed_url_name(managed_url: &ManagedUrl, live: &LiveState) -> String {
+	let domain = live
+		.domains
+		.values()
+		.find(|domain| domain.id == managed_url.domain_id)
+		.map(|domain| domain.data.domain.name.clone())
+		.unwrap_or_else(|| managed_url.domain_id.to_string());
+	let host = match managed_url.sub_domain.as_str() {
+		"@" => domain,
+		sub_domain => format!("{}.{}", sub_domain, domain),
+	};
+	format!("{}{}", host, managed_url.path)
+}
+
+/// Convert an error converting a declared resource to a request to an error of
+/// the plan
+fn conversion_error(
+	resource_type: IaacResourceType,
+	name: &str,
+	err: IaacConversionError,
+) -> ApiErrorResponse {
+	ApiErrorResponse::error_with_message(
+		ErrorType::WrongParameters,
+		format!("The {} `{}` is invalid: {}", resource_type, name, err),
+	)
+}
+
+/// Add a field to the changes if its value differs
+fn compare<T>(changes: &mut Vec<FieldChange>, field: &str, from: Option<&T>, to: &T)
+where
+	T: Serialize + PartialEq,
+{
+	if from == Some(to) {
+		return;
+	}
+	changes.push(FieldChange {
+		field: field.to_string(),
+		from: from.map(|from| from.to_json_value()).unwrap_or(Value::Null),
+		to: to.to_json_value(),
+	});
+}
+
+/// Resolve a declared deployment to the request that would create it, looking
+/// up the runner and machine type it refers to in the live state and resolving
+/// the values of its environment variables
+fn resolve_deployment(
+	deployment: &IaacDeployment,
+	current: Option<&GetDeploymentInfoResponse>,
+	live: &LiveState,
+	resolver: &ValueResolver<'_>,
+) -> Result<DesiredDeployment, ApiErrorResponse> {
+	let (registry, image_tag) = match &deployment.image {
+		IaacDeploymentImage::PatrRegistry {
+			registry,
+			repository: Either::Left(repository_id),
+			tag,
+		} => (
+			DeploymentRegistry::PatrRegistry {
+				registry: *registry,
+				repository_id: *repository_id,
+			},
+			tag.clone(),
+		),
+		IaacDeploymentImage::PatrRegistry {
+			registry,
+			repository: Either::Right(repository),
+			tag,
+		} => (
+			DeploymentRegistry::PatrRegistry {
+				registry: *registry,
+				repository_id: live
+					.repositories
+					.get(repository)
+					.ok_or_else(|| {
+						ApiErrorResponse::error_with_message(
+							ErrorType::WrongParameters,
+							format!(
+								"The repository `{}` of the deployment `{}` does not exist in the workspace",
+								repository, deployment.name
+							),
+						)
+					})?
+					.id,
+			},
+			tag.clone(),
+		),
+		IaacDeploymentImage::ExternalRegistry {
+			registry,
+			repository,
+			tag,
+		} => (
+			DeploymentRegistry::ExternalRegistry {
+				registry: registry.clone(),
+				image_name: repository.clone(),
+			},
+			tag.clone(),
+		),
+	};
+
+	let runner = live
+		.runners
+		.iter()
+		.find(|runner| {
+			Uuid::parse_str(&deployment.region).is_ok_and(|id| id == runner.id) ||
+				runner.data.name == deployment.region
+		})
+		.ok_or_else(|| {
+			ApiErrorResponse::error_with_message(
+				ErrorType::WrongParameters,
+				format!(
+					"The region `{}` of the deployment `{}` is not a runner of the workspace",
+					deployment.region, deployment.name
+				),
+			)
+		})?
+		.id;
+
+	let cpu: String = deployment.machine_type.cpu.clone().into();
+	let ram: String = deployment.machine_type.ram.clone().into();
+	let machine_type = live
+		.machine_types
+		.iter()
+		.find(|machine_type| deployment.machine_type.matches(&machine_type.data))
+		.ok_or_else(|| {
+			ApiErrorResponse::error_with_message(
+				ErrorType::WrongParameters,
+				format!(
+					"There is no machine type with {} and {} for the deployment `{}`",
+					cpu, ram, deployment.name
+				),
+			)
+		})?
+		.id;
+
+	let mut environment_variables = BTreeMap::new();
+	let mut pending_outputs = BTreeMap::new();
+	let mut pending_secrets = BTreeMap::new();
+	let mut sensitive = BTreeSet::new();
+	for (key, value) in &deployment.environment_variables.0 {
+		let value = match value {
+			IaacEnvironmentVariableValue::Secret {
+				from_secret: Either::Right(name),
+			} if resolver.is_pending(IaacResourceType::Secret, name) => {
+				pending_secrets.insert(key.clone(), name.clone());
+				continue;
+			}
+			IaacEnvironmentVariableValue::Secret { from_secret } => {
+				let from_secret = match from_secret {
+					Either::Left(id) => *id,
+					Either::Right(name) => live
+						.secrets
+						.get(name)
+						.ok_or_else(|| {
+							ApiErrorResponse::error_with_message(
+								ErrorType::WrongParameters,
+								format!(
+									"The secret `{}` of the environment variable `{}` of the deployment `{}` does not exist in the workspace",
+									name, key, deployment.name
+								),
+							)
+						})?
+						.id,
+				};
+				environment_variables.insert(
+					key.clone(),
+					EnvironmentVariableValue::Secret { from_secret },
+				);
+				continue;
+			}
+			IaacEnvironmentVariableValue::Value(value) => value,
+		};
+		match resolver.resolve(
+			IaacResourceType::Deployment,
+			&deployment.name,
+			&format!("environment_variables.{}", key),
+			value,
+		)? {
+			ResolvedValue::Known {
+				value,
+				sensitive: is_sensitive,
+			} => {
+				if is_sensitive {
+					sensitive.insert(key.clone());
+				}
+				environment_variables.insert(key.clone(), EnvironmentVariableValue::String(value));
+			}
+			ResolvedValue::KnownAfterApply(reference) => {
+				if reference.output.is_secret() {
+					sensitive.insert(key.clone());
+				}
+				pending_outputs.insert(key.clone(), reference);
+			}
+		}
+	}
+
+	let request = CreateDeploymentRequest {
+		name: deployment.name.clone(),
+		registry,
+		image_tag,
+		runner,
+		machine_type,
+		running_details: DeploymentRunningDetails {
+			deploy_on_push: deployment.deploy_on_push,
+			min_horizontal_scale: u16::from(deployment.min_horizontal_scale),
+			max_horizontal_scale: u16::from(deployment.max_horizontal_scale),
+			ports: deployment.ports.0.clone(),
+			environment_variables,
+			startup_probe: deployment.startup_probe.clone(),
+			liveness_probe: deployment.liveness_probe.clone(),
+			config_mounts: deployment
+				.config_mounts
+				.iter()
+				.map(|(path, contents)| (path.clone(), Base64String::from(contents.as_bytes())))
+				.collect(),
+			// If the volumes are not declared, the volumes of an existing
+			// deployment are left as they are
+			volumes: match &deployment.volumes {
+				Some(volumes) => volumes.clone(),
+				None => current
+					.map(|current| current.running_details.volumes.clone())
+					.unwrap_or_default(),
+			},
+		},
+		deploy_on_create: true,
+	};
+
+	Ok(DesiredDeployment {
+		request,
+		pending_outputs,
+		pending_secrets,
+		sensitive,
+	})
+}
+
+/// Plan the change to a single deployment, by comparing the deployment that
+/// currently exists with the same name (if any) against the declared one
+fn plan_deployment(
+	current: Option<&GetDeploymentInfoResponse>,
+	desired: DesiredDeployment,
+) -> PlannedChange {
+	let changes = deployment_field_changes(current, &desired);
+
+	let action = match current {
+		None => PlannedAction::Create,
+		Some(current)
+			if current.deployment.data.registry != desired.request.registry ||
+				current.deployment.data.image_tag != desired.request.image_tag =>
+		{
+			PlannedAction::Replace
+		}
+		Some(_) if changes.is_empty() => PlannedAction::NoOp,
+		Some(_) => PlannedAction::Update,
+	};
+
+	PlannedChange {
+		resource_type: IaacResourceType::Deployment,
+		name: desired.request.name.clone(),
+		action,
+		current: current.cloned().map(LiveResource::Deployment),
+		desired: Some(DesiredResource::Deployment(desired)),
+		changes,
+	}
+}
+
+/// List the fields of a deployment that differ between its current and desired
+/// state. If the deployment does not exist yet, all the fields are listed. Each
+/// environment variable is listed as a field of its own, so that the values of
+/// the variables that are secrets can be hidden.
+fn deployment_field_changes(
+	current: Option<&GetDeploymentInfoResponse>,
+	desired_deployment: &DesiredDeployment,
+) -> Vec<FieldChange> {
+	/// Hide a value that is a secret, if there is a value at all
+	fn redact(value: Value) -> Value {
+		match value {
+			Value::Null => Value::Null,
+			_ => Value::String(SENSITIVE_VALUE.to_string()),
+		}
+	}
+
+	let mut changes = Vec::new();
+	let desired = &desired_deployment.request;
+	let deployment = current.map(|current| &current.deployment.data);
+	let details = current.map(|current| &current.running_details);
+	let desired_details = &desired.running_details;
+
+	compare(
+		&mut changes,
+		"registry",
+		deployment.map(|deployment| &deployment.registry),
+		&desired.registry,
+	);
+	compare(
+		&mut changes,
+		"image_tag",
+		deployment.map(|deployment| &deployment.image_tag),
+		&desired.image_tag,
+	);
+	compare(
+		&mut changes,
+		"runner",
+		deployment.map(|deployment| &deployment.runner),
+		&desired.runner,
+	);
+	compare(
+		&mut changes,
+		"machine_type",
+		deployment.map(|deployment| &deployment.machine_type),
+		&desired.machine_type,
+	);
+	compare(
+		&mut changes,
+		"deploy_on_push",
+		details.map(|details| &details.deploy_on_push),
+		&desired_details.deploy_on_push,
+	);
+	compare(
+		&mut changes,
+		"min_horizontal_scale",
+		details.map(|details| &details.min_horizontal_scale),
+		&desired_details.min_horizontal_scale,
+	);
+	compare(
+		&mut changes,
+		"max_horizontal_scale",
+		details.map(|details| &details.max_horizontal_scale),
+		&desired_details.max_horizontal_scale,
+	);
+	compare(
+		&mut changes,
+		"ports",
+		details.map(|details| &details.ports),
+		&desired_details.ports,
+	);
+
+	let current_environment_variables = details.map(|details| &details.environment_variables);
+	let keys = current_environment_variables
+		.into_iter()
+		.flat_map(|environment_variables| environment_variables.keys())
+		.chain(desired_details.environment_variables.keys())
+		.chain(desired_deployment.pending_outputs.keys())
+		.chain(desired_deployment.pending_secrets.keys())
+		.collect::<BTreeSet<_>>();
+	for key in keys {
+		let from = current_environment_variables
+			.and_then(|environment_variables| environment_variables.get(key));
+		let to = desired_details.environment_variables.get(key);
+		let pending = desired_deployment.pending_outputs.contains_key(key) ||
+			desired_deployment.pending_secrets.contains_key(key);
+		if !pending && from == to {
+			continue;
+		}
+
+		let from = from.map(|from| from.to_json_value()).unwrap_or(Value::Null);
+		let to = if pending {
+			Value::String(KNOWN_AFTER_APPLY.to_string())
+		} else {
+			to.map(|to| to.to_json_value()).unwrap_or(Value::Null)
+		};
+		// A variable that is a secret now may have been set to the same secret
+		// before, so neither of its values is shown
+		let (from, to) = if desired_deployment.sensitive.contains(key) {
+			(redact(from), if pending { to } else { redact(to) })
+		} else {
+			(from, to)
+		};
+
+		changes.push(FieldChange {
+			field: format!("environment_variables.{}", key),
+			from,
+			to,
+		});
+	}
+
+	compare(
+		&mut changes,
+		"startup_probe",
+		details.map(|details| &details.startup_probe),
+		&desired_details.startup_probe,
+	);
+	compare(
+		&mut changes,
+		"liveness_probe",
+		details.map(|details| &details.liveness_probe),
+		&desired_details.liveness_probe,
+	);
+	compare(
+		&mut changes,
+		"config_mounts",
+		details.map(|details| &details.config_mounts),
+		&desired_details.config_mounts,
+	);
+	compare(
+		&mut changes,
+		"volumes",
+		details.map(|details| &details.volumes),
+		&desired_details.volumes,
+	);
+
+	changes
+}
+
+/// Build the request that updates a deployment from its current state to its
+/// desired state, with only the fields that differ
+pub(super) fn deployment_update_request(
+	current: &GetDeploymentInfoResponse,
+	desired: &CreateDeploymentRequest,
+) -> UpdateDeploymentRequest {
+	/// Only set the value if it differs from the current value
+	fn changed<T>(current: &T, desired: &T) -> Option<T>
+	where
+		T: PartialEq + Clone,
+	{
+		(current != desired).then(|| desired.clone())
+	}
+
+	let deployment = &current.deployment.data;
+	let details = &current.running_details;
+	let desired_details = &desired.running_details;
+
+	UpdateDeploymentRequest {
+		name: None,
+		runner: changed(&deployment.runner, &desired.runner),
+		machine_type: changed(&deployment.machine_type, &desired.machine_type),
+		deploy_on_push: changed(&details.deploy_on_push, &desired_details.deploy_on_push),
+		min_horizontal_scale: changed(
+			&details.min_horizontal_scale,
+			&desired_details.min_horizontal_scale,
+		),
+		max_horizontal_scale: changed(
+			&details.max_horizontal_scale,
+			&desired_details.max_horizontal_scale,
+		),
+		ports: changed(&details.ports, &desired_details.ports),
+		environment_variables: changed(
+			&details.environment_variables,
+			&desired_details.environment_variables,
+		),
+		// A probe cannot be removed through an update, so only probes that are
+		// added or changed are sent
+		startup_probe: changed(&details.startup_probe, &desired_details.startup_probe).flatten(),
+		liveness_probe: changed(&details.liveness_probe, &desired_details.liveness_probe).flatten(),
+		config_mounts: changed(&details.config_mounts, &desired_details.config_mounts),
+		volumes: changed(&details.volumes, &desired_details.volumes),
+	}
+}
+
+/// Build the request that creates a deployment again exactly as it was, used to
+/// undo the deletion of a deployment
+pub(super) fn deployment_recreate_request(
+	previous: &GetDeploymentInfoResponse,
+) -> CreateDeploymentRequest {
+	let deployment = &previous.deployment.data;
+	CreateDeploymentRequest {
+		name: deployment.name.clone(),
+		registry: deployment.registry.clone(),
+		image_tag: deployment.image_tag.clone(),
+		runner: deployment.runner,
+		machine_type: deployment.machine_type,
+		running_details: previous.running_details.clone(),
+		deploy_on_create: !matches!(
+			deployment.status,
+			DeploymentStatus::Created | DeploymentStatus::Stopped
+		),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{collections::BTreeMap, path::PathBuf};
+
+	use models::{
+		api::{
+			workspace::{
+				domain::{Domain, DomainNameserverType, WorkspaceDomain},
+				managed_url::{ManagedUrl, ManagedUrlType},
+				secret::Secret,
+			},
+			WithId,
+		},
+		iaac::{IaacResourceType, Resource},
+		utils::Uuid,
+	};
+
+	use super::{LiveResource, LiveState, Plan, PlannedAction};
+	use crate::iaac::{ResourceGraph, SourcedResource};
+
+	/// Build the graph of a list of resources, as they would be declared in an
+	/// IaaC file
+	fn graph(yaml: &str) -> ResourceGraph {
+		ResourceGraph::build(
+			serde_yaml::from_str::<Vec<Resource>>(yaml)
+				.unwrap()
+				.into_iter()
+				.map(|resource| SourcedResource {
+					file: PathBuf::from("resources.yaml"),
+					resource,
+				})
+				.collect(),
+		)
+		.unwrap()
+	}
+
+	/// A workspace with two domains, two managed URLs on one of them and two
+	/// secrets
+	fn workspace() -> LiveState {
+		let domain = |name: &str| {
+			(
+				name.to_string(),
+				WithId::new(
+					Uuid::new_v4(),
+					WorkspaceDomain {
+						domain: Domain {
+							name: name.to_string(),
+							last_unverified: None,
+						},
+						is_verified: true,
+						nameserver_type: DomainNameserverType::Internal,
+					},
+				),
+			)
+		};
+		let secret = |name: &str| {
+			(
+				name.to_string(),
+				WithId::new(
+					Uuid::new_v4(),
+					Secret {
+						name: name.to_string(),
+						deployment_id: None,
+					},
+				),
+			)
+		};
+		let domains = BTreeMap::from([domain("example.com"), domain("other.com")]);
+		let domain_id = domains["example.com"].id;
+		let managed_url = |path: &str, url: &str| {
+			WithId::new(
+				Uuid::new_v4(),
+				ManagedUrl {
+					sub_domain: "@".to_string(),
+					domain_id,
+					path: path.to_string(),
+					url_type: ManagedUrlType::ProxyUrl {
+						url: url.to_string(),
+						http_only: false,
+					},
+					is_configured: true,
+				},
+			)
+		};
+
+		LiveState {
+			deployments: BTreeMap::new(),
+			runners: Vec::new(),
+			machine_types: Vec::new(),
+			databases: BTreeMap::new(),
+			repositories: BTreeMap::new(),
+			domains,
+			managed_urls: vec![
+				managed_url("/", "https://old.example.org"),
+				managed_url("/stale", "https://stale.example.org"),
+			],
+			secrets: BTreeMap::from([secret("old-secret"), secret("stale-secret")]),
+			static_sites: BTreeMap::new(),
+		}
+	}
+
+	const RESOURCES: &str = r#"
+- type: Secret
+  name: new-secret
+  value: hunter2
+- type: Secret
+  name: old-secret
+  value: hunter2
+- type: Domain
+  name: example.com
+  nameserver_type: internal
+- type: Domain
+  name: other.com
+  nameserver_type: external
+- type: ManagedUrl
+  name: site
+  domain: example.com
+  target:
+    proxy:
+      url: https://new.example.org
+"#;
+
+	/// The type, name and action of every change of a plan, in order
+	fn actions(plan: &Plan) -> Vec<(IaacResourceType, String, PlannedAction)> {
+		plan.changes
+			.iter()
+			.map(|change| (change.resource_type, change.name.clone(), change.action))
+			.collect()
+	}
+
+	#[test]
+	fn assert_declared_resources_are_planned_against_the_live_state() {
+		let live = workspace();
+		let plan = Plan::create(Uuid::new_v4(), &graph(RESOURCES), &live, false).unwrap();
+
+		assert_eq!(
+			actions(&plan),
+			vec![
+				(
+					IaacResourceType::Domain,
+					"example.com".to_string(),
+					PlannedAction::NoOp
+				),
+				(
+					IaacResourceType::ManagedUrl,
+					"site".to_string(),
+					PlannedAction::Update
+				),
+				(
+					IaacResourceType::Domain,
+					"other.com".to_string(),
+					PlannedAction::Replace
+				),
+				(
+					IaacResourceType::Secret,
+					"new-secret".to_string(),
+					PlannedAction::Create
+				),
+				(
+					IaacResourceType::Secret,
+					"old-secret".to_string(),
+					PlannedAction::NoOp
+				),
+			]
+		);
+
+		let fields = |name: &str| {
+			plan.changes
+				.iter()
+				.find(|change| change.name == name)
+				.unwrap()
+				.changes
+				.iter()
+				.map(|change| change.field.clone())
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(fields("site"), vec!["url_type"]);
+		assert_eq!(fields("other.com"), vec!["nameserver_type"]);
+		assert!(fields("example.com").is_empty());
+		assert!(!plan.is_empty());
+	}
+
+	#[test]
+	fn assert_undeclared_resources_are_only_deleted_when_pruning() {
+		let live = workspace();
+		let graph = graph(RESOURCES);
+
+		let plan = Plan::create(Uuid::new_v4(), &graph, &live, false).unwrap();
+		assert!(plan
+			.changes
+			.iter()
+			.all(|change| change.action != PlannedAction::Delete));
+
+		let plan = Plan::create(Uuid::new_v4(), &graph, &live, true).unwrap();
+		let deleted = actions(&plan)
+			.into_iter()
+			.filter(|(_, _, action)| *action == PlannedAction::Delete)
+			.map(|(resource_type, name, _)| (resource_type, name))
+			.collect::<Vec<_>>();
+		// The managed URL is deleted before anything it may depend on
+		assert_eq!(
+			deleted,
+			vec![
+				(IaacResourceType::ManagedUrl, "example.com/stale".to_string()),
+				(IaacResourceType::Secret, "stale-secret".to_string()),
+			]
+		);
+
+		let stale = plan
+			.changes
+			.iter()
+			.find(|change| change.name == "example.com/stale")
+			.unwrap();
+		assert!(stale.desired.is_none());
+		assert!(matches!(
+			&stale.current,
+			Some(LiveResource::ManagedUrl(managed_url)) if managed_url.data.path == "/stale"
+		));
+	}
+
+	#[test]
+	fn assert_a_workspace_that_matches_its_declaration_has_no_changes() {
+		let live = workspace();
+		let plan = Plan::create(
+			Uuid::new_v4(),
+			&graph(
+				r#"
+- type: Secret
+  name: old-secret
+  value: hunter2
+- type: Domain
+  name: example.com
+  nameserver_type: internal
+- type: ManagedUrl
+  name: site
+  domain: example.com
+  target:
+    proxy:
+      url: https://old.example.org
+"#,
+			),
+			&live,
+			false,
+		)
+		.unwrap();
+
+		assert!(plan.is_empty());
+		assert!(plan.changes.iter().all(|change| change.changes.is_empty()));
+	}
+}
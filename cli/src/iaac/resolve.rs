@@ -0,0 +1,241 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use models::{
+	iaac::{
+		IaacOutput,
+		IaacOutputReference,
+		IaacResolver,
+		IaacResourceType,
+		MaybeExternallySourced,
+	},
+	ApiErrorResponse,
+};
+
+use super::{LiveState, ResourceKey};
+use crate::prelude::*;
+
+/// A value of a declared resource, after resolving where it is sourced from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolvedValue {
+	/// The value is known while planning
+	Known {
+		/// The value itself
+		value: String,
+		/// Whether the value is a secret, which must never be shown
+		sensitive: bool,
+	},
+	/// The value is an output of a resource that is created or replaced by the
+	/// plan, and is only known once that resource has been applied
+	KnownAfterApply(IaacOutputReference),
+}
+
+/// Resolves the externally sourced values of the declared resources, either
+/// from the environment that the CLI runs in or from the outputs of other
+/// resources of the workspace
+#[derive(Debug, Clone)]
+pub struct ValueResolver<'a> {
+	/// The live state of the workspace, which the outputs of the resources
+	/// that already exist are read from
+	live: &'a LiveState,
+	/// The resources that are going to be created or replaced by the plan,
+	/// whose outputs are not known until the plan is applied
+	pending: BTreeSet<ResourceKey>,
+}
+
+impl<'a> ValueResolver<'a> {
+	/// Create a resolver for the given live state of a workspace
+	pub fn new(live: &'a LiveState) -> Self {
+		Self {
+			live,
+			pending: BTreeSet::new(),
+		}
+	}
+
+	/// Mark a resource as being created or replaced by the plan, so that the
+	/// values sourced from its outputs are only resolved when applying
+	pub fn mark_pending(&mut self, resource_type: IaacResourceType, name: &str) {
+		self.pending.insert((resource_type, name.to_string()));
+	}
+
+	/// Check if a resource is going to be created or replaced by the plan
+	pub fn is_pending(&self, resource_type: IaacResourceType, name: &str) -> bool {
+		self.pending.contains(&(resource_type, name.to_string()))
+	}
+
+	/// Resolve a value of a field of a declared resource
+	pub fn resolve(
+		&self,
+		resource_type: IaacResourceType,
+		name: &str,
+		field: &str,
+		value: &MaybeExternallySourced<String>,
+	) -> Result<ResolvedValue, ApiErrorResponse> {
+		match value {
+			MaybeExternallySourced::Value(value) => Ok(ResolvedValue::Known {
+				value: value.clone(),
+				sensitive: false,
+			}),
+			// Values are read from the environment to keep them out of the IaaC
+			// files, which is usually because they are secrets
+			MaybeExternallySourced::FromEnvironment { from_env } => std::env::var(from_env)
+				.map(|value| ResolvedValue::Known {
+					value,
+					sensitive: true,
+				})
+				.map_err(|_| {
+					ApiErrorResponse::error_with_message(
+						ErrorType::WrongParameters,
+						format!(
+							"The field `{}` of the {} `{}` is sourced from the environment variable `{}`, which is not set",
+							field, resource_type, name, from_env
+						),
+					)
+				}),
+			MaybeExternallySourced::FromResource { from_resource } => {
+				if self.is_pending(from_resource.resource_type, &from_resource.name) {
+					return Ok(ResolvedValue::KnownAfterApply(from_resource.clone()));
+				}
+
+				self.live_output(from_resource)
+					.map(|value| ResolvedValue::Known {
+						value,
+						sensitive: from_resource.output.is_secret(),
+					})
+					.ok_or_else(|| {
+						ApiErrorResponse::error_with_message(
+							ErrorType::WrongParameters,
+							format!(
+								"The field `{}` of the {} `{}` is sourced from `{}`, but the {} `{}` does not exist in the workspace",
+								field,
+								resource_type,
+								name,
+								from_resource,
+								from_resource.resource_type,
+								from_resource.name
+							),
+						)
+					})
+			}
+		}
+	}
+
+	/// Read an output of a resource that already exists in the workspace
+	fn live_output(&self, reference: &IaacOutputReference) -> Option<String> {
+		match reference.resource_type {
+			IaacResourceType::Deployment => {
+				let deployment = self.live.deployments.get(&reference.name)?;
+				output_from_id(reference.output, deployment.deployment.id)
+			}
+			IaacResourceType::Database => {
+				let database = self.live.databases.get(&reference.name)?;
+				let connection = &database.data.public_connection;
+				match reference.output {
+					IaacOutput::Id => Some(database.id.to_string()),
+					IaacOutput::Host => Some(connection.host.clone()),
+					IaacOutput::Port => Some(connection.port.to_string()),
+					IaacOutput::Username => Some(connection.username.clone()),
+					IaacOutput::Password => Some(connection.password.clone()),
+					IaacOutput::InternalUrl => None,
+				}
+			}
+			// The other resources only have the outputs that are derived from
+			// their ID
+			resource_type => output_from_id(
+				reference.output,
+				self.live.resource_id(resource_type, &reference.name)?,
+			),
+		}
+	}
+}
+
+/// Looks up the IDs of the declared resources that a plan has been applied to
+/// so far, which the references to the resources created by the plan are
+/// resolved with
+#[derive(Debug, Clone, Copy)]
+pub struct AppliedIds<'a>(pub &'a BTreeMap<ResourceKey, Uuid>);
+
+impl IaacResolver for AppliedIds<'_> {
+	fn resource_id(&self, resource_type: IaacResourceType, name: &str) -> Option<Uuid> {
+		self.0.get(&(resource_type, name.to_string())).copied()
+	}
+
+	fn runner_id(&self, _: &str) -> Option<Uuid> {
+		None
+	}
+}
+
+/// Get the value of an output of a resource that can be derived from the ID of
+/// the resource alone, which is all that is known of a resource right after it
+/// is created
+pub fn output_from_id(output: IaacOutput, id: Uuid) -> Option<String> {
+	match output {
+		IaacOutput::Id => Some(id.to_string()),
+		// Deployments are reachable by the name of their service from the other
+		// deployments of the workspace
+		IaacOutput::InternalUrl => Some(format!("service-{}", id)),
+		IaacOutput::Host | IaacOutput::Port | IaacOutput::Username | IaacOutput::Password => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::BTreeMap;
+
+	use models::iaac::{IaacResourceType, MaybeExternallySourced};
+
+	use super::{ResolvedValue, ValueResolver};
+	use crate::iaac::LiveState;
+
+	/// The live state of an empty workspace
+	fn empty_workspace() -> LiveState {
+		LiveState {
+			deployments: BTreeMap::new(),
+			runners: Vec::new(),
+			machine_types: Vec::new(),
+			databases: BTreeMap::new(),
+			repositories: BTreeMap::new(),
+			domains: BTreeMap::new(),
+			managed_urls: Vec::new(),
+			secrets: BTreeMap::new(),
+			static_sites: BTreeMap::new(),
+		}
+	}
+
+	#[test]
+	fn assert_environment_values_are_sensitive() {
+		std::env::set_var("PATR_TEST_ENVIRONMENT_VALUE", "hunter2");
+
+		let live = empty_workspace();
+		let resolver = ValueResolver::new(&live);
+		let resolve = |value| {
+			resolver.resolve(
+				IaacResourceType::Deployment,
+				"api",
+				"environment_variables.PASSWORD",
+				&value,
+			)
+		};
+
+		assert_eq!(
+			resolve(MaybeExternallySourced::FromEnvironment {
+				from_env: "PATR_TEST_ENVIRONMENT_VALUE".to_string(),
+			})
+			.unwrap(),
+			ResolvedValue::Known {
+				value: "hunter2".to_string(),
+				sensitive: true,
+			}
+		);
+		assert_eq!(
+			resolve(MaybeExternallySourced::Value("hunter2".to_string())).unwrap(),
+			ResolvedValue::Known {
+				value: "hunter2".to_string(),
+				sensitive: false,
+			}
+		);
+		assert!(resolve(MaybeExternallySourced::FromEnvironment {
+			from_env: "PATR_TEST_UNSET_ENVIRONMENT_VALUE".to_string(),
+		})
+		.is_err());
+	}
+}
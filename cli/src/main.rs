@@ -12,6 +12,9 @@ use crate::prelude::*;
 mod app;
 /// All the commands, arguments and the functionalities for it.
 mod commands;
+/// The engine that plans and applies the changes declared in IaaC files to a
+/// workspace
+mod iaac;
 /// Utilities module for helper functions, structs, and enums.
 mod utils;
 
@@ -40,6 +43,7 @@ async fn main() -> ExitCode {
 			eprintln!("Loading default state...");
 		})
 		.unwrap_or_default();
+	utils::initialize_client(&state);
 
 	let output_format = global_args.output;
 	let Ok(output) = command.execute(global_args, state).await.map_err(|err| {
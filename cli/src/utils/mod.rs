@@ -8,13 +8,6 @@ pub use self::{client::*, storage::*};
 
 /// Constants used in the CLI
 pub mod constants {
-	/// The base URL for the Patr API
-	pub const API_BASE_URL: &str = if cfg!(debug_assertions) {
-		"http://localhost:3000"
-	} else {
-		"https://api.patr.cloud"
-	};
-
 	/// The user agent for the CLI
 	pub const USER_AGENT_STRING: &str = concat!(
 		"patr-cli/",
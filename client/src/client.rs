@@ -0,0 +1,408 @@
+use std::{
+	fmt::Display,
+	str::FromStr,
+	sync::{Arc, OnceLock},
+};
+
+use futures::lock::Mutex;
+use http::{HeaderMap, Method, StatusCode};
+use models::{
+	api::auth::{RenewAccessTokenPath, RenewAccessTokenRequest, RenewAccessTokenRequestHeaders},
+	utils::{constants, Headers},
+	ApiResponseBody,
+};
+use preprocess::Preprocessable;
+use serde::{de::DeserializeOwned, Serialize};
+use typed_builder::TypedBuilder;
+use url::Url;
+
+use crate::{prelude::*, retry, RetryPolicy, Session, SessionState};
+
+/// The user agent that the client uses when it renews the session by itself
+const USER_AGENT: &str = concat!("patr-client/", env!("CARGO_PKG_VERSION"));
+
+/// A function that is called with the renewed session whenever the client
+/// renews the access token of the session
+pub type SessionRenewedHook = Arc<dyn Fn(&Session) + Send + Sync>;
+
+/// The client used by [`make_request`], [`stream_request`][1] and
+/// [`paginate`][2]
+///
+/// [1]: crate::stream_request
+/// [2]: crate::paginate
+static DEFAULT_CLIENT: OnceLock<ApiClient> = OnceLock::new();
+
+/// A client for the Patr API. Cloning the client is cheap, and all the clones
+/// share the same session.
+///
+/// ## Example
+/// ```rust,ignore
+/// let client = ApiClient::builder()
+/// 	.base_url(Url::parse("http://localhost:3000")?)
+/// 	.session(Session {
+/// 		access_token,
+/// 		refresh_token,
+/// 	})
+/// 	.on_session_renewed(|session: &Session| save_token(&session.access_token))
+/// 	.build();
+///
+/// let response = client
+/// 	.make_request(
+/// 		ApiRequest::<GetUserInfoRequest>::builder()
+/// 			.path(GetUserInfoPath)
+/// 			.query(())
+/// 			.headers(GetUserInfoRequestHeaders {
+/// 				authorization: BearerToken::from_str(&access_token)?,
+/// 				user_agent: UserAgent::from_static("my-app"),
+/// 			})
+/// 			.body(GetUserInfoRequest)
+/// 			.build(),
+/// 	)
+/// 	.await?;
+/// ```
+#[derive(Clone, TypedBuilder)]
+pub struct ApiClient {
+	/// The URL of the API that the requests are made to. Defaults to
+	/// [`constants::API_BASE_URL`].
+	#[builder(default = Url::parse(constants::API_BASE_URL).expect("the API base URL is not valid"))]
+	base_url: Url,
+	/// The user agent that the client uses when it renews the session by
+	/// itself. The other requests use the user agent in their headers.
+	#[builder(default = UserAgent::from_static(USER_AGENT))]
+	user_agent: UserAgent,
+	/// When and how often failed requests are retried
+	#[builder(default)]
+	retry_policy: RetryPolicy,
+	/// The session of the logged in user, if any. Without a session, expired
+	/// access tokens are not renewed.
+	#[builder(
+		default,
+		setter(transform = |session: Session| Arc::new(Mutex::new(SessionState::new(session))))
+	)]
+	session: Arc<Mutex<SessionState>>,
+	/// Called whenever the access token of the session is renewed, so that the
+	/// renewed session can be stored
+	#[builder(
+		default,
+		setter(transform = |hook: impl Fn(&Session) + Send + Sync + 'static| {
+			Some(Arc::new(hook) as SessionRenewedHook)
+		})
+	)]
+	on_session_renewed: Option<SessionRenewedHook>,
+	/// The HTTP client used to send the requests
+	#[builder(default)]
+	http_client: reqwest::Client,
+}
+
+impl Default for ApiClient {
+	fn default() -> Self {
+		Self::builder().build()
+	}
+}
+
+/// Why a request to the API failed
+pub(crate) enum RequestFailure {
+	/// The request could not be sent, or no response was received for it
+	Network(ApiErrorResponse),
+	/// The API responded with an error
+	Api(ApiErrorResponse),
+}
+
+impl RequestFailure {
+	/// The status code the API responded with, if it responded at all
+	pub fn status_code(&self) -> Option<StatusCode> {
+		match self {
+			Self::Network(_) => None,
+			Self::Api(error) => Some(error.status_code),
+		}
+	}
+
+	/// The error that the request failed with
+	pub fn error(&self) -> &ApiErrorResponse {
+		match self {
+			Self::Network(error) | Self::Api(error) => error,
+		}
+	}
+
+	/// Converts the failure into the error that the request failed with
+	pub fn into_error(self) -> ApiErrorResponse {
+		match self {
+			Self::Network(error) | Self::Api(error) => error,
+		}
+	}
+}
+
+/// A request that is ready to be sent. It is kept around so that it can be
+/// sent again when it is retried.
+struct PreparedRequest {
+	/// The HTTP method of the request
+	method: Method,
+	/// The URL of the request, including the query
+	url: Url,
+	/// The headers of the request
+	headers: HeaderMap,
+	/// The JSON body of the request, if the request has one
+	body: Option<serde_json::Value>,
+}
+
+impl ApiClient {
+	/// The client used by the functions of this crate that do not take a
+	/// client. It uses the default configuration and has no session.
+	pub fn default_client() -> &'static Self {
+		DEFAULT_CLIENT.get_or_init(Self::default)
+	}
+
+	/// Replaces the session of the client, and all of its clones. Pass [`None`]
+	/// to log out.
+	pub async fn set_session(&self, session: Option<Session>) {
+		*self.session.lock().await = match session {
+			Some(session) => SessionState::new(session),
+			None => SessionState::default(),
+		};
+	}
+
+	/// The current session of the client, if any
+	pub async fn session(&self) -> Option<Session> {
+		self.session.lock().await.session.clone()
+	}
+
+	/// Make an API request to an endpoint.
+	///
+	/// If the request is made with the access token of the session and the
+	/// token has expired, the session is renewed and the request is sent again
+	/// with the renewed token. Idempotent requests that fail with a transient
+	/// error are retried according to the [`RetryPolicy`] of the client.
+	pub async fn make_request<E>(
+		&self,
+		ApiRequest {
+			path,
+			query,
+			headers,
+			body,
+		}: ApiRequest<E>,
+	) -> Result<ApiSuccessResponse<E>, ApiErrorResponse>
+	where
+		E: ApiEndpoint,
+		<E::RequestBody as Preprocessable>::Processed: Send,
+		E::ResponseBody: DeserializeOwned,
+		E::RequestBody: Serialize,
+	{
+		let body = serde_json::to_value(&body).map_err(client_error)?;
+		let mut request = PreparedRequest {
+			method: E::METHOD,
+			url: self.endpoint_url::<E>(&path, &query)?,
+			headers: headers.to_header_map(),
+			body: (!body.is_null()).then_some(body),
+		};
+
+		let mut renewed = false;
+		let mut retries = 0;
+		loop {
+			let access_token = self.authorize(&mut request.headers).await;
+			let failure = match self.send::<E>(&request).await {
+				Ok(response) => return Ok(response),
+				Err(failure) => failure,
+			};
+
+			if let (Some(access_token), false, ErrorType::AuthorizationTokenInvalid) =
+				(&access_token, renewed, &failure.error().body.error)
+			{
+				self.renew_session(access_token).await?;
+				renewed = true;
+				continue;
+			}
+
+			let Some(backoff) = self.retry_policy.backoff(
+				&request.method,
+				retries,
+				failure.status_code(),
+				&failure.error().body.error,
+			) else {
+				return Err(failure.into_error());
+			};
+			warn!(
+				"Request to `{}` failed: `{}`. Retrying in {:?}",
+				request.url,
+				failure.error().body.message,
+				backoff
+			);
+			retry::sleep(backoff).await;
+			retries += 1;
+		}
+	}
+
+	/// The URL of an endpoint, with the given path and query
+	pub(crate) fn endpoint_url<E>(
+		&self,
+		path: &E::RequestPath,
+		query: &E::RequestQuery,
+	) -> Result<Url, ApiErrorResponse>
+	where
+		E: ApiEndpoint,
+		<E::RequestBody as Preprocessable>::Processed: Send,
+	{
+		let mut url = self
+			.base_url
+			.join(path.to_string().as_str())
+			.map_err(client_error)?;
+		let query = serde_urlencoded::to_string(query).map_err(client_error)?;
+		if !query.is_empty() {
+			url.set_query(Some(&query));
+		}
+		Ok(url)
+	}
+
+	/// Sends the current access token of the session in the `Authorization`
+	/// header, if the header has an access token that the session has replaced.
+	/// Returns the access token of the session that the request is sent with,
+	/// if any. See [`SessionState::authorize`] for more details.
+	pub(crate) async fn authorize(&self, headers: &mut HeaderMap) -> Option<String> {
+		self.session.lock().await.authorize(headers)
+	}
+
+	/// Renews the access token of the session, after a request made with the
+	/// given access token failed because the token has expired. If the session
+	/// has already been renewed since, by another request, nothing is done.
+	pub(crate) async fn renew_session(&self, expired_token: &str) -> Result<(), ApiErrorResponse> {
+		// The lock is held until the session is renewed, so that requests that
+		// fail at the same time only renew the session once
+		let mut state = self.session.lock().await;
+		let Some(session) = state.session.as_ref() else {
+			return Err(ApiErrorResponse::error(
+				ErrorType::AuthorizationTokenInvalid,
+			));
+		};
+		if session.access_token != expired_token {
+			return Ok(());
+		}
+
+		let refresh_token = BearerToken::from_str(&session.refresh_token)
+			.map_err(|_| ApiErrorResponse::error(ErrorType::AuthorizationTokenInvalid))?;
+		let request = PreparedRequest {
+			method: RenewAccessTokenRequest::METHOD,
+			url: self.endpoint_url::<RenewAccessTokenRequest>(&RenewAccessTokenPath, &())?,
+			headers: RenewAccessTokenRequestHeaders {
+				refresh_token,
+				user_agent: self.user_agent.clone(),
+			}
+			.to_header_map(),
+			body: None,
+		};
+		let access_token = self
+			.send::<RenewAccessTokenRequest>(&request)
+			.await
+			.map_err(RequestFailure::into_error)?
+			.body
+			.access_token;
+
+		debug!("Renewed the access token of the session");
+		if let (Some(session), Some(hook)) = (state.renew(access_token), &self.on_session_renewed) {
+			hook(session);
+		}
+
+		Ok(())
+	}
+
+	/// Starts building a request to the API with the HTTP client of the client
+	pub(crate) fn request_builder(
+		&self,
+		method: Method,
+		url: Url,
+		headers: HeaderMap,
+	) -> reqwest::RequestBuilder {
+		self.http_client.request(method, url).headers(headers)
+	}
+
+	/// Sends a request to the API once and parses the response
+	async fn send<E>(
+		&self,
+		request: &PreparedRequest,
+	) -> Result<ApiSuccessResponse<E>, RequestFailure>
+	where
+		E: ApiEndpoint,
+		<E::RequestBody as Preprocessable>::Processed: Send,
+		E::ResponseBody: DeserializeOwned,
+	{
+		let builder = self.request_builder(
+			request.method.clone(),
+			request.url.clone(),
+			request.headers.clone(),
+		);
+		let builder = match &request.body {
+			Some(body) => builder.json(body),
+			None => builder,
+		};
+
+		self.execute::<E>(builder).await
+	}
+
+	/// Sends a request that has been built to the API once and parses the
+	/// response
+	pub(crate) async fn execute<E>(
+		&self,
+		builder: reqwest::RequestBuilder,
+	) -> Result<ApiSuccessResponse<E>, RequestFailure>
+	where
+		E: ApiEndpoint,
+		<E::RequestBody as Preprocessable>::Processed: Send,
+		E::ResponseBody: DeserializeOwned,
+	{
+		let response = builder
+			.send()
+			.await
+			.map_err(|err| RequestFailure::Network(client_error(err)))?;
+		let status_code = response.status();
+		let headers = response.headers().clone();
+		let body = response
+			.bytes()
+			.await
+			.map_err(|err| RequestFailure::Network(client_error(err)))?;
+
+		match serde_json::from_slice::<ApiResponseBody<E::ResponseBody>>(&body) {
+			Ok(ApiResponseBody::Success(ApiSuccessResponseBody {
+				success: _,
+				response: body,
+			})) => Ok(ApiSuccessResponse {
+				status_code,
+				headers: E::ResponseHeaders::from_header_map(&headers)
+					.map_err(|err| RequestFailure::Api(client_error(err)))?,
+				body,
+			}),
+			Ok(ApiResponseBody::Error(error)) => Err(RequestFailure::Api(ApiErrorResponse {
+				status_code,
+				body: error,
+			})),
+			Err(err) => {
+				error!(
+					"Error parsing the response `{}` as JSON: {}",
+					String::from_utf8_lossy(&body),
+					err
+				);
+				Err(RequestFailure::Api(ApiErrorResponse {
+					status_code,
+					..client_error(err)
+				}))
+			}
+		}
+	}
+}
+
+/// Make an API request to an endpoint, using the default client. See
+/// [`ApiClient::make_request`] for more details.
+pub async fn make_request<E>(
+	request: ApiRequest<E>,
+) -> Result<ApiSuccessResponse<E>, ApiErrorResponse>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+	E::ResponseBody: DeserializeOwned,
+	E::RequestBody: Serialize,
+{
+	ApiClient::default_client().make_request(request).await
+}
+
+/// Creates an error response for an error that happened in the client, before
+/// or after the request was handled by the API
+pub(crate) fn client_error(error: impl Display) -> ApiErrorResponse {
+	ApiErrorResponse::error_with_message(ErrorType::server_error(&error), error)
+}
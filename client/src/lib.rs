@@ -0,0 +1,41 @@
+//! A typed client for the Patr API, used by the CLI, the runners and anything
+//! else that talks to the API from Rust. Every endpoint declared in the
+//! [`models`] crate can be called with the request and response types of the
+//! endpoint, and the endpoints declared with `declare_stream_endpoint!` can be
+//! streamed over a websocket. Endpoints that take a raw or `multipart/form-data`
+//! body can be uploaded to with a streamed body.
+//!
+//! The [`ApiClient`] takes care of renewing the access token of a logged in
+//! user, retrying idempotent requests that fail with a transient error and
+//! going through the pages of paginated endpoints. For the cases where none of
+//! that needs to be configured, the [`make_request`], [`stream_request`],
+//! [`paginate`], [`upload_raw`] and [`upload_multipart`] functions use a
+//! default client.
+
+/// The client that makes requests to the API
+mod client;
+/// Helpers to go through all the pages of a paginated endpoint
+mod pagination;
+/// When and how often failed requests are retried
+mod retry;
+/// The session of a logged in user, used to renew their access token
+mod session;
+/// Streaming requests to the API over websockets. Browsers cannot set the
+/// `Authorization` header of a websocket, so this is not available on wasm.
+#[cfg(not(target_arch = "wasm32"))]
+mod stream;
+/// Uploading raw and `multipart/form-data` bodies to the API, which are sent
+/// as they are streamed instead of being serialized as JSON
+mod upload;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::stream::*;
+pub use self::{client::*, pagination::*, retry::*, session::*, upload::*};
+
+/// The prelude module contains all the commonly used types and traits that are
+/// used across the crate. This is mostly used to avoid having to import a lot
+/// of things from different modules.
+mod prelude {
+	pub use models::prelude::*;
+	pub use tracing::{debug, error, info, instrument, trace, warn};
+}
@@ -0,0 +1,142 @@
+use futures::{stream, Stream};
+use models::utils::HasHeader;
+use preprocess::Preprocessable;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{prelude::*, ApiClient};
+
+impl ApiClient {
+	/// Make requests to a paginated endpoint, going through the pages one by
+	/// one starting from the page in the query of the request. Each item of the
+	/// stream is the response for one page. The stream ends once all the items
+	/// in the [`TotalCountHeader`] have been fetched, or on the first error.
+	///
+	/// ## Example
+	/// ```rust,ignore
+	/// let deployments = client
+	/// 	.paginate(
+	/// 		ApiRequest::<ListDeploymentRequest>::builder()
+	/// 			.path(ListDeploymentPath { workspace_id })
+	/// 			.query(Paginated {
+	/// 				data: ListDeploymentQuery { project_id: None },
+	/// 				count: 100,
+	/// 				page: 0,
+	/// 			})
+	/// 			.headers(headers)
+	/// 			.body(ListDeploymentRequest)
+	/// 			.build(),
+	/// 	)
+	/// 	.map_ok(|response| response.body.deployments)
+	/// 	.try_concat()
+	/// 	.await?;
+	/// ```
+	pub fn paginate<E, Q>(
+		&self,
+		ApiRequest {
+			path,
+			query,
+			headers,
+			body,
+		}: ApiRequest<E>,
+	) -> impl Stream<Item = Result<ApiSuccessResponse<E>, ApiErrorResponse>>
+	where
+		E: ApiEndpoint<RequestQuery = Paginated<Q>>,
+		<E::RequestBody as Preprocessable>::Processed: Send,
+		E::ResponseHeaders: HasHeader<TotalCountHeader>,
+		E::ResponseBody: DeserializeOwned,
+		E::RequestBody: Serialize + Clone,
+		Q: Clone,
+	{
+		let client = self.clone();
+		stream::try_unfold(Some(query), move |query| {
+			let client = client.clone();
+			let path = path.clone();
+			let headers = headers.clone();
+			let body = body.clone();
+			async move {
+				let Some(query) = query else {
+					return Ok(None);
+				};
+
+				let response = client
+					.make_request(
+						ApiRequest::<E>::builder()
+							.path(path)
+							.query(query.clone())
+							.headers(headers)
+							.body(body)
+							.build(),
+					)
+					.await?;
+
+				let TotalCountHeader(total_count) =
+					*HasHeader::<TotalCountHeader>::get_header(&response.headers);
+
+				Ok(Some((response, next_page(query, total_count))))
+			}
+		})
+	}
+}
+
+/// The query for the page after the given one, or [`None`] if all the
+/// `total_count` items have been fetched with the given page
+fn next_page<Q>(query: Paginated<Q>, total_count: usize) -> Option<Paginated<Q>> {
+	let fetched = query.page.saturating_add(1).saturating_mul(query.count);
+	(query.count > 0 && fetched < total_count).then(|| Paginated {
+		page: query.page + 1,
+		..query
+	})
+}
+
+/// Make requests to a paginated endpoint using the default client, going
+/// through all the pages. See [`ApiClient::paginate`] for more details.
+pub fn paginate<E, Q>(
+	request: ApiRequest<E>,
+) -> impl Stream<Item = Result<ApiSuccessResponse<E>, ApiErrorResponse>>
+where
+	E: ApiEndpoint<RequestQuery = Paginated<Q>>,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+	E::ResponseHeaders: HasHeader<TotalCountHeader>,
+	E::ResponseBody: DeserializeOwned,
+	E::RequestBody: Serialize + Clone,
+	Q: Clone,
+{
+	ApiClient::default_client().paginate(request)
+}
+
+#[cfg(test)]
+mod tests {
+	use models::utils::Paginated;
+
+	use super::next_page;
+
+	fn page(count: usize, page: usize) -> Paginated<()> {
+		Paginated {
+			data: (),
+			count,
+			page,
+		}
+	}
+
+	#[test]
+	fn assert_pages_are_fetched_until_the_total_count() {
+		assert_eq!(next_page(page(10, 0), 25), Some(page(10, 1)));
+		assert_eq!(next_page(page(10, 1), 25), Some(page(10, 2)));
+		assert_eq!(next_page(page(10, 2), 25), None);
+		assert_eq!(next_page(page(10, 1), 20), None);
+		assert_eq!(next_page(page(10, 0), 0), None);
+	}
+
+	#[test]
+	fn assert_pagination_starts_from_the_given_page() {
+		assert_eq!(next_page(page(5, 3), 21), Some(page(5, 4)));
+		assert_eq!(next_page(page(5, 4), 21), None);
+		assert_eq!(next_page(page(5, 5), 21), None);
+	}
+
+	#[test]
+	fn assert_empty_pages_are_not_paginated() {
+		assert_eq!(next_page(page(0, 0), 10), None);
+		assert_eq!(next_page(page(usize::MAX, usize::MAX), usize::MAX), None);
+	}
+}
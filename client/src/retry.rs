@@ -0,0 +1,226 @@
+use std::time::Duration;
+
+use http::{Method, StatusCode};
+
+use crate::prelude::*;
+
+/// When and how often a request that fails with a transient error is retried.
+/// Only idempotent requests (`GET`, `HEAD`, `PUT`, `DELETE` and `OPTIONS`) are
+/// retried, since the others may have already been processed by the API
+/// before failing.
+///
+/// The time between retries starts at [`initial_backoff`] and doubles after
+/// every retry, up to [`max_backoff`].
+///
+/// [`initial_backoff`]: RetryPolicy::initial_backoff
+/// [`max_backoff`]: RetryPolicy::max_backoff
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+	/// The number of times a request is retried before giving up. Set this to 0
+	/// to never retry requests.
+	pub max_retries: u32,
+	/// The time to wait before retrying a request for the first time
+	pub initial_backoff: Duration,
+	/// The maximum time to wait before retrying a request. If the API asks to
+	/// wait longer than this before retrying, the request is not retried.
+	pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+	/// A policy that never retries requests
+	pub const NEVER: Self = Self {
+		max_retries: 0,
+		initial_backoff: Duration::ZERO,
+		max_backoff: Duration::ZERO,
+	};
+
+	/// Returns how long to wait before retrying a request that failed, or
+	/// [`None`] if the request should not be retried. `retries` is the number
+	/// of times the request has already been retried.
+	///
+	/// A request is retried if it could not be sent, if no response was
+	/// received, or if the API is temporarily unavailable or rate limiting the
+	/// request.
+	pub(crate) fn backoff(
+		&self,
+		method: &Method,
+		retries: u32,
+		status_code: Option<StatusCode>,
+		error: &ErrorType,
+	) -> Option<Duration> {
+		let idempotent = [
+			Method::GET,
+			Method::HEAD,
+			Method::PUT,
+			Method::DELETE,
+			Method::OPTIONS,
+		]
+		.contains(method);
+		let transient = match status_code {
+			None => true,
+			Some(status_code) => matches!(
+				status_code,
+				StatusCode::TOO_MANY_REQUESTS |
+					StatusCode::BAD_GATEWAY |
+					StatusCode::SERVICE_UNAVAILABLE |
+					StatusCode::GATEWAY_TIMEOUT
+			),
+		};
+		if !idempotent || !transient || retries >= self.max_retries {
+			return None;
+		}
+
+		let backoff = self
+			.initial_backoff
+			.saturating_mul(2u32.saturating_pow(retries))
+			.min(self.max_backoff);
+		match error.retry_after().map(Duration::from_secs) {
+			Some(retry_after) if retry_after > self.max_backoff => None,
+			Some(retry_after) => Some(retry_after.max(backoff)),
+			None => Some(backoff),
+		}
+	}
+}
+
+impl Default for RetryPolicy {
+	fn default() -> Self {
+		Self {
+			max_retries: 3,
+			initial_backoff: Duration::from_millis(250),
+			max_backoff: Duration::from_secs(10),
+		}
+	}
+}
+
+/// Waits for the given duration before retrying a request
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn sleep(duration: Duration) {
+	tokio::time::sleep(duration).await
+}
+
+/// Waits for the given duration before retrying a request
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn sleep(duration: Duration) {
+	gloo_timers::future::sleep(duration).await
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use http::{Method, StatusCode};
+	use models::ErrorType;
+
+	use super::RetryPolicy;
+
+	const POLICY: RetryPolicy = RetryPolicy {
+		max_retries: 3,
+		initial_backoff: Duration::from_secs(1),
+		max_backoff: Duration::from_secs(5),
+	};
+
+	#[test]
+	fn assert_only_idempotent_requests_are_retried() {
+		let error = ErrorType::InternalServerError;
+		for method in [Method::GET, Method::HEAD, Method::PUT, Method::DELETE, Method::OPTIONS] {
+			assert_eq!(
+				POLICY.backoff(&method, 0, None, &error),
+				Some(Duration::from_secs(1))
+			);
+		}
+		for method in [Method::POST, Method::PATCH] {
+			assert_eq!(POLICY.backoff(&method, 0, None, &error), None);
+		}
+	}
+
+	#[test]
+	fn assert_only_transient_failures_are_retried() {
+		let error = ErrorType::InternalServerError;
+		for status_code in [
+			StatusCode::TOO_MANY_REQUESTS,
+			StatusCode::BAD_GATEWAY,
+			StatusCode::SERVICE_UNAVAILABLE,
+			StatusCode::GATEWAY_TIMEOUT,
+		] {
+			assert!(POLICY
+				.backoff(&Method::GET, 0, Some(status_code), &error)
+				.is_some());
+		}
+		for status_code in [
+			StatusCode::BAD_REQUEST,
+			StatusCode::UNAUTHORIZED,
+			StatusCode::NOT_FOUND,
+			StatusCode::INTERNAL_SERVER_ERROR,
+		] {
+			assert_eq!(
+				POLICY.backoff(&Method::GET, 0, Some(status_code), &error),
+				None
+			);
+		}
+	}
+
+	#[test]
+	fn assert_backoff_doubles_up_to_the_cap() {
+		let error = ErrorType::InternalServerError;
+		let backoffs = (0..4)
+			.map(|retries| POLICY.backoff(&Method::GET, retries, None, &error))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			backoffs,
+			[
+				Some(Duration::from_secs(1)),
+				Some(Duration::from_secs(2)),
+				Some(Duration::from_secs(4)),
+				None,
+			]
+		);
+
+		let policy = RetryPolicy {
+			max_retries: 10,
+			..POLICY
+		};
+		assert_eq!(
+			policy.backoff(&Method::GET, 3, None, &error),
+			Some(Duration::from_secs(5))
+		);
+		assert_eq!(
+			policy.backoff(&Method::GET, 9, None, &error),
+			Some(Duration::from_secs(5))
+		);
+		assert_eq!(RetryPolicy::NEVER.backoff(&Method::GET, 0, None, &error), None);
+	}
+
+	#[test]
+	fn assert_retry_after_is_respected() {
+		let status_code = Some(StatusCode::TOO_MANY_REQUESTS);
+		assert_eq!(
+			POLICY.backoff(
+				&Method::GET,
+				0,
+				status_code,
+				&ErrorType::TooManyRequests { retry_after: 3 }
+			),
+			Some(Duration::from_secs(3))
+		);
+		// The backoff is used if it is longer than what the API asked for
+		assert_eq!(
+			POLICY.backoff(
+				&Method::GET,
+				2,
+				status_code,
+				&ErrorType::TooManyRequests { retry_after: 3 }
+			),
+			Some(Duration::from_secs(4))
+		);
+		// Waiting longer than the cap is not worth it
+		assert_eq!(
+			POLICY.backoff(
+				&Method::GET,
+				0,
+				status_code,
+				&ErrorType::TooManyRequests { retry_after: 6 }
+			),
+			None
+		);
+	}
+}
@@ -0,0 +1,137 @@
+use std::collections::HashSet;
+
+use http::{header::AUTHORIZATION, HeaderMap, HeaderValue};
+
+/// The session of a logged in user. When a request made with the access token
+/// of the session fails because the token has expired, the client renews the
+/// access token using the refresh token and sends the request again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+	/// The access token used to authenticate the requests of the user
+	pub access_token: String,
+	/// The refresh token used to get a new access token when the current one
+	/// expires
+	pub refresh_token: String,
+}
+
+/// The session of a client, along with the access tokens that the session has
+/// had over time
+#[derive(Debug, Default)]
+pub(crate) struct SessionState {
+	/// The current session of the client, if any
+	pub session: Option<Session>,
+	/// The access tokens that were replaced when the session was renewed. The
+	/// callers of the client may still hold on to these, so requests made with
+	/// them are sent with the current access token instead.
+	pub replaced_tokens: HashSet<String>,
+}
+
+impl SessionState {
+	/// Creates the state of a client that starts with the given session
+	pub fn new(session: Session) -> Self {
+		Self {
+			session: Some(session),
+			replaced_tokens: HashSet::new(),
+		}
+	}
+
+	/// Replaces the token in the `Authorization` header with the current access
+	/// token of the session, if the token is one that the session has since
+	/// replaced. Any other token, such as the refresh token or an API token, is
+	/// left as is.
+	///
+	/// Returns the access token that the request is sent with, if it is the
+	/// access token of the session. Only those requests can be retried after
+	/// renewing the session.
+	pub fn authorize(&self, headers: &mut HeaderMap) -> Option<String> {
+		let session = self.session.as_ref()?;
+		let token = headers
+			.get(AUTHORIZATION)?
+			.to_str()
+			.ok()?
+			.trim_start_matches("Bearer ")
+			.to_string();
+
+		if self.replaced_tokens.contains(&token) {
+			let value = HeaderValue::from_str(&format!("Bearer {}", session.access_token)).ok()?;
+			headers.insert(AUTHORIZATION, value);
+			Some(session.access_token.clone())
+		} else {
+			(token == session.access_token).then_some(token)
+		}
+	}
+
+	/// Replaces the access token of the session with a renewed one
+	pub fn renew(&mut self, access_token: String) -> Option<&Session> {
+		let session = self.session.as_mut()?;
+		let expired_token = std::mem::replace(&mut session.access_token, access_token);
+		self.replaced_tokens.insert(expired_token);
+		Some(session)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use http::{header::AUTHORIZATION, HeaderMap, HeaderValue};
+
+	use super::{Session, SessionState};
+
+	fn session() -> Session {
+		Session {
+			access_token: "access-1".to_string(),
+			refresh_token: "refresh".to_string(),
+		}
+	}
+
+	fn headers(token: &str) -> HeaderMap {
+		let mut headers = HeaderMap::new();
+		headers.insert(
+			AUTHORIZATION,
+			HeaderValue::from_str(&format!("Bearer {token}")).unwrap(),
+		);
+		headers
+	}
+
+	#[test]
+	fn assert_only_session_tokens_are_authorized() {
+		let state = SessionState::new(session());
+
+		let mut access = headers("access-1");
+		assert_eq!(state.authorize(&mut access), Some("access-1".to_string()));
+		assert_eq!(access, headers("access-1"));
+
+		let mut refresh = headers("refresh");
+		assert_eq!(state.authorize(&mut refresh), None);
+		assert_eq!(refresh, headers("refresh"));
+
+		let mut api_token = headers("api-token");
+		assert_eq!(state.authorize(&mut api_token), None);
+		assert_eq!(api_token, headers("api-token"));
+
+		assert_eq!(state.authorize(&mut HeaderMap::new()), None);
+		assert_eq!(SessionState::default().authorize(&mut headers("access-1")), None);
+	}
+
+	#[test]
+	fn assert_renewed_tokens_replace_expired_ones() {
+		let mut state = SessionState::new(session());
+		assert_eq!(
+			state.renew("access-2".to_string()),
+			Some(&Session {
+				access_token: "access-2".to_string(),
+				refresh_token: "refresh".to_string(),
+			})
+		);
+		state.renew("access-3".to_string());
+
+		// Requests made with any of the expired tokens are sent with the
+		// current one
+		for token in ["access-1", "access-2", "access-3"] {
+			let mut request = headers(token);
+			assert_eq!(state.authorize(&mut request), Some("access-3".to_string()));
+			assert_eq!(request, headers("access-3"));
+		}
+
+		assert_eq!(SessionState::default().renew("access".to_string()), None);
+	}
+}
@@ -0,0 +1,116 @@
+use futures::{Stream, StreamExt};
+use models::utils::{Headers, WebSocketUpgrade};
+use preprocess::Preprocessable;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio_tungstenite::tungstenite::{
+	client::IntoClientRequest,
+	Error as TungsteniteError,
+	Message,
+};
+
+use crate::{client::client_error, prelude::*, ApiClient};
+
+impl ApiClient {
+	/// Send a streaming request to the API to listen for messages over a
+	/// websocket. Each message from the server is parsed as JSON.
+	///
+	/// If the request is made with the access token of the session and the
+	/// token has expired, the session is renewed and the request is sent again
+	/// with the renewed token.
+	pub async fn stream_request<E, ServerMsg, ClientMsg>(
+		&self,
+		ApiRequest {
+			path,
+			query,
+			headers,
+			body: _,
+		}: ApiRequest<E>,
+	) -> Result<impl Stream<Item = Result<ServerMsg, ErrorType>>, ApiErrorResponse>
+	where
+		E: ApiEndpoint<RequestBody = WebSocketUpgrade<ServerMsg, ClientMsg>>,
+		<E::RequestBody as Preprocessable>::Processed: Send,
+		ServerMsg: DeserializeOwned,
+		ClientMsg: Serialize,
+	{
+		let mut url = self.endpoint_url::<E>(&path, &query)?;
+		let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+		url.set_scheme(scheme)
+			.map_err(|()| client_error(format!("cannot connect to `{url}` over a websocket")))?;
+		let mut headers = headers.to_header_map();
+
+		let mut renewed = false;
+		let stream = loop {
+			let access_token = self.authorize(&mut headers).await;
+
+			let mut request = url.as_str().into_client_request().map_err(client_error)?;
+			for (header, value) in headers.iter() {
+				request.headers_mut().insert(header.clone(), value.clone());
+			}
+			*request.method_mut() = E::METHOD;
+
+			let error = match tokio_tungstenite::connect_async(request).await {
+				Ok((stream, _)) => break stream,
+				Err(TungsteniteError::Http(response)) => {
+					let (parts, body) = response.into_parts();
+					let body = body.unwrap_or_default();
+					ApiErrorResponse {
+						status_code: parts.status,
+						body: serde_json::from_slice(&body).unwrap_or_else(|err| {
+							error!("Failed to parse error body: {}", err);
+							client_error(err).body
+						}),
+					}
+				}
+				Err(err) => return Err(client_error(err)),
+			};
+
+			match (&access_token, &error.body.error) {
+				(Some(access_token), ErrorType::AuthorizationTokenInvalid) if !renewed => {
+					self.renew_session(access_token).await?;
+					renewed = true;
+				}
+				_ => return Err(error),
+			}
+		};
+
+		Ok(stream.filter_map(|msg| async move {
+			match msg {
+				Ok(Message::Text(text)) => Some(
+					serde_json::from_str(&text)
+						.inspect_err(|err| warn!("Error parsing text as JSON: {}", err))
+						.map_err(ErrorType::server_error),
+				),
+				Ok(Message::Binary(bin)) => Some(
+					serde_json::from_slice(&bin)
+						.inspect_err(|err| {
+							warn!(
+								"Error parsing binary `{}` as JSON: {}",
+								String::from_utf8_lossy(&bin),
+								err
+							)
+						})
+						.map_err(ErrorType::server_error),
+				),
+				Ok(_) => None,
+				Err(err) => {
+					warn!("Error from websocket stream: {}", err);
+					Some(Err(ErrorType::server_error(err)))
+				}
+			}
+		}))
+	}
+}
+
+/// Send a streaming request to the API to listen for messages, using the
+/// default client. See [`ApiClient::stream_request`] for more details.
+pub async fn stream_request<E, ServerMsg, ClientMsg>(
+	request: ApiRequest<E>,
+) -> Result<impl Stream<Item = Result<ServerMsg, ErrorType>>, ApiErrorResponse>
+where
+	E: ApiEndpoint<RequestBody = WebSocketUpgrade<ServerMsg, ClientMsg>>,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+	ServerMsg: DeserializeOwned,
+	ClientMsg: Serialize,
+{
+	ApiClient::default_client().stream_request(request).await
+}
@@ -0,0 +1,200 @@
+use models::utils::{Headers, Multipart, RawBody};
+use preprocess::Preprocessable;
+use reqwest::{
+	multipart::{Form, Part},
+	Body,
+	RequestBuilder,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use url::form_urlencoded;
+
+use crate::{client::client_error, prelude::*, ApiClient};
+
+/// The body of an endpoint that takes a raw body, which can be uploaded with
+/// [`ApiClient::upload_raw`]
+pub trait RawUpload {}
+
+impl<const MAX_SIZE: u64> RawUpload for RawBody<MAX_SIZE> {}
+
+/// The body of an endpoint that takes a `multipart/form-data` form, which can
+/// be uploaded with [`ApiClient::upload_multipart`]
+pub trait MultipartUpload {
+	/// The fields of the form that are not files
+	type Fields: Serialize;
+}
+
+impl<T, const MAX_SIZE: u64> MultipartUpload for Multipart<T, MAX_SIZE>
+where
+	T: Serialize,
+{
+	type Fields = T;
+}
+
+impl ApiClient {
+	/// Upload a raw body to an endpoint that takes one, such as an archive or
+	/// a blob. The body can be streamed using [`Body::wrap_stream`], so that it
+	/// does not have to be buffered in memory.
+	///
+	/// The body can only be sent once, so the request is never retried. If the
+	/// request is made with the access token of the session and the token has
+	/// expired, the session is renewed and the error is returned, so that the
+	/// upload can be sent again with a new body.
+	///
+	/// ## Example
+	/// ```rust,ignore
+	/// let response = client
+	/// 	.upload_raw::<UploadArchiveRequest>(
+	/// 		UploadArchivePath { workspace_id },
+	/// 		(),
+	/// 		headers,
+	/// 		Body::wrap_stream(ReaderStream::new(file)),
+	/// 	)
+	/// 	.await?;
+	/// ```
+	pub async fn upload_raw<E>(
+		&self,
+		path: E::RequestPath,
+		query: E::RequestQuery,
+		headers: E::RequestHeaders,
+		body: Body,
+	) -> Result<ApiSuccessResponse<E>, ApiErrorResponse>
+	where
+		E: ApiEndpoint,
+		E::RequestBody: RawUpload,
+		<E::RequestBody as Preprocessable>::Processed: Send,
+		E::ResponseBody: DeserializeOwned,
+	{
+		self.upload::<E>(&path, &query, headers, |builder| builder.body(body))
+			.await
+	}
+
+	/// Upload a `multipart/form-data` form to an endpoint that takes one. The
+	/// fields of the form are sent first, followed by the files in the given
+	/// order, each under the name it is paired with. The contents of the files
+	/// can be streamed using [`Part::stream`], so that they do not have to be
+	/// buffered in memory.
+	///
+	/// The form can only be sent once, so the request is never retried. See
+	/// [`ApiClient::upload_raw`] for what happens when the access token of the
+	/// session has expired.
+	///
+	/// ## Example
+	/// ```rust,ignore
+	/// let response = client
+	/// 	.upload_multipart::<UploadStaticSiteRequest>(
+	/// 		UploadStaticSitePath {
+	/// 			workspace_id,
+	/// 			static_site_id,
+	/// 		},
+	/// 		(),
+	/// 		headers,
+	/// 		UploadStaticSiteRequestFields {
+	/// 			message: "v1.0.0".to_string(),
+	/// 		},
+	/// 		vec![(
+	/// 			"file".to_string(),
+	/// 			Part::stream(Body::wrap_stream(ReaderStream::new(file)))
+	/// 				.file_name("site.zip"),
+	/// 		)],
+	/// 	)
+	/// 	.await?;
+	/// ```
+	pub async fn upload_multipart<E>(
+		&self,
+		path: E::RequestPath,
+		query: E::RequestQuery,
+		headers: E::RequestHeaders,
+		fields: <E::RequestBody as MultipartUpload>::Fields,
+		files: Vec<(String, Part)>,
+	) -> Result<ApiSuccessResponse<E>, ApiErrorResponse>
+	where
+		E: ApiEndpoint,
+		E::RequestBody: MultipartUpload,
+		<E::RequestBody as Preprocessable>::Processed: Send,
+		E::ResponseBody: DeserializeOwned,
+	{
+		// The fields are sent the same way as a urlencoded form, which is how
+		// the API parses them
+		let fields = serde_urlencoded::to_string(&fields).map_err(client_error)?;
+		let form = form_urlencoded::parse(fields.as_bytes())
+			.into_owned()
+			.fold(Form::new(), |form, (name, value)| form.text(name, value));
+		let form = files
+			.into_iter()
+			.fold(form, |form, (name, file)| form.part(name, file));
+
+		self.upload::<E>(&path, &query, headers, |builder| builder.multipart(form))
+			.await
+	}
+
+	/// Sends an upload to an endpoint once, with the body set by `set_body`
+	async fn upload<E>(
+		&self,
+		path: &E::RequestPath,
+		query: &E::RequestQuery,
+		headers: E::RequestHeaders,
+		set_body: impl FnOnce(RequestBuilder) -> RequestBuilder,
+	) -> Result<ApiSuccessResponse<E>, ApiErrorResponse>
+	where
+		E: ApiEndpoint,
+		<E::RequestBody as Preprocessable>::Processed: Send,
+		E::ResponseBody: DeserializeOwned,
+	{
+		let url = self.endpoint_url::<E>(path, query)?;
+		let mut headers = headers.to_header_map();
+		let access_token = self.authorize(&mut headers).await;
+
+		let builder = set_body(self.request_builder(E::METHOD, url, headers));
+		let error = match self.execute::<E>(builder).await {
+			Ok(response) => return Ok(response),
+			Err(failure) => failure.into_error(),
+		};
+
+		if let (Some(access_token), ErrorType::AuthorizationTokenInvalid) =
+			(&access_token, &error.body.error)
+		{
+			self.renew_session(access_token).await?;
+		}
+
+		Err(error)
+	}
+}
+
+/// Upload a raw body to an endpoint, using the default client. See
+/// [`ApiClient::upload_raw`] for more details.
+pub async fn upload_raw<E>(
+	path: E::RequestPath,
+	query: E::RequestQuery,
+	headers: E::RequestHeaders,
+	body: Body,
+) -> Result<ApiSuccessResponse<E>, ApiErrorResponse>
+where
+	E: ApiEndpoint,
+	E::RequestBody: RawUpload,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+	E::ResponseBody: DeserializeOwned,
+{
+	ApiClient::default_client()
+		.upload_raw::<E>(path, query, headers, body)
+		.await
+}
+
+/// Upload a `multipart/form-data` form to an endpoint, using the default
+/// client. See [`ApiClient::upload_multipart`] for more details.
+pub async fn upload_multipart<E>(
+	path: E::RequestPath,
+	query: E::RequestQuery,
+	headers: E::RequestHeaders,
+	fields: <E::RequestBody as MultipartUpload>::Fields,
+	files: Vec<(String, Part)>,
+) -> Result<ApiSuccessResponse<E>, ApiErrorResponse>
+where
+	E: ApiEndpoint,
+	E::RequestBody: MultipartUpload,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+	E::ResponseBody: DeserializeOwned,
+{
+	ApiClient::default_client()
+		.upload_multipart::<E>(path, query, headers, fields, files)
+		.await
+}
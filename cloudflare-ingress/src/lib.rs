@@ -130,6 +130,14 @@ pub async fn main(req: Request, env: Env, ctx: Context) -> Result<Response> {
 			let cached_object = cache_store.get(&cache_key, true).await?;
 
 			if let Some(response) = cached_object {
+				if req.method() == Method::Get {
+					let bytes = response
+						.headers()
+						.get("content-length")?
+						.and_then(|length| length.parse().ok())
+						.unwrap_or_default();
+					report_static_site_bandwidth(&env, &ctx, &static_site_id, bytes)?;
+				}
 				return Ok(response);
 			}
 
@@ -355,6 +363,10 @@ pub async fn main(req: Request, env: Env, ctx: Context) -> Result<Response> {
 					let _ = cache_store.put(cache_key, cached_response).await;
 				});
 
+				if req.method() == Method::Get {
+					report_static_site_bandwidth(&env, &ctx, &static_site_id, file.size().into())?;
+				}
+
 				return Ok(response);
 			}
 
@@ -397,6 +409,50 @@ pub async fn main(req: Request, env: Env, ctx: Context) -> Result<Response> {
 	}
 }
 
+/// Reports the bytes served by a static site to the Patr API in the background,
+/// so that the workspace of the static site can be billed for its bandwidth.
+pub fn report_static_site_bandwidth(
+	env: &Env,
+	ctx: &Context,
+	static_site_id: &str,
+	bytes: u64,
+) -> Result<()> {
+	if bytes == 0 {
+		return Ok(());
+	}
+
+	let request = Request::new_with_init(
+		&format!(
+			"{}/static-site/{}/bandwidth",
+			constants::PATR_API_URL,
+			static_site_id
+		),
+		&RequestInit {
+			body: Some(format!("{{\"bytes\":{}}}", bytes).into()),
+			headers: {
+				let mut headers = Headers::new();
+
+				headers.set(
+					"authorization",
+					&format!("Bearer {}", env.secret(constants::INGRESS_API_TOKEN)?),
+				)?;
+				headers.set("content-type", "application/json")?;
+
+				headers
+			},
+			cf: CfProperties::new(),
+			method: Method::Post,
+			redirect: RequestRedirect::Follow,
+		},
+	)?;
+
+	ctx.wait_until(async move {
+		let _ = Fetch::Request(request).send().await;
+	});
+
+	Ok(())
+}
+
 /// Gets the path of the URL without the mount point. A request stripped of it's
 /// mount point will be made in the case of static sites since they are stored
 /// in a bucket with the mount point as the root.
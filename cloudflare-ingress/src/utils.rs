@@ -8,6 +8,13 @@ pub mod constants {
 	pub const INGRESS_KV: &str = "INGRESS_KV";
 	/// The cloudflare R2 bucket that stores all the static sites
 	pub const STATIC_SITE_BUCKET: &str = "STATIC_SITE_BUCKET";
+	/// The secret that stores the token used to report the bandwidth of static
+	/// sites to the Patr API. This must be the same as the ingress token in the
+	/// config of the API
+	pub const INGRESS_API_TOKEN: &str = "INGRESS_API_TOKEN";
+
+	/// The URL of the Patr API
+	pub const PATR_API_URL: &str = "https://api.patr.cloud";
 
 	/// The default status code for a temporary redirect
 	pub const STATUS_CODE_TEMPORAL_REDIRECT: u16 = 307;
@@ -23,7 +23,7 @@ pub async fn list_deployments(
 		ApiRequest::builder()
 			.path(ListDeploymentPath { workspace_id })
 			.query(Paginated {
-				data: (),
+				data: ListDeploymentQuery { project_id: None },
 				page: page.unwrap_or(0),
 				count: count.unwrap_or(10),
 			})
@@ -0,0 +1,44 @@
+use models::api::workspace::*;
+
+use crate::prelude::*;
+
+/// List the audit logs of a workspace, latest first
+#[server(ListAuditLogsFn, endpoint = "/workspace/audit-log/list")]
+pub async fn list_audit_logs(
+	access_token: Option<String>,
+	workspace_id: Uuid,
+	page: Option<usize>,
+	count: Option<usize>,
+) -> Result<(usize, ListAuditLogsResponse), ServerFnError<ErrorType>> {
+	use std::str::FromStr;
+
+	let access_token = access_token
+		.ok_or_else(|| ServerFnError::WrappedServerError(ErrorType::MalformedAccessToken))?;
+	let access_token = BearerToken::from_str(access_token.as_str())
+		.map_err(|_| ServerFnError::WrappedServerError(ErrorType::MalformedAccessToken))?;
+
+	make_api_call::<ListAuditLogsRequest>(
+		ApiRequest::builder()
+			.path(ListAuditLogsPath { workspace_id })
+			.query(Paginated {
+				data: ListAuditLogsQuery {
+					resource_id: None,
+					user_id: None,
+					action: None,
+					from: None,
+					to: None,
+				},
+				page: page.unwrap_or(0),
+				count: count.unwrap_or(10),
+			})
+			.headers(ListAuditLogsRequestHeaders {
+				authorization: access_token,
+				user_agent: UserAgent::from_static("todo"),
+			})
+			.body(ListAuditLogsRequest)
+			.build(),
+	)
+	.await
+	.map(|res| (res.headers.total_count.0, res.body))
+	.map_err(ServerFnError::WrappedServerError)
+}
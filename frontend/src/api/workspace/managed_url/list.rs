@@ -25,6 +25,7 @@ pub async fn list_managed_urls(
 					order: None,
 					order_by: None,
 					filter: None,
+					project_id: None,
 				},
 			})
 			.headers(ListManagedURLRequestHeaders {
@@ -3,6 +3,7 @@ mod database;
 mod deployment;
 mod domain;
 mod get_workspace_info;
+mod list_audit_logs;
 mod list_workspaces;
 mod managed_url;
 mod rbac;
@@ -14,6 +15,7 @@ pub use self::{
 	deployment::*,
 	domain::*,
 	get_workspace_info::*,
+	list_audit_logs::*,
 	list_workspaces::*,
 	managed_url::*,
 	rbac::*,
@@ -55,6 +55,7 @@ fn RunnerWorkspaceRoutes() -> impl IntoView {
 				<Route path={LoggedInRoute::Workspace} view={WorkspacePage}>
 					<Route path={AppRoutes::Empty} view={ManageWorkspace}>
 						<Route path="" view={ManageWorkspaceSettingsTab} />
+						<Route path="audit-logs" view={ManageWorkspaceAuditLogsTab} />
 					</Route>
 					<Route path="/create" view={CreateWorkspace} />
 				</Route>
@@ -28,6 +28,7 @@ pub async fn sign_up(
 				recovery_method: RecoveryMethod::Email {
 					recovery_email: email,
 				},
+				invite_token: None,
 			})
 			.build(),
 	)
@@ -43,6 +43,10 @@ pub fn ManageWorkspace() -> impl IntoView {
 					name: "Transactions".to_owned(),
 					path: "".to_owned(),
 				},
+				TabItem {
+					name: "Audit Logs".to_owned(),
+					path: "audit-logs".to_owned(),
+				},
 			]} />
 		</ContainerHead>
 
@@ -0,0 +1,83 @@
+use models::api::workspace::WorkspaceAuditLog;
+
+use crate::prelude::*;
+
+#[component]
+fn AuditLogCard(
+	/// The audit log to show
+	audit_log: WithId<WorkspaceAuditLog>,
+) -> impl IntoView {
+	view! {
+		<tr class="fr-ct-ct bg-secondary-light full-width bd-light br-bottom-sm row-card px-xl">
+			<td class="flex-col-3 fr-ct-ct">{audit_log.data.date.to_string()}</td>
+			<td class="flex-col-2 fr-ct-ct">{audit_log.data.action.clone()}</td>
+			<td class="flex-col-3 fr-ct-ct">{audit_log.data.resource_id.to_string()}</td>
+			<td class="flex-col-2 fr-ct-ct">{audit_log.data.ip_address.clone()}</td>
+			<td class="flex-col-2 fr-ct-ct">
+				{if audit_log.data.request_success {
+					view! { <p class="txt-success">"Success"</p> }
+				} else {
+					view! { <p class="txt-warning">"Failed"</p> }
+				}}
+			</td>
+		</tr>
+	}
+}
+
+#[component]
+pub fn ManageWorkspaceAuditLogsTab() -> impl IntoView {
+	let (state, _) = AuthState::load();
+
+	let audit_logs = create_resource(
+		move || {
+			(
+				state.get().get_access_token(),
+				state.get().get_last_used_workspace_id(),
+			)
+		},
+		move |(access_token, workspace_id)| async move {
+			match workspace_id {
+				Some(workspace_id) => list_audit_logs(access_token, workspace_id, None, None).await,
+				None => Err(ServerFnError::WrappedServerError(
+					ErrorType::WrongParameters,
+				)),
+			}
+		},
+	);
+
+	view! {
+		<div class="flex flex-col items-start justify-start w-full h-full fit-wide-screen mx-auto px-md my-xl">
+			<TableDashboard
+				column_grids={vec![3, 2, 3, 2, 2]}
+				headings={vec![
+					"Date".into_view(),
+					"Action".into_view(),
+					"Resource".into_view(),
+					"IP Address".into_view(),
+					"Status".into_view(),
+				]}
+
+				render_rows={view! {
+					<Transition>
+						{move || match audit_logs.get() {
+							Some(Ok((_, data))) => {
+								view! {
+									<For
+										each={move || data.audit_logs.clone()}
+										key={|state| state.id}
+										let:audit_log
+									>
+										<AuditLogCard audit_log={audit_log} />
+									</For>
+								}
+									.into_view()
+							}
+							_ => view! {}.into_view(),
+						}}
+					</Transition>
+				}
+					.into_view()}
+			/>
+		</div>
+	}
+}
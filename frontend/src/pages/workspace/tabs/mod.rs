@@ -1,3 +1,4 @@
+mod audit_logs;
 mod settings;
 
-pub use self::settings::*;
+pub use self::{audit_logs::*, settings::*};
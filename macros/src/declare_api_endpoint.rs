@@ -1,8 +1,10 @@
 use proc_macro::TokenStream;
 use quote::format_ident;
 use syn::{
+	parenthesized,
 	parse::{Parse, ParseStream},
 	parse_macro_input,
+	token::Brace,
 	Attribute,
 	Block,
 	Error,
@@ -15,6 +17,8 @@ use syn::{
 	Token,
 };
 
+use crate::endpoint_documentation::{EndpointDocumentation, EndpointRequest, EndpointResponse};
+
 /// A helper struct to parse an API endpoint
 pub struct ApiEndpoint {
 	/// The documentation for the API endpoint. This is used for all the
@@ -31,6 +35,8 @@ pub struct ApiEndpoint {
 	path_body: Option<FieldsNamed>,
 	/// The authentication for this endpoint.
 	auth: Option<Block>,
+	/// The rate limits for this endpoint.
+	rate_limit: Option<Block>,
 	/// Should this route be allowed through APIs or only through the web-login
 	api_allowed: bool,
 
@@ -39,7 +45,7 @@ pub struct ApiEndpoint {
 	/// Whether the query is paginated or not.
 	paginate_query: Option<bool>,
 	/// The body of the request.
-	request: Option<FieldsNamed>,
+	request: Option<RequestBody>,
 	/// The required request headers for the endpoint.
 	request_headers: Option<FieldsNamed>,
 
@@ -49,6 +55,60 @@ pub struct ApiEndpoint {
 	response: Option<FieldsNamed>,
 }
 
+/// The body of a request to an API endpoint
+enum RequestBody {
+	/// A JSON body with the given fields, declared as `request = { ... }`
+	Json(FieldsNamed),
+	/// A raw stream of bytes, declared as `request = raw(max_size = ...)`
+	Raw {
+		/// The maximum size of the body, in bytes
+		max_size: Expr,
+	},
+	/// A `multipart/form-data` body, declared as
+	/// `request = multipart(max_size = ...) { ... }`. The fields are the
+	/// fields of the form that are not files, and can be left out.
+	Multipart {
+		/// The maximum size of the whole body, in bytes
+		max_size: Expr,
+		/// The fields of the form that are not files
+		fields: Option<FieldsNamed>,
+	},
+}
+
+impl Parse for RequestBody {
+	fn parse(input: ParseStream) -> Result<Self, Error> {
+		if input.peek(Brace) {
+			return Ok(Self::Json(input.parse()?));
+		}
+
+		let kind = input.parse::<Ident>()?;
+		let content;
+		parenthesized!(content in input);
+		let option = content.parse::<Ident>()?;
+		if option != "max_size" {
+			return Err(Error::new(option.span(), "Expected `max_size`"));
+		}
+		content.parse::<Token![=]>()?;
+		let max_size = content.parse()?;
+
+		match kind.to_string().as_str() {
+			"raw" => Ok(Self::Raw { max_size }),
+			"multipart" => Ok(Self::Multipart {
+				max_size,
+				fields: if input.peek(Brace) {
+					Some(input.parse()?)
+				} else {
+					None
+				},
+			}),
+			_ => Err(Error::new(
+				kind.span(),
+				"Expected a request body, `raw(...)` or `multipart(...)`",
+			)),
+		}
+	}
+}
+
 impl Parse for ApiEndpoint {
 	fn parse(input: ParseStream) -> Result<Self, Error> {
 		let meta = Attribute::parse_outer(input)?
@@ -84,6 +144,7 @@ impl Parse for ApiEndpoint {
 		};
 
 		let mut auth = None;
+		let mut rate_limit = None;
 		let mut query = None;
 		let mut paginate_query = None;
 		let mut request = None;
@@ -155,6 +216,14 @@ impl Parse for ApiEndpoint {
 
 					auth = Some(input.parse()?);
 				}
+				"rate_limit" => {
+					if rate_limit.is_some() {
+						return Err(Error::new(ident.span(), "Duplicate field"));
+					}
+					input.parse::<Token![=]>()?;
+
+					rate_limit = Some(input.parse()?);
+				}
 				"api" => {
 					if api_allowed.is_some() {
 						return Err(Error::new(ident.span(), "Duplicate field"));
@@ -180,6 +249,7 @@ impl Parse for ApiEndpoint {
 			path,
 			path_body,
 			auth,
+			rate_limit,
 			api_allowed,
 
 			query,
@@ -206,6 +276,7 @@ pub fn parse(input: TokenStream) -> TokenStream {
 		api_allowed,
 
 		auth,
+		rate_limit,
 		query,
 		paginate_query,
 		request_headers,
@@ -215,6 +286,32 @@ pub fn parse(input: TokenStream) -> TokenStream {
 		response,
 	} = parse_macro_input!(input as ApiEndpoint);
 
+	let documentation_impl = match (EndpointDocumentation {
+		name: &name,
+		documentation: &documentation,
+		path_body: path_body.as_ref(),
+		auth: auth.as_ref(),
+		query: query.as_ref(),
+		paginated: paginate_query.unwrap_or(false),
+		request_headers: request_headers.as_ref(),
+		request: match &request {
+			None => EndpointRequest::None,
+			Some(RequestBody::Json(fields)) => EndpointRequest::Json(fields),
+			Some(RequestBody::Raw { max_size }) => EndpointRequest::Raw { max_size },
+			Some(RequestBody::Multipart { max_size, fields }) => EndpointRequest::Multipart {
+				fields: fields.as_ref(),
+				max_size,
+			},
+		},
+		response_headers: response_headers.as_ref(),
+		response: EndpointResponse::Json(response.as_ref()),
+	})
+	.documentation_impl()
+	{
+		Ok(documentation_impl) => documentation_impl,
+		Err(err) => return err.into_compile_error().into(),
+	};
+
 	let (path_default_impl, path_body) = if let Some(body) = path_body {
 		(
 			quote::quote! {},
@@ -235,13 +332,114 @@ pub fn parse(input: TokenStream) -> TokenStream {
 	let path_name = format_ident!("{}Path", name);
 
 	let request_name = format_ident!("{}Request", name);
-	let request_body = if let Some(body) = request {
-		quote::quote! {
-			#body
+	let request_fields_name = format_ident!("{}RequestFields", name);
+	let (request_decl, request_body_type) = match request {
+		Some(RequestBody::Raw { max_size }) => (
+			quote::quote! {
+				/// The request for the #name endpoint. The body of the request is
+				/// streamed as raw bytes.
+				///
+				/// The documentation for the endpoint is below:
+				///
+				#[doc = #documentation]
+				#[derive(
+					Copy,
+					Debug,
+					Clone,
+					PartialEq,
+				)]
+				pub struct #request_name;
+			},
+			quote::quote! {
+				models::utils::RawBody<{ #max_size }>
+			},
+		),
+		Some(RequestBody::Multipart { max_size, fields }) => {
+			let fields = fields
+				.map(|fields| {
+					quote::quote! {
+						#fields
+					}
+				})
+				.unwrap_or_else(|| {
+					quote::quote! {
+						{}
+					}
+				});
+			(
+				quote::quote! {
+					/// The request for the #name endpoint. The body of the request is a
+					/// `multipart/form-data` form, whose files are streamed.
+					///
+					/// The documentation for the endpoint is below:
+					///
+					#[doc = #documentation]
+					#[derive(
+						Copy,
+						Debug,
+						Clone,
+						PartialEq,
+					)]
+					pub struct #request_name;
+
+					#[::preprocess::sync]
+					/// The fields of the `multipart/form-data` form of the #name
+					/// endpoint that are not files.
+					///
+					/// The documentation for the endpoint is below:
+					///
+					#[doc = #documentation]
+					#[derive(
+						Debug,
+						Clone,
+						PartialEq,
+						serde::Serialize,
+						serde::Deserialize,
+					)]
+					#[serde(rename_all = "camelCase")]
+					pub struct #request_fields_name #fields
+				},
+				quote::quote! {
+					models::utils::Multipart<#request_fields_name, { #max_size }>
+				},
+			)
 		}
-	} else {
-		quote::quote! {
-			;
+		request => {
+			let request_body = if let Some(RequestBody::Json(body)) = request {
+				quote::quote! {
+					#body
+				}
+			} else {
+				quote::quote! {
+					;
+				}
+			};
+			(
+				quote::quote! {
+					#[::preprocess::sync]
+					/// The request body for the #name endpoint
+					///
+					/// The documentation for the endpoint is below:
+					///
+					#[doc = #documentation]
+					#[derive(
+						Debug,
+						Clone,
+						PartialEq,
+						serde::Serialize,
+						serde::Deserialize,
+					)]
+					#[serde(rename_all = "camelCase")]
+					pub struct #request_name #request_body
+
+					impl models::utils::RequiresResponseHeaders for #request_name {
+						type RequiredResponseHeaders = ();
+					}
+				},
+				quote::quote! {
+					Self
+				},
+			)
 		}
 	};
 
@@ -291,6 +489,16 @@ pub fn parse(input: TokenStream) -> TokenStream {
 		quote::quote!()
 	};
 
+	let rate_limit_impl = rate_limit
+		.map(|block| {
+			quote::quote! {
+				fn get_rate_limit() -> Option<models::utils::RateLimit<Self>> {
+					Some(#block)
+				}
+			}
+		})
+		.unwrap_or_default();
+
 	let (auth_type, auth_impl) = auth
 		.map(|block| {
 			(
@@ -407,25 +615,7 @@ pub fn parse(input: TokenStream) -> TokenStream {
 			type RequiredResponseHeaders = ();
 		}
 
-		#[::preprocess::sync]
-		/// The request body for the #name endpoint
-		///
-		/// The documentation for the endpoint is below:
-		///
-		#[doc = #documentation]
-		#[derive(
-			Debug,
-			Clone,
-			PartialEq,
-			serde::Serialize,
-			serde::Deserialize,
-		)]
-		#[serde(rename_all = "camelCase")]
-		pub struct #request_name #request_body
-
-		impl models::utils::RequiresResponseHeaders for #request_name {
-			type RequiredResponseHeaders = ();
-		}
+		#request_decl
 
 		#query_decl
 
@@ -457,19 +647,24 @@ pub fn parse(input: TokenStream) -> TokenStream {
 		}
 
 		impl models::ApiEndpoint for #request_name {
+			const NAME: &'static str = ::core::stringify!(#name);
 			const METHOD: ::http::Method = ::http::Method::#method;
 			const API_ALLOWED: bool = #api_allowed;
 
 			type RequestPath = #path_name;
 			type RequestQuery = #query_name;
 			type RequestHeaders = #request_headers_name;
-			type RequestBody = Self;
+			type RequestBody = #request_body_type;
 			type Authenticator = models::utils::#auth_type;
 
 			#auth_impl
 
+			#rate_limit_impl
+
 			type ResponseHeaders = #response_headers_name;
 			type ResponseBody = #response_name;
+
+			#documentation_impl
 		}
 	}
 	.into()
@@ -17,6 +17,8 @@ use syn::{
 	Variant,
 };
 
+use crate::endpoint_documentation::{EndpointDocumentation, EndpointRequest, EndpointResponse};
+
 /// A helper struct to parse an API endpoint
 pub struct ApiEndpoint {
 	/// The documentation for the API endpoint. This is used for all the
@@ -231,6 +233,24 @@ pub fn parse(input: TokenStream) -> TokenStream {
 		client_msg,
 	} = parse_macro_input!(input as ApiEndpoint);
 
+	let documentation_impl = match (EndpointDocumentation {
+		name: &name,
+		documentation: &documentation,
+		path_body: path_body.as_ref(),
+		auth: auth.as_ref(),
+		query: query.as_ref(),
+		paginated: paginate_query.unwrap_or(false),
+		request_headers: request_headers.as_ref(),
+		request: EndpointRequest::None,
+		response_headers: response_headers.as_ref(),
+		response: EndpointResponse::WebSocket,
+	})
+	.documentation_impl()
+	{
+		Ok(documentation_impl) => documentation_impl,
+		Err(err) => return err.into_compile_error().into(),
+	};
+
 	let (path_default_impl, path_body) = if let Some(body) = path_body {
 		(
 			quote::quote! {},
@@ -478,6 +498,7 @@ pub fn parse(input: TokenStream) -> TokenStream {
 		}
 
 		impl models::ApiEndpoint for #request_name {
+			const NAME: &'static str = ::core::stringify!(#name);
 			const METHOD: ::http::Method = ::http::Method::#method;
 			const API_ALLOWED: bool = #api_allowed;
 
@@ -491,6 +512,8 @@ pub fn parse(input: TokenStream) -> TokenStream {
 
 			type ResponseHeaders = #response_headers_name;
 			type ResponseBody = models::utils::GenericResponse;
+
+			#documentation_impl
 		}
 	}
 	.into()
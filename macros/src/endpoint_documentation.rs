@@ -0,0 +1,326 @@
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{
+	Attribute,
+	Block,
+	Error,
+	Expr,
+	ExprLit,
+	Field,
+	FieldsNamed,
+	Ident,
+	Lit,
+	LitStr,
+	Meta,
+	MetaNameValue,
+	Token,
+	Type,
+	TypePath,
+};
+
+/// The parts of the declaration of an endpoint that are documented
+pub struct EndpointDocumentation<'a> {
+	/// The name of the endpoint
+	pub name: &'a Ident,
+	/// The documentation comment of the endpoint
+	pub documentation: &'a str,
+	/// The parameters in the path of the endpoint
+	pub path_body: Option<&'a FieldsNamed>,
+	/// The block that creates the authenticator of the endpoint, if any
+	pub auth: Option<&'a Block>,
+	/// The query params of the endpoint
+	pub query: Option<&'a FieldsNamed>,
+	/// Whether the query is paginated or not
+	pub paginated: bool,
+	/// The required request headers of the endpoint
+	pub request_headers: Option<&'a FieldsNamed>,
+	/// The body of the request, if the endpoint takes one
+	pub request: EndpointRequest<'a>,
+	/// The required response headers of the endpoint
+	pub response_headers: Option<&'a FieldsNamed>,
+	/// What the endpoint responds with
+	pub response: EndpointResponse<'a>,
+}
+
+/// What the body of a request to an endpoint is
+pub enum EndpointRequest<'a> {
+	/// The endpoint does not take a body
+	None,
+	/// A JSON body with the given fields
+	Json(&'a FieldsNamed),
+	/// A raw stream of bytes, of at most `max_size` bytes
+	Raw {
+		/// The maximum size of the body, in bytes
+		max_size: &'a Expr,
+	},
+	/// A `multipart/form-data` body with the given fields, followed by files
+	Multipart {
+		/// The fields of the form that are not files
+		fields: Option<&'a FieldsNamed>,
+		/// The maximum size of the whole body, in bytes
+		max_size: &'a Expr,
+	},
+}
+
+/// What an endpoint responds with
+pub enum EndpointResponse<'a> {
+	/// A JSON body with the given fields
+	Json(Option<&'a FieldsNamed>),
+	/// A websocket, for streaming endpoints
+	WebSocket,
+}
+
+impl EndpointDocumentation<'_> {
+	/// Generates the implementation of `ApiEndpoint::documentation` for the
+	/// endpoint
+	pub fn documentation_impl(&self) -> Result<TokenStream2, Error> {
+		let name = self.name.to_string();
+		let documentation = self.documentation.trim();
+		let authentication = authentication(self.auth);
+		// Path parameters are not renamed, since they have to match the path
+		let path = fields(self.path_body, false)?;
+		let query = fields(self.query, true)?;
+		let paginated = self.paginated;
+		let request_headers = headers(self.request_headers);
+		let request_body = match self.request {
+			EndpointRequest::None => quote::quote! {
+				None
+			},
+			EndpointRequest::Json(request) => {
+				let fields = fields(Some(request), true)?;
+				quote::quote! {
+					Some(models::utils::RequestBodyDocumentation::Json(#fields))
+				}
+			}
+			EndpointRequest::Raw { max_size } => quote::quote! {
+				Some(models::utils::RequestBodyDocumentation::Raw {
+					max_size: #max_size,
+				})
+			},
+			EndpointRequest::Multipart { fields: request, max_size } => {
+				let fields = fields(request, true)?;
+				quote::quote! {
+					Some(models::utils::RequestBodyDocumentation::Multipart {
+						fields: #fields,
+						max_size: #max_size,
+					})
+				}
+			}
+		};
+		let response_headers = headers(self.response_headers);
+		let response = match self.response {
+			EndpointResponse::Json(response) => {
+				let fields = fields(response, true)?;
+				quote::quote! {
+					models::utils::ResponseDocumentation::Json(#fields)
+				}
+			}
+			EndpointResponse::WebSocket => quote::quote! {
+				models::utils::ResponseDocumentation::WebSocket
+			},
+		};
+
+		Ok(quote::quote! {
+			#[allow(unused_imports, unused_variables)]
+			fn documentation(
+				generator: &mut models::utils::SchemaGenerator,
+			) -> models::utils::EndpointDocumentation {
+				use models::utils::{SchemaFromForeignType as _, SchemaFromJsonSchema as _};
+
+				models::utils::EndpointDocumentation {
+					name: #name,
+					description: #documentation,
+					authentication: #authentication,
+					path: #path,
+					query: #query,
+					paginated: #paginated,
+					request_headers: #request_headers,
+					request_body: #request_body,
+					response_headers: #response_headers,
+					response: #response,
+				}
+			}
+		})
+	}
+}
+
+/// The serde attributes of a field that change how the field is documented
+#[derive(Default)]
+struct SerdeFieldAttributes {
+	/// The name the field is renamed to, if any
+	rename: Option<String>,
+	/// Whether the fields of the field are flattened into the parent struct
+	flatten: bool,
+	/// Whether the field can be left out, either because it has a default value
+	/// or because it is skipped when serializing
+	optional: bool,
+}
+
+/// Generates an expression that evaluates to the documentation of the given
+/// fields, as a `Vec<models::utils::FieldDocumentation>`. This expects a
+/// `generator` variable of type `&mut models::utils::SchemaGenerator` and the
+/// `SchemaFromForeignType` and `SchemaFromJsonSchema` traits to be in scope.
+///
+/// If `camel_case` is true, the names of the fields are converted to camelCase,
+/// the same way `#[serde(rename_all = "camelCase")]` does.
+fn fields(fields: Option<&FieldsNamed>, camel_case: bool) -> Result<TokenStream2, Error> {
+	let fields = fields
+		.into_iter()
+		.flat_map(|fields| &fields.named)
+		.map(|field| {
+			let Field {
+				attrs, ident, ty, ..
+			} = field;
+			let SerdeFieldAttributes {
+				rename,
+				flatten,
+				optional,
+			} = serde_field_attributes(attrs)?;
+
+			let name = rename.unwrap_or_else(|| {
+				let name = ident.as_ref().map(ToString::to_string).unwrap_or_default();
+				let name = name.trim_start_matches("r#");
+				if camel_case {
+					to_camel_case(name)
+				} else {
+					name.to_string()
+				}
+			});
+			let description = documentation(attrs);
+			let required = !(optional || flatten || is_option(ty));
+
+			Ok(quote::quote! {
+				models::utils::FieldDocumentation {
+					name: #name.to_string(),
+					description: #description.to_string(),
+					required: #required,
+					flatten: #flatten,
+					schema: (&&models::utils::SchemaOf::<#ty>::new()).schema(generator),
+				}
+			})
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
+
+	Ok(quote::quote! {
+		vec![#(#fields),*]
+	})
+}
+
+/// Generates an expression that evaluates to the documentation of the given
+/// header fields, as a `Vec<models::utils::HeaderDocumentation>`. The name of
+/// each header is taken from its [`headers::Header`] implementation.
+fn headers(fields: Option<&FieldsNamed>) -> TokenStream2 {
+	let headers =
+		fields
+			.into_iter()
+			.flat_map(|fields| &fields.named)
+			.map(|Field { attrs, ty, .. }| {
+				let description = documentation(attrs);
+				quote::quote! {
+					models::utils::HeaderDocumentation {
+						name: <#ty as ::headers::Header>::name().as_str(),
+						description: #description.to_string(),
+					}
+				}
+			});
+
+	quote::quote! {
+		vec![#(#headers),*]
+	}
+}
+
+/// Generates an expression that evaluates to the
+/// `models::utils::EndpointAuthentication` of an endpoint, from the block that
+/// creates its authenticator, if any.
+fn authentication(auth: Option<&Block>) -> TokenStream2 {
+	match auth {
+		Some(block) => quote::quote! {
+			{
+				let authenticator: models::utils::AppAuthentication<Self> = #block;
+				models::utils::EndpointAuthentication::from(authenticator)
+			}
+		},
+		None => quote::quote! {
+			models::utils::EndpointAuthentication::None
+		},
+	}
+}
+
+/// Joins the documentation comments in the given attributes into a single
+/// string, without the leading space of each line
+fn documentation(attrs: &[Attribute]) -> String {
+	attrs
+		.iter()
+		.filter_map(|attr| {
+			let Meta::NameValue(MetaNameValue {
+				path,
+				value: Expr::Lit(ExprLit {
+					lit: Lit::Str(lit), ..
+				}),
+				..
+			}) = &attr.meta
+			else {
+				return None;
+			};
+			path.is_ident("doc").then(|| lit.value())
+		})
+		.map(|line| line.strip_prefix(' ').map(str::to_string).unwrap_or(line))
+		.collect::<Vec<_>>()
+		.join("\n")
+		.trim()
+		.to_string()
+}
+
+/// Parses the serde attributes of a field that change how the field is
+/// documented. All the other serde attributes are ignored.
+fn serde_field_attributes(attrs: &[Attribute]) -> Result<SerdeFieldAttributes, Error> {
+	let mut attributes = SerdeFieldAttributes::default();
+
+	for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+		attr.parse_nested_meta(|meta| {
+			if meta.path.is_ident("rename") {
+				attributes.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+			} else if meta.path.is_ident("flatten") {
+				attributes.flatten = true;
+			} else if meta.path.is_ident("default") || meta.path.is_ident("skip_serializing_if") {
+				attributes.optional = true;
+				if meta.input.peek(Token![=]) {
+					meta.value()?.parse::<Expr>()?;
+				}
+			} else if meta.input.peek(Token![=]) {
+				meta.value()?.parse::<Expr>()?;
+			} else if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+				meta.parse_nested_meta(|_| Ok(()))?;
+			}
+			Ok(())
+		})?;
+	}
+
+	Ok(attributes)
+}
+
+/// Checks if a type is an [`Option`], in which case the field can be left out
+fn is_option(ty: &Type) -> bool {
+	let Type::Path(TypePath { path, .. }) = ty else {
+		return false;
+	};
+	path.segments
+		.last()
+		.is_some_and(|segment| segment.ident == "Option")
+}
+
+/// Converts a snake_case name to camelCase
+fn to_camel_case(name: &str) -> String {
+	let mut capitalize = false;
+	name.chars()
+		.fold(String::with_capacity(name.len()), |mut camel_case, ch| {
+			if ch == '_' {
+				capitalize = true;
+			} else if capitalize {
+				camel_case.extend(ch.to_uppercase());
+				capitalize = false;
+			} else {
+				camel_case.push(ch);
+			}
+			camel_case
+		})
+}
@@ -15,6 +15,10 @@ mod declare_app_route;
 /// The proc macro for declaring a streaming endpoint. A streaming endpoint is
 /// basically a websocket endpoint.
 mod declare_stream_endpoint;
+/// Generates the documentation of the endpoints declared by
+/// [`declare_api_endpoint`] and [`declare_stream_endpoint`], which is used to
+/// build the OpenAPI document of the API.
+mod endpoint_documentation;
 /// A derive macro for the `HasHeaders` trait.
 mod has_headers;
 /// A proc macro for stripping whitespaces and newlines from SQL queries.
@@ -33,8 +37,9 @@ mod version;
 ///
 /// This macro allows easy definition of an API endpoint along with the request
 /// URL, headers, query, body as well as the response headers and body.
-/// Generates the required structs for the endpoint. Currently only supports
-/// JSON endpoints.
+/// Generates the required structs for the endpoint. The request body can be
+/// JSON, a raw stream of bytes (`models::utils::RawBody`) or a
+/// `multipart/form-data` form with files (`models::utils::Multipart`).
 ///
 /// ## Example usage:
 /// ```rust
@@ -57,6 +62,8 @@ mod version;
 ///         pub header1: AcceptRanges,
 ///         pub token: BearerToken,
 ///     },
+///     // Can also be `raw(max_size = ...)` to stream the body as bytes, or
+///     // `multipart(max_size = ...) { ... }` for a form with files
 ///     request = {
 ///         pub body_param1: String,
 ///     },
@@ -67,6 +74,15 @@ mod version;
 ///             extract_workspace_id: |req| req.path.workspace_id,
 ///         }
 ///     },
+///     // Ref: RateLimit
+///     rate_limit = {
+///         RateLimit::<Self> {
+///             bucket: "endpointName",
+///             max_requests_per_ip: 30,
+///             max_failures_per_account: 5,
+///             extract_account: Some(|req| req.body.body_param1.clone()),
+///         }
+///     },
 ///     response_headers = {
 ///         pub header1: AcceptRanges,
 ///     },
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -11,7 +12,7 @@ use crate::{
 /// Recovery method options provided to the user when they forget their
 /// passsword and request a password change by hitting the ForgetPassword API
 /// endpoint. The curent recovery options are email and phone number.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(untagged)]
 #[preprocess::sync]
 pub enum RecoveryMethod {
@@ -68,5 +69,10 @@ macros::declare_api_endpoint!(
 		/// The recovery method the user would recover their account with
 		#[serde(flatten)]
 		pub recovery_method: RecoveryMethod,
+		/// The signed token of an invite to a workspace, if the user is signing up from an
+		/// invite link. The invite is accepted once the sign up is completed.
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		#[preprocess(optional(trim, length(min = 1)))]
+		pub invite_token: Option<String>,
 	},
 );
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::prelude::*;
@@ -7,7 +8,7 @@ use crate::prelude::*;
 /// When they forget their password and request a password change by hitting the
 /// ForgetPassword API endpoint, these are the options presented to them. The
 /// current recovery options are email and phone number.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum PreferredRecoveryOption {
 	/// Send OTP to phone number
@@ -26,6 +27,14 @@ macros::declare_api_endpoint!(
 		/// The user-agent used to access this API
 		pub user_agent: UserAgent,
 	},
+	rate_limit = {
+		RateLimit::<Self> {
+			bucket: "forgotPassword",
+			max_requests_per_ip: 10,
+			max_failures_per_account: 5,
+			extract_account: Some(|req| req.body.user_id.clone()),
+		}
+	},
 	request = {
 		/// The user identifier. It can either be a username or an email ID
 		/// depending on what user enters
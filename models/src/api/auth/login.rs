@@ -13,6 +13,14 @@ macros::declare_api_endpoint!(
 		/// The user-agent used to access this API
 		pub user_agent: UserAgent,
 	},
+	rate_limit = {
+		RateLimit::<Self> {
+			bucket: "login",
+			max_requests_per_ip: 20,
+			max_failures_per_account: 5,
+			extract_account: Some(|req| req.body.user_id.clone()),
+		}
+	},
 	request = {
 		/// The user identifier of the user
 		/// It can be either the username or the email of the user depending on the user input
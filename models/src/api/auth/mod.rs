@@ -1,5 +1,7 @@
 /// All OAuth related endpoints go here.
 pub mod oauth;
+/// All endpoints to sign in with an external OpenID Connect provider go here.
+pub mod oidc;
 
 /// The endpoint to complete the sign up process
 mod complete_sign_up;
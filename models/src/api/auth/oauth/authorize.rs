@@ -1,9 +1,10 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::prelude::*;
 
 /// The type of request that the third-party app is making.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum OAuthAuthorizeResponseType {
 	/// The third-party app is requesting a temporary authorization code.
@@ -13,7 +14,7 @@ pub enum OAuthAuthorizeResponseType {
 }
 
 /// The method used to hash the code challenge.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum CodeChallengeHashMethod {
 	/// The code challenge is hashed using the SHA-256 algorithm.
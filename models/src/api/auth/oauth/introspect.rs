@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
@@ -7,7 +8,7 @@ use crate::{
 };
 
 /// The response from the OAuthIntrospect endpoint
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 #[serde(rename_all = "camelCase", untagged)]
 pub enum OAuthIntrospectResponseType {
 	/// If the access token is valid
@@ -23,6 +24,7 @@ pub enum OAuthIntrospectResponseType {
 		scope: String,
 		/// The expiry time of the access token
 		#[serde(with = "time::serde::timestamp")]
+		#[schemars(with = "i64")]
 		expires_at: OffsetDateTime,
 	},
 	/// If the access token is invalid
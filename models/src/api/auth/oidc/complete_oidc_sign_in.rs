@@ -0,0 +1,43 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to complete signing in with an external OpenID Connect provider. The code is
+	/// exchanged with the provider for an ID token, which is used to find the user to sign
+	/// in. If no user is linked to the identity yet, it is linked to the user with the same
+	/// verified email, or a new user is created if the provider allows sign ups.
+	CompleteOidcSignIn,
+	POST "/auth/sso/:provider_id/callback" {
+		/// The ID of the provider that the sign in was started with
+		pub provider_id: String,
+	},
+	api = false,
+	request_headers = {
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	rate_limit = {
+		RateLimit::<Self> {
+			bucket: "oidc",
+			max_requests_per_ip: 20,
+			max_failures_per_account: 5,
+			extract_account: None,
+		}
+	},
+	request = {
+		/// The authorization code that the provider redirected the user back with
+		#[preprocess(trim, length(min = 1))]
+		pub code: String,
+		/// The state that the provider redirected the user back with
+		#[preprocess(trim, length(min = 1))]
+		pub state: String,
+	},
+	response = {
+		/// The access token is used to authenticate the user, implying that the user is logged in
+		/// once the route is completed successfully.
+		pub access_token: String,
+		/// The access token has a expiry, and the refresh token (below) is used to
+		/// renew the access token.
+		/// It contains the login_id and the refresh_token concatenated together.
+		pub refresh_token: String,
+	}
+);
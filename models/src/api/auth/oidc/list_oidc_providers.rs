@@ -0,0 +1,17 @@
+use super::OidcProvider;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to list all the external OpenID Connect providers that a user can sign in with
+	ListOidcProviders,
+	GET "/auth/sso",
+	api = false,
+	request_headers = {
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	response = {
+		/// The list of providers that are configured
+		pub providers: Vec<OidcProvider>,
+	}
+);
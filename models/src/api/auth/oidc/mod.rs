@@ -0,0 +1,22 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The endpoint to complete a sign in with an external OpenID Connect provider
+mod complete_oidc_sign_in;
+/// The endpoint to list the external OpenID Connect providers that can be used
+/// to sign in
+mod list_oidc_providers;
+/// The endpoint to start a sign in with an external OpenID Connect provider
+mod start_oidc_sign_in;
+
+pub use self::{complete_oidc_sign_in::*, list_oidc_providers::*, start_oidc_sign_in::*};
+
+/// An external OpenID Connect provider that users can sign in with
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcProvider {
+	/// The ID of the provider. This is used in the URLs of the sign in flow.
+	pub id: String,
+	/// The name of the provider, as shown to the user on the sign in page
+	pub name: String,
+}
@@ -0,0 +1,22 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to start signing in with an external OpenID Connect provider. This returns the
+	/// URL of the provider that the user should be redirected to. Once the user has
+	/// authorized the sign in, the provider redirects them back to the frontend with a
+	/// code and a state, which are then sent to the callback route to complete the sign in.
+	StartOidcSignIn,
+	GET "/auth/sso/:provider_id/authorize" {
+		/// The ID of the provider to sign in with
+		pub provider_id: String,
+	},
+	api = false,
+	request_headers = {
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	response = {
+		/// The URL of the provider that the user should be redirected to
+		pub authorization_url: String,
+	}
+);
@@ -16,6 +16,14 @@ macros::declare_api_endpoint!(
 		/// The user-agent used to access this API
 		pub user_agent: UserAgent,
 	},
+	rate_limit = {
+		RateLimit::<Self> {
+			bucket: "resetPassword",
+			max_requests_per_ip: 10,
+			max_failures_per_account: 5,
+			extract_account: Some(|req| req.body.user_id.clone()),
+		}
+	},
 	request = {
 		/// The user ID of the user
 		#[preprocess(trim, length(min = 2), regex = USERNAME_VALIDITY_REGEX)]
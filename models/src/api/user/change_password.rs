@@ -17,6 +17,14 @@ macros::declare_api_endpoint!(
 	authentication = {
 		AppAuthentication::<Self>::PlainTokenAuthenticator
 	},
+	rate_limit = {
+		RateLimit::<Self> {
+			bucket: "changePassword",
+			max_requests_per_ip: 20,
+			max_failures_per_account: 5,
+			extract_account: None,
+		}
+	},
 	request = {
 		/// The current password of the user.
 		#[preprocess(trim, length(min = 8), custom = "validate_password")]
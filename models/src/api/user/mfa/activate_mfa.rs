@@ -11,6 +11,14 @@ macros::declare_api_endpoint!(
 	authentication = {
 		AppAuthentication::<Self>::PlainTokenAuthenticator
 	},
+	rate_limit = {
+		RateLimit::<Self> {
+			bucket: "mfa",
+			max_requests_per_ip: 20,
+			max_failures_per_account: 5,
+			extract_account: None,
+		}
+	},
 	request = {
 		/// The one time password to activate mfa
 		#[preprocess(none)]
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// All endpoints related to API tokens
@@ -33,7 +34,7 @@ pub use self::{
 
 /// The phone number of a user. This is used to send OTPs, notifications, etc to
 /// the user.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UserPhoneNumber {
 	/// The country code of the phone number. This is a 2 letter code, such as
@@ -50,7 +51,7 @@ pub struct UserPhoneNumber {
 /// This is not the entire user object, but only the information that is allowed
 /// to be public. For privacy reasons, things like their email address and phone
 /// number are not public.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BasicUserInfo {
 	/// The username of the user. This is unique to the user.
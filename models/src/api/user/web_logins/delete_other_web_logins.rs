@@ -0,0 +1,18 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Delete all the web logins of the user, except the one that is making the
+	/// request. This will log the user out of all the other devices.
+	DeleteOtherWebLogins,
+	DELETE "/user/login",
+	api = false,
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::PlainTokenAuthenticator
+	}
+);
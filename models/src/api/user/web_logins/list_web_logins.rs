@@ -17,7 +17,7 @@ macros::declare_api_endpoint!(
 	},
 	pagination = true,
 	response_headers = {
-		/// The total number of databases in the requested workspace
+		/// The total number of web logins of the user
 		pub total_count: TotalCountHeader,
 	},
 	response = {
@@ -1,3 +1,5 @@
+/// The endpoint to delete all the web logins of a user, except the current one
+mod delete_other_web_logins;
 /// The endpoint to delete a web login
 mod delete_web_login;
 /// The endpoint to get the details of a web login
@@ -7,10 +9,16 @@ mod list_web_logins;
 
 use std::net::IpAddr;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
-pub use self::{delete_web_login::*, get_web_login_info::*, list_web_logins::*};
+pub use self::{
+	delete_other_web_logins::*,
+	delete_web_login::*,
+	get_web_login_info::*,
+	list_web_logins::*,
+};
 use crate::prelude::*;
 
 /// Tracks a user's web login.
@@ -162,13 +170,15 @@ use crate::prelude::*;
 /// user's data gets leaked. That's a huge problem. So we need to make sure that
 /// we not only secure things for our users, but also inform them about security
 /// events that might affect their account.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UserWebLogin {
 	/// The time at which this login expires. If the expiry has elapsed the user
 	/// should be automatically logged out.
+	#[schemars(with = "String")]
 	pub token_expiry: OffsetDateTime,
 	/// When this login was created.
+	#[schemars(with = "String")]
 	pub created: OffsetDateTime,
 	/// Which IP address this login was created from
 	pub created_ip: IpAddr,
@@ -182,6 +192,17 @@ pub struct UserWebLogin {
 	/// user-agents here. Also, unknown user-agents can maybe be rejected? Or
 	/// would that cause too many false positives? Need to test and find out.
 	pub created_user_agent: String,
+	/// The name and version of the browser that was used to create this login,
+	/// as parsed from the [`created_user_agent`][1] (e.g. `Chrome 120.0.0.0`).
+	///
+	/// [1]: UserWebLogin::created_user_agent
+	pub created_browser: String,
+	/// The name and version of the operating system that was used to create
+	/// this login, as parsed from the [`created_user_agent`][1] (e.g. `Mac OSX
+	/// 10.15.7`).
+	///
+	/// [1]: UserWebLogin::created_user_agent
+	pub created_os: String,
 	/// The country from which this login was created. This is again derived
 	/// from an IP service like ipinfo.io
 	pub created_country: String,
@@ -192,6 +213,10 @@ pub struct UserWebLogin {
 	/// The timezone of the IP address from which this login was created. This
 	/// is calculated using.....yeah no, ipinfo.io again.
 	pub created_timezone: String,
+	/// The last time this login was used to make a request, to the nearest
+	/// minute. This is used to show the user when a session was last active.
+	#[schemars(with = "String")]
+	pub last_activity: OffsetDateTime,
 }
 
 #[cfg(test)]
@@ -216,16 +241,19 @@ mod test {
 					longitude: 0.0,
 				},
 				created_user_agent: "user-agent".to_string(),
+				created_browser: "Chrome 120.0.0.0".to_string(),
+				created_os: "Mac OSX 10.15.7".to_string(),
 				created_country: "IN".to_string(),
 				created_region: "Karnataka".to_string(),
 				created_city: "Bengaluru".to_string(),
 				created_timezone: "UTC".to_string(),
+				last_activity: OffsetDateTime::UNIX_EPOCH,
 			}
 			.readable(),
 			&[
 				Token::Struct {
 					name: "UserWebLogin",
-					len: 9,
+					len: 12,
 				},
 				Token::Str("tokenExpiry"),
 				Token::Str("1970-01-01 00:00:00.0 +00:00:00"),
@@ -245,6 +273,10 @@ mod test {
 				Token::StructEnd,
 				Token::Str("createdUserAgent"),
 				Token::Str("user-agent"),
+				Token::Str("createdBrowser"),
+				Token::Str("Chrome 120.0.0.0"),
+				Token::Str("createdOs"),
+				Token::Str("Mac OSX 10.15.7"),
 				Token::Str("createdCountry"),
 				Token::Str("IN"),
 				Token::Str("createdRegion"),
@@ -253,6 +285,8 @@ mod test {
 				Token::Str("Bengaluru"),
 				Token::Str("createdTimezone"),
 				Token::Str("UTC"),
+				Token::Str("lastActivity"),
+				Token::Str("1970-01-01 00:00:00.0 +00:00:00"),
 				Token::StructEnd,
 			],
 		);
@@ -0,0 +1,34 @@
+use super::BillingStatement;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to get the billing statement of a workspace for a month. The usage of the workspace
+	/// within the month is priced with the billing plan that the workspace was on when the month
+	/// ended, or its current billing plan for the current month
+	GetBillingStatement,
+	GET "/workspace/:workspace_id/billing/statement/:year/:month" {
+		/// The ID of the workspace to get the billing statement of
+		pub workspace_id: Uuid,
+		/// The year of the statement
+		pub year: u16,
+		/// The month of the statement, from 1 to 12
+		pub month: u8,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::Billing(BillingPermission::View),
+		}
+	},
+	response = {
+		/// The billing statement of the workspace for the month
+		#[serde(flatten)]
+		pub statement: BillingStatement,
+	}
+);
@@ -0,0 +1,32 @@
+use std::collections::BTreeMap;
+
+use super::BillingPlan;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to list all the billing plans that a workspace can be on, along with the current
+	/// billing plan of the workspace
+	ListBillingPlans,
+	GET "/workspace/:workspace_id/billing/plan" {
+		/// The ID of the workspace to list the billing plans for
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::Billing(BillingPermission::View),
+		}
+	},
+	response = {
+		/// The ID of the current billing plan of the workspace
+		pub current_plan_id: String,
+		/// All the billing plans, keyed by their ID
+		pub plans: BTreeMap<String, BillingPlan>,
+	}
+);
@@ -0,0 +1,28 @@
+use super::BillingStatementSummary;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to list the billing statements of a workspace, for all the months that the
+	/// workspace has any usage in, latest first
+	ListBillingStatements,
+	GET "/workspace/:workspace_id/billing/statement" {
+		/// The ID of the workspace to list the billing statements of
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::Billing(BillingPermission::View),
+		}
+	},
+	response = {
+		/// A summary of the billing statement of each month, latest first
+		pub statements: Vec<BillingStatementSummary>,
+	}
+);
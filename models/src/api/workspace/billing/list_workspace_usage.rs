@@ -0,0 +1,49 @@
+use time::OffsetDateTime;
+
+use super::{WorkspaceUsage, WorkspaceUsageType};
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to list the hourly usage records of a workspace, latest first. These are the
+	/// records that the billing statements of the workspace are calculated from
+	ListWorkspaceUsage,
+	GET "/workspace/:workspace_id/billing/usage" {
+		/// The ID of the workspace to list the usage of
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::Billing(BillingPermission::View),
+		}
+	},
+	query = {
+		/// Only list the usage of this resource
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub resource_id: Option<Uuid>,
+		/// Only list the usage of this type
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub usage_type: Option<WorkspaceUsageType>,
+		/// Only list the usage recorded for hours at or after this time
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub from: Option<OffsetDateTime>,
+		/// Only list the usage recorded for hours before this time
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub to: Option<OffsetDateTime>,
+	},
+	pagination = true,
+	response_headers = {
+		/// The total number of usage records matching the filters
+		pub total_count: TotalCountHeader,
+	},
+	response = {
+		/// The list of usage records of the workspace, latest first
+		pub usage: Vec<WorkspaceUsage>,
+	}
+);
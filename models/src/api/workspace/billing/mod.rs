@@ -0,0 +1,149 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// The endpoint to get the billing statement of a workspace for a month
+mod get_billing_statement;
+/// The endpoint to list the billing plans, along with the current plan of the
+/// workspace
+mod list_billing_plans;
+/// The endpoint to list the billing statements of all the months a workspace
+/// has any usage in
+mod list_billing_statements;
+/// The endpoint to list the hourly usage records of a workspace
+mod list_workspace_usage;
+/// The endpoint to change the billing plan of a workspace
+mod update_billing_plan;
+
+pub use self::{
+	get_billing_statement::*,
+	list_billing_plans::*,
+	list_billing_statements::*,
+	list_workspace_usage::*,
+	update_billing_plan::*,
+};
+
+/// The type of usage that is recorded for a resource of a workspace every hour
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, JsonSchema)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(
+	not(target_arch = "wasm32"),
+	sqlx(type_name = "USAGE_TYPE", rename_all = "snake_case")
+)]
+pub enum WorkspaceUsageType {
+	/// A deployment that is running. The quantity is the most replicas of the
+	/// deployment that its runner reported as running within the hour, which
+	/// are priced based on its machine type
+	Deployment,
+	/// A volume that is created. The quantity is the size of the volume, in GB
+	Volume,
+	/// The images pushed to a container registry repository. The quantity is
+	/// the size of all the images of the repository, in bytes
+	ContainerRegistryStorage,
+	/// The bandwidth used by a static site. The quantity is the number of bytes
+	/// transferred by the static site within that hour
+	StaticSiteBandwidth,
+}
+
+/// The usage of a resource of a workspace, recorded for an hour
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceUsage {
+	/// The ID of the resource that the usage is recorded for
+	pub resource_id: Uuid,
+	/// The type of usage that is recorded
+	pub usage_type: WorkspaceUsageType,
+	/// The start of the hour that the usage is recorded for
+	#[schemars(with = "String")]
+	pub hour: OffsetDateTime,
+	/// The machine type of the deployment, if the usage is for a deployment
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub machine_type: Option<Uuid>,
+	/// The quantity of the usage. The unit depends on the type of the usage
+	pub quantity: u64,
+}
+
+/// A plan that the usage of a workspace is priced with. All prices are in
+/// cents. Resources that are billed hourly are priced per month, and are
+/// prorated over the number of hours they are used for.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BillingPlan {
+	/// The name of the plan, as shown to the user
+	pub name: String,
+	/// The price of a CPU of a deployment for a month
+	#[serde(alias = "cpumonthlyprice")]
+	pub cpu_monthly_price: u64,
+	/// The price of 1 GB of memory of a deployment for a month
+	#[serde(alias = "memorygbmonthlyprice")]
+	pub memory_gb_monthly_price: u64,
+	/// The price of 1 GB of volume storage for a month
+	#[serde(alias = "volumegbmonthlyprice")]
+	pub volume_gb_monthly_price: u64,
+	/// The price of 1 GB of container registry storage for a month
+	#[serde(alias = "containerregistrygbmonthlyprice")]
+	pub container_registry_gb_monthly_price: u64,
+	/// The price of 1 GB of bandwidth used by static sites
+	#[serde(alias = "staticsitebandwidthgbprice")]
+	pub static_site_bandwidth_gb_price: u64,
+}
+
+/// A line of a billing statement, which is the total usage of a type of
+/// resource over the period of the statement, along with its price
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BillingStatementLineItem {
+	/// The type of usage that is billed
+	pub usage_type: WorkspaceUsageType,
+	/// The machine type of the deployments, if the usage is for deployments
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub machine_type: Option<Uuid>,
+	/// A human readable description of the line item, including its unit
+	pub description: String,
+	/// The total quantity used over the period. This is in replica-hours for
+	/// deployments, GB-hours for volumes, byte-hours for container registry
+	/// storage and bytes for static site bandwidth
+	pub quantity: u64,
+	/// The price of the line item, in cents
+	pub amount: u64,
+}
+
+/// The billing statement of a workspace for a month. This can be exported as
+/// an invoice for the workspace.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BillingStatement {
+	/// The year of the statement
+	pub year: u16,
+	/// The month of the statement, from 1 to 12
+	pub month: u8,
+	/// The start of the period that the statement covers
+	#[schemars(with = "String")]
+	pub period_start: OffsetDateTime,
+	/// The end (exclusive) of the period that the statement covers
+	#[schemars(with = "String")]
+	pub period_end: OffsetDateTime,
+	/// The ID of the plan that the usage is priced with
+	pub plan_id: String,
+	/// The plan that the usage is priced with
+	pub plan: BillingPlan,
+	/// The line items of the statement
+	pub line_items: Vec<BillingStatementLineItem>,
+	/// The total price of the statement, in cents
+	pub total: u64,
+}
+
+/// A summary of the billing statement of a workspace for a month
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BillingStatementSummary {
+	/// The year of the statement
+	pub year: u16,
+	/// The month of the statement, from 1 to 12
+	pub month: u8,
+	/// The total price of the statement, in cents
+	pub total: u64,
+}
@@ -0,0 +1,29 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to change the billing plan of a workspace. The new plan is used to price the
+	/// statement of the current month. Statements of months that have ended keep the plan that
+	/// the workspace was on when the month ended
+	UpdateBillingPlan,
+	PUT "/workspace/:workspace_id/billing/plan" {
+		/// The ID of the workspace to change the billing plan of
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::Billing(BillingPermission::Edit),
+		}
+	},
+	request = {
+		/// The ID of the billing plan to change to
+		#[preprocess(trim, length(min = 1))]
+		pub plan_id: String,
+	},
+);
@@ -0,0 +1,54 @@
+use crate::{ci::GitProvider, prelude::*};
+
+macros::declare_api_endpoint!(
+	/// Route to connect a git repository to a workspace. The webhook of the repository needs to be
+	/// added to the provider with the URL and secret in the response. The secret is only returned
+	/// in the response of this route, and cannot be retrieved later
+	ConnectCiRepository,
+	POST "/workspace/:workspace_id/ci/repository" {
+		/// The ID of the workspace to connect the repository to
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::CiRepository(CiRepositoryPermission::Connect),
+		}
+	},
+	request = {
+		/// The provider that the repository is hosted on
+		#[preprocess(none)]
+		pub provider: GitProvider,
+		/// The URL of the API of the provider. This is only needed for self-hosted providers
+		#[preprocess(optional(trim, length(min = 1)))]
+		pub api_url: Option<String>,
+		/// The full name of the repository on the provider, such as `patr/api`
+		#[preprocess(trim, length(min = 1))]
+		pub full_name: String,
+		/// The runner to run the pipelines of the repository on
+		#[preprocess(none)]
+		pub runner_id: Uuid,
+		/// The path of the CI file in the repository. Defaults to `.patr.yml`
+		#[preprocess(optional(trim, length(min = 1)))]
+		pub ci_file_path: Option<String>,
+		/// The access token to read the CI file, clone the repository and report the status of
+		/// the runs with. This is only needed for private repositories, and to report statuses
+		#[preprocess(optional(trim, length(min = 1)))]
+		pub access_token: Option<String>,
+	},
+	response = {
+		/// The ID of the connected repository
+		#[serde(flatten)]
+		pub id: WithId<()>,
+		/// The URL that the provider needs to send the webhooks of the repository to
+		pub webhook_url: String,
+		/// The secret that the provider needs to sign the webhooks of the repository with
+		pub webhook_secret: String,
+	}
+);
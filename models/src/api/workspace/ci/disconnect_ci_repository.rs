@@ -0,0 +1,25 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to disconnect a git repository from a workspace. The webhooks of the repository no
+	/// longer run any pipelines after this
+	DisconnectCiRepository,
+	DELETE "/workspace/:workspace_id/ci/repository/:repository_id" {
+		/// The ID of the workspace the repository is connected to
+		pub workspace_id: Uuid,
+		/// The ID of the repository to disconnect
+		pub repository_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.repository_id,
+			permission: Permission::CiRepository(CiRepositoryPermission::Disconnect),
+		}
+	}
+);
@@ -0,0 +1,30 @@
+use super::CiRepository;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to get the details of a git repository connected to a workspace
+	GetCiRepositoryInfo,
+	GET "/workspace/:workspace_id/ci/repository/:repository_id" {
+		/// The ID of the workspace the repository is connected to
+		pub workspace_id: Uuid,
+		/// The ID of the repository
+		pub repository_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.repository_id,
+			permission: Permission::CiRepository(CiRepositoryPermission::View),
+		}
+	},
+	response = {
+		/// The details of the repository
+		#[serde(flatten)]
+		pub repository: WithId<CiRepository>,
+	}
+);
@@ -0,0 +1,35 @@
+use crate::{ci::CiStepLog, prelude::*};
+
+macros::declare_api_endpoint!(
+	/// Route to get the logs of the steps of a pipeline run, in the order they were written
+	GetCiRunLogs,
+	GET "/workspace/:workspace_id/ci/repository/:repository_id/run/:run_id/logs" {
+		/// The ID of the workspace the repository is connected to
+		pub workspace_id: Uuid,
+		/// The ID of the repository the run is of
+		pub repository_id: Uuid,
+		/// The ID of the run to get the logs of
+		pub run_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.repository_id,
+			permission: Permission::CiRepository(CiRepositoryPermission::View),
+		}
+	},
+	pagination = true,
+	response_headers = {
+		/// The total number of lines of the logs received so far
+		pub total_count: TotalCountHeader,
+	},
+	response = {
+		/// The lines of the logs
+		pub logs: Vec<CiStepLog>,
+	}
+);
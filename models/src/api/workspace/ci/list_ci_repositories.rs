@@ -0,0 +1,31 @@
+use super::CiRepository;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to list all the git repositories connected to a workspace
+	ListCiRepositories,
+	GET "/workspace/:workspace_id/ci/repository" {
+		/// The ID of the workspace to list the repositories of
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	pagination = true,
+	authentication = {
+		AppAuthentication::<Self>::WorkspaceMembershipAuthenticator {
+			extract_workspace_id: |req| req.path.workspace_id,
+		}
+	},
+	response_headers = {
+		/// The total number of items in the pagination
+		pub total_count: TotalCountHeader,
+	},
+	response = {
+		/// The list of repositories connected to the workspace
+		pub repositories: Vec<WithId<CiRepository>>,
+	}
+);
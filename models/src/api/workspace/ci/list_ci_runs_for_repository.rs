@@ -0,0 +1,39 @@
+use super::{CiRun, CiRunState};
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to list the pipeline runs of a git repository connected to a workspace, latest first
+	ListCiRunsForRepository,
+	GET "/workspace/:workspace_id/ci/repository/:repository_id/run" {
+		/// The ID of the workspace the repository is connected to
+		pub workspace_id: Uuid,
+		/// The ID of the repository to list the runs of
+		pub repository_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.repository_id,
+			permission: Permission::CiRepository(CiRepositoryPermission::View),
+		}
+	},
+	query = {
+		/// Only list the runs in this state
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub state: Option<CiRunState>,
+	},
+	pagination = true,
+	response_headers = {
+		/// The total number of runs matching the filters
+		pub total_count: TotalCountHeader,
+	},
+	response = {
+		/// The list of runs of the repository
+		pub runs: Vec<WithId<CiRun>>,
+	}
+);
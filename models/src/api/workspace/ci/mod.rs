@@ -0,0 +1,120 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::{
+	ci::{CiStepReport, Event, GitProvider},
+	prelude::*,
+};
+
+/// The endpoint to connect a git repository to a workspace
+mod connect_ci_repository;
+/// The endpoint to disconnect a git repository from a workspace
+mod disconnect_ci_repository;
+/// The endpoint to get the details of a connected git repository
+mod get_ci_repository_info;
+/// The endpoint to get the logs of a pipeline run
+mod get_ci_run_logs;
+/// The endpoint to list all the git repositories connected to a workspace
+mod list_ci_repositories;
+/// The endpoint to list the pipeline runs of a connected git repository
+mod list_ci_runs_for_repository;
+/// The endpoint to update a connected git repository
+mod update_ci_repository;
+/// The endpoint to validate a CI file
+mod validate_ci_file;
+
+pub use self::{
+	connect_ci_repository::*,
+	disconnect_ci_repository::*,
+	get_ci_repository_info::*,
+	get_ci_run_logs::*,
+	list_ci_repositories::*,
+	list_ci_runs_for_repository::*,
+	update_ci_repository::*,
+	validate_ci_file::*,
+};
+
+/// A git repository connected to a workspace. The provider of the repository
+/// sends a webhook to Patr for every push, tag and pull request, and the CI
+/// pipeline in the repository is run for it on the runner of the repository.
+/// If an access token is set, the status of every run is reported back to the
+/// commit it was run on.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CiRepository {
+	/// The provider that the repository is hosted on
+	pub provider: GitProvider,
+	/// The URL of the API of the provider
+	pub api_url: String,
+	/// The full name of the repository on the provider, such as `patr/api`
+	pub full_name: String,
+	/// The runner that the pipelines of the repository are run on
+	pub runner_id: Uuid,
+	/// The path of the CI file in the repository
+	pub ci_file_path: String,
+	/// Whether an access token is set for the repository. The access token is
+	/// used to read the CI file of private repositories, clone them and report
+	/// the status of the runs back to the provider. It is never returned.
+	pub has_access_token: bool,
+	/// Whether the webhooks of the repository run its pipelines. The webhooks
+	/// of a disabled repository are ignored.
+	pub enabled: bool,
+	/// The time at which the repository was connected
+	#[schemars(with = "String")]
+	pub created: OffsetDateTime,
+}
+
+/// A run of the CI pipeline of a connected git repository
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CiRun {
+	/// The repository that the pipeline is run for
+	pub repository_id: Uuid,
+	/// The runner that the pipeline is run on
+	pub runner_id: Uuid,
+	/// The commit that the pipeline is run on
+	pub commit_sha: String,
+	/// The branch of the event. For pull requests, this is the branch that the
+	/// pull request is to be merged into
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub branch: Option<String>,
+	/// The tag that was pushed, if the event is a tag
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub tag: Option<String>,
+	/// The type of the git event that the pipeline is run for
+	pub event: Event,
+	/// The state of the run
+	pub state: CiRunState,
+	/// The reason the run failed without any step failing, such as the CI file
+	/// being invalid
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	/// The report of every step of the pipeline, once the run is complete
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub steps: Vec<CiStepReport>,
+	/// The time at which the run was queued
+	#[schemars(with = "String")]
+	pub created: OffsetDateTime,
+	/// The time at which the run completed, if it has
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[schemars(with = "Option<String>")]
+	pub finished: Option<OffsetDateTime>,
+}
+
+/// The state of a run of a CI pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(
+	not(target_arch = "wasm32"),
+	sqlx(type_name = "CI_RUN_STATE", rename_all = "snake_case")
+)]
+pub enum CiRunState {
+	/// The run has been sent to the runner, which has not reported back yet
+	Queued,
+	/// Every step of the run that was run succeeded
+	Succeeded,
+	/// The run could not be completed, or a step of it failed
+	Failed,
+}
@@ -0,0 +1,44 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to update a git repository connected to a workspace. Only the parameters that are
+	/// provided are updated
+	UpdateCiRepository,
+	PATCH "/workspace/:workspace_id/ci/repository/:repository_id" {
+		/// The ID of the workspace the repository is connected to
+		pub workspace_id: Uuid,
+		/// The ID of the repository to update
+		pub repository_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.repository_id,
+			permission: Permission::CiRepository(CiRepositoryPermission::Edit),
+		}
+	},
+	request = {
+		/// The new runner to run the pipelines of the repository on
+		#[preprocess(none)]
+		pub runner_id: Option<Uuid>,
+		/// The new path of the CI file in the repository
+		#[preprocess(optional(trim, length(min = 1)))]
+		pub ci_file_path: Option<String>,
+		/// The new access token of the repository
+		#[preprocess(optional(trim, length(min = 1)))]
+		pub access_token: Option<String>,
+		/// Removes the access token of the repository. This cannot be set along with a new access
+		/// token
+		#[preprocess(none)]
+		#[serde(default)]
+		pub remove_access_token: bool,
+		/// Whether the webhooks of the repository should run its pipelines
+		#[preprocess(none)]
+		pub enabled: Option<bool>,
+	},
+);
@@ -0,0 +1,33 @@
+use crate::{ci::CiDiagnostic, prelude::*};
+
+macros::declare_api_endpoint!(
+	/// Route to validate a CI file. Every problem found in the file is returned, along with where
+	/// it is in the file
+	ValidateCiFile,
+	POST "/workspace/:workspace_id/ci/validate" {
+		/// The ID of the workspace to validate the CI file for
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::WorkspaceMembershipAuthenticator {
+			extract_workspace_id: |req| req.path.workspace_id,
+		}
+	},
+	request = {
+		/// The contents of the CI file
+		#[preprocess(none)]
+		pub content: String,
+	},
+	response = {
+		/// Whether the file can be run. Files that only have warnings can still be run
+		pub valid: bool,
+		/// The problems found in the file, in the order they appear in the file
+		pub diagnostics: Vec<CiDiagnostic>,
+	}
+);
@@ -1,16 +1,18 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 use crate::prelude::*;
 
 /// The response body for the ListContainerRepositories endpoint.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct ContainerRepositoryTagAndDigestInfo {
 	/// The tag of the repository
 	pub tag: String,
 	/// The digest that this tag points to
 	pub digest: String,
 	/// The last updated time of the tag
+	#[schemars(with = "String")]
 	pub last_updated: OffsetDateTime,
 }
 
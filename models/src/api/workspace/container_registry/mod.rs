@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
@@ -50,7 +51,7 @@ pub struct ContainerRepositoryImageInfo {
 }
 /// Represents a repository of container images in Patr's in-build container
 /// registry.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ContainerRepository {
 	/// The name of the repository.
@@ -61,10 +62,12 @@ pub struct ContainerRepository {
 	/// updated.
 	///
 	/// TODO: Change this to audit log
+	#[schemars(with = "String")]
 	pub last_updated: OffsetDateTime,
 	/// The time the repository was created.nlas
 	///
 	/// TODO: Change this to audit log
+	#[schemars(with = "String")]
 	pub created: OffsetDateTime,
 }
 
@@ -1,7 +1,8 @@
 use crate::prelude::*;
 
 macros::declare_api_endpoint!(
-	/// Route to delete a workspace. Only the super admin of a workspace can delete a workspace
+	/// Route to delete a workspace. Only the super admin of a workspace can delete a workspace.
+	/// Unless `cascade` is set, the workspace must not have any resources left in it
 	DeleteWorkspace,
 	DELETE "/workspace/:workspace_id" {
 		/// The ID of the workspace to be deleted
@@ -13,6 +14,13 @@ macros::declare_api_endpoint!(
 		/// The user-agent used to access this API
 		pub user_agent: UserAgent,
 	},
+	query = {
+		/// Whether to delete all the resources of the workspace along with it. If set, the
+		/// workspace is scheduled for deletion and can be restored until the grace period ends,
+		/// after which all its resources are destroyed
+		#[serde(default)]
+		pub cascade: bool,
+	},
 	authentication = {
 		AppAuthentication::<Self>::WorkspaceSuperAdminAuthenticator {
 			extract_workspace_id: |req| req.path.workspace_id,
@@ -0,0 +1,28 @@
+use super::WorkspaceDeletionPlanItem;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to list everything that will be destroyed if the workspace is deleted along with
+	/// all its resources, in the order they will be destroyed in. Nothing is deleted by this
+	/// route
+	GetWorkspaceDeletionPlan,
+	GET "/workspace/:workspace_id/deletion-plan" {
+		/// The ID of the workspace to get the deletion plan of
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::WorkspaceSuperAdminAuthenticator {
+			extract_workspace_id: |req| req.path.workspace_id,
+		}
+	},
+	response = {
+		/// The resources that will be destroyed, in the order they will be destroyed in
+		pub resources: Vec<WorkspaceDeletionPlanItem>,
+	}
+);
@@ -0,0 +1,27 @@
+use super::{WorkspaceDeletionPlanItem, WorkspaceDeletionStatus};
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to get the progress of the deletion of a workspace, along with the resources that
+	/// are yet to be destroyed. Only the super admin of the workspace can use this route
+	GetWorkspaceDeletionStatus,
+	GET "/workspace/:workspace_id/deletion" {
+		/// The ID of the workspace to get the deletion status of
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::PlainTokenAuthenticator
+	},
+	response = {
+		/// The progress of the deletion
+		pub status: WorkspaceDeletionStatus,
+		/// The resources of the workspace that are yet to be destroyed
+		pub remaining_resources: Vec<WorkspaceDeletionPlanItem>,
+	}
+);
@@ -0,0 +1,129 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// The endpoint to list everything that will be destroyed when a workspace is
+/// deleted along with all its resources
+mod get_workspace_deletion_plan;
+/// The endpoint to get the progress of the deletion of a workspace
+mod get_workspace_deletion_status;
+/// The endpoint to restore a workspace that is scheduled for deletion
+mod restore_workspace;
+
+pub use self::{
+	get_workspace_deletion_plan::*,
+	get_workspace_deletion_status::*,
+	restore_workspace::*,
+};
+
+/// A step of the deletion of a workspace. The steps are run in the order they
+/// are declared in, and every step destroys one kind of resource of the
+/// workspace. A step that fails is retried until it succeeds, after which the
+/// deletion continues with the next step.
+#[derive(
+	Debug,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Hash,
+	Serialize,
+	Deserialize,
+	JsonSchema,
+)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(
+	not(target_arch = "wasm32"),
+	sqlx(type_name = "WORKSPACE_DELETION_STEP", rename_all = "snake_case")
+)]
+pub enum WorkspaceDeletionStep {
+	/// The deployments of the workspace are deleted and removed from their
+	/// runners
+	Deployments,
+	/// The static sites of the workspace are deleted
+	StaticSites,
+	/// The managed databases of the workspace are deleted
+	ManagedDatabases,
+	/// The volumes of the workspace are deleted
+	Volumes,
+	/// The container registry repositories of the workspace are deleted, along
+	/// with all the images pushed to them
+	ContainerRegistry,
+	/// The managed URLs of the workspace are deleted
+	ManagedUrls,
+	/// The domains of the workspace are deleted, along with all the DNS
+	/// records of the domains
+	Domains,
+	/// The secrets of the workspace are deleted
+	Secrets,
+	/// The runners of the workspace are deleted
+	Runners,
+	/// The workspace itself is deleted, along with any resources that are
+	/// still left in it
+	Workspace,
+}
+
+impl WorkspaceDeletionStep {
+	/// Returns the step that is run after this one, or [`None`] if this is the
+	/// last step of the deletion
+	pub fn next(&self) -> Option<Self> {
+		match self {
+			Self::Deployments => Some(Self::StaticSites),
+			Self::StaticSites => Some(Self::ManagedDatabases),
+			Self::ManagedDatabases => Some(Self::Volumes),
+			Self::Volumes => Some(Self::ContainerRegistry),
+			Self::ContainerRegistry => Some(Self::ManagedUrls),
+			Self::ManagedUrls => Some(Self::Domains),
+			Self::Domains => Some(Self::Secrets),
+			Self::Secrets => Some(Self::Runners),
+			Self::Runners => Some(Self::Workspace),
+			Self::Workspace => None,
+		}
+	}
+}
+
+/// A resource that will be destroyed when a workspace is deleted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceDeletionPlanItem {
+	/// The step of the deletion in which the resource is destroyed
+	pub step: WorkspaceDeletionStep,
+	/// The ID of the resource
+	pub resource_id: Uuid,
+	/// The name of the resource, as shown to the user
+	pub name: String,
+}
+
+/// The progress of the deletion of a workspace
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceDeletionStatus {
+	/// The user that requested the deletion
+	pub requested_by: Uuid,
+	/// The time at which the deletion was requested
+	#[schemars(with = "String")]
+	pub requested: OffsetDateTime,
+	/// The time at which the grace period ends and the resources of the
+	/// workspace start getting destroyed. The workspace can be restored until
+	/// then
+	#[schemars(with = "String")]
+	pub scheduled: OffsetDateTime,
+	/// The step of the deletion that is currently being run. This is not set
+	/// once the deletion is completed
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub current_step: Option<WorkspaceDeletionStep>,
+	/// The number of times the current step has failed so far
+	pub failed_attempts: u32,
+	/// The error of the last failed attempt of the current step, if any
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub last_error: Option<String>,
+	/// The time at which the deletion was completed
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[schemars(with = "Option<String>")]
+	pub completed: Option<OffsetDateTime>,
+}
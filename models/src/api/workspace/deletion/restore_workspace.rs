@@ -0,0 +1,20 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to restore a workspace that is scheduled for deletion. This can only be done by the
+	/// super admin of the workspace, before the grace period of the deletion ends
+	RestoreWorkspace,
+	POST "/workspace/:workspace_id/restore" {
+		/// The ID of the workspace to restore
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::PlainTokenAuthenticator
+	},
+);
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
@@ -10,11 +11,12 @@ pub use self::{delete_deploy_history::*, list_deploy_history::*};
 
 /// The deployment history of a deployment. This is a list of the images digests
 /// the deployment has ran and the timestamp of when the digest previously ran
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DeploymentDeployHistory {
 	/// The images digests the deployment has ran
 	pub image_digest: String,
 	/// The timestamp of when the digest previously ran
+	#[schemars(with = "String")]
 	pub created: OffsetDateTime,
 }
@@ -1,5 +1,6 @@
 use std::{collections::BTreeMap, fmt::Display, str::FromStr};
 
+use schemars::JsonSchema;
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 use time::OffsetDateTime;
 
@@ -50,7 +51,7 @@ use crate::{prelude::*, utils::constants};
 /// This can be classified by the number of CPU and Memory allocated to the
 /// deployment. The machine type can be used to classify the deployment based on
 /// the resources it requires.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 pub struct DeploymentMachineType {
 	/// The number of CPU nodes allocated to the deployment. This is the number
 	/// of vCPUs in case of cloud deployments and the number of physical CPUs in
@@ -63,8 +64,7 @@ pub struct DeploymentMachineType {
 }
 
 /// Deployment information
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-#[cfg_attr(not(target_arch = "wasm32"), derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Deployment {
 	/// Name of the deployment
@@ -90,8 +90,7 @@ pub struct Deployment {
 }
 
 /// Deployment running details
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-#[cfg_attr(not(target_arch = "wasm32"), derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DeploymentRunningDetails {
 	/// if the deployment should deploy as soon as a new image digest is pushed
@@ -124,8 +123,7 @@ pub struct DeploymentRunningDetails {
 
 /// The type of environment variable
 /// The keys can either have a string as a value or a secret
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
-#[cfg_attr(not(target_arch = "wasm32"), derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, JsonSchema)]
 #[serde(untagged)]
 pub enum EnvironmentVariableValue {
 	/// String
@@ -178,10 +176,11 @@ impl EnvironmentVariableValue {
 	strum::Display,
 	strum::VariantNames,
 	Hash,
+	JsonSchema,
 )]
 #[strum(serialize_all = "camelCase")]
 #[serde(rename_all = "camelCase")]
-#[cfg_attr(not(target_arch = "wasm32"), derive(sqlx::Type, schemars::JsonSchema))]
+#[cfg_attr(not(target_arch = "wasm32"), derive(sqlx::Type))]
 #[cfg_attr(
 	not(target_arch = "wasm32"),
 	sqlx(type_name = "EXPOSED_PORT_TYPE", rename_all = "lowercase")
@@ -196,8 +195,7 @@ pub enum ExposedPortType {
 }
 
 /// The deployment startup/liveness probe
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-#[cfg_attr(not(target_arch = "wasm32"), derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DeploymentProbe {
 	/// The port the probe will be using
@@ -207,8 +205,7 @@ pub struct DeploymentProbe {
 }
 
 /// Patr registry
-#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
-#[cfg_attr(not(target_arch = "wasm32"), derive(schemars::JsonSchema))]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, JsonSchema)]
 pub struct PatrRegistry;
 
 impl Display for PatrRegistry {
@@ -244,8 +241,7 @@ impl Serialize for PatrRegistry {
 }
 
 /// Deployment registry
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-#[cfg_attr(not(target_arch = "wasm32"), derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(untagged)]
 pub enum DeploymentRegistry {
 	/// Patr registry offered by patr
@@ -304,8 +300,8 @@ impl DeploymentRegistry {
 
 /// All the possible deployment status a deployment can be
 /// in during its life cycle
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
-#[cfg_attr(not(target_arch = "wasm32"), derive(sqlx::Type, schemars::JsonSchema))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, JsonSchema)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(sqlx::Type))]
 #[serde(rename_all = "camelCase")]
 #[cfg_attr(
 	not(target_arch = "wasm32"),
@@ -357,10 +353,11 @@ impl FromStr for DeploymentStatus {
 }
 
 /// Deployment metrics
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DeploymentMetric {
 	/// The timestamp of the metric
+	#[schemars(with = "String")]
 	pub timestamp: OffsetDateTime,
 	/// The cpu usage of a pod
 	pub cpu_usage: String,
@@ -373,10 +370,11 @@ pub struct DeploymentMetric {
 }
 
 /// Deployment logs
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DeploymentLog {
 	/// Timestamp of a deployment log
+	#[schemars(with = "String")]
 	pub timestamp: OffsetDateTime,
 	/// The logs of a deployment
 	pub log: String,
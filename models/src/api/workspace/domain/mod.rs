@@ -4,6 +4,7 @@ use std::{
 	str::FromStr,
 };
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
@@ -44,17 +45,18 @@ pub use self::{
 };
 
 /// The domain metadata information
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Domain {
 	/// The name of the domain
 	pub name: String,
 	/// Last verified time of the domain
+	#[schemars(with = "Option<String>")]
 	pub last_unverified: Option<OffsetDateTime>,
 }
 
 /// The domain information in a workspace
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct WorkspaceDomain {
 	/// The domain metadata
@@ -91,7 +93,7 @@ pub struct PatrControlledDomain {
 }
 
 /// The DNS record type of a domain
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[allow(clippy::upper_case_acronyms)]
 #[serde(tag = "type")]
 pub enum DnsRecordValue {
@@ -210,7 +212,7 @@ impl Display for DnsRecordValue {
 }
 
 /// Type of domain nameserver
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[cfg_attr(not(target_arch = "wasm32"), derive(sqlx::Type))]
 #[serde(rename_all = "camelCase")]
 #[cfg_attr(
@@ -259,7 +261,7 @@ impl FromStr for DomainNameserverType {
 }
 
 /// The DNS record information of patr domain
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PatrDomainDnsRecord {
 	/// The domain ID
@@ -0,0 +1,28 @@
+use std::collections::BTreeMap;
+
+use super::{WorkspaceQuotaType, WorkspaceQuotaUsage};
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to get the quotas of a workspace, along with the current usage of each quota
+	GetWorkspaceQuota,
+	GET "/workspace/:workspace_id/quota" {
+		/// The ID of the workspace to get the quotas of
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::WorkspaceMembershipAuthenticator {
+			extract_workspace_id: |req| req.path.workspace_id,
+		}
+	},
+	response = {
+		/// The current usage and the limit of each quota of the workspace
+		pub quotas: BTreeMap<WorkspaceQuotaType, WorkspaceQuotaUsage>,
+	}
+);
@@ -0,0 +1,52 @@
+use time::OffsetDateTime;
+
+use super::WorkspaceAuditLog;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to list the audit logs of a workspace. Every state-changing request made on a
+	/// workspace is recorded in the audit log. Only the super admin of a workspace can view
+	/// the audit logs
+	ListAuditLogs,
+	GET "/workspace/:workspace_id/audit-log" {
+		/// The ID of the workspace to get the audit logs of
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::WorkspaceSuperAdminAuthenticator {
+			extract_workspace_id: |req| req.path.workspace_id,
+		}
+	},
+	query = {
+		/// Only list the audit logs of this resource
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub resource_id: Option<Uuid>,
+		/// Only list the audit logs of requests made by this user
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub user_id: Option<Uuid>,
+		/// Only list the audit logs of this action (`create`, `update` or `delete`)
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub action: Option<String>,
+		/// Only list the audit logs recorded at or after this time
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub from: Option<OffsetDateTime>,
+		/// Only list the audit logs recorded before this time
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub to: Option<OffsetDateTime>,
+	},
+	pagination = true,
+	response_headers = {
+		/// The total number of audit logs matching the filters
+		pub total_count: TotalCountHeader,
+	},
+	response = {
+		/// The list of audit logs of the workspace, latest first
+		pub audit_logs: Vec<WithId<WorkspaceAuditLog>>,
+	}
+);
@@ -26,6 +26,9 @@ macros::declare_api_endpoint!(
 		pub order_by: Option<ListOrderBy>,
 		/// Search by a specific query
 		pub filter: Option<String>,
+		/// Only list the managed URLs that are in this project
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub project_id: Option<Uuid>,
 	},
 	pagination = true,
 	response_headers = {
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumDiscriminants, EnumString, VariantNames};
 
@@ -22,7 +23,19 @@ pub use self::{
 use crate::utils::Uuid;
 
 /// Which field to order the list by for paginated requests
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[derive(
+	Clone,
+	Copy,
+	Debug,
+	Default,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Deserialize,
+	Serialize,
+	JsonSchema,
+)]
 #[serde(rename_all = "camelCase")]
 pub enum ListOrderBy {
 	/// Order the list by the status of the resource
@@ -37,7 +50,7 @@ pub enum ListOrderBy {
 }
 
 /// Managed URL information
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ManagedUrl {
 	/// Subdomain of the URL
@@ -65,6 +78,7 @@ pub struct ManagedUrl {
 	EnumDiscriminants,
 	EnumString,
 	VariantNames,
+	JsonSchema,
 )]
 #[strum_discriminants(
 	name(ManagedUrlTypeDiscriminant),
@@ -1,19 +1,36 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use strum::EnumIter;
 use time::OffsetDateTime;
 
 use crate::prelude::*;
 
+/// This module contains all the models that corresponds to the usage and
+/// billing of a workspace
+pub mod billing;
+/// This module contains all the models that corresponds to the CI pipelines
+/// that are run for a workspace
+pub mod ci;
 /// All the modules that corresponds to Patr's in-build container registry
 pub mod container_registry;
 /// This module contains all the database models
 pub mod database;
+/// This module contains all the models that corresponds to deleting a
+/// workspace along with all its resources
+pub mod deletion;
 /// This module contains all the deployment models
 pub mod deployment;
 /// All the modules that corresponds to Patr Domains
 pub mod domain;
 /// This module contains all the managed URL models
 pub mod managed_url;
+/// This module contains all the models that corresponds to transferring the
+/// ownership of a workspace to another user
+pub mod ownership_transfer;
+/// This module contains all the models that corresponds to projects, which are
+/// used to group resources within a workspace
+pub mod project;
 /// This module contains all the models that corresponds to the RBAC of Patr
 pub mod rbac;
 /// This module contains all the models that corresponds to a runner of a Patr
@@ -21,10 +38,16 @@ pub mod rbac;
 pub mod runner;
 /// This module contains all the models that corresponds to Patr secrets
 pub mod secret;
+/// This module contains all the models that corresponds to service accounts,
+/// which are non-human identities owned by a workspace
+pub mod service_account;
 /// This module contains all the static site models
 pub mod static_site;
 /// This module contains all the models that corresponds to a deployment volume
 pub mod volume;
+/// This module contains all the models that corresponds to the webhooks that
+/// the events of a workspace are sent to
+pub mod webhook;
 
 /// The endpoint to create a workspace
 mod create_workspace;
@@ -32,22 +55,34 @@ mod create_workspace;
 mod delete_workspace;
 /// The endpoint to get the details of a workspace
 mod get_workspace_info;
+/// The endpoint to get the quotas of a workspace, along with the current usage
+mod get_workspace_quota;
 /// The endpoint to check if a workspace name is available
 mod is_name_available;
+/// The endpoint to list the audit logs of a workspace
+mod list_audit_logs;
+/// The endpoint to move a resource to another workspace
+mod move_resource;
 /// The endpoint to update the details of a workspace
 mod update_workspace_info;
+/// The endpoint to update the single sign-on settings of a workspace
+mod update_workspace_sso;
 
 pub use self::{
 	create_workspace::*,
 	delete_workspace::*,
 	get_workspace_info::*,
+	get_workspace_quota::*,
 	is_name_available::*,
+	list_audit_logs::*,
+	move_resource::*,
 	update_workspace_info::*,
+	update_workspace_sso::*,
 };
 
 /// The details of a workspace. A workspace contains all the resources that will
 /// be created. A resource cannot exist outside of a workspace.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Workspace {
 	/// The name of the workspace. This must be unique across Patr. This is used
@@ -57,13 +92,20 @@ pub struct Workspace {
 	/// The userId of the user that is the super admin of this workspace. This
 	/// user has the highest level of permissions in this workspace.
 	pub super_admin_id: Uuid,
+	/// The ID of the external OpenID Connect provider that members of this
+	/// workspace must sign in with, if any. Members that sign in any other way
+	/// will not be able to access the workspace. The super admin of the
+	/// workspace is exempt from this, so that they cannot be locked out.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub sso_provider_id: Option<String>,
 }
 
 /// Logs corresponding to the actions performed on the workspace
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct WorkspaceAuditLog {
 	/// Date and time of the audit log
+	#[schemars(with = "String")]
 	pub date: OffsetDateTime,
 	/// The IP address of the user who made the request
 	pub ip_address: String,
@@ -75,6 +117,10 @@ pub struct WorkspaceAuditLog {
 	/// The login ID of the user who made the request
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub login_id: Option<Uuid>,
+	/// Whether the request was made by a service account of the workspace, in
+	/// which case the user ID is the ID of the service account
+	#[serde(default)]
+	pub service_account: bool,
 	/// The resource ID of the resource the request was made on
 	pub resource_id: Uuid,
 	/// The action that was performed on the resource
@@ -88,3 +134,54 @@ pub struct WorkspaceAuditLog {
 	/// Was the request successful or not
 	pub request_success: bool,
 }
+
+/// The resources of a workspace that are limited by a quota. Creating or
+/// updating a resource such that the usage of the workspace goes beyond its
+/// quota will fail with [`ErrorType::QuotaExceeded`].
+#[derive(
+	Debug,
+	Clone,
+	Copy,
+	Default,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Hash,
+	Serialize,
+	Deserialize,
+	EnumIter,
+	JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum WorkspaceQuotaType {
+	/// The number of deployments in the workspace
+	#[default]
+	Deployments,
+	/// The number of static sites in the workspace
+	StaticSites,
+	/// The total number of CPUs of all the deployments in the workspace, which
+	/// is the CPU count of the machine type of each deployment multiplied by
+	/// its maximum horizontal scale
+	CpuCount,
+	/// The total amount of memory of all the deployments in the workspace, in
+	/// 0.25 GB increments. This is the memory count of the machine type of each
+	/// deployment multiplied by its maximum horizontal scale
+	MemoryCount,
+	/// The total size of all the volumes in the workspace, in GB
+	VolumeStorage,
+	/// The total size of all the images pushed to the container registry of
+	/// the workspace, in bytes
+	ContainerRegistryStorage,
+}
+
+/// The current usage of a workspace for a [`WorkspaceQuotaType`], along with
+/// the limit of the quota.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceQuotaUsage {
+	/// The amount of the quota that is currently used
+	pub used: u64,
+	/// The maximum amount allowed by the quota
+	pub limit: u64,
+}
@@ -0,0 +1,43 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to move a deployment, secret, volume or container registry repository to another
+	/// workspace. The user must be the super admin of both the workspaces. Any resources that
+	/// the resource depends on (such as the secrets of a deployment) are moved along with it,
+	/// as long as they are not used by any other resource. Deployments with volumes, or with
+	/// managed URLs that are not on a verified domain of the new workspace, cannot be moved.
+	/// Permissions given on the moved resources by the roles and API tokens of the old
+	/// workspace are removed
+	MoveResourceToWorkspace,
+	POST "/workspace/:workspace_id/resource/:resource_id/move" {
+		/// The ID of the workspace that the resource is currently in
+		pub workspace_id: Uuid,
+		/// The ID of the resource to move
+		pub resource_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::WorkspaceSuperAdminAuthenticator {
+			extract_workspace_id: |req| req.path.workspace_id,
+		}
+	},
+	request = {
+		/// The ID of the workspace to move the resource to
+		#[preprocess(none)]
+		pub new_workspace_id: Uuid,
+		/// The runner of the new workspace to run the deployment on. This is required when
+		/// moving a deployment, and is ignored otherwise
+		#[preprocess(none)]
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub runner_id: Option<Uuid>,
+	},
+	response = {
+		/// The IDs of all the resources that were moved, including the resource itself
+		pub moved_resources: Vec<Uuid>,
+	}
+);
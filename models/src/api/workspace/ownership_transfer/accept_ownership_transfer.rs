@@ -0,0 +1,21 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to accept the ownership of a workspace. The transfer must have been sent to the
+	/// user that is currently logged in. Once accepted, the user becomes the super admin of the
+	/// workspace
+	AcceptWorkspaceOwnershipTransfer,
+	POST "/workspace/:workspace_id/ownership-transfer/accept" {
+		/// The ID of the workspace to accept the ownership of
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::PlainTokenAuthenticator
+	},
+);
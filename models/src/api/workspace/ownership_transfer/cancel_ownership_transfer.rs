@@ -0,0 +1,20 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to cancel a pending transfer of the ownership of a workspace. This can be used by
+	/// the super admin of the workspace to cancel the transfer, or by the new owner to decline it
+	CancelWorkspaceOwnershipTransfer,
+	DELETE "/workspace/:workspace_id/ownership-transfer" {
+		/// The ID of the workspace to cancel the ownership transfer of
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::PlainTokenAuthenticator
+	},
+);
@@ -0,0 +1,34 @@
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to start transferring the ownership of a workspace to another user. The new owner
+	/// has to accept the transfer before they become the super admin of the workspace. Starting
+	/// a new transfer replaces any pending transfer of the workspace
+	InitiateWorkspaceOwnershipTransfer,
+	POST "/workspace/:workspace_id/ownership-transfer" {
+		/// The ID of the workspace to transfer
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::WorkspaceSuperAdminAuthenticator {
+			extract_workspace_id: |req| req.path.workspace_id,
+		}
+	},
+	request = {
+		/// The ID of the user that will become the new owner of the workspace
+		#[preprocess(none)]
+		pub new_owner_id: Uuid,
+	},
+	response = {
+		/// The time after which the transfer can no longer be accepted
+		pub expiry: OffsetDateTime,
+	}
+);
@@ -0,0 +1,22 @@
+use super::WorkspaceOwnershipTransfer;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to list all the pending transfers of the ownership of a workspace to the user that
+	/// is currently logged in
+	ListOwnershipTransfers,
+	GET "/workspace/ownership-transfer",
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::PlainTokenAuthenticator
+	},
+	response = {
+		/// The list of pending ownership transfers, with the ID of the workspace as the ID
+		pub transfers: Vec<WithId<WorkspaceOwnershipTransfer>>,
+	}
+);
@@ -0,0 +1,42 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// The endpoint to accept the ownership of a workspace
+mod accept_ownership_transfer;
+/// The endpoint to cancel or decline a pending ownership transfer
+mod cancel_ownership_transfer;
+/// The endpoint to start transferring the ownership of a workspace to another
+/// user
+mod initiate_ownership_transfer;
+/// The endpoint to list the pending ownership transfers sent to the current
+/// user
+mod list_ownership_transfers;
+
+pub use self::{
+	accept_ownership_transfer::*,
+	cancel_ownership_transfer::*,
+	initiate_ownership_transfer::*,
+	list_ownership_transfers::*,
+};
+
+/// A pending transfer of the ownership of a workspace to another user. The
+/// transfer only takes effect once the new owner accepts it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceOwnershipTransfer {
+	/// The name of the workspace being transferred
+	pub workspace_name: String,
+	/// The user that will become the super admin of the workspace
+	pub new_owner_id: Uuid,
+	/// The super admin that started the transfer
+	pub requested_by: Uuid,
+	/// The time at which the transfer was started
+	#[schemars(with = "String")]
+	pub created: OffsetDateTime,
+	/// The time after which the transfer can no longer be accepted
+	#[schemars(with = "String")]
+	pub expiry: OffsetDateTime,
+}
@@ -0,0 +1,28 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to add a resource to a project. Only deployments, static sites, secrets,
+	/// volumes and managed URLs can be added to a project. A resource can only be in one
+	/// project at a time, so adding it to a project moves it out of any other project.
+	AddResourceToProject,
+	PUT "/workspace/:workspace_id/project/:project_id/resource/:resource_id" {
+		/// The ID of the workspace
+		pub workspace_id: Uuid,
+		/// The ID of the project
+		pub project_id: Uuid,
+		/// The ID of the resource to add to the project
+		pub resource_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.project_id,
+			permission: Permission::Project(ProjectPermission::Edit),
+		}
+	}
+);
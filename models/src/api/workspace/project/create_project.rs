@@ -0,0 +1,35 @@
+use crate::{prelude::*, utils::constants::RESOURCE_NAME_REGEX};
+
+macros::declare_api_endpoint!(
+	/// Route to create a new project in a workspace
+	CreateProject,
+	POST "/workspace/:workspace_id/project" {
+		/// The ID of the workspace
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::Project(ProjectPermission::Create),
+		}
+	},
+	request = {
+		/// The name of the project
+		#[preprocess(trim, regex = RESOURCE_NAME_REGEX)]
+		pub name: String,
+		/// A description of what the project is used for
+		#[preprocess(trim, length(max = 500))]
+		pub description: String,
+	},
+	response = {
+		/// The ID of the created project
+		#[serde(flatten)]
+		pub id: WithId<()>,
+	}
+);
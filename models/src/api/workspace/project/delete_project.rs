@@ -0,0 +1,25 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to delete a project. The resources in the project are not deleted,
+	/// and are removed from the project instead.
+	DeleteProject,
+	DELETE "/workspace/:workspace_id/project/:project_id" {
+		/// The ID of the workspace
+		pub workspace_id: Uuid,
+		/// The ID of the project to delete
+		pub project_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.project_id,
+			permission: Permission::Project(ProjectPermission::Delete),
+		}
+	}
+);
@@ -0,0 +1,30 @@
+use super::Project;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to get the details of a project
+	GetProjectInfo,
+	GET "/workspace/:workspace_id/project/:project_id" {
+		/// The ID of the workspace
+		pub workspace_id: Uuid,
+		/// The ID of the project
+		pub project_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.project_id,
+			permission: Permission::Project(ProjectPermission::View),
+		}
+	},
+	response = {
+		/// The details of the project
+		#[serde(flatten)]
+		pub project: WithId<Project>,
+	}
+);
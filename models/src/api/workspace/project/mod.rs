@@ -0,0 +1,40 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The endpoint to add a resource to a project
+mod add_resource_to_project;
+/// The endpoint to create a project
+mod create_project;
+/// The endpoint to delete a project
+mod delete_project;
+/// The endpoint to get the details of a project
+mod get_project_info;
+/// The endpoint to list all the projects in a workspace
+mod list_projects;
+/// The endpoint to remove a resource from a project
+mod remove_resource_from_project;
+/// The endpoint to update the details of a project
+mod update_project;
+
+pub use self::{
+	add_resource_to_project::*,
+	create_project::*,
+	delete_project::*,
+	get_project_info::*,
+	list_projects::*,
+	remove_resource_from_project::*,
+	update_project::*,
+};
+
+/// A project within a workspace. A project is used to group resources in a
+/// workspace, such as deployments, static sites, secrets, volumes and managed
+/// URLs. Roles can be given permissions on a project, which apply to all the
+/// resources in that project.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Project {
+	/// The name of the project. This is unique within a workspace.
+	pub name: String,
+	/// A description of what the project is used for
+	pub description: String,
+}
@@ -0,0 +1,26 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to remove a resource from a project. The resource itself is not deleted.
+	RemoveResourceFromProject,
+	DELETE "/workspace/:workspace_id/project/:project_id/resource/:resource_id" {
+		/// The ID of the workspace
+		pub workspace_id: Uuid,
+		/// The ID of the project
+		pub project_id: Uuid,
+		/// The ID of the resource to remove from the project
+		pub resource_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.project_id,
+			permission: Permission::Project(ProjectPermission::Edit),
+		}
+	}
+);
@@ -0,0 +1,32 @@
+use crate::{prelude::*, utils::constants::RESOURCE_NAME_REGEX};
+
+macros::declare_api_endpoint!(
+	/// Route to update the details of a project
+	UpdateProject,
+	PATCH "/workspace/:workspace_id/project/:project_id" {
+		/// The ID of the workspace
+		pub workspace_id: Uuid,
+		/// The ID of the project to update
+		pub project_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.project_id,
+			permission: Permission::Project(ProjectPermission::Edit),
+		}
+	},
+	request = {
+		/// The new name of the project
+		#[preprocess(optional(trim, regex = RESOURCE_NAME_REGEX))]
+		pub name: Option<String>,
+		/// The new description of the project
+		#[preprocess(optional(trim, length(max = 500)))]
+		pub description: Option<String>,
+	}
+);
@@ -0,0 +1,33 @@
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to approve a request for elevated access to a workspace. The user is given the
+	/// requested role from now until the requested duration runs out.
+	ApproveAccessRequest,
+	POST "/workspace/:workspace_id/rbac/access-request/:request_id/approve" {
+		/// The ID of the workspace
+		pub workspace_id: Uuid,
+		/// The ID of the access request to approve
+		pub request_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::ModifyRoles,
+		}
+	},
+	response = {
+		/// The time after which the role is taken away from the user. This is not
+		/// set if the user already had the role without any expiry
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub valid_until: Option<OffsetDateTime>,
+	}
+);
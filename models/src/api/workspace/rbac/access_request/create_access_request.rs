@@ -0,0 +1,39 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to request a role in a workspace for a limited number of hours. The request has
+	/// to be approved by a user that can modify the roles of the workspace, after which the
+	/// role is given to the user until the requested duration runs out.
+	CreateAccessRequest,
+	POST "/workspace/:workspace_id/rbac/access-request" {
+		/// The ID of the workspace
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::WorkspaceMembershipAuthenticator {
+			extract_workspace_id: |req| req.path.workspace_id,
+		}
+	},
+	request = {
+		/// The role that the user is requesting
+		#[preprocess(none)]
+		pub role_id: Uuid,
+		/// The number of hours that the user needs the role for. This can be at most a week
+		#[preprocess(range(min = 1, max = 168))]
+		pub duration_hours: u32,
+		/// The reason for requesting the access, shown to the approver
+		#[preprocess(trim, length(min = 1, max = 500))]
+		pub reason: String,
+	},
+	response = {
+		/// The ID of the created access request
+		#[serde(flatten)]
+		pub id: WithId<()>,
+	}
+);
@@ -0,0 +1,32 @@
+use super::WorkspaceAccessRequest;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to list all the pending requests for elevated access to a workspace
+	ListAccessRequests,
+	GET "/workspace/:workspace_id/rbac/access-request" {
+		/// The ID of the workspace
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::ViewRoles,
+		}
+	},
+	pagination = true,
+	response_headers = {
+		/// The total number of pending access requests in the workspace
+		pub total_count: TotalCountHeader,
+	},
+	response = {
+		/// The list of pending access requests in the workspace
+		pub access_requests: Vec<WithId<WorkspaceAccessRequest>>,
+	}
+);
@@ -0,0 +1,41 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// The endpoint to approve a request for elevated access to a workspace
+mod approve_access_request;
+/// The endpoint to request elevated access to a workspace
+mod create_access_request;
+/// The endpoint to list all the pending requests for elevated access to a
+/// workspace
+mod list_access_requests;
+/// The endpoint to reject a request for elevated access to a workspace
+mod reject_access_request;
+
+pub use self::{
+	approve_access_request::*,
+	create_access_request::*,
+	list_access_requests::*,
+	reject_access_request::*,
+};
+
+/// A pending request by a user for a role in a workspace for a limited amount
+/// of time. Once approved, the user is given the role until the requested
+/// duration runs out, after which the role is automatically taken away.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceAccessRequest {
+	/// The userId of the user that requested the access
+	pub user_id: Uuid,
+	/// The role that the user requested
+	pub role_id: Uuid,
+	/// The number of hours that the user requested the role for
+	pub duration_hours: u32,
+	/// The reason the user gave for requesting the access
+	pub reason: String,
+	/// The time at which the access was requested
+	#[schemars(with = "String")]
+	pub created: OffsetDateTime,
+}
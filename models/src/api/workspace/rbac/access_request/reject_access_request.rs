@@ -0,0 +1,25 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to reject a request for elevated access to a workspace. The request is deleted
+	/// and the user is not given the requested role.
+	RejectAccessRequest,
+	DELETE "/workspace/:workspace_id/rbac/access-request/:request_id" {
+		/// The ID of the workspace
+		pub workspace_id: Uuid,
+		/// The ID of the access request to reject
+		pub request_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::ModifyRoles,
+		}
+	},
+);
@@ -0,0 +1,106 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{prelude::*, rbac::ResourcePermissionTypeDiscriminant};
+
+/// The reason a permission on a resource was either granted or denied to a user
+/// (or an API token) in a workspace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PermissionDecision {
+	/// The user is the super admin of the workspace, and has all permissions
+	SuperAdmin,
+	/// The API token has super admin access to the workspace, and has all
+	/// permissions
+	ApiTokenSuperAdmin,
+	/// The user is not a member of the workspace, or none of their roles in the
+	/// workspace are currently valid. API tokens lose access to a workspace
+	/// once the user that created them is no longer a member.
+	NotAMember,
+	/// The decision was made based on the rules for the permission. A matching
+	/// include rule always grants the permission. Otherwise, the permission is
+	/// granted only if there is at least one exclude rule and none of the
+	/// exclude rules match the resource.
+	#[serde(rename_all = "camelCase")]
+	Rules {
+		/// All the rules for the permission that were evaluated
+		rules: Vec<PermissionRule>,
+	},
+}
+
+/// A rule for a permission, given either by a role of the user or by the API
+/// token itself.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionRule {
+	/// Where the rule comes from
+	pub source: PermissionRuleSource,
+	/// Whether the rule includes or excludes the resources listed in it
+	pub permission_type: ResourcePermissionTypeDiscriminant,
+	/// The ID listed in the rule that matched the resource. This is either the
+	/// resource itself, or the project that the resource is a part of. Not set
+	/// if the rule does not list the resource.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub matched_resource_id: Option<Uuid>,
+}
+
+/// Where a [`PermissionRule`] comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PermissionRuleSource {
+	/// The rule is a part of a role assigned to the user
+	#[serde(rename_all = "camelCase")]
+	Role {
+		/// The ID of the role
+		role_id: Uuid,
+		/// The name of the role
+		name: String,
+	},
+	/// The rule is a part of the permissions of the API token
+	#[serde(rename_all = "camelCase")]
+	ApiToken {
+		/// The ID of the API token
+		token_id: Uuid,
+	},
+}
+
+macros::declare_api_endpoint!(
+	/// Route to explain whether a user (or an API token) has a permission on a resource in
+	/// the workspace, along with the roles and rules that led to the decision. Exactly one of
+	/// `userId` or `tokenId` must be provided.
+	ExplainPermission,
+	GET "/workspace/:workspace_id/rbac/explain-permission" {
+		/// The ID of the workspace
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::ViewRoles,
+		}
+	},
+	query = {
+		/// The user to explain the permission for
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub user_id: Option<Uuid>,
+		/// The API token to explain the permission for
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub token_id: Option<Uuid>,
+		/// The ID of the permission to check
+		pub permission_id: Uuid,
+		/// The ID of the resource to check the permission on
+		pub resource_id: Uuid,
+	},
+	response = {
+		/// Whether the permission is granted
+		pub allowed: bool,
+		/// The reason the permission was granted or denied
+		pub decision: PermissionDecision,
+	}
+);
@@ -0,0 +1,27 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to accept an invite to a workspace. The invite must have been sent to the
+	/// user that is currently logged in, either by their username or by one of their
+	/// verified emails.
+	AcceptWorkspaceInvite,
+	POST "/workspace/invite/accept",
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::PlainTokenAuthenticator
+	},
+	request = {
+		/// The signed token of the invite, from the invite link
+		#[preprocess(trim, length(min = 1))]
+		pub invite_token: String,
+	},
+	response = {
+		/// The ID of the workspace that the user is now a member of
+		pub workspace_id: Uuid,
+	}
+);
@@ -0,0 +1,40 @@
+use super::WorkspaceInvitee;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to invite a user to a workspace with a set of roles. The user becomes a member
+	/// of the workspace with those roles once they accept the invite.
+	CreateWorkspaceInvite,
+	POST "/workspace/:workspace_id/rbac/invite" {
+		/// The ID of the workspace
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::ModifyRoles,
+		}
+	},
+	request = {
+		/// The user to invite to the workspace
+		#[serde(flatten)]
+		pub invitee: WorkspaceInvitee,
+		/// The list of roles the user will have once they accept the invite
+		#[preprocess(none)]
+		pub roles: Vec<Uuid>,
+	},
+	response = {
+		/// The ID of the created invite
+		#[serde(flatten)]
+		pub id: WithId<()>,
+		/// The signed token of the invite. This is used in the invite link, and
+		/// can only be used by the invited user to accept or decline the invite.
+		pub invite_token: String,
+	}
+);
@@ -0,0 +1,22 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to decline an invite to a workspace. The invite must have been sent to the
+	/// user that is currently logged in.
+	DeclineWorkspaceInvite,
+	POST "/workspace/invite/decline",
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::PlainTokenAuthenticator
+	},
+	request = {
+		/// The signed token of the invite, from the invite link
+		#[preprocess(trim, length(min = 1))]
+		pub invite_token: String,
+	},
+);
@@ -0,0 +1,32 @@
+use super::WorkspaceInvite;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to list all the pending invites of a workspace
+	ListWorkspaceInvites,
+	GET "/workspace/:workspace_id/rbac/invite" {
+		/// The ID of the workspace
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	pagination = true,
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::ViewRoles,
+		}
+	},
+	response_headers = {
+		/// The total number of items in the pagination
+		pub total_count: TotalCountHeader,
+	},
+	response = {
+		/// The list of pending invites of the workspace
+		pub invites: Vec<WithId<WorkspaceInvite>>,
+	}
+);
@@ -0,0 +1,73 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::{prelude::*, utils::constants::USERNAME_VALIDITY_REGEX};
+
+/// The endpoint to accept an invite to a workspace
+mod accept_workspace_invite;
+/// The endpoint to invite a user to a workspace
+mod create_workspace_invite;
+/// The endpoint to decline an invite to a workspace
+mod decline_workspace_invite;
+/// The endpoint to list all the pending invites of a workspace
+mod list_workspace_invites;
+/// The endpoint to resend an invite to a workspace
+mod resend_workspace_invite;
+/// The endpoint to revoke an invite to a workspace
+mod revoke_workspace_invite;
+
+pub use self::{
+	accept_workspace_invite::*,
+	create_workspace_invite::*,
+	decline_workspace_invite::*,
+	list_workspace_invites::*,
+	resend_workspace_invite::*,
+	revoke_workspace_invite::*,
+};
+
+/// The user that is invited to a workspace. A user can either be invited by
+/// their email (in which case they may not have an account yet), or by their
+/// username.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(untagged)]
+#[preprocess::sync]
+pub enum WorkspaceInvitee {
+	#[serde(rename_all = "camelCase")]
+	/// Invite a user by their email. The invite can be accepted by any user
+	/// that has this email verified on their account, including a user that
+	/// signs up with this email using the invite.
+	Email {
+		/// The email of the user to invite
+		#[preprocess(email)]
+		email: String,
+	},
+	#[serde(rename_all = "camelCase")]
+	/// Invite an existing user by their username
+	Username {
+		/// The username of the user to invite
+		#[preprocess(trim, length(min = 2), regex = USERNAME_VALIDITY_REGEX)]
+		username: String,
+	},
+}
+
+/// A pending invite for a user to join a workspace
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceInvite {
+	/// The user that is invited to the workspace
+	#[serde(flatten)]
+	pub invitee: WorkspaceInvitee,
+	/// The roles that the user will have in the workspace once they accept the
+	/// invite
+	pub roles: Vec<Uuid>,
+	/// The userId of the user that created the invite
+	pub invited_by: Uuid,
+	/// The time at which the invite was created
+	#[schemars(with = "String")]
+	pub created: OffsetDateTime,
+	/// The time after which the invite can no longer be accepted. Resending
+	/// the invite extends this.
+	#[schemars(with = "String")]
+	pub expiry: OffsetDateTime,
+}
@@ -0,0 +1,29 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to resend an invite to a workspace. This generates a new invite token and
+	/// extends the expiry of the invite. Any previously sent invite link stops working.
+	ResendWorkspaceInvite,
+	POST "/workspace/:workspace_id/rbac/invite/:invite_id/resend" {
+		/// The ID of the workspace
+		pub workspace_id: Uuid,
+		/// The ID of the invite to resend
+		pub invite_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::ModifyRoles,
+		}
+	},
+	response = {
+		/// The new signed token of the invite
+		pub invite_token: String,
+	}
+);
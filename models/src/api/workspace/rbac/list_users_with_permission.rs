@@ -0,0 +1,34 @@
+use super::PermissionDecision;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to list all the users in the workspace that have a permission on a resource,
+	/// along with the reason each of them has the permission
+	ListUsersWithPermission,
+	GET "/workspace/:workspace_id/rbac/permission/:permission_id/users" {
+		/// The ID of the workspace
+		pub workspace_id: Uuid,
+		/// The ID of the permission to check
+		pub permission_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize user
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::ViewRoles,
+		}
+	},
+	query = {
+		/// The ID of the resource to check the permission on
+		pub resource_id: Uuid,
+	},
+	response = {
+		/// The users that have the permission, along with the reason they have it
+		pub users: Vec<WithId<PermissionDecision>>,
+	}
+);
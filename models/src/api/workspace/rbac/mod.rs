@@ -1,13 +1,29 @@
+/// The models that corresponds to users requesting elevated access to a
+/// workspace for a limited amount of time
+pub mod access_request;
+/// The models that corresponds to inviting users to a workspace
+pub mod invite;
 /// The models that corresponds to all role RBAC in a workspace on resources
 pub mod role;
 /// The models that corresponds to all user RBAC in a workspace
 pub mod user;
 
+/// The endpoint to explain why a user has (or does not have) a permission on a
+/// resource
+mod explain_permission;
 /// The endpoint to get the current permissions of the user in the workspace
 mod get_current_permissions;
 /// The endpoint to list all the permissions in the workspace
 mod list_all_permissions;
 /// The endpoint to list all the resource types in the workspace
 mod list_all_resource_types;
+/// The endpoint to list all the users that have a permission on a resource
+mod list_users_with_permission;
 
-pub use self::{get_current_permissions::*, list_all_permissions::*, list_all_resource_types::*};
+pub use self::{
+	explain_permission::*,
+	get_current_permissions::*,
+	list_all_permissions::*,
+	list_all_resource_types::*,
+	list_users_with_permission::*,
+};
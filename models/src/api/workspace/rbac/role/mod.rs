@@ -11,6 +11,7 @@ mod list_users_for_role;
 /// The endpoint to update the details of a role in the workspace
 mod update_role;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 pub use self::{
@@ -23,7 +24,7 @@ pub use self::{
 };
 
 /// The role metadata
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Role {
 	/// The name of the role
@@ -1,7 +1,10 @@
+use time::OffsetDateTime;
+
 use crate::prelude::*;
 
 macros::declare_api_endpoint!(
-	/// Route to update the roles of a user in a workspace
+	/// Route to update the roles of a user in a workspace. The roles can optionally be
+	/// given for a limited amount of time, after which they are automatically taken away
 	UpdateUserRolesInWorkspace,
 	POST "/workspace/:workspace_id/rbac/user/:user_id" {
 		/// The ID of the workspace
@@ -26,5 +29,15 @@ macros::declare_api_endpoint!(
 		/// added to the workspace
 		#[preprocess(none)]
 		pub roles: Vec<Uuid>,
+		/// The time from which the roles are valid. If not specified, the roles
+		/// are valid immediately
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		#[preprocess(none)]
+		pub valid_from: Option<OffsetDateTime>,
+		/// The time after which the roles are no longer valid. If not specified,
+		/// the roles are valid until they are removed
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		#[preprocess(none)]
+		pub valid_until: Option<OffsetDateTime>,
 	},
 );
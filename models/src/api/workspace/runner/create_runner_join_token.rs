@@ -0,0 +1,82 @@
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to create a one-time join token for a runner. A runner that is started with the join
+	/// token exchanges it for an API token of the given service account, so that the runner does
+	/// not need to be given a long-lived token by hand. Since the runner gets the permissions of
+	/// the service account, this needs the same permission as creating an API token for the
+	/// service account
+	CreateRunnerJoinToken,
+	POST "/workspace/:workspace_id/runner/:runner_id/join-token" {
+		/// The ID of the workspace the runner is in
+		pub workspace_id: Uuid,
+		/// The ID of the runner to create the join token for
+		pub runner_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::EditWorkspace,
+		}
+	},
+	request = {
+		/// The ID of the service account that the runner authenticates as. The service account
+		/// must be in the same workspace as the runner, and needs to be able to edit the runner for
+		/// the runner to report back what it is running
+		#[preprocess(none)]
+		pub service_account_id: Uuid,
+	},
+	response = {
+		/// The join token to start the runner with
+		pub join_token: String,
+		/// The time after which the join token can no longer be used
+		pub expiry: OffsetDateTime,
+	}
+);
+
+#[cfg(test)]
+mod tests {
+	use std::str::FromStr;
+
+	use super::*;
+
+	#[test]
+	fn assert_join_token_needs_workspace_edit_permission() {
+		let workspace_id = Uuid::new_v4();
+		let request = ApiRequest::<CreateRunnerJoinTokenRequest> {
+			path: CreateRunnerJoinTokenPath {
+				workspace_id,
+				runner_id: Uuid::new_v4(),
+			},
+			query: (),
+			headers: CreateRunnerJoinTokenRequestHeaders {
+				authorization: BearerToken::from_str("token").unwrap(),
+				user_agent: UserAgent::from_static("test"),
+			},
+			body: CreateRunnerJoinTokenRequest {
+				service_account_id: Uuid::new_v4(),
+			},
+		};
+
+		// A user who can only edit the runner must not be able to hand the runner a
+		// service account with more permissions than they have
+		let AppAuthentication::ResourcePermissionAuthenticator {
+			extract_resource_id,
+			permission,
+		} = CreateRunnerJoinTokenRequest::get_authenticator()
+		else {
+			panic!("join tokens must be created with a resource permission");
+		};
+		assert_eq!(permission, Permission::EditWorkspace);
+		assert_ne!(permission, Permission::Runner(RunnerPermission::Edit));
+		assert_eq!(extract_resource_id(&request), workspace_id);
+	}
+}
@@ -0,0 +1,34 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route for a runner to exchange a join token for an API token of the service account that
+	/// the join token was created for. A join token can only be exchanged once
+	JoinRunner,
+	POST "/runner/join",
+	request_headers = {
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	rate_limit = {
+		RateLimit::<Self> {
+			bucket: "runner-join",
+			max_requests_per_ip: 20,
+			max_failures_per_account: 5,
+			extract_account: None,
+		}
+	},
+	request = {
+		/// The join token that the runner was started with
+		#[preprocess(trim, length(min = 1))]
+		pub join_token: String,
+	},
+	response = {
+		/// The ID of the workspace the runner is in
+		pub workspace_id: Uuid,
+		/// The ID of the runner
+		pub runner_id: Uuid,
+		/// The API token of the service account, which the runner uses to access the API from
+		/// now on
+		pub api_token: String,
+	}
+);
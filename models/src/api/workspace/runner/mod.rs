@@ -1,22 +1,39 @@
 /// The endpoint to add a runner to a workspace
 mod add_runner_to_workspace;
+/// The endpoint to create a one-time join token for a runner
+mod create_runner_join_token;
 /// The endpoint to get the details of a runner in a workspace
 mod get_runner_info;
+/// The endpoint for a runner to exchange a join token for an API token
+mod join_runner;
 /// The endpoint to list all the runners in a workspace
 mod list_runners_for_workspace;
 /// The endpoint to remove a runner from a workspace
 mod remove_runner_from_workspace;
+/// The endpoint for a runner to report how a CI pipeline run went
+mod report_ci_pipeline_run;
+/// The endpoint for a runner to send the logs of a CI pipeline run
+mod report_ci_step_logs;
+/// The endpoint for a runner to report how many replicas of its deployments
+/// are running
+mod report_running_replicas;
 /// The endpoint to stream the runner data for a workspace
 mod stream_runner_data_for_workspace;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 pub use self::{
 	add_runner_to_workspace::*,
+	create_runner_join_token::*,
 	get_runner_info::*,
+	join_runner::*,
 	list_runners_for_workspace::*,
 	remove_runner_from_workspace::*,
+	report_ci_pipeline_run::*,
+	report_ci_step_logs::*,
+	report_running_replicas::*,
 	stream_runner_data_for_workspace::*,
 };
 
@@ -29,7 +46,7 @@ pub use self::{
 /// runners are arbitrary code that executes the deployments, they can execute
 /// the deployments in any way they want. This includes running the deployments
 /// on a VM, kubernetes, or even on other PaaS providers.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Runner {
 	/// The name of the runner
@@ -37,5 +54,6 @@ pub struct Runner {
 	/// Whether the runner is connected to the Patr API currently or not
 	pub connected: bool,
 	/// The last timestamp the runner was seen online
+	#[schemars(with = "Option<String>")]
 	pub last_seen: Option<OffsetDateTime>,
 }
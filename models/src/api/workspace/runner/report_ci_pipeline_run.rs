@@ -0,0 +1,33 @@
+use crate::{ci::CiPipelineRunReport, prelude::*};
+
+macros::declare_api_endpoint!(
+	/// Route for a runner to report how a CI pipeline run that it was asked to execute went, once
+	/// the run is complete. Only a runner that can edit itself can report the results of its runs
+	ReportCiPipelineRun,
+	POST "/workspace/:workspace_id/runner/:runner_id/ci-run/:run_id/report" {
+		/// The ID of the workspace the runner is in
+		pub workspace_id: Uuid,
+		/// The ID of the runner that executed the run
+		pub runner_id: Uuid,
+		/// The ID of the run
+		pub run_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize the runner
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.runner_id,
+			permission: Permission::Runner(RunnerPermission::Edit),
+		}
+	},
+	request = {
+		/// The report of the run
+		#[preprocess(none)]
+		#[serde(flatten)]
+		pub report: CiPipelineRunReport,
+	},
+);
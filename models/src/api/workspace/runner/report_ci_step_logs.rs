@@ -0,0 +1,37 @@
+use crate::{ci::CiStepLog, prelude::*};
+
+macros::declare_api_endpoint!(
+	/// Route for a runner to send the logs of the steps of a CI pipeline run while the run is
+	/// in progress. Only a runner that can edit itself can send the logs of its runs
+	ReportCiStepLogs,
+	POST "/workspace/:workspace_id/runner/:runner_id/ci-run/:run_id/logs" {
+		/// The ID of the workspace the runner is in
+		pub workspace_id: Uuid,
+		/// The ID of the runner that is executing the run
+		pub runner_id: Uuid,
+		/// The ID of the run
+		pub run_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize the runner
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.runner_id,
+			permission: Permission::Runner(RunnerPermission::Edit),
+		}
+	},
+	request = {
+		/// The number of the first line in `logs`, counting every line of the run from 0. Lines
+		/// that were already received are ignored, so a batch can be sent again if sending it
+		/// failed.
+		#[preprocess(none)]
+		pub first_line: u64,
+		/// The lines of the logs, in the order they were written
+		#[preprocess(none)]
+		pub logs: Vec<CiStepLog>,
+	},
+);
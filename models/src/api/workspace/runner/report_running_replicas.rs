@@ -0,0 +1,35 @@
+use std::collections::BTreeMap;
+
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route for a runner to report how many replicas of each of its deployments are running. The
+	/// deployments are billed for the replicas that the runner reported as running, rather than
+	/// the replicas they were configured with. Since this changes what the workspace is billed
+	/// for, the runner needs to be able to edit itself to report them
+	ReportRunningReplicas,
+	POST "/workspace/:workspace_id/runner/:runner_id/running-replicas" {
+		/// The ID of the workspace the runner is in
+		pub workspace_id: Uuid,
+		/// The ID of the runner that is running the deployments
+		pub runner_id: Uuid,
+	},
+	request_headers = {
+		/// Token used to authorize the runner
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.runner_id,
+			permission: Permission::Runner(RunnerPermission::Edit),
+		}
+	},
+	request = {
+		/// The number of replicas that are running for each deployment of the
+		/// runner, keyed by the ID of the deployment
+		#[preprocess(none)]
+		pub replicas: BTreeMap<Uuid, u16>,
+	},
+);
@@ -1,5 +1,6 @@
 use crate::{
 	api::workspace::deployment::{Deployment, DeploymentRunningDetails},
+	ci::CiPipelineRun,
 	prelude::*,
 	rbac::ResourceType,
 };
@@ -49,17 +50,25 @@ macros::declare_stream_endpoint!(
 			/// The ID of the deployment that was deleted
 			id: Uuid
 		},
+		/// A CI pipeline needs to be run on the runner
+		CiPipelineRunRequested {
+			/// The run of the pipeline
+			#[serde(flatten)]
+			run: WithId<CiPipelineRun>,
+		},
 	},
 	client_msg = {},
 );
 
 impl StreamRunnerDataForWorkspaceServerMsg {
-	/// Get the resource type that this message is related to
-	pub fn resource_type(&self) -> ResourceType {
+	/// Get the resource type that this message is related to, if the message
+	/// is related to a resource at all
+	pub fn resource_type(&self) -> Option<ResourceType> {
 		match self {
-			Self::DeploymentCreated { .. } => ResourceType::Deployment,
-			Self::DeploymentUpdated { .. } => ResourceType::Deployment,
-			Self::DeploymentDeleted { .. } => ResourceType::Deployment,
+			Self::DeploymentCreated { .. } => Some(ResourceType::Deployment),
+			Self::DeploymentUpdated { .. } => Some(ResourceType::Deployment),
+			Self::DeploymentDeleted { .. } => Some(ResourceType::Deployment),
+			Self::CiPipelineRunRequested { .. } => None,
 		}
 	}
 }
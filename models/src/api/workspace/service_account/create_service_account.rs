@@ -0,0 +1,33 @@
+use crate::{prelude::*, utils::constants::RESOURCE_NAME_REGEX};
+
+macros::declare_api_endpoint!(
+	/// Route to create a service account in a workspace. The service account has no permissions
+	/// until it is given roles in the workspace
+	CreateServiceAccount,
+	POST "/workspace/:workspace_id/service-account" {
+		/// The ID of the workspace to create the service account in
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::EditWorkspace,
+		}
+	},
+	request = {
+		/// The name of the service account
+		#[preprocess(trim, regex = RESOURCE_NAME_REGEX)]
+		pub name: String,
+	},
+	response = {
+		/// The ID of the created service account. This is the ID that roles are given to
+		#[serde(flatten)]
+		pub id: WithId<()>,
+	}
+);
@@ -0,0 +1,52 @@
+use ipnetwork::IpNetwork;
+use time::OffsetDateTime;
+
+use crate::{prelude::*, utils::constants::RESOURCE_NAME_REGEX};
+
+macros::declare_api_endpoint!(
+	/// Route to create an API token for a service account. The token has the permissions of the
+	/// roles of the service account
+	CreateServiceAccountApiToken,
+	POST "/workspace/:workspace_id/service-account/:service_account_id/api-token" {
+		/// The ID of the workspace the service account is in
+		pub workspace_id: Uuid,
+		/// The ID of the service account to create the token for
+		pub service_account_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::EditWorkspace,
+		}
+	},
+	request = {
+		/// A user-friendly name for the token
+		#[preprocess(trim, length(min = 4), regex = RESOURCE_NAME_REGEX)]
+		pub name: String,
+		/// The token is rejected if it is used before this time
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		#[preprocess(none)]
+		pub token_nbf: Option<OffsetDateTime>,
+		/// The token is rejected if it is used after this time
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		#[preprocess(none)]
+		pub token_exp: Option<OffsetDateTime>,
+		/// The IP addresses that are allowed to use this token. If this is not specified, then any
+		/// IP address can use this token
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		#[preprocess(none)]
+		pub allowed_ips: Option<Vec<IpNetwork>>,
+	},
+	response = {
+		/// The ID of the created token
+		pub id: Uuid,
+		/// The token itself
+		pub token: String,
+	}
+);
@@ -0,0 +1,25 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to delete a service account. All the API tokens of the service account are revoked,
+	/// and all its roles are taken away
+	DeleteServiceAccount,
+	DELETE "/workspace/:workspace_id/service-account/:service_account_id" {
+		/// The ID of the workspace the service account is in
+		pub workspace_id: Uuid,
+		/// The ID of the service account to delete
+		pub service_account_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::EditWorkspace,
+		}
+	},
+);
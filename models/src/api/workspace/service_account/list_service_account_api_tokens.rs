@@ -0,0 +1,29 @@
+use super::ServiceAccountApiToken;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to list all the API tokens of a service account that are not revoked
+	ListServiceAccountApiTokens,
+	GET "/workspace/:workspace_id/service-account/:service_account_id/api-token" {
+		/// The ID of the workspace the service account is in
+		pub workspace_id: Uuid,
+		/// The ID of the service account to list the tokens of
+		pub service_account_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::EditWorkspace,
+		}
+	},
+	response = {
+		/// The list of API tokens of the service account
+		pub tokens: Vec<WithId<ServiceAccountApiToken>>,
+	}
+);
@@ -0,0 +1,27 @@
+use super::ServiceAccount;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to list all the service accounts of a workspace
+	ListServiceAccounts,
+	GET "/workspace/:workspace_id/service-account" {
+		/// The ID of the workspace to list the service accounts of
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::EditWorkspace,
+		}
+	},
+	response = {
+		/// The list of service accounts of the workspace
+		pub service_accounts: Vec<WithId<ServiceAccount>>,
+	}
+);
@@ -0,0 +1,70 @@
+use ipnetwork::IpNetwork;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+/// The endpoint to create a service account in a workspace
+mod create_service_account;
+/// The endpoint to create an API token for a service account
+mod create_service_account_api_token;
+/// The endpoint to delete a service account
+mod delete_service_account;
+/// The endpoint to list all the API tokens of a service account
+mod list_service_account_api_tokens;
+/// The endpoint to list all the service accounts of a workspace
+mod list_service_accounts;
+/// The endpoint to revoke an API token of a service account
+mod revoke_service_account_api_token;
+
+pub use self::{
+	create_service_account::*,
+	create_service_account_api_token::*,
+	delete_service_account::*,
+	list_service_account_api_tokens::*,
+	list_service_accounts::*,
+	revoke_service_account_api_token::*,
+};
+
+/// A non-human identity that is owned by a workspace. Service accounts are
+/// used by runners, CI jobs and other automation, so that they keep working
+/// regardless of which members are part of the workspace.
+///
+/// A service account is given permissions the same way as a user, by giving it
+/// roles in the workspace through the RBAC endpoints. It authenticates using
+/// its own API tokens, and every action performed by it is recorded in the
+/// audit logs as the service account.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceAccount {
+	/// The name of the service account
+	pub name: String,
+	/// The time at which the service account was created
+	#[schemars(with = "String")]
+	pub created: OffsetDateTime,
+}
+
+/// An API token of a service account. Unlike the API tokens of a user, a
+/// service account's token does not have its own permissions. It always has
+/// the permissions of the roles of the service account.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceAccountApiToken {
+	/// A user-friendly name for the token
+	pub name: String,
+	/// The token is rejected if it is used before this time
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[schemars(with = "Option<String>")]
+	pub token_nbf: Option<OffsetDateTime>,
+	/// The token is rejected if it is used after this time
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[schemars(with = "Option<String>")]
+	pub token_exp: Option<OffsetDateTime>,
+	/// The IP addresses that are allowed to use this token. If this is not
+	/// specified, then any IP address can use this token
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[schemars(with = "Option<Vec<String>>")]
+	pub allowed_ips: Option<Vec<IpNetwork>>,
+	/// The time at which this token was created
+	#[schemars(with = "String")]
+	pub created: OffsetDateTime,
+}
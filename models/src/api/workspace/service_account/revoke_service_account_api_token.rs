@@ -0,0 +1,26 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to revoke an API token of a service account. The token can no longer be used
+	RevokeServiceAccountApiToken,
+	DELETE "/workspace/:workspace_id/service-account/:service_account_id/api-token/:token_id" {
+		/// The ID of the workspace the service account is in
+		pub workspace_id: Uuid,
+		/// The ID of the service account the token belongs to
+		pub service_account_id: Uuid,
+		/// The ID of the token to revoke
+		pub token_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::EditWorkspace,
+		}
+	},
+);
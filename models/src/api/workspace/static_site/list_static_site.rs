@@ -19,6 +19,11 @@ macros::declare_api_endpoint!(
 			extract_workspace_id: |req| req.path.workspace_id
 		}
 	},
+	query = {
+		/// Only list the static sites that are in this project
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub project_id: Option<Uuid>,
+	},
 	pagination = true,
 	response_headers = {
 		/// The total number of databases in the requested workspace
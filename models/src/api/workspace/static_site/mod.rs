@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
@@ -38,7 +39,7 @@ use super::deployment::DeploymentStatus;
 use crate::utils::Uuid;
 
 /// Static site
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct StaticSite {
 	/// Name of the static site
@@ -60,14 +61,14 @@ pub struct StaticSite {
 }
 
 /// Static site details
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct StaticSiteDetails {
 	// add more details here, like metrics, etc.
 }
 
 /// Static site upload history
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct StaticSiteUploadHistory {
 	/// The upload ID
@@ -77,7 +78,9 @@ pub struct StaticSiteUploadHistory {
 	/// The user ID of the user who uploaded
 	pub uploaded_by: Uuid,
 	/// The timestamp of when the static site was created
+	#[schemars(with = "String")]
 	pub created: OffsetDateTime,
 	/// The timestamp of when the static site was processed
+	#[schemars(with = "Option<String>")]
 	pub processed: Option<OffsetDateTime>,
 }
@@ -2,7 +2,8 @@ use crate::prelude::*;
 
 macros::declare_api_endpoint!(
 	/// Route to upload to a static site
-	/// This route will upload a new index.html file which would go live
+	/// This route will upload a new archive of the files of the static site,
+	/// sent as a file in the form, which would go live
 	UploadStaticSite,
 	POST "/workspace/:workspace_id/infrastructure/static-site/:static_site_id/upload" {
 		/// The workspace ID of the user
@@ -22,10 +23,7 @@ macros::declare_api_endpoint!(
 			permission: Permission::StaticSite(StaticSitePermission::Upload),
 		}
 	},
-	request = {
-		/// The new index.html file
-		#[preprocess(trim, lowercase)]
-		pub file: String,
+	request = multipart(max_size = 100 * 1024 * 1024) {
 		/// The release note (eg: v1.0.0)
 		#[preprocess(trim, lowercase)]
 		pub message: String
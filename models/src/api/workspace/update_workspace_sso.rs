@@ -0,0 +1,30 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to update the single sign-on settings of a workspace. When a provider is set,
+	/// members of the workspace must sign in with that external OpenID Connect provider to
+	/// access the workspace.
+	UpdateWorkspaceSso,
+	PUT "/workspace/:workspace_id/sso" {
+		/// The ID of the workspace to update
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::EditWorkspace,
+		}
+	},
+	request = {
+		/// The ID of the provider that members must sign in with. If this is not set,
+		/// members can sign in any way they want to.
+		#[preprocess(optional(trim, length(min = 1)))]
+		pub sso_provider_id: Option<String>,
+	},
+);
@@ -14,6 +14,11 @@ macros::declare_api_endpoint!(
 		/// The user-agent used to access this API
 		pub user_agent: UserAgent,
 	},
+	query = {
+		/// Only list the volumes that are in this project
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub project_id: Option<Uuid>,
+	},
 	pagination = true,
 	authentication = {
 		AppAuthentication::<Self>::WorkspaceMembershipAuthenticator {
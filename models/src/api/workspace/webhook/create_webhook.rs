@@ -0,0 +1,45 @@
+use std::collections::BTreeSet;
+
+use super::WebhookEventType;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to create a webhook in a workspace. The secret used to sign the events sent to the
+	/// webhook is only returned in the response of this route, and cannot be retrieved later
+	CreateWebhook,
+	POST "/workspace/:workspace_id/webhook" {
+		/// The ID of the workspace to create the webhook in
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::EditWorkspace,
+		}
+	},
+	request = {
+		/// The name of the webhook
+		#[preprocess(trim, length(min = 1))]
+		pub name: String,
+		/// The URL to send the events to. This must be an `http` or `https` URL, with a host
+		/// that only resolves to public addresses
+		#[preprocess(trim, length(min = 1))]
+		pub url: String,
+		/// The types of events to send to the webhook
+		#[preprocess(none)]
+		pub event_types: BTreeSet<WebhookEventType>,
+	},
+	response = {
+		/// The ID of the created webhook
+		#[serde(flatten)]
+		pub id: WithId<()>,
+		/// The secret used to sign the events sent to the webhook
+		pub secret: String,
+	}
+);
@@ -0,0 +1,24 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to delete a webhook. Any pending deliveries of the webhook are not attempted anymore
+	DeleteWebhook,
+	DELETE "/workspace/:workspace_id/webhook/:webhook_id" {
+		/// The ID of the workspace the webhook is in
+		pub workspace_id: Uuid,
+		/// The ID of the webhook to delete
+		pub webhook_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::EditWorkspace,
+		}
+	},
+);
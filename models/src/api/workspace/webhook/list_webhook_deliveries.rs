@@ -0,0 +1,39 @@
+use super::{WebhookDelivery, WebhookDeliveryStatus};
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to list the deliveries of events to a webhook, latest first
+	ListWebhookDeliveries,
+	GET "/workspace/:workspace_id/webhook/:webhook_id/delivery" {
+		/// The ID of the workspace the webhook is in
+		pub workspace_id: Uuid,
+		/// The ID of the webhook to list the deliveries of
+		pub webhook_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::EditWorkspace,
+		}
+	},
+	query = {
+		/// Only list the deliveries with this status
+		#[serde(default, skip_serializing_if = "Option::is_none")]
+		pub status: Option<WebhookDeliveryStatus>,
+	},
+	pagination = true,
+	response_headers = {
+		/// The total number of deliveries matching the filters
+		pub total_count: TotalCountHeader,
+	},
+	response = {
+		/// The list of deliveries of the webhook, with the ID of the delivery as the ID
+		pub deliveries: Vec<WithId<WebhookDelivery>>,
+	}
+);
@@ -0,0 +1,27 @@
+use super::Webhook;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to list all the webhooks of a workspace
+	ListWebhooks,
+	GET "/workspace/:workspace_id/webhook" {
+		/// The ID of the workspace to list the webhooks of
+		pub workspace_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::EditWorkspace,
+		}
+	},
+	response = {
+		/// The list of webhooks of the workspace
+		pub webhooks: Vec<WithId<Webhook>>,
+	}
+);
@@ -0,0 +1,241 @@
+use std::collections::BTreeSet;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::prelude::*;
+
+/// The endpoint to create a webhook in a workspace
+mod create_webhook;
+/// The endpoint to delete a webhook
+mod delete_webhook;
+/// The endpoint to list the deliveries of a webhook
+mod list_webhook_deliveries;
+/// The endpoint to list all the webhooks of a workspace
+mod list_webhooks;
+/// The endpoint to deliver an event to a webhook again
+mod redeliver_webhook_delivery;
+/// The endpoint to update a webhook
+mod update_webhook;
+
+pub use self::{
+	create_webhook::*,
+	delete_webhook::*,
+	list_webhook_deliveries::*,
+	list_webhooks::*,
+	redeliver_webhook_delivery::*,
+	update_webhook::*,
+};
+
+/// A webhook of a workspace. Every event of the workspace that the webhook is
+/// subscribed to is sent to the URL of the webhook as a `POST` request, with a
+/// [`WebhookPayload`] as the body. The body is signed with the secret of the
+/// webhook using HMAC-SHA256, and the signature is sent as a hex string in the
+/// `X-Patr-Signature-256` header, prefixed with `sha256=`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+	/// The name of the webhook
+	pub name: String,
+	/// The URL that the events are sent to
+	pub url: String,
+	/// The types of events that are sent to the webhook
+	pub event_types: BTreeSet<WebhookEventType>,
+	/// Whether the webhook is enabled. Events are not sent to a disabled
+	/// webhook
+	pub enabled: bool,
+	/// The time at which the webhook was created
+	#[schemars(with = "String")]
+	pub created: OffsetDateTime,
+}
+
+/// The types of events of a workspace that a webhook can subscribe to
+#[derive(
+	Debug,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Hash,
+	Serialize,
+	Deserialize,
+	JsonSchema,
+)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(
+	not(target_arch = "wasm32"),
+	sqlx(type_name = "WEBHOOK_EVENT_TYPE", rename_all = "snake_case")
+)]
+pub enum WebhookEventType {
+	/// A deployment is created
+	DeploymentCreated,
+	/// The configuration of a deployment is updated
+	DeploymentUpdated,
+	/// The status of a deployment changes
+	DeploymentStatusChanged,
+	/// An image is pushed to a container registry repository
+	ImagePushed,
+	/// A new version of a static site is uploaded
+	StaticSiteUploaded,
+	/// A runner disconnects from Patr
+	RunnerDisconnected,
+	/// A user is added to the workspace with a role
+	MemberAdded,
+}
+
+/// An event that happened in a workspace, along with the details of the event
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum WebhookEvent {
+	/// A deployment is created
+	#[serde(rename_all = "camelCase")]
+	DeploymentCreated {
+		/// The ID of the deployment
+		deployment_id: Uuid,
+	},
+	/// The configuration of a deployment is updated
+	#[serde(rename_all = "camelCase")]
+	DeploymentUpdated {
+		/// The ID of the deployment
+		deployment_id: Uuid,
+	},
+	/// The status of a deployment changes
+	#[serde(rename_all = "camelCase")]
+	DeploymentStatusChanged {
+		/// The ID of the deployment
+		deployment_id: Uuid,
+		/// The new status of the deployment
+		status: String,
+	},
+	/// An image is pushed to a container registry repository
+	#[serde(rename_all = "camelCase")]
+	ImagePushed {
+		/// The ID of the repository
+		repository_id: Uuid,
+		/// The tag that the image is pushed with
+		tag: String,
+		/// The digest of the manifest of the image
+		digest: String,
+	},
+	/// A new version of a static site is uploaded
+	#[serde(rename_all = "camelCase")]
+	StaticSiteUploaded {
+		/// The ID of the static site
+		static_site_id: Uuid,
+		/// The ID of the upload
+		upload_id: Uuid,
+	},
+	/// A runner disconnects from Patr
+	#[serde(rename_all = "camelCase")]
+	RunnerDisconnected {
+		/// The ID of the runner
+		runner_id: Uuid,
+	},
+	/// A user is added to the workspace with a role
+	#[serde(rename_all = "camelCase")]
+	MemberAdded {
+		/// The ID of the user
+		user_id: Uuid,
+		/// The ID of the role the user is added with
+		role_id: Uuid,
+	},
+}
+
+impl WebhookEvent {
+	/// Returns the type of the event, which is used to decide which webhooks
+	/// the event is sent to
+	pub fn event_type(&self) -> WebhookEventType {
+		match self {
+			Self::DeploymentCreated { .. } => WebhookEventType::DeploymentCreated,
+			Self::DeploymentUpdated { .. } => WebhookEventType::DeploymentUpdated,
+			Self::DeploymentStatusChanged { .. } => WebhookEventType::DeploymentStatusChanged,
+			Self::ImagePushed { .. } => WebhookEventType::ImagePushed,
+			Self::StaticSiteUploaded { .. } => WebhookEventType::StaticSiteUploaded,
+			Self::RunnerDisconnected { .. } => WebhookEventType::RunnerDisconnected,
+			Self::MemberAdded { .. } => WebhookEventType::MemberAdded,
+		}
+	}
+}
+
+/// An event of a workspace, as published on the change feed of the database.
+/// Every event has a unique ID, so that an event that is received more than
+/// once is only delivered once.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceEvent {
+	/// The ID of the event
+	pub id: Uuid,
+	/// The ID of the workspace that the event happened in
+	pub workspace_id: Uuid,
+	/// The details of the event
+	pub event: WebhookEvent,
+}
+
+/// The body of the request that is sent to a webhook for an event
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayload {
+	/// The ID of the event. This is the same across redeliveries of the event,
+	/// and can be used to ignore duplicate deliveries
+	pub event_id: Uuid,
+	/// The ID of the workspace that the event happened in
+	pub workspace_id: Uuid,
+	/// The time at which the event happened
+	pub created: OffsetDateTime,
+	/// The details of the event
+	pub event: WebhookEvent,
+}
+
+/// The status of the delivery of an event to a webhook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(
+	not(target_arch = "wasm32"),
+	sqlx(type_name = "WEBHOOK_DELIVERY_STATUS", rename_all = "snake_case")
+)]
+pub enum WebhookDeliveryStatus {
+	/// The event has not been delivered yet, and will be attempted again
+	Pending,
+	/// The webhook responded with a successful status code
+	Succeeded,
+	/// Every attempt to deliver the event failed, and it will not be attempted
+	/// again unless it is redelivered
+	Failed,
+}
+
+/// The delivery of an event to a webhook
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDelivery {
+	/// The ID of the event that is delivered
+	pub event_id: Uuid,
+	/// The type of the event that is delivered
+	pub event_type: WebhookEventType,
+	/// The status of the delivery
+	pub status: WebhookDeliveryStatus,
+	/// The number of times the delivery has been attempted
+	pub attempts: u32,
+	/// The time of the last attempt of the delivery, if any
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[schemars(with = "Option<String>")]
+	pub last_attempt: Option<OffsetDateTime>,
+	/// The time of the next attempt of the delivery, if it is pending
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	#[schemars(with = "Option<String>")]
+	pub next_attempt: Option<OffsetDateTime>,
+	/// The status code that the webhook responded with in the last attempt, if
+	/// it responded at all
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub response_status_code: Option<u16>,
+	/// The error of the last attempt, if it failed
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub last_error: Option<String>,
+	/// The time at which the delivery was created
+	#[schemars(with = "String")]
+	pub created: OffsetDateTime,
+}
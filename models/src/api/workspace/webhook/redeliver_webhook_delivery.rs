@@ -0,0 +1,27 @@
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to deliver an event to a webhook again. The delivery is reset to pending, and is
+	/// attempted again along with all its retries, regardless of whether it succeeded or failed
+	RedeliverWebhookDelivery,
+	POST "/workspace/:workspace_id/webhook/:webhook_id/delivery/:delivery_id/redeliver" {
+		/// The ID of the workspace the webhook is in
+		pub workspace_id: Uuid,
+		/// The ID of the webhook
+		pub webhook_id: Uuid,
+		/// The ID of the delivery to attempt again
+		pub delivery_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::EditWorkspace,
+		}
+	},
+);
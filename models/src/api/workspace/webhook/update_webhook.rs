@@ -0,0 +1,41 @@
+use std::collections::BTreeSet;
+
+use super::WebhookEventType;
+use crate::prelude::*;
+
+macros::declare_api_endpoint!(
+	/// Route to update a webhook. Only the parameters that are provided are updated
+	UpdateWebhook,
+	PATCH "/workspace/:workspace_id/webhook/:webhook_id" {
+		/// The ID of the workspace the webhook is in
+		pub workspace_id: Uuid,
+		/// The ID of the webhook to update
+		pub webhook_id: Uuid,
+	},
+	request_headers = {
+		/// The authorization token
+		pub authorization: BearerToken,
+		/// The user-agent used to access this API
+		pub user_agent: UserAgent,
+	},
+	authentication = {
+		AppAuthentication::<Self>::ResourcePermissionAuthenticator {
+			extract_resource_id: |req| req.path.workspace_id,
+			permission: Permission::EditWorkspace,
+		}
+	},
+	request = {
+		/// The new name of the webhook
+		#[preprocess(optional(trim, length(min = 1)))]
+		pub name: Option<String>,
+		/// The new URL to send the events to
+		#[preprocess(optional(trim, length(min = 1)))]
+		pub url: Option<String>,
+		/// The new types of events to send to the webhook
+		#[preprocess(none)]
+		pub event_types: Option<BTreeSet<WebhookEventType>>,
+		/// Whether the webhook should be enabled
+		#[preprocess(none)]
+		pub enabled: Option<bool>,
+	},
+);
@@ -1,10 +1,15 @@
-use std::{collections::BTreeMap, fmt::Display, ops::Deref};
+use std::{collections::BTreeMap, fmt::Display, ops::Deref, time::Duration};
 
 use monostate::MustBe;
+use schemars::{
+	gen::SchemaGenerator,
+	schema::{InstanceType, Schema, SchemaObject},
+	JsonSchema,
+};
 use serde::{Deserialize, Serialize};
 
 /// Indicates the type of task
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(deny_unknown_fields, tag = "kind")]
 pub enum CiFlow {
 	/// CI pipeline task
@@ -12,10 +17,11 @@ pub enum CiFlow {
 }
 
 /// Pipeline action defines the CI pipeline steps which will be executed.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Pipeline {
 	/// version of pipeline
+	#[schemars(schema_with = "pipeline_version_schema")]
 	pub version: MustBe!("v0"),
 	/// name of pipeline
 	pub name: String,
@@ -24,20 +30,47 @@ pub struct Pipeline {
 	pub services: Vec<Service>,
 	/// list of steps to be executed in a single pipeline
 	pub steps: Vec<Step>,
+	/// the number of minutes the whole pipeline may run for before it is
+	/// stopped and marked as failed. Defaults to 60 minutes
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub timeout: Option<u32>,
+}
+
+impl Pipeline {
+	/// The number of minutes a pipeline may run for if it does not set a
+	/// timeout
+	pub const DEFAULT_TIMEOUT_MINUTES: u32 = 60;
+
+	/// The time the whole pipeline may run for
+	pub fn timeout(&self) -> Duration {
+		minutes(self.timeout.unwrap_or(Self::DEFAULT_TIMEOUT_MINUTES))
+	}
+}
+
+/// The JSON schema of the version of a pipeline, which can only be `v0`
+fn pipeline_version_schema(_: &mut SchemaGenerator) -> Schema {
+	SchemaObject {
+		instance_type: Some(InstanceType::String.into()),
+		const_value: Some("v0".into()),
+		..Default::default()
+	}
+	.into()
 }
 
 /// Step represent a single unit of work or a decision block which will be done
 /// in pipeline
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(untagged)]
 pub enum Step {
+	/// A unit of work, which runs commands in a container
 	Work(Work),
+	/// A decision block, which decides the step to run next
 	Decision(Decision),
 }
 
 /// A decision block decides the next steps based on the branches and events
 /// during CI initialization
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Decision {
 	/// name of the decision
@@ -54,8 +87,9 @@ pub struct Decision {
 }
 
 /// A decision block decides the next steps based on the branches and events
-/// during CI initialization
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// during CI initialization. At least one of the conditions must be defined,
+/// which is checked when the pipeline is validated.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct When {
 	/// Represents the list of branch in glob pattern which will be matched
@@ -64,21 +98,27 @@ pub struct When {
 	/// Represents the list of events which will match one of git event
 	#[serde(default, skip_serializing_if = "Vec::is_empty")]
 	pub event: Vec<Event>,
-	// TODO: need to add constraint that atleast one of the condition should be
-	// defined
 }
 
 /// Event represents a type of action in git provider
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(sqlx::Type))]
 #[serde(rename_all = "snake_case")]
+#[cfg_attr(
+	not(target_arch = "wasm32"),
+	sqlx(type_name = "CI_EVENT", rename_all = "snake_case")
+)]
 pub enum Event {
+	/// A commit was pushed to a branch
 	Commit,
+	/// A tag was pushed
 	Tag,
+	/// A pull request was opened or updated
 	Pull,
 }
 
 /// Work represent a single unit of work which will be done in pipeline
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Work {
 	/// name of the work
@@ -91,19 +131,34 @@ pub struct Work {
 	pub commands: OneOrMany<String>,
 	/// list of environmental variables that has to be defined while
 	/// initializing container
-	#[serde(
-		default,
-		alias = "env",
-		skip_serializing_if = "BTreeMap::is_empty"
-	)]
+	#[serde(default, alias = "env", skip_serializing_if = "BTreeMap::is_empty")]
 	pub environment: BTreeMap<String, EnvVarValue>,
 	/// the next command that has to be running after this command
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub next: Option<String>,
+	/// the number of minutes the step may run for before its container is
+	/// killed and the step is marked as failed. Defaults to 10 minutes
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub timeout: Option<u32>,
+}
+
+impl Work {
+	/// The number of minutes a step may run for if it does not set a timeout
+	pub const DEFAULT_TIMEOUT_MINUTES: u32 = 10;
+
+	/// The time the step may run for
+	pub fn timeout(&self) -> Duration {
+		minutes(self.timeout.unwrap_or(Self::DEFAULT_TIMEOUT_MINUTES))
+	}
+}
+
+/// A duration of the given number of minutes
+fn minutes(minutes: u32) -> Duration {
+	Duration::from_secs(u64::from(minutes) * 60)
 }
 
 /// Service represents a background job which will run during pipeline
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Service {
 	/// name of the service
@@ -112,26 +167,18 @@ pub struct Service {
 	pub image: String,
 	/// list of commands to be executed with the given image.
 	/// these commands will start executing from the repo source
-	#[serde(
-		default,
-		alias = "command",
-		skip_serializing_if = "Option::is_none"
-	)]
+	#[serde(default, alias = "command", skip_serializing_if = "Option::is_none")]
 	pub commands: Option<OneOrMany<String>>,
 	/// list of environmental variables that has to be defined while
 	/// initializing container
-	#[serde(
-		default,
-		alias = "env",
-		skip_serializing_if = "BTreeMap::is_empty"
-	)]
+	#[serde(default, alias = "env", skip_serializing_if = "BTreeMap::is_empty")]
 	pub environment: BTreeMap<String, EnvVarValue>,
 	/// TCP port to access this service
 	pub port: u16,
 }
 
 /// A decorative wrapper to use either a one or many values of same type
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, JsonSchema)]
 #[serde(untagged)]
 pub enum OneOrMany<T> {
 	/// A single value
@@ -149,19 +196,26 @@ impl<T> From<OneOrMany<T>> for Vec<T> {
 	}
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+/// The value of an environment variable of a step or a service
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(untagged, rename_all = "snake_case")]
 pub enum EnvVarValue {
+	/// A plain value
 	Value(String),
-	ValueFromSecret { from_secret: String },
+	/// The value of a secret of the workspace
+	ValueFromSecret {
+		/// The name of the secret
+		from_secret: String,
+	},
 }
 
 /// A wrapped string type used to represent the valid naming
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, JsonSchema)]
 #[serde(try_from = "String", into = "String")]
 pub struct LabelName(String);
 
 impl LabelName {
+	/// Check if the given name can be used as a label
 	pub fn is_valid_label_name(name: &str) -> bool {
 		// https://kubernetes.io/docs/concepts/overview/working-with-objects/names/#dns-subdomain-names
 		// 1. 0 < label.len() <= 63
@@ -176,6 +230,7 @@ impl LabelName {
 			!name.ends_with('-')
 	}
 
+	/// Get the name as a string slice
 	pub fn as_str(&self) -> &str {
 		&self.0
 	}
@@ -0,0 +1,315 @@
+use super::CiSpan;
+
+/// A single node of a block-style YAML document. A line that starts a list
+/// item, such as `- name: build`, is split into a node for the `-` and a node
+/// for the rest of the line, indented to where the rest starts.
+#[derive(Debug, Clone, Copy)]
+struct Node<'a> {
+	/// The line the node is on, starting from 0
+	line: usize,
+	/// The column the node starts at, starting from 0
+	indent: usize,
+	/// Whether the node is the `-` that starts a list item
+	is_item: bool,
+	/// The text of the node
+	text: &'a str,
+}
+
+/// A segment of the path to a field of a CI file
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment<'a> {
+	/// A field of a map
+	Key(&'a str),
+	/// An item of a list
+	Index(usize),
+}
+
+/// Find where the field at the given path is in the source of a CI file, such
+/// as `steps[1].then`. The value of the field is pointed at if it is on the
+/// same line as the field, and the field itself otherwise. If the field cannot
+/// be found, the closest field that contains it is pointed at instead.
+///
+/// Only block-style YAML is understood, which is what CI files are written in.
+/// Values written in flow style, such as `[main, develop]`, are pointed at as
+/// a whole.
+pub(super) fn locate(source: &str, path: &str) -> Option<CiSpan> {
+	locate_node(source, path, true)
+}
+
+/// Find where the field at the given path is in the source of a CI file, like
+/// [`locate`], but always point at the field itself instead of its value
+pub(super) fn locate_key(source: &str, path: &str) -> Option<CiSpan> {
+	locate_node(source, path, false)
+}
+
+/// Find where the field at the given path is in the source of a CI file,
+/// pointing at the value of the field if it is on the same line and
+/// `prefer_value` is set
+fn locate_node(source: &str, path: &str, prefer_value: bool) -> Option<CiSpan> {
+	let nodes = parse_nodes(source);
+	let mut block = nodes.as_slice();
+	let mut found = None;
+
+	for segment in parse_path(path) {
+		let Some(indent) = block.first().map(|node| node.indent) else {
+			break;
+		};
+
+		match segment {
+			Segment::Key(key) => {
+				let Some((position, value_offset)) =
+					block.iter().enumerate().find_map(|(position, node)| {
+						(!node.is_item && node.indent == indent)
+							.then(|| key_value_offset(node.text, key))
+							.flatten()
+							.map(|offset| (position, offset))
+					})
+				else {
+					break;
+				};
+				let node = block[position];
+				let value = strip_comment(&node.text[value_offset..]);
+
+				found = Some(
+					if value.is_empty() || !prefer_value {
+						CiSpan {
+							line: node.line + 1,
+							column: node.indent + 1,
+							length: node.text[..value_offset]
+								.trim_end()
+								.trim_end_matches(':')
+								.len(),
+						}
+					} else {
+						CiSpan {
+							line: node.line + 1,
+							column: node.indent + value_offset + 1,
+							length: value.len(),
+						}
+					},
+				);
+
+				// A value on the same line has no fields within it that can be
+				// found
+				if !value.is_empty() {
+					break;
+				}
+
+				// Lists may be indented as much as the field they are the value
+				// of
+				let children = &block[position + 1..];
+				let length = children
+					.iter()
+					.take_while(|child| {
+						child.indent > indent || (child.indent == indent && child.is_item)
+					})
+					.count();
+				block = &children[..length];
+			}
+			Segment::Index(index) => {
+				let Some(position) = block
+					.iter()
+					.enumerate()
+					.filter(|(_, node)| node.is_item && node.indent == indent)
+					.map(|(position, _)| position)
+					.nth(index)
+				else {
+					break;
+				};
+				let item = block[position];
+
+				let children = &block[position + 1..];
+				let length = children
+					.iter()
+					.take_while(|child| child.indent > indent)
+					.count();
+				block = &children[..length];
+
+				found = Some(match block.first() {
+					Some(first) if first.line == item.line => CiSpan {
+						line: first.line + 1,
+						column: first.indent + 1,
+						length: strip_comment(first.text).len(),
+					},
+					_ => CiSpan {
+						line: item.line + 1,
+						column: item.indent + 1,
+						length: 1,
+					},
+				});
+			}
+		}
+	}
+
+	found
+}
+
+/// Split the source of a YAML document into its nodes, skipping blank lines,
+/// comments and document markers
+fn parse_nodes(source: &str) -> Vec<Node<'_>> {
+	let mut nodes = Vec::new();
+
+	for (line, text) in source.lines().enumerate() {
+		let mut indent = text.len() - text.trim_start_matches(' ').len();
+		let mut text = text[indent..].trim_end();
+		if text.is_empty() || text.starts_with('#') || text == "---" || text == "..." {
+			continue;
+		}
+
+		while text == "-" || text.starts_with("- ") {
+			nodes.push(Node {
+				line,
+				indent,
+				is_item: true,
+				text: "-",
+			});
+			let rest = &text[1..];
+			let spaces = rest.len() - rest.trim_start_matches(' ').len();
+			indent += 1 + spaces;
+			text = &rest[spaces..];
+		}
+
+		if !text.is_empty() {
+			nodes.push(Node {
+				line,
+				indent,
+				is_item: false,
+				text,
+			});
+		}
+	}
+
+	nodes
+}
+
+/// Split a path such as `steps[1].environment.TOKEN` into its segments
+fn parse_path(path: &str) -> Vec<Segment<'_>> {
+	let mut segments = Vec::new();
+
+	for part in path.split('.').filter(|part| !part.is_empty()) {
+		let (key, mut indices) = part
+			.find('[')
+			.map_or((part, ""), |position| part.split_at(position));
+		if !key.is_empty() {
+			segments.push(Segment::Key(key));
+		}
+		while let Some((index, rest)) = indices
+			.strip_prefix('[')
+			.and_then(|indices| indices.split_once(']'))
+		{
+			if let Ok(index) = index.parse() {
+				segments.push(Segment::Index(index));
+			}
+			indices = rest;
+		}
+	}
+
+	segments
+}
+
+/// If the text of a node is the given field, or one of its aliases, return
+/// where the value of the field starts within the text
+fn key_value_offset(text: &str, key: &str) -> Option<usize> {
+	let aliases: &[&str] = match key {
+		"commands" => &["commands", "command"],
+		"environment" => &["environment", "env"],
+		key => return key_value_offset_exact(text, key),
+	};
+
+	aliases
+		.iter()
+		.find_map(|alias| key_value_offset_exact(text, alias))
+}
+
+/// If the text of a node is exactly the given field, which may be quoted,
+/// return where the value of the field starts within the text
+fn key_value_offset_exact(text: &str, key: &str) -> Option<usize> {
+	let quote = text
+		.chars()
+		.next()
+		.filter(|quote| matches!(quote, '"' | '\''));
+	let quote_length = quote.map_or(0, char::len_utf8);
+
+	let rest = text[quote_length..].strip_prefix(key)?;
+	let rest = match quote {
+		Some(quote) => rest.strip_prefix(quote)?,
+		None => rest,
+	};
+	let rest = rest.strip_prefix(':')?;
+	if !rest.is_empty() && !rest.starts_with(' ') {
+		return None;
+	}
+
+	let spaces = rest.len() - rest.trim_start().len();
+	Some(text.len() - rest.len() + spaces)
+}
+
+/// Remove a comment at the end of a value
+fn strip_comment(value: &str) -> &str {
+	value
+		.find(" #")
+		.map_or(value, |position| &value[..position])
+		.trim_end()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{locate, locate_key};
+	use crate::ci::CiSpan;
+
+	const SOURCE: &str = r#"kind: Pipeline
+version: v0
+name: build
+services:
+  - name: postgres
+    image: postgres:16
+    port: 5432
+steps:
+- name: test
+  image: rust
+  command:
+    - cargo test # run the tests
+  env:
+    TOKEN:
+      from_secret: token
+  next: should-deploy
+- name: should-deploy
+  when:
+    branch: [main]
+  then: deploy
+"#;
+
+	fn span(line: usize, column: usize, length: usize) -> Option<CiSpan> {
+		Some(CiSpan {
+			line,
+			column,
+			length,
+		})
+	}
+
+	#[test]
+	fn assert_fields_are_located() {
+		for (path, expected) in [
+			("name", span(3, 7, 5)),
+			("services[0].port", span(7, 11, 4)),
+			("services[0]", span(5, 5, 14)),
+			("steps[0].next", span(16, 9, 13)),
+			("steps[0].commands", span(11, 3, 7)),
+			("steps[0].commands[0]", span(12, 7, 10)),
+			("steps[0].environment.TOKEN", span(14, 5, 5)),
+			("steps[0].environment.TOKEN.from_secret", span(15, 20, 5)),
+			("steps[1].then", span(20, 9, 6)),
+			("steps[1].when.branch", span(19, 13, 6)),
+			("steps[1].when.branch[0]", span(19, 13, 6)),
+			// Fields that do not exist point to the closest field that does
+			("steps[1].else", span(17, 3, 19)),
+			("steps[5]", span(8, 1, 5)),
+			("unknown", None),
+		] {
+			assert_eq!(locate(SOURCE, path), expected, "locating `{}`", path);
+		}
+
+		assert_eq!(locate_key(SOURCE, "steps[0].next"), span(16, 3, 4));
+		assert_eq!(locate_key(SOURCE, "steps[0].commands"), span(11, 3, 7));
+	}
+}
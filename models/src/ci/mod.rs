@@ -1 +1,13 @@
+/// The format of the CI files in a repository
+mod file_format;
+/// Finding where the fields of a CI file are in its source
+mod locate;
+/// The runs of CI pipelines, and the reports of how they went
+mod run;
+/// Validating CI files, and the problems that can be found in them
+mod validate;
+/// The git providers that send webhooks for the events of a repository, and
+/// the events that are parsed from them
+mod webhook;
 
+pub use self::{file_format::*, run::*, validate::*, webhook::*};
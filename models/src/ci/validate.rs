@@ -0,0 +1,639 @@
+use std::{
+	collections::{BTreeMap, HashMap},
+	fmt::{Display, Formatter},
+};
+
+use schemars::{schema::RootSchema, JsonSchema};
+use serde::{Deserialize, Serialize};
+
+use super::{
+	locate::{locate, locate_key},
+	CiFlow,
+	Decision,
+	EnvVarValue,
+	OneOrMany,
+	Pipeline,
+	Service,
+	Step,
+	Work,
+};
+
+/// How severe a problem found in a CI file is
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum CiDiagnosticSeverity {
+	/// The file cannot be run until the problem is fixed
+	Error,
+	/// The file can be run, but probably does not do what was intended
+	Warning,
+}
+
+/// Where a problem is in a CI file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CiSpan {
+	/// The line of the problem, starting from 1
+	pub line: usize,
+	/// The column of the problem, starting from 1
+	pub column: usize,
+	/// The number of characters that the problem spans
+	pub length: usize,
+}
+
+/// A problem found in a CI file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CiDiagnostic {
+	/// How severe the problem is
+	pub severity: CiDiagnosticSeverity,
+	/// The field that the problem is in, such as `steps[1].then`. This is empty
+	/// if the problem is with the file as a whole.
+	pub path: String,
+	/// What the problem is
+	pub message: String,
+	/// Where the problem is in the file, if it can be found
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub span: Option<CiSpan>,
+}
+
+/// The result of validating a CI file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CiFileValidation {
+	/// The contents of the file, if the file could be parsed
+	pub flow: Option<CiFlow>,
+	/// The problems found in the file, in the order they appear in the file
+	pub diagnostics: Vec<CiDiagnostic>,
+}
+
+impl CiDiagnostic {
+	/// Create an error for the field at the given path
+	fn error(path: impl Into<String>, message: impl Into<String>) -> Self {
+		Self {
+			severity: CiDiagnosticSeverity::Error,
+			path: path.into(),
+			message: message.into(),
+			span: None,
+		}
+	}
+
+	/// Create a warning for the field at the given path
+	fn warning(path: impl Into<String>, message: impl Into<String>) -> Self {
+		Self {
+			severity: CiDiagnosticSeverity::Warning,
+			path: path.into(),
+			message: message.into(),
+			span: None,
+		}
+	}
+}
+
+impl Display for CiDiagnostic {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"{}",
+			match self.severity {
+				CiDiagnosticSeverity::Error => "error",
+				CiDiagnosticSeverity::Warning => "warning",
+			}
+		)?;
+		if let Some(span) = &self.span {
+			write!(f, " at {}:{}", span.line, span.column)?;
+		}
+		if !self.path.is_empty() {
+			write!(f, " in `{}`", self.path)?;
+		}
+		write!(f, ": {}", self.message)
+	}
+}
+
+impl CiFileValidation {
+	/// Check if any of the problems found prevent the file from being run
+	pub fn has_errors(&self) -> bool {
+		self.diagnostics
+			.iter()
+			.any(|diagnostic| diagnostic.severity == CiDiagnosticSeverity::Error)
+	}
+}
+
+/// Parse and validate the contents of a CI file, pointing every problem found
+/// at where it is in the file
+pub fn validate_ci_file(source: &str) -> CiFileValidation {
+	let (flow, mut diagnostics) = match serde_yaml::from_str::<CiFlow>(source) {
+		Ok(flow) => {
+			let diagnostics = match &flow {
+				CiFlow::Pipeline(pipeline) => pipeline.validate(),
+			};
+			(Some(flow), diagnostics)
+		}
+		Err(err) => (None, parse_errors(source, err)),
+	};
+
+	for diagnostic in &mut diagnostics {
+		if diagnostic.span.is_none() {
+			diagnostic.span = locate(source, &diagnostic.path);
+		}
+	}
+	diagnostics.sort_by_key(|diagnostic| {
+		diagnostic
+			.span
+			.map(|span| (span.line, span.column))
+			.unwrap_or_default()
+	});
+
+	CiFileValidation { flow, diagnostics }
+}
+
+/// The JSON schema of a CI file, which editors can use to autocomplete and
+/// check the files as they are written
+pub fn ci_file_json_schema() -> RootSchema {
+	schemars::schema_for!(CiFlow)
+}
+
+/// Find out why a CI file could not be parsed. Steps and services are parsed
+/// one by one, so that the problem can be pointed at the step or service that
+/// has it, instead of the whole list of steps.
+fn parse_errors(source: &str, err: serde_yaml::Error) -> Vec<CiDiagnostic> {
+	let value = serde_yaml::from_str::<serde_yaml::Value>(source).ok();
+	let list = |field: &str| {
+		value
+			.as_ref()
+			.and_then(|value| value.get(field))
+			.and_then(serde_yaml::Value::as_sequence)
+			.cloned()
+			.unwrap_or_default()
+	};
+
+	let mut diagnostics = Vec::new();
+	for (index, step) in list("steps").into_iter().enumerate() {
+		let result = if step.get("when").is_some() {
+			serde_yaml::from_value::<Decision>(step).map(drop)
+		} else {
+			serde_yaml::from_value::<Work>(step).map(drop)
+		};
+		if let Err(err) = result {
+			diagnostics.push(field_error(source, format!("steps[{}]", index), err));
+		}
+	}
+	for (index, service) in list("services").into_iter().enumerate() {
+		if let Err(err) = serde_yaml::from_value::<Service>(service) {
+			diagnostics.push(field_error(source, format!("services[{}]", index), err));
+		}
+	}
+
+	if diagnostics.is_empty() {
+		diagnostics.push(CiDiagnostic {
+			span: err.location().map(|location| CiSpan {
+				line: location.line(),
+				column: location.column(),
+				length: 1,
+			}),
+			..CiDiagnostic::error("", err.to_string())
+		});
+	}
+
+	diagnostics
+}
+
+/// Create an error for a step or a service that could not be parsed. Unknown
+/// fields are pointed at directly.
+fn field_error(source: &str, path: String, err: serde_yaml::Error) -> CiDiagnostic {
+	let message = err.to_string();
+	let unknown_field = message
+		.strip_prefix("unknown field `")
+		.and_then(|rest| rest.split_once('`'))
+		.map(|(field, _)| field);
+
+	match unknown_field {
+		Some(field) => {
+			let path = format!("{}.{}", path, field);
+			CiDiagnostic {
+				span: locate_key(source, &path),
+				..CiDiagnostic::error(path, message)
+			}
+		}
+		None => CiDiagnostic::error(path, message),
+	}
+}
+
+impl Pipeline {
+	/// Check the pipeline for problems that parsing it does not catch, such as
+	/// steps that refer to steps that do not exist, steps that can never be
+	/// reached, and steps that lead back to themselves
+	pub fn validate(&self) -> Vec<CiDiagnostic> {
+		let mut diagnostics = Vec::new();
+
+		if self.steps.is_empty() {
+			diagnostics.push(CiDiagnostic::error(
+				"steps",
+				"a pipeline must have at least one step",
+			));
+		}
+
+		let mut steps = HashMap::new();
+		for (index, step) in self.steps.iter().enumerate() {
+			if let Some(first) = steps.insert(step.name(), index) {
+				// Edges lead to the first step with a name
+				steps.insert(step.name(), first);
+				diagnostics.push(CiDiagnostic::error(
+					format!("steps[{}].name", index),
+					format!(
+						"the name `{}` is already used by `steps[{}]`",
+						step.name(),
+						first
+					),
+				));
+			}
+		}
+
+		// The steps that each step leads to, along with the field it leads from
+		let mut edges = vec![Vec::new(); self.steps.len()];
+		for (index, step) in self.steps.iter().enumerate() {
+			let mut edge = |field: &str, target: &str| match steps.get(target) {
+				Some(&target) => edges[index].push((field.to_string(), target)),
+				None => diagnostics.push(CiDiagnostic::error(
+					format!("steps[{}].{}", index, field),
+					format!("there is no step named `{}`", target),
+				)),
+			};
+
+			match step {
+				Step::Work(work) => {
+					if let Some(next) = &work.next {
+						edge("next", next);
+					}
+					validate_work(index, work, &mut diagnostics);
+				}
+				Step::Decision(decision) => {
+					edge("then", &decision.then);
+					if let Some(else_) = &decision.else_ {
+						edge("else", else_);
+					}
+					validate_decision(index, decision, &mut diagnostics);
+				}
+			}
+		}
+
+		validate_step_graph(&edges, &self.steps, &mut diagnostics);
+
+		if self.timeout == Some(0) {
+			diagnostics.push(CiDiagnostic::error(
+				"timeout",
+				"the timeout of a pipeline cannot be 0",
+			));
+		}
+
+		let mut services = BTreeMap::new();
+		for (index, service) in self.services.iter().enumerate() {
+			if let Some(first) = services.insert(service.name.as_str(), index) {
+				services.insert(service.name.as_str(), first);
+				diagnostics.push(CiDiagnostic::error(
+					format!("services[{}].name", index),
+					format!(
+						"the name `{}` is already used by `services[{}]`",
+						service.name, first
+					),
+				));
+			}
+			if service.port == 0 {
+				diagnostics.push(CiDiagnostic::error(
+					format!("services[{}].port", index),
+					"the port of a service cannot be 0",
+				));
+			}
+			if let Some(OneOrMany::Many(commands)) = &service.commands {
+				if commands.is_empty() {
+					diagnostics.push(CiDiagnostic::warning(
+						format!("services[{}].commands", index),
+						"the service runs no commands. Remove `commands` to run the default command of the image",
+					));
+				}
+			}
+			validate_environment(
+				&format!("services[{}]", index),
+				&service.environment,
+				&mut diagnostics,
+			);
+		}
+
+		diagnostics
+	}
+}
+
+/// Check a unit of work for problems
+fn validate_work(index: usize, work: &Work, diagnostics: &mut Vec<CiDiagnostic>) {
+	if work.image.trim().is_empty() {
+		diagnostics.push(CiDiagnostic::error(
+			format!("steps[{}].image", index),
+			"the image of a step cannot be empty",
+		));
+	}
+	if matches!(&work.commands, OneOrMany::Many(commands) if commands.is_empty()) {
+		diagnostics.push(CiDiagnostic::warning(
+			format!("steps[{}].commands", index),
+			"the step runs no commands",
+		));
+	}
+	if work.next.as_deref() == Some(work.name.as_str()) {
+		diagnostics.push(CiDiagnostic::error(
+			format!("steps[{}].next", index),
+			"a step cannot be followed by itself",
+		));
+	}
+	if work.timeout == Some(0) {
+		diagnostics.push(CiDiagnostic::error(
+			format!("steps[{}].timeout", index),
+			"the timeout of a step cannot be 0",
+		));
+	}
+	validate_environment(&format!("steps[{}]", index), &work.environment, diagnostics);
+}
+
+/// Check a decision for problems
+fn validate_decision(index: usize, decision: &Decision, diagnostics: &mut Vec<CiDiagnostic>) {
+	if decision.when.branch.is_empty() && decision.when.event.is_empty() {
+		diagnostics.push(CiDiagnostic::error(
+			format!("steps[{}].when", index),
+			"at least one of `branch` or `event` must be given",
+		));
+	}
+	if decision.else_.as_deref() == Some(decision.then.as_str()) {
+		diagnostics.push(CiDiagnostic::warning(
+			format!("steps[{}].else", index),
+			"both `then` and `else` lead to the same step, so the decision has no effect",
+		));
+	}
+}
+
+/// Check the environment variables of a step or a service for problems
+fn validate_environment(
+	path: &str,
+	environment: &BTreeMap<String, EnvVarValue>,
+	diagnostics: &mut Vec<CiDiagnostic>,
+) {
+	for (key, value) in environment {
+		if key.is_empty() || key.contains('=') || key.contains('\0') {
+			diagnostics.push(CiDiagnostic::error(
+				format!("{}.environment", path),
+				format!("`{}` is not a valid name for an environment variable", key),
+			));
+		}
+		if matches!(value, EnvVarValue::ValueFromSecret { from_secret } if from_secret.trim().is_empty())
+		{
+			diagnostics.push(CiDiagnostic::error(
+				format!("{}.environment.{}.from_secret", path, key),
+				"the name of the secret cannot be empty",
+			));
+		}
+	}
+}
+
+/// Check that every step can be reached from the first step, and that no step
+/// leads back to a step that was run before it
+fn validate_step_graph(
+	edges: &[Vec<(String, usize)>],
+	steps: &[Step],
+	diagnostics: &mut Vec<CiDiagnostic>,
+) {
+	/// The state of a step while walking the steps
+	#[derive(Clone, Copy, PartialEq, Eq)]
+	enum Visit {
+		/// The step has not been reached yet
+		Unvisited,
+		/// The steps that follow the step are being walked
+		InProgress,
+		/// The step and every step that follows it have been walked
+		Done,
+	}
+
+	if steps.is_empty() {
+		return;
+	}
+
+	let mut visits = vec![Visit::Unvisited; steps.len()];
+	// Walk the steps depth first without recursion, keeping the step and the
+	// position of the next edge to follow from it
+	let mut stack = vec![(0, 0)];
+	visits[0] = Visit::InProgress;
+	while let Some((step, edge)) = stack.last_mut() {
+		let Some((field, target)) = edges[*step].get(*edge) else {
+			visits[*step] = Visit::Done;
+			stack.pop();
+			continue;
+		};
+		let (step, target) = (*step, *target);
+		*edge += 1;
+
+		match visits[target] {
+			Visit::Unvisited => {
+				visits[target] = Visit::InProgress;
+				stack.push((target, 0));
+			}
+			// Following the edge leads back to a step that is still running,
+			// so the pipeline would never finish
+			Visit::InProgress if target != step => {
+				diagnostics.push(CiDiagnostic::error(
+					format!("steps[{}].{}", step, field),
+					format!(
+						"this leads back to the step `{}`, so the pipeline would never finish",
+						steps[target].name()
+					),
+				));
+			}
+			// Steps that lead to themselves are reported along with the step
+			Visit::InProgress | Visit::Done => (),
+		}
+	}
+
+	for (index, visit) in visits.into_iter().enumerate() {
+		if visit == Visit::Unvisited {
+			diagnostics.push(CiDiagnostic::warning(
+				format!("steps[{}].name", index),
+				format!(
+					"the step `{}` is never run, since no step leads to it",
+					steps[index].name()
+				),
+			));
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{validate_ci_file, CiDiagnosticSeverity, CiSpan};
+
+	/// The severity, path and span of every problem found in a file
+	fn problems(source: &str) -> Vec<(CiDiagnosticSeverity, String, Option<CiSpan>)> {
+		validate_ci_file(source)
+			.diagnostics
+			.into_iter()
+			.map(|diagnostic| (diagnostic.severity, diagnostic.path, diagnostic.span))
+			.collect()
+	}
+
+	#[test]
+	fn assert_valid_pipeline_has_no_problems() {
+		let validation = validate_ci_file(
+			r#"
+kind: Pipeline
+version: v0
+name: build
+steps:
+  - name: test
+    image: rust
+    commands: cargo test
+    next: should-deploy
+  - name: should-deploy
+    when:
+      branch: [main]
+    then: deploy
+  - name: deploy
+    image: alpine
+    commands: ./deploy.sh
+"#,
+		);
+		assert!(validation.flow.is_some());
+		assert_eq!(validation.diagnostics, vec![]);
+	}
+
+	#[test]
+	fn assert_pipeline_problems_are_located() {
+		use CiDiagnosticSeverity::*;
+
+		let source = r#"kind: Pipeline
+version: v0
+name: build
+services:
+  - name: db
+    image: postgres
+    port: 5432
+  - name: db
+    image: postgres
+    port: 5433
+steps:
+  - name: test
+    image: rust
+    commands: cargo test
+    next: check
+  - name: check
+    when: {}
+    then: deploy-prod
+    else: test
+  - name: orphan
+    image: alpine
+    commands: []
+"#;
+		assert_eq!(
+			problems(source),
+			vec![
+				(
+					Error,
+					"services[1].name".to_string(),
+					Some(CiSpan {
+						line: 8,
+						column: 11,
+						length: 2
+					})
+				),
+				(
+					Error,
+					"steps[1].when".to_string(),
+					Some(CiSpan {
+						line: 17,
+						column: 11,
+						length: 2
+					})
+				),
+				(
+					Error,
+					"steps[1].then".to_string(),
+					Some(CiSpan {
+						line: 18,
+						column: 11,
+						length: 11
+					})
+				),
+				(
+					Error,
+					"steps[1].else".to_string(),
+					Some(CiSpan {
+						line: 19,
+						column: 11,
+						length: 4
+					})
+				),
+				(
+					Warning,
+					"steps[2].name".to_string(),
+					Some(CiSpan {
+						line: 20,
+						column: 11,
+						length: 6
+					})
+				),
+				(
+					Warning,
+					"steps[2].commands".to_string(),
+					Some(CiSpan {
+						line: 22,
+						column: 15,
+						length: 2
+					})
+				),
+			]
+		);
+	}
+
+	#[test]
+	fn assert_parse_errors_point_at_the_step() {
+		let source = r#"kind: Pipeline
+version: v0
+name: build
+steps:
+  - name: test
+    image: rust
+    commands: cargo test
+    nxt: deploy
+"#;
+		let validation = validate_ci_file(source);
+		assert!(validation.flow.is_none());
+		assert!(validation.has_errors());
+		assert_eq!(
+			problems(source),
+			vec![(
+				CiDiagnosticSeverity::Error,
+				"steps[0].nxt".to_string(),
+				Some(CiSpan {
+					line: 8,
+					column: 5,
+					length: 3
+				})
+			)]
+		);
+	}
+
+	#[test]
+	fn assert_zero_timeouts_are_rejected() {
+		let source = r#"kind: Pipeline
+version: v0
+name: build
+timeout: 0
+steps:
+  - name: test
+    image: rust
+    commands: cargo test
+    timeout: 0
+"#;
+		let paths = problems(source)
+			.into_iter()
+			.map(|(severity, path, _)| (severity, path))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			paths,
+			vec![
+				(CiDiagnosticSeverity::Error, "steps[0].timeout".to_string()),
+				(CiDiagnosticSeverity::Error, "timeout".to_string()),
+			]
+		);
+	}
+}
@@ -0,0 +1,393 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{CiTrigger, Event};
+
+/// The providers of git repositories whose webhooks can run the pipelines of
+/// a repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(
+	not(target_arch = "wasm32"),
+	sqlx(type_name = "GIT_PROVIDER", rename_all = "snake_case")
+)]
+pub enum GitProvider {
+	/// GitHub, or a GitHub Enterprise Server
+	Github,
+	/// GitLab, either on gitlab.com or self-hosted
+	Gitlab,
+	/// Gitea, or any of its forks such as Forgejo
+	Gitea,
+}
+
+impl GitProvider {
+	/// The URL of the API of the provider, for the providers that have a public
+	/// instance. Self-hosted instances need the URL of their API to be given.
+	pub fn default_api_url(&self) -> Option<&'static str> {
+		match self {
+			Self::Github => Some("https://api.github.com"),
+			Self::Gitlab => Some("https://gitlab.com/api/v4"),
+			Self::Gitea => None,
+		}
+	}
+
+	/// The header that the provider sends the type of the event of a webhook
+	/// in
+	pub fn event_header(&self) -> &'static str {
+		match self {
+			Self::Github => "X-GitHub-Event",
+			Self::Gitlab => "X-Gitlab-Event",
+			Self::Gitea => "X-Gitea-Event",
+		}
+	}
+
+	/// Parse the body of a webhook of the provider into the git event that the
+	/// pipeline of the repository is run for. Returns [`None`] for events that
+	/// do not run a pipeline, such as pings, deleted branches and closed pull
+	/// requests.
+	pub fn parse_webhook_event(
+		&self,
+		event: &str,
+		body: &[u8],
+	) -> Result<Option<CiTrigger>, serde_json::Error> {
+		match (self, event) {
+			(Self::Github | Self::Gitea, "push") |
+			(Self::Gitlab, "Push Hook" | "Tag Push Hook") => {
+				serde_json::from_slice::<PushPayload>(body).map(PushPayload::into_trigger)
+			}
+			(Self::Github | Self::Gitea, "pull_request") => {
+				serde_json::from_slice::<PullRequestPayload>(body)
+					.map(PullRequestPayload::into_trigger)
+			}
+			(Self::Gitlab, "Merge Request Hook") => {
+				serde_json::from_slice::<MergeRequestPayload>(body)
+					.map(MergeRequestPayload::into_trigger)
+			}
+			_ => Ok(None),
+		}
+	}
+}
+
+/// The body of a push webhook, which is sent for both branches and tags. The
+/// fields are the ones that GitHub, GitLab and Gitea have in common.
+#[derive(Debug, Deserialize)]
+struct PushPayload {
+	/// The full name of the ref that was pushed, such as `refs/heads/main`
+	#[serde(rename = "ref")]
+	reference: String,
+	/// The commit that the ref points to after the push. This is all zeros if
+	/// the ref was deleted.
+	after: String,
+	/// The commit that the ref points to after the push, which GitLab sends
+	/// instead of the tag object for annotated tags
+	#[serde(default)]
+	checkout_sha: Option<String>,
+	/// The commit at the head of the ref after the push, which GitHub and
+	/// Gitea send instead of the tag object for annotated tags
+	#[serde(default)]
+	head_commit: Option<CommitPayload>,
+	/// The repository that was pushed to
+	repository: RepositoryPayload,
+}
+
+/// The body of a pull request webhook of GitHub and Gitea
+#[derive(Debug, Deserialize)]
+struct PullRequestPayload {
+	/// What happened to the pull request, such as `opened`
+	action: String,
+	/// The pull request itself
+	pull_request: PullRequest,
+	/// The repository that the pull request is to be merged into
+	repository: RepositoryPayload,
+}
+
+/// A pull request of GitHub or Gitea
+#[derive(Debug, Deserialize)]
+struct PullRequest {
+	/// The branch that the pull request is merged from
+	head: PullRequestHead,
+	/// The branch that the pull request is merged into
+	base: PullRequestBase,
+}
+
+/// The branch that a pull request is merged from
+#[derive(Debug, Deserialize)]
+struct PullRequestHead {
+	/// The commit at the head of the branch
+	sha: String,
+	/// The repository the branch is in, which is a fork for pull requests from
+	/// forks. This is missing if the fork has been deleted.
+	#[serde(default)]
+	repo: Option<RepositoryPayload>,
+}
+
+/// The branch that a pull request is merged into
+#[derive(Debug, Deserialize)]
+struct PullRequestBase {
+	/// The name of the branch
+	#[serde(rename = "ref")]
+	reference: String,
+}
+
+/// The body of a merge request webhook of GitLab
+#[derive(Debug, Deserialize)]
+struct MergeRequestPayload {
+	/// The merge request itself
+	object_attributes: MergeRequestAttributes,
+	/// The project that the merge request is to be merged into
+	project: RepositoryPayload,
+}
+
+/// A merge request of GitLab
+#[derive(Debug, Deserialize)]
+struct MergeRequestAttributes {
+	/// What happened to the merge request, such as `open`
+	#[serde(default)]
+	action: Option<String>,
+	/// The commit that was at the head of the source branch before the update.
+	/// This is only sent for updates that pushed new commits.
+	#[serde(default)]
+	oldrev: Option<String>,
+	/// The branch that the merge request is merged into
+	target_branch: String,
+	/// The commit at the head of the source branch
+	last_commit: CommitPayload,
+	/// The project the source branch is in
+	source: RepositoryPayload,
+}
+
+/// A commit sent in a webhook
+#[derive(Debug, Deserialize)]
+struct CommitPayload {
+	/// The SHA of the commit
+	id: String,
+}
+
+/// A repository sent in a webhook
+#[derive(Debug, Deserialize)]
+struct RepositoryPayload {
+	/// The HTTP URL to clone the repository from
+	#[serde(alias = "git_http_url")]
+	clone_url: String,
+}
+
+impl PushPayload {
+	/// The git event of the push, if the push was not a deletion of a ref
+	fn into_trigger(self) -> Option<CiTrigger> {
+		if self.after.bytes().all(|byte| byte == b'0') {
+			return None;
+		}
+
+		let (event, branch, tag) = if let Some(branch) = self.reference.strip_prefix("refs/heads/")
+		{
+			(Event::Commit, Some(branch.to_string()), None)
+		} else if let Some(tag) = self.reference.strip_prefix("refs/tags/") {
+			(Event::Tag, None, Some(tag.to_string()))
+		} else {
+			return None;
+		};
+
+		Some(CiTrigger {
+			clone_url: self.repository.clone_url,
+			commit_sha: self
+				.checkout_sha
+				.or(self.head_commit.map(|commit| commit.id))
+				.unwrap_or(self.after),
+			branch,
+			tag,
+			event,
+		})
+	}
+}
+
+impl PullRequestPayload {
+	/// The git event of the pull request, if commits were pushed to it
+	fn into_trigger(self) -> Option<CiTrigger> {
+		// GitHub calls a push to a pull request `synchronize`, and Gitea calls
+		// it `synchronized`
+		if !matches!(
+			self.action.as_str(),
+			"opened" | "reopened" | "synchronize" | "synchronized"
+		) {
+			return None;
+		}
+
+		Some(CiTrigger {
+			clone_url: self
+				.pull_request
+				.head
+				.repo
+				.unwrap_or(self.repository)
+				.clone_url,
+			commit_sha: self.pull_request.head.sha,
+			branch: Some(self.pull_request.base.reference),
+			tag: None,
+			event: Event::Pull,
+		})
+	}
+}
+
+impl MergeRequestPayload {
+	/// The git event of the merge request, if commits were pushed to it
+	fn into_trigger(self) -> Option<CiTrigger> {
+		let attributes = self.object_attributes;
+		match attributes.action.as_deref() {
+			Some("open" | "reopen") => (),
+			// Updates are also sent for changes to the title, labels, etc.
+			Some("update") if attributes.oldrev.is_some() => (),
+			_ => return None,
+		}
+
+		Some(CiTrigger {
+			clone_url: attributes.source.clone_url,
+			commit_sha: attributes.last_commit.id,
+			branch: Some(attributes.target_branch),
+			tag: None,
+			event: Event::Pull,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::json;
+
+	use super::GitProvider;
+	use crate::ci::{CiTrigger, Event};
+
+	fn parse(provider: GitProvider, event: &str, body: serde_json::Value) -> Option<CiTrigger> {
+		provider
+			.parse_webhook_event(event, &serde_json::to_vec(&body).unwrap())
+			.unwrap()
+	}
+
+	#[test]
+	fn assert_pushes_are_parsed() {
+		let trigger = parse(
+			GitProvider::Github,
+			"push",
+			json!({
+				"ref": "refs/heads/main",
+				"after": "1111111111111111111111111111111111111111",
+				"head_commit": { "id": "1111111111111111111111111111111111111111" },
+				"repository": { "clone_url": "https://github.com/patr/api.git" }
+			}),
+		)
+		.unwrap();
+		assert_eq!(trigger.event, Event::Commit);
+		assert_eq!(trigger.branch.as_deref(), Some("main"));
+		assert_eq!(trigger.tag, None);
+		assert_eq!(trigger.clone_url, "https://github.com/patr/api.git");
+
+		let trigger = parse(
+			GitProvider::Gitlab,
+			"Tag Push Hook",
+			json!({
+				"ref": "refs/tags/v1.0.0",
+				"after": "2222222222222222222222222222222222222222",
+				"checkout_sha": "3333333333333333333333333333333333333333",
+				"project": { "git_http_url": "https://gitlab.com/patr/api.git" },
+				"repository": { "git_http_url": "https://gitlab.com/patr/api.git" }
+			}),
+		)
+		.unwrap();
+		assert_eq!(trigger.event, Event::Tag);
+		assert_eq!(trigger.tag.as_deref(), Some("v1.0.0"));
+		assert_eq!(trigger.branch, None);
+		assert_eq!(
+			trigger.commit_sha,
+			"3333333333333333333333333333333333333333"
+		);
+
+		// Deleting a branch does not run anything
+		assert_eq!(
+			parse(
+				GitProvider::Gitea,
+				"push",
+				json!({
+					"ref": "refs/heads/feature",
+					"after": "0000000000000000000000000000000000000000",
+					"repository": { "clone_url": "https://gitea.com/patr/api.git" }
+				}),
+			),
+			None
+		);
+	}
+
+	#[test]
+	fn assert_pull_requests_are_parsed() {
+		let pull_request = |action: &str| {
+			json!({
+				"action": action,
+				"pull_request": {
+					"head": {
+						"sha": "4444444444444444444444444444444444444444",
+						"repo": { "clone_url": "https://gitea.com/fork/api.git" }
+					},
+					"base": { "ref": "main" }
+				},
+				"repository": { "clone_url": "https://gitea.com/patr/api.git" }
+			})
+		};
+
+		let trigger = parse(
+			GitProvider::Gitea,
+			"pull_request",
+			pull_request("synchronized"),
+		)
+		.unwrap();
+		assert_eq!(trigger.event, Event::Pull);
+		assert_eq!(trigger.branch.as_deref(), Some("main"));
+		assert_eq!(trigger.clone_url, "https://gitea.com/fork/api.git");
+		assert_eq!(
+			parse(GitProvider::Github, "pull_request", pull_request("closed")),
+			None
+		);
+
+		let merge_request = |action: &str, oldrev: Option<&str>| {
+			json!({
+				"object_attributes": {
+					"action": action,
+					"oldrev": oldrev,
+					"target_branch": "develop",
+					"last_commit": { "id": "5555555555555555555555555555555555555555" },
+					"source": { "git_http_url": "https://gitlab.com/patr/api.git" }
+				},
+				"project": { "git_http_url": "https://gitlab.com/patr/api.git" }
+			})
+		};
+		let trigger = parse(
+			GitProvider::Gitlab,
+			"Merge Request Hook",
+			merge_request("update", Some("4444444444444444444444444444444444444444")),
+		)
+		.unwrap();
+		assert_eq!(trigger.branch.as_deref(), Some("develop"));
+		assert_eq!(
+			trigger.commit_sha,
+			"5555555555555555555555555555555555555555"
+		);
+		// Editing the title of a merge request does not run anything
+		assert_eq!(
+			parse(
+				GitProvider::Gitlab,
+				"Merge Request Hook",
+				merge_request("update", None)
+			),
+			None
+		);
+	}
+
+	#[test]
+	fn assert_other_events_are_ignored() {
+		assert_eq!(
+			parse(
+				GitProvider::Github,
+				"ping",
+				json!({ "zen": "Keep it simple" })
+			),
+			None
+		);
+	}
+}
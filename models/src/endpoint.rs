@@ -5,12 +5,15 @@ use preprocess::Preprocessable;
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::utils::{
+	EndpointDocumentation,
 	FromAxumRequest,
 	HasHeaders,
 	Headers,
 	IntoAxumResponse,
 	RequiresRequestHeaders as RequestHeaders,
+	RateLimit,
 	RequiresResponseHeaders as ResponseHeaders,
+	SchemaGenerator,
 };
 
 /// A trait that defines an API endpoint.
@@ -52,6 +55,8 @@ where
 	Self::ResponseBody:
 		IntoAxumResponse + RequestHeaders + ResponseHeaders + Debug + Send + 'static,
 {
+	/// The name of the endpoint, such as `CreateDeployment`
+	const NAME: &'static str;
 	/// The HTTP method that should be used for this endpoint
 	const METHOD: http::Method;
 	/// If true, this route can be accessed by the API. Otherwise, it'll only be
@@ -76,10 +81,13 @@ where
 	/// [`typed_headers::Header`]
 	type RequestHeaders;
 	/// The request body that should be used for this endpoint. This should be a
-	/// struct that implements [`serde::Deserialize`] and [`serde::Serialize`].
-	/// Any request should be of JSON type.
+	/// struct that implements [`FromAxumRequest`]. This can either be a JSON
+	/// body (any struct that implements [`serde::Deserialize`] and
+	/// [`serde::Serialize`]), or a stream request, such as a
+	/// [`RawBody`][1], a [`Multipart`][2] form or a websocket.
 	///
-	/// TODO: Later on, allow stream requests, such as `multipart/form-data`
+	/// [1]: crate::utils::RawBody
+	/// [2]: crate::utils::Multipart
 	type RequestBody;
 
 	/// The authenticator that should be used for this endpoint. This should be
@@ -129,4 +137,17 @@ where
 	{
 		Self::Authenticator::default()
 	}
+
+	/// The rate limits that should be applied to this endpoint, if any. This
+	/// is used to protect endpoints such as login, password reset, etc. from
+	/// brute-force attacks. By default, no rate limits are applied.
+	fn get_rate_limit() -> Option<RateLimit<Self>> {
+		None
+	}
+
+	/// The documentation of this endpoint, generated from its declaration by
+	/// [`macros::declare_api_endpoint`]. The schemas of the types used by the
+	/// endpoint are added to the given generator, so that they can be shared
+	/// across all the endpoints in the OpenAPI document.
+	fn documentation(generator: &mut SchemaGenerator) -> EndpointDocumentation;
 }
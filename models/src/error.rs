@@ -4,11 +4,22 @@ use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumIter, IntoEnumIterator};
 
-use crate::prelude::*;
+use crate::{api::workspace::WorkspaceQuotaType, prelude::*};
 
 /// A list of all the possible errors that can be returned by the API
 #[derive(
-	Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Display, EnumIter,
+	Debug,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Serialize,
+	Deserialize,
+	Display,
+	EnumIter,
+	schemars::JsonSchema,
 )]
 #[serde(rename_all = "camelCase")]
 #[strum(serialize_all = "camelCase")]
@@ -92,6 +103,49 @@ pub enum ErrorType {
 	RunnerAlreadyConnected,
 	/// The operation is not allowed in the current runner mode
 	InvalidRunnerMode,
+	/// The state of a sign in with an external OpenID Connect provider is
+	/// invalid or has expired
+	InvalidOidcState,
+	/// The ID token returned by an external OpenID Connect provider could not
+	/// be validated
+	InvalidOidcToken,
+	/// The external OpenID Connect provider did not return a verified email,
+	/// so the identity cannot be linked to a user
+	OidcEmailNotVerified,
+	/// The invite to a workspace is invalid, has expired, or was not sent to
+	/// the current user
+	InvalidWorkspaceInvite,
+	/// There is no pending transfer of the ownership of the workspace to the
+	/// current user, or the transfer has expired
+	InvalidOwnershipTransfer,
+	/// The grace period of the deletion of the workspace has ended, and its
+	/// resources are being destroyed, so it can no longer be restored
+	WorkspaceDeletionInProgress,
+	/// The body of the request is larger than the endpoint allows
+	PayloadTooLarge,
+	/// Creating or updating the resource would make the workspace exceed one
+	/// of its quotas
+	#[serde(rename_all = "camelCase")]
+	QuotaExceeded {
+		/// The quota that would be exceeded
+		quota: WorkspaceQuotaType,
+	},
+	/// Too many requests have been made from the client in a short period of
+	/// time. The client should wait for `retry_after` seconds before trying
+	/// again
+	#[serde(rename_all = "camelCase")]
+	TooManyRequests {
+		/// The number of seconds after which the client can retry the request
+		retry_after: u64,
+	},
+	/// The account has been temporarily locked due to too many failed
+	/// authentication attempts. The client should wait for `retry_after`
+	/// seconds before trying again
+	#[serde(rename_all = "camelCase")]
+	AccountLocked {
+		/// The number of seconds after which the account will be unlocked
+		retry_after: u64,
+	},
 }
 
 impl ErrorType {
@@ -135,6 +189,16 @@ impl ErrorType {
 			Self::RoleInUse => StatusCode::CONFLICT,
 			Self::RunnerAlreadyConnected => StatusCode::CONFLICT,
 			Self::InvalidRunnerMode => StatusCode::FORBIDDEN,
+			Self::InvalidOidcState => StatusCode::BAD_REQUEST,
+			Self::InvalidOidcToken => StatusCode::UNAUTHORIZED,
+			Self::OidcEmailNotVerified => StatusCode::UNAUTHORIZED,
+			Self::InvalidWorkspaceInvite => StatusCode::BAD_REQUEST,
+			Self::InvalidOwnershipTransfer => StatusCode::BAD_REQUEST,
+			Self::WorkspaceDeletionInProgress => StatusCode::CONFLICT,
+			Self::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+			Self::QuotaExceeded { .. } => StatusCode::FORBIDDEN,
+			Self::TooManyRequests { .. } => StatusCode::TOO_MANY_REQUESTS,
+			Self::AccountLocked { .. } => StatusCode::TOO_MANY_REQUESTS,
 		}
 	}
 
@@ -183,6 +247,28 @@ impl ErrorType {
 			Self::RoleInUse => "The role is currently assigned to users and cannot be deleted",
 			Self::RunnerAlreadyConnected => "Another instance of the same runner ID is already connected",
 			Self::InvalidRunnerMode => "That operation is not allowed in the mode the runner is currently in",
+			Self::InvalidOidcState => "Your sign in request is invalid or has expired. Please try again",
+			Self::InvalidOidcToken => "The identity provider returned an invalid response. Please try again",
+			Self::OidcEmailNotVerified => "Your email has not been verified with the identity provider",
+			Self::InvalidWorkspaceInvite => "The invite is invalid or has expired",
+			Self::InvalidOwnershipTransfer => "The ownership transfer is invalid or has expired",
+			Self::WorkspaceDeletionInProgress => "The workspace is already being deleted and can no longer be restored",
+			Self::PayloadTooLarge => "The body of the request is too large",
+			Self::QuotaExceeded { .. } => "The workspace has reached its limit for that resource",
+			Self::TooManyRequests { .. } => "Too many requests. Please try again later",
+			Self::AccountLocked { .. } => "Your account has been temporarily locked due to too many failed attempts. Please try again later",
+		}
+	}
+
+	/// Returns the number of seconds after which the request can be retried,
+	/// if this error is a rate limiting error. This is used to set the
+	/// `Retry-After` header in the response
+	pub fn retry_after(&self) -> Option<u64> {
+		match self {
+			Self::TooManyRequests { retry_after } | Self::AccountLocked { retry_after } => {
+				Some(*retry_after)
+			}
+			_ => None,
 		}
 	}
 
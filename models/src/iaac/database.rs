@@ -1,28 +1,193 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(
-	Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord,
-)]
-#[serde(rename_all = "camelCase", deny_unknown_fields)]
+use super::{resolve_runner, IaacConversionError, IaacResolver};
+use crate::{
+	api::workspace::database::{CreateDatabaseRequest, DatabaseEngine},
+	prelude::*,
+};
+
+/// The IaaC format of a database
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub struct IaacDatabase {
+	/// The name of the database
 	pub name: String,
-	#[serde(alias = "dbEngine")]
+	/// The engine of the database
+	#[serde(alias = "dbEngine", alias = "db_engine")]
 	pub engine: IaacDatabaseEngine,
-	#[serde(skip_serializing_if = "Option::is_none")]
+	/// The version of the engine that the database runs
+	pub version: String,
+	/// The runner that the database runs on, either by its name or its ID
+	pub region: String,
+	/// The plan of the database, which decides its CPU, RAM and storage
+	#[serde(
+		alias = "databasePlanId",
+		alias = "database_plan_id",
+		alias = "plan_id"
+	)]
+	pub plan: Uuid,
+	/// The number of nodes of the database
+	#[serde(
+		alias = "numNodes",
+		alias = "num_node",
+		alias = "nodes",
+		alias = "replicas",
+		default = "default_num_nodes"
+	)]
+	pub num_nodes: u16,
+	/// The name of the secret that the password of the database is saved to
+	#[serde(
+		alias = "savePasswordTo",
+		default,
+		skip_serializing_if = "Option::is_none"
+	)]
 	pub save_password_to: Option<String>,
 }
 
-#[derive(
-	Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord,
-)]
+/// Databases have a single node by default
+fn default_num_nodes() -> u16 {
+	1
+}
+
+impl IaacDatabase {
+	/// Convert the database to the request that creates it, looking up the
+	/// runner it refers to with the resolver
+	pub fn to_create_request(
+		&self,
+		resolver: &impl IaacResolver,
+	) -> Result<CreateDatabaseRequest, IaacConversionError> {
+		Ok(CreateDatabaseRequest {
+			name: self.name.clone(),
+			engine: self.engine.clone().into(),
+			database_plan_id: self.plan,
+			region: resolve_runner(resolver, &self.region)?,
+			version: self.version.clone(),
+			num_node: self.num_nodes,
+		})
+	}
+}
+
+impl From<CreateDatabaseRequest> for IaacDatabase {
+	fn from(request: CreateDatabaseRequest) -> Self {
+		Self {
+			name: request.name,
+			engine: request.engine.into(),
+			version: request.version,
+			region: request.region.to_string(),
+			plan: request.database_plan_id,
+			num_nodes: request.num_node,
+			save_password_to: None,
+		}
+	}
+}
+
+/// The engine of a database
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(deny_unknown_fields)]
 pub enum IaacDatabaseEngine {
+	/// PostgreSQL
 	#[serde(alias = "postgres", alias = "postgresql")]
 	Postgres,
+	/// MySQL
 	#[serde(alias = "mysql")]
 	MySQL,
+	/// MongoDB
 	#[serde(alias = "mongodb", alias = "mongo")]
 	MongoDB,
+	/// Redis
 	#[serde(alias = "redis")]
 	Redis,
 }
+
+impl From<IaacDatabaseEngine> for DatabaseEngine {
+	fn from(engine: IaacDatabaseEngine) -> Self {
+		match engine {
+			IaacDatabaseEngine::Postgres => Self::Postgres,
+			IaacDatabaseEngine::MySQL => Self::Mysql,
+			IaacDatabaseEngine::MongoDB => Self::Mongo,
+			IaacDatabaseEngine::Redis => Self::Redis,
+		}
+	}
+}
+
+impl From<DatabaseEngine> for IaacDatabaseEngine {
+	fn from(engine: DatabaseEngine) -> Self {
+		match engine {
+			DatabaseEngine::Postgres => Self::Postgres,
+			DatabaseEngine::Mysql => Self::MySQL,
+			DatabaseEngine::Mongo => Self::MongoDB,
+			DatabaseEngine::Redis => Self::Redis,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{IaacDatabase, IaacDatabaseEngine};
+	use crate::{api::workspace::database::CreateDatabaseRequest, utils::Uuid};
+
+	fn database() -> IaacDatabase {
+		IaacDatabase {
+			name: "users".to_string(),
+			engine: IaacDatabaseEngine::Postgres,
+			version: "16".to_string(),
+			region: "default".to_string(),
+			plan: Uuid::parse_str("01234567890123456789abcdefabcdef").unwrap(),
+			num_nodes: 1,
+			save_password_to: Some("users-password".to_string()),
+		}
+	}
+
+	#[test]
+	fn assert_iaac_database_round_trips() {
+		let database = database();
+		let serialized = serde_json::to_value(&database).unwrap();
+		assert_eq!(
+			serialized,
+			serde_json::json!({
+				"name": "users",
+				"engine": "Postgres",
+				"version": "16",
+				"region": "default",
+				"plan": "01234567890123456789abcdefabcdef",
+				"num_nodes": 1,
+				"save_password_to": "users-password",
+			})
+		);
+		assert_eq!(
+			serde_json::from_value::<IaacDatabase>(serialized).unwrap(),
+			database
+		);
+	}
+
+	#[test]
+	fn assert_iaac_database_aliases_work() {
+		let parsed: IaacDatabase = serde_json::from_value(serde_json::json!({
+			"name": "users",
+			"dbEngine": "postgresql",
+			"version": "16",
+			"region": "default",
+			"databasePlanId": "01234567890123456789abcdefabcdef",
+			"savePasswordTo": "users-password",
+		}))
+		.unwrap();
+		assert_eq!(parsed, database());
+	}
+
+	#[test]
+	fn assert_iaac_database_converts_from_request() {
+		let region = Uuid::parse_str("abcdefabcdef01234567890123456789").unwrap();
+		let database = IaacDatabase::from(CreateDatabaseRequest {
+			name: "users".to_string(),
+			engine: IaacDatabaseEngine::MySQL.into(),
+			database_plan_id: Uuid::parse_str("01234567890123456789abcdefabcdef").unwrap(),
+			region,
+			version: "8".to_string(),
+			num_node: 3,
+		});
+		assert_eq!(database.engine, IaacDatabaseEngine::MySQL);
+		assert_eq!(database.region, region.to_string());
+		assert_eq!(database.num_nodes, 3);
+		assert_eq!(database.save_password_to, None);
+	}
+}
@@ -1,24 +1,34 @@
-use std::{collections::BTreeMap, convert::Infallible};
+use std::{
+	collections::BTreeMap,
+	convert::Infallible,
+	fmt::{Display, Formatter},
+};
 
 use either::Either;
 use serde::{Deserialize, Serialize};
 
 use crate::{
 	api::workspace::deployment::{
+		DeploymentMachineType,
 		DeploymentProbe,
-		EnvironmentVariableValue,
 		ExposedPortType,
 		PatrRegistry,
 	},
+	iaac::{IaacReference, MaybeExternallySourced},
 	prelude::*,
 };
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+/// The IaaC format of a deployment
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case", deny_unknown_fields)]
 pub struct IaacDeployment {
+	/// The name of the deployment
 	pub name: String,
+	/// The image that the deployment runs
 	pub image: IaacDeploymentImage,
+	/// The runner that the deployment runs on, either by its name or its ID
 	pub region: String,
+	/// The CPU and RAM of the machine that the deployment runs on
 	#[serde(
 		alias = "spec",
 		alias = "specs",
@@ -27,51 +37,83 @@ pub struct IaacDeployment {
 		default
 	)]
 	pub machine_type: IaacDeploymentMachineType,
+	/// Whether the deployment is deployed as soon as a new image is pushed
 	#[serde(default = "default_deploy_on_push")]
 	pub deploy_on_push: bool,
+	/// The minimum number of instances of the deployment
 	#[serde(alias = "min-scale", alias = "minscale", default = "default_min_scale")]
 	pub min_horizontal_scale: u8,
+	/// The maximum number of instances of the deployment
 	#[serde(alias = "max-scale", alias = "maxscale", default = "default_max_scale")]
 	pub max_horizontal_scale: u8,
+	/// The ports that the deployment exposes, along with their type
 	#[serde(alias = "port")]
 	pub ports: IaacDeploymentPorts,
+	/// The environment variables of the deployment
 	#[serde(default, alias = "env", alias = "envVars")]
 	pub environment_variables: IaacDeploymentEnvVars,
+	/// The startup probe of the deployment, if any
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub startup_probe: Option<DeploymentProbe>,
+	/// The liveness probe of the deployment, if any
 	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub liveness_probe: Option<DeploymentProbe>,
+	/// The files that are mounted in the deployment, with the path of the file
+	/// as the key and the contents of the file as the value
 	#[serde(alias = "configs", default, skip_serializing_if = "BTreeMap::is_empty")]
 	pub config_mounts: BTreeMap<String, String>,
+	/// The volumes that are mounted in the deployment by their ID, along with
+	/// the path that each of them is mounted at. If not given, the volumes of
+	/// an existing deployment are left as they are.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub volumes: Option<BTreeMap<Uuid, String>>,
 }
 
+/// Deployments are deployed on push by default
 fn default_deploy_on_push() -> bool {
 	true
 }
 
+/// Deployments have at least one instance by default
 fn default_min_scale() -> u8 {
 	1
 }
 
+/// Deployments can scale up to two instances by default
 fn default_max_scale() -> u8 {
 	2
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
-#[serde(try_from = "&str", rename_all = "snake_case", untagged)]
+/// The image of a deployment, given as a string such as `nginx:latest` or
+/// `registry.patr.cloud/api:stable`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(
+	try_from = "String",
+	into = "String",
+	rename_all = "snake_case",
+	untagged
+)]
 pub enum IaacDeploymentImage {
+	/// An image in the container registry of Patr
 	PatrRegistry {
+		/// The Patr registry
 		#[serde(alias = "server")]
 		registry: PatrRegistry,
+		/// The repository in the registry, either by its ID or its name
 		#[serde(alias = "repo", with = "either::serde_untagged")]
 		repository: Either<Uuid, String>,
+		/// The tag of the image
 		tag: String,
 	},
+	/// An image in any other registry
 	ExternalRegistry {
+		/// The URL of the registry
 		#[serde(alias = "server")]
 		registry: String,
+		/// The name of the image in the registry
 		#[serde(alias = "repo")]
 		repository: String,
+		/// The tag of the image
 		tag: String,
 	},
 }
@@ -121,13 +163,64 @@ impl TryFrom<&str> for IaacDeploymentImage {
 	}
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
-#[serde(try_from = "&str", rename_all = "snake_case", deny_unknown_fields)]
+impl TryFrom<String> for IaacDeploymentImage {
+	type Error = Infallible;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> {
+		value.as_str().try_into()
+	}
+}
+
+impl Display for IaacDeploymentImage {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::PatrRegistry {
+				registry,
+				repository,
+				tag,
+			} => write!(f, "{}/{}:{}", registry, repository, tag),
+			Self::ExternalRegistry {
+				registry,
+				repository,
+				tag,
+			} => write!(f, "{}/{}:{}", registry, repository, tag),
+		}
+	}
+}
+
+impl From<IaacDeploymentImage> for String {
+	fn from(value: IaacDeploymentImage) -> Self {
+		value.to_string()
+	}
+}
+
+/// The machine type of a deployment, given as a string such as `1vCPU 1GB RAM`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(
+	try_from = "String",
+	into = "String",
+	rename_all = "snake_case",
+	deny_unknown_fields
+)]
 pub struct IaacDeploymentMachineType {
+	/// The number of CPUs of the machine
 	pub cpu: IaacDeploymentCpu,
+	/// The amount of RAM of the machine
 	pub ram: IaacDeploymentRam,
 }
 
+impl IaacDeploymentMachineType {
+	/// Check if a machine type of a deployment has the CPU and RAM of this
+	/// machine type. The memory of a machine type is counted in 0.25 GB
+	/// increments, which matches either GB or GiB based RAM.
+	pub fn matches(&self, machine_type: &DeploymentMachineType) -> bool {
+		let memory_count = u64::from(machine_type.memory_count);
+		self.cpu.count() == Some(f32::from(machine_type.cpu_count)) &&
+			(self.ram.bytes() == memory_count * 250 * 1000 * 1000 ||
+				self.ram.bytes() == memory_count * 256 * 1024 * 1024)
+	}
+}
+
 impl TryFrom<&str> for IaacDeploymentMachineType {
 	type Error = &'static str;
 
@@ -143,6 +236,31 @@ impl TryFrom<&str> for IaacDeploymentMachineType {
 	}
 }
 
+impl TryFrom<String> for IaacDeploymentMachineType {
+	type Error = &'static str;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> {
+		value.as_str().try_into()
+	}
+}
+
+impl From<IaacDeploymentMachineType> for String {
+	fn from(value: IaacDeploymentMachineType) -> Self {
+		let cpu: String = value.cpu.into();
+		let ram: String = value.ram.into();
+		format!("{} {}", cpu, ram)
+	}
+}
+
+impl From<&DeploymentMachineType> for IaacDeploymentMachineType {
+	fn from(machine_type: &DeploymentMachineType) -> Self {
+		Self {
+			cpu: IaacDeploymentCpu(format!("{}vCPU", machine_type.cpu_count)),
+			ram: IaacDeploymentRam(u64::from(machine_type.memory_count) * 256 * 1024 * 1024),
+		}
+	}
+}
+
 impl Default for IaacDeploymentMachineType {
 	fn default() -> Self {
 		Self {
@@ -152,10 +270,18 @@ impl Default for IaacDeploymentMachineType {
 	}
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+/// The number of CPUs of a machine, such as `1vCPU` or `0.5vCPU`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(try_from = "String", into = "String", deny_unknown_fields)]
 pub struct IaacDeploymentCpu(String);
 
+impl IaacDeploymentCpu {
+	/// Get the number of CPUs
+	pub fn count(&self) -> Option<f32> {
+		self.0.strip_suffix("vCPU")?.parse().ok()
+	}
+}
+
 impl TryFrom<String> for IaacDeploymentCpu {
 	type Error = &'static str;
 
@@ -196,10 +322,18 @@ impl Into<String> for IaacDeploymentCpu {
 	}
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+/// The amount of RAM of a machine, such as `1GB` or `512MiB`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(try_from = "String", into = "String", deny_unknown_fields)]
 pub struct IaacDeploymentRam(u64);
 
+impl IaacDeploymentRam {
+	/// Get the amount of RAM in bytes
+	pub fn bytes(&self) -> u64 {
+		self.0
+	}
+}
+
 impl TryFrom<String> for IaacDeploymentRam {
 	type Error = &'static str;
 
@@ -267,16 +401,9 @@ impl Into<String> for IaacDeploymentRam {
 			return format!("{}GB RAM", bytes / 1000_000_000);
 		}
 
-		// GiB
-		if bytes >= (1024 * 1024 * 1024) {
-			return if bytes % (1024 * 1024 * 1024) == 0 {
-				format!("{}GiB RAM", bytes / (1024 * 1024 * 1024))
-			} else {
-				format!(
-					"{:.1}GiB RAM",
-					(bytes as f64) / (1024f64 * 1024f64 * 1024f64)
-				)
-			};
+		// GiB. Fractions are not used, since they cannot be parsed back.
+		if bytes >= (1024 * 1024 * 1024) && bytes % (1024 * 1024 * 1024) == 0 {
+			return format!("{}GiB RAM", bytes / (1024 * 1024 * 1024));
 		}
 
 		// MB
@@ -285,12 +412,8 @@ impl Into<String> for IaacDeploymentRam {
 		}
 
 		// MiB
-		if bytes >= (1024 * 1024) {
-			return if bytes % (1024 * 1024) == 0 {
-				format!("{}MiB RAM", bytes / (1024 * 1024))
-			} else {
-				format!("{:.1}MiB RAM", (bytes as f64) / (1024f64 * 1024f64))
-			};
+		if bytes >= (1024 * 1024) && bytes % (1024 * 1024) == 0 {
+			return format!("{}MiB RAM", bytes / (1024 * 1024));
 		}
 
 		// KB
@@ -299,21 +422,18 @@ impl Into<String> for IaacDeploymentRam {
 		}
 
 		// KiB
-		if bytes >= (1024) {
-			return if bytes % 1024 == 0 {
-				format!("{}KiB RAM", bytes / 1024)
-			} else {
-				format!("{:.1}KiB RAM", (bytes as f64) / 1024f64)
-			};
+		if bytes >= 1024 && bytes % 1024 == 0 {
+			return format!("{}KiB RAM", bytes / 1024);
 		}
 
 		format!("{}B RAM", bytes)
 	}
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
-#[serde(try_from = "OneOrMore<String>")]
-pub struct IaacDeploymentPorts(BTreeMap<StringifiedU16, ExposedPortType>);
+/// The ports of a deployment, given as a list such as `8080: http`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(try_from = "OneOrMore<String>", into = "Vec<String>")]
+pub struct IaacDeploymentPorts(pub BTreeMap<StringifiedU16, ExposedPortType>);
 
 impl TryFrom<OneOrMore<String>> for IaacDeploymentPorts {
 	type Error = &'static str;
@@ -365,43 +485,227 @@ impl TryFrom<OneOrMore<String>> for IaacDeploymentPorts {
 	}
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
-#[serde(try_from = "Vec<String>")]
-pub struct IaacDeploymentEnvVars(BTreeMap<String, EnvironmentVariableValue>);
+impl From<IaacDeploymentPorts> for Vec<String> {
+	fn from(value: IaacDeploymentPorts) -> Self {
+		value
+			.0
+			.into_iter()
+			.map(|(port, r#type)| format!("{}: {}", port, r#type))
+			.collect()
+	}
+}
+
+/// The environment variables of a deployment, given either as a list such as
+/// `KEY=VALUE`, or as a map of the name of each variable to its value. Only the
+/// map format allows values that are sourced from elsewhere.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(try_from = "IaacDeploymentEnvVarsFormat")]
+pub struct IaacDeploymentEnvVars(pub BTreeMap<String, IaacEnvironmentVariableValue>);
+
+/// The value of an environment variable of a deployment
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged, deny_unknown_fields)]
+pub enum IaacEnvironmentVariableValue {
+	/// The value is the content of a secret of the workspace
+	Secret {
+		/// The secret, either by its ID or its name
+		#[serde(alias = "secret", with = "either::serde_untagged")]
+		from_secret: IaacReference,
+	},
+	/// The value is given directly, or sourced from elsewhere
+	Value(MaybeExternallySourced<String>),
+}
 
-impl TryFrom<Vec<String>> for IaacDeploymentEnvVars {
+/// The formats that the environment variables of a deployment can be given in
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum IaacDeploymentEnvVarsFormat {
+	/// A list of `KEY=VALUE` strings
+	List(Vec<String>),
+	/// A map of the name of each variable to its value
+	Map(BTreeMap<String, IaacEnvironmentVariableValue>),
+}
+
+impl TryFrom<IaacDeploymentEnvVarsFormat> for IaacDeploymentEnvVars {
 	type Error = &'static str;
 
-	fn try_from(value: Vec<String>) -> Result<Self, Self::Error> {
-		fn parse_one_env(env: String) -> Result<(String, EnvironmentVariableValue), &'static str> {
+	fn try_from(value: IaacDeploymentEnvVarsFormat) -> Result<Self, Self::Error> {
+		fn parse_one_env(
+			env: String,
+		) -> Result<(String, IaacEnvironmentVariableValue), &'static str> {
 			if let Some((key, value)) = env.split_once('=') {
 				return Ok((
 					key.trim().to_string(),
-					EnvironmentVariableValue::String(value.trim().to_string()),
+					IaacEnvironmentVariableValue::Value(MaybeExternallySourced::Value(
+						value.trim().to_string(),
+					)),
 				));
 			}
 
 			Err("environment variable must be of the format KEY=VALUE")
 		}
 
-		value
-			.into_iter()
-			.map(parse_one_env)
-			.collect::<Result<_, _>>()
-			.map(Self)
+		match value {
+			IaacDeploymentEnvVarsFormat::List(list) => list
+				.into_iter()
+				.map(parse_one_env)
+				.collect::<Result<_, _>>()
+				.map(Self),
+			IaacDeploymentEnvVarsFormat::Map(map) => Ok(Self(map)),
+		}
 	}
 }
 
 #[cfg(test)]
 mod tests {
+	use std::collections::BTreeMap;
+
 	use either::Either;
 
 	use crate::{
-		iaac::IaacDeploymentImage,
-		models::workspace::deployment::PatrRegistry,
-		utils::Uuid,
+		api::workspace::deployment::{DeploymentMachineType, ExposedPortType, PatrRegistry},
+		iaac::{
+			IaacDeploymentEnvVars,
+			IaacDeploymentImage,
+			IaacDeploymentMachineType,
+			IaacDeploymentPorts,
+			IaacEnvironmentVariableValue,
+			IaacOutput,
+			IaacOutputReference,
+			IaacResourceType,
+			MaybeExternallySourced,
+		},
+		utils::{StringifiedU16, Uuid},
 	};
 
+	#[test]
+	fn assert_iaac_deployment_env_vars_parsing_works() {
+		let list: IaacDeploymentEnvVars =
+			serde_json::from_value(serde_json::json!(["PORT=8080", "HOST = localhost"])).unwrap();
+		assert_eq!(
+			list,
+			IaacDeploymentEnvVars(BTreeMap::from([
+				(
+					"PORT".to_string(),
+					IaacEnvironmentVariableValue::Value(MaybeExternallySourced::Value(
+						"8080".to_string()
+					))
+				),
+				(
+					"HOST".to_string(),
+					IaacEnvironmentVariableValue::Value(MaybeExternallySourced::Value(
+						"localhost".to_string()
+					))
+				),
+			]))
+		);
+
+		let map = IaacDeploymentEnvVars(BTreeMap::from([
+			(
+				"PORT".to_string(),
+				IaacEnvironmentVariableValue::Value(MaybeExternallySourced::Value(
+					"8080".to_string(),
+				)),
+			),
+			(
+				"TOKEN".to_string(),
+				IaacEnvironmentVariableValue::Value(MaybeExternallySourced::FromEnvironment {
+					from_env: "API_TOKEN".to_string(),
+				}),
+			),
+			(
+				"DATABASE_PASSWORD".to_string(),
+				IaacEnvironmentVariableValue::Value(MaybeExternallySourced::FromResource {
+					from_resource: IaacOutputReference {
+						resource_type: IaacResourceType::Database,
+						name: "users".to_string(),
+						output: IaacOutput::Password,
+					},
+				}),
+			),
+			(
+				"API_KEY".to_string(),
+				IaacEnvironmentVariableValue::Secret {
+					from_secret: Either::Right("api-key".to_string()),
+				},
+			),
+		]));
+		let serialized = serde_json::to_value(&map).unwrap();
+		assert_eq!(
+			serialized,
+			serde_json::json!({
+				"PORT": "8080",
+				"TOKEN": { "from_env": "API_TOKEN" },
+				"DATABASE_PASSWORD": { "from_resource": "database.users.password" },
+				"API_KEY": { "from_secret": "api-key" },
+			})
+		);
+		assert_eq!(
+			serde_json::from_value::<IaacDeploymentEnvVars>(serialized).unwrap(),
+			map
+		);
+	}
+
+	#[test]
+	fn assert_iaac_deployment_values_serialize_to_parseable_strings() {
+		let image = IaacDeploymentImage::PatrRegistry {
+			registry: PatrRegistry,
+			repository: Either::Right("api".to_string()),
+			tag: "stable".to_string(),
+		};
+		let serialized = serde_json::to_value(&image).unwrap();
+		assert_eq!(
+			serialized,
+			serde_json::json!("registry.patr.cloud/api:stable")
+		);
+		assert_eq!(
+			serde_json::from_value::<IaacDeploymentImage>(serialized).unwrap(),
+			image
+		);
+
+		let image = IaacDeploymentImage::ExternalRegistry {
+			registry: "docker.io".to_string(),
+			repository: "library/nginx".to_string(),
+			tag: "latest".to_string(),
+		};
+		let serialized = serde_json::to_value(&image).unwrap();
+		assert_eq!(
+			serialized,
+			serde_json::json!("docker.io/library/nginx:latest")
+		);
+		assert_eq!(
+			serde_json::from_value::<IaacDeploymentImage>(serialized).unwrap(),
+			image
+		);
+
+		for (cpu_count, memory_count, string) in [
+			(1, 4, "1vCPU 1GiB RAM"),
+			(2, 6, "2vCPU 1536MiB RAM"),
+			(4, 2, "4vCPU 512MiB RAM"),
+		] {
+			let live = DeploymentMachineType {
+				cpu_count,
+				memory_count,
+			};
+			let machine_type = IaacDeploymentMachineType::from(&live);
+			let serialized = serde_json::to_value(&machine_type).unwrap();
+			assert_eq!(serialized, serde_json::json!(string));
+			let parsed = serde_json::from_value::<IaacDeploymentMachineType>(serialized).unwrap();
+			assert!(parsed.matches(&live));
+		}
+
+		let ports = IaacDeploymentPorts(BTreeMap::from([
+			(StringifiedU16::from(80), ExposedPortType::Http),
+			(StringifiedU16::from(5432), ExposedPortType::Tcp),
+		]));
+		let serialized = serde_json::to_value(&ports).unwrap();
+		assert_eq!(serialized, serde_json::json!(["80: http", "5432: tcp"]));
+		assert_eq!(
+			serde_json::from_value::<IaacDeploymentPorts>(serialized).unwrap(),
+			ports
+		);
+	}
+
 	#[test]
 	fn assert_iaac_deployment_image_parsing_works() {
 		for (string, value) in [
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+use crate::api::workspace::container_registry::CreateContainerRepositoryRequest;
+
+/// The IaaC format of a repository in the container registry
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct IaacDockerRepository {
+	/// The name of the repository
+	#[serde(alias = "repository", alias = "repo")]
+	pub name: String,
+}
+
+impl From<IaacDockerRepository> for CreateContainerRepositoryRequest {
+	fn from(repository: IaacDockerRepository) -> Self {
+		Self {
+			name: repository.name,
+		}
+	}
+}
+
+impl From<CreateContainerRepositoryRequest> for IaacDockerRepository {
+	fn from(request: CreateContainerRepositoryRequest) -> Self {
+		Self { name: request.name }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_test::{assert_de_tokens, assert_tokens, Token};
+
+	use super::IaacDockerRepository;
+
+	#[test]
+	fn assert_iaac_docker_repository_types() {
+		assert_tokens(
+			&IaacDockerRepository {
+				name: "api".to_string(),
+			},
+			&[
+				Token::Struct {
+					name: "IaacDockerRepository",
+					len: 1,
+				},
+				Token::Str("name"),
+				Token::Str("api"),
+				Token::StructEnd,
+			],
+		);
+	}
+
+	#[test]
+	fn assert_iaac_docker_repository_aliases_work() {
+		assert_de_tokens(
+			&IaacDockerRepository {
+				name: "api".to_string(),
+			},
+			&[
+				Token::Struct {
+					name: "IaacDockerRepository",
+					len: 1,
+				},
+				Token::Str("repo"),
+				Token::Str("api"),
+				Token::StructEnd,
+			],
+		);
+	}
+}
@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+
+use crate::api::workspace::domain::{AddDomainToWorkspaceRequest, DomainNameserverType};
+
+/// The IaaC format of a domain
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct IaacDomain {
+	/// The domain name, such as `example.com`
+	#[serde(alias = "domain")]
+	pub name: String,
+	/// Whether the DNS of the domain is managed by Patr or elsewhere
+	#[serde(
+		alias = "nameserver",
+		alias = "nameservers",
+		alias = "nameserverType",
+		alias = "ns"
+	)]
+	pub nameserver_type: IaacDomainNameserverType,
+}
+
+/// Whether the DNS of a domain is managed by Patr or elsewhere
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum IaacDomainNameserverType {
+	/// The nameservers of the domain point to Patr, and Patr manages its DNS
+	#[serde(alias = "Internal", alias = "patr", alias = "Patr")]
+	Internal,
+	/// The DNS of the domain is managed by a different provider
+	#[serde(alias = "External")]
+	External,
+}
+
+impl From<IaacDomainNameserverType> for DomainNameserverType {
+	fn from(nameserver_type: IaacDomainNameserverType) -> Self {
+		match nameserver_type {
+			IaacDomainNameserverType::Internal => Self::Internal,
+			IaacDomainNameserverType::External => Self::External,
+		}
+	}
+}
+
+impl From<DomainNameserverType> for IaacDomainNameserverType {
+	fn from(nameserver_type: DomainNameserverType) -> Self {
+		match nameserver_type {
+			DomainNameserverType::Internal => Self::Internal,
+			DomainNameserverType::External => Self::External,
+		}
+	}
+}
+
+impl From<IaacDomain> for AddDomainToWorkspaceRequest {
+	fn from(domain: IaacDomain) -> Self {
+		Self {
+			domain: domain.name,
+			nameserver_type: domain.nameserver_type.into(),
+		}
+	}
+}
+
+impl From<AddDomainToWorkspaceRequest> for IaacDomain {
+	fn from(request: AddDomainToWorkspaceRequest) -> Self {
+		Self {
+			name: request.domain,
+			nameserver_type: request.nameserver_type.into(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_test::{assert_de_tokens, assert_tokens, Token};
+
+	use super::{IaacDomain, IaacDomainNameserverType};
+	use crate::api::workspace::domain::AddDomainToWorkspaceRequest;
+
+	#[test]
+	fn assert_iaac_domain_types() {
+		assert_tokens(
+			&IaacDomain {
+				name: "example.com".to_string(),
+				nameserver_type: IaacDomainNameserverType::Internal,
+			},
+			&[
+				Token::Struct {
+					name: "IaacDomain",
+					len: 2,
+				},
+				Token::Str("name"),
+				Token::Str("example.com"),
+				Token::Str("nameserver_type"),
+				Token::UnitVariant {
+					name: "IaacDomainNameserverType",
+					variant: "internal",
+				},
+				Token::StructEnd,
+			],
+		);
+	}
+
+	#[test]
+	fn assert_iaac_domain_aliases_work() {
+		assert_de_tokens(
+			&IaacDomain {
+				name: "example.com".to_string(),
+				nameserver_type: IaacDomainNameserverType::Internal,
+			},
+			&[
+				Token::Struct {
+					name: "IaacDomain",
+					len: 2,
+				},
+				Token::Str("domain"),
+				Token::Str("example.com"),
+				Token::Str("ns"),
+				Token::UnitVariant {
+					name: "IaacDomainNameserverType",
+					variant: "patr",
+				},
+				Token::StructEnd,
+			],
+		);
+	}
+
+	#[test]
+	fn assert_iaac_domain_converts_to_and_from_request() {
+		let domain = IaacDomain {
+			name: "example.com".to_string(),
+			nameserver_type: IaacDomainNameserverType::External,
+		};
+		let request = AddDomainToWorkspaceRequest::from(domain.clone());
+		assert_eq!(IaacDomain::from(request), domain);
+	}
+}
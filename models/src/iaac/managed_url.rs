@@ -0,0 +1,307 @@
+use either::Either;
+use serde::{Deserialize, Serialize};
+
+use super::{
+	resolve_reference,
+	IaacConversionError,
+	IaacReference,
+	IaacResolver,
+	IaacResourceType,
+};
+use crate::api::workspace::managed_url::{CreateManagedURLRequest, ManagedUrlType};
+
+/// The IaaC format of a managed URL
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct IaacManagedUrl {
+	/// The name that the managed URL is referred to by in the IaaC files.
+	/// Managed URLs do not have a name in Patr, so this is only used to
+	/// identify the managed URL among the declared resources.
+	pub name: String,
+	/// The sub-domain of the managed URL. Use `@` for the domain itself.
+	#[serde(
+		alias = "subDomain",
+		alias = "subdomain",
+		default = "default_sub_domain"
+	)]
+	pub sub_domain: String,
+	/// The domain of the managed URL, either by its ID or its name
+	#[serde(with = "either::serde_untagged")]
+	pub domain: IaacReference,
+	/// The path of the managed URL
+	#[serde(default = "default_path")]
+	pub path: String,
+	/// Where the requests to the managed URL are sent to
+	#[serde(alias = "url_type", alias = "urlType", alias = "to")]
+	pub target: IaacManagedUrlTarget,
+}
+
+/// Managed URLs point to the domain itself by default
+fn default_sub_domain() -> String {
+	"@".to_string()
+}
+
+/// Managed URLs handle all the paths of the domain by default
+fn default_path() -> String {
+	"/".to_string()
+}
+
+/// Where the requests to a managed URL are sent to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum IaacManagedUrlTarget {
+	/// The requests are proxied to a port of a deployment
+	#[serde(alias = "proxy_deployment", alias = "proxyDeployment")]
+	Deployment {
+		/// The deployment, either by its ID or its name
+		#[serde(with = "either::serde_untagged")]
+		deployment: IaacReference,
+		/// The port of the deployment
+		port: u16,
+	},
+	/// The requests are proxied to a static site
+	#[serde(alias = "proxy_static_site", alias = "proxyStaticSite")]
+	StaticSite {
+		/// The static site, either by its ID or its name
+		#[serde(alias = "site", with = "either::serde_untagged")]
+		static_site: IaacReference,
+	},
+	/// The requests are proxied to a URL
+	#[serde(alias = "proxy_url", alias = "proxyUrl")]
+	Proxy {
+		/// The URL that the requests are proxied to
+		url: String,
+		/// Whether the URL is only served over HTTP
+		#[serde(alias = "httpOnly", default)]
+		http_only: bool,
+	},
+	/// The requests are redirected to a URL
+	Redirect {
+		/// The URL that the requests are redirected to
+		url: String,
+		/// Whether the redirect is permanent
+		#[serde(alias = "permanent_redirect", alias = "permanentRedirect", default)]
+		permanent: bool,
+		/// Whether the URL is only served over HTTP
+		#[serde(alias = "httpOnly", default)]
+		http_only: bool,
+	},
+}
+
+impl IaacManagedUrl {
+	/// Convert the managed URL to the request that creates it, looking up the
+	/// resources it refers to by name with the resolver
+	pub fn to_create_request(
+		&self,
+		resolver: &impl IaacResolver,
+	) -> Result<CreateManagedURLRequest, IaacConversionError> {
+		Ok(CreateManagedURLRequest {
+			sub_domain: self.sub_domain.clone(),
+			domain_id: resolve_reference(resolver, IaacResourceType::Domain, &self.domain)?,
+			path: self.path.clone(),
+			url_type: self.target.to_url_type(resolver)?,
+		})
+	}
+}
+
+impl IaacManagedUrlTarget {
+	/// Convert the target to the type of the managed URL, looking up the
+	/// resources it refers to by name with the resolver
+	pub fn to_url_type(
+		&self,
+		resolver: &impl IaacResolver,
+	) -> Result<ManagedUrlType, IaacConversionError> {
+		Ok(match self {
+			Self::Deployment { deployment, port } => ManagedUrlType::ProxyDeployment {
+				deployment_id: resolve_reference(
+					resolver,
+					IaacResourceType::Deployment,
+					deployment,
+				)?,
+				port: *port,
+			},
+			Self::StaticSite { static_site } => ManagedUrlType::ProxyStaticSite {
+				static_site_id: resolve_reference(
+					resolver,
+					IaacResourceType::StaticSite,
+					static_site,
+				)?,
+			},
+			Self::Proxy { url, http_only } => ManagedUrlType::ProxyUrl {
+				url: url.clone(),
+				http_only: *http_only,
+			},
+			Self::Redirect {
+				url,
+				permanent,
+				http_only,
+			} => ManagedUrlType::Redirect {
+				url: url.clone(),
+				permanent_redirect: *permanent,
+				http_only: *http_only,
+			},
+		})
+	}
+}
+
+impl From<ManagedUrlType> for IaacManagedUrlTarget {
+	fn from(url_type: ManagedUrlType) -> Self {
+		match url_type {
+			ManagedUrlType::ProxyDeployment {
+				deployment_id,
+				port,
+			} => Self::Deployment {
+				deployment: Either::Left(deployment_id),
+				port,
+			},
+			ManagedUrlType::ProxyStaticSite { static_site_id } => Self::StaticSite {
+				static_site: Either::Left(static_site_id),
+			},
+			ManagedUrlType::ProxyUrl { url, http_only } => Self::Proxy { url, http_only },
+			ManagedUrlType::Redirect {
+				url,
+				permanent_redirect,
+				http_only,
+			} => Self::Redirect {
+				url,
+				permanent: permanent_redirect,
+				http_only,
+			},
+		}
+	}
+}
+
+impl From<CreateManagedURLRequest> for IaacManagedUrl {
+	fn from(request: CreateManagedURLRequest) -> Self {
+		Self {
+			name: format!(
+				"{}.{}{}",
+				request.sub_domain, request.domain_id, request.path
+			),
+			sub_domain: request.sub_domain,
+			domain: Either::Left(request.domain_id),
+			path: request.path,
+			target: request.url_type.into(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use either::Either;
+
+	use super::{IaacManagedUrl, IaacManagedUrlTarget};
+	use crate::{
+		api::workspace::managed_url::ManagedUrlType,
+		iaac::{IaacConversionError, IaacResolver, IaacResourceType},
+		utils::Uuid,
+	};
+
+	/// A resolver that knows a single domain and deployment
+	struct TestResolver;
+
+	impl IaacResolver for TestResolver {
+		fn resource_id(&self, resource_type: IaacResourceType, name: &str) -> Option<Uuid> {
+			match (resource_type, name) {
+				(IaacResourceType::Domain, "example.com") => {
+					Some(Uuid::parse_str("01234567890123456789abcdefabcdef").unwrap())
+				}
+				(IaacResourceType::Deployment, "api") => {
+					Some(Uuid::parse_str("abcdefabcdef01234567890123456789").unwrap())
+				}
+				_ => None,
+			}
+		}
+
+		fn runner_id(&self, _: &str) -> Option<Uuid> {
+			None
+		}
+	}
+
+	fn managed_url() -> IaacManagedUrl {
+		IaacManagedUrl {
+			name: "api".to_string(),
+			sub_domain: "api".to_string(),
+			domain: Either::Right("example.com".to_string()),
+			path: "/".to_string(),
+			target: IaacManagedUrlTarget::Deployment {
+				deployment: Either::Right("api".to_string()),
+				port: 8080,
+			},
+		}
+	}
+
+	#[test]
+	fn assert_iaac_managed_url_round_trips() {
+		let managed_url = managed_url();
+		let serialized = serde_json::to_value(&managed_url).unwrap();
+		assert_eq!(
+			serialized,
+			serde_json::json!({
+				"name": "api",
+				"sub_domain": "api",
+				"domain": "example.com",
+				"path": "/",
+				"target": {
+					"deployment": {
+						"deployment": "api",
+						"port": 8080,
+					},
+				},
+			})
+		);
+		assert_eq!(
+			serde_json::from_value::<IaacManagedUrl>(serialized).unwrap(),
+			managed_url
+		);
+		assert_eq!(
+			serde_json::from_value::<IaacManagedUrl>(serde_json::json!({
+				"name": "api",
+				"subdomain": "api",
+				"domain": "example.com",
+				"to": {
+					"proxy_deployment": {
+						"deployment": "api",
+						"port": 8080,
+					},
+				},
+			}))
+			.unwrap(),
+			managed_url
+		);
+	}
+
+	#[test]
+	fn assert_iaac_managed_url_resolves_references() {
+		let request = managed_url().to_create_request(&TestResolver).unwrap();
+		assert_eq!(
+			request.domain_id,
+			Uuid::parse_str("01234567890123456789abcdefabcdef").unwrap()
+		);
+		assert_eq!(
+			request.url_type,
+			ManagedUrlType::ProxyDeployment {
+				deployment_id: Uuid::parse_str("abcdefabcdef01234567890123456789").unwrap(),
+				port: 8080,
+			}
+		);
+
+		let managed_url = IaacManagedUrl::from(request);
+		assert_eq!(
+			managed_url.domain,
+			Either::Left(Uuid::parse_str("01234567890123456789abcdefabcdef").unwrap())
+		);
+
+		let unresolved = IaacManagedUrl {
+			domain: Either::Right("example.org".to_string()),
+			..managed_url
+		};
+		assert_eq!(
+			unresolved.to_create_request(&TestResolver),
+			Err(IaacConversionError::UnresolvedReference {
+				resource_type: IaacResourceType::Domain,
+				name: "example.org".to_string(),
+			})
+		);
+	}
+}
@@ -1,76 +1,302 @@
+/// The IaaC format of a database
 mod database;
+/// The IaaC format of a deployment
 mod deployment;
+/// The IaaC format of a repository in the container registry
+mod docker_repo;
+/// The IaaC format of a domain
+mod domain;
+/// The IaaC format of a managed URL
+mod managed_url;
+/// The outputs of resources, which other resources can refer to
+mod output;
+/// The IaaC format of a secret
+mod secret;
+/// The IaaC format of a static site
+mod static_site;
+
+use std::{
+	fmt::{Display, Formatter},
+	str::FromStr,
+};
 
 use either::Either;
 use serde::{Deserialize, Serialize};
 
-pub use self::{database::*, deployment::*};
+pub use self::{
+	database::*,
+	deployment::*,
+	docker_repo::*,
+	domain::*,
+	managed_url::*,
+	output::*,
+	secret::*,
+	static_site::*,
+};
 use crate::prelude::*;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
-#[serde(rename_all = "snake_case", deny_unknown_fields)]
+/// A single resource declared in an IaaC file, along with the resources that it
+/// depends on. The resources that a resource depends on are created before it,
+/// and deleted after it.
+///
+/// Unknown fields are rejected by the data of the resource instead of here,
+/// since serde rejects every flattened field of a struct that denies unknown
+/// fields.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
 pub struct Resource {
+	/// The resource itself
 	#[serde(flatten)]
 	pub data: IaacResource,
+	/// The resources that this resource depends on
+	#[serde(default = "default_depends_on")]
 	pub depends_on: OneOrMore<Dependency>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+/// The default dependencies of a resource, which is none
+fn default_depends_on() -> OneOrMore<Dependency> {
+	OneOrMore::Multiple(Vec::new())
+}
+
+/// The data of a resource declared in an IaaC file, based on the type of the
+/// resource.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "type", deny_unknown_fields)]
 pub enum IaacResource {
+	/// A deployment
 	Deployment(IaacDeployment),
-	Database,
-	StaticSite,
-	ManagedUrl,
-	Domain,
-	DockerRepository,
-	Secret,
+	/// A database
+	Database(IaacDatabase),
+	/// A static site
+	StaticSite(IaacStaticSite),
+	/// A managed URL
+	ManagedUrl(IaacManagedUrl),
+	/// A domain
+	Domain(IaacDomain),
+	/// A repository in the container registry
+	DockerRepository(IaacDockerRepository),
+	/// A secret
+	Secret(IaacSecret),
 }
 
 impl IaacResource {
+	/// Get the type of the resource
 	pub fn get_resource_type(&self) -> IaacResourceType {
 		match self {
 			Self::Deployment(_) => IaacResourceType::Deployment,
-			Self::Database => IaacResourceType::Database,
-			Self::StaticSite => IaacResourceType::StaticSite,
-			Self::ManagedUrl => IaacResourceType::ManagedUrl,
-			Self::Domain => IaacResourceType::Domain,
-			Self::DockerRepository => IaacResourceType::DockerRepository,
-			Self::Secret => IaacResourceType::Secret,
+			Self::Database(_) => IaacResourceType::Database,
+			Self::StaticSite(_) => IaacResourceType::StaticSite,
+			Self::ManagedUrl(_) => IaacResourceType::ManagedUrl,
+			Self::Domain(_) => IaacResourceType::Domain,
+			Self::DockerRepository(_) => IaacResourceType::DockerRepository,
+			Self::Secret(_) => IaacResourceType::Secret,
+		}
+	}
+
+	/// Get the name of the resource, which is used to identify it among the
+	/// other resources of the same type
+	pub fn get_name(&self) -> &str {
+		match self {
+			Self::Deployment(deployment) => &deployment.name,
+			Self::Database(database) => &database.name,
+			Self::StaticSite(static_site) => &static_site.name,
+			Self::ManagedUrl(managed_url) => &managed_url.name,
+			Self::Domain(domain) => &domain.name,
+			Self::DockerRepository(repository) => &repository.name,
+			Self::Secret(secret) => &secret.name,
+		}
+	}
+
+	/// Get the values of the resource that can be sourced from elsewhere, along
+	/// with the field that each value is of
+	pub fn sourced_values(&self) -> Vec<(String, &MaybeExternallySourced<String>)> {
+		match self {
+			Self::Deployment(deployment) => deployment
+				.environment_variables
+				.0
+				.iter()
+				.filter_map(|(key, value)| match value {
+					IaacEnvironmentVariableValue::Value(value) => {
+						Some((format!("environment_variables.{}", key), value))
+					}
+					IaacEnvironmentVariableValue::Secret { .. } => None,
+				})
+				.collect(),
+			Self::Secret(secret) => vec![("value".to_string(), &secret.value)],
+			Self::Database(_) |
+			Self::StaticSite(_) |
+			Self::ManagedUrl(_) |
+			Self::Domain(_) |
+			Self::DockerRepository(_) => Vec::new(),
 		}
 	}
+
+	/// Get the outputs of other resources that the values of this resource
+	/// are sourced from
+	pub fn referenced_outputs(&self) -> Vec<&IaacOutputReference> {
+		self.sourced_values()
+			.into_iter()
+			.filter_map(|(_, value)| match value {
+				MaybeExternallySourced::FromResource { from_resource } => Some(from_resource),
+				MaybeExternallySourced::Value(_) |
+				MaybeExternallySourced::FromEnvironment { .. } => None,
+			})
+			.collect()
+	}
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+/// The type of a resource declared in an IaaC file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum IaacResourceType {
+	/// A deployment
 	Deployment,
+	/// A database
 	Database,
+	/// A static site
 	StaticSite,
+	/// A managed URL
 	ManagedUrl,
+	/// A domain
 	Domain,
+	/// A repository in the container registry
 	DockerRepository,
+	/// A secret
 	Secret,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+impl Display for IaacResourceType {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"{}",
+			match self {
+				Self::Deployment => "deployment",
+				Self::Database => "database",
+				Self::StaticSite => "static_site",
+				Self::ManagedUrl => "managed_url",
+				Self::Domain => "domain",
+				Self::DockerRepository => "docker_repository",
+				Self::Secret => "secret",
+			}
+		)
+	}
+}
+
+impl FromStr for IaacResourceType {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"deployment" => Ok(Self::Deployment),
+			"database" => Ok(Self::Database),
+			"static_site" => Ok(Self::StaticSite),
+			"managed_url" => Ok(Self::ManagedUrl),
+			"domain" => Ok(Self::Domain),
+			"docker_repository" => Ok(Self::DockerRepository),
+			"secret" => Ok(Self::Secret),
+			resource_type => Err(format!("unknown resource type `{}`", resource_type)),
+		}
+	}
+}
+
+/// A resource that another resource depends on.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(deny_unknown_fields)]
 pub struct Dependency {
+	/// The type of the resource that is depended on
 	pub resource: IaacResourceType,
+	/// The resource that is depended on. This is either the ID of a resource
+	/// that already exists in the workspace, or the name of a resource that is
+	/// declared in the IaaC files.
+	#[serde(with = "either::serde_untagged")]
 	pub identifier: Either<Uuid, String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+/// A reference from one resource to another. This is either the ID of a
+/// resource that already exists in the workspace, or the name of a resource
+/// that is declared in the IaaC files.
+pub type IaacReference = Either<Uuid, String>;
+
+/// Looks up the IDs of the resources that are referred to by name in IaaC
+/// files, when converting them to API requests
+pub trait IaacResolver {
+	/// Get the ID of a resource of the given type by its name
+	fn resource_id(&self, resource_type: IaacResourceType, name: &str) -> Option<Uuid>;
+	/// Get the ID of a runner of the workspace by its name
+	fn runner_id(&self, name: &str) -> Option<Uuid>;
+}
+
+/// An error converting a resource declared in an IaaC file to an API request
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum IaacConversionError {
+	/// A resource that is referred to by name does not exist
+	#[error("the {resource_type} `{name}` does not exist")]
+	UnresolvedReference {
+		/// The type of the resource that is referred to
+		resource_type: IaacResourceType,
+		/// The name of the resource that is referred to
+		name: String,
+	},
+	/// A runner that is referred to by name does not exist
+	#[error("the runner `{0}` does not exist")]
+	UnresolvedRunner(String),
+	/// A value that is sourced from elsewhere has not been resolved yet
+	#[error("the value of `{field}` is sourced from elsewhere and has not been resolved")]
+	ExternallySourcedValue {
+		/// The field that the value is of
+		field: String,
+	},
+}
+
+/// Get the ID of the resource that a reference refers to
+pub(crate) fn resolve_reference(
+	resolver: &impl IaacResolver,
+	resource_type: IaacResourceType,
+	reference: &IaacReference,
+) -> Result<Uuid, IaacConversionError> {
+	match reference {
+		Either::Left(id) => Ok(*id),
+		Either::Right(name) => resolver.resource_id(resource_type, name).ok_or_else(|| {
+			IaacConversionError::UnresolvedReference {
+				resource_type,
+				name: name.clone(),
+			}
+		}),
+	}
+}
+
+/// Get the ID of a runner that is referred to either by its ID or its name
+pub(crate) fn resolve_runner(
+	resolver: &impl IaacResolver,
+	runner: &str,
+) -> Result<Uuid, IaacConversionError> {
+	Uuid::parse_str(runner)
+		.ok()
+		.or_else(|| resolver.runner_id(runner))
+		.ok_or_else(|| IaacConversionError::UnresolvedRunner(runner.to_string()))
+}
+
+/// A value that can either be given directly, or be sourced from elsewhere
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(untagged, rename_all = "camelCase", deny_unknown_fields)]
 pub enum MaybeExternallySourced<T> {
+	/// The value is given directly
 	Value(T),
+	/// The value is read from an environment variable. Such values are
+	/// treated as secrets, and are never shown in a plan.
 	#[serde(rename_all = "snake_case")]
 	FromEnvironment {
+		/// The name of the environment variable
 		#[serde(alias = "env")]
 		from_env: String,
 	},
+	/// The value is an output of another resource, such as the password of a
+	/// database. The resource is created before the resources that use its
+	/// outputs.
 	#[serde(rename_all = "snake_case")]
 	FromResource {
-		#[serde(alias = "env")]
-		from_resource: String,
+		/// The output of the resource
+		#[serde(alias = "from_output")]
+		from_resource: IaacOutputReference,
 	},
 }
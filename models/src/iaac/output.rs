@@ -0,0 +1,199 @@
+use std::{
+	fmt::{Display, Formatter},
+	str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::IaacResourceType;
+
+/// An output of a resource, which other resources can use as the value of
+/// their fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IaacOutput {
+	/// The ID of the resource. Every resource has this output.
+	Id,
+	/// The host that other deployments of the workspace can reach a deployment
+	/// at
+	InternalUrl,
+	/// The host that a database can be connected to at
+	Host,
+	/// The port that a database can be connected to at
+	Port,
+	/// The username to connect to a database with
+	Username,
+	/// The password to connect to a database with
+	Password,
+}
+
+impl IaacOutput {
+	/// Check if the output is a secret, whose value must never be shown
+	pub fn is_secret(&self) -> bool {
+		matches!(self, Self::Password)
+	}
+
+	/// Check if resources of the given type have this output
+	pub fn is_output_of(&self, resource_type: IaacResourceType) -> bool {
+		match self {
+			Self::Id => true,
+			Self::InternalUrl => resource_type == IaacResourceType::Deployment,
+			Self::Host | Self::Port | Self::Username | Self::Password => {
+				resource_type == IaacResourceType::Database
+			}
+		}
+	}
+}
+
+impl Display for IaacOutput {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"{}",
+			match self {
+				Self::Id => "id",
+				Self::InternalUrl => "internal_url",
+				Self::Host => "host",
+				Self::Port => "port",
+				Self::Username => "username",
+				Self::Password => "password",
+			}
+		)
+	}
+}
+
+impl FromStr for IaacOutput {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"id" => Ok(Self::Id),
+			"internal_url" | "internalUrl" => Ok(Self::InternalUrl),
+			"host" => Ok(Self::Host),
+			"port" => Ok(Self::Port),
+			"username" | "user" => Ok(Self::Username),
+			"password" => Ok(Self::Password),
+			output => Err(format!("unknown output `{}`", output)),
+		}
+	}
+}
+
+/// A reference to an output of a resource, written as
+/// `<resource type>.<resource name>.<output>`, such as
+/// `database.users.password`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct IaacOutputReference {
+	/// The type of the resource
+	pub resource_type: IaacResourceType,
+	/// The name of the resource
+	pub name: String,
+	/// The output of the resource
+	pub output: IaacOutput,
+}
+
+impl Display for IaacOutputReference {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}.{}.{}", self.resource_type, self.name, self.output)
+	}
+}
+
+impl TryFrom<String> for IaacOutputReference {
+	type Error = String;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> {
+		// The name is in the middle, since names such as domain names can
+		// contain dots themselves
+		let (Some((resource_type, rest)), Some((_, output))) =
+			(value.split_once('.'), value.rsplit_once('.'))
+		else {
+			return Err(format!(
+				"output `{}` must be of the format `<type>.<name>.<output>`",
+				value
+			));
+		};
+		let name = rest
+			.strip_suffix(output)
+			.and_then(|name| name.strip_suffix('.'))
+			.unwrap_or_default();
+		if name.is_empty() {
+			return Err(format!(
+				"output `{}` must be of the format `<type>.<name>.<output>`",
+				value
+			));
+		}
+
+		let resource_type = resource_type.parse::<IaacResourceType>()?;
+		let output = output.parse::<IaacOutput>()?;
+		if !output.is_output_of(resource_type) {
+			return Err(format!(
+				"resources of type `{}` do not have the output `{}`",
+				resource_type, output
+			));
+		}
+
+		Ok(Self {
+			resource_type,
+			name: name.to_string(),
+			output,
+		})
+	}
+}
+
+impl From<IaacOutputReference> for String {
+	fn from(value: IaacOutputReference) -> Self {
+		value.to_string()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{IaacOutput, IaacOutputReference};
+	use crate::iaac::IaacResourceType;
+
+	#[test]
+	fn assert_iaac_output_reference_parsing_works() {
+		for (string, value) in [
+			(
+				"database.users.password",
+				IaacOutputReference {
+					resource_type: IaacResourceType::Database,
+					name: "users".to_string(),
+					output: IaacOutput::Password,
+				},
+			),
+			(
+				"deployment.api.internal_url",
+				IaacOutputReference {
+					resource_type: IaacResourceType::Deployment,
+					name: "api".to_string(),
+					output: IaacOutput::InternalUrl,
+				},
+			),
+			(
+				"domain.example.com.id",
+				IaacOutputReference {
+					resource_type: IaacResourceType::Domain,
+					name: "example.com".to_string(),
+					output: IaacOutput::Id,
+				},
+			),
+		] {
+			let parsed = IaacOutputReference::try_from(string.to_string()).unwrap();
+			assert_eq!(parsed, value);
+			assert_eq!(parsed.to_string(), string);
+		}
+	}
+
+	#[test]
+	fn assert_invalid_iaac_output_references_are_rejected() {
+		for string in [
+			"database.password",
+			"database",
+			"deployment.api.password",
+			"database.users.unknown",
+			"unknown.users.id",
+		] {
+			assert!(IaacOutputReference::try_from(string.to_string()).is_err());
+		}
+	}
+}
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+use super::{IaacConversionError, MaybeExternallySourced};
+use crate::api::workspace::secret::CreateSecretRequest;
+
+/// The IaaC format of a secret
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct IaacSecret {
+	/// The name of the secret
+	pub name: String,
+	/// The value of the secret. This is usually read from an environment
+	/// variable, so that the value is not saved in the IaaC files.
+	#[serde(alias = "content")]
+	pub value: MaybeExternallySourced<String>,
+}
+
+impl IaacSecret {
+	/// Convert the secret to the request that creates it. The value of the
+	/// secret must have been resolved to a direct value.
+	pub fn to_create_request(&self) -> Result<CreateSecretRequest, IaacConversionError> {
+		let MaybeExternallySourced::Value(value) = &self.value else {
+			return Err(IaacConversionError::ExternallySourcedValue {
+				field: "value".to_string(),
+			});
+		};
+
+		Ok(CreateSecretRequest {
+			name: self.name.clone(),
+			value: value.clone(),
+		})
+	}
+}
+
+impl From<CreateSecretRequest> for IaacSecret {
+	fn from(request: CreateSecretRequest) -> Self {
+		Self {
+			name: request.name,
+			value: MaybeExternallySourced::Value(request.value),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::IaacSecret;
+	use crate::iaac::{IaacConversionError, MaybeExternallySourced};
+
+	#[test]
+	fn assert_iaac_secret_round_trips() {
+		for (secret, json) in [
+			(
+				IaacSecret {
+					name: "api-key".to_string(),
+					value: MaybeExternallySourced::Value("hunter2".to_string()),
+				},
+				serde_json::json!({
+					"name": "api-key",
+					"value": "hunter2",
+				}),
+			),
+			(
+				IaacSecret {
+					name: "api-key".to_string(),
+					value: MaybeExternallySourced::FromEnvironment {
+						from_env: "API_KEY".to_string(),
+					},
+				},
+				serde_json::json!({
+					"name": "api-key",
+					"value": { "from_env": "API_KEY" },
+				}),
+			),
+		] {
+			assert_eq!(serde_json::to_value(&secret).unwrap(), json);
+			assert_eq!(serde_json::from_value::<IaacSecret>(json).unwrap(), secret);
+		}
+	}
+
+	#[test]
+	fn assert_iaac_secret_needs_a_resolved_value() {
+		let secret = IaacSecret {
+			name: "api-key".to_string(),
+			value: MaybeExternallySourced::FromEnvironment {
+				from_env: "API_KEY".to_string(),
+			},
+		};
+		assert_eq!(
+			secret.to_create_request(),
+			Err(IaacConversionError::ExternallySourcedValue {
+				field: "value".to_string()
+			})
+		);
+
+		let secret = IaacSecret {
+			value: MaybeExternallySourced::Value("hunter2".to_string()),
+			..secret
+		};
+		let request = secret.to_create_request().unwrap();
+		assert_eq!(request.value, "hunter2");
+		assert_eq!(IaacSecret::from(request), secret);
+	}
+}
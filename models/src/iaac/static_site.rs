@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+use crate::api::workspace::static_site::{CreateStaticSiteRequest, StaticSiteDetails};
+
+/// The IaaC format of a static site
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct IaacStaticSite {
+	/// The name of the static site
+	pub name: String,
+	/// Reserved for the directory containing the files of the static site,
+	/// relative to the IaaC file. Uploading the files from IaaC is not
+	/// supported yet, so a static site with a `source` is currently rejected
+	/// when it is planned. Static sites are created without any files, which
+	/// have to be uploaded to the static site afterwards.
+	#[serde(
+		alias = "dir",
+		alias = "directory",
+		alias = "path",
+		default,
+		skip_serializing_if = "Option::is_none"
+	)]
+	pub source: Option<String>,
+	/// The message of the upload of the files of the static site
+	#[serde(alias = "upload_message", default = "default_message")]
+	pub message: String,
+}
+
+/// The default message of an upload of a static site
+fn default_message() -> String {
+	"Uploaded from IaaC".to_string()
+}
+
+impl From<IaacStaticSite> for CreateStaticSiteRequest {
+	fn from(static_site: IaacStaticSite) -> Self {
+		Self {
+			name: static_site.name,
+			message: static_site.message,
+			// Static sites are always created without any files, since a
+			// `source` is rejected when planning
+			file: None,
+			static_site_details: StaticSiteDetails {},
+		}
+	}
+}
+
+impl From<CreateStaticSiteRequest> for IaacStaticSite {
+	fn from(request: CreateStaticSiteRequest) -> Self {
+		Self {
+			name: request.name,
+			source: None,
+			message: request.message,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::IaacStaticSite;
+	use crate::api::workspace::static_site::CreateStaticSiteRequest;
+
+	#[test]
+	fn assert_iaac_static_site_round_trips() {
+		let static_site = IaacStaticSite {
+			name: "docs".to_string(),
+			source: Some("./dist".to_string()),
+			message: "Uploaded from IaaC".to_string(),
+		};
+		let serialized = serde_json::to_value(&static_site).unwrap();
+		assert_eq!(
+			serialized,
+			serde_json::json!({
+				"name": "docs",
+				"source": "./dist",
+				"message": "Uploaded from IaaC",
+			})
+		);
+		assert_eq!(
+			serde_json::from_value::<IaacStaticSite>(serialized).unwrap(),
+			static_site
+		);
+		assert_eq!(
+			serde_json::from_value::<IaacStaticSite>(serde_json::json!({
+				"name": "docs",
+				"dir": "./dist",
+			}))
+			.unwrap(),
+			static_site
+		);
+	}
+
+	#[test]
+	fn assert_iaac_static_site_converts_to_and_from_request() {
+		let static_site = IaacStaticSite {
+			name: "docs".to_string(),
+			source: None,
+			message: "Initial upload".to_string(),
+		};
+		let request = CreateStaticSiteRequest::from(static_site.clone());
+		assert_eq!(request.file, None);
+		assert_eq!(IaacStaticSite::from(request), static_site);
+	}
+}
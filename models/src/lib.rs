@@ -7,8 +7,8 @@ pub mod api;
 pub mod ci;
 /// Any data that is sent to or from cloudflare (mostly KV)
 pub mod cloudflare;
-// / All infrastructure as code related structs and formats
-// pub mod iaac;
+/// All infrastructure as code related structs and formats
+pub mod iaac;
 /// All data related to permissions and RBAC data representation
 pub mod rbac;
 /// Utility functions and structs
@@ -27,6 +27,7 @@ pub mod prelude {
 		api::WithId,
 		rbac::{
 			BillingPermission,
+			CiRepositoryPermission,
 			ContainerRegistryRepositoryPermission,
 			DatabasePermission,
 			DeploymentPermission,
@@ -34,6 +35,7 @@ pub mod prelude {
 			DomainPermission,
 			ManagedURLPermission,
 			Permission,
+			ProjectPermission,
 			RunnerPermission,
 			SecretPermission,
 			StaticSitePermission,
@@ -48,6 +50,7 @@ pub mod prelude {
 			LoginId,
 			OneOrMore,
 			Paginated,
+			RateLimit,
 			StringifiedU16,
 			TotalCountHeader,
 			Uuid,
@@ -4,6 +4,7 @@ use std::{
 };
 
 use macros::RecursiveEnumIter;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use strum::{
 	Display,
@@ -85,6 +86,44 @@ pub enum ResourceType {
 	/// managed by Patr, and is automatically updated when the deployment /
 	/// static site is updated.
 	ManagedURL,
+	/// A git repository connected to a workspace. Pushes, tags and pull
+	/// requests on the repository run the CI pipeline of the repository on a
+	/// runner, and the status of the run is reported back to the repository.
+	CiRepository,
+}
+
+/// A list of all permissions that can be granted on a project.
+#[derive(
+	Eq,
+	Copy,
+	Hash,
+	Debug,
+	Clone,
+	Display,
+	EnumIter,
+	PartialEq,
+	Serialize,
+	EnumString,
+	EnumMessage,
+	Deserialize,
+	VariantNames,
+)]
+#[strum(serialize_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
+pub enum ProjectPermission {
+	/// This permission allows the user to create a new project in a workspace.
+	Create,
+	/// This permission allows the user to view the project and it's details,
+	/// but not edit it, delete it, or create a new one.
+	View,
+	/// This permission allows the user to edit the project, which includes
+	/// adding resources to and removing resources from the project, but not
+	/// delete it or create a new one.
+	Edit,
+	/// This permission allows the user to delete the project, but not create a
+	/// new one, view it, or edit it. The resources in the project are not
+	/// deleted along with it.
+	Delete,
 }
 
 /// A list of all permissions that can be granted on a Database.
@@ -513,6 +552,40 @@ pub enum VolumePermission {
 	Delete,
 }
 
+/// A list of all permissions that can be granted on a connected CI repository.
+#[derive(
+	Eq,
+	Copy,
+	Hash,
+	Debug,
+	Clone,
+	Display,
+	EnumIter,
+	PartialEq,
+	Serialize,
+	EnumString,
+	EnumMessage,
+	Deserialize,
+	VariantNames,
+)]
+#[strum(serialize_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
+pub enum CiRepositoryPermission {
+	/// This permission allows the user to connect a new git repository to a
+	/// workspace, so that its pipelines are run on the runners of the
+	/// workspace.
+	Connect,
+	/// This permission allows the user to view the connected repository, its
+	/// details and the runs of its pipelines, but not edit or disconnect it.
+	View,
+	/// This permission allows the user to edit the connected repository, such
+	/// as the runner its pipelines are run on, but not disconnect it.
+	Edit,
+	/// This permission allows the user to disconnect the repository from the
+	/// workspace, after which its webhooks no longer run any pipelines.
+	Disconnect,
+}
+
 /// A list of all permissions that can be granted on a resource.
 #[derive(
 	Eq,
@@ -531,6 +604,9 @@ pub enum VolumePermission {
 #[strum(serialize_all = "camelCase")]
 #[serde(rename_all = "camelCase")]
 pub enum Permission {
+	/// All permissions related to projects
+	#[strum(to_string = "project::{0}")]
+	Project(ProjectPermission),
 	/// All permissions related to a domains
 	#[strum(to_string = "domain::{0}")]
 	Domain(DomainPermission),
@@ -564,6 +640,9 @@ pub enum Permission {
 	/// All secret permissions
 	#[strum(to_string = "secret::{0}")]
 	Secret(SecretPermission),
+	/// All permissions for a connected CI repository
+	#[strum(to_string = "ciRepository::{0}")]
+	CiRepository(CiRepositoryPermission),
 	/// View all roles in a workspace
 	ViewRoles,
 	/// Edit roles in a workspace. This permission allows the user to edit
@@ -586,6 +665,7 @@ impl Permission {
 	/// the permission.
 	pub fn description(&self) -> String {
 		match self {
+			Permission::Project(permission) => permission.get_documentation(),
 			Permission::Domain(permission) => permission.get_documentation(),
 			Permission::DnsRecord(permission) => permission.get_documentation(),
 			Permission::Deployment(permission) => permission.get_documentation(),
@@ -597,6 +677,7 @@ impl Permission {
 			Permission::StaticSite(permission) => permission.get_documentation(),
 			Permission::Secret(permission) => permission.get_documentation(),
 			Permission::Volume(permission) => permission.get_documentation(),
+			Permission::CiRepository(permission) => permission.get_documentation(),
 			Permission::ViewRoles => self.get_documentation(),
 			Permission::ModifyRoles => self.get_documentation(),
 			Permission::EditWorkspace => self.get_documentation(),
@@ -615,6 +696,7 @@ impl FromStr for Permission {
 		};
 
 		Ok(match permission_type {
+			"project" => Self::Project(permission.parse()?),
 			"domain" => Self::Domain(permission.parse()?),
 			"dnsRecord" => Self::DnsRecord(permission.parse()?),
 			"deployment" => Self::Deployment(permission.parse()?),
@@ -626,6 +708,7 @@ impl FromStr for Permission {
 			"staticSite" => Self::StaticSite(permission.parse()?),
 			"secret" => Self::Secret(permission.parse()?),
 			"volume" => Self::Volume(permission.parse()?),
+			"ciRepository" => Self::CiRepository(permission.parse()?),
 			"viewRoles" => Self::ViewRoles,
 			"modifyRoles" => Self::ModifyRoles,
 			"editWorkspace" => Self::EditWorkspace,
@@ -678,7 +761,7 @@ where
 }
 
 /// Represents the kind of permission that is granted on a workspace.
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "camelCase", tag = "type")]
 pub enum WorkspacePermission {
 	/// The user is the super admin of the workspace.
@@ -796,7 +879,7 @@ impl WorkspacePermission {
 }
 
 /// Represents the type of permission that is granted on a set of Resource IDs.
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, EnumDiscriminants)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, EnumDiscriminants, JsonSchema)]
 #[serde(
 	rename_all = "camelCase",
 	tag = "permissionType",
@@ -804,8 +887,9 @@ impl WorkspacePermission {
 )]
 #[strum_discriminants(
 	name(ResourcePermissionTypeDiscriminant),
-	derive(strum::Display),
+	derive(strum::Display, Serialize, Deserialize, JsonSchema),
 	strum(serialize_all = "snake_case"),
+	serde(rename_all = "camelCase"),
 	cfg_attr(not(target_arch = "wasm32"), derive(sqlx::Type)),
 	cfg_attr(
 		not(target_arch = "wasm32"),
@@ -814,13 +898,15 @@ impl WorkspacePermission {
 )]
 pub enum ResourcePermissionType {
 	/// The user is allowed to access a set of Resource IDs. Any other
-	/// Resource IDs are by default not allowed.
+	/// Resource IDs are by default not allowed. If a project ID is in the set,
+	/// all the resources in that project are allowed.
 	Include(
 		/// Set of Resource IDs to allow
 		BTreeSet<Uuid>,
 	),
 	/// The user is not allowed to access a set of Resource IDs. Any other
-	/// Resource IDs are by default allowed.
+	/// Resource IDs are by default allowed. If a project ID is in the set, all
+	/// the resources in that project are not allowed.
 	Exclude(
 		/// Set of Resource IDs to not allow
 		BTreeSet<Uuid>,
@@ -153,7 +153,16 @@ impl ApiErrorResponse {
 
 impl IntoResponse for ApiErrorResponse {
 	fn into_response(self) -> axum::response::Response {
-		(self.status_code, Json(self.body)).into_response()
+		if let Some(retry_after) = self.body.error.retry_after() {
+			(
+				self.status_code,
+				[(http::header::RETRY_AFTER, retry_after.to_string())],
+				Json(self.body),
+			)
+				.into_response()
+		} else {
+			(self.status_code, Json(self.body)).into_response()
+		}
 	}
 }
 
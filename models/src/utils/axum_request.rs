@@ -23,6 +23,13 @@ where
 	fn from_axum_request(
 		request: Request<Body>,
 	) -> impl Future<Output = Result<Self, ErrorType>> + Send;
+
+	/// The parsed body as JSON, so that it can be recorded (such as in the
+	/// audit log). Bodies that are streamed, such as uploads and websockets,
+	/// can't be recorded and return [`None`].
+	fn to_json(&self) -> Option<serde_json::Value> {
+		None
+	}
 }
 
 impl<T> FromAxumRequest for T
@@ -47,4 +54,8 @@ where
 				.map(|Json(body)| body)
 		}
 	}
+
+	fn to_json(&self) -> Option<serde_json::Value> {
+		serde_json::to_value(self).ok()
+	}
 }
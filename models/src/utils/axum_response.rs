@@ -21,6 +21,13 @@ pub trait IntoAxumResponse {
 	/// Convert the type to a [`Response`] that can be used with [`axum`].
 	fn into_axum_response(self) -> Response;
 
+	/// The response as JSON, so that it can be recorded (such as in the audit
+	/// log). Responses that are streamed, such as websockets, can't be recorded
+	/// and return [`None`].
+	fn to_json(&self) -> Option<serde_json::Value> {
+		None
+	}
+
 	/// Check if the type is the same as the type parameter.
 	fn is<T>(&self) -> bool
 	where
@@ -49,6 +56,10 @@ where
 			.into_response(),
 		}
 	}
+
+	fn to_json(&self) -> Option<serde_json::Value> {
+		serde_json::to_value(self).ok()
+	}
 }
 
 /// A type that can be used to return a custom [`Response`] from an endpoint.
@@ -1,5 +1,10 @@
 use std::ops::Deref;
 
+use schemars::{
+	gen::SchemaGenerator,
+	schema::{InstanceType, Schema, SchemaObject},
+	JsonSchema,
+};
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 
 /// A type that can be used to represent a constant `false` boolean.
@@ -98,8 +103,47 @@ impl AsRef<bool> for False {
 	}
 }
 
+impl JsonSchema for False {
+	fn is_referenceable() -> bool {
+		false
+	}
+
+	fn schema_name() -> String {
+		"False".to_string()
+	}
+
+	fn json_schema(_: &mut SchemaGenerator) -> Schema {
+		constant_bool_schema(false)
+	}
+}
+
+impl JsonSchema for True {
+	fn is_referenceable() -> bool {
+		false
+	}
+
+	fn schema_name() -> String {
+		"True".to_string()
+	}
+
+	fn json_schema(_: &mut SchemaGenerator) -> Schema {
+		constant_bool_schema(true)
+	}
+}
+
+/// The schema of a boolean that can only have the given value
+fn constant_bool_schema(value: bool) -> Schema {
+	SchemaObject {
+		instance_type: Some(InstanceType::Boolean.into()),
+		const_value: Some(value.into()),
+		..Default::default()
+	}
+	.into()
+}
+
 #[cfg(test)]
 mod tests {
+	use schemars::schema_for;
 	use serde_test::{assert_tokens, Token};
 
 	use super::{False, True};
@@ -113,4 +157,15 @@ mod tests {
 	fn assert_false_types() {
 		assert_tokens(&False, &[Token::Bool(false)]);
 	}
+
+	#[test]
+	fn assert_bool_schemas_are_constant() {
+		let schema = serde_json::to_value(schema_for!(True)).unwrap();
+		assert_eq!(schema["type"], "boolean");
+		assert_eq!(schema["const"], true);
+
+		let schema = serde_json::to_value(schema_for!(False)).unwrap();
+		assert_eq!(schema["type"], "boolean");
+		assert_eq!(schema["const"], false);
+	}
 }
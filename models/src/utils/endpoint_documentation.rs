@@ -0,0 +1,246 @@
+use std::marker::PhantomData;
+
+use ipnetwork::IpNetwork;
+use preprocess::Preprocessable;
+pub use schemars::{
+	gen::{SchemaGenerator, SchemaSettings},
+	schema::Schema,
+};
+use schemars::JsonSchema;
+use time::{Duration, OffsetDateTime};
+
+use crate::prelude::*;
+
+/// The documentation of an API endpoint, generated by
+/// [`macros::declare_api_endpoint`] and [`macros::declare_stream_endpoint`]
+/// from the declaration of the endpoint. This is used to build the OpenAPI
+/// document of the API.
+#[derive(Debug, Clone)]
+pub struct EndpointDocumentation {
+	/// The name of the endpoint, such as `ConnectCiRepository`
+	pub name: &'static str,
+	/// The documentation comment of the endpoint
+	pub description: &'static str,
+	/// Who can access the endpoint
+	pub authentication: EndpointAuthentication,
+	/// The parameters in the path of the endpoint
+	pub path: Vec<FieldDocumentation>,
+	/// The query parameters of the endpoint, without the pagination parameters
+	pub query: Vec<FieldDocumentation>,
+	/// Whether the endpoint is paginated, in which case it takes the `count`
+	/// and `page` query parameters
+	pub paginated: bool,
+	/// The headers that the request must have
+	pub request_headers: Vec<HeaderDocumentation>,
+	/// The body of the request, if the endpoint takes one
+	pub request_body: Option<RequestBodyDocumentation>,
+	/// The headers that the response has
+	pub response_headers: Vec<HeaderDocumentation>,
+	/// What the endpoint responds with
+	pub response: ResponseDocumentation,
+}
+
+/// Who can access an endpoint, as documented in its [`EndpointDocumentation`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointAuthentication {
+	/// Anyone can access the endpoint, without a token
+	None,
+	/// Any logged in user can access the endpoint
+	PlainToken,
+	/// Only the super admin of the workspace in the request can access the
+	/// endpoint
+	WorkspaceSuperAdmin,
+	/// Only the members of the workspace in the request can access the
+	/// endpoint
+	WorkspaceMembership,
+	/// Only the users with the given permission on the resource in the request
+	/// can access the endpoint
+	ResourcePermission(Permission),
+}
+
+impl<E> From<AppAuthentication<E>> for EndpointAuthentication
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+	fn from(authentication: AppAuthentication<E>) -> Self {
+		match authentication {
+			AppAuthentication::PlainTokenAuthenticator => Self::PlainToken,
+			AppAuthentication::WorkspaceSuperAdminAuthenticator { .. } => Self::WorkspaceSuperAdmin,
+			AppAuthentication::WorkspaceMembershipAuthenticator { .. } => Self::WorkspaceMembership,
+			AppAuthentication::ResourcePermissionAuthenticator { permission, .. } => {
+				Self::ResourcePermission(permission)
+			}
+		}
+	}
+}
+
+/// The body of a request to an endpoint, as documented in its
+/// [`EndpointDocumentation`]
+#[derive(Debug, Clone)]
+pub enum RequestBodyDocumentation {
+	/// A JSON body with the given fields
+	Json(Vec<FieldDocumentation>),
+	/// A raw stream of bytes, of at most `max_size` bytes
+	Raw {
+		/// The maximum size of the body, in bytes
+		max_size: u64,
+	},
+	/// A `multipart/form-data` body with the given fields, followed by any
+	/// number of files
+	Multipart {
+		/// The fields of the form that are not files
+		fields: Vec<FieldDocumentation>,
+		/// The maximum size of the whole body, in bytes
+		max_size: u64,
+	},
+}
+
+/// What an endpoint responds with, as documented in its
+/// [`EndpointDocumentation`]
+#[derive(Debug, Clone)]
+pub enum ResponseDocumentation {
+	/// The endpoint responds with a JSON body with the given fields, along with
+	/// `success: true`
+	Json(Vec<FieldDocumentation>),
+	/// The endpoint upgrades the request to a websocket, over which JSON
+	/// messages are sent
+	WebSocket,
+}
+
+/// The documentation of a field of the path, query or body of an endpoint
+#[derive(Debug, Clone)]
+pub struct FieldDocumentation {
+	/// The name of the field, as it is serialized
+	pub name: String,
+	/// The documentation comment of the field
+	pub description: String,
+	/// Whether the field must always be present
+	pub required: bool,
+	/// Whether the fields of the field are flattened into the struct the field
+	/// is in, instead of the field being nested
+	pub flatten: bool,
+	/// The JSON schema of the field
+	pub schema: Schema,
+}
+
+/// The documentation of a header of a request or a response of an endpoint
+#[derive(Debug, Clone)]
+pub struct HeaderDocumentation {
+	/// The name of the header, in lowercase
+	pub name: &'static str,
+	/// The documentation comment of the header
+	pub description: String,
+}
+
+/// A helper to get the JSON schema of a type in the generated documentation of
+/// an endpoint. Types that implement [`JsonSchema`] use their own schema, and
+/// the few foreign types that do not, such as [`OffsetDateTime`], are
+/// documented as the type they are serialized as. Any other type is a compile
+/// error, so that every field of an endpoint is documented.
+///
+/// The schema is picked using autoref specialization, which only works on
+/// concrete types:
+/// ```rust,ignore
+/// use models::utils::{SchemaFromForeignType as _, SchemaFromJsonSchema as _};
+///
+/// let schema = (&&SchemaOf::<String>::new()).schema(generator);
+/// ```
+pub struct SchemaOf<T: ?Sized>(PhantomData<T>);
+
+impl<T> SchemaOf<T>
+where
+	T: ?Sized,
+{
+	/// Create a new helper to get the JSON schema of `T`
+	pub const fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T> Default for SchemaOf<T>
+where
+	T: ?Sized,
+{
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Gets the JSON schema of a type that implements [`JsonSchema`]. See
+/// [`SchemaOf`] for how this is used.
+pub trait SchemaFromJsonSchema {
+	/// Get the JSON schema of the type
+	fn schema(&self, generator: &mut SchemaGenerator) -> Schema;
+}
+
+impl<T> SchemaFromJsonSchema for &SchemaOf<T>
+where
+	T: JsonSchema + ?Sized,
+{
+	fn schema(&self, generator: &mut SchemaGenerator) -> Schema {
+		generator.subschema_for::<T>()
+	}
+}
+
+/// Gets the JSON schema of a foreign type that does not implement
+/// [`JsonSchema`], as the schema of the type it is serialized as. See
+/// [`SchemaOf`] for how this is used.
+pub trait SchemaFromForeignType {
+	/// Get the JSON schema of the type
+	fn schema(&self, generator: &mut SchemaGenerator) -> Schema;
+}
+
+/// Implements [`SchemaFromForeignType`] for `SchemaOf<$foreign>`, using the
+/// schema of `$serialized_as`
+macro_rules! schema_from_foreign_type {
+	($($foreign:ty => $serialized_as:ty),* $(,)?) => {
+		$(
+			impl SchemaFromForeignType for SchemaOf<$foreign> {
+				fn schema(&self, generator: &mut SchemaGenerator) -> Schema {
+					generator.subschema_for::<$serialized_as>()
+				}
+			}
+		)*
+	};
+}
+
+schema_from_foreign_type! {
+	OffsetDateTime => String,
+	Option<OffsetDateTime> => Option<String>,
+	Option<Duration> => Option<String>,
+	Option<Vec<IpNetwork>> => Option<Vec<String>>,
+}
+
+#[cfg(test)]
+mod tests {
+	use time::OffsetDateTime;
+
+	use super::{SchemaFromForeignType as _, SchemaFromJsonSchema as _, SchemaOf, SchemaSettings};
+
+	#[test]
+	fn assert_schemas_are_generated_for_documented_types() {
+		let mut generator = SchemaSettings::draft2019_09().into_generator();
+
+		let schema =
+			serde_json::to_value((&&SchemaOf::<Option<String>>::new()).schema(&mut generator))
+				.unwrap();
+		assert_eq!(schema, serde_json::json!({ "type": ["string", "null"] }));
+	}
+
+	#[test]
+	fn assert_foreign_types_are_documented_as_their_serialized_type() {
+		let mut generator = SchemaSettings::draft2019_09().into_generator();
+
+		let schema =
+			serde_json::to_value((&&SchemaOf::<OffsetDateTime>::new()).schema(&mut generator))
+				.unwrap();
+		assert_eq!(schema, serde_json::json!({ "type": "string" }));
+
+		let schema = serde_json::to_value(
+			(&&SchemaOf::<Option<OffsetDateTime>>::new()).schema(&mut generator),
+		)
+		.unwrap();
+		assert_eq!(schema, serde_json::json!({ "type": ["string", "null"] }));
+	}
+}
@@ -1,8 +1,9 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Represents a geo location. Used to identify where a user logged in from,
 /// etc (for audit log purposes).
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, PartialOrd, JsonSchema)]
 pub struct GeoLocation {
 	/// The latitude of the location.
 	pub latitude: f64,
@@ -7,6 +7,10 @@ mod audit_logger;
 /// endpoint. More details can be found in the documentation for the
 /// [`AppAuthentication`] enum.
 mod authentication_type;
+/// A middleware to represent the rate limits applied to an API endpoint, to
+/// protect it from brute-force attacks. More details can be found in the
+/// documentation for the [`RateLimit`] struct.
+mod rate_limit;
 
 #[allow(unused_imports)]
-pub use self::{audit_logger::*, authentication_type::*};
+pub use self::{audit_logger::*, authentication_type::*, rate_limit::*};
@@ -0,0 +1,75 @@
+use std::fmt::Debug;
+
+use preprocess::Preprocessable;
+
+use crate::prelude::*;
+
+/// This struct represents the rate limits that are applied to an API endpoint.
+/// This is mostly used for endpoints that are susceptible to brute-force
+/// attacks, such as login, password reset, MFA verification, etc.
+///
+/// Two kinds of limits are enforced on an endpoint:
+/// - A sliding-window limit on the number of requests that can be made from a
+///   single IP address within the window.
+/// - A sliding-window limit on the number of failed attempts on a single
+///   account (as given by [`extract_account`][1]) within the window. Once this
+///   limit is crossed, the account is locked for a period of time that
+///   increases with every subsequent lockout.
+///
+/// This struct is used in the [`ApiEndpoint`] trait to specify the rate limits
+/// of an endpoint. The router extension uses this to mount the corresponding
+/// [`tower::Layer`] in the router.
+///
+/// [1]: RateLimit::extract_account
+pub struct RateLimit<E>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+	/// The name of the bucket that the requests are counted in. Endpoints that
+	/// share the same bucket will share the same per-IP request count.
+	pub bucket: &'static str,
+	/// The maximum number of requests that can be made from a single IP
+	/// address within the rate limiting window.
+	pub max_requests_per_ip: u32,
+	/// The maximum number of failed attempts that can be made on a single
+	/// account within the rate limiting window, before the account is locked.
+	pub max_failures_per_account: u32,
+	/// This function is used to extract the account identifier (such as the
+	/// username or email) from the request. The failed attempts and lockouts
+	/// are tracked against this identifier. For authenticated endpoints, the
+	/// failed attempts are always tracked against the authenticated user and
+	/// this function is not used.
+	pub extract_account: Option<fn(&ApiRequest<E>) -> String>,
+}
+
+impl<E> Clone for RateLimit<E>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<E> Copy for RateLimit<E>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+}
+
+impl<E> Debug for RateLimit<E>
+where
+	E: ApiEndpoint,
+	<E::RequestBody as Preprocessable>::Processed: Send,
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("RateLimit")
+			.field("bucket", &self.bucket)
+			.field("max_requests_per_ip", &self.max_requests_per_ip)
+			.field("max_failures_per_account", &self.max_failures_per_account)
+			.finish_non_exhaustive()
+	}
+}
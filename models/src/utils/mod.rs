@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// This module contains all the utilities used for parsing a request and using
@@ -15,6 +16,9 @@ mod base64string;
 /// A set of constant booleans that are used to ensure that the values are
 /// forced to be either true or false.
 mod bools;
+/// The documentation of an API endpoint, generated from its declaration. This
+/// is used by the API to build the OpenAPI document of all the endpoints.
+mod endpoint_documentation;
 /// Represents a location on the planet. This is used to represent the location
 /// of a user, a login, etc. Basically just a latitude and longitude.
 mod geo_location;
@@ -25,6 +29,9 @@ mod header_utils;
 /// A set of middlewares that are used by the API to perform certain tasks, like
 /// authentication, audit logging, etc.
 mod middlewares;
+/// A `multipart/form-data` request body, made of fields that are parsed with
+/// the request and files that are streamed by the endpoint.
+mod multipart;
 /// Represents a value that can be either one or many. This is used to represent
 /// a value that can be either a single value or a list of values, such as
 /// audience in a JWT, a dependency string in a CI yaml file, etc.
@@ -33,6 +40,9 @@ mod one_or_many;
 /// request enforces a response header to be present, which provides the total
 /// number of items in the response.
 mod paginated;
+/// A raw request body, streamed by the endpoint instead of being parsed with the
+/// request. This is used for uploads that should not be buffered in memory.
+mod raw_body;
 /// A helper type that serializes and deserializes u16 values as strings. This
 /// is used for using u16 values as keys in a JSON object.
 mod stringified_u16;
@@ -52,11 +62,14 @@ pub use self::{
 	axum_response::*,
 	base64string::*,
 	bools::*,
+	endpoint_documentation::*,
 	geo_location::*,
 	header_utils::*,
 	middlewares::*,
+	multipart::*,
 	one_or_many::*,
 	paginated::*,
+	raw_body::*,
 	stringified_u16::*,
 	tuple_utils::*,
 	uuid::*,
@@ -173,7 +186,19 @@ pub mod constants {
 }
 
 /// Ordering of the list for paginated requests
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[derive(
+	Clone,
+	Copy,
+	Debug,
+	Default,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Deserialize,
+	Serialize,
+	JsonSchema,
+)]
 #[serde(rename_all = "camelCase")]
 pub enum ListOrder {
 	/// Ascending order
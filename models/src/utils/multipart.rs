@@ -0,0 +1,273 @@
+use axum::{
+	body::{Body, Bytes},
+	http::{header::CONTENT_TYPE, Request},
+};
+use futures::{Stream, TryStreamExt};
+use multer::{Constraints, Field, SizeLimit};
+use preprocess::Preprocessable;
+use serde::{de::DeserializeOwned, Serialize};
+use sync_wrapper::SyncWrapper;
+
+use super::{raw_body, FromAxumRequest, RequiresRequestHeaders, RequiresResponseHeaders};
+use crate::ErrorType;
+
+/// A `multipart/form-data` body, used by endpoints that take files along with
+/// some fields. The fields are parsed into `T` when the request is parsed, the
+/// same way a query would be. The files are not read until the endpoint asks
+/// for them, so that they can be streamed to wherever they need to go without
+/// buffering them in memory.
+///
+/// All the fields must come before the first file in the form. The whole body
+/// can be at most `MAX_SIZE` bytes long, after which reading from it fails with
+/// [`ErrorType::PayloadTooLarge`].
+pub struct Multipart<T, const MAX_SIZE: u64> {
+	/// The fields of the form that are not files
+	pub fields: T,
+	/// The files in the form, in the order they were sent
+	pub files: MultipartFiles,
+}
+
+/// The files in a [`Multipart`] body. Each file has to be read (or dropped)
+/// before the next one can be read.
+pub struct MultipartFiles {
+	/// The rest of the body, after the part that has already been read
+	multipart: SyncWrapper<multer::Multipart<'static>>,
+	/// The first file in the form, which had to be read to know that all the
+	/// fields before it were parsed
+	first_file: Option<SyncWrapper<Field<'static>>>,
+}
+
+impl MultipartFiles {
+	/// Returns the next file in the form, or [`None`] if there are no more
+	/// files
+	pub async fn next_file(&mut self) -> Result<Option<FilePart>, ErrorType> {
+		if let Some(file) = self.first_file.take() {
+			return Ok(Some(FilePart(file.into_inner())));
+		}
+
+		let Some(field) = self
+			.multipart
+			.get_mut()
+			.next_field()
+			.await
+			.map_err(multipart_error)?
+		else {
+			return Ok(None);
+		};
+		if field.file_name().is_none() {
+			tracing::debug!("Field `{:?}` was sent after a file", field.name());
+			return Err(ErrorType::WrongParameters);
+		}
+
+		Ok(Some(FilePart(field)))
+	}
+}
+
+/// A file in a [`Multipart`] body
+pub struct FilePart(Field<'static>);
+
+impl FilePart {
+	/// The name of the field of the form that the file was sent in
+	pub fn name(&self) -> Option<&str> {
+		self.0.name()
+	}
+
+	/// The name of the file, as sent by the client
+	pub fn file_name(&self) -> Option<&str> {
+		self.0.file_name()
+	}
+
+	/// The content type of the file, as sent by the client
+	pub fn content_type(&self) -> Option<&str> {
+		self.0.content_type().map(AsRef::as_ref)
+	}
+
+	/// Consumes the file and returns a stream of its bytes
+	pub fn into_stream(self) -> impl Stream<Item = Result<Bytes, ErrorType>> + Send + Unpin {
+		self.0.map_err(multipart_error)
+	}
+}
+
+impl<T, const MAX_SIZE: u64> FromAxumRequest for Multipart<T, MAX_SIZE>
+where
+	T: Serialize + DeserializeOwned + Send,
+{
+	#[tracing::instrument(skip(request))]
+	async fn from_axum_request(request: Request<Body>) -> Result<Self, ErrorType> {
+		if raw_body::content_length(&request)?.is_some_and(|length| length > MAX_SIZE) {
+			tracing::debug!("Content length is larger than the maximum of {MAX_SIZE} bytes");
+			return Err(ErrorType::PayloadTooLarge);
+		}
+
+		let boundary = request
+			.headers()
+			.get(CONTENT_TYPE)
+			.and_then(|value| value.to_str().ok())
+			.and_then(|value| multer::parse_boundary(value).ok())
+			.ok_or_else(|| {
+				tracing::debug!("Request is not `multipart/form-data`");
+				ErrorType::WrongParameters
+			})?;
+		let mut multipart = multer::Multipart::with_constraints(
+			request.into_body().into_data_stream(),
+			boundary,
+			Constraints::new().size_limit(SizeLimit::new().whole_stream(MAX_SIZE)),
+		);
+
+		let mut fields = Vec::new();
+		let first_file = loop {
+			let Some(field) = multipart.next_field().await.map_err(multipart_error)? else {
+				break None;
+			};
+			if field.file_name().is_some() {
+				break Some(SyncWrapper::new(field));
+			}
+
+			let Some(name) = field.name().map(String::from) else {
+				tracing::debug!("Field without a name in the form");
+				return Err(ErrorType::WrongParameters);
+			};
+			fields.push((name, field.text().await.map_err(multipart_error)?));
+		};
+
+		// The fields are parsed the same way as a urlencoded form, so that
+		// numbers, booleans, etc. can be used as fields
+		let fields = serde_urlencoded::to_string(&fields)
+			.map_err(|err| err.to_string())
+			.and_then(|form| serde_urlencoded::from_str(&form).map_err(|err| err.to_string()))
+			.map_err(|err| {
+				tracing::debug!("Failed to parse fields: {}", err);
+				ErrorType::WrongParameters
+			})?;
+
+		Ok(Self {
+			fields,
+			files: MultipartFiles {
+				multipart: SyncWrapper::new(multipart),
+				first_file,
+			},
+		})
+	}
+
+	fn to_json(&self) -> Option<serde_json::Value> {
+		// Only the fields are recorded, since the files are streamed
+		serde_json::to_value(&self.fields).ok()
+	}
+}
+
+impl<T, const MAX_SIZE: u64> Preprocessable for Multipart<T, MAX_SIZE>
+where
+	T: Preprocessable,
+{
+	type Processed = Multipart<T::Processed, MAX_SIZE>;
+
+	fn preprocess(self) -> Result<Self::Processed, preprocess::Error> {
+		Ok(Multipart {
+			fields: self.fields.preprocess()?,
+			files: self.files,
+		})
+	}
+}
+
+impl<T, const MAX_SIZE: u64> RequiresRequestHeaders for Multipart<T, MAX_SIZE> {
+	type RequiredRequestHeaders = ();
+}
+
+impl<T, const MAX_SIZE: u64> RequiresResponseHeaders for Multipart<T, MAX_SIZE> {
+	type RequiredResponseHeaders = ();
+}
+
+/// Converts an error from reading a multipart body to an [`ErrorType`]
+fn multipart_error(error: multer::Error) -> ErrorType {
+	match error {
+		multer::Error::StreamSizeExceeded { .. } | multer::Error::FieldSizeExceeded { .. } => {
+			ErrorType::PayloadTooLarge
+		}
+		error => {
+			tracing::debug!("Failed to read multipart body: {}", error);
+			ErrorType::WrongParameters
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use axum::{
+		body::Body,
+		http::{header::CONTENT_TYPE, Request},
+	};
+	use futures::{executor::block_on, TryStreamExt};
+	use serde::{Deserialize, Serialize};
+
+	use super::Multipart;
+	use crate::{utils::FromAxumRequest, ErrorType};
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	struct Fields {
+		message: String,
+		retries: u32,
+	}
+
+	fn form_request(body: &'static str) -> Request<Body> {
+		Request::builder()
+			.header(CONTENT_TYPE, "multipart/form-data; boundary=X")
+			.body(Body::from(body.replace('\n', "\r\n")))
+			.unwrap()
+	}
+
+	const FORM: &str = "--X
+Content-Disposition: form-data; name=\"message\"
+
+v1.0.0
+--X
+Content-Disposition: form-data; name=\"retries\"
+
+3
+--X
+Content-Disposition: form-data; name=\"file\"; filename=\"site.zip\"
+Content-Type: application/zip
+
+contents
+--X--
+";
+
+	#[test]
+	fn assert_multipart_fields_and_files_are_parsed() {
+		block_on(async {
+			let Multipart { fields, mut files } =
+				Multipart::<Fields, 1024>::from_axum_request(form_request(FORM))
+					.await
+					.unwrap();
+			assert_eq!(
+				fields,
+				Fields {
+					message: "v1.0.0".to_string(),
+					retries: 3,
+				}
+			);
+
+			let file = files.next_file().await.unwrap().unwrap();
+			assert_eq!(file.name(), Some("file"));
+			assert_eq!(file.file_name(), Some("site.zip"));
+			assert_eq!(file.content_type(), Some("application/zip"));
+			let contents = file
+				.into_stream()
+				.map_ok(|chunk| chunk.to_vec())
+				.try_concat()
+				.await
+				.unwrap();
+			assert_eq!(contents, b"contents");
+
+			assert!(files.next_file().await.unwrap().is_none());
+		});
+	}
+
+	#[test]
+	fn assert_multipart_body_is_limited() {
+		block_on(async {
+			let result = Multipart::<Fields, 16>::from_axum_request(form_request(FORM)).await;
+			assert!(matches!(result, Err(ErrorType::PayloadTooLarge)));
+		});
+	}
+}
@@ -0,0 +1,165 @@
+use axum::{
+	body::{Body, Bytes},
+	http::{header::CONTENT_LENGTH, Request},
+};
+use futures::{Stream, StreamExt};
+use preprocess::Preprocessable;
+use sync_wrapper::SyncWrapper;
+
+use super::{FromAxumRequest, RequiresRequestHeaders, RequiresResponseHeaders};
+use crate::ErrorType;
+
+/// A raw stream of bytes, used as the body of endpoints that take an upload of
+/// any kind, such as an archive or a blob. The body is not read when the
+/// request is parsed, so that it can be streamed to wherever it needs to go
+/// without buffering it in memory.
+///
+/// The body can be at most `MAX_SIZE` bytes long. Requests with a larger
+/// `Content-Length` are rejected before the endpoint is called, and the stream
+/// fails with [`ErrorType::PayloadTooLarge`] as soon as more than `MAX_SIZE`
+/// bytes have been read from it.
+pub struct RawBody<const MAX_SIZE: u64> {
+	/// The `Content-Length` of the request, if the client sent one
+	content_length: Option<u64>,
+	/// The body of the request. [`Body`] is not [`Sync`], but it is only ever
+	/// accessed by value, so it can be wrapped.
+	body: SyncWrapper<Body>,
+}
+
+impl<const MAX_SIZE: u64> RawBody<MAX_SIZE> {
+	/// Create a raw body from the body of a request
+	pub fn new(body: Body, content_length: Option<u64>) -> Self {
+		Self {
+			content_length,
+			body: SyncWrapper::new(body),
+		}
+	}
+
+	/// The `Content-Length` of the request, if the client sent one
+	pub fn content_length(&self) -> Option<u64> {
+		self.content_length
+	}
+
+	/// Consumes the body and returns a stream of its bytes. The stream fails
+	/// with [`ErrorType::PayloadTooLarge`] once more than `MAX_SIZE` bytes have
+	/// been read.
+	pub fn into_stream(self) -> impl Stream<Item = Result<Bytes, ErrorType>> + Send + Unpin {
+		let mut read = 0u64;
+		self.body
+			.into_inner()
+			.into_data_stream()
+			.map(move |chunk| -> Result<Bytes, ErrorType> {
+				let chunk = chunk.map_err(|err| {
+					tracing::debug!("Failed to read body: {}", err);
+					ErrorType::WrongParameters
+				})?;
+				read = read.saturating_add(chunk.len() as u64);
+				if read > MAX_SIZE {
+					return Err(ErrorType::PayloadTooLarge);
+				}
+				Ok(chunk)
+			})
+	}
+}
+
+impl<const MAX_SIZE: u64> FromAxumRequest for RawBody<MAX_SIZE> {
+	#[tracing::instrument(skip(request))]
+	async fn from_axum_request(request: Request<Body>) -> Result<Self, ErrorType> {
+		let content_length = content_length(&request)?;
+		if content_length.is_some_and(|length| length > MAX_SIZE) {
+			tracing::debug!(
+				"Content length `{:?}` is larger than the maximum of {} bytes",
+				content_length,
+				MAX_SIZE
+			);
+			return Err(ErrorType::PayloadTooLarge);
+		}
+
+		Ok(Self::new(request.into_body(), content_length))
+	}
+}
+
+impl<const MAX_SIZE: u64> Preprocessable for RawBody<MAX_SIZE> {
+	type Processed = Self;
+
+	fn preprocess(self) -> Result<Self, preprocess::Error> {
+		Ok(self)
+	}
+}
+
+impl<const MAX_SIZE: u64> RequiresRequestHeaders for RawBody<MAX_SIZE> {
+	type RequiredRequestHeaders = ();
+}
+
+impl<const MAX_SIZE: u64> RequiresResponseHeaders for RawBody<MAX_SIZE> {
+	type RequiredResponseHeaders = ();
+}
+
+/// Parses the `Content-Length` header of a request, if there is one
+pub(super) fn content_length(request: &Request<Body>) -> Result<Option<u64>, ErrorType> {
+	request
+		.headers()
+		.get(CONTENT_LENGTH)
+		.map(|value| {
+			value
+				.to_str()
+				.ok()
+				.and_then(|value| value.parse().ok())
+				.ok_or_else(|| {
+					tracing::debug!("Invalid content length: {:?}", value);
+					ErrorType::WrongParameters
+				})
+		})
+		.transpose()
+}
+
+#[cfg(test)]
+mod tests {
+	use axum::{
+		body::Body,
+		http::{header::CONTENT_LENGTH, Request},
+	};
+	use futures::{executor::block_on, TryStreamExt};
+
+	use super::RawBody;
+	use crate::{utils::FromAxumRequest, ErrorType};
+
+	#[test]
+	fn assert_raw_body_is_streamed() {
+		block_on(async {
+			let body = RawBody::<8>::from_axum_request(Request::new(Body::from("contents")))
+				.await
+				.unwrap();
+			let contents = body
+				.into_stream()
+				.map_ok(|chunk| chunk.to_vec())
+				.try_concat()
+				.await
+				.unwrap();
+			assert_eq!(contents, b"contents");
+		});
+	}
+
+	#[test]
+	fn assert_raw_body_is_limited() {
+		block_on(async {
+			let request = Request::builder()
+				.header(CONTENT_LENGTH, "9")
+				.body(Body::from("too large"))
+				.unwrap();
+			let result = RawBody::<8>::from_axum_request(request).await;
+			assert!(matches!(result, Err(ErrorType::PayloadTooLarge)));
+
+			// Without a content length, the limit is enforced while streaming
+			let body = RawBody::<8>::from_axum_request(Request::new(Body::from("too large")))
+				.await
+				.unwrap();
+			let result = body
+				.into_stream()
+				.map_ok(|chunk| chunk.to_vec())
+				.try_concat()
+				.await;
+			assert!(matches!(result, Err(ErrorType::PayloadTooLarge)));
+		});
+	}
+}
@@ -51,7 +51,7 @@ where
 	/// The headers of the request.
 	pub headers: E::RequestHeaders,
 	/// The body of the request. This is the actual data that was sent by the
-	/// client. Can be either JSON or Websockets.
+	/// client. Can be either JSON, a raw or multipart stream, or Websockets.
 	pub body: <E::RequestBody as Preprocessable>::Processed,
 }
 
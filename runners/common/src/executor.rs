@@ -1,14 +1,18 @@
 use std::{future::Future, time::Duration};
 
 use futures::Stream;
-use models::api::workspace::deployment::*;
+use models::{
+	api::workspace::deployment::*,
+	ci::{CiPipelineRun, CiPipelineRunReport, CiStepLog},
+};
 use serde::{de::DeserializeOwned, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
 
 use crate::prelude::*;
 
 /// This trait is the main trait that the runner needs to implement to run the
 /// resources.
-pub trait RunnerExecutor: Sized {
+pub trait RunnerExecutor: Sized + Send + Sync {
 	/// The reconciliation interval for the runner. This is the interval at
 	/// which the runner will reconcile ALL the resources with the server. The
 	/// default is 10 minutes.
@@ -45,4 +49,21 @@ pub trait RunnerExecutor: Sized {
 	/// This function should return a stream of all the running deployment IDs
 	/// in the runner, sorted by the deployment ID.
 	fn list_running_deployments<'a>(&self) -> impl Future<Output = impl Stream<Item = Uuid> + 'a>;
+
+	/// This function should return the number of replicas of a deployment that
+	/// are running right now, or [`None`] if that could not be determined. This
+	/// is reported to the Patr API, which bills the deployment for the replicas
+	/// that were running.
+	fn count_running_replicas(&self, deployment_id: Uuid) -> impl Future<Output = Option<u16>>;
+
+	/// This function is called when a CI pipeline needs to be run. The runner
+	/// should clone the repository, start the services of the pipeline, and
+	/// run its steps, sending the logs of each step as they are written. The
+	/// report of the run is returned once the run is complete. Runs are
+	/// executed in the background, so this can take as long as the run needs.
+	fn run_ci_pipeline(
+		&self,
+		run: WithId<CiPipelineRun>,
+		logs: UnboundedSender<CiStepLog>,
+	) -> impl Future<Output = CiPipelineRunReport> + Send;
 }
@@ -33,7 +33,7 @@ pub mod prelude {
 		app::{AppRequest, AppState, ProcessedApiRequest},
 		executor::RunnerExecutor,
 		runner::Runner,
-		utils::{client, config::*, constants, ext_traits::*},
+		utils::{config::*, constants, ext_traits::*},
 	};
 
 	/// The type of the database connection. A mutable reference to this should
@@ -19,6 +19,7 @@ pub async fn sign_up(
 						username,
 						password,
 						recovery_method: _,
+						invite_token: _,
 					},
 			},
 		database,
@@ -10,11 +10,12 @@ pub async fn list_deployment(
 		request:
 			ProcessedApiRequest {
 				path: ListDeploymentPath { workspace_id: _ },
-				query: Paginated {
-					data: (),
-					count,
-					page,
-				},
+				query:
+					Paginated {
+						data: ListDeploymentQuery { project_id: _ },
+						count,
+						page,
+					},
 				headers:
 					ListDeploymentRequestHeaders {
 						authorization: _,
@@ -0,0 +1,146 @@
+use models::{
+	api::workspace::runner::*,
+	ci::{CiPipelineRun, CiRunStatus, CiStepLog},
+};
+use tokio::{sync::mpsc::unbounded_channel, task};
+
+use crate::prelude::*;
+
+/// The most lines of logs that are sent to the Patr API at once
+const LOG_BATCH_SIZE: usize = 500;
+
+impl<E> super::Runner<E>
+where
+	E: RunnerExecutor + Clone + 'static,
+{
+	/// Start a run of a CI pipeline in the background. The logs of the steps
+	/// are written to the logs of the runner as they come in, followed by the
+	/// status and duration of every step once the run is complete. In managed
+	/// mode, the logs are also sent to the Patr API as they come in, and the
+	/// report of the run is sent once it is complete.
+	pub(super) fn start_ci_pipeline_run(&self, run: WithId<CiPipelineRun>) {
+		let executor = self.executor.clone();
+		let mode = self.state.config.mode.clone();
+		task::spawn(async move {
+			let run_id = run.id;
+			info!("Starting CI pipeline run `{}`", run_id);
+
+			let (logs_sender, mut logs_receiver) = unbounded_channel::<CiStepLog>();
+			let logs_mode = mode.clone();
+			let logs_task = task::spawn(async move {
+				let mut first_line = 0;
+				let mut logs = Vec::new();
+				// Every line written since the last batch was sent is sent as the
+				// next batch
+				while logs_receiver.recv_many(&mut logs, LOG_BATCH_SIZE).await > 0 {
+					for CiStepLog { step, line } in &logs {
+						info!("[{}] [{}] {}", run_id, step, line);
+					}
+
+					if let RunnerMode::Managed {
+						workspace_id,
+						runner_id,
+						api_token,
+						join_token: _,
+						user_agent,
+					} = &logs_mode
+					{
+						_ = client::make_request(
+							ApiRequest::<ReportCiStepLogsRequest>::builder()
+								.path(ReportCiStepLogsPath {
+									workspace_id: *workspace_id,
+									runner_id: *runner_id,
+									run_id,
+								})
+								.headers(ReportCiStepLogsRequestHeaders {
+									authorization: api_token
+										.clone()
+										.expect("The API token is set when the runner starts"),
+									user_agent: user_agent.clone(),
+								})
+								.query(())
+								.body(ReportCiStepLogsRequest {
+									first_line,
+									logs: logs.clone(),
+								})
+								.build(),
+						)
+						.await
+						.inspect_err(|err| {
+							error!(
+								"Failed to send the logs of CI pipeline run `{}`: {:?}",
+								run_id, err.body.error
+							);
+						});
+					}
+
+					first_line += logs.len() as u64;
+					logs.clear();
+				}
+			});
+
+			let report = executor.run_ci_pipeline(run, logs_sender).await;
+			// The sender is dropped with the run, so this finishes once every
+			// log line has been written
+			_ = logs_task.await;
+
+			for step in &report.steps {
+				info!(
+					"CI pipeline run `{}`: step `{}` {:?}{}",
+					run_id,
+					step.name,
+					step.status,
+					step.duration
+						.map(|duration| format!(" in {}", duration))
+						.unwrap_or_default()
+				);
+			}
+			match (report.status, &report.error) {
+				(CiRunStatus::Succeeded, _) => {
+					info!("CI pipeline run `{}` succeeded", run_id);
+				}
+				(CiRunStatus::Failed, Some(error)) => {
+					warn!("CI pipeline run `{}` failed: {}", run_id, error);
+				}
+				(CiRunStatus::Failed, None) => {
+					warn!("CI pipeline run `{}` failed", run_id);
+				}
+			}
+
+			let RunnerMode::Managed {
+				workspace_id,
+				runner_id,
+				api_token,
+				join_token: _,
+				user_agent,
+			} = mode
+			else {
+				return;
+			};
+
+			_ = client::make_request(
+				ApiRequest::<ReportCiPipelineRunRequest>::builder()
+					.path(ReportCiPipelineRunPath {
+						workspace_id,
+						runner_id,
+						run_id,
+					})
+					.headers(ReportCiPipelineRunRequestHeaders {
+						authorization: api_token
+							.expect("The API token is set when the runner starts"),
+						user_agent,
+					})
+					.query(())
+					.body(ReportCiPipelineRunRequest { report })
+					.build(),
+			)
+			.await
+			.inspect_err(|err| {
+				error!(
+					"Failed to report CI pipeline run `{}`: {:?}",
+					run_id, err.body.error
+				);
+			});
+		});
+	}
+}
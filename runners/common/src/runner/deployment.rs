@@ -1,7 +1,7 @@
 use std::{collections::BTreeMap, pin::pin};
 
 use futures::StreamExt;
-use models::api::workspace::deployment::*;
+use models::api::workspace::{deployment::*, runner::*};
 use tokio::time::{Duration, Instant};
 
 use crate::{prelude::*, utils::delayed_future::DelayedFuture};
@@ -60,6 +60,60 @@ where
 		}
 	}
 
+	/// Report the number of running replicas of every deployment that is
+	/// running on the runner to the Patr API, which bills the deployments for
+	/// them. This is only done in managed mode, after every full
+	/// reconciliation, so the [`RunnerExecutor::FULL_RECONCILIATION_INTERVAL`]
+	/// needs to be less than an hour for the deployments to be billed for
+	/// every hour that they run.
+	pub(super) async fn report_running_replicas(&self) {
+		let RunnerMode::Managed {
+			workspace_id,
+			runner_id,
+			api_token,
+			join_token: _,
+			user_agent,
+		} = &self.state.config.mode
+		else {
+			return;
+		};
+
+		let mut running_deployments = pin!(self.executor.list_running_deployments().await);
+
+		let mut replicas = BTreeMap::new();
+		while let Some(deployment_id) = running_deployments.next().await {
+			if replicas.contains_key(&deployment_id) {
+				continue;
+			}
+			if let Some(count) = self.executor.count_running_replicas(deployment_id).await {
+				replicas.insert(deployment_id, count);
+			}
+		}
+
+		trace!("Reporting running replicas of {} deployments", replicas.len());
+
+		_ = client::make_request(
+			ApiRequest::<ReportRunningReplicasRequest>::builder()
+				.path(ReportRunningReplicasPath {
+					workspace_id: *workspace_id,
+					runner_id: *runner_id,
+				})
+				.headers(ReportRunningReplicasRequestHeaders {
+					authorization: api_token
+						.clone()
+						.expect("The API token is set when the runner starts"),
+					user_agent: user_agent.clone(),
+				})
+				.query(())
+				.body(ReportRunningReplicasRequest { replicas })
+				.build(),
+		)
+		.await
+		.inspect_err(|err| {
+			error!("Failed to report running replicas: {:?}", err.body.error);
+		});
+	}
+
 	/// Reconcile a specific deployment. This function will run the
 	/// reconciliation for a specific deployment (based on the ID)
 	pub(super) async fn reconcile_deployment(&mut self, deployment_id: Uuid) {
@@ -337,6 +391,7 @@ where
 				workspace_id,
 				runner_id: _,
 				api_token,
+				join_token: _,
 				user_agent,
 			} => client::make_request(
 				ApiRequest::<GetDeploymentInfoRequest>::builder()
@@ -345,7 +400,9 @@ where
 						deployment_id,
 					})
 					.headers(GetDeploymentInfoRequestHeaders {
-						authorization: api_token.clone(),
+						authorization: api_token
+							.clone()
+							.expect("The API token is set when the runner starts"),
 						user_agent: user_agent.clone(),
 					})
 					.query(())
@@ -23,6 +23,8 @@ use tracing_subscriber::{
 
 use crate::{db, prelude::*, utils::delayed_future::DelayedFuture};
 
+/// All CI pipeline related functions for the runner
+mod ci;
 /// All deployment related functions for the runner
 mod deployment;
 
@@ -215,11 +217,9 @@ where
 		Self,
 		UnboundedReceiverStream<StreamRunnerDataForWorkspaceServerMsg>,
 	) {
-		let config = RunnerSettings::<E::Settings>::parse(E::RUNNER_INTERNAL_NAME)
+		let mut config = RunnerSettings::<E::Settings>::parse(E::RUNNER_INTERNAL_NAME)
 			.expect("Failed to parse settings");
 
-		let executor = E::create(&config).await;
-
 		tracing::dispatcher::set_global_default(Dispatch::new(
 			tracing_subscriber::registry().with(
 				FmtLayer::new()
@@ -248,6 +248,31 @@ where
 		))
 		.expect("Failed to set global default subscriber");
 
+		if let RunnerMode::Managed {
+			workspace_id,
+			runner_id,
+			api_token: api_token @ None,
+			join_token,
+			user_agent,
+		} = &mut config.mode
+		{
+			let join_token = join_token
+				.take()
+				.expect("Either an API token or a join token must be provided");
+			*api_token = Some(
+				join_workspace(
+					E::RUNNER_INTERNAL_NAME,
+					workspace_id,
+					runner_id,
+					join_token,
+					user_agent,
+				)
+				.await,
+			);
+		}
+
+		let executor = E::create(&config).await;
+
 		let reconciliation_list = Vec::new();
 		let next_reconcile_future = future::pending().boxed();
 
@@ -283,6 +308,7 @@ where
 	async fn reconcile_all(&mut self) {
 		// Reconcile all resources
 		self.reconcile_all_deployments().await;
+		self.report_running_replicas().await;
 	}
 
 	/// Handle a message from the server. This function will handle the message
@@ -290,11 +316,21 @@ where
 	/// message is for.
 	async fn handle_server_message(&mut self, msg: StreamRunnerDataForWorkspaceServerMsg) {
 		info!("Handling server message: {:?}", msg);
+
+		// CI pipeline runs are not resources that need to be reconciled
+		let msg = match msg {
+			StreamRunnerDataForWorkspaceServerMsg::CiPipelineRunRequested { run } => {
+				self.start_ci_pipeline_run(run);
+				return;
+			}
+			msg => msg,
+		};
+
 		// if this resource is already queued for reconciliation, remove that
 		let resource_id = get_resource_id_from_message(&msg);
 
 		match msg.resource_type() {
-			ResourceType::Deployment => {
+			Some(ResourceType::Deployment) => {
 				self.reconcile_deployment(resource_id).await;
 			}
 			_ => {
@@ -329,6 +365,7 @@ where
 				workspace_id,
 				runner_id,
 				api_token,
+				join_token: _,
 				user_agent,
 			} => client::stream_request(
 				ApiRequest::<StreamRunnerDataForWorkspaceRequest>::builder()
@@ -337,7 +374,9 @@ where
 						runner_id: *runner_id,
 					})
 					.headers(StreamRunnerDataForWorkspaceRequestHeaders {
-						authorization: api_token.clone(),
+						authorization: api_token
+							.clone()
+							.expect("The API token is set when the runner starts"),
 						user_agent: user_agent.clone(),
 					})
 					.query(())
@@ -402,5 +441,53 @@ fn get_resource_id_from_message(message: &StreamRunnerDataForWorkspaceServerMsg)
 		DeploymentCreated { deployment, .. } => deployment.id,
 		DeploymentUpdated { deployment, .. } => deployment.id,
 		DeploymentDeleted { id } => *id,
+		CiPipelineRunRequested { run } => run.id,
+	}
+}
+
+/// Exchange the join token of a runner for an API token, and store the API
+/// token in the credentials file of the runner, so that the runner does not
+/// need to join again the next time it starts.
+async fn join_workspace(
+	name: &str,
+	workspace_id: &Uuid,
+	runner_id: &Uuid,
+	join_token: String,
+	user_agent: &UserAgent,
+) -> BearerToken {
+	info!("No API token found. Joining the workspace using the join token");
+
+	let response = client::make_request(
+		ApiRequest::<JoinRunnerRequest>::builder()
+			.path(JoinRunnerPath)
+			.query(())
+			.headers(JoinRunnerRequestHeaders {
+				user_agent: user_agent.clone(),
+			})
+			.body(JoinRunnerRequest { join_token })
+			.build(),
+	)
+	.await
+	.expect("Failed to join the workspace using the join token")
+	.body;
+
+	if response.workspace_id != *workspace_id || response.runner_id != *runner_id {
+		panic!("The join token was not created for this runner");
 	}
+
+	std::fs::write(
+		format!("{}.json", credentials_file_name(name)),
+		serde_json::to_vec_pretty(&serde_json::json!({
+			"apiToken": response.api_token,
+		}))
+		.expect("Failed to serialize the credentials of the runner"),
+	)
+	.expect("Failed to store the credentials of the runner");
+
+	info!("Joined the workspace as runner `{runner_id}`");
+
+	response
+		.api_token
+		.parse()
+		.expect("Failed to parse the API token")
 }
@@ -63,6 +63,7 @@ where
 				panic!("Unknown running environment found!");
 			}
 		}
+		.add_source(File::with_name(&credentials_file_name(name)).required(false))
 		.add_source(Environment::with_prefix("PATR").separator("_"))
 		.build()?
 		.try_deserialize()
@@ -118,9 +119,24 @@ pub enum RunnerMode {
 		/// The runner ID to connect the runner for.
 		#[serde(alias = "runnerid")]
 		runner_id: Uuid,
-		/// The bearer token for the runner to access the API
-		#[serde(alias = "apitoken")]
-		api_token: BearerToken,
+		/// The bearer token for the runner to access the API. If this is not
+		/// given, the runner joins the workspace using the join token, and the
+		/// API token it receives is stored in the credentials file of the
+		/// runner, so that it is used the next time the runner starts.
+		#[serde(
+			default,
+			alias = "apitoken",
+			skip_serializing_if = "Option::is_none"
+		)]
+		api_token: Option<BearerToken>,
+		/// The one-time join token that the runner exchanges for an API token
+		/// if it does not have one yet
+		#[serde(
+			default,
+			alias = "jointoken",
+			skip_serializing_if = "Option::is_none"
+		)]
+		join_token: Option<String>,
 		/// The user agent that the runner uses to access the API
 		#[serde(skip, default = "get_user_agent")]
 		user_agent: UserAgent,
@@ -139,6 +155,13 @@ impl RunnerMode {
 	}
 }
 
+/// Get the name of the file that the credentials of the runner are stored in,
+/// without the extension. This file holds the API token that the runner
+/// receives when it joins a workspace using a join token.
+pub fn credentials_file_name(name: &str) -> String {
+	format!("{}-credentials", name)
+}
+
 /// Get the user agent for the runner
 fn get_user_agent() -> UserAgent {
 	UserAgent::from_str(concat!(
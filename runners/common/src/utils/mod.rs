@@ -1,8 +1,6 @@
 /// The data that is stored inside the access token, which will be encoded as a
 /// JWT.
 pub mod access_token_data;
-/// The client for the Patr API to get runner data for a given workspace.
-pub mod client;
 /// The configuration for the runner.
 pub mod config;
 /// A utility that returns a value after a delay.
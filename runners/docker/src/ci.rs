@@ -0,0 +1,563 @@
+use std::{
+	collections::{BTreeMap, HashMap, HashSet},
+	pin::pin,
+	time::Duration,
+};
+
+use bollard::{
+	container::{
+		Config,
+		CreateContainerOptions,
+		KillContainerOptions,
+		ListContainersOptions,
+		LogOutput,
+		LogsOptions,
+		NetworkingConfig,
+		RemoveContainerOptions,
+		WaitContainerOptions,
+	},
+	errors::Error as DockerError,
+	image::CreateImageOptions,
+	network::CreateNetworkOptions,
+	secret::{EndpointSettings, HostConfig},
+	volume::CreateVolumeOptions,
+};
+use common::prelude::*;
+use futures::StreamExt;
+use models::ci::*;
+use time::OffsetDateTime;
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::DockerRunner;
+
+/// The directory that the repository is cloned to in every container of a run
+const WORKSPACE_DIR: &str = "/workspace";
+/// The image used to clone the repository of a run
+const CLONE_IMAGE: &str = "alpine/git:latest";
+/// The name of the step that the logs of cloning the repository are sent as
+const CLONE_STEP: &str = "clone";
+/// The label that every container of a run is marked with
+const RUN_LABEL: &str = "patr.ciRunId";
+/// The time that cloning the repository of a run may take
+const CLONE_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+impl DockerRunner {
+	/// Run a CI pipeline. The repository is cloned to a volume, the services
+	/// are started on a network that is shared with the steps, and the steps
+	/// are run one after another, starting from the first step and following
+	/// the `next`, `then` and `else` of each step. Everything that is created
+	/// for the run is removed once the run is complete.
+	pub(super) async fn execute_ci_pipeline(
+		&self,
+		WithId {
+			id,
+			data: CiPipelineRun {
+				trigger,
+				pipeline,
+				secrets,
+			},
+		}: WithId<CiPipelineRun>,
+		logs: UnboundedSender<CiStepLog>,
+	) -> CiPipelineRunReport {
+		let run = CiRun {
+			id,
+			resources: format!("patr-ci-{}", id),
+			trigger,
+			secrets,
+			logs,
+		};
+		let mut report = CiPipelineRunReport::skipped(&pipeline);
+
+		// Whatever is still running when the pipeline times out is killed when
+		// the run is cleaned up
+		let run_pipeline = async {
+			match self.prepare_ci_run(&run, &pipeline).await {
+				Ok(()) => self.run_ci_steps(&run, &pipeline, &mut report).await,
+				Err(error) => report.fail(error),
+			}
+		};
+		if tokio::time::timeout(pipeline.timeout(), run_pipeline)
+			.await
+			.is_err()
+		{
+			report.fail(format!(
+				"The pipeline timed out after {}",
+				format_minutes(pipeline.timeout())
+			));
+		}
+
+		self.clean_up_ci_run(&run).await;
+		report
+	}
+
+	/// Create the network and the volume of a run, clone the repository to the
+	/// volume, and start the services of the pipeline
+	async fn prepare_ci_run(&self, run: &CiRun, pipeline: &Pipeline) -> Result<(), String> {
+		self.docker
+			.create_network(CreateNetworkOptions {
+				name: run.resources.clone(),
+				labels: HashMap::from([(RUN_LABEL.to_string(), run.id.to_string())]),
+				..Default::default()
+			})
+			.await
+			.map_err(|err| format!("Unable to create the network of the run: {}", err))?;
+		self.docker
+			.create_volume(CreateVolumeOptions {
+				name: run.resources.clone(),
+				labels: HashMap::from([(RUN_LABEL.to_string(), run.id.to_string())]),
+				..Default::default()
+			})
+			.await
+			.map_err(|err| format!("Unable to create the volume of the run: {}", err))?;
+
+		// The clone URL and the commit are passed as environment variables, so
+		// that they are never interpreted by the shell
+		let clone = run.container_config(
+			CLONE_IMAGE,
+			Some(vec![String::from(
+				r#"git clone "$CI_CLONE_URL" . && git checkout -q "$CI_COMMIT_SHA""#,
+			)]),
+			vec![
+				format!("CI_CLONE_URL={}", run.trigger.clone_url),
+				format!("CI_COMMIT_SHA={}", run.trigger.commit_sha),
+			],
+		);
+		self.pull_ci_image(CLONE_IMAGE).await?;
+		let exit_code = self
+			.run_ci_container(run, CLONE_STEP, clone, CLONE_TIMEOUT)
+			.await?;
+		if exit_code != 0 {
+			return Err(format!(
+				"Unable to clone the repository. Git exited with code {}",
+				exit_code
+			));
+		}
+
+		for service in &pipeline.services {
+			let environment = run.environment(&service.name, &service.environment)?;
+			let mut config = run.container_config(
+				&service.image,
+				service.commands.clone().map(Vec::from),
+				environment,
+			);
+			config.exposed_ports = Some(HashMap::from([(
+				format!("{}/tcp", service.port),
+				HashMap::new(),
+			)]));
+			// Other containers of the run reach the service by its name
+			config.networking_config = Some(NetworkingConfig {
+				endpoints_config: HashMap::from([(
+					run.resources.clone(),
+					EndpointSettings {
+						aliases: Some(vec![service.name.to_string()]),
+						..Default::default()
+					},
+				)]),
+			});
+
+			self.pull_ci_image(&service.image).await?;
+			let container = self
+				.docker
+				.create_container(
+					Some(CreateContainerOptions {
+						name: format!("{}-{}", run.resources, service.name),
+						..Default::default()
+					}),
+					config,
+				)
+				.await
+				.map_err(|err| {
+					format!("Unable to create the service `{}`: {}", service.name, err)
+				})?;
+			self.docker
+				.start_container::<String>(&container.id, None)
+				.await
+				.map_err(|err| {
+					format!("Unable to start the service `{}`: {}", service.name, err)
+				})?;
+			info!("Started service `{}` of CI run `{}`", service.name, run.id);
+		}
+
+		Ok(())
+	}
+
+	/// Run the steps of a pipeline, recording the status and duration of each
+	/// step in the report. The run stops at the first step that fails.
+	async fn run_ci_steps(
+		&self,
+		run: &CiRun,
+		pipeline: &Pipeline,
+		report: &mut CiPipelineRunReport,
+	) {
+		let mut visited = HashSet::new();
+		let mut current = pipeline.first_step().map(|step| step.name().to_string());
+
+		while let Some(name) = current.take() {
+			let Some(index) = pipeline.step_index(&name) else {
+				report.fail(format!("The step `{}` does not exist", name));
+				return;
+			};
+			if !visited.insert(index) {
+				report.fail(format!(
+					"The step `{}` is run more than once, which would never finish",
+					name
+				));
+				return;
+			}
+
+			let step = &pipeline.steps[index];
+			let started_at = OffsetDateTime::now_utc();
+			// A step that is interrupted by the timeout of the pipeline never
+			// finishes, so it is recorded as failed until it does
+			report.steps[index] = CiStepReport {
+				name: name.clone(),
+				status: CiStepStatus::Failed,
+				started_at: Some(started_at),
+				duration: None,
+			};
+			let succeeded = match step {
+				Step::Decision(_) => true,
+				Step::Work(work) => match self.run_ci_work(run, work).await {
+					Ok(0) => true,
+					Ok(exit_code) => {
+						run.log(&work.name, format!("Exited with code {}", exit_code));
+						false
+					}
+					Err(error) => {
+						run.log(&work.name, error);
+						false
+					}
+				},
+			};
+
+			report.steps[index] = CiStepReport {
+				name: name.clone(),
+				status: if succeeded {
+					CiStepStatus::Succeeded
+				} else {
+					CiStepStatus::Failed
+				},
+				started_at: Some(started_at),
+				duration: Some(OffsetDateTime::now_utc() - started_at),
+			};
+			if !succeeded {
+				report.status = CiRunStatus::Failed;
+				return;
+			}
+
+			current = step.next(&run.trigger).map(str::to_string);
+		}
+	}
+
+	/// Run the commands of a unit of work in its image, from the directory
+	/// that the repository is cloned to. Returns the exit code of the commands.
+	async fn run_ci_work(&self, run: &CiRun, work: &Work) -> Result<i64, String> {
+		let environment = run.environment(&work.name, &work.environment)?;
+		let config = run.container_config(
+			&work.image,
+			Some(Vec::from(work.commands.clone())),
+			environment,
+		);
+
+		self.pull_ci_image(&work.image).await?;
+		self.run_ci_container(run, &work.name, config, work.timeout())
+			.await
+	}
+
+	/// Run a container of a run to completion, sending its logs as the logs of
+	/// the given step, and remove it once it exits. The container is killed if
+	/// it runs for longer than the timeout. Returns the exit code of the
+	/// container.
+	async fn run_ci_container(
+		&self,
+		run: &CiRun,
+		step: &str,
+		config: Config<String>,
+		timeout: Duration,
+	) -> Result<i64, String> {
+		let container = self
+			.docker
+			.create_container::<String, String>(None, config)
+			.await
+			.map_err(|err| format!("Unable to create the container: {}", err))?
+			.id;
+
+		let exit_code = match tokio::time::timeout(
+			timeout,
+			self.follow_ci_container(run, step, &container),
+		)
+		.await
+		{
+			Ok(exit_code) => exit_code,
+			Err(_) => {
+				if let Err(err) = self
+					.docker
+					.kill_container(&container, None::<KillContainerOptions<String>>)
+					.await
+				{
+					warn!("Error killing CI container `{}`: {:?}", container, err);
+				}
+				Err(format!(
+					"The step timed out after {}",
+					format_minutes(timeout)
+				))
+			}
+		};
+
+		if let Err(err) = self
+			.docker
+			.remove_container(
+				&container,
+				Some(RemoveContainerOptions {
+					force: true,
+					v: false,
+					..Default::default()
+				}),
+			)
+			.await
+		{
+			warn!("Error removing CI container `{}`: {:?}", container, err);
+		}
+
+		exit_code
+	}
+
+	/// Start a container, send its logs as they are written, and wait for it
+	/// to exit
+	async fn follow_ci_container(
+		&self,
+		run: &CiRun,
+		step: &str,
+		container: &str,
+	) -> Result<i64, String> {
+		self.docker
+			.start_container::<String>(container, None)
+			.await
+			.map_err(|err| format!("Unable to start the container: {}", err))?;
+
+		let mut output = pin!(self.docker.logs(
+			container,
+			Some(LogsOptions::<String> {
+				follow: true,
+				stdout: true,
+				stderr: true,
+				..Default::default()
+			}),
+		));
+		// Chunks of the logs are not split by lines, so the last line of a
+		// chunk is only sent once the rest of it is written
+		let mut partial_line = String::new();
+		while let Some(chunk) = output.next().await {
+			let chunk = match chunk {
+				Ok(LogOutput::StdOut { message } | LogOutput::StdErr { message }) => message,
+				Ok(_) => continue,
+				Err(err) => {
+					warn!(
+						"Error reading the logs of CI container `{}`: {:?}",
+						container, err
+					);
+					break;
+				}
+			};
+			partial_line.push_str(&String::from_utf8_lossy(&chunk));
+			while let Some((line, rest)) = partial_line.split_once('\n') {
+				run.log(step, line.trim_end_matches('\r').to_string());
+				partial_line = rest.to_string();
+			}
+		}
+		if !partial_line.is_empty() {
+			run.log(step, partial_line);
+		}
+
+		let mut wait = pin!(self
+			.docker
+			.wait_container(container, None::<WaitContainerOptions<String>>));
+		match wait.next().await {
+			Some(Ok(response)) => Ok(response.status_code),
+			// Docker reports a non-zero exit code as an error
+			Some(Err(DockerError::DockerContainerWaitError { code, .. })) => Ok(code),
+			Some(Err(err)) => Err(format!("Unable to wait for the container: {}", err)),
+			None => Err(String::from("The container exited without an exit code")),
+		}
+	}
+
+	/// Pull an image used by a run. Images without a tag are pulled with the
+	/// `latest` tag, since Docker pulls every tag of an image otherwise.
+	async fn pull_ci_image(&self, image: &str) -> Result<(), String> {
+		let name = image.rsplit('/').next().unwrap_or(image);
+		let from_image = if name.contains(':') || name.contains('@') {
+			image.to_string()
+		} else {
+			format!("{}:latest", image)
+		};
+
+		let mut pull_image = self.docker.create_image(
+			Some(CreateImageOptions {
+				from_image,
+				..Default::default()
+			}),
+			None,
+			None,
+		);
+		while let Some(result) = pull_image.next().await {
+			if let Err(err) = result {
+				return Err(format!("Unable to pull the image `{}`: {}", image, err));
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Remove the containers, the network and the volume of a run
+	async fn clean_up_ci_run(&self, run: &CiRun) {
+		let containers = self
+			.docker
+			.list_containers(Some(ListContainersOptions {
+				all: true,
+				filters: HashMap::from([(
+					String::from("label"),
+					vec![format!("{}={}", RUN_LABEL, run.id)],
+				)]),
+				..Default::default()
+			}))
+			.await
+			.inspect_err(|err| error!("Error listing CI containers: {:?}", err))
+			.unwrap_or_default();
+
+		for container in containers {
+			let Some(id) = container.id else {
+				continue;
+			};
+			if let Err(err) = self
+				.docker
+				.remove_container(
+					&id,
+					Some(RemoveContainerOptions {
+						force: true,
+						v: false,
+						..Default::default()
+					}),
+				)
+				.await
+			{
+				warn!("Error removing CI container `{}`: {:?}", id, err);
+			}
+		}
+
+		if let Err(err) = self.docker.remove_network(&run.resources).await {
+			warn!("Error removing CI network `{}`: {:?}", run.resources, err);
+		}
+		if let Err(err) = self.docker.remove_volume(&run.resources, None).await {
+			warn!("Error removing CI volume `{}`: {:?}", run.resources, err);
+		}
+	}
+}
+
+/// Format a timeout, which is always a whole number of minutes
+fn format_minutes(timeout: Duration) -> String {
+	match timeout.as_secs() / 60 {
+		1 => String::from("1 minute"),
+		minutes => format!("{} minutes", minutes),
+	}
+}
+
+/// The details of a run that every container of the run needs
+struct CiRun {
+	/// The ID of the run
+	id: Uuid,
+	/// The name of the network and the volume of the run
+	resources: String,
+	/// The git event that the pipeline is run for
+	trigger: CiTrigger,
+	/// The secrets that the steps and services of the pipeline use
+	secrets: CiSecrets,
+	/// Where the logs of the steps are sent to
+	logs: UnboundedSender<CiStepLog>,
+}
+
+impl CiRun {
+	/// Send a line of the logs of a step
+	fn log(&self, step: &str, line: String) {
+		// The receiver only goes away if the runner is shutting down
+		_ = self.logs.send(CiStepLog {
+			step: step.to_string(),
+			line,
+		});
+	}
+
+	/// The environment variables of a step or a service, along with the ones
+	/// that describe the event that the pipeline is run for
+	fn environment(
+		&self,
+		name: &str,
+		environment: &BTreeMap<String, EnvVarValue>,
+	) -> Result<Vec<String>, String> {
+		let mut variables = vec![
+			String::from("CI=true"),
+			format!("CI_COMMIT_SHA={}", self.trigger.commit_sha),
+			format!(
+				"CI_EVENT={}",
+				match self.trigger.event {
+					Event::Commit => "commit",
+					Event::Tag => "tag",
+					Event::Pull => "pull",
+				}
+			),
+		];
+		if let Some(branch) = &self.trigger.branch {
+			variables.push(format!("CI_BRANCH={}", branch));
+		}
+		if let Some(tag) = &self.trigger.tag {
+			variables.push(format!("CI_TAG={}", tag));
+		}
+
+		for (key, value) in environment {
+			let value = value.resolve(&self.secrets).ok_or_else(|| {
+				format!(
+					"The environment variable `{}` of `{}` uses a secret that is not available",
+					key, name
+				)
+			})?;
+			variables.push(format!("{}={}", key, value));
+		}
+
+		Ok(variables)
+	}
+
+	/// The configuration of a container of the run. The container has the
+	/// repository mounted as its working directory and is on the network of
+	/// the run. The commands, if any, are run by the shell one after another,
+	/// stopping at the first one that fails.
+	fn container_config(
+		&self,
+		image: &str,
+		commands: Option<Vec<String>>,
+		environment: Vec<String>,
+	) -> Config<String> {
+		let (entrypoint, cmd) = match commands {
+			Some(commands) => (
+				Some(vec![String::from("/bin/sh"), String::from("-c")]),
+				Some(vec![format!("set -e\n{}", commands.join("\n"))]),
+			),
+			None => (None, None),
+		};
+
+		Config {
+			image: Some(image.to_string()),
+			entrypoint,
+			cmd,
+			env: Some(environment),
+			working_dir: Some(WORKSPACE_DIR.to_string()),
+			labels: Some(HashMap::from([(
+				RUN_LABEL.to_string(),
+				self.id.to_string(),
+			)])),
+			host_config: Some(HostConfig {
+				binds: Some(vec![format!("{}:{}", self.resources, WORKSPACE_DIR)]),
+				network_mode: Some(self.resources.clone()),
+				..Default::default()
+			}),
+			..Default::default()
+		}
+	}
+}
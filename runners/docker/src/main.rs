@@ -21,8 +21,15 @@ use bollard::{
 };
 use common::prelude::*;
 use futures::{Stream, StreamExt};
-use models::api::workspace::deployment::*;
+use models::{
+	api::workspace::deployment::*,
+	ci::{CiPipelineRun, CiPipelineRunReport, CiStepLog},
+};
 use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Running CI pipelines using Docker
+mod ci;
 
 /// The configuration for the runner.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -273,6 +280,28 @@ impl RunnerExecutor for DockerRunner {
 		.boxed()
 	}
 
+	async fn count_running_replicas(&self, id: Uuid) -> Option<u16> {
+		let containers = self
+			.docker
+			.list_containers(Some(ListContainersOptions {
+				filters: HashMap::from([
+					(
+						String::from("label"),
+						vec![format!("patr.deploymentId={}", id)],
+					),
+					(String::from("status"), vec![String::from("running")]),
+				]),
+				..Default::default()
+			}))
+			.await
+			.inspect_err(|err| {
+				error!("Error listing containers: {:?}", err);
+			})
+			.ok()?;
+
+		Some(containers.len().try_into().unwrap_or(u16::MAX))
+	}
+
 	async fn delete_deployment(&self, id: Uuid) -> Result<(), Duration> {
 		// Check if the container exists, first.
 		let container = self
@@ -310,6 +339,14 @@ impl RunnerExecutor for DockerRunner {
 		}
 		Ok(())
 	}
+
+	async fn run_ci_pipeline(
+		&self,
+		run: WithId<CiPipelineRun>,
+		logs: UnboundedSender<CiStepLog>,
+	) -> CiPipelineRunReport {
+		self.execute_ci_pipeline(run, logs).await
+	}
 }
 
 #[tokio::main]
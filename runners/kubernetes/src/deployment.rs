@@ -1,5 +1,6 @@
 use std::{collections::BTreeMap, str::FromStr, sync::Arc, time::Duration};
 
+use client::make_request;
 use futures::{future, StreamExt};
 use k8s_openapi::{
 	api::{
@@ -50,7 +51,7 @@ use tokio::{
 };
 use tokio_stream::wrappers::{BroadcastStream, UnboundedReceiverStream};
 
-use crate::{client::make_request, constants, prelude::*};
+use crate::{constants, prelude::*};
 
 /// Starts the deployment controller. This function will spawn a new task that
 /// will run the controller. This function will return a sender that can be
@@ -34,8 +34,6 @@ pub mod prelude {
 /// All app state that is shared across the entire application. Used to share
 /// ApiTokens, backend connections, etc.
 mod app;
-/// The client used to communicate with the Patr API.
-mod client;
 /// All the constants used by the controller.
 mod constants;
 /// All functions and business logic to run a database controller and keep it